@@ -0,0 +1,199 @@
+//! Throughput benchmarks for the tick-loop hot paths.
+//!
+//! Run with `cargo bench`; compare against a saved run with
+//! `cargo bench -- --save-baseline main` then `--baseline main`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use sattebaaz::config::StrategyConfig;
+use sattebaaz::execution::order_builder::OrderBuilder;
+use sattebaaz::feeds::binance::LatestPrices;
+use sattebaaz::models::market::{Asset, Duration, Market, OrderBook, Side};
+use sattebaaz::models::order::{OrderIntent, OrderSide, OrderType};
+use sattebaaz::models::signal::VolRegime;
+use sattebaaz::signals::probability::ProbabilityModel;
+use sattebaaz::strategies::orchestrator::StrategyOrchestrator;
+
+/// Anvil's first dev key — deterministic, never funded on Polygon.
+const BENCH_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn dec(v: f64) -> Decimal {
+    Decimal::from_str(&format!("{v:.2}")).unwrap()
+}
+
+/// A 20-level book around `mid`, one cent per level, like a live 5m market.
+fn book(token_id: &str, mid: f64) -> OrderBook {
+    let mut book = OrderBook::new(token_id.to_string());
+    for i in 0..20 {
+        let size = dec(25.0 + 15.0 * i as f64);
+        let bid = mid - 0.01 * (i + 1) as f64;
+        let ask = mid + 0.01 * (i + 1) as f64;
+        if bid > 0.0 {
+            book.bids.insert(dec(bid), size);
+        }
+        if ask < 1.0 {
+            book.asks.insert(dec(ask), size);
+        }
+    }
+    book
+}
+
+/// Market in its prime zone, so every strategy gets to evaluate.
+fn market() -> Market {
+    let mut m = Market::new(
+        "btc-updown-5m-bench".to_string(),
+        Asset::BTC,
+        Duration::FiveMin,
+        "yes_token".to_string(),
+        "no_token".to_string(),
+    );
+    let now = chrono::Utc::now();
+    m.open_time = now - chrono::Duration::seconds(60);
+    m.close_time = now + chrono::Duration::seconds(240);
+    m.reference_price = 100_000.0;
+    m
+}
+
+fn bench_book(c: &mut Criterion) {
+    let mut group = c.benchmark_group("order_book");
+    // A typical price_change message: a few levels move, one empties
+    let bids = [(dec(0.49), dec(120.0)), (dec(0.47), dec(0.0)), (dec(0.45), dec(310.0))];
+    let asks = [(dec(0.51), dec(80.0)), (dec(0.53), dec(0.0)), (dec(0.56), dec(40.0))];
+    group.bench_function("apply_delta", |b| {
+        b.iter_batched_ref(
+            || book("yes", 0.50),
+            |book| book.apply_delta(black_box(bids), black_box(asks)),
+            BatchSize::SmallInput,
+        )
+    });
+    let full = book("yes", 0.50);
+    group.bench_function("clone", |b| b.iter(|| black_box(&full).clone()));
+    group.bench_function("best_bid_ask", |b| {
+        b.iter(|| (black_box(&full).best_bid(), black_box(&full).best_ask()))
+    });
+    group.bench_function("buy_market_price", |b| {
+        b.iter(|| black_box(&full).calculate_buy_market_price(black_box(250.0)))
+    });
+    group.finish();
+}
+
+fn bench_signing(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let builder = OrderBuilder::new(137, BENCH_PRIVATE_KEY.to_string(), None, 0);
+    let intent = OrderIntent {
+        token_id: "71321045679252212594626385532706912750332728571942532289631379312455583992563"
+            .to_string(),
+        market_side: Side::Yes,
+        order_side: OrderSide::Buy,
+        price: dec(0.52),
+        size: dec(10.0),
+        order_type: OrderType::GTC,
+        post_only: false,
+        expiration: None,
+        strategy_tag: "bench".to_string(),
+        group_id: None,
+    };
+    let batch = vec![intent.clone(); 4];
+
+    let mut group = c.benchmark_group("order_builder");
+    group.bench_function("build_signed", |b| {
+        b.iter(|| runtime.block_on(builder.build(black_box(&intent))).unwrap())
+    });
+    group.bench_function("build_batch_4", |b| {
+        b.iter(|| runtime.block_on(builder.build_batch(black_box(&batch))).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_orchestrator(c: &mut Criterion) {
+    let orch = StrategyOrchestrator::new(StrategyConfig::default());
+    let market = market();
+    let yes_book = book("yes_token", 0.52);
+    let no_book = book("no_token", 0.47);
+
+    c.bench_function("orchestrator/evaluate", |b| {
+        b.iter(|| {
+            orch.evaluate(
+                black_box(&market), black_box(&yes_book), black_box(&no_book),
+                VolRegime::Medium, 100.0, black_box(100_050.0),
+                None, None, None,
+                0.0, 0.001, 0.1, false,
+            )
+        })
+    });
+}
+
+fn bench_probability(c: &mut Criterion) {
+    let model = ProbabilityModel::new();
+    let mut group = c.benchmark_group("probability");
+    group.bench_function("fair_prob_up", |b| {
+        b.iter(|| model.fair_prob_up(black_box(100_050.0), 100_000.0, black_box(3.2), 0.0008, 0.0))
+    });
+    group.bench_function("mispricing", |b| {
+        b.iter(|| {
+            model.mispricing(black_box(100_050.0), 100_000.0, black_box(3.2), 0.0008, 0.0, 0.52, 0.47)
+        })
+    });
+    group.bench_function("kelly_size", |b| {
+        b.iter(|| model.kelly_size(black_box(0.04), black_box(0.52), 0.5, 0.25))
+    });
+    group.finish();
+}
+
+/// Price reads while a writer thread records prints as fast as it can — the
+/// feed's aggTrade stream at its worst. The RwLock map is what `BinanceFeed`
+/// used before; its reads queue behind every write.
+fn bench_price_reads(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut group = c.benchmark_group("prices_under_writes");
+
+    let locked = Arc::new(tokio::sync::RwLock::new(HashMap::from([(Asset::BTC, 100_000.0)])));
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let (locked, stop) = (locked.clone(), stop.clone());
+        std::thread::spawn(move || {
+            let mut price = 100_000.0;
+            while !stop.load(Ordering::Relaxed) {
+                price += 0.5;
+                locked.blocking_write().insert(Asset::BTC, price);
+            }
+        })
+    };
+    group.bench_function("rwlock_map", |b| {
+        b.iter(|| runtime.block_on(async { locked.read().await.get(&Asset::BTC).copied() }))
+    });
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+
+    let latest = Arc::new(LatestPrices::default());
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let (latest, stop) = (latest.clone(), stop.clone());
+        std::thread::spawn(move || {
+            let mut price = 100_000.0;
+            while !stop.load(Ordering::Relaxed) {
+                price += 0.5;
+                latest.record(Asset::BTC, price, chrono::Utc::now());
+            }
+        })
+    };
+    group.bench_function("latest_prices", |b| b.iter(|| latest.get(black_box(Asset::BTC))));
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_book,
+    bench_signing,
+    bench_orchestrator,
+    bench_probability,
+    bench_price_reads
+);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+//! Trade-history backfill
+//!
+//! Imports trades and on-chain activity (redemptions, merges, ...) from
+//! Polymarket's data API into the trade journal, so analysis also covers
+//! activity from before journaling existed or from outages.
+//!
+//! Usage:  cargo run --bin backfill -- [--from YYYY-MM-DD] [--to YYYY-MM-DD] [--user 0x...]
+//!
+//! Defaults: everything up to the first live journal entry. The account is
+//! POLYMARKET_FUNDER_ADDRESS, else the address of POLYMARKET_PRIVATE_KEY.
+//! Entries already in the journal are skipped, so re-running is safe.
+
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use sattebaaz::cli::arg;
+use sattebaaz::config::Config;
+use sattebaaz::execution::order_builder::OrderBuilder;
+use sattebaaz::telemetry::journal::{Backfill, TradeJournal};
+
+fn date_arg(name: &str) -> anyhow::Result<Option<i64>> {
+    let Some(v) = arg(name) else { return Ok(None) };
+    let date = NaiveDate::parse_from_str(&v, "%Y-%m-%d").with_context(|| format!("{name} {v}: expected YYYY-MM-DD"))?;
+    Ok(Some(date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp()))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt().with_env_filter("info").with_target(false).init();
+    let config = Config::load_or_default();
+
+    let user = match arg("--user").or_else(|| config.polymarket.funder_address.clone()) {
+        Some(user) => user,
+        None if !config.polymarket.private_key.is_empty() => {
+            let builder = OrderBuilder::new(
+                config.polymarket.chain_id,
+                config.polymarket.private_key.clone(),
+                None,
+                config.polymarket.signature_type,
+            );
+            format!("{:?}", builder.address())
+        }
+        None => anyhow::bail!("No account: pass --user or set POLYMARKET_FUNDER_ADDRESS / POLYMARKET_PRIVATE_KEY"),
+    };
+    let path = config
+        .telemetry
+        .journal_path
+        .clone()
+        .context("Trade journal is disabled (TRADE_JOURNAL=off)")?;
+    let journal = TradeJournal::open(&path)?;
+
+    let start = date_arg("--from")?.unwrap_or(0);
+    let end = match date_arg("--to")? {
+        Some(end) => end,
+        None => journal.first_live()?.unwrap_or_else(Utc::now).timestamp(),
+    };
+    println!("  Backfilling {user} into {path} ({} → {})",
+        chrono::DateTime::from_timestamp(start, 0).unwrap_or_default().format("%Y-%m-%d %H:%M"),
+        chrono::DateTime::from_timestamp(end, 0).unwrap_or_default().format("%Y-%m-%d %H:%M"));
+
+    let report = Backfill::new(&config.polymarket.data_api_host, &user)
+        .run(&journal, start, end)
+        .await?;
+    println!("  Fetched {} | imported {} | already journaled {}",
+        report.fetched, report.imported, report.duplicates);
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Intraday vol calibration
+//!
+//! Estimates per-asset 1-minute vol by UTC hour of day from Binance futures
+//! klines and writes the curves to the vol calibration file, then prints how
+//! they compare with the hardcoded `Asset::annual_volatility` constants.
+//!
+//! Usage:  cargo run --bin calibrate_vol -- [--days 14] [--csv-dir DIR] [--out FILE]
+//!
+//! By default the last `--days` of 1m klines are fetched from BINANCE_REST_URL.
+//! With `--csv-dir`, klines are read from data.binance.vision CSV dumps instead
+//! (files named like BTCUSDT-1m-2026-10-01.csv). The output path defaults to
+//! VOL_CALIBRATION (vol_calibration.json).
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sattebaaz::cli::arg;
+use sattebaaz::config::Config;
+use sattebaaz::models::market::Asset;
+use sattebaaz::signals::seasonality::{parse_kline_csv, VolCalibration, VolCurve};
+use std::path::Path;
+
+/// Binance futures klines page limit
+const KLINE_PAGE: usize = 1500;
+
+fn symbol(asset: Asset) -> String {
+    format!("{}USDT", asset.slug_prefix().to_uppercase())
+}
+
+async fn fetch_klines(
+    http: &reqwest::Client,
+    rest_url: &str,
+    asset: Asset,
+    start: DateTime<Utc>,
+) -> anyhow::Result<Vec<(DateTime<Utc>, f64)>> {
+    let mut closes = Vec::new();
+    let mut from = start.timestamp_millis();
+    loop {
+        let rows: Vec<Vec<serde_json::Value>> = http
+            .get(format!("{}/fapi/v1/klines", rest_url.trim_end_matches('/')))
+            .query(&[
+                ("symbol", symbol(asset)),
+                ("interval", "1m".to_string()),
+                ("startTime", from.to_string()),
+                ("limit", KLINE_PAGE.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        for row in &rows {
+            let open_ms = row.first().and_then(|v| v.as_i64());
+            let close = row.get(4).and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok());
+            if let (Some(t), Some(c)) = (open_ms.and_then(DateTime::from_timestamp_millis), close) {
+                closes.push((t, c));
+            }
+        }
+        match closes.last() {
+            Some((last, _)) if rows.len() == KLINE_PAGE => from = last.timestamp_millis() + 60_000,
+            _ => return Ok(closes),
+        }
+    }
+}
+
+fn read_csv_dir(dir: &Path, asset: Asset) -> anyhow::Result<Vec<(DateTime<Utc>, f64)>> {
+    let prefix = format!("{}-", symbol(asset));
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == "csv")
+                && p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix))
+        })
+        .collect();
+    files.sort();
+    let mut closes = Vec::new();
+    for file in files {
+        let text = std::fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+        closes.extend(parse_kline_csv(&text));
+    }
+    closes.sort_by_key(|(t, _)| *t);
+    Ok(closes)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt().with_env_filter("info").with_target(false).init();
+    let config = Config::load_or_default();
+
+    let days: i64 = arg("--days").map(|d| d.parse()).transpose().context("--days")?.unwrap_or(14);
+    let csv_dir = arg("--csv-dir");
+    let out = arg("--out")
+        .or_else(|| config.strategy.vol_calibration_path.clone())
+        .unwrap_or_else(|| "vol_calibration.json".to_string());
+    let http = reqwest::Client::new();
+
+    let mut curves = Vec::new();
+    for asset in [Asset::BTC, Asset::ETH, Asset::SOL, Asset::XRP] {
+        let closes = match &csv_dir {
+            Some(dir) => read_csv_dir(Path::new(dir), asset)?,
+            None => {
+                let start = Utc::now() - chrono::Duration::days(days);
+                fetch_klines(&http, &config.binance.rest_url, asset, start).await?
+            }
+        };
+        match VolCurve::from_closes(asset, &closes) {
+            Some(curve) => curves.push(curve),
+            None => println!("  {asset:?}: no data, keeping the hardcoded constant"),
+        }
+    }
+
+    println!("  asset  constant  calibrated  quietest hour    busiest hour");
+    for c in &curves {
+        let factor = |h: usize| c.hourly_per_minute[h] / c.overall_per_minute;
+        let quiet = (0..24).min_by(|&a, &b| factor(a).total_cmp(&factor(b))).unwrap_or(0);
+        let busy = (0..24).max_by(|&a, &b| factor(a).total_cmp(&factor(b))).unwrap_or(0);
+        println!(
+            "  {:<5}  {:>7.0}%  {:>9.0}%  {quiet:02}:00 x{:.2}     {busy:02}:00 x{:.2}",
+            format!("{:?}", c.asset),
+            c.asset.annual_volatility() * 100.0,
+            c.annualized() * 100.0,
+            factor(quiet),
+            factor(busy),
+        );
+    }
+
+    let source = match &csv_dir {
+        Some(dir) => format!("binance kline csv {dir}"),
+        None => format!("binance futures klines {days}d"),
+    };
+    VolCalibration { generated_at: Utc::now(), source, curves }.save(&out)?;
+    println!("  Wrote {out}");
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Feature/label export for offline research
+//!
+//! Joins a market recording (MARKET_RECORDING: Binance ticks, book states at
+//! evaluation time, strategy signals, window outcomes) into one row per book
+//! state of every resolved market, and writes it as Parquet keyed by
+//! `market` and `ts_ms`. Alongside it, one row per strategy signal with the
+//! market's state at that moment and the candidate order — the ML entry
+//! filter's inputs (`signals::ml_filter::INPUTS`) — labelled `won`.
+//!
+//! Usage:  cargo run --bin export_features -- [--recording FILE] [--out FILE] [--intents FILE]
+//!
+//! Features use only data at or before the row's timestamp; labels
+//! (`yes_won`, `final_price`, `yes_mid_fwd_*`, `won`) come from after it.
+
+use anyhow::Context;
+use sattebaaz::cli::arg;
+use sattebaaz::config::Config;
+use sattebaaz::signals::seasonality::Seasonality;
+use sattebaaz::sim::features;
+use sattebaaz::telemetry::recorder::{MarketRecorder, Recorded};
+
+fn main() -> anyhow::Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt().with_env_filter("info").with_target(false).init();
+    let config = Config::load_or_default();
+
+    let recording = arg("--recording")
+        .or(config.telemetry.recording_path.clone())
+        .context("No recording: pass --recording or set MARKET_RECORDING")?;
+    let out = arg("--out").unwrap_or_else(|| "features.parquet".into());
+    let intents_out = arg("--intents").unwrap_or_else(|| "intents.parquet".into());
+    let records = MarketRecorder::read(&recording)?;
+    let count = |f: fn(&Recorded) -> bool| records.iter().filter(|r| f(r)).count();
+    println!(
+        "  {recording}: {} ticks | {} book states | {} signals | {} outcomes",
+        count(|r| matches!(r, Recorded::Tick { .. })),
+        count(|r| matches!(r, Recorded::Book { .. })),
+        count(|r| matches!(r, Recorded::Signal { .. })),
+        count(|r| matches!(r, Recorded::Outcome { .. })),
+    );
+
+    let seasonality = Seasonality::load(config.strategy.vol_calibration_path.as_deref());
+    let rows = features::align(&records, &seasonality);
+    let markets: std::collections::HashSet<&str> = rows.iter().map(|r| r.market.as_str()).collect();
+    features::write_parquet(&rows, &out)?;
+    println!("  Wrote {} rows over {} resolved markets to {out}", rows.len(), markets.len());
+
+    let intents = features::align_intents(&records, &seasonality);
+    features::write_intents_parquet(&intents, &intents_out)?;
+    println!("  Wrote {} labelled signals to {intents_out}", intents.len());
+    Ok(())
+}
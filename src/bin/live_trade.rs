@@ -6,19 +6,43 @@
 //! Requires: POLYMARKET_PRIVATE_KEY in .env
 //!
 //! Usage:  cargo run --bin live_trade
+//!
+//! Warm restart: `kill -HUP <pid>` saves positions, exit order IDs, reference
+//! prices and session P&L to SESSION_FILE (default live_session.json) and exits
+//! WITHOUT cancelling the resting GTC exits. The next start resumes them.
 
-use sattebaaz::config::Config;
+use sattebaaz::config::{Config, ExitStyle, JoinKind};
 use sattebaaz::execution::clob_client::ClobClient;
 use sattebaaz::execution::order_builder::OrderBuilder;
-use sattebaaz::execution::polygon_merger::PolygonMerger;
+use sattebaaz::execution::polygon_merger::{reverted_gas, GasSpend, PolygonMerger};
+use sattebaaz::execution::rejection::RejectReason;
+use sattebaaz::execution::session::{self, RestingStatus};
+use sattebaaz::execution::settlement::SettlementTracker;
 use sattebaaz::feeds::binance::BinanceFeed;
+use sattebaaz::feeds::clock;
+use sattebaaz::feeds::matic_price::MaticPrice;
 use sattebaaz::feeds::market_discovery::MarketDiscovery;
 use sattebaaz::feeds::polymarket::PolymarketFeed;
+use sattebaaz::feeds::readiness::ReadinessBarrier;
+use sattebaaz::feeds::token_balances::TokenBalances;
+use sattebaaz::feeds::user_ws::UserWsFeed;
 use sattebaaz::models::market::{Asset, Duration, Side};
-use sattebaaz::models::order::{OrderSide, OrderType};
+use sattebaaz::models::order::{taker_fee, OrderSide, OrderStatus, OrderType};
+use sattebaaz::models::position::{is_mid_cycle, MID_CYCLE_TAG};
+use sattebaaz::models::signal::VolRegime;
+use sattebaaz::risk::carry::{CarryBook, RedeemResult};
+use sattebaaz::risk::exit_manager::{ExitInputs, ExitManager};
+use sattebaaz::signals::half_life::{HoldLimits, OpportunityHalfLife};
 use sattebaaz::signals::probability::ProbabilityModel;
+use sattebaaz::telemetry::hold_time::{ExitReason, HoldTimeReport};
+use sattebaaz::telemetry::journal::{JournalEntry, TradeJournal};
+use sattebaaz::telemetry::pnl::{CohortPnl, ExitPnlBreakdown};
+use sattebaaz::tui::dashboard::{BookTop, Dashboard, MarketRow, PositionRow, TradingState};
 
 use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Write;
@@ -30,7 +54,7 @@ use tracing::debug;
 // CONFIGURATION
 // ═══════════════════════════════════════════════════════════════════════════
 const TICK_MS: u64 = 2000;             // Poll every 2s
-const FEED_INIT_SECS: u64 = 12;
+const FEED_READY_TIMEOUT_SECS: u64 = 30; // Max wait for markets/books/prices at startup
 const DASHBOARD_SECS: u64 = 10;
 
 // Entry signals
@@ -39,14 +63,11 @@ const ARB_THRESHOLD: f64 = 0.97;       // Buy both when YES_ask + NO_ask < this
 const PRICE_FLOOR: f64 = 0.20;         // Don't buy below 20¢
 const PRICE_CEILING: f64 = 0.80;       // Don't buy above 80¢
 const MAX_SPREAD_PCT: f64 = 0.10;      // Don't enter if spread > 10% of ask
-const MIN_REMAINING_SECS: f64 = 120.0; // Need at least 2 min for lag to correct
+const MIN_REMAINING_SECS: f64 = 120.0; // Need at least 2 min for lag to correct (until the half-life is known)
 const MIN_BTC_MOVE_PCT: f64 = 0.005;   // Require ≥0.005% BTC move since last tick
 
 // Exit signals
-const TAKE_PROFIT_PCT: f64 = 0.10;     // Exit when bid ≥ entry × (1 + 10%)
-const STOP_LOSS_PCT: f64 = 0.20;       // Cut loss when bid ≤ entry × (1 - 20%) — wide for thin books
-const MAX_HOLD_SECS: f64 = 120.0;      // Force exit after 2 minutes
-const PRE_RESOLVE_EXIT_SECS: f64 = 90.0; // Start closing in last 90s if profitable
+const MAX_HOLD_SECS: f64 = 120.0;      // Time exit after 2 minutes (until the half-life is known)
 
 // Position sizing
 const MAX_POSITIONS: usize = 2;
@@ -58,6 +79,9 @@ const ENTRY_COOLDOWN_SECS: u64 = 10;
 // Safety
 const MAX_SESSION_LOSS_PCT: f64 = 0.30; // Kill switch: stop if down 30% from start
 const BALANCE_SYNC_CYCLES: u32 = 3;     // Sync real balance from CLOB every N market cycles
+const SETTLEMENT_POLL_SECS: u64 = 3;    // Re-check assumed fills this often
+const SETTLEMENT_MAX_POLLS: u32 = 40;   // ~2 min of LIVE before the assumption stands
+const SELL_POLL_BACKSTOP_TICKS: u64 = 5; // With the user channel up, still poll exits every N ticks
 // Market orders (FOK) fill instantly — no hold time needed
 
 // Realized volatility tracking
@@ -67,7 +91,7 @@ const VOL_WINDOW: usize = 30;
 // DATA TYPES
 // ═══════════════════════════════════════════════════════════════════════════
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct Position {
     id: usize,
     side: Side,
@@ -77,15 +101,21 @@ struct Position {
     cost_basis: f64,   // actual USDC spent (from build_market_order)
     tp_price: f64,     // take-profit price
     strategy: String,
+    #[serde(with = "session::instant_age")]
     opened_at: tokio::time::Instant,
     market_slug: String,
     // Active GTC sell order — always one active (TP, SL, or force)
     sell_order_id: Option<String>,
     sell_order_price: f64,      // price of the active sell order
     sell_order_type: String,    // "tp", "sl", "force"
+    #[serde(default)]
+    sell_order_size: f64,       // shares on the active sell order; 0 = the whole position
+    #[serde(default)]
+    exit_reason: Option<ExitReason>, // why the active sell order was placed
     sell_attempts: u32,         // how many times we've placed/replaced sell orders
-    #[allow(dead_code)]
-    order_id: Option<String>,
+    #[serde(default)]
+    sell_taker: bool,           // active sell crossed the bid when placed: pays the taker fee
+    order_id: Option<String>,   // entry order — matched against settlements
 }
 
 #[derive(Clone)]
@@ -124,6 +154,7 @@ impl std::fmt::Display for TradeLog {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct Stats {
     entries: usize,
     exits: usize,
@@ -133,13 +164,54 @@ struct Stats {
     total_resolution_pnl: f64,
     cycles: u32,
     order_failures: usize,
+    cohorts: CohortPnl,
+    /// Assumed fills the exchange later contradicted
+    #[serde(default)]
+    fill_corrections: usize,
+    /// Time in trade per strategy and exit reason
+    #[serde(default)]
+    hold_times: HoldTimeReport,
+    /// Realized P&L per exit reason
+    #[serde(default)]
+    exit_pnl: ExitPnlBreakdown,
+    /// Taker fees paid: market-buy entries and exits that crossed the bid
+    #[serde(default)]
+    fees: f64,
+    /// Gas for merges and redemptions, in USD
+    #[serde(default)]
+    gas: f64,
+}
+
+/// Everything a warm restart carries over.
+#[derive(Serialize, Deserialize)]
+struct LiveSession {
+    starting_capital: f64,
+    capital: f64,
+    positions: Vec<Position>,
+    stats: Stats,
+    ref_prices: HashMap<String, f64>,
+    join_kinds: HashMap<String, JoinKind>,
+    resolved_slugs: HashSet<String>,
+    trade_id: usize,
+    next_pos_id: usize,
 }
 
 impl Stats {
     fn new() -> Self {
         Self { entries: 0, exits: 0, resolutions: 0, winning_exits: 0,
                total_exit_pnl: 0.0, total_resolution_pnl: 0.0, cycles: 0,
-               order_failures: 0 }
+               order_failures: 0, cohorts: CohortPnl::new(),
+               fill_corrections: 0, hold_times: HoldTimeReport::new(), exit_pnl: ExitPnlBreakdown::new(), fees: 0.0, gas: 0.0 }
+    }
+
+    /// Realized P&L before fees and gas.
+    fn gross_pnl(&self) -> f64 {
+        self.total_exit_pnl + self.total_resolution_pnl
+    }
+
+    /// Realized P&L after fees and gas: what the kill switch and the summary go by.
+    fn net_pnl(&self) -> f64 {
+        self.gross_pnl() - self.fees - self.gas
     }
 }
 
@@ -153,6 +225,20 @@ async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt().with_env_filter("info").with_target(false).init();
 
     let config = Config::load_or_default();
+    let join_policy = config.strategy.join_policy.clone();
+    let exit_mgr = ExitManager::new(&config.risk.exit_ladder);
+    let journal = config.telemetry.journal_path.as_ref().and_then(|path| match TradeJournal::open(path) {
+        Ok(j) => Some(j),
+        Err(e) => {
+            eprintln!("  WARNING: Trade journal disabled: {}", e);
+            None
+        }
+    });
+    // Lag entry cut-off from how fast journaled mispricings closed
+    let half_life = OpportunityHalfLife::new(config.strategy.half_life.clone());
+    if let Some(entries) = journal.as_ref().and_then(|j| j.entries().ok()) {
+        half_life.load(&entries);
+    }
 
     // Validate we have a real private key
     if config.is_dry_run() {
@@ -177,19 +263,41 @@ async fn main() -> anyhow::Result<()> {
     // Fee rate fetched dynamically per token. Default 1000 (crypto markets have taker fees).
     order_builder.set_fee_rate_bps(1000);
 
-    let clob_client = ClobClient::new(config.polymarket.clone());
+    let clob_client = Arc::new(ClobClient::new(config.polymarket.clone()));
 
     // Initialize L2 API key auth
     println!("  Initializing CLOB authentication...");
     clob_client.init_auth().await?;
 
-    // Cancel ALL stale orders from previous runs to free locked USDC
-    println!("  Cancelling any stale orders from previous runs...");
-    match clob_client.cancel_all().await {
-        Ok(_) => println!("  All stale orders cancelled."),
-        Err(e) => eprintln!("  WARNING: Could not cancel stale orders: {}", e),
+    // Resume a warm-restart snapshot if one was left for us
+    let session_path = std::path::PathBuf::from(
+        std::env::var("SESSION_FILE").unwrap_or_else(|_| "live_session.json".to_string()),
+    );
+    let restored: Option<LiveSession> = match session::take_fresh(&session_path, |s| clock::now() < session_deadline(s)) {
+        Ok(Some(snapshot)) => {
+            println!("  Resuming session saved at {} ({} open positions)",
+                snapshot.saved_at.format("%H:%M:%S"), snapshot.state.positions.len());
+            Some(snapshot.state)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("  WARNING: Could not restore session: {}", e);
+            None
+        }
+    };
+
+    // Orders from previous runs lock USDC; keep only the exits a resumed
+    // position is still working
+    let keep: HashSet<String> = restored
+        .iter()
+        .flat_map(|s| s.positions.iter().filter_map(|p| p.sell_order_id.clone()))
+        .collect();
+    if restored.is_some() {
+        println!("  Warm restart — keeping {} resting exit orders.", keep.len());
+    }
+    if reconcile_orders(&clob_client, &keep).await {
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
     }
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
     // Fetch real balance (after cancelling stale orders so USDC is unlocked)
     let starting_capital = match clob_client.fetch_balance().await {
@@ -204,14 +312,13 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    if starting_capital < 1.0 {
+    if starting_capital < 1.0 && restored.is_none() {
         eprintln!("  ERROR: Balance too low (${:.2}). Need at least $1.00 to trade.", starting_capital);
         std::process::exit(1);
     }
 
     // Initialize on-chain merger for arb positions
-    let polygon_rpc = std::env::var("POLYGON_RPC_URL")
-        .unwrap_or_else(|_| "https://polygon-rpc.com".to_string());
+    let polygon_rpc = config.polymarket.polygon_rpc_url.clone();
     let merger_wallet = alloy_signer_local::PrivateKeySigner::from_bytes(
         &alloy_primitives::B256::from_slice(
             &hex::decode(
@@ -220,8 +327,10 @@ async fn main() -> anyhow::Result<()> {
         )
     ).expect("invalid private key");
 
+    let matic_price = Arc::new(MaticPrice::new(&config.polymarket.matic_price_url, config.polymarket.matic_usd));
     let merger = PolygonMerger::new(&polygon_rpc, merger_wallet)
-        .expect("failed to create PolygonMerger");
+        .expect("failed to create PolygonMerger")
+        .with_matic_price(matic_price.clone());
 
     // Check MATIC balance for gas
     match merger.check_gas_balance().await {
@@ -249,7 +358,11 @@ async fn main() -> anyhow::Result<()> {
         println!("  ARB: enabled ✓");
     }
 
+    // Session loss limit keeps measuring from the original start across restarts
+    let starting_capital = restored.as_ref().map_or(starting_capital, |s| s.starting_capital);
+
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
+    clock::start(ClobClient::new(config.polymarket.clone()), shutdown_tx.subscribe()).await;
     let prob_model = ProbabilityModel::new();
     let vol_per_min = Asset::BTC.vol_per_minute();
 
@@ -263,11 +376,19 @@ async fn main() -> anyhow::Result<()> {
     println!("{}", "=".repeat(80));
     println!("  BTC 5-MIN | REAL ORDERS | ${:.2} USDC | TAKER FEE 1000bps", starting_capital);
     println!("  Wallet: {:?}", order_builder.address());
-    println!("  TP: {:.0}% | SL: {:.0}% | Edge: >{:.0}¢ | Max/pos: ${:.2}",
-        TAKE_PROFIT_PCT * 100.0, STOP_LOSS_PCT * 100.0, LAG_MIN_EDGE * 100.0, MAX_COST_PER_POS);
+    println!("  Exit ladder: {} rungs | Edge: >{:.0}¢ | Max/pos: ${:.2}",
+        config.risk.exit_ladder.rungs.len(), LAG_MIN_EDGE * 100.0, MAX_COST_PER_POS);
     println!("  Kill switch: stop if down {:.0}% from start", MAX_SESSION_LOSS_PCT * 100.0);
     println!("{}", "=".repeat(80));
 
+    // Winners left at resolution are carried and redeemed on-chain (CARRY=true)
+    let carry = config.risk.carry.enabled.then(|| Arc::new(CarryBook::new(&config.risk.carry)));
+    matic_price.spawn(shutdown_tx.subscribe());
+    let mut redemptions = carry.as_ref().map(|c| c.spawn_redeemer(merger.clone(), shutdown_tx.subscribe()));
+    if let Some(carry) = &carry {
+        println!("  CARRY: on — ${:.2} pending redemption", carry.pending_value());
+    }
+
     // Data feeds
     let binance = Arc::new(BinanceFeed::new(config.binance.clone()));
     let mut poly_feed = PolymarketFeed::new(config.polymarket.clone());
@@ -277,15 +398,59 @@ async fn main() -> anyhow::Result<()> {
     binance.start_funding_poller(shutdown_tx.subscribe());
     poly.start(&shutdown_tx);
 
-    println!("  Waiting {}s for feeds...\n", FEED_INIT_SECS);
-    let _ = std::io::stdout().flush();
-    tokio::time::sleep(tokio::time::Duration::from_secs(FEED_INIT_SECS)).await;
+    // On-chain token balances: exits never ask for more than the wallet holds
+    let token_balances = (config.polymarket.token_balance_secs > 0)
+        .then(|| TokenBalances::new(&polygon_rpc, &format!("{:?}", order_builder.token_holder())))
+        .and_then(|r| r.map_err(|e| eprintln!("  WARNING: token balance feed disabled: {}", e)).ok())
+        .map(Arc::new);
+    if let Some(balances) = &token_balances {
+        let _ = balances.spawn(
+            poly.markets.clone(),
+            std::time::Duration::from_secs(config.polymarket.token_balance_secs),
+            shutdown_tx.subscribe(),
+        );
+    }
+
+    // Entries booked on an assumed fill keep being checked until the exchange
+    // settles them, by poll or user-channel fill. Exit orders learn they
+    // matched or were cancelled from the same channel.
+    let user_ws = UserWsFeed::new(&config.polymarket.ws_host, &format!("{:?}", order_builder.address()));
+    let mut order_updates = user_ws.subscribe_order_updates();
+    let mut exit_statuses: HashMap<String, RestingStatus> = HashMap::new();
+    let settlement = Arc::new(SettlementTracker::new(SETTLEMENT_MAX_POLLS));
+    settlement.spawn(
+        clob_client.clone(),
+        user_ws.subscribe_fills(),
+        std::time::Duration::from_secs(SETTLEMENT_POLL_SECS),
+        shutdown_tx.subscribe(),
+    );
+    user_ws.start(&shutdown_tx);
+
+    // Wait for discovery, first book per token and first BTC price
+    let barrier = ReadinessBarrier::new(vec![(Asset::BTC, Duration::FiveMin)]);
+    let readiness = barrier
+        .wait(
+            &poly,
+            &binance,
+            std::time::Duration::from_secs(FEED_READY_TIMEOUT_SECS),
+            |r| {
+                println!("  Warming up: {}", r.summary());
+                let _ = std::io::stdout().flush();
+            },
+        )
+        .await;
+    if readiness.is_ready() {
+        println!("  Feeds ready.\n");
+    } else {
+        println!("  Feeds not fully ready after {}s — still missing: {}\n",
+            FEED_READY_TIMEOUT_SECS, readiness.missing.join(", "));
+    }
 
     // Show initial state
     let slug = MarketDiscovery::current_slug(Asset::BTC, Duration::FiveMin);
     let rem = MarketDiscovery::time_remaining_in_current(Duration::FiveMin);
     let live = poly.get_market(&slug).is_some();
-    let btc_price = binance.get_price(Asset::BTC).await.unwrap_or(0.0);
+    let btc_price = binance.get_price(Asset::BTC).unwrap_or(0.0);
     println!("  BTC: ${:.2}  |  Market: {} | {:.0}s left | {}",
         btc_price, slug, rem, if live { "LIVE" } else { "waiting..." });
     println!("  Trading active. Ctrl+C to stop.\n");
@@ -301,12 +466,43 @@ async fn main() -> anyhow::Result<()> {
     let mut resolved_slugs: HashSet<String> = HashSet::new();
     let mut fee_fetched_slugs: HashSet<String> = HashSet::new();
     let mut ref_prices: HashMap<String, f64> = HashMap::new();
+    let mut join_kinds: HashMap<String, JoinKind> = HashMap::new();
     let mut last_entry = tokio::time::Instant::now() - tokio::time::Duration::from_secs(999);
     let mut last_dash = tokio::time::Instant::now();
     let mut prev_btc_price: f64 = 0.0;
     let mut btc_returns: VecDeque<f64> = VecDeque::new();
     let mut realized_vol_per_min: f64 = vol_per_min;
 
+    if let Some(s) = restored {
+        capital = s.capital;
+        positions = s.positions;
+        stats = s.stats;
+        ref_prices = s.ref_prices;
+        join_kinds = s.join_kinds;
+        resolved_slugs = s.resolved_slugs;
+        trade_id = s.trade_id;
+        next_pos_id = s.next_pos_id;
+
+        // Re-query every exit order: live ones keep being managed, fills are
+        // picked up by the exit loop, anything else gets re-placed.
+        for pos in positions.iter_mut() {
+            let Some(sell_oid) = pos.sell_order_id.clone() else { continue };
+            match clob_client.get_order(&sell_oid).await {
+                Ok((status, _)) => match RestingStatus::from_clob(&status) {
+                    RestingStatus::Live => println!("  [RESUME] #{} {:?} {} exit @ {:.2} still live",
+                        pos.id, pos.side, pos.sell_order_type, pos.sell_order_price),
+                    RestingStatus::Matched => println!("  [RESUME] #{} {:?} {} exit filled while down",
+                        pos.id, pos.side, pos.sell_order_type),
+                    RestingStatus::Gone => {
+                        println!("  [RESUME] #{} {:?} exit order {} — will re-place", pos.id, pos.side, status);
+                        pos.sell_order_id = None;
+                    }
+                },
+                Err(e) => eprintln!("  [RESUME] #{} status check failed ({}) — retrying in loop", pos.id, e),
+            }
+        }
+    }
+
     // Shutdown handler
     let shutdown_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
     let sf = shutdown_flag.clone();
@@ -314,6 +510,20 @@ async fn main() -> anyhow::Result<()> {
         let _ = tokio::signal::ctrl_c().await;
         sf.store(true, std::sync::atomic::Ordering::Relaxed);
     });
+    // SIGHUP = warm restart: save state and leave exit orders resting
+    let warm_restart = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    #[cfg(unix)]
+    {
+        let sf = shutdown_flag.clone();
+        let warm = warm_restart.clone();
+        tokio::spawn(async move {
+            use tokio::signal::unix::{signal, SignalKind};
+            let Ok(mut hup) = signal(SignalKind::hangup()) else { return };
+            hup.recv().await;
+            warm.store(true, std::sync::atomic::Ordering::Relaxed);
+            sf.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+    }
 
     let mut poll = tokio::time::interval(tokio::time::Duration::from_millis(TICK_MS));
     let entry_cooldown = tokio::time::Duration::from_secs(ENTRY_COOLDOWN_SECS);
@@ -322,21 +532,108 @@ async fn main() -> anyhow::Result<()> {
     // ═══════════════════════════════════════════════════════════════════════
     // MAIN LOOP
     // ═══════════════════════════════════════════════════════════════════════
+    let mut tick: u64 = 0;
     loop {
         poll.tick().await;
+        tick += 1;
         if shutdown_flag.load(std::sync::atomic::Ordering::Relaxed) {
-            println!("\n  Shutting down — cancelling all open orders...");
-            if let Err(e) = clob_client.cancel_all().await {
-                eprintln!("  WARNING: Failed to cancel orders: {}", e);
+            let saved = warm_restart.load(std::sync::atomic::Ordering::Relaxed) && {
+                let state = LiveSession {
+                    starting_capital, capital,
+                    positions: positions.clone(), stats: stats.clone(),
+                    ref_prices: ref_prices.clone(), join_kinds: join_kinds.clone(),
+                    resolved_slugs: resolved_slugs.clone(), trade_id, next_pos_id,
+                };
+                match session::save(&session_path, &state) {
+                    Ok(()) => {
+                        println!("\n  Warm restart — session saved to {}, {} exit orders left resting.",
+                            session_path.display(),
+                            positions.iter().filter(|p| p.sell_order_id.is_some()).count());
+                        true
+                    }
+                    Err(e) => {
+                        eprintln!("  WARNING: Could not save session: {}", e);
+                        false
+                    }
+                }
+            };
+            if !saved {
+                println!("\n  Shutting down — cancelling all open orders...");
+                if let Err(e) = clob_client.cancel_all().await {
+                    eprintln!("  WARNING: Failed to cancel orders: {}", e);
+                }
             }
             let _ = shutdown_tx.send(());
             break;
         }
 
+        // ── Final statuses of exit orders, pushed by the user channel ──
+        loop {
+            match order_updates.try_recv() {
+                Ok(update) if update.is_final() => {
+                    if positions.iter().any(|p| p.sell_order_id.as_deref() == Some(update.order_id.as_str())) {
+                        let status = if update.status == OrderStatus::Filled { RestingStatus::Matched } else { RestingStatus::Gone };
+                        exit_statuses.insert(update.order_id, status);
+                    }
+                }
+                Ok(_) | Err(tokio::sync::broadcast::error::TryRecvError::Lagged(_)) => {}
+                Err(_) => break,
+            }
+        }
+
+        // ── Correct entries whose assumed fill the exchange contradicted ──
+        for s in settlement.take_settlements().into_iter().filter(|s| s.needs_correction()) {
+            stats.fill_corrections += 1;
+            let Some(i) = positions.iter().position(|p| p.order_id.as_deref() == Some(s.order_id.as_str())) else {
+                eprintln!("  ⚠ SETTLEMENT {}: position already closed ({:.2} booked, {:.2} filled) — balance sync will correct capital",
+                    &s.order_id[..8.min(s.order_id.len())], s.assumed_shares, s.actual_shares);
+                continue;
+            };
+            let pos = &mut positions[i];
+            let real_shares = (s.actual_shares * 100.0).floor() / 100.0;
+            let real_cost = pos.cost_basis * s.fill_ratio();
+            capital += pos.cost_basis - real_cost;
+            // The resting exit was sized for the assumed fill
+            if let Some(sell_oid) = pos.sell_order_id.take() {
+                let _ = clob_client.cancel_order(&sell_oid).await;
+            }
+            println!("  SETTLEMENT #{} {:?}: booked {:.2} shares, filled {:.2} — capital ${:.2}",
+                pos.id, pos.side, s.assumed_shares, real_shares, capital);
+            if real_shares > 0.0 {
+                pos.size = real_shares;
+                pos.cost_basis = real_cost;
+            } else {
+                positions.remove(i);
+                stats.entries = stats.entries.saturating_sub(1);
+            }
+        }
+
+        // ── Carried winners redeemed on-chain: the USDC is now spendable ──
+        while let Some(result) = redemptions.as_mut().and_then(|rx| rx.try_recv().ok()) {
+            match result {
+                RedeemResult::Paid { redemption, tx } => {
+                    capital += redemption.shares.to_f64().unwrap_or(0.0);
+                    stats.gas += tx.gas.usd();
+                    journal_gas(journal.as_ref(), &tx.gas, &redemption.market, "carry");
+                    println!("  REDEEMED {} +${} tx={} gas ${:.4} — capital ${:.2}",
+                        redemption.market, redemption.shares, &tx.hash[..10.min(tx.hash.len())], tx.gas.usd(), capital);
+                }
+                RedeemResult::Reverted { market, gas } => {
+                    stats.gas += gas.usd();
+                    journal_gas(journal.as_ref(), &gas, &market, "carry");
+                }
+                RedeemResult::Abandoned(p) => {
+                    eprintln!("  ⚠ [CARRY] {} redeem gave up after {} attempts — ${} to redeem by hand",
+                        p.market, p.attempts, p.shares);
+                }
+            }
+        }
+
+        let settling = carry.as_ref().map_or(0.0, |c| c.pending_value().to_f64().unwrap_or(0.0));
         let now_inst = tokio::time::Instant::now();
 
         // ── Safety: kill switch ──
-        let realized_pnl = stats.total_exit_pnl + stats.total_resolution_pnl;
+        let realized_pnl = stats.net_pnl();
         if realized_pnl < -(starting_capital * MAX_SESSION_LOSS_PCT) {
             println!("\n  ⚠ KILL SWITCH: Realized P&L ${:.3} exceeds {:.0}% max loss. Stopping.",
                 realized_pnl, MAX_SESSION_LOSS_PCT * 100.0);
@@ -347,7 +644,7 @@ async fn main() -> anyhow::Result<()> {
         }
 
         // ── Get BTC price ──
-        let btc_price = match binance.get_price(Asset::BTC).await {
+        let btc_price = match binance.get_price(Asset::BTC) {
             Some(p) if p > 0.0 => p,
             _ => continue,
         };
@@ -390,7 +687,9 @@ async fn main() -> anyhow::Result<()> {
             p
         } else {
             let total_secs = 300.0;
-            let is_fresh = remaining > (total_secs - 15.0);
+            let join = join_policy.classify_elapsed(total_secs - remaining, total_secs);
+            join_kinds.insert(slug.clone(), join);
+            let is_fresh = join == JoinKind::Fresh;
             let calibrated = if is_fresh {
                 btc_price
             } else {
@@ -402,15 +701,15 @@ async fn main() -> anyhow::Result<()> {
                 calibrate_reference_price(btc_price, yes_mid, remaining / 60.0, vol_per_min)
             };
             ref_prices.insert(slug.clone(), calibrated);
-            println!("  [NEW MARKET] {} ref=${:.2} ({}cal) | {:.0}s left",
-                slug, calibrated, if is_fresh { "raw" } else { "book" }, remaining);
+            println!("  [NEW MARKET] {} ref=${:.2} ({}cal) | {:.0}s left | {:?} join",
+                slug, calibrated, if is_fresh { "raw" } else { "book" }, remaining, join);
             let _ = std::io::stdout().flush();
             calibrated
         };
 
         // ── Resolution: check if old positions survived to new market ──
-        // IMPORTANT: We cannot redeem tokens on-chain, so any position still open
-        // at resolution is effectively lost capital (tokens are unredeemed ERC1155s).
+        // Without CARRY, tokens still held at resolution are never redeemed, so
+        // they're written off. With it, winners are carried until redeemed.
         // Lag positions should ALWAYS exit via TP/SL/time/pre-res before this.
         let mut to_resolve: Vec<usize> = Vec::new();
         for (i, pos) in positions.iter().enumerate() {
@@ -419,29 +718,56 @@ async fn main() -> anyhow::Result<()> {
             }
         }
         if !to_resolve.is_empty() {
-            // Cancel any stale GTC orders from the old market before resolving
-            let _ = clob_client.cancel_all().await;
+            // Cancel stale GTC orders on the old market's tokens before resolving,
+            // leaving exits resting in other markets alone
+            let old_tokens: HashSet<String> = to_resolve.iter().map(|&i| positions[i].token_id.clone()).collect();
+            for token in &old_tokens {
+                let _ = clob_client.cancel_market_orders(None, Some(token)).await;
+            }
             let old_slug = &positions[to_resolve[0]].market_slug;
             let old_ref = ref_prices.get(old_slug).copied().unwrap_or(btc_price);
             let winner = if btc_price >= old_ref { Side::Yes } else { Side::No };
             println!("  ⚠ [RESOLVE] {} — {} positions survived to resolution!", old_slug, to_resolve.len());
             println!("    ref=${:.2} final=${:.2} → {:?} wins", old_ref, btc_price, winner);
-            println!("    WARNING: Tokens are unredeemed ERC1155s. Writing off as lost capital.");
+            let condition_id = poly.get_market(old_slug).and_then(|m| m.condition_id.clone());
+            if carry.is_none() {
+                println!("    WARNING: Tokens are unredeemed ERC1155s. Writing off as lost capital.");
+            }
             for &i in to_resolve.iter().rev() {
                 let pos = &positions[i];
-                // Write off entire cost — we can't get USDC back without redeemPositions()
-                let pnl = -pos.cost_basis;
+                // Carried winners pay $1 a share once redeemed; anything else is lost
+                let carried = carry.as_ref().filter(|_| pos.side == winner);
+                let pnl = match carried {
+                    Some(carry) => {
+                        let shares = Decimal::from_f64_retain(pos.size).unwrap_or_default().round_dp(6);
+                        carry.hold(&pos.market_slug, condition_id.as_deref(), winner, shares, Utc::now());
+                        pos.size - pos.cost_basis
+                    }
+                    None => -pos.cost_basis,
+                };
+                let exit_price = if carried.is_some() { 1.0 } else { 0.0 };
+                let hold_secs = now_inst.duration_since(pos.opened_at).as_secs_f64();
                 stats.resolutions += 1;
                 stats.total_resolution_pnl += pnl;
+                stats.cohorts.record(&pos.strategy, pnl);
+                stats.hold_times.record(&pos.strategy, ExitReason::Resolution, hold_secs);
+                stats.exit_pnl.record(ExitReason::Resolution, pnl);
+                if let Some(journal) = &journal {
+                    let mut entry = journal_entry(pos, exit_price, pos.size, pos.order_id.clone().unwrap_or_default())
+                        .with_exit(ExitReason::Resolution, hold_secs);
+                    entry.kind = "resolution".into();
+                    journal.record(&entry);
+                }
 
                 trade_id += 1;
                 let log = TradeLog {
-                    id: trade_id, time: Utc::now(), action: "EXPIRED".into(),
-                    side: pos.side, price: 0.0,
+                    id: trade_id, time: Utc::now(),
+                    action: if carried.is_some() { "CARRY" } else { "EXPIRED" }.into(),
+                    side: pos.side, price: exit_price,
                     size: pos.size, pnl, strategy: pos.strategy.clone(),
                     capital_after: capital,
                 };
-                println!("  LOST {}", log);
+                println!("  {} {}", if carried.is_some() { "WON" } else { "LOST" }, log);
                 let _ = std::io::stdout().flush();
                 push_log(&mut trade_log, log);
             }
@@ -452,7 +778,7 @@ async fn main() -> anyhow::Result<()> {
             }
             stats.cycles += 1;
             // Sync balance from CLOB periodically to catch any drift
-            if stats.cycles % BALANCE_SYNC_CYCLES == 0 {
+            if stats.cycles.is_multiple_of(BALANCE_SYNC_CYCLES) {
                 if let Ok(real_bal) = clob_client.fetch_balance().await {
                     let drift = (capital - real_bal).abs();
                     if drift > 0.05 {
@@ -462,14 +788,18 @@ async fn main() -> anyhow::Result<()> {
                     }
                 }
             }
-            println!("  [CYCLE {}] Capital: ${:.2}", stats.cycles, capital);
+            match &carry {
+                Some(carry) => println!("  [CYCLE {}] Capital: ${:.2} (+${:.2} pending redemption)",
+                    stats.cycles, capital, carry.pending_value()),
+                None => println!("  [CYCLE {}] Capital: ${:.2}", stats.cycles, capital),
+            }
             let _ = std::io::stdout().flush();
         }
 
         // ── Get Polymarket market and books ──
         let market = match poly.get_market(&slug) {
             Some(m) => m,
-            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
+            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, settling, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
         };
         // Fetch fee rate + neg_risk once per new market
         if !fee_fetched_slugs.contains(&slug) {
@@ -486,13 +816,20 @@ async fn main() -> anyhow::Result<()> {
         }
         let yes_book = match poly.get_book(&market.yes_token_id) {
             Some(b) => b,
-            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
+            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, settling, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
         };
         let no_book = match poly.get_book(&market.no_token_id) {
             Some(b) => b,
-            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
+            None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, settling, starting_capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
         };
 
+        // ── Lag timing for this market under the current vol ──
+        let regime = VolRegime::from_atr(Asset::BTC, btc_price * realized_vol_per_min);
+        let limits = half_life.limits(&market, regime, Utc::now()).unwrap_or(HoldLimits {
+            min_remaining_secs: MIN_REMAINING_SECS,
+            max_hold_secs: MAX_HOLD_SECS,
+        });
+
         // ── Fair value from Binance (using realized vol) ──
         let time_remaining_min = remaining / 60.0;
         let fair_up = prob_model.fair_prob_up(btc_price, ref_p, time_remaining_min, realized_vol_per_min, 0.0);
@@ -507,83 +844,110 @@ async fn main() -> anyhow::Result<()> {
         // ══════════════════════════════════════════════════════════════════════
         // EXIT LOGIC — ALL sells are GTC limit orders. No FOK.
         //
-        // Every position has ONE active GTC sell order at all times:
+        // Every position has ONE active GTC sell order at all times, picked
+        // by the exit ladder (risk.exit_ladder, EXIT_LADDER). By default:
         //   "tp"    → entry * 1.10  (wait for profit)
         //   "sl"    → bid * 0.50    (aggressive, fills at best bid)
         //   "force" → 0.01          (emergency, fills at any bid)
         //
         // Each tick: check if sell order filled → if yes, record exit.
-        // If conditions escalate → cancel current order → place more aggressive one.
+        // If the ladder escalates → cancel current order → place more aggressive one.
         // ══════════════════════════════════════════════════════════════════════
         let mut exits: Vec<usize> = Vec::new();
-        for (i, pos) in positions.iter().enumerate() {
+        for (i, pos) in positions.iter_mut().enumerate() {
             if pos.market_slug != slug { continue; }
 
             let current_bid = if pos.side == Side::Yes { yes_bid } else { no_bid };
             let hold_secs = now_inst.duration_since(pos.opened_at).as_secs_f64();
-            let pct_change = if pos.entry_price > 0.0 {
-                (current_bid - pos.entry_price) / pos.entry_price
-            } else { 0.0 };
 
             // ── Step 1: Check if current sell order has filled ──
-            if let Some(ref sell_oid) = pos.sell_order_id {
-                match clob_client.get_order(sell_oid).await {
-                    Ok((status, _size_matched)) if status == "MATCHED" => {
-                        // SOLD! GTC order filled automatically.
-                        let proceeds = pos.sell_order_price * pos.size;
-                        let pnl = proceeds - pos.cost_basis;
+            // The user channel reports matches and cancels as they happen; while
+            // it's up, polling is only a backstop for missed messages.
+            if let Some(sell_oid) = pos.sell_order_id.clone() {
+                let status = match exit_statuses.remove(&sell_oid) {
+                    Some(status) => Some(status),
+                    None if user_ws.is_connected() && !tick.is_multiple_of(SELL_POLL_BACKSTOP_TICKS) => None,
+                    None => match clob_client.get_order(&sell_oid).await {
+                        Ok((status, _size_matched)) => Some(RestingStatus::from_clob(&status)),
+                        Err(e) => {
+                            debug!("  Sell status check failed for #{}: {}", pos.id, e);
+                            None
+                        }
+                    },
+                };
+                let sold = if pos.sell_order_size > 0.0 { pos.sell_order_size.min(pos.size) } else { pos.size };
+                match status {
+                    Some(RestingStatus::Matched) => {
+                        // SOLD! GTC order filled automatically. A sell capped to
+                        // the tokens on-chain may cover only part of the position:
+                        // book the shares it sold, the rest is offered again below
+                        let partial = sold < pos.size;
+                        let cost = pos.cost_basis * sold / pos.size;
+                        let proceeds = pos.sell_order_price * sold;
+                        let pnl = proceeds - cost;
                         capital += proceeds;
+                        if pos.sell_taker {
+                            stats.fees += fee_usdc(pos.sell_order_price, sold, order_builder.fee_rate_bps());
+                        }
 
-                        stats.exits += 1;
                         stats.total_exit_pnl += pnl;
-                        if pnl > 0.0 { stats.winning_exits += 1; }
+                        stats.cohorts.record(&pos.strategy, pnl);
+                        let reason = pos.exit_reason.unwrap_or(match pos.sell_order_type.as_str() {
+                            "force" => ExitReason::Force,
+                            "sl" => ExitReason::StopLoss,
+                            _ => ExitReason::TakeProfit,
+                        });
+                        stats.hold_times.record(&pos.strategy, reason, hold_secs);
+                        stats.exit_pnl.record(reason, pnl);
+                        if let Some(journal) = &journal {
+                            journal.record(&journal_entry(pos, pos.sell_order_price, sold, sell_oid.clone()).with_exit(reason, hold_secs));
+                        }
 
                         trade_id += 1;
                         let log = TradeLog {
                             id: trade_id, time: Utc::now(),
                             action: format!("SELL({})", pos.sell_order_type),
-                            side: pos.side, price: pos.sell_order_price, size: pos.size,
+                            side: pos.side, price: pos.sell_order_price, size: sold,
                             pnl, strategy: pos.strategy.clone(),
                             capital_after: capital,
                         };
-                        println!("  EXIT  {} [GTC {} filled]", log, pos.sell_order_type);
-                        let _ = std::io::stdout().flush();
-                        push_log(&mut trade_log, log);
-                        exits.push(i);
-                        continue;
-                    }
-                    Ok((ref status, _)) => {
-                        if status == "CANCELLED" {
-                            // Order was cancelled externally, will re-place below
-                            debug!("  Sell order #{} was cancelled externally", pos.id);
+                        if partial {
+                            pos.size -= sold;
+                            pos.cost_basis -= cost;
+                            pos.sell_order_id = None;
+                            pos.sell_order_size = 0.0;
+                            println!("  PARTIAL EXIT {} [GTC {} filled, {:.2} left]", log, pos.sell_order_type, pos.size);
+                            let _ = std::io::stdout().flush();
+                            push_log(&mut trade_log, log);
+                        } else {
+                            stats.exits += 1;
+                            if pnl > 0.0 { stats.winning_exits += 1; }
+                            println!("  EXIT  {} [GTC {} filled]", log, pos.sell_order_type);
+                            let _ = std::io::stdout().flush();
+                            push_log(&mut trade_log, log);
+                            exits.push(i);
+                            continue;
                         }
-                        // Otherwise LIVE — still waiting for fill
                     }
-                    Err(e) => {
-                        debug!("  Sell status check failed for #{}: {}", pos.id, e);
+                    Some(RestingStatus::Gone) => {
+                        // Order was cancelled externally, will re-place below
+                        debug!("  Sell order #{} was cancelled externally", pos.id);
                     }
+                    // LIVE, or not checked this tick — still waiting for fill
+                    Some(RestingStatus::Live) | None => {}
                 }
             }
 
             // ── Step 2: Determine what sell order SHOULD be active ──
-            let desired_type = if remaining < 30.0 {
-                "force" // last 30s: emergency dump
-            } else if remaining < 60.0 || hold_secs >= MAX_HOLD_SECS {
-                "force" // deadline or max hold time
-            } else if pct_change <= -STOP_LOSS_PCT {
-                "sl" // hard SL triggered
-            } else if remaining < PRE_RESOLVE_EXIT_SECS && pct_change > 0.02 {
-                "sl" // pre-resolve profit lock (aggressive price to fill fast)
-            } else {
-                "tp" // normal: wait for take profit
-            };
+            let decision = exit_mgr.decide(&ExitInputs {
+                remaining_secs: remaining, hold_secs, entry_price: pos.entry_price, bid: current_bid,
+                max_hold_secs: Some(limits.max_hold_secs),
+            });
 
             // ── Step 3: Replace sell order if type needs to escalate ──
             // Only replace if: (a) no order exists, (b) need to escalate, (c) order was cancelled
-            let current_type = &pos.sell_order_type;
             let needs_replacement = pos.sell_order_id.is_none()
-                || (desired_type == "force" && current_type != "force")
-                || (desired_type == "sl" && current_type == "tp");
+                || ExitManager::should_replace(ExitStyle::parse(&pos.sell_order_type), decision.style);
 
             if needs_replacement {
                 // Cancel existing order first
@@ -603,34 +967,24 @@ async fn main() -> anyhow::Result<()> {
 
         // ── Step 4: Place/replace sell orders for positions that need them ──
         // (Done outside the immutable iterator loop above)
+        let mut booked: HashMap<String, f64> = HashMap::new();
+        for pos in &positions {
+            *booked.entry(pos.token_id.clone()).or_default() += pos.size;
+        }
         for pos in positions.iter_mut() {
             if pos.market_slug != slug { continue; }
 
             let current_bid = if pos.side == Side::Yes { yes_bid } else { no_bid };
             let hold_secs = now_inst.duration_since(pos.opened_at).as_secs_f64();
-            let pct_change = if pos.entry_price > 0.0 {
-                (current_bid - pos.entry_price) / pos.entry_price
-            } else { 0.0 };
-
-            let desired_type = if remaining < 30.0 || (remaining < 60.0) || hold_secs >= MAX_HOLD_SECS {
-                "force"
-            } else if pct_change <= -STOP_LOSS_PCT {
-                "sl"
-            } else if remaining < PRE_RESOLVE_EXIT_SECS && pct_change > 0.02 {
-                "sl"
-            } else {
-                "tp"
-            };
-
-            let desired_price = match desired_type {
-                "force" => 0.01,
-                "sl" => (current_bid * 0.50).max(0.01),
-                _ => pos.tp_price,
-            };
+            let decision = exit_mgr.decide(&ExitInputs {
+                remaining_secs: remaining, hold_secs, entry_price: pos.entry_price, bid: current_bid,
+                max_hold_secs: Some(limits.max_hold_secs),
+            });
+            let desired_type = decision.style.as_str();
+            let desired_price = decision.price;
 
             let needs_replacement = pos.sell_order_id.is_none()
-                || (desired_type == "force" && pos.sell_order_type != "force")
-                || (desired_type == "sl" && pos.sell_order_type == "tp");
+                || ExitManager::should_replace(ExitStyle::parse(&pos.sell_order_type), decision.style);
 
             if !needs_replacement { continue; }
 
@@ -641,6 +995,24 @@ async fn main() -> anyhow::Result<()> {
                 pos.sell_order_id = None;
             }
 
+            // Sell no more than the chain says we hold, less what the
+            // other positions in this token need. The position keeps its
+            // booked size; only this order is smaller.
+            let mut sell_size = pos.size;
+            if let Some(held) = token_balances.as_ref().and_then(|b| b.get(&pos.token_id)).and_then(|d| d.to_f64()) {
+                let available = held - (booked[&pos.token_id] - pos.size);
+                if available < 0.01 {
+                    debug!("  #{} sell waits: no {:?} tokens on-chain yet", pos.id, pos.side);
+                    continue;
+                }
+                if available < pos.size {
+                    let capped = (available * 100.0).floor() / 100.0;
+                    println!("  ⚠ #{} {:?}: {:.2} shares booked, {:.2} on-chain — selling {:.2}",
+                        pos.id, pos.side, pos.size, available, capped);
+                    sell_size = capped;
+                }
+            }
+
             // Place new GTC sell
             use rust_decimal::prelude::FromPrimitive;
             let intent = sattebaaz::models::order::OrderIntent {
@@ -649,12 +1021,13 @@ async fn main() -> anyhow::Result<()> {
                 order_side: OrderSide::Sell,
                 price: rust_decimal::Decimal::from_f64(desired_price)
                     .unwrap_or(rust_decimal::Decimal::ZERO),
-                size: rust_decimal::Decimal::from_f64(pos.size)
+                size: rust_decimal::Decimal::from_f64(sell_size)
                     .unwrap_or(rust_decimal::Decimal::ZERO),
                 order_type: OrderType::GTC,
                 post_only: false,
                 expiration: None,
                 strategy_tag: pos.strategy.clone(),
+                group_id: None,
             };
 
             match order_builder.build(&intent).await {
@@ -665,6 +1038,9 @@ async fn main() -> anyhow::Result<()> {
                             pos.sell_order_id = Some(oid.clone());
                             pos.sell_order_price = desired_price;
                             pos.sell_order_type = desired_type.to_string();
+                            pos.sell_order_size = sell_size;
+                            pos.exit_reason = Some(decision.reason);
+                            pos.sell_taker = desired_price <= current_bid;
                             pos.sell_attempts += 1;
                             println!("  SELL ORDER #{}: {} @ {:.2} [oid:{}]",
                                 pos.id, desired_type.to_uppercase(), desired_price,
@@ -694,7 +1070,13 @@ async fn main() -> anyhow::Result<()> {
         // ══════════════════════════════════════════════
         // ENTRY LOGIC — find new opportunities
         // ══════════════════════════════════════════════
-        if remaining > MIN_REMAINING_SECS
+        // Mid-cycle joins trade smaller; entries that fall under the $1 order
+        // minimum as a result are simply skipped.
+        let join = join_kinds.get(&slug).copied().unwrap_or(JoinKind::Fresh);
+        let size_mult = join_policy.size_mult(join);
+        let tag = if join == JoinKind::MidCycle { MID_CYCLE_TAG } else { "" };
+        if remaining > limits.min_remaining_secs
+            && join != JoinKind::Skip
             && positions.len() < MAX_POSITIONS
             && now_inst.duration_since(last_entry) >= entry_cooldown
         {
@@ -727,19 +1109,19 @@ async fn main() -> anyhow::Result<()> {
             }
 
             let mut entered = false;
-            if yes_net_edge > LAG_MIN_EDGE && yes_ask >= PRICE_FLOOR && yes_ask <= PRICE_CEILING
+            if yes_net_edge > LAG_MIN_EDGE && (PRICE_FLOOR..=PRICE_CEILING).contains(&yes_ask)
                 && yes_spread_ok && btc_just_moved && btc_up && !has_stuck_position
             {
                 // Market buy: walk book, cap spend to available depth
-                let desired = MAX_COST_PER_POS.min(capital - 0.10);
+                let desired = MAX_COST_PER_POS.min(capital - 0.10) * size_mult;
                 if let Some((worst_price, depth_usdc)) = yes_book.calculate_buy_market_price(desired) {
                     let spend = desired.min(depth_usdc); // cap to book depth
                     if spend >= MIN_ORDER_COST && capital >= spend {
                         let shares = spend / worst_price;
                         entered = try_market_buy(
-                            &order_builder, &clob_client, &market.yes_token_id, Side::Yes,
+                            &order_builder, &clob_client, &settlement, &exit_mgr, &market.yes_token_id, Side::Yes,
                             spend, worst_price, shares,
-                            &format!("lag(+{:.0}¢,net+{:.0}¢)", yes_mispricing * 100.0, yes_net_edge * 100.0),
+                            &format!("lag(+{:.0}¢,net+{:.0}¢){tag}", yes_mispricing * 100.0, yes_net_edge * 100.0),
                             &slug, &mut capital, &mut positions, &mut trade_log,
                             &mut trade_id, &mut next_pos_id, &mut stats, now_inst,
                         ).await;
@@ -748,18 +1130,18 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            if !entered && no_net_edge > LAG_MIN_EDGE && no_ask >= PRICE_FLOOR && no_ask <= PRICE_CEILING
+            if !entered && no_net_edge > LAG_MIN_EDGE && (PRICE_FLOOR..=PRICE_CEILING).contains(&no_ask)
                 && no_spread_ok && btc_just_moved && btc_down && !has_stuck_position
             {
-                let desired = MAX_COST_PER_POS.min(capital - 0.10);
+                let desired = MAX_COST_PER_POS.min(capital - 0.10) * size_mult;
                 if let Some((worst_price, depth_usdc)) = no_book.calculate_buy_market_price(desired) {
                     let spend = desired.min(depth_usdc);
                     if spend >= MIN_ORDER_COST && capital >= spend {
                         let shares = spend / worst_price;
                         entered = try_market_buy(
-                            &order_builder, &clob_client, &market.no_token_id, Side::No,
+                            &order_builder, &clob_client, &settlement, &exit_mgr, &market.no_token_id, Side::No,
                             spend, worst_price, shares,
-                            &format!("lag(+{:.0}¢,net+{:.0}¢)", no_mispricing * 100.0, no_net_edge * 100.0),
+                            &format!("lag(+{:.0}¢,net+{:.0}¢){tag}", no_mispricing * 100.0, no_net_edge * 100.0),
                             &slug, &mut capital, &mut positions, &mut trade_log,
                             &mut trade_id, &mut next_pos_id, &mut stats, now_inst,
                         ).await;
@@ -776,7 +1158,7 @@ async fn main() -> anyhow::Result<()> {
                 if let Some(ref cid) = condition_id {
                     let arb_cost_per_pair = yes_ask + no_ask;
                     let edge = 1.0 - arb_cost_per_pair;
-                    let arb_budget = (capital * 0.20).min(MAX_COST_PER_POS);
+                    let arb_budget = (capital * 0.20).min(MAX_COST_PER_POS) * size_mult;
                     let arb_size = arb_budget / arb_cost_per_pair;
                     let total_cost = arb_cost_per_pair * arb_size;
 
@@ -784,8 +1166,8 @@ async fn main() -> anyhow::Result<()> {
                         // Leg 1: Buy YES (market order)
                         let yes_spend = yes_ask * arb_size;
                         let yes_ok = try_market_buy(
-                            &order_builder, &clob_client, &market.yes_token_id, Side::Yes,
-                            yes_spend, yes_ask, arb_size, "arb_yes",
+                            &order_builder, &clob_client, &settlement, &exit_mgr, &market.yes_token_id, Side::Yes,
+                            yes_spend, yes_ask, arb_size, &format!("arb_yes{tag}"),
                             &slug, &mut capital, &mut positions, &mut trade_log,
                             &mut trade_id, &mut next_pos_id, &mut stats, now_inst,
                         ).await;
@@ -794,8 +1176,8 @@ async fn main() -> anyhow::Result<()> {
                             // Leg 2: Buy NO (market order)
                             let no_spend = no_ask * arb_size;
                             let no_ok = try_market_buy(
-                                &order_builder, &clob_client, &market.no_token_id, Side::No,
-                                no_spend, no_ask, arb_size, "arb_no",
+                                &order_builder, &clob_client, &settlement, &exit_mgr, &market.no_token_id, Side::No,
+                                no_spend, no_ask, arb_size, &format!("arb_no{tag}"),
                                 &slug, &mut capital, &mut positions, &mut trade_log,
                                 &mut trade_id, &mut next_pos_id, &mut stats, now_inst,
                             ).await;
@@ -806,11 +1188,15 @@ async fn main() -> anyhow::Result<()> {
                                 let _ = std::io::stdout().flush();
 
                                 match merger.merge_positions(cid, arb_size).await {
-                                    Ok(tx_hash) => {
+                                    Ok(tx) => {
                                         // Merge succeeded! Remove arb positions, credit $1 per pair
                                         let merge_revenue = arb_size; // $1 per merged pair
                                         capital += merge_revenue;
                                         let arb_pnl = merge_revenue - total_cost;
+                                        // Gas is paid in MATIC: out of P&L, not capital
+                                        let gas = tx.gas.usd();
+                                        stats.gas += gas;
+                                        journal_gas(journal.as_ref(), &tx.gas, &slug, "arb");
 
                                         // Remove the two arb positions (last two added)
                                         let len = positions.len();
@@ -821,6 +1207,8 @@ async fn main() -> anyhow::Result<()> {
 
                                         stats.exits += 1;
                                         stats.total_exit_pnl += arb_pnl;
+                                        stats.exit_pnl.record(ExitReason::Strategy, arb_pnl);
+                                        stats.cohorts.record(tag, arb_pnl);
                                         if arb_pnl > 0.0 { stats.winning_exits += 1; }
 
                                         trade_id += 1;
@@ -830,16 +1218,20 @@ async fn main() -> anyhow::Result<()> {
                                             side: Side::Yes,
                                             price: arb_cost_per_pair,
                                             size: arb_size,
-                                            pnl: arb_pnl,
-                                            strategy: format!("arb(edge={:.0}¢,tx={})", edge * 100.0, &tx_hash[..10.min(tx_hash.len())]),
+                                            pnl: arb_pnl - gas,
+                                            strategy: format!("arb(edge={:.0}¢,gas=${gas:.4},tx={}){tag}", edge * 100.0, &tx.hash[..10.min(tx.hash.len())]),
                                             capital_after: capital,
                                         };
-                                        println!("  MERGE {} +${:.4}", log, arb_pnl);
+                                        println!("  MERGE {} {:+.4}", log, arb_pnl - gas);
                                         let _ = std::io::stdout().flush();
                                         push_log(&mut trade_log, log);
                                         last_entry = now_inst;
                                     }
                                     Err(e) => {
+                                        if let Some(gas) = reverted_gas(&e) {
+                                            stats.gas += gas.usd();
+                                            journal_gas(journal.as_ref(), gas, &slug, "arb");
+                                        }
                                         // Merge failed — keep positions, they'll exit via TP/SL/force
                                         eprintln!("  [ARB] Merge FAILED: {}. Positions kept as lag fallback.", e);
                                         last_entry = now_inst;
@@ -857,7 +1249,7 @@ async fn main() -> anyhow::Result<()> {
         }
 
         // ── Dashboard ──
-        maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, starting_capital, btc_price,
+        maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, settling, starting_capital, btc_price,
             &positions, &trade_log, &stats, remaining, &slug,
             fair_up, yes_ask, yes_bid, no_ask, no_bid, ref_p, btc_move_pct);
     }
@@ -865,18 +1257,36 @@ async fn main() -> anyhow::Result<()> {
     // ═══════════════════════════════════════════════════════════
     // SESSION SUMMARY
     // ═══════════════════════════════════════════════════════════
-    let realized_pnl = stats.total_exit_pnl + stats.total_resolution_pnl;
+    let realized_pnl = stats.net_pnl();
     let exit_wr = if stats.exits > 0 { stats.winning_exits as f64 / stats.exits as f64 * 100.0 } else { 0.0 };
     println!("\n{}", "=".repeat(80));
     println!("  LIVE SESSION COMPLETE | {} cycles", stats.cycles);
     println!("{}", "=".repeat(80));
     println!("  Capital:    ${:.2} → ${:.2}  |  Realized P&L: {:>+.3} ({:>+.1}%)",
         starting_capital, capital, realized_pnl, realized_pnl / starting_capital * 100.0);
+    println!("  Gross P&L:  {:>+.4}  |  Fees: ${:.4}  |  Gas: ${:.4}", stats.gross_pnl(), stats.fees, stats.gas);
+    if let Some(carry) = carry.as_ref().filter(|c| !c.pending().is_empty()) {
+        println!("  Carried:    ${:.2} pending redemption ({} markets, resumed next run)",
+            carry.pending_value(), carry.pending().len());
+    }
     println!("  Entries:    {}  |  Exits: {} ({:.0}% win)  |  Resolutions: {}",
         stats.entries, stats.exits, exit_wr, stats.resolutions);
     println!("  Exit P&L:   {:>+.4}  |  Resolution P&L: {:>+.4}",
         stats.total_exit_pnl, stats.total_resolution_pnl);
+    println!("  {}", stats.cohorts.line(stats.entries));
     println!("  Order failures: {}", stats.order_failures);
+    if !stats.exit_pnl.is_empty() {
+        println!("  P&L by exit:");
+        for line in stats.exit_pnl.lines() {
+            println!("    {}", line);
+        }
+    }
+    if !stats.hold_times.is_empty() {
+        println!("  Hold times:");
+        for s in stats.hold_times.summary() {
+            println!("    {}", s);
+        }
+    }
     if !trade_log.is_empty() {
         println!("  Last trades:");
         for t in trade_log.iter().rev().take(10).collect::<Vec<_>>().iter().rev() {
@@ -898,6 +1308,8 @@ async fn main() -> anyhow::Result<()> {
 async fn try_market_buy(
     order_builder: &OrderBuilder,
     clob_client: &ClobClient,
+    settlement: &SettlementTracker,
+    exit_mgr: &ExitManager,
     token_id: &str,
     side: Side,
     spend: f64,
@@ -929,7 +1341,7 @@ async fn try_market_buy(
         Ok(result) => {
             if result.status == sattebaaz::models::order::OrderStatus::Rejected {
                 let msg = result.error_msg.unwrap_or_default();
-                if msg.contains("couldn't be fully filled") || msg.contains("no orders found") {
+                if RejectReason::parse(&msg) == RejectReason::NoMatch {
                     // Book too thin for our amount — not an error, skip
                     return false;
                 }
@@ -943,6 +1355,7 @@ async fn try_market_buy(
             // the order is MATCHED and get the real size_matched from the CLOB.
             let buy_oid = result.order_id.clone();
             let mut confirmed_shares: Option<f64> = None;
+            let mut assumed = false;
             let mut last_status = String::new();
             for attempt in 0..5 {
                 if attempt > 0 {
@@ -968,7 +1381,8 @@ async fn try_market_buy(
                                 let fallback = (actual_shares * 100.0).floor() / 100.0;
                                 if fallback > 0.0 {
                                     confirmed_shares = Some(fallback);
-                                    println!("  BUY ASSUMED FILLED: status still {} after {}ms, using {:.2} shares",
+                                    assumed = true;
+                                    println!("  BUY ASSUMED FILLED: status still {} after {}ms, using {:.2} shares (settling in background)",
                                         status, (attempt + 1) * 500, fallback);
                                 }
                             }
@@ -998,9 +1412,9 @@ async fn try_market_buy(
 
             *capital -= actual_spend;
 
-            // Calculate TP price: round DOWN to 0.01 tick so it sits on the book
-            let tp_price = ((worst_price * (1.0 + TAKE_PROFIT_PCT)) * 100.0).floor() / 100.0;
-            let tp_price = tp_price.min(0.99); // can't sell above 0.99
+            // Opening exit from the ladder, rounded DOWN to the 0.01 tick so it sits on the book
+            let opening = exit_mgr.opening(worst_price);
+            let tp_price = opening.price;
 
             // Immediately place GTC limit SELL at TP price — this sits on the book
             // and fills automatically. Maker order = zero fees.
@@ -1016,6 +1430,7 @@ async fn try_market_buy(
                     post_only: false,
                     expiration: None,
                     strategy_tag: strategy.to_string(),
+                    group_id: None,
                 };
                 match order_builder.build(&intent).await {
                     Ok(tp_signed) => {
@@ -1057,11 +1472,19 @@ async fn try_market_buy(
                 market_slug: slug.to_string(),
                 sell_order_id,
                 sell_order_price: tp_price,
-                sell_order_type: "tp".to_string(),
+                sell_order_type: opening.style.as_str().to_string(),
+                sell_order_size: real_shares,
+                exit_reason: Some(opening.reason),
                 sell_attempts: initial_sell_attempts,
+                sell_taker: false,
                 order_id: Some(buy_oid.clone()),
             });
+            stats.fees += fee_usdc(worst_price, real_shares, order_builder.fee_rate_bps());
+            if assumed {
+                settlement.track(&buy_oid, token_id, real_shares);
+            }
             stats.entries += 1;
+            if is_mid_cycle(strategy) { stats.cohorts.mid_cycle_entries += 1; }
             *trade_id += 1;
             let log = TradeLog {
                 id: *trade_id, time: Utc::now(), action: "BUY".into(),
@@ -1105,12 +1528,103 @@ fn push_log(log: &mut VecDeque<TradeLog>, entry: TradeLog) {
     if log.len() > 50 { log.pop_front(); }
 }
 
+/// When the 5-minute window `slug` names closes.
+fn window_close(slug: &str) -> Option<DateTime<Utc>> {
+    let start: i64 = slug.rsplit('-').next()?.parse().ok()?;
+    DateTime::from_timestamp(start + Duration::FiveMin.interval_seconds() as i64, 0)
+}
+
+/// A snapshot is worth resuming until the first of its positions' markets
+/// closes — after that, resolving them would judge the winner on a price
+/// from past the close. Without positions, until the window it was saved in
+/// closes.
+fn session_deadline(snapshot: &session::Snapshot<LiveSession>) -> DateTime<Utc> {
+    let saved_in = clock::window_start(Duration::FiveMin, snapshot.saved_at);
+    let saved_close = DateTime::from_timestamp((saved_in + Duration::FiveMin.interval_seconds()) as i64, 0)
+        .unwrap_or(snapshot.saved_at);
+    snapshot
+        .state
+        .positions
+        .iter()
+        .filter_map(|p| window_close(&p.market_slug))
+        .min()
+        .unwrap_or(saved_close)
+}
+
+/// Cancel every open order on the account except those in `keep`. When the
+/// orders can't be listed, a cold start (nothing to keep) cancels them all
+/// and a warm one leaves them be. Returns whether anything was cancelled.
+async fn reconcile_orders(clob: &ClobClient, keep: &HashSet<String>) -> bool {
+    let open = match clob.list_open_orders().await {
+        Ok(open) => open,
+        Err(e) if keep.is_empty() => {
+            eprintln!("  WARNING: Could not list open orders ({}) — cancelling all", e);
+            return match clob.cancel_all().await {
+                Ok(_) => true,
+                Err(e) => {
+                    eprintln!("  WARNING: Could not cancel stale orders: {}", e);
+                    false
+                }
+            };
+        }
+        Err(e) => {
+            eprintln!("  WARNING: Could not list open orders ({}) — leaving them resting", e);
+            return false;
+        }
+    };
+    let stale: Vec<String> = open.into_iter().map(|o| o.id).filter(|id| !keep.contains(id)).collect();
+    if stale.is_empty() {
+        return false;
+    }
+    println!("  Cancelling {} orders left by previous runs...", stale.len());
+    match clob.cancel_orders(&stale).await {
+        Ok(canceled) => {
+            println!("  {} stale orders cancelled.", canceled.len());
+            !canceled.is_empty()
+        }
+        Err(e) => {
+            eprintln!("  WARNING: Could not cancel stale orders: {}", e);
+            false
+        }
+    }
+}
+
+/// Journal line for closing `pos` at `price`.
+fn journal_entry(pos: &Position, price: f64, size: f64, order_id: String) -> JournalEntry {
+    use rust_decimal::prelude::FromPrimitive;
+    let fill = sattebaaz::models::order::Fill {
+        order_id,
+        token_id: pos.token_id.clone(),
+        side: OrderSide::Sell,
+        price: rust_decimal::Decimal::from_f64(price).unwrap_or_default(),
+        size: rust_decimal::Decimal::from_f64(size).unwrap_or_default(),
+        timestamp: Utc::now(),
+        fee: rust_decimal::Decimal::ZERO,
+    };
+    // One close per order: its sell fills in full or not at all
+    JournalEntry::from_fill(&fill, "close", &pos.market_slug, &pos.strategy)
+}
+
+/// Journal the gas an on-chain transaction burned for `strategy`.
+fn journal_gas(journal: Option<&TradeJournal>, gas: &GasSpend, market: &str, strategy: &str) {
+    if let Some(journal) = journal {
+        journal.record(&JournalEntry::from_gas(gas, market, strategy));
+    }
+}
+
+/// Taker fee in USDC for `size` shares at `price`.
+fn fee_usdc(price: f64, size: f64, fee_rate_bps: u32) -> f64 {
+    let d = |x: f64| Decimal::from_f64_retain(x).unwrap_or_default();
+    taker_fee(d(price), d(size), fee_rate_bps).to_f64().unwrap_or(0.0)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn maybe_dashboard(
     now: tokio::time::Instant,
     last: &mut tokio::time::Instant,
     interval: tokio::time::Duration,
     capital: f64,
+    settling: f64,
     starting_capital: f64,
     btc_price: f64,
     positions: &[Position],
@@ -1129,51 +1643,60 @@ fn maybe_dashboard(
     if now.duration_since(*last) < interval { return; }
     *last = now;
 
-    let realized_pnl = stats.total_exit_pnl + stats.total_resolution_pnl;
-    let exposure: f64 = positions.iter().map(|p| p.entry_price * p.size).sum();
     let exit_wr = if stats.exits > 0 { stats.winning_exits as f64 / stats.exits as f64 * 100.0 } else { 0.0 };
-
-    println!();
-    println!("  {}", "-".repeat(76));
-    println!("  {} | Cycle {} | BTC ${:.0} | Capital: ${:.2} | Realized P&L: {:>+.3} ({:>+.1}%)",
-        Utc::now().format("%H:%M:%S"), stats.cycles, btc_price, capital, realized_pnl, realized_pnl / starting_capital * 100.0);
-    println!("  Market: {} | {:.0}s left | Exposure: ${:.2} | {} open | {} order fails",
-        slug, remaining, exposure, positions.len(), stats.order_failures);
-    println!("  Stats: {} entries | {} exits ({:.0}% win) | {} resolved | exit_pnl: {:>+.3} | res_pnl: {:>+.3}",
-        stats.entries, stats.exits, exit_wr, stats.resolutions, stats.total_exit_pnl, stats.total_resolution_pnl);
-    let pct_move = if ref_p > 0.0 { (btc_price - ref_p) / ref_p * 100.0 } else { 0.0 };
-    let yes_spread = if yes_ask > 0.0 { (yes_ask - yes_bid) / yes_ask * 100.0 } else { 0.0 };
-    let no_spread = if no_ask > 0.0 { (no_ask - no_bid) / no_ask * 100.0 } else { 0.0 };
-    println!("  Fair: UP={:.3} DN={:.3} | BTC {:>+.3}% from ref | YES {:.2}/{:.2} ({:.0}%sp) | NO {:.2}/{:.2} ({:.0}%sp)",
-        fair_up, 1.0-fair_up, pct_move, yes_bid, yes_ask, yes_spread, no_bid, no_ask, no_spread);
     let yes_misp = fair_up - yes_ask;
-    let no_misp = (1.0-fair_up) - no_ask;
+    let no_misp = (1.0 - fair_up) - no_ask;
     let yes_net = yes_misp - (yes_ask - yes_bid);
     let no_net = no_misp - (no_ask - no_bid);
-    println!("  Mispricing: YES {:>+.3}(net{:>+.3}) | NO {:>+.3}(net{:>+.3}) | need >{:.3} & move>{:.2}% | last_move={:.3}%",
-        yes_misp, yes_net, no_misp, no_net, LAG_MIN_EDGE, MIN_BTC_MOVE_PCT, btc_move_pct);
+    let market = MarketRow {
+        slug: slug.to_string(),
+        remaining_secs: remaining,
+        reference: ref_p,
+        spot: btc_price,
+        fair_up,
+        yes: BookTop { bid: yes_bid, ask: yes_ask },
+        no: BookTop { bid: no_bid, ask: no_ask },
+        signal: format!("Mispricing: YES {:>+.3}(net{:>+.3}) | NO {:>+.3}(net{:>+.3}) | need >{:.3} & move>{:.2}% | last_move={:.3}%",
+            yes_misp, yes_net, no_misp, no_net, LAG_MIN_EDGE, MIN_BTC_MOVE_PCT, btc_move_pct),
+        ladders: Vec::new(),
+    };
 
-    if !positions.is_empty() {
-        println!("  Open positions:");
-        for p in positions {
-            let age = now.duration_since(p.opened_at).as_secs();
+    Dashboard {
+        title: format!("Cycle {} | BTC ${:.0}", stats.cycles, btc_price),
+        time: Utc::now(),
+        capital,
+        starting_capital,
+        realized_pnl: stats.net_pnl(),
+        fees: stats.fees,
+        gas: stats.gas,
+        exposure: positions.iter().map(|p| p.entry_price * p.size).sum(),
+        settling,
+        state: TradingState::Active,
+        size_mult: 1.0,
+        stats: vec![format!(
+            "Stats: {} entries | {} exits ({:.0}% win) | {} resolved | exit_pnl: {:>+.3} | res_pnl: {:>+.3} | {} order fails",
+            stats.entries, stats.exits, exit_wr, stats.resolutions, stats.total_exit_pnl, stats.total_resolution_pnl,
+            stats.order_failures
+        )],
+        markets: vec![market],
+        positions: positions.iter().map(|p| {
             let sell_str = if p.sell_order_id.is_some() {
                 format!(" | {} @{:.2}", p.sell_order_type.to_uppercase(), p.sell_order_price)
             } else {
                 " | NO SELL ORDER".to_string()
             };
-            println!("    #{} {:?} @ {:.3} x{:.2} | {}s held | {}{}",
-                p.id, p.side, p.entry_price, p.size, age, p.strategy, sell_str);
-        }
-    }
-
-    if !trade_log.is_empty() {
-        println!("  Recent:");
-        for t in trade_log.iter().rev().take(5).collect::<Vec<_>>().iter().rev() {
-            println!("    {}", t);
-        }
+            PositionRow {
+                id: p.id.to_string(),
+                market: p.market_slug.clone(),
+                side: p.side,
+                entry: p.entry_price,
+                size: p.size,
+                held_secs: now.duration_since(p.opened_at).as_secs(),
+                strategy: format!("{}{}", p.strategy, sell_str),
+            }
+        }).collect(),
+        recent: trade_log.iter().map(|t| t.to_string()).collect(),
+        logs: Vec::new(),
     }
-
-    println!("  {}", "-".repeat(76));
-    let _ = std::io::stdout().flush();
+    .print_text();
 }
@@ -8,12 +8,24 @@
 //!
 //! Usage:  cargo run --bin paper_trade
 
-use sattebaaz::config::Config;
+use sattebaaz::config::{Config, JoinKind};
+use sattebaaz::execution::clob_client::ClobClient;
 use sattebaaz::feeds::binance::BinanceFeed;
+use sattebaaz::feeds::clock;
 use sattebaaz::feeds::market_discovery::MarketDiscovery;
 use sattebaaz::feeds::polymarket::PolymarketFeed;
+use sattebaaz::feeds::readiness::ReadinessBarrier;
 use sattebaaz::models::market::{Asset, Duration, Side};
+use sattebaaz::models::order::OrderSide;
+use sattebaaz::models::position::MID_CYCLE_TAG;
+use sattebaaz::models::signal::VolRegime;
+use sattebaaz::signals::half_life::{HoldLimits, OpportunityHalfLife};
 use sattebaaz::signals::probability::ProbabilityModel;
+use sattebaaz::sim::impact::ImpactModel;
+use sattebaaz::telemetry::hold_time::{ExitReason, HoldTimeReport};
+use sattebaaz::telemetry::journal::TradeJournal;
+use sattebaaz::telemetry::pnl::{CohortPnl, ExitPnlBreakdown};
+use sattebaaz::tui::dashboard::{BookTop, Dashboard, MarketRow, PositionRow, TradingState};
 
 use chrono::{DateTime, Utc};
 use statrs::distribution::{ContinuousCDF, Normal};
@@ -27,7 +39,7 @@ use tokio::sync::broadcast;
 // ═══════════════════════════════════════════════════════════════════════════
 const STARTING_CAPITAL: f64 = 5.0;
 const TICK_MS: u64 = 2000;             // Poll every 2s
-const FEED_INIT_SECS: u64 = 10;
+const FEED_READY_TIMEOUT_SECS: u64 = 30; // Max wait for markets/books/prices at startup
 const DASHBOARD_SECS: u64 = 8;
 
 // Entry signals
@@ -36,14 +48,14 @@ const ARB_THRESHOLD: f64 = 0.97;       // Buy both when YES_ask + NO_ask < this
 const PRICE_FLOOR: f64 = 0.30;         // Don't buy below 30¢ — OTM tokens have fatal gamma risk
 const PRICE_CEILING: f64 = 0.70;       // Don't buy above 70¢ — too expensive, low payout
 const MAX_SPREAD_PCT: f64 = 0.10;      // Don't enter if spread > 10% of ask (tighter = better fills)
-const MIN_REMAINING_SECS: f64 = 120.0; // Need at least 2 min for lag to correct
+const MIN_REMAINING_SECS: f64 = 120.0; // Need at least 2 min for lag to correct (until the half-life is known)
 const MIN_BTC_MOVE_PCT: f64 = 0.01;    // Require ≥0.01% BTC move since last tick
 
 // Exit signals — calibrated for binary option token vol (~21%/min 1σ at p≈0.65)
 // SL must be ≥1σ to avoid noise stops. At 60% directional win rate, 1:1 ratio → +EV
 const TAKE_PROFIT_PCT: f64 = 0.10;     // Exit when bid ≥ entry × (1 + 10%)
 const STOP_LOSS_PCT: f64 = 0.08;       // Cut loss FAST when bid ≤ entry × (1 - 8%)
-const MAX_HOLD_SECS: f64 = 120.0;      // Force exit after 2 minutes
+const MAX_HOLD_SECS: f64 = 120.0;      // Force exit after 2 minutes (until the half-life is known)
 const PRE_RESOLVE_EXIT_SECS: f64 = 60.0; // Close positions in last 60s
 
 // Position sizing
@@ -55,7 +67,6 @@ const SL_COOLDOWN_SECS: u64 = 45;      // Extended cooldown after a stop loss (a
 
 // Fill simulation (realistic)
 const TAKER_FILL_PROB: f64 = 0.70;     // 70% fill (conservative — accounts for thin books)
-const SLIPPAGE_BPS: f64 = 50.0;        // 0.5% slippage on entries (eat through book depth)
 
 // Realized volatility tracking
 const VOL_WINDOW: usize = 30;          // Track last 30 BTC ticks (~60s) for realized vol
@@ -64,20 +75,10 @@ const VOL_WINDOW: usize = 30;          // Track last 30 BTC ticks (~60s) for rea
 // DATA TYPES
 // ═══════════════════════════════════════════════════════════════════════════
 
-struct Rng(u64);
-impl Rng {
-    fn new(seed: u64) -> Self { Self(seed) }
-    fn next_f64(&mut self) -> f64 {
-        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-        (self.0 >> 33) as f64 / (1u64 << 31) as f64
-    }
-}
-
 #[derive(Clone)]
 struct Position {
     id: usize,
     side: Side,           // Yes or No
-    token_id: String,
     entry_price: f64,
     size: f64,
     strategy: String,
@@ -129,12 +130,17 @@ struct Stats {
     total_exit_pnl: f64,
     total_resolution_pnl: f64,
     cycles: u32,
+    cohorts: CohortPnl,
+    hold_times: HoldTimeReport,
+    exit_pnl: ExitPnlBreakdown,
 }
 
 impl Stats {
     fn new() -> Self {
         Self { entries: 0, exits: 0, resolutions: 0, winning_exits: 0,
-               total_exit_pnl: 0.0, total_resolution_pnl: 0.0, cycles: 0 }
+               total_exit_pnl: 0.0, total_resolution_pnl: 0.0, cycles: 0,
+               cohorts: CohortPnl::new(),
+               hold_times: HoldTimeReport::new(), exit_pnl: ExitPnlBreakdown::new() }
     }
 }
 
@@ -151,16 +157,28 @@ async fn main() -> anyhow::Result<()> {
     println!("  BTC 5-MIN PAPER TRADER");
     println!("  Real Polymarket + Binance data | ${:.2} capital | NO FEES", STARTING_CAPITAL);
     println!("{}", "=".repeat(80));
-    println!("  Lag edge:    >{:.0}¢  |  TP: {:.0}%  |  SL: {:.0}%  |  Slippage: {:.0}bps",
-        LAG_MIN_EDGE * 100.0, TAKE_PROFIT_PCT * 100.0, STOP_LOSS_PCT * 100.0, SLIPPAGE_BPS);
-    println!("  Max hold:    {:.0}s   |  Positions: max {}  |  Max cost: ${:.2}/pos  |  Directional: YES",
+    println!("  Lag edge:    >{:.0}¢  |  TP: {:.0}%  |  SL: {:.0}%  |  Fills walk the book",
+        LAG_MIN_EDGE * 100.0, TAKE_PROFIT_PCT * 100.0, STOP_LOSS_PCT * 100.0);
+    println!("  Max hold:    {:.0}s*  |  Positions: max {}  |  Max cost: ${:.2}/pos  |  Directional: YES",
         MAX_HOLD_SECS, MAX_POSITIONS, MAX_COST_PER_POS);
+    println!("  * and 2 min entry cut-off, until journaled opportunity half-lives set them per market");
     println!("{}\n", "=".repeat(80));
 
-    let config = Config::default();
+    let config = Config::load_or_default();
+    let sim_rng = config.sim.root_rng();
+    println!("  Sim seed: {} (set SIM_SEED to replay fills)\n", sim_rng.seed());
+    // Our fills deplete the levels they take until the book refills
+    let mut impact = ImpactModel::new(config.sim.impact_half_life_secs);
+    let join_policy = config.strategy.join_policy.clone();
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
+    clock::start(ClobClient::new(config.polymarket.clone()), shutdown_tx.subscribe()).await;
     let prob_model = ProbabilityModel::new();
     let vol_per_min = Asset::BTC.vol_per_minute();
+    // Lag entry cut-off and max hold from how fast journaled mispricings closed
+    let half_life = OpportunityHalfLife::new(config.strategy.half_life.clone());
+    if let Some(entries) = config.telemetry.journal_path.as_ref().and_then(|p| TradeJournal::open(p).ok()?.entries().ok()) {
+        half_life.load(&entries);
+    }
 
     // Data feeds
     let binance = Arc::new(BinanceFeed::new(config.binance.clone()));
@@ -169,25 +187,38 @@ async fn main() -> anyhow::Result<()> {
     binance.start_funding_poller(shutdown_tx.subscribe());
     poly.start(&shutdown_tx);
 
-    println!("  Waiting {}s for feeds...\n", FEED_INIT_SECS);
-    let _ = std::io::stdout().flush();
-    tokio::time::sleep(tokio::time::Duration::from_secs(FEED_INIT_SECS)).await;
+    // Wait for discovery, first book per token and first BTC price
+    let barrier = ReadinessBarrier::new(vec![(Asset::BTC, Duration::FiveMin)]);
+    let readiness = barrier
+        .wait(
+            &poly,
+            &binance,
+            std::time::Duration::from_secs(FEED_READY_TIMEOUT_SECS),
+            |r| {
+                println!("  Warming up: {}", r.summary());
+                let _ = std::io::stdout().flush();
+            },
+        )
+        .await;
+    if readiness.is_ready() {
+        println!("  Feeds ready.\n");
+    } else {
+        println!("  Feeds not fully ready after {}s — still missing: {}\n",
+            FEED_READY_TIMEOUT_SECS, readiness.missing.join(", "));
+    }
 
     // Show initial state
     let slug = MarketDiscovery::current_slug(Asset::BTC, Duration::FiveMin);
     let rem = MarketDiscovery::time_remaining_in_current(Duration::FiveMin);
     let live = poly.get_market(&slug).is_some();
-    let btc_price = binance.get_price(Asset::BTC).await.unwrap_or(0.0);
+    let btc_price = binance.get_price(Asset::BTC).unwrap_or(0.0);
     println!("  BTC: ${:.2}  |  Market: {} | {:.0}s left | {}",
         btc_price, slug, rem, if live { "LIVE" } else { "waiting..." });
     println!("  Trading active. Ctrl+C to stop.\n");
     let _ = std::io::stdout().flush();
 
     // ── State ──
-    let mut rng = Rng::new(
-        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default().as_nanos() as u64,
-    );
+    let mut rng = sim_rng.fork("paper_fills");
     let mut capital = STARTING_CAPITAL;
     let mut positions: Vec<Position> = Vec::new();
     let mut trade_log: VecDeque<TradeLog> = VecDeque::new();
@@ -196,7 +227,9 @@ async fn main() -> anyhow::Result<()> {
     let mut stats = Stats::new();
     let mut resolved_slugs: HashSet<String> = HashSet::new();
     let mut ref_prices: HashMap<String, f64> = HashMap::new();  // slug → ref_price
+    let mut join_kinds: HashMap<String, JoinKind> = HashMap::new(); // slug → how we joined
     let mut last_entry = tokio::time::Instant::now() - tokio::time::Duration::from_secs(999);
+    let mut last_stop: Option<tokio::time::Instant> = None;
     let mut last_dash = tokio::time::Instant::now();
     let mut prev_btc_price: f64 = 0.0; // Track previous tick's BTC price for momentum check
     let mut btc_returns: VecDeque<f64> = VecDeque::new(); // Realized vol tracker
@@ -212,6 +245,7 @@ async fn main() -> anyhow::Result<()> {
 
     let mut poll = tokio::time::interval(tokio::time::Duration::from_millis(TICK_MS));
     let entry_cooldown = tokio::time::Duration::from_secs(ENTRY_COOLDOWN_SECS);
+    let sl_cooldown = tokio::time::Duration::from_secs(SL_COOLDOWN_SECS);
     let dash_interval = tokio::time::Duration::from_secs(DASHBOARD_SECS);
 
     // ═══════════════════════════════════════════════════════════
@@ -228,7 +262,7 @@ async fn main() -> anyhow::Result<()> {
         let now_inst = tokio::time::Instant::now();
 
         // ── Get BTC price ──
-        let btc_price = match binance.get_price(Asset::BTC).await {
+        let btc_price = match binance.get_price(Asset::BTC) {
             Some(p) if p > 0.0 => p,
             _ => continue,
         };
@@ -273,11 +307,14 @@ async fn main() -> anyhow::Result<()> {
         // ── Track reference price per market ──
         // When joining mid-cycle, calibrate ref from the book's implied probability
         // so our model agrees with the market. Only fresh cycles use raw BTC price.
+        // The join policy skips late joins and sizes mid-cycle joins down.
         let ref_p = if let Some(&p) = ref_prices.get(&slug) {
             p
         } else {
             let total_secs = 300.0; // 5-min market
-            let is_fresh = remaining > (total_secs - 15.0); // Within first 15s of market
+            let join = join_policy.classify_elapsed(total_secs - remaining, total_secs);
+            join_kinds.insert(slug.clone(), join);
+            let is_fresh = join == JoinKind::Fresh;
             let calibrated = if is_fresh {
                 btc_price // Fresh market — we have the true open price
             } else {
@@ -290,8 +327,8 @@ async fn main() -> anyhow::Result<()> {
                 calibrate_reference_price(btc_price, yes_mid, remaining / 60.0, vol_per_min)
             };
             ref_prices.insert(slug.clone(), calibrated);
-            println!("  [NEW MARKET] {} ref=${:.2} ({}cal) | {:.0}s left",
-                slug, calibrated, if is_fresh { "raw" } else { "book" }, remaining);
+            println!("  [NEW MARKET] {} ref=${:.2} ({}cal) | {:.0}s left | {:?} join",
+                slug, calibrated, if is_fresh { "raw" } else { "book" }, remaining, join);
             let _ = std::io::stdout().flush();
             calibrated
         };
@@ -322,6 +359,10 @@ async fn main() -> anyhow::Result<()> {
                 capital += pos.entry_price * pos.size + pnl; // return cost + pnl
                 stats.resolutions += 1;
                 stats.total_resolution_pnl += pnl;
+                stats.cohorts.record(&pos.strategy, pnl);
+                stats.hold_times.record(&pos.strategy, ExitReason::Resolution,
+                    now_inst.duration_since(pos.opened_at).as_secs_f64());
+                stats.exit_pnl.record(ExitReason::Resolution, pnl);
 
                 trade_id += 1;
                 let log = TradeLog {
@@ -358,16 +399,26 @@ async fn main() -> anyhow::Result<()> {
             None => { maybe_dashboard(now_inst, &mut last_dash, dash_interval, capital, btc_price, &positions, &trade_log, &stats, remaining, &slug, 0.5, 0.0, 0.0, 0.0, 0.0, ref_p, btc_move_pct); continue; }
         };
 
+        // ── Lag timing for this market under the current vol ──
+        let regime = VolRegime::from_atr(Asset::BTC, btc_price * realized_vol_per_min);
+        let limits = half_life.limits(&market, regime, Utc::now()).unwrap_or(HoldLimits {
+            min_remaining_secs: MIN_REMAINING_SECS,
+            max_hold_secs: MAX_HOLD_SECS,
+        });
+
         // ── Fair value from Binance (using realized vol) ──
         let time_remaining_min = remaining / 60.0;
         let fair_up = prob_model.fair_prob_up(btc_price, ref_p, time_remaining_min, realized_vol_per_min, 0.0);
         let fair_down = 1.0 - fair_up;
 
-        // Book prices
-        let yes_ask = yes_book.best_ask().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0)).unwrap_or(1.0);
-        let yes_bid = yes_book.best_bid().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(0.0)).unwrap_or(0.0);
-        let no_ask = no_book.best_ask().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0)).unwrap_or(1.0);
-        let no_bid = no_book.best_bid().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(0.0)).unwrap_or(0.0);
+        // Book prices, net of what our recent fills took
+        let now_ms = Utc::now().timestamp_millis();
+        let yes_view = impact.apply(&yes_book, now_ms);
+        let no_view = impact.apply(&no_book, now_ms);
+        let yes_ask = yes_view.best_ask().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0)).unwrap_or(1.0);
+        let yes_bid = yes_view.best_bid().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(0.0)).unwrap_or(0.0);
+        let no_ask = no_view.best_ask().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0)).unwrap_or(1.0);
+        let no_bid = no_view.best_bid().map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(0.0)).unwrap_or(0.0);
 
         // ══════════════════════════════════════════════
         // EXIT LOGIC — check existing positions first
@@ -375,47 +426,50 @@ async fn main() -> anyhow::Result<()> {
         let mut exits: Vec<usize> = Vec::new();
         for (i, pos) in positions.iter().enumerate() {
             if pos.market_slug != slug { continue; } // old market, handled above
-            if pos.strategy == "arb" { continue; } // arb holds to resolution for guaranteed profit
+            if pos.strategy.starts_with("arb") { continue; } // arb holds to resolution for guaranteed profit
 
             let current_bid = if pos.side == Side::Yes { yes_bid } else { no_bid };
             let hold_secs = now_inst.duration_since(pos.opened_at).as_secs_f64();
             let pct_change = if pos.entry_price > 0.0 { (current_bid - pos.entry_price) / pos.entry_price } else { 0.0 };
 
-            let should_exit = if pct_change >= TAKE_PROFIT_PCT {
-                true // Take profit
-            } else if pct_change <= -STOP_LOSS_PCT {
-                true // Stop loss
-            } else if hold_secs >= MAX_HOLD_SECS {
-                true // Max hold time
-            } else if remaining < PRE_RESOLVE_EXIT_SECS && pct_change > 0.0 {
-                true // Pre-resolution exit if profitable
-            } else {
-                false
-            };
+            let should_exit = pct_change >= TAKE_PROFIT_PCT // Take profit
+                || pct_change <= -STOP_LOSS_PCT // Stop loss
+                || hold_secs >= limits.max_hold_secs // Max hold time
+                || (remaining < PRE_RESOLVE_EXIT_SECS && pct_change > 0.0); // Pre-resolution exit if profitable
 
             if should_exit && current_bid > 0.01 {
-                // Simulate sell fill with slippage (sell at slightly worse than bid)
-                if rng.next_f64() < TAKER_FILL_PROB {
-                    let sell_slippage = current_bid * (SLIPPAGE_BPS / 10000.0);
-                    let fill_price = (current_bid - sell_slippage).max(0.01);
-                    let proceeds = fill_price * pos.size;
+                // Simulate the sell walking the bids; too thin a book waits a tick
+                let book = if pos.side == Side::Yes { &yes_book } else { &no_book };
+                let deep_enough = impact.depth(book, OrderSide::Sell, now_ms) >= pos.size;
+                let fill = (deep_enough && rng.chance(TAKER_FILL_PROB))
+                    .then(|| impact.execute(book, OrderSide::Sell, pos.size, None, now_ms))
+                    .flatten();
+                if let Some(fill) = fill {
+                    let fill_price = fill.avg_price();
+                    let proceeds = fill.notional;
                     let cost_basis = pos.entry_price * pos.size;
                     let pnl = proceeds - cost_basis;
                     capital += proceeds;
 
                     stats.exits += 1;
                     stats.total_exit_pnl += pnl;
+                    stats.cohorts.record(&pos.strategy, pnl);
                     if pnl > 0.0 { stats.winning_exits += 1; }
 
                     trade_id += 1;
-                    let reason = if pct_change >= TAKE_PROFIT_PCT { "tp" }
-                        else if pct_change <= -STOP_LOSS_PCT { "sl" }
-                        else if hold_secs >= MAX_HOLD_SECS { "time" }
-                        else { "pre_res" };
+                    let reason = if pct_change >= TAKE_PROFIT_PCT { ExitReason::TakeProfit }
+                        else if pct_change <= -STOP_LOSS_PCT { ExitReason::StopLoss }
+                        else if hold_secs >= limits.max_hold_secs { ExitReason::Time }
+                        else { ExitReason::PreResolve };
+                    stats.hold_times.record(&pos.strategy, reason, hold_secs);
+                    stats.exit_pnl.record(reason, pnl);
+                    if reason == ExitReason::StopLoss {
+                        last_stop = Some(now_inst);
+                    }
                     let log = TradeLog {
                         id: trade_id, time: Utc::now(),
                         action: format!("SELL({})", reason),
-                        side: pos.side, price: current_bid, size: pos.size,
+                        side: pos.side, price: fill_price, size: pos.size,
                         pnl, strategy: pos.strategy.clone(),
                         capital_after: capital,
                     };
@@ -434,9 +488,14 @@ async fn main() -> anyhow::Result<()> {
         // ENTRY LOGIC — find new opportunities
         // ══════════════════════════════════════════════
         // Adaptive cooldown: use longer cooldown after a stop loss to prevent chop
-        if remaining > MIN_REMAINING_SECS
+        let join = join_kinds.get(&slug).copied().unwrap_or(JoinKind::Fresh);
+        let size_mult = join_policy.size_mult(join);
+        let tag = if join == JoinKind::MidCycle { MID_CYCLE_TAG } else { "" };
+        if remaining > limits.min_remaining_secs
+            && join != JoinKind::Skip
             && positions.len() < MAX_POSITIONS
             && now_inst.duration_since(last_entry) >= entry_cooldown
+            && last_stop.is_none_or(|t| now_inst.duration_since(t) >= sl_cooldown)
         {
             let yes_mispricing = fair_up - yes_ask;
             let no_mispricing = fair_down - no_ask;
@@ -456,115 +515,114 @@ async fn main() -> anyhow::Result<()> {
             // Only buy YES if BTC just moved UP (book hasn't caught up to higher price)
             // Only buy NO if BTC just moved DOWN (book hasn't caught up to lower price)
             let mut entered = false;
-            if yes_mispricing > LAG_MIN_EDGE && yes_ask >= PRICE_FLOOR && yes_ask <= PRICE_CEILING
+            if yes_mispricing > LAG_MIN_EDGE && (PRICE_FLOOR..=PRICE_CEILING).contains(&yes_ask)
                 && yes_spread_ok && btc_just_moved && btc_up
             {
-                // Apply slippage: we pay slightly more than best ask
-                let buy_slippage = yes_ask * (SLIPPAGE_BPS / 10000.0);
-                let fill_price = yes_ask + buy_slippage;
-                let cost = MAX_COST_PER_POS.min(capital * 0.20);
-                let size = cost / fill_price;
-                if cost >= MIN_POSITION_COST && capital >= cost {
-                    if rng.next_f64() < TAKER_FILL_PROB {
-                        capital -= cost;
-                        next_pos_id += 1;
-                        positions.push(Position {
-                            id: next_pos_id, side: Side::Yes,
-                            token_id: market.yes_token_id.clone(),
-                            entry_price: fill_price, size,
-                            strategy: format!("lag(+{:.0}¢)", yes_mispricing * 100.0),
-                            opened_at: now_inst, market_slug: slug.clone(),
-                        });
-                        stats.entries += 1;
-                        trade_id += 1;
-                        let log = TradeLog {
-                            id: trade_id, time: Utc::now(), action: "BUY".into(),
-                            side: Side::Yes, price: fill_price, size, pnl: 0.0,
-                            strategy: format!("lag(+{:.0}¢)", yes_mispricing * 100.0),
-                            capital_after: capital,
-                        };
-                        println!("  ENTRY {}", log);
-                        let _ = std::io::stdout().flush();
-                        push_log(&mut trade_log, log);
-                        last_entry = now_inst;
-                        entered = true;
-                    }
+                // Walk the asks: thin levels push the average price up
+                let cost = MAX_COST_PER_POS.min(capital * 0.20) * size_mult;
+                let fill = (cost >= MIN_POSITION_COST && capital >= cost && rng.chance(TAKER_FILL_PROB))
+                    .then(|| impact.buy_usdc(&yes_book, cost, now_ms))
+                    .flatten();
+                if let Some(fill) = fill {
+                    let (fill_price, size) = (fill.avg_price(), fill.shares);
+                    capital -= fill.notional;
+                    next_pos_id += 1;
+                    positions.push(Position {
+                        id: next_pos_id, side: Side::Yes,
+                        entry_price: fill_price, size,
+                        strategy: format!("lag(+{:.0}¢){tag}", yes_mispricing * 100.0),
+                        opened_at: now_inst, market_slug: slug.clone(),
+                    });
+                    stats.entries += 1;
+                    if join == JoinKind::MidCycle { stats.cohorts.mid_cycle_entries += 1; }
+                    trade_id += 1;
+                    let log = TradeLog {
+                        id: trade_id, time: Utc::now(), action: "BUY".into(),
+                        side: Side::Yes, price: fill_price, size, pnl: 0.0,
+                        strategy: format!("lag(+{:.0}¢){tag}", yes_mispricing * 100.0),
+                        capital_after: capital,
+                    };
+                    println!("  ENTRY {}", log);
+                    let _ = std::io::stdout().flush();
+                    push_log(&mut trade_log, log);
+                    last_entry = now_inst;
+                    entered = true;
                 }
             }
             // Only buy NO if BTC just moved DOWN
-            if !entered && no_mispricing > LAG_MIN_EDGE && no_ask >= PRICE_FLOOR && no_ask <= PRICE_CEILING
+            if !entered && no_mispricing > LAG_MIN_EDGE && (PRICE_FLOOR..=PRICE_CEILING).contains(&no_ask)
                 && no_spread_ok && btc_just_moved && btc_down
             {
-                let buy_slippage = no_ask * (SLIPPAGE_BPS / 10000.0);
-                let fill_price = no_ask + buy_slippage;
-                let cost = MAX_COST_PER_POS.min(capital * 0.20);
-                let size = cost / fill_price;
-                if cost >= MIN_POSITION_COST && capital >= cost {
-                    if rng.next_f64() < TAKER_FILL_PROB {
-                        capital -= cost;
-                        next_pos_id += 1;
-                        positions.push(Position {
-                            id: next_pos_id, side: Side::No,
-                            token_id: market.no_token_id.clone(),
-                            entry_price: fill_price, size,
-                            strategy: format!("lag(+{:.0}¢)", no_mispricing * 100.0),
-                            opened_at: now_inst, market_slug: slug.clone(),
-                        });
-                        stats.entries += 1;
-                        trade_id += 1;
-                        let log = TradeLog {
-                            id: trade_id, time: Utc::now(), action: "BUY".into(),
-                            side: Side::No, price: fill_price, size, pnl: 0.0,
-                            strategy: format!("lag(+{:.0}¢)", no_mispricing * 100.0),
-                            capital_after: capital,
-                        };
-                        println!("  ENTRY {}", log);
-                        let _ = std::io::stdout().flush();
-                        push_log(&mut trade_log, log);
-                        last_entry = now_inst;
-                        entered = true;
-                    }
+                let cost = MAX_COST_PER_POS.min(capital * 0.20) * size_mult;
+                let fill = (cost >= MIN_POSITION_COST && capital >= cost && rng.chance(TAKER_FILL_PROB))
+                    .then(|| impact.buy_usdc(&no_book, cost, now_ms))
+                    .flatten();
+                if let Some(fill) = fill {
+                    let (fill_price, size) = (fill.avg_price(), fill.shares);
+                    capital -= fill.notional;
+                    next_pos_id += 1;
+                    positions.push(Position {
+                        id: next_pos_id, side: Side::No,
+                        entry_price: fill_price, size,
+                        strategy: format!("lag(+{:.0}¢){tag}", no_mispricing * 100.0),
+                        opened_at: now_inst, market_slug: slug.clone(),
+                    });
+                    stats.entries += 1;
+                    if join == JoinKind::MidCycle { stats.cohorts.mid_cycle_entries += 1; }
+                    trade_id += 1;
+                    let log = TradeLog {
+                        id: trade_id, time: Utc::now(), action: "BUY".into(),
+                        side: Side::No, price: fill_price, size, pnl: 0.0,
+                        strategy: format!("lag(+{:.0}¢){tag}", no_mispricing * 100.0),
+                        capital_after: capital,
+                    };
+                    println!("  ENTRY {}", log);
+                    let _ = std::io::stdout().flush();
+                    push_log(&mut trade_log, log);
+                    last_entry = now_inst;
+                    entered = true;
                 }
             }
 
             // ── Arb: buy both when YES+NO < threshold ──
             if !entered && yes_ask + no_ask < ARB_THRESHOLD && positions.len() + 1 < MAX_POSITIONS {
-                let arb_size = (capital * 0.20 / (yes_ask + no_ask)).max(MIN_POSITION_COST);
+                let arb_size = (capital * 0.20 * size_mult / (yes_ask + no_ask)).max(MIN_POSITION_COST);
                 let arb_cost = (yes_ask + no_ask) * arb_size;
-                if arb_cost <= capital * 0.40 && arb_size >= MIN_POSITION_COST {
-                    if rng.next_f64() < TAKER_FILL_PROB {
-                        capital -= arb_cost;
-                        next_pos_id += 1;
-                        positions.push(Position {
-                            id: next_pos_id, side: Side::Yes,
-                            token_id: market.yes_token_id.clone(),
-                            entry_price: yes_ask, size: arb_size,
-                            strategy: "arb".into(), opened_at: now_inst,
-                            market_slug: slug.clone(),
-                        });
-                        next_pos_id += 1;
-                        positions.push(Position {
-                            id: next_pos_id, side: Side::No,
-                            token_id: market.no_token_id.clone(),
-                            entry_price: no_ask, size: arb_size,
-                            strategy: "arb".into(), opened_at: now_inst,
-                            market_slug: slug.clone(),
-                        });
-                        stats.entries += 2;
-                        trade_id += 1;
-                        let edge = 1.0 - yes_ask - no_ask;
-                        let log = TradeLog {
-                            id: trade_id, time: Utc::now(), action: "ARB".into(),
-                            side: Side::Yes, price: yes_ask + no_ask, size: arb_size,
-                            pnl: 0.0,
-                            strategy: format!("arb(edge={:.0}¢)", edge * 100.0),
-                            capital_after: capital,
-                        };
-                        println!("  ENTRY {}", log);
-                        let _ = std::io::stdout().flush();
-                        push_log(&mut trade_log, log);
-                        last_entry = now_inst;
-                    }
+                let deep_enough = impact.depth(&yes_book, OrderSide::Buy, now_ms) >= arb_size
+                    && impact.depth(&no_book, OrderSide::Buy, now_ms) >= arb_size;
+                if arb_cost <= capital * 0.40 && arb_size >= MIN_POSITION_COST && deep_enough && rng.chance(TAKER_FILL_PROB) {
+                    let yes_price = impact.execute(&yes_book, OrderSide::Buy, arb_size, None, now_ms).map_or(yes_ask, |f| f.avg_price());
+                    let no_price = impact.execute(&no_book, OrderSide::Buy, arb_size, None, now_ms).map_or(no_ask, |f| f.avg_price());
+                    capital -= (yes_price + no_price) * arb_size;
+                    next_pos_id += 1;
+                    positions.push(Position {
+                        id: next_pos_id, side: Side::Yes,
+                        entry_price: yes_price, size: arb_size,
+                        strategy: format!("arb{tag}"), opened_at: now_inst,
+                        market_slug: slug.clone(),
+                    });
+                    next_pos_id += 1;
+                    positions.push(Position {
+                        id: next_pos_id, side: Side::No,
+                        entry_price: no_price, size: arb_size,
+                        strategy: format!("arb{tag}"), opened_at: now_inst,
+                        market_slug: slug.clone(),
+                    });
+                    stats.entries += 2;
+                    if join == JoinKind::MidCycle { stats.cohorts.mid_cycle_entries += 2; }
+                    trade_id += 1;
+                    let edge = 1.0 - yes_price - no_price;
+                    let log = TradeLog {
+                        id: trade_id, time: Utc::now(), action: "ARB".into(),
+                        side: Side::Yes, price: yes_price + no_price, size: arb_size,
+                        pnl: 0.0,
+                        strategy: format!("arb(edge={:.0}¢){tag}", edge * 100.0),
+                        capital_after: capital,
+                    };
+                    println!("  ENTRY {}", log);
+                    let _ = std::io::stdout().flush();
+                    push_log(&mut trade_log, log);
+                    last_entry = now_inst;
                 }
             }
         }
@@ -589,6 +647,19 @@ async fn main() -> anyhow::Result<()> {
         stats.entries, stats.exits, exit_wr, stats.resolutions);
     println!("  Exit P&L:   {:>+.4}  |  Resolution P&L: {:>+.4}",
         stats.total_exit_pnl, stats.total_resolution_pnl);
+    println!("  {}", stats.cohorts.line(stats.entries));
+    if !stats.exit_pnl.is_empty() {
+        println!("  P&L by exit:");
+        for line in stats.exit_pnl.lines() {
+            println!("    {}", line);
+        }
+    }
+    if !stats.hold_times.is_empty() {
+        println!("  Hold times:");
+        for s in stats.hold_times.summary() {
+            println!("    {}", s);
+        }
+    }
     if !trade_log.is_empty() {
         println!("  Last trades:");
         for t in trade_log.iter().rev().take(10).collect::<Vec<_>>().iter().rev() {
@@ -659,46 +730,50 @@ fn maybe_dashboard(
     if now.duration_since(*last) < interval { return; }
     *last = now;
 
-    let realized_pnl = stats.total_exit_pnl + stats.total_resolution_pnl;
-    let exposure: f64 = positions.iter().map(|p| p.entry_price * p.size).sum();
     let exit_wr = if stats.exits > 0 { stats.winning_exits as f64 / stats.exits as f64 * 100.0 } else { 0.0 };
-
-    println!();
-    println!("  {}", "-".repeat(76));
-    println!("  {} | Cycle {} | BTC ${:.0} | Capital: ${:.2} | Realized P&L: {:>+.3} ({:>+.1}%)",
-        Utc::now().format("%H:%M:%S"), stats.cycles, btc_price, capital, realized_pnl, realized_pnl / STARTING_CAPITAL * 100.0);
-    println!("  Market: {} | {:.0}s left | Exposure: ${:.2} | {} open",
-        slug, remaining, exposure, positions.len());
-    println!("  Stats: {} entries | {} exits ({:.0}% win) | {} resolved | exit_pnl: {:>+.3} | res_pnl: {:>+.3}",
-        stats.entries, stats.exits, exit_wr, stats.resolutions, stats.total_exit_pnl, stats.total_resolution_pnl);
-    let pct_move = if ref_p > 0.0 { (btc_price - ref_p) / ref_p * 100.0 } else { 0.0 };
-    let yes_spread = if yes_ask > 0.0 { (yes_ask - yes_bid) / yes_ask * 100.0 } else { 0.0 };
-    let no_spread = if no_ask > 0.0 { (no_ask - no_bid) / no_ask * 100.0 } else { 0.0 };
-    println!("  Fair: UP={:.3} DN={:.3} | BTC {:>+.3}% from ref | YES {:.2}/{:.2} ({:.0}%sp) | NO {:.2}/{:.2} ({:.0}%sp)",
-        fair_up, 1.0-fair_up, pct_move, yes_bid, yes_ask, yes_spread, no_bid, no_ask, no_spread);
     let yes_misp = fair_up - yes_ask;
-    let no_misp = (1.0-fair_up) - no_ask;
-    println!("  Mispricing: YES {:>+.3} | NO {:>+.3} | need >{:.3} & move>{:.2}% | last_move={:.3}%",
-        yes_misp, no_misp, LAG_MIN_EDGE, MIN_BTC_MOVE_PCT, btc_move_pct);
-
-    // Open positions detail
-    if !positions.is_empty() {
-        println!("  Open positions:");
-        for p in positions {
-            let age = now.duration_since(p.opened_at).as_secs();
-            println!("    #{} {:?} @ {:.3} x{:.2} | {}s held | {}",
-                p.id, p.side, p.entry_price, p.size, age, p.strategy);
-        }
+    let no_misp = (1.0 - fair_up) - no_ask;
+    let market = MarketRow {
+        slug: slug.to_string(),
+        remaining_secs: remaining,
+        reference: ref_p,
+        spot: btc_price,
+        fair_up,
+        yes: BookTop { bid: yes_bid, ask: yes_ask },
+        no: BookTop { bid: no_bid, ask: no_ask },
+        signal: format!("Mispricing: YES {:>+.3} | NO {:>+.3} | need >{:.3} & move>{:.2}% | last_move={:.3}%",
+            yes_misp, no_misp, LAG_MIN_EDGE, MIN_BTC_MOVE_PCT, btc_move_pct),
+        ladders: Vec::new(),
+    };
+
+    Dashboard {
+        title: format!("Cycle {} | BTC ${:.0}", stats.cycles, btc_price),
+        time: Utc::now(),
+        capital,
+        starting_capital: STARTING_CAPITAL,
+        realized_pnl: stats.total_exit_pnl + stats.total_resolution_pnl,
+        fees: 0.0, // Paper fills are fee-free
+        gas: 0.0,
+        exposure: positions.iter().map(|p| p.entry_price * p.size).sum(),
+        settling: 0.0,
+        state: TradingState::Active,
+        size_mult: 1.0,
+        stats: vec![format!(
+            "Stats: {} entries | {} exits ({:.0}% win) | {} resolved | exit_pnl: {:>+.3} | res_pnl: {:>+.3}",
+            stats.entries, stats.exits, exit_wr, stats.resolutions, stats.total_exit_pnl, stats.total_resolution_pnl
+        )],
+        markets: vec![market],
+        positions: positions.iter().map(|p| PositionRow {
+            id: p.id.to_string(),
+            market: p.market_slug.clone(),
+            side: p.side,
+            entry: p.entry_price,
+            size: p.size,
+            held_secs: now.duration_since(p.opened_at).as_secs(),
+            strategy: p.strategy.clone(),
+        }).collect(),
+        recent: trade_log.iter().map(|t| t.to_string()).collect(),
+        logs: Vec::new(),
     }
-
-    // Recent trades
-    if !trade_log.is_empty() {
-        println!("  Recent:");
-        for t in trade_log.iter().rev().take(5).collect::<Vec<_>>().iter().rev() {
-            println!("    {}", t);
-        }
-    }
-
-    println!("  {}", "-".repeat(76));
-    let _ = std::io::stdout().flush();
+    .print_text();
 }
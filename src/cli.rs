@@ -0,0 +1,36 @@
+//! Command-line flags for the subcommands and tools.
+
+/// Value of `--flag value` or `--flag=value` on the command line; the
+/// first occurrence wins.
+pub fn arg(name: &str) -> Option<String> {
+    arg_in(std::env::args().skip(1), name)
+}
+
+fn arg_in(mut args: impl Iterator<Item = String>, name: &str) -> Option<String> {
+    while let Some(a) = args.next() {
+        if let Some(v) = a.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(v.to_string());
+        }
+        if a == name {
+            return args.next();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(str::to_string)
+    }
+
+    #[test]
+    fn test_both_flag_forms() {
+        assert_eq!(arg_in(args("--days 7 --out x.toml"), "--days").as_deref(), Some("7"));
+        assert_eq!(arg_in(args("--days=7"), "--days").as_deref(), Some("7"));
+        assert_eq!(arg_in(args("--days-ago=7 --days"), "--days"), None);
+        assert_eq!(arg_in(args("--out x"), "--days"), None);
+    }
+}
@@ -1,12 +1,22 @@
+use crate::feeds::market_discovery::MarketDiscovery;
+use crate::feeds::polymarket::NEXT_WINDOW_LEAD_SECS;
+use crate::models::market::{Asset, Duration, Market};
+use crate::models::signal::VolRegime;
+use crate::sim::rng::SimRng;
+use crate::telemetry::alerts::AlertSeverity;
+use crate::telemetry::hold_time::ExitReason;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub polymarket: PolymarketConfig,
     pub binance: BinanceConfig,
+    pub liquidations: LiquidationFeedConfig,
     pub strategy: StrategyConfig,
     pub risk: RiskConfig,
     pub telemetry: TelemetryConfig,
+    pub sim: SimConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,10 +24,41 @@ pub struct PolymarketConfig {
     pub clob_host: String,
     pub ws_host: String,
     pub gamma_api_host: String,
+    pub data_api_host: String,        // Account history (trades, redemptions)
+    pub rtds_host: String,            // Real-time data socket (Chainlink oracle prices)
+    pub polygon_rpc_url: String,      // Polygon JSON-RPC (merges, sweeps)
     pub chain_id: u64,
     pub private_key: String,
     pub funder_address: Option<String>,
     pub signature_type: u8, // 0 = EOA, 1 = Poly Proxy
+    pub retry: RestRetryConfig,
+    pub prewarm_secs: u64,  // Authenticated ping to the order host every N seconds (0 = off)
+    pub token_balance_secs: u64, // Read our conditional token balances from chain every N seconds (0 = off)
+    pub matic_price_url: String, // MATIC/USD ticker for pricing gas (empty = fixed matic_usd)
+    pub matic_usd: f64,          // MATIC/USD used until the ticker is read
+}
+
+/// Retries for transient CLOB REST failures (transport errors, 429, 5xx),
+/// and the cap on requests in flight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestRetryConfig {
+    pub max_retries: u32,             // Retries per call after the first attempt (0 = off)
+    pub base_ms: u64,                 // First backoff; doubles per retry, jittered
+    pub max_backoff_ms: u64,          // Cap on a single backoff
+    pub budget_per_min: u32,          // Retries allowed across all calls per minute
+    pub max_in_flight: usize,         // Concurrent CLOB requests; beyond it, exits and cancels queue ahead of entries
+}
+
+impl Default for RestRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_ms: 200,
+            max_backoff_ms: 2_000,
+            budget_per_min: 30,
+            max_in_flight: 8,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +66,18 @@ pub struct BinanceConfig {
     pub ws_url: String,
     pub rest_url: String,
     pub streams: Vec<String>, // e.g. ["btcusdt@trade", "btcusdt@kline_1m"]
+    pub spot_ws_url: String,
+    pub spot_streams: Vec<String>, // Spot prints for the basis signal; empty = no spot connection
+}
+
+/// Other venues' liquidation sockets, merged with Binance's (see
+/// `feeds::liquidations`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationFeedConfig {
+    pub bybit_enabled: bool,
+    pub bybit_ws_url: String,
+    pub okx_enabled: bool,
+    pub okx_ws_url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,9 +87,12 @@ pub struct StrategyConfig {
     pub lag_exploit_enabled: bool,
     pub market_making_enabled: bool,
     pub momentum_enabled: bool,
+    pub late_gamma_enabled: bool,
+    pub open_sniper_enabled: bool,
 
     pub straddle_max_combined: f64,   // Max YES+NO sum to enter straddle (e.g. 0.97)
     pub straddle_max_capital_pct: f64, // Max % of capital per straddle (e.g. 0.25)
+    pub straddle_min_leg_pct: f64,    // Floor on a leg shrunk for existing inventory, as a share of the straddle size (e.g. 0.25)
     pub bias_min_confidence: f64,      // Min confidence to amplify (e.g. 0.35)
     pub bias_max_capital_pct: f64,     // Max % on directional bet (e.g. 0.15)
 
@@ -47,14 +103,48 @@ pub struct StrategyConfig {
     pub lag_kelly_fraction: f64,      // Fractional Kelly (e.g. 0.25)
 
     pub mm_base_size_pct: f64,        // Base quote size as % of capital (e.g. 0.10)
+    pub mm_levels: usize,             // Quotes per side, stepping away from fair value (e.g. 3)
+    pub mm_level_spacing: f64,        // Extra distance from fair value per level (e.g. 0.01)
+    pub mm_level_size_mult: f64,      // Each level's size relative to the one inside it (e.g. 1.5)
 
     pub momentum_min_signal: f64,     // Min momentum to trade (e.g. 0.003)
     pub momentum_min_divergence: f64, // Min divergence (e.g. 0.02)
 
+    pub late_gamma_window_secs: f64,  // Active in the last N seconds of a window (e.g. 90)
+    pub late_gamma_min_edge: f64,     // Min model-vs-book disagreement (e.g. 0.15)
+    pub late_gamma_order_usdc: f64,   // Max cost per order (e.g. 1.0)
+    pub late_gamma_market_usdc: f64,  // Max cost per market window (e.g. 2.0)
+
+    pub open_sniper_window_secs: f64,     // Active in the first N seconds of a window (e.g. 5)
+    pub open_sniper_min_edge: f64,        // Min fair-value-vs-ask gap on opening quotes (e.g. 0.08)
+    pub open_sniper_order_usdc: f64,      // Max cost of the single entry per window; must buy 5 shares (e.g. 5.0)
+    pub open_sniper_max_book_age_ms: f64, // Skip books older than this (e.g. 250)
+
     pub lockout_seconds_5m: f64,      // Stop trading N seconds before resolution (e.g. 30)
     pub lockout_seconds_15m: f64,     // (e.g. 30)
 
+    pub eval_budget_ms: f64,          // Max time per strategy evaluation; overruns are discarded (e.g. 5.0)
+    pub eval_overrun_bench_secs: u64, // Skip a strategy this long once it keeps overrunning (e.g. 30)
+    pub eval_overrun_streak: u32,     // Overruns in a row that bench a strategy (e.g. 3)
+    pub eval_throttle: EvalThrottleConfig,
+    pub max_market_notional_per_eval: f64, // Cap on combined intent notional per market per evaluation; 0 = off
+    pub vol_calibration_path: Option<String>, // Hour-of-day vol curves from `calibrate_vol`; None = constants only
+
     pub capital_allocation: CapitalAllocation,
+    pub join_policy: JoinPolicyConfig,
+    pub fill_quality: FillQualityConfig,
+    pub depth_sizing: DepthSizingConfig,
+    pub spread_control: SpreadControlConfig,
+    pub screen: MarketScreenConfig,
+    pub ml_filter: MlFilterConfig,
+    pub fair_value: FairValueConfig,
+    pub price_series: PriceSeriesConfig,
+    pub basis: BasisConfig,
+    pub half_life: HalfLifeConfig,
+    pub projection: ProjectionConfig,
+    pub edge: EdgeConfig,
+    pub scale_in: ScaleInConfig,
+    pub pre_position: PrePositionConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +154,266 @@ pub struct CapitalAllocation {
     pub eth_15m_pct: f64,
     pub sol_15m_pct: f64,
     pub xrp_15m_pct: f64,
+
+    pub dynamic: bool,                // Re-weight by observed opportunity (static split is the prior)
+    pub min_market_pct: f64,          // Floor per market so none is starved (e.g. 0.05)
+    pub score_weight: f64,            // Blend: 0 = static split only, 1 = scores only (e.g. 0.70)
+}
+
+/// Online sizing feedback from post-trade adverse selection (see `telemetry::tca`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillQualityConfig {
+    pub enabled: bool,
+    pub max_adverse_selection: f64,   // Tolerated avg adverse move per share before shrinking (e.g. 0.02)
+    pub window: usize,                // Recent marked fills considered per strategy (e.g. 30)
+    pub min_fills: usize,             // Don't react to fewer marked fills than this (e.g. 8)
+    pub min_size_mult: f64,           // Floor on the size multiplier (e.g. 0.25)
+}
+
+/// Caps taker orders at a share of the book they can reach (see
+/// `strategies::depth_sizing`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthSizingConfig {
+    pub enabled: bool,
+    pub max_depth_pct: f64,           // Max share of the liquidity within the band one eval may take (e.g. 0.25)
+    pub slippage_band: f64,           // Acceptable VWAP drift past the best price (e.g. 0.02)
+}
+
+/// Online MM spread control from realized markouts (see
+/// `strategies::spread_control`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpreadControlConfig {
+    pub enabled: bool,
+    pub window: usize,                // Recent marked MM fills considered per series (e.g. 30)
+    pub min_fills: usize,             // Don't react to fewer marked fills than this (e.g. 8)
+    pub step: f64,                    // Multiplicative spread change per marked fill (e.g. 0.10)
+    pub tolerance: f64,               // Markouts within ± this per share leave the spread alone (e.g. 0.005)
+    pub min_mult: f64,                // Tightest spread multiplier (e.g. 0.75)
+    pub max_mult: f64,                // Widest spread multiplier (e.g. 3.0)
+}
+
+/// Fair-value ensemble (see `signals::fair_value`): standard errors of each
+/// source, in probability units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairValueConfig {
+    pub model_sigma: f64,             // Binance-driven model (e.g. 0.05)
+    pub book_sigma: f64,              // Book mid, before half the spread is added (e.g. 0.02)
+    pub book_half_life_secs: f64,     // Book weight halves per N seconds without an update; 0 = no decay (e.g. 5)
+    pub alt_sigma: f64,               // Second venue's implied probability (e.g. 0.06)
+    pub alt_max_age_secs: f64,        // Ignore second-venue prices older than this (e.g. 5)
+}
+
+/// Which Binance futures price drives fair value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriceSeries {
+    /// Last aggTrade print — fastest, noisiest
+    Last,
+    /// Mark price from `@markPrice`
+    Mark,
+    /// Spot index from `@markPrice` — closest to what settlement oracles track
+    Index,
+}
+
+impl PriceSeries {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "last" => Some(Self::Last),
+            "mark" => Some(Self::Mark),
+            "index" => Some(Self::Index),
+            _ => None,
+        }
+    }
+}
+
+/// Price series per market type, keyed like "btc_5m".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSeriesConfig {
+    pub default: PriceSeries,
+    pub per_market: HashMap<String, PriceSeries>,
+}
+
+impl PriceSeriesConfig {
+    pub fn for_market(&self, asset: Asset, duration: Duration) -> PriceSeries {
+        let key = format!("{}_{}", asset.slug_prefix(), duration.slug_suffix());
+        self.per_market.get(&key).copied().unwrap_or(self.default)
+    }
+}
+
+/// Perp-spot basis signal (see `signals::basis`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasisConfig {
+    pub enabled: bool,
+    pub window_secs: u64,             // Basis history kept per asset (e.g. 300)
+    pub sample_ms: u64,               // At most one basis sample per N ms (e.g. 1000)
+    pub max_price_age_ms: u64,        // Both legs must be this fresh to sample (e.g. 2000)
+    pub z_threshold: f64,             // Std devs from the window mean that count as sharp (e.g. 2.5)
+    pub bias_boost: f64,              // Bias confidence added (or removed) by a sharp move (e.g. 0.10)
+}
+
+/// Lag thresholds and max hold from how long observed mispricings last (see
+/// `signals::half_life`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HalfLifeConfig {
+    pub enabled: bool,
+    pub min_samples: usize,           // Observed opportunities needed before a series/hour's half-life is used (e.g. 20)
+    pub reference_secs: f64,          // Half-life the static lag edge assumes; shorter raises it, longer lowers it (e.g. 5)
+    pub min_edge_mult: f64,           // Floor on the lag edge multiplier (e.g. 0.75)
+    pub max_edge_mult: f64,           // Cap on the lag edge multiplier (e.g. 2.0)
+    pub hold_half_lives: f64,         // Lag positions are cut after this many half-lives (e.g. 3)
+    pub min_hold_secs: f64,           // Never cut sooner than this (e.g. 10)
+    pub max_hold_secs: f64,           // Nor allow longer than this (e.g. 120)
+    pub remaining_half_lives: f64,    // Lag entries need this many half-lives left in the window (e.g. 24)
+    pub min_remaining_secs: f64,      // Floor on that entry cut-off (e.g. 30)
+    pub max_remaining_secs: f64,      // Cap on it (e.g. 180)
+    pub low_vol_mult: f64,            // Hold multiplier in Dead/Low vol; divides remaining needed (e.g. 1.25)
+    pub high_vol_mult: f64,           // Same in High vol (e.g. 0.75)
+    pub extreme_vol_mult: f64,        // Same in Extreme vol (e.g. 0.5)
+}
+
+/// Latency-compensated Binance price for the lag model (see
+/// `signals::projection`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionConfig {
+    pub enabled: bool,
+    pub lookback_ms: u64,             // Prints used to fit the drift (e.g. 500)
+    pub latency_ms: f64,              // Feed + eval + submit delay added to the last print's age (e.g. 150)
+    pub max_horizon_ms: f64,          // Never project further ahead than this (e.g. 1000)
+}
+
+/// Confidence-weighted entry thresholds (see `strategies::edge`): extra net
+/// edge required on top of each strategy's base threshold, in probability units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeConfig {
+    pub enabled: bool,
+    pub uncertainty_mult: f64,        // Extra edge per unit of fair-value uncertainty above the floor (e.g. 0.5)
+    pub uncertainty_floor: f64,       // Uncertainty already priced into base thresholds (e.g. 0.03)
+    pub book_age_per_sec: f64,        // Extra edge per second since the book last updated (e.g. 0.002)
+    pub max_staleness_edge: f64,      // Cap on the book-age term (e.g. 0.03)
+    pub calibration_mult: f64,        // Multiplier on sqrt(observed - expected Brier) (e.g. 0.5)
+    pub calibration_window: usize,    // Resolved prediction samples kept (e.g. 500)
+    pub calibration_min_samples: usize, // Don't react to fewer samples than this (e.g. 50)
+    pub calibration_sample_secs: f64, // Sample each market's fair value at most this often (e.g. 15)
+}
+
+/// Adding to an open position at a better price (see
+/// `PositionManager::apply_scale_in`). Off means buys into a held position
+/// go through unchecked, as before.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleInConfig {
+    pub enabled: bool,
+    pub max_adds: u32,                // Add-on fills per position after the first entry (e.g. 2)
+    pub max_total_cost_usdc: f64,     // Cap on the position's blended cost basis including adds (e.g. 10)
+    pub min_improvement: f64,         // Add-on price must beat the blended entry by this much (e.g. 0.01)
+    pub strategies: Vec<String>,      // Strategy families allowed to scale in (e.g. ["lag"])
+}
+
+/// Minimum time between strategy evaluations of one asset: the strategy
+/// loop re-evaluates faster when its vol regime says prices are moving and
+/// slower when they aren't, and faster again as a window nears resolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalThrottleConfig {
+    pub dead_ms: u64,                 // (e.g. 500)
+    pub low_ms: u64,                  // (e.g. 300)
+    pub medium_ms: u64,               // (e.g. 200)
+    pub high_ms: u64,                 // (e.g. 150)
+    pub extreme_ms: u64,              // (e.g. 100)
+    pub endgame_secs: f64,            // With a window this close to resolving... (e.g. 60)
+    pub endgame_mult: f64,            // ...scale the cooldown by this (e.g. 0.5)
+}
+
+impl EvalThrottleConfig {
+    /// Cooldown for an asset in `regime` whose nearest window resolves in
+    /// `remaining_secs`.
+    pub fn cooldown(&self, regime: VolRegime, remaining_secs: f64) -> std::time::Duration {
+        let ms = match regime {
+            VolRegime::Dead => self.dead_ms,
+            VolRegime::Low => self.low_ms,
+            VolRegime::Medium => self.medium_ms,
+            VolRegime::High => self.high_ms,
+            VolRegime::Extreme => self.extreme_ms,
+        } as f64;
+        let ms = if remaining_secs <= self.endgame_secs { ms * self.endgame_mult } else { ms };
+        std::time::Duration::from_millis(ms as u64)
+    }
+}
+
+/// Opening orders for the next window, queued before it opens (see
+/// `strategies::pre_position`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrePositionConfig {
+    pub enabled: bool,
+    pub lead_secs: f64,               // Queue the next window's opening orders this long before it opens (e.g. 10)
+}
+
+/// ONNX-scored entry filter (see `signals::ml_filter`); needs `--features ml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MlFilterConfig {
+    pub model_path: Option<String>,   // Trained on `export_features` output; None = off
+    pub min_win_prob: f64,            // Drop intents the model scores below this (e.g. 0.5)
+    pub strategy_min_win_prob: HashMap<String, f64>, // Per strategy family ("lag", "mm", ...) overrides
+}
+
+/// Market-level "do not trade" screen applied before any strategy runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketScreenConfig {
+    pub enabled: bool,
+    pub min_top_depth: f64,           // Min shares at best bid + ask on each token (e.g. 5.0)
+    pub max_spread: f64,              // Max bid-ask spread on either token for takers; 0 = off (e.g. 0.10)
+    pub max_book_age_secs: f64,       // Skip books not updated for this long; 0 = off (e.g. 30)
+    pub min_fill_success: f64,        // Min recent taker fill rate for the market type (e.g. 0.20)
+}
+
+/// How markets first seen part-way through their window are traded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinPolicyConfig {
+    pub fresh_window_secs: f64,       // Seen within N seconds of open = fresh join (e.g. 15)
+    pub min_remaining_pct: f64,       // Skip mid-cycle joins with less than X of the window left (e.g. 0.40)
+    pub mid_cycle_size_mult: f64,     // Capital multiplier for mid-cycle joins (e.g. 0.50)
+}
+
+/// How we came to be trading a market window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JoinKind {
+    /// Seen at (or before) open — reference price and book history are trustworthy
+    Fresh,
+    /// Joined mid-window — trade at reduced size
+    MidCycle,
+    /// Joined too late in the window to trade at all
+    Skip,
+}
+
+impl JoinPolicyConfig {
+    /// Classify a join from seconds elapsed at join and the window length.
+    pub fn classify_elapsed(&self, elapsed_at_join: f64, window_secs: f64) -> JoinKind {
+        if elapsed_at_join <= self.fresh_window_secs {
+            return JoinKind::Fresh;
+        }
+        let remaining_pct = (window_secs - elapsed_at_join) / window_secs.max(1.0);
+        if remaining_pct < self.min_remaining_pct {
+            JoinKind::Skip
+        } else {
+            JoinKind::MidCycle
+        }
+    }
+
+    /// Classify a market by when this process first saw it. Markets with no
+    /// join time (built locally rather than discovered) count as fresh.
+    pub fn classify(&self, market: &Market) -> JoinKind {
+        let Some(joined_at) = market.joined_at else {
+            return JoinKind::Fresh;
+        };
+        let elapsed = (joined_at - market.open_time).num_milliseconds() as f64 / 1000.0;
+        self.classify_elapsed(elapsed, market.duration.seconds() as f64)
+    }
+
+    /// Capital multiplier for a join kind.
+    pub fn size_mult(&self, kind: JoinKind) -> f64 {
+        match kind {
+            JoinKind::Fresh => 1.0,
+            JoinKind::MidCycle => self.mid_cycle_size_mult,
+            JoinKind::Skip => 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +424,225 @@ pub struct RiskConfig {
     pub loss_streak_size_mult: f64,   // Size multiplier during streak (e.g. 0.50)
     pub max_price_deviation: f64,     // Reject orders deviating >X from midpoint
     pub pause_duration_secs: u64,     // Pause duration after drawdown (e.g. 3600)
+    pub orphan_sweep_secs: u64,       // Cancel open orders we don't track every N seconds (0 = off)
+    pub orphan_min_age_secs: u64,     // Leave orders younger than this alone (in-flight submits)
+    pub net_resting_orders: bool,     // Replace/top up our resting orders instead of stacking new ones
+    pub requote_ticks: u32,           // Leave a resting quote unless its target price moves by more than N ticks; 0 = exact
+    pub unwind_max_slippage: f64,     // Flatten a matched leg of an unwound trade no further than this from its fill (e.g. 0.05)
+    pub intent_dedup_ms: u64,         // Suppress repeats of an intent (same token/side/cent/strategy) for N ms; 0 = off
+    pub loop_max_round_trips: usize,  // Round trips per market and strategy family allowed per window; 0 = off (e.g. 3)
+    pub loop_window_secs: u64,        // Window for counting round trips (e.g. 300)
+    pub loop_cooloff_secs: u64,       // Entries blocked for this long once the limit is exceeded (e.g. 600)
+    pub loop_exempt: Vec<String>,     // Strategy families that round-trip by design (e.g. ["mm"])
+    pub market_max_loss_usdc: f64,    // Abandon an (asset, duration) series after losing this much in it; 0 = off
+    pub market_stop_mins: u64,        // How long an abandoned series sits out; 0 = rest of the session
+    pub settlement_hold_secs: u64,    // Sell proceeds aren't spendable until the trade confirms on-chain, or for at most N seconds; 0 = at once (e.g. 60)
+
+    pub compounding: CompoundingConfig,
+    pub buckets: CapitalBucketConfig,
+    pub resolution_guard: ResolutionGuardConfig,
+    pub exit_ladder: ExitLadderConfig,
+    pub safe_mode: SafeModeConfig,
+    pub canary: CanaryConfig,
+    pub approval: ApprovalConfig,
+    pub carry: CarryConfig,
+}
+
+/// How aggressively a resting exit is priced. Ordered: a position's exit
+/// only ever escalates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitStyle {
+    Tp,
+    Sl,
+    Force,
+}
+
+impl ExitStyle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExitStyle::Tp => "tp",
+            ExitStyle::Sl => "sl",
+            ExitStyle::Force => "force",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "tp" => Some(ExitStyle::Tp),
+            "sl" => Some(ExitStyle::Sl),
+            "force" => Some(ExitStyle::Force),
+            _ => None,
+        }
+    }
+}
+
+/// Where an exit order is priced, before rounding down to the cent and
+/// clamping to [0.01, 0.99].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitPrice {
+    /// Entry price × mult
+    Entry(f64),
+    /// Current best bid × mult
+    Bid(f64),
+    Fixed(f64),
+}
+
+/// One rung of the exit ladder: applies when every condition set holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitRung {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_below: Option<f64>, // Seconds left in the window < this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hold_at_least: Option<f64>,   // Seconds held >= this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pct_at_most: Option<f64>,     // Bid vs entry change <= this (e.g. -0.20)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pct_above: Option<f64>,       // Bid vs entry change > this (e.g. 0.02)
+    pub style: ExitStyle,
+    pub price: ExitPrice,
+    pub reason: ExitReason,
+}
+
+impl ExitRung {
+    pub fn is_unconditional(&self) -> bool {
+        self.remaining_below.is_none()
+            && self.hold_at_least.is_none()
+            && self.pct_at_most.is_none()
+            && self.pct_above.is_none()
+    }
+}
+
+/// Exit escalation ladder (see `risk::exit_manager`): the first matching
+/// rung decides a position's exit, and the last rung must be unconditional.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitLadderConfig {
+    pub rungs: Vec<ExitRung>,
+}
+
+/// Near-close guard against the book being "right" for a reason we can't see:
+/// the market resolves on the oracle price, not Binance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionGuardConfig {
+    pub enabled: bool,
+    pub window_secs: f64,             // Guard entries in the last N seconds (e.g. 90)
+    pub max_divergence: f64,          // Max |fair(Binance) - fair(oracle)| before acting (e.g. 0.10)
+    pub max_oracle_age_secs: f64,     // Ignore oracle prices older than this (e.g. 15)
+    pub invert: bool,                 // Flip blocked entries to the oracle-favoured side instead of dropping them
+}
+
+/// Reduced-risk start after the previous session ended abnormally (crash or
+/// kill switch), as recorded in the session state file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeModeConfig {
+    pub enabled: bool,
+    pub state_path: String,           // Where the session state is kept across restarts
+    pub size_mult: f64,               // Order size multiplier while in safe mode (e.g. 0.5)
+    pub max_markets: usize,           // Trade only the top N markets by allocator priority (e.g. 2)
+}
+
+/// Probation for newly enabled strategy families (see `risk::canary`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryConfig {
+    pub strategies: Vec<String>,      // Strategy families on probation (e.g. ["momentum"]); empty = off
+    pub state_path: String,           // Where probation progress is kept across restarts
+    pub max_order_usdc: f64,          // Entries are shrunk to this cost while on probation (e.g. 5.0)
+    pub loss_budget_usdc: f64,        // Entries stop once probation losses reach this (e.g. 25.0)
+    pub min_hours: f64,               // Probation lasts at least this long (e.g. 24)
+    pub min_trades: u64,              // And at least this many closed trades (e.g. 30)
+    pub min_win_rate: f64,            // Graduation needs this win rate (e.g. 0.50)
+    pub min_pnl_usdc: f64,            // And this realized P&L (e.g. 0.0)
+}
+
+/// Human-in-the-loop confirmation for large entries (see `risk::approval`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalConfig {
+    pub min_notional_usdc: f64,       // Entries costing at least this wait for an operator; 0 = off (e.g. 25.0)
+    pub timeout_secs: u64,            // Held entries not approved within this are dropped (e.g. 60)
+}
+
+/// Holding resolved winners until they're redeemed on-chain (see `risk::carry`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryConfig {
+    pub enabled: bool,
+    pub state_path: String,           // Where unredeemed winners are kept across restarts
+    pub retry_secs: u64,              // First redeem this long after resolution, then retry every N seconds (e.g. 60)
+    pub max_attempts: u32,            // Give up redeeming automatically after this many failures (e.g. 30)
+}
+
+/// Per-strategy virtual capital buckets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapitalBucketConfig {
+    pub enabled: bool,
+    pub straddle_pct: f64,
+    pub arb_pct: f64,
+    pub lag_pct: f64,
+    pub mm_pct: f64,
+    pub momentum_pct: f64,
+    pub late_gamma_pct: f64,
+    pub open_sniper_pct: f64,
+    pub rebalance_interval_secs: u64, // 0 = never rebalance
+    pub rebalance_min_drift: f64,     // Rebalance only if a bucket drifts >X from target (e.g. 0.20)
+}
+
+impl CapitalBucketConfig {
+    /// Target capital shares by bucket name.
+    pub fn shares(&self) -> [(&'static str, f64); 7] {
+        [
+            ("straddle", self.straddle_pct),
+            ("arb", self.arb_pct),
+            ("lag", self.lag_pct),
+            ("mm", self.mm_pct),
+            ("momentum", self.momentum_pct),
+            ("late_gamma", self.late_gamma_pct),
+            ("open_sniper", self.open_sniper_pct),
+        ]
+    }
+}
+
+/// How profits are treated as capital grows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompoundingMode {
+    /// Reinvest everything — trading capital grows with profits
+    Compound,
+    /// Trade with capital up to the watermark, sweep profits above it
+    Sweep,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompoundingConfig {
+    pub mode: CompoundingMode,
+    pub watermark: f64,               // Capital kept for trading; 0 = starting capital
+    pub sweep_fraction: f64,          // Share of profit above watermark to sweep (e.g. 1.0)
+    pub min_sweep_usdc: f64,          // Don't send transfers smaller than this (e.g. 5.0)
+    pub sweep_address: Option<String>, // Cold wallet receiving swept USDC
+    pub sweep_interval_secs: u64,     // How often to check for a sweep (e.g. 3600)
+}
+
+/// Simulation settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimConfig {
+    /// Root seed for every RNG in a simulated run; None = fresh seed per run
+    pub seed: Option<u64>,
+    /// Book levels our simulated fills take refill with this half-life; 0 = no lasting impact
+    pub impact_half_life_secs: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self { seed: None, impact_half_life_secs: 10.0 }
+    }
+}
+
+impl SimConfig {
+    /// Root RNG for this run. Fork per component: `root.fork("paper_fills")`.
+    pub fn root_rng(&self) -> SimRng {
+        match self.seed {
+            Some(seed) => SimRng::new(seed),
+            None => SimRng::from_entropy(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +651,43 @@ pub struct TelemetryConfig {
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
     pub discord_webhook_url: Option<String>,
+    pub slack_webhook_url: Option<String>,
+    /// Minimum severity delivered to each sink
+    pub telegram_min_severity: AlertSeverity,
+    pub discord_min_severity: AlertSeverity,
+    pub slack_min_severity: AlertSeverity,
+    /// Retries per sink for transient delivery failures (5xx, 429, network)
+    pub alert_max_retries: u32,
+    /// First retry delay; doubles on each further retry
+    pub alert_retry_base_ms: u64,
+    /// Identical alerts within this window are sent once
+    pub alert_dedup_window_secs: u64,
+    /// Per-severity alerts per minute (0 = unlimited)
+    pub alert_info_per_min: u32,
+    pub alert_warning_per_min: u32,
+    pub alert_critical_per_min: u32,
+    /// Periodic "still alive" alert interval (0 = off)
+    pub heartbeat_alert_secs: u64,
+    /// Dead-man's-switch URL pinged every `healthcheck_interval_secs`
+    pub healthcheck_url: Option<String>,
+    pub healthcheck_interval_secs: u64,
+    /// Bind address for the read-only dashboard HTTP API (None = off)
+    pub api_addr: Option<String>,
+    /// Engine state (`telemetry::state::EngineState`) written here every 30s and at shutdown (None = off)
+    pub state_snapshot_path: Option<String>,
+    /// Market-data recording for `export_features` (None = off)
+    pub recording_path: Option<String>,
+    /// Bind address for the gRPC event stream (None = off)
+    pub grpc_addr: Option<String>,
+    /// Append-only JSONL trade journal (None = off)
+    pub journal_path: Option<String>,
+    /// Evaluate strategies but never submit; journal what would have been traded
+    pub observer_mode: bool,
+    /// An observed opportunity not seen again for this long has closed
+    pub observer_gap_ms: u64,
+    /// Also evaluate a shadow strategy config (`SHADOW_*` overrides) and
+    /// compare its hypothetical P&L with the live one's
+    pub shadow_ab: bool,
     pub alert_on_trade: bool,
     pub alert_on_error: bool,
     pub alert_on_drawdown: bool,
@@ -95,8 +701,11 @@ impl Default for StrategyConfig {
             lag_exploit_enabled: true,
             market_making_enabled: true,
             momentum_enabled: true,
+            late_gamma_enabled: false,
+            open_sniper_enabled: false,
             straddle_max_combined: 0.97,
             straddle_max_capital_pct: 0.25,
+            straddle_min_leg_pct: 0.25,
             bias_min_confidence: 0.35,
             bias_max_capital_pct: 0.15,
             arb_min_edge: 0.02,
@@ -104,156 +713,1337 @@ impl Default for StrategyConfig {
             lag_min_edge: 0.03,
             lag_kelly_fraction: 0.25,
             mm_base_size_pct: 0.10,
+            mm_levels: 1,
+            mm_level_spacing: 0.01,
+            mm_level_size_mult: 1.5,
             momentum_min_signal: 0.003,
             momentum_min_divergence: 0.02,
+            late_gamma_window_secs: 90.0,
+            late_gamma_min_edge: 0.15,
+            late_gamma_order_usdc: 1.0,
+            late_gamma_market_usdc: 2.0,
+            open_sniper_window_secs: 5.0,
+            open_sniper_min_edge: 0.08,
+            open_sniper_order_usdc: 5.0,
+            open_sniper_max_book_age_ms: 250.0,
             lockout_seconds_5m: 30.0,
             lockout_seconds_15m: 30.0,
+            eval_budget_ms: 5.0,
+            eval_overrun_bench_secs: 30,
+            eval_overrun_streak: 3,
+            eval_throttle: EvalThrottleConfig::default(),
+            max_market_notional_per_eval: 0.0,
+            vol_calibration_path: Some("vol_calibration.json".into()),
             capital_allocation: CapitalAllocation::default(),
+            join_policy: JoinPolicyConfig::default(),
+            fill_quality: FillQualityConfig::default(),
+            depth_sizing: DepthSizingConfig::default(),
+            spread_control: SpreadControlConfig::default(),
+            screen: MarketScreenConfig::default(),
+            ml_filter: MlFilterConfig::default(),
+            fair_value: FairValueConfig::default(),
+            price_series: PriceSeriesConfig::default(),
+            basis: BasisConfig::default(),
+            half_life: HalfLifeConfig::default(),
+            projection: ProjectionConfig::default(),
+            edge: EdgeConfig::default(),
+            scale_in: ScaleInConfig::default(),
+            pre_position: PrePositionConfig::default(),
         }
     }
 }
 
-impl Default for CapitalAllocation {
+impl Default for EvalThrottleConfig {
     fn default() -> Self {
         Self {
-            btc_5m_pct: 0.40,
-            btc_15m_pct: 0.20,
-            eth_15m_pct: 0.20,
-            sol_15m_pct: 0.10,
-            xrp_15m_pct: 0.10,
+            dead_ms: 500,
+            low_ms: 300,
+            medium_ms: 200,
+            high_ms: 150,
+            extreme_ms: 100,
+            endgame_secs: 60.0,
+            endgame_mult: 0.5,
         }
     }
 }
 
-impl Default for RiskConfig {
+impl Default for PrePositionConfig {
+    fn default() -> Self {
+        Self { enabled: false, lead_secs: 10.0 }
+    }
+}
+
+impl Default for ScaleInConfig {
     fn default() -> Self {
         Self {
-            max_exposure_pct: 0.50,
-            max_daily_loss_pct: 0.10,
-            loss_streak_threshold: 5,
-            loss_streak_size_mult: 0.50,
-            max_price_deviation: 0.15,
-            pause_duration_secs: 3600,
+            enabled: false,
+            max_adds: 2,
+            max_total_cost_usdc: 10.0,
+            min_improvement: 0.01,
+            strategies: vec!["lag".into()],
         }
     }
 }
 
-impl Default for Config {
+impl Default for EdgeConfig {
     fn default() -> Self {
         Self {
-            polymarket: PolymarketConfig {
-                clob_host: "https://clob.polymarket.com".into(),
-                ws_host: "wss://ws-subscriptions-clob.polymarket.com/ws/market".into(),
-                gamma_api_host: "https://gamma-api.polymarket.com".into(),
-                chain_id: 137,
-                private_key: String::new(),
-                funder_address: None,
-                signature_type: 0,
-            },
-            binance: BinanceConfig {
-                ws_url: "wss://fstream.binance.com".into(),
-                rest_url: "https://fapi.binance.com".into(),
-                streams: vec![
-                    "btcusdt@aggTrade".into(),
-                    "btcusdt@kline_1m".into(),
-                    "ethusdt@aggTrade".into(),
-                    "ethusdt@kline_1m".into(),
-                    "solusdt@aggTrade".into(),
-                    "solusdt@kline_1m".into(),
-                    "xrpusdt@aggTrade".into(),
-                    "xrpusdt@kline_1m".into(),
-                    "btcusdt@forceOrder".into(),
-                    "ethusdt@forceOrder".into(),
-                    "solusdt@forceOrder".into(),
-                    "xrpusdt@forceOrder".into(),
-                ],
-            },
-            strategy: StrategyConfig::default(),
-            risk: RiskConfig::default(),
-            telemetry: TelemetryConfig {
-                log_level: "info".into(),
-                telegram_bot_token: None,
-                telegram_chat_id: None,
-                discord_webhook_url: None,
-                alert_on_trade: true,
-                alert_on_error: true,
-                alert_on_drawdown: true,
-            },
+            enabled: true,
+            uncertainty_mult: 0.5,
+            uncertainty_floor: 0.03,
+            book_age_per_sec: 0.002,
+            max_staleness_edge: 0.03,
+            calibration_mult: 0.5,
+            calibration_window: 500,
+            calibration_min_samples: 50,
+            calibration_sample_secs: 15.0,
         }
     }
 }
 
-impl Config {
-    /// Load configuration from environment variables (.env file) with defaults.
-    ///
-    /// Required env vars:
-    ///   POLYMARKET_PRIVATE_KEY — hex private key for signing
-    ///   STARTING_CAPITAL — initial USDC balance (default: 5)
-    ///
-    /// Optional env vars:
-    ///   POLYMARKET_FUNDER_ADDRESS — proxy wallet address
-    ///   POLYMARKET_SIGNATURE_TYPE — 0=EOA, 1=PolyProxy (default: 0)
-    ///   TELEGRAM_BOT_TOKEN, TELEGRAM_CHAT_ID — for alerts
-    ///   DISCORD_WEBHOOK_URL — for alerts
-    ///   RUST_LOG — log level (default: info)
-    ///   DRY_RUN — set to "true" to use random key (no real orders)
-    pub fn load_or_default() -> Self {
-        // Load .env file if present
-        let _ = dotenv::dotenv();
+impl Default for FairValueConfig {
+    fn default() -> Self {
+        Self {
+            model_sigma: 0.05,
+            book_sigma: 0.02,
+            book_half_life_secs: 5.0,
+            alt_sigma: 0.06,
+            alt_max_age_secs: 5.0,
+        }
+    }
+}
 
-        let mut config = Self::default();
+impl Default for MarketScreenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_top_depth: 5.0,
+            max_spread: 0.10,
+            max_book_age_secs: 30.0,
+            min_fill_success: 0.20,
+        }
+    }
+}
 
-        // Polymarket credentials
-        if let Ok(key) = std::env::var("POLYMARKET_PRIVATE_KEY") {
-            if key != "your_private_key_here" {
-                config.polymarket.private_key = key;
-            }
+impl Default for PriceSeriesConfig {
+    fn default() -> Self {
+        Self {
+            default: PriceSeries::Last,
+            per_market: HashMap::new(),
         }
+    }
+}
 
-        if let Ok(addr) = std::env::var("POLYMARKET_FUNDER_ADDRESS") {
-            if !addr.is_empty() && addr != "optional_proxy_address" {
-                config.polymarket.funder_address = Some(addr);
-            }
+impl Default for HalfLifeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_samples: 20,
+            reference_secs: 5.0,
+            min_edge_mult: 0.75,
+            max_edge_mult: 2.0,
+            hold_half_lives: 3.0,
+            min_hold_secs: 10.0,
+            max_hold_secs: 120.0,
+            remaining_half_lives: 24.0,
+            min_remaining_secs: 30.0,
+            max_remaining_secs: 180.0,
+            low_vol_mult: 1.25,
+            high_vol_mult: 0.75,
+            extreme_vol_mult: 0.5,
         }
+    }
+}
 
-        if let Ok(sig_type) = std::env::var("POLYMARKET_SIGNATURE_TYPE") {
-            config.polymarket.signature_type = sig_type.parse().unwrap_or(0);
+impl Default for BasisConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 300,
+            sample_ms: 1000,
+            max_price_age_ms: 2000,
+            z_threshold: 2.5,
+            bias_boost: 0.10,
         }
+    }
+}
 
-        // Starting capital
-        if let Ok(capital) = std::env::var("STARTING_CAPITAL") {
-            if let Ok(_val) = capital.parse::<f64>() {
-                // Stored in config for PositionManager initialization
-                // (passed through main.rs)
-            }
+impl Default for ProjectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lookback_ms: 500,
+            latency_ms: 150.0,
+            max_horizon_ms: 1000.0,
         }
+    }
+}
 
-        // Telegram alerts
-        if let Ok(token) = std::env::var("TELEGRAM_BOT_TOKEN") {
+impl Default for DepthSizingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_depth_pct: 0.25,
+            slippage_band: 0.02,
+        }
+    }
+}
+
+impl Default for SpreadControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window: 30,
+            min_fills: 8,
+            step: 0.10,
+            tolerance: 0.005,
+            min_mult: 0.75,
+            max_mult: 3.0,
+        }
+    }
+}
+
+impl Default for FillQualityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_adverse_selection: 0.02,
+            window: 30,
+            min_fills: 8,
+            min_size_mult: 0.25,
+        }
+    }
+}
+
+impl Default for JoinPolicyConfig {
+    fn default() -> Self {
+        Self {
+            fresh_window_secs: 15.0,
+            min_remaining_pct: 0.40,
+            mid_cycle_size_mult: 0.50,
+        }
+    }
+}
+
+impl Default for CapitalAllocation {
+    fn default() -> Self {
+        Self {
+            btc_5m_pct: 0.40,
+            btc_15m_pct: 0.20,
+            eth_15m_pct: 0.20,
+            sol_15m_pct: 0.10,
+            xrp_15m_pct: 0.10,
+            dynamic: true,
+            min_market_pct: 0.05,
+            score_weight: 0.70,
+        }
+    }
+}
+
+impl CapitalAllocation {
+    /// Static capital share for a market type.
+    pub fn for_market(&self, asset: Asset, duration: Duration) -> f64 {
+        match (asset, duration) {
+            (Asset::BTC, Duration::FiveMin) => self.btc_5m_pct,
+            (Asset::BTC, Duration::FifteenMin) => self.btc_15m_pct,
+            (Asset::ETH, Duration::FifteenMin) => self.eth_15m_pct,
+            (Asset::SOL, Duration::FifteenMin) => self.sol_15m_pct,
+            (Asset::XRP, Duration::FifteenMin) => self.xrp_15m_pct,
+            // 5-min markets for non-BTC assets (future expansion)
+            _ => 0.05,
+        }
+    }
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            max_exposure_pct: 0.50,
+            max_daily_loss_pct: 0.10,
+            loss_streak_threshold: 5,
+            loss_streak_size_mult: 0.50,
+            max_price_deviation: 0.15,
+            pause_duration_secs: 3600,
+            orphan_sweep_secs: 60,
+            orphan_min_age_secs: 120,
+            net_resting_orders: true,
+            requote_ticks: 1,
+            unwind_max_slippage: 0.05,
+            intent_dedup_ms: 2000,
+            loop_max_round_trips: 3,
+            loop_window_secs: 300,
+            loop_cooloff_secs: 600,
+            loop_exempt: vec!["mm".into()],
+            market_max_loss_usdc: 0.0,
+            market_stop_mins: 0,
+            settlement_hold_secs: 0,
+            compounding: CompoundingConfig::default(),
+            buckets: CapitalBucketConfig::default(),
+            resolution_guard: ResolutionGuardConfig::default(),
+            exit_ladder: ExitLadderConfig::default(),
+            safe_mode: SafeModeConfig::default(),
+            canary: CanaryConfig::default(),
+            approval: ApprovalConfig::default(),
+            carry: CarryConfig::default(),
+        }
+    }
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        Self {
+            strategies: Vec::new(),
+            state_path: "canary_state.json".into(),
+            max_order_usdc: 5.0,
+            loss_budget_usdc: 25.0,
+            min_hours: 24.0,
+            min_trades: 30,
+            min_win_rate: 0.50,
+            min_pnl_usdc: 0.0,
+        }
+    }
+}
+
+impl Default for ExitLadderConfig {
+    /// Dump anything in the last minute, and anything held 2 minutes; sell
+    /// into the bid on a 20% loss or, in the last 90s, to lock in a 2% gain;
+    /// otherwise rest at entry + 10%.
+    fn default() -> Self {
+        let rung = |style, price, reason| ExitRung {
+            remaining_below: None,
+            hold_at_least: None,
+            pct_at_most: None,
+            pct_above: None,
+            style,
+            price,
+            reason,
+        };
+        Self {
+            rungs: vec![
+                ExitRung { remaining_below: Some(60.0), ..rung(ExitStyle::Force, ExitPrice::Fixed(0.01), ExitReason::Force) },
+                ExitRung { hold_at_least: Some(120.0), ..rung(ExitStyle::Force, ExitPrice::Fixed(0.01), ExitReason::Time) },
+                ExitRung { pct_at_most: Some(-0.20), ..rung(ExitStyle::Sl, ExitPrice::Bid(0.5), ExitReason::StopLoss) },
+                ExitRung {
+                    remaining_below: Some(90.0),
+                    pct_above: Some(0.02),
+                    ..rung(ExitStyle::Sl, ExitPrice::Bid(0.5), ExitReason::PreResolve)
+                },
+                rung(ExitStyle::Tp, ExitPrice::Entry(1.10), ExitReason::TakeProfit),
+            ],
+        }
+    }
+}
+
+impl Default for SafeModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            state_path: "session_state.json".into(),
+            size_mult: 0.5,
+            max_markets: 2,
+        }
+    }
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            min_notional_usdc: 0.0,
+            timeout_secs: 60,
+        }
+    }
+}
+
+impl Default for CarryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            state_path: "carry_state.json".into(),
+            retry_secs: 60,
+            max_attempts: 30,
+        }
+    }
+}
+
+impl Default for ResolutionGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 90.0,
+            max_divergence: 0.10,
+            max_oracle_age_secs: 15.0,
+            invert: false,
+        }
+    }
+}
+
+impl Default for CapitalBucketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            straddle_pct: 0.25,
+            arb_pct: 0.25,
+            lag_pct: 0.20,
+            mm_pct: 0.15,
+            momentum_pct: 0.05,
+            late_gamma_pct: 0.05,
+            open_sniper_pct: 0.05,
+            rebalance_interval_secs: 0,
+            rebalance_min_drift: 0.20,
+        }
+    }
+}
+
+impl Default for CompoundingConfig {
+    fn default() -> Self {
+        Self {
+            mode: CompoundingMode::Compound,
+            watermark: 0.0,
+            sweep_fraction: 1.0,
+            min_sweep_usdc: 5.0,
+            sweep_address: None,
+            sweep_interval_secs: 3600,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            polymarket: PolymarketConfig {
+                clob_host: "https://clob.polymarket.com".into(),
+                ws_host: "wss://ws-subscriptions-clob.polymarket.com/ws/market".into(),
+                gamma_api_host: "https://gamma-api.polymarket.com".into(),
+                data_api_host: "https://data-api.polymarket.com".into(),
+                rtds_host: "wss://ws-live-data.polymarket.com".into(),
+                polygon_rpc_url: "https://polygon-rpc.com".into(),
+                chain_id: 137,
+                private_key: String::new(),
+                funder_address: None,
+                signature_type: 0,
+                retry: RestRetryConfig::default(),
+                prewarm_secs: 20,
+                token_balance_secs: 30,
+                matic_price_url: "https://api.binance.com/api/v3/ticker/price?symbol=POLUSDT".into(),
+                matic_usd: 0.25,
+            },
+            binance: BinanceConfig {
+                ws_url: "wss://fstream.binance.com".into(),
+                rest_url: "https://fapi.binance.com".into(),
+                streams: vec![
+                    "btcusdt@aggTrade".into(),
+                    "btcusdt@kline_1m".into(),
+                    "ethusdt@aggTrade".into(),
+                    "ethusdt@kline_1m".into(),
+                    "solusdt@aggTrade".into(),
+                    "solusdt@kline_1m".into(),
+                    "xrpusdt@aggTrade".into(),
+                    "xrpusdt@kline_1m".into(),
+                    "btcusdt@forceOrder".into(),
+                    "ethusdt@forceOrder".into(),
+                    "solusdt@forceOrder".into(),
+                    "xrpusdt@forceOrder".into(),
+                    "btcusdt@markPrice@1s".into(),
+                    "ethusdt@markPrice@1s".into(),
+                    "solusdt@markPrice@1s".into(),
+                    "xrpusdt@markPrice@1s".into(),
+                ],
+                spot_ws_url: "wss://stream.binance.com:9443".into(),
+                spot_streams: vec![
+                    "btcusdt@aggTrade".into(),
+                    "ethusdt@aggTrade".into(),
+                    "solusdt@aggTrade".into(),
+                    "xrpusdt@aggTrade".into(),
+                ],
+            },
+            liquidations: LiquidationFeedConfig {
+                bybit_enabled: true,
+                bybit_ws_url: "wss://stream.bybit.com/v5/public/linear".into(),
+                okx_enabled: true,
+                okx_ws_url: "wss://ws.okx.com:8443/ws/v5/public".into(),
+            },
+            strategy: StrategyConfig::default(),
+            risk: RiskConfig::default(),
+            telemetry: TelemetryConfig {
+                log_level: "info".into(),
+                telegram_bot_token: None,
+                telegram_chat_id: None,
+                discord_webhook_url: None,
+                slack_webhook_url: None,
+                telegram_min_severity: AlertSeverity::Info,
+                discord_min_severity: AlertSeverity::Info,
+                slack_min_severity: AlertSeverity::Info,
+                alert_max_retries: 3,
+                alert_retry_base_ms: 500,
+                alert_dedup_window_secs: 300,
+                alert_info_per_min: 10,
+                alert_warning_per_min: 20,
+                alert_critical_per_min: 0,
+                heartbeat_alert_secs: 0,
+                healthcheck_url: None,
+                healthcheck_interval_secs: 60,
+                api_addr: None,
+                state_snapshot_path: None,
+                grpc_addr: None,
+                recording_path: None,
+                journal_path: Some("trade_journal.jsonl".into()),
+                observer_mode: false,
+                observer_gap_ms: 1000,
+                shadow_ab: false,
+                alert_on_trade: true,
+                alert_on_error: true,
+                alert_on_drawdown: true,
+            },
+            sim: SimConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration from environment variables (.env file) with defaults.
+    ///
+    /// Required env vars:
+    ///   POLYMARKET_PRIVATE_KEY — hex private key for signing
+    ///   STARTING_CAPITAL — initial USDC balance (default: 5)
+    ///
+    /// Optional env vars:
+    ///   POLYMARKET_FUNDER_ADDRESS — proxy wallet address
+    ///   CLOB_HOST, GAMMA_API_HOST, BINANCE_WS_URL, BINANCE_REST_URL, POLYGON_RPC_URL — endpoint overrides
+    ///     (e.g. regional endpoints picked by `sattebaaz probe --save`)
+    ///   POLYMARKET_SIGNATURE_TYPE — 0=EOA, 1=PolyProxy (default: 0)
+    ///   BYBIT_LIQUIDATIONS, OKX_LIQUIDATIONS — merge that venue's liquidations into the cascade signal (default: true)
+    ///   TELEGRAM_BOT_TOKEN, TELEGRAM_CHAT_ID — for alerts
+    ///   DISCORD_WEBHOOK_URL — for alerts
+    ///   SLACK_WEBHOOK_URL — Slack incoming webhook for alerts
+    ///   TELEGRAM_MIN_SEVERITY, DISCORD_MIN_SEVERITY, SLACK_MIN_SEVERITY — info | warning | critical (default: info)
+    ///   ALERT_MAX_RETRIES — delivery retries per sink (default: 3)
+    ///   CLOB_MAX_RETRIES — retries per CLOB REST call on transient failures, 0 = off (default: 3)
+    ///   CLOB_RETRY_BASE_MS — first CLOB retry backoff, doubled per retry with jitter (default: 200)
+    ///   CLOB_RETRY_BUDGET_PER_MIN — CLOB retries allowed per minute across all calls (default: 30)
+    ///   CLOB_MAX_IN_FLIGHT — concurrent CLOB requests; when full, cancels and exits are served before entries (default: 8)
+    ///   CLOB_PREWARM_SECS — keep the order connection warm with a ping every N seconds, 0 = off (default: 20)
+    ///   TOKEN_BALANCE_SECS — poll on-chain token balances to reconcile inventory and cap exits every N seconds, 0 = off (default: 30)
+    ///   MATIC_PRICE_URL — MATIC/USD ticker used to price gas in P&L, "off" for the fixed price (default: Binance POLUSDT)
+    ///   MATIC_USD — MATIC/USD price until the ticker is read (default: 0.25)
+    ///   ALERT_DEDUP_WINDOW_SECS — suppress identical alerts within window (default: 300)
+    ///   ALERT_INFO_PER_MIN, ALERT_WARNING_PER_MIN, ALERT_CRITICAL_PER_MIN — rate limits, 0 = unlimited (default: 10, 20, 0)
+    ///   HEARTBEAT_ALERT_SECS — periodic "alive" alert interval, 0 = off (default: 0)
+    ///   HEALTHCHECK_URL — dead-man's-switch ping URL, e.g. healthchecks.io (default: none)
+    ///   HEALTHCHECK_INTERVAL_SECS — ping interval (default: 60)
+    ///   STRATEGY_BUDGET_MS — per-strategy evaluation time budget (default: 5.0)
+    ///   STRATEGY_OVERRUN_BENCH_SECS — how long a benched strategy sits out (default: 30)
+    ///   STRATEGY_OVERRUN_STREAK — overruns in a row that bench a strategy (default: 3)
+    ///   EVAL_THROTTLE_MS_<REGIME> — min ms between evaluations of an asset in a vol regime, e.g.
+    ///     EVAL_THROTTLE_MS_EXTREME=100 (default: dead 500, low 300, medium 200, high 150, extreme 100)
+    ///   EVAL_THROTTLE_ENDGAME_SECS — within this long of a window resolving... (default: 60)
+    ///   EVAL_THROTTLE_ENDGAME_MULT — ...the throttle is scaled by this (default: 0.5)
+    ///   MAX_MARKET_NOTIONAL_PER_EVAL — combined intent notional cap per market per evaluation, 0 = off (default: 0)
+    ///   ORPHAN_SWEEP_SECS — cancel open orders nothing tracks every N seconds, 0 = off (default: 60)
+    ///   ORPHAN_MIN_AGE_SECS — minimum age before an untracked order is swept (default: 120)
+    ///   NET_RESTING_ORDERS — net new intents against our resting orders per strategy, token and side (default: true)
+    ///   REQUOTE_TICKS — keep a resting quote unless its target moves by more than N ticks (default: 1)
+    ///   UNWIND_MAX_SLIPPAGE — max distance from its fill price a matched leg is flattened at (default: 0.05)
+    ///   STRADDLE_MIN_LEG_PCT — floor on a straddle leg shrunk for existing inventory (default: 0.25)
+    ///   MM_LEVELS — market-making quotes per side (default: 1)
+    ///   MM_LEVEL_SPACING — extra distance from fair value per quote level (default: 0.01)
+    ///   MM_LEVEL_SIZE_MULT — each level's size relative to the one inside it (default: 1.5)
+    ///   INTENT_DEDUP_MS — drop repeats of a submitted intent within this many ms, 0 = off (default: 2000)
+    ///   LOOP_MAX_ROUND_TRIPS — round trips per market and strategy family before a cooloff, 0 = off (default: 3)
+    ///   LOOP_WINDOW_SECS, LOOP_COOLOFF_SECS — round-trip counting window and cooloff length (default: 300, 600)
+    ///   MARKET_MAX_LOSS_USDC — abandon a market series after losing this much in it, 0 = off (default: 0)
+    ///   MARKET_STOP_MINS — how long an abandoned series sits out, 0 = rest of the session (default: 0)
+    ///   SETTLEMENT_HOLD_SECS — hold sell proceeds as settling until the trade confirms on-chain, at most
+    ///     this long, 0 = spendable at once (default: 0)
+    ///   CANARY_STRATEGIES — strategy families on probation, comma-separated (e.g. momentum,lag; default: none)
+    ///   CANARY_MAX_ORDER_USDC, CANARY_LOSS_BUDGET_USDC — probation entry cap and loss budget (default: 5, 25)
+    ///   CANARY_MIN_HOURS, CANARY_MIN_TRADES — probation length before graduation is considered (default: 24, 30)
+    ///   CANARY_MIN_WIN_RATE, CANARY_MIN_PNL_USDC — realized stats needed to graduate (default: 0.50, 0)
+    ///   CANARY_STATE_PATH — probation progress file (default: canary_state.json)
+    ///   APPROVAL_MIN_USDC — hold entries costing at least this for operator approval via
+    ///     Telegram buttons or the dashboard API (default: 0 = off)
+    ///   APPROVAL_TIMEOUT_SECS — held entries not approved within this are dropped (default: 60)
+    ///   CARRY — hold winning tokens left at resolution and redeem them on-chain, counting them as
+    ///     pending redemption until paid instead of as cash (default: false)
+    ///   CARRY_RETRY_SECS, CARRY_MAX_ATTEMPTS — redeem delay/retry interval and attempts before giving up (default: 60, 30)
+    ///   CARRY_STATE_PATH — unredeemed winners file (default: carry_state.json)
+    ///   TRADE_JOURNAL — trade journal file, "off" to disable (default: trade_journal.jsonl)
+    ///   OBSERVER_MODE — run feeds and strategies but submit nothing; journal hypothetical opportunities instead (default: false)
+    ///   OBSERVER_GAP_MS — an opportunity unseen for this long counts as gone (default: 1000)
+    ///   SHADOW_AB — run a shadow orchestrator beside the live one, journaling its hypothetical fills (default: false)
+    ///   SHADOW_<NAME> — in the shadow's config only, replaces <NAME> (e.g. SHADOW_HALF_LIFE_HOLD=4)
+    ///   HALF_LIFE_ADAPT — scale lag edge and cap lag holds by how long journaled opportunities lasted (default: true)
+    ///   HALF_LIFE_MIN_SAMPLES — observed opportunities needed per series/hour before adapting (default: 20)
+    ///   HALF_LIFE_HOLD, HALF_LIFE_REMAINING — lag max hold and minimum window left, in half-lives (default: 3, 24)
+    ///   HALF_LIFE_VOL_MULTS — hold multipliers for low,high,extreme vol; remaining is divided by them (default: 1.25,0.75,0.5)
+    ///   VOL_CALIBRATION — intraday vol curves file, "off" to disable (default: vol_calibration.json)
+    ///   DASHBOARD_API_ADDR — serve the dashboard API (book depth etc.) on this address, e.g. 127.0.0.1:8787 (default: off)
+    ///   STATE_SNAPSHOT_PATH — write the full engine state as JSON here every 30s and at shutdown (default: off)
+    ///   MARKET_RECORDING — record ticks, book states, signals and outcomes to this JSONL file for `export_features` (default: off)
+    ///   GRPC_ADDR — stream prices, books, signals, orders, fills and P&L over gRPC on this address, e.g. 127.0.0.1:50051 (default: off)
+    ///   DYNAMIC_ALLOCATION — re-weight capital by market opportunity (default: true)
+    ///   LATE_GAMMA — enable late-window gamma scalping (default: false)
+    ///   LATE_GAMMA_MARKET_USDC — max late-gamma cost per market window (default: 2.0)
+    ///   OPEN_SNIPER — enable the opening-seconds sniper (default: false)
+    ///   OPEN_SNIPER_WINDOW_SECS — seconds after open the sniper may trade (default: 5)
+    ///   OPEN_SNIPER_MAX_BOOK_AGE_MS — sniper skips books older than this (default: 250)
+    ///   OPEN_SNIPER_ORDER_USDC — max cost of the sniper's one entry per window (default: 5.0)
+    ///   OPEN_SNIPER_MIN_EDGE — min fair-value-vs-ask gap the sniper takes (default: 0.08)
+    ///   EXIT_LADDER — exit escalation rungs as JSON, e.g. {"rungs": [...]} (default: see ExitLadderConfig)
+    ///   SAFE_MODE — start in safe mode after a crash or kill switch (default: true)
+    ///   SAFE_MODE_STATE_PATH — session state file (default: session_state.json)
+    ///   SAFE_MODE_SIZE_MULT — order size multiplier in safe mode (default: 0.5)
+    ///   SAFE_MODE_MAX_MARKETS — markets traded in safe mode (default: 2)
+    ///   RESOLUTION_GUARD — block late entries when Binance and the oracle disagree (default: true)
+    ///   RESOLUTION_GUARD_INVERT — flip blocked entries to the oracle side instead (default: false)
+    ///   JOIN_MIN_REMAINING_PCT — skip mid-cycle joins with less of the window left (default: 0.40)
+    ///   FILL_QUALITY — shrink strategies whose fills get adversely selected (default: true)
+    ///   FILL_QUALITY_MAX_ADVERSE — tolerated avg adverse move per share (default: 0.02)
+    ///   FILL_QUALITY_MIN_SIZE_MULT — floor on the feedback size multiplier (default: 0.25)
+    ///   SPREAD_CONTROL — widen/tighten MM spreads from 5s/30s fill markouts (default: true)
+    ///   SPREAD_CONTROL_MIN_MULT, SPREAD_CONTROL_MAX_MULT — bounds on the MM spread multiplier (default: 0.75, 3.0)
+    ///   DEPTH_SIZING — cap taker orders to a share of displayed liquidity (default: true)
+    ///   DEPTH_SIZING_MAX_PCT — share of the liquidity within the band a taker may take (default: 0.25)
+    ///   DEPTH_SIZING_BAND — acceptable VWAP slippage past the best price (default: 0.02)
+    ///   FV_MODEL_SIGMA, FV_BOOK_SIGMA, FV_ALT_SIGMA — fair-value ensemble source standard errors (default: 0.05, 0.02, 0.06)
+    ///   FV_BOOK_HALF_LIFE_SECS — book mid weight half-life without updates (default: 5)
+    ///   PRICE_SERIES — Binance price driving fair value: last, mark or index (default: last)
+    ///   PRICE_SERIES_<MARKET> — per market type override, e.g. PRICE_SERIES_BTC_15M=index
+    ///   BASIS_SIGNAL — track the perp-spot basis; sharp moves lean bias and gate momentum (default: true)
+    ///   BASIS_Z_THRESHOLD — std devs from the 5-minute mean that count as a sharp basis move (default: 2.5)
+    ///   PRICE_PROJECTION — project the Binance price over feed + eval latency for lag entries (default: true)
+    ///   PRICE_PROJECTION_LOOKBACK_MS — prints used to fit the drift (default: 500)
+    ///   PRICE_PROJECTION_LATENCY_MS — latency added to the last print's age (default: 150)
+    ///   EDGE_SCALING — widen entry edge thresholds by uncertainty, book age and calibration error (default: true)
+    ///   EDGE_UNCERTAINTY_MULT — extra edge per unit of fair-value uncertainty over EDGE_UNCERTAINTY_FLOOR (default: 0.5, 0.03)
+    ///   EDGE_BOOK_AGE_PER_SEC — extra edge per second of book age, capped at EDGE_MAX_STALENESS (default: 0.002, 0.03)
+    ///   EDGE_CALIBRATION_MULT — multiplier on the model's recent calibration error (default: 0.5)
+    ///   SCALE_IN — cap adds to held lag positions and require a better price (default: false)
+    ///   SCALE_IN_MAX_ADDS — add-on fills per position (default: 2)
+    ///   SCALE_IN_MAX_COST_USDC — max blended cost basis of a scaled-in position (default: 10)
+    ///   SCALE_IN_MIN_IMPROVEMENT — add-on price must beat the average entry by this much (default: 0.01)
+    ///   PRE_POSITION — queue pre-position capable strategies' opening orders for the next window (default: false)
+    ///   PRE_POSITION_LEAD_SECS — how long before the next window opens they're queued (default: 10)
+    ///   ML_FILTER_MODEL — ONNX entry filter model, needs a `--features ml` build (default: off)
+    ///   ML_FILTER_MIN_PROB — drop intents scored below this win probability (default: 0)
+    ///   ML_FILTER_MIN_PROB_<FAMILY> — per-family override, e.g. ML_FILTER_MIN_PROB_LAG=0.55
+    ///   MARKET_SCREEN — skip untradeable books before running strategies (default: true)
+    ///   SCREEN_MIN_DEPTH — min shares at the touch on each token (default: 5)
+    ///   SCREEN_MAX_SPREAD — max spread on either token for takers (market making exempt), 0 = off (default: 0.10)
+    ///   SCREEN_MAX_BOOK_AGE_SECS — max seconds since a book update, 0 = off (default: 30)
+    ///   SCREEN_MIN_FILL_SUCCESS — min taker fill rate over the last 30 min for the market type (default: 0.20)
+    ///   MID_CYCLE_SIZE_MULT — size multiplier for mid-cycle joins (default: 0.50)
+    ///   COMPOUNDING_MODE — "compound" (default) or "sweep"
+    ///   SWEEP_WATERMARK, SWEEP_FRACTION, SWEEP_ADDRESS — profit sweep settings
+    ///   STRATEGY_BUCKETS — segregate capital per strategy (default: false)
+    ///   BUCKET_REBALANCE_SECS — bucket rebalance interval, 0 = never (default: 0)
+    ///   SIM_SEED — fixed seed for reproducible simulated runs (default: random per run)
+    ///   SIM_IMPACT_HALF_LIFE_SECS — refill half-life of book levels our simulated fills took, 0 = none (default: 10)
+    ///   RUST_LOG — log level (default: info)
+    ///   LOG_FORMAT — text | json (same as --log-format; read before config loads)
+    ///   DRY_RUN — set to "true" to use random key (no real orders)
+    pub fn load_or_default() -> Self {
+        // Load .env file if present
+        let _ = dotenv::dotenv();
+        Self::from_vars(|name| std::env::var(name))
+    }
+
+    /// The A/B shadow configuration: this environment with each
+    /// `SHADOW_<NAME>` variable standing in for `<NAME>` (e.g.
+    /// `SHADOW_HALF_LIFE_HOLD=4`). Only its strategy settings are used.
+    pub fn load_shadow() -> Self {
+        let _ = dotenv::dotenv();
+        Self::from_vars(|name| std::env::var(format!("SHADOW_{name}")).or_else(|_| std::env::var(name)))
+    }
+
+    /// Configuration from `env` lookups over the defaults.
+    fn from_vars(env: impl Fn(&str) -> Result<String, std::env::VarError>) -> Self {
+        let mut config = Self::default();
+
+        // Polymarket credentials
+        if let Ok(key) = env("POLYMARKET_PRIVATE_KEY") {
+            if key != "your_private_key_here" {
+                config.polymarket.private_key = key;
+            }
+        }
+
+        if let Ok(addr) = env("POLYMARKET_FUNDER_ADDRESS") {
+            if !addr.is_empty() && addr != "optional_proxy_address" {
+                config.polymarket.funder_address = Some(addr);
+            }
+        }
+
+        if let Ok(sig_type) = env("POLYMARKET_SIGNATURE_TYPE") {
+            config.polymarket.signature_type = sig_type.parse().unwrap_or(0);
+        }
+
+        // Endpoints
+        if let Ok(v) = env("CLOB_HOST") {
+            if !v.is_empty() {
+                config.polymarket.clob_host = v.trim_end_matches('/').to_string();
+            }
+        }
+        if let Ok(v) = env("GAMMA_API_HOST") {
+            if !v.is_empty() {
+                config.polymarket.gamma_api_host = v.trim_end_matches('/').to_string();
+            }
+        }
+        if let Ok(v) = env("BINANCE_WS_URL") {
+            if !v.is_empty() {
+                config.binance.ws_url = v.trim_end_matches('/').to_string();
+            }
+        }
+        if let Ok(v) = env("BINANCE_REST_URL") {
+            if !v.is_empty() {
+                config.binance.rest_url = v.trim_end_matches('/').to_string();
+            }
+        }
+        if let Ok(v) = env("BYBIT_LIQUIDATIONS") {
+            config.liquidations.bybit_enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("OKX_LIQUIDATIONS") {
+            config.liquidations.okx_enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("POLYGON_RPC_URL") {
+            if !v.is_empty() {
+                config.polymarket.polygon_rpc_url = v;
+            }
+        }
+
+        // CLOB REST retries
+        if let Ok(v) = env("CLOB_MAX_RETRIES") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.retry.max_retries = n;
+            }
+        }
+        if let Ok(v) = env("CLOB_RETRY_BASE_MS") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.retry.base_ms = n;
+            }
+        }
+        if let Ok(v) = env("CLOB_RETRY_BUDGET_PER_MIN") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.retry.budget_per_min = n;
+            }
+        }
+        if let Ok(v) = env("CLOB_MAX_IN_FLIGHT") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.retry.max_in_flight = n;
+            }
+        }
+        if let Ok(v) = env("CLOB_PREWARM_SECS") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.prewarm_secs = n;
+            }
+        }
+        if let Ok(v) = env("TOKEN_BALANCE_SECS") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.token_balance_secs = n;
+            }
+        }
+        if let Ok(v) = env("MATIC_PRICE_URL") {
+            config.polymarket.matic_price_url = if v == "off" { String::new() } else { v };
+        }
+        if let Ok(v) = env("MATIC_USD") {
+            if let Ok(n) = v.parse() {
+                config.polymarket.matic_usd = n;
+            }
+        }
+
+        // Starting capital
+        if let Ok(capital) = env("STARTING_CAPITAL") {
+            if let Ok(_val) = capital.parse::<f64>() {
+                // Stored in config for PositionManager initialization
+                // (passed through main.rs)
+            }
+        }
+
+        // Telegram alerts
+        if let Ok(token) = env("TELEGRAM_BOT_TOKEN") {
             if !token.is_empty() && token != "your_bot_token" {
                 config.telemetry.telegram_bot_token = Some(token);
             }
         }
-        if let Ok(chat) = std::env::var("TELEGRAM_CHAT_ID") {
-            if !chat.is_empty() && chat != "your_chat_id" {
-                config.telemetry.telegram_chat_id = Some(chat);
+        if let Ok(chat) = env("TELEGRAM_CHAT_ID") {
+            if !chat.is_empty() && chat != "your_chat_id" {
+                config.telemetry.telegram_chat_id = Some(chat);
+            }
+        }
+
+        // Discord alerts
+        if let Ok(url) = env("DISCORD_WEBHOOK_URL") {
+            if !url.is_empty() && url != "your_webhook_url" {
+                config.telemetry.discord_webhook_url = Some(url);
+            }
+        }
+
+        // Slack alerts
+        if let Ok(url) = env("SLACK_WEBHOOK_URL") {
+            if !url.is_empty() && url != "your_webhook_url" {
+                config.telemetry.slack_webhook_url = Some(url);
+            }
+        }
+
+        // Per-sink severity filters
+        for (var, min) in [
+            ("TELEGRAM_MIN_SEVERITY", &mut config.telemetry.telegram_min_severity),
+            ("DISCORD_MIN_SEVERITY", &mut config.telemetry.discord_min_severity),
+            ("SLACK_MIN_SEVERITY", &mut config.telemetry.slack_min_severity),
+        ] {
+            if let Some(severity) = env(var).ok().and_then(|v| AlertSeverity::parse(&v)) {
+                *min = severity;
+            }
+        }
+        if let Ok(v) = env("ALERT_MAX_RETRIES") {
+            if let Ok(n) = v.parse() {
+                config.telemetry.alert_max_retries = n;
+            }
+        }
+        if let Ok(v) = env("ALERT_DEDUP_WINDOW_SECS") {
+            if let Ok(n) = v.parse() {
+                config.telemetry.alert_dedup_window_secs = n;
+            }
+        }
+        if let Ok(v) = env("HEARTBEAT_ALERT_SECS") {
+            if let Ok(n) = v.parse() {
+                config.telemetry.heartbeat_alert_secs = n;
+            }
+        }
+        if let Ok(url) = env("HEALTHCHECK_URL") {
+            if !url.is_empty() {
+                config.telemetry.healthcheck_url = Some(url);
+            }
+        }
+        if let Ok(v) = env("HEALTHCHECK_INTERVAL_SECS") {
+            if let Ok(n) = v.parse() {
+                config.telemetry.healthcheck_interval_secs = n;
+            }
+        }
+        if let Ok(v) = env("STRATEGY_BUDGET_MS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.eval_budget_ms = n;
+            }
+        }
+        if let Ok(v) = env("STRATEGY_OVERRUN_BENCH_SECS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.eval_overrun_bench_secs = n;
+            }
+        }
+        if let Ok(v) = env("STRATEGY_OVERRUN_STREAK") {
+            if let Ok(n) = v.parse() {
+                config.strategy.eval_overrun_streak = n;
+            }
+        }
+        let throttle = &mut config.strategy.eval_throttle;
+        for (regime, field) in [
+            ("DEAD", &mut throttle.dead_ms),
+            ("LOW", &mut throttle.low_ms),
+            ("MEDIUM", &mut throttle.medium_ms),
+            ("HIGH", &mut throttle.high_ms),
+            ("EXTREME", &mut throttle.extreme_ms),
+        ] {
+            if let Some(ms) = env(&format!("EVAL_THROTTLE_MS_{regime}")).ok().and_then(|v| v.parse().ok()) {
+                *field = ms;
+            }
+        }
+        if let Some(v) = env("EVAL_THROTTLE_ENDGAME_SECS").ok().and_then(|v| v.parse().ok()) {
+            throttle.endgame_secs = v;
+        }
+        if let Some(v) = env("EVAL_THROTTLE_ENDGAME_MULT").ok().and_then(|v| v.parse().ok()) {
+            throttle.endgame_mult = v;
+        }
+        if let Ok(v) = env("MAX_MARKET_NOTIONAL_PER_EVAL") {
+            if let Ok(n) = v.parse() {
+                config.strategy.max_market_notional_per_eval = n;
+            }
+        }
+        if let Ok(v) = env("ORPHAN_SWEEP_SECS") {
+            if let Ok(n) = v.parse() {
+                config.risk.orphan_sweep_secs = n;
+            }
+        }
+        if let Ok(v) = env("ORPHAN_MIN_AGE_SECS") {
+            if let Ok(n) = v.parse() {
+                config.risk.orphan_min_age_secs = n;
+            }
+        }
+        if let Ok(v) = env("NET_RESTING_ORDERS") {
+            config.risk.net_resting_orders = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("INTENT_DEDUP_MS") {
+            if let Ok(n) = v.parse() {
+                config.risk.intent_dedup_ms = n;
+            }
+        }
+        if let Ok(v) = env("REQUOTE_TICKS") {
+            if let Ok(n) = v.parse() {
+                config.risk.requote_ticks = n;
+            }
+        }
+        if let Ok(v) = env("UNWIND_MAX_SLIPPAGE") {
+            if let Ok(n) = v.parse() {
+                config.risk.unwind_max_slippage = n;
+            }
+        }
+        if let Ok(v) = env("STRADDLE_MIN_LEG_PCT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.straddle_min_leg_pct = n;
+            }
+        }
+        if let Ok(v) = env("MM_LEVELS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.mm_levels = n;
+            }
+        }
+        if let Ok(v) = env("MM_LEVEL_SPACING") {
+            if let Ok(n) = v.parse() {
+                config.strategy.mm_level_spacing = n;
+            }
+        }
+        if let Ok(v) = env("MM_LEVEL_SIZE_MULT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.mm_level_size_mult = n;
+            }
+        }
+        if let Some(n) = env("LOOP_MAX_ROUND_TRIPS").ok().and_then(|v| v.parse().ok()) {
+            config.risk.loop_max_round_trips = n;
+        }
+        if let Some(n) = env("MARKET_MAX_LOSS_USDC").ok().and_then(|v| v.parse().ok()) {
+            config.risk.market_max_loss_usdc = n;
+        }
+        for (var, field) in [
+            ("LOOP_WINDOW_SECS", &mut config.risk.loop_window_secs),
+            ("LOOP_COOLOFF_SECS", &mut config.risk.loop_cooloff_secs),
+            ("MARKET_STOP_MINS", &mut config.risk.market_stop_mins),
+            ("SETTLEMENT_HOLD_SECS", &mut config.risk.settlement_hold_secs),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+        if let Ok(path) = env("VOL_CALIBRATION") {
+            config.strategy.vol_calibration_path = match path.as_str() {
+                "" | "off" | "none" => None,
+                _ => Some(path),
+            };
+        }
+        if let Ok(path) = env("TRADE_JOURNAL") {
+            config.telemetry.journal_path = match path.as_str() {
+                "" | "off" | "none" => None,
+                _ => Some(path),
+            };
+        }
+        if let Ok(v) = env("OBSERVER_MODE") {
+            config.telemetry.observer_mode = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("OBSERVER_GAP_MS") {
+            if let Ok(n) = v.parse() {
+                config.telemetry.observer_gap_ms = n;
+            }
+        }
+        if let Ok(v) = env("SHADOW_AB") {
+            config.telemetry.shadow_ab = v == "true" || v == "1";
+        }
+        if let Ok(addr) = env("DASHBOARD_API_ADDR") {
+            if !addr.is_empty() {
+                config.telemetry.api_addr = Some(addr);
+            }
+        }
+        if let Ok(path) = env("STATE_SNAPSHOT_PATH") {
+            if !path.is_empty() {
+                config.telemetry.state_snapshot_path = Some(path);
+            }
+        }
+        if let Ok(addr) = env("GRPC_ADDR") {
+            if !addr.is_empty() {
+                config.telemetry.grpc_addr = Some(addr);
+            }
+        }
+        if let Ok(path) = env("MARKET_RECORDING") {
+            config.telemetry.recording_path = match path.as_str() {
+                "" | "off" | "none" => None,
+                _ => Some(path),
+            };
+        }
+        for (var, limit) in [
+            ("ALERT_INFO_PER_MIN", &mut config.telemetry.alert_info_per_min),
+            ("ALERT_WARNING_PER_MIN", &mut config.telemetry.alert_warning_per_min),
+            ("ALERT_CRITICAL_PER_MIN", &mut config.telemetry.alert_critical_per_min),
+        ] {
+            if let Some(n) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *limit = n;
             }
         }
 
-        // Discord alerts
-        if let Ok(url) = std::env::var("DISCORD_WEBHOOK_URL") {
-            if !url.is_empty() && url != "your_webhook_url" {
-                config.telemetry.discord_webhook_url = Some(url);
+        // Capital allocation
+        if let Ok(v) = env("DYNAMIC_ALLOCATION") {
+            config.strategy.capital_allocation.dynamic = v == "true" || v == "1";
+        }
+
+        // Late-window gamma scalping
+        if let Ok(v) = env("LATE_GAMMA") {
+            config.strategy.late_gamma_enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("LATE_GAMMA_MARKET_USDC") {
+            config.strategy.late_gamma_market_usdc = v.parse().unwrap_or(2.0);
+        }
+
+        // Opening-seconds sniper
+        if let Ok(v) = env("OPEN_SNIPER") {
+            config.strategy.open_sniper_enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("OPEN_SNIPER_WINDOW_SECS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.open_sniper_window_secs = n;
+            }
+        }
+        if let Ok(v) = env("OPEN_SNIPER_MAX_BOOK_AGE_MS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.open_sniper_max_book_age_ms = n;
+            }
+        }
+        if let Ok(v) = env("OPEN_SNIPER_ORDER_USDC") {
+            if let Ok(n) = v.parse() {
+                config.strategy.open_sniper_order_usdc = n;
+            }
+        }
+        if let Ok(v) = env("OPEN_SNIPER_MIN_EDGE") {
+            if let Ok(n) = v.parse() {
+                config.strategy.open_sniper_min_edge = n;
+            }
+        }
+
+        // Exit escalation ladder
+        if let Ok(v) = env("EXIT_LADDER") {
+            match serde_json::from_str(&v) {
+                Ok(ladder) => config.risk.exit_ladder = ladder,
+                Err(e) => tracing::warn!("Ignoring EXIT_LADDER: {e}"),
+            }
+        }
+
+        // Canary probation for new strategies
+        if let Ok(v) = env("CANARY_STRATEGIES") {
+            config.risk.canary.strategies =
+                v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+        }
+        if let Ok(path) = env("CANARY_STATE_PATH") {
+            if !path.is_empty() {
+                config.risk.canary.state_path = path;
+            }
+        }
+        for (var, field) in [
+            ("CANARY_MAX_ORDER_USDC", &mut config.risk.canary.max_order_usdc),
+            ("CANARY_LOSS_BUDGET_USDC", &mut config.risk.canary.loss_budget_usdc),
+            ("CANARY_MIN_HOURS", &mut config.risk.canary.min_hours),
+            ("CANARY_MIN_WIN_RATE", &mut config.risk.canary.min_win_rate),
+            ("CANARY_MIN_PNL_USDC", &mut config.risk.canary.min_pnl_usdc),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+        if let Some(n) = env("CANARY_MIN_TRADES").ok().and_then(|v| v.parse().ok()) {
+            config.risk.canary.min_trades = n;
+        }
+
+        // Operator approval for large entries
+        if let Some(v) = env("APPROVAL_MIN_USDC").ok().and_then(|v| v.parse().ok()) {
+            config.risk.approval.min_notional_usdc = v;
+        }
+        if let Some(v) = env("APPROVAL_TIMEOUT_SECS").ok().and_then(|v| v.parse().ok()) {
+            config.risk.approval.timeout_secs = v;
+        }
+
+        // Carrying resolved winners until redeemed
+        if let Ok(v) = env("CARRY") {
+            config.risk.carry.enabled = v == "true" || v == "1";
+        }
+        if let Ok(path) = env("CARRY_STATE_PATH") {
+            if !path.is_empty() {
+                config.risk.carry.state_path = path;
+            }
+        }
+        if let Some(v) = env("CARRY_RETRY_SECS").ok().and_then(|v| v.parse().ok()) {
+            config.risk.carry.retry_secs = v;
+        }
+        if let Some(v) = env("CARRY_MAX_ATTEMPTS").ok().and_then(|v| v.parse().ok()) {
+            config.risk.carry.max_attempts = v;
+        }
+
+        // Safe mode after an abnormal shutdown
+        if let Ok(v) = env("SAFE_MODE") {
+            config.risk.safe_mode.enabled = v == "true" || v == "1";
+        }
+        if let Ok(path) = env("SAFE_MODE_STATE_PATH") {
+            if !path.is_empty() {
+                config.risk.safe_mode.state_path = path;
+            }
+        }
+        if let Some(v) = env("SAFE_MODE_SIZE_MULT").ok().and_then(|v| v.parse().ok()) {
+            config.risk.safe_mode.size_mult = v;
+        }
+        if let Some(v) = env("SAFE_MODE_MAX_MARKETS").ok().and_then(|v| v.parse().ok()) {
+            config.risk.safe_mode.max_markets = v;
+        }
+
+        // Resolution sniping guard
+        if let Ok(v) = env("RESOLUTION_GUARD") {
+            config.risk.resolution_guard.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("RESOLUTION_GUARD_INVERT") {
+            config.risk.resolution_guard.invert = v == "true" || v == "1";
+        }
+
+        // Mid-window join policy
+        if let Ok(v) = env("JOIN_MIN_REMAINING_PCT") {
+            config.strategy.join_policy.min_remaining_pct = v.parse().unwrap_or(0.40);
+        }
+        if let Ok(v) = env("MID_CYCLE_SIZE_MULT") {
+            config.strategy.join_policy.mid_cycle_size_mult = v.parse().unwrap_or(0.50);
+        }
+
+        // Fill quality feedback
+        if let Ok(v) = env("FILL_QUALITY") {
+            config.strategy.fill_quality.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("FILL_QUALITY_MAX_ADVERSE") {
+            if let Ok(n) = v.parse() {
+                config.strategy.fill_quality.max_adverse_selection = n;
+            }
+        }
+        if let Ok(v) = env("FILL_QUALITY_MIN_SIZE_MULT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.fill_quality.min_size_mult = n;
+            }
+        }
+
+        // MM spread control
+        if let Ok(v) = env("SPREAD_CONTROL") {
+            config.strategy.spread_control.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("SPREAD_CONTROL_MIN_MULT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.spread_control.min_mult = n;
+            }
+        }
+        if let Ok(v) = env("SPREAD_CONTROL_MAX_MULT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.spread_control.max_mult = n;
+            }
+        }
+
+        // Depth-aware taker sizing
+        if let Ok(v) = env("DEPTH_SIZING") {
+            config.strategy.depth_sizing.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("DEPTH_SIZING_MAX_PCT") {
+            if let Ok(n) = v.parse() {
+                config.strategy.depth_sizing.max_depth_pct = n;
+            }
+        }
+        if let Ok(v) = env("DEPTH_SIZING_BAND") {
+            if let Ok(n) = v.parse() {
+                config.strategy.depth_sizing.slippage_band = n;
+            }
+        }
+
+        // Fair-value ensemble
+        for (var, field) in [
+            ("FV_MODEL_SIGMA", &mut config.strategy.fair_value.model_sigma),
+            ("FV_BOOK_SIGMA", &mut config.strategy.fair_value.book_sigma),
+            ("FV_BOOK_HALF_LIFE_SECS", &mut config.strategy.fair_value.book_half_life_secs),
+            ("FV_ALT_SIGMA", &mut config.strategy.fair_value.alt_sigma),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+
+        // Fair-value price series
+        if let Some(series) = env("PRICE_SERIES").ok().and_then(|v| PriceSeries::parse(&v)) {
+            config.strategy.price_series.default = series;
+        }
+        for (asset, duration) in MarketDiscovery::all_market_types() {
+            let key = format!("{}_{}", asset.slug_prefix(), duration.slug_suffix());
+            let var = format!("PRICE_SERIES_{}", key.to_uppercase());
+            if let Some(series) = env(&var).ok().and_then(|v| PriceSeries::parse(&v)) {
+                config.strategy.price_series.per_market.insert(key, series);
+            }
+        }
+
+        // Opportunity half-life
+        if let Ok(v) = env("HALF_LIFE_ADAPT") {
+            config.strategy.half_life.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("HALF_LIFE_MIN_SAMPLES") {
+            if let Ok(n) = v.parse() {
+                config.strategy.half_life.min_samples = n;
+            }
+        }
+        for (var, field) in [
+            ("HALF_LIFE_HOLD", &mut config.strategy.half_life.hold_half_lives),
+            ("HALF_LIFE_REMAINING", &mut config.strategy.half_life.remaining_half_lives),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+        if let Ok(v) = env("HALF_LIFE_VOL_MULTS") {
+            let mults: Vec<f64> = v.split(',').filter_map(|m| m.trim().parse().ok()).collect();
+            if let [low, high, extreme] = mults[..] {
+                let hl = &mut config.strategy.half_life;
+                (hl.low_vol_mult, hl.high_vol_mult, hl.extreme_vol_mult) = (low, high, extreme);
+            }
+        }
+
+        // Perp-spot basis
+        if let Ok(v) = env("BASIS_SIGNAL") {
+            config.strategy.basis.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("BASIS_Z_THRESHOLD") {
+            if let Ok(n) = v.parse() {
+                config.strategy.basis.z_threshold = n;
+            }
+        }
+
+        // Latency-compensated price
+        if let Ok(v) = env("PRICE_PROJECTION") {
+            config.strategy.projection.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("PRICE_PROJECTION_LOOKBACK_MS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.projection.lookback_ms = n;
+            }
+        }
+        if let Ok(v) = env("PRICE_PROJECTION_LATENCY_MS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.projection.latency_ms = n;
+            }
+        }
+
+        // Confidence-weighted edge thresholds
+        if let Ok(v) = env("EDGE_SCALING") {
+            config.strategy.edge.enabled = v.parse().unwrap_or(true);
+        }
+        for (var, field) in [
+            ("EDGE_UNCERTAINTY_MULT", &mut config.strategy.edge.uncertainty_mult),
+            ("EDGE_UNCERTAINTY_FLOOR", &mut config.strategy.edge.uncertainty_floor),
+            ("EDGE_BOOK_AGE_PER_SEC", &mut config.strategy.edge.book_age_per_sec),
+            ("EDGE_MAX_STALENESS", &mut config.strategy.edge.max_staleness_edge),
+            ("EDGE_CALIBRATION_MULT", &mut config.strategy.edge.calibration_mult),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+
+        // Scale-in
+        if let Ok(v) = env("SCALE_IN") {
+            config.strategy.scale_in.enabled = v.parse().unwrap_or(false);
+        }
+        if let Some(v) = env("SCALE_IN_MAX_ADDS").ok().and_then(|v| v.parse().ok()) {
+            config.strategy.scale_in.max_adds = v;
+        }
+        for (var, field) in [
+            ("SCALE_IN_MAX_COST_USDC", &mut config.strategy.scale_in.max_total_cost_usdc),
+            ("SCALE_IN_MIN_IMPROVEMENT", &mut config.strategy.scale_in.min_improvement),
+        ] {
+            if let Some(v) = env(var).ok().and_then(|v| v.parse().ok()) {
+                *field = v;
+            }
+        }
+
+        // Next-window pre-positioning
+        if let Ok(v) = env("PRE_POSITION") {
+            config.strategy.pre_position.enabled = v.parse().unwrap_or(false);
+        }
+        if let Some(v) = env("PRE_POSITION_LEAD_SECS").ok().and_then(|v| v.parse().ok()) {
+            config.strategy.pre_position.lead_secs = v;
+        }
+
+        // ML entry filter
+        if let Ok(path) = env("ML_FILTER_MODEL") {
+            config.strategy.ml_filter.model_path = match path.as_str() {
+                "" | "off" | "none" => None,
+                _ => Some(path),
+            };
+        }
+        if let Some(p) = env("ML_FILTER_MIN_PROB").ok().and_then(|v| v.parse().ok()) {
+            config.strategy.ml_filter.min_win_prob = p;
+        }
+        for family in ["straddle", "arb", "lag", "mm", "momentum", "late_gamma", "open_sniper", "other"] {
+            let var = format!("ML_FILTER_MIN_PROB_{}", family.to_uppercase());
+            if let Some(p) = env(&var).ok().and_then(|v| v.parse().ok()) {
+                config.strategy.ml_filter.strategy_min_win_prob.insert(family.to_string(), p);
+            }
+        }
+
+        // Market screen
+        if let Ok(v) = env("MARKET_SCREEN") {
+            config.strategy.screen.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("SCREEN_MIN_DEPTH") {
+            if let Ok(n) = v.parse() {
+                config.strategy.screen.min_top_depth = n;
+            }
+        }
+        if let Ok(v) = env("SCREEN_MAX_SPREAD") {
+            if let Ok(n) = v.parse() {
+                config.strategy.screen.max_spread = n;
+            }
+        }
+        if let Ok(v) = env("SCREEN_MAX_BOOK_AGE_SECS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.screen.max_book_age_secs = n;
+            }
+        }
+        if let Ok(v) = env("SCREEN_MIN_FILL_SUCCESS") {
+            if let Ok(n) = v.parse() {
+                config.strategy.screen.min_fill_success = n;
+            }
+        }
+
+        // Compounding / profit sweep
+        if let Ok(mode) = env("COMPOUNDING_MODE") {
+            config.risk.compounding.mode = match mode.to_lowercase().as_str() {
+                "sweep" => CompoundingMode::Sweep,
+                _ => CompoundingMode::Compound,
+            };
+        }
+        if let Ok(v) = env("SWEEP_WATERMARK") {
+            config.risk.compounding.watermark = v.parse().unwrap_or(0.0);
+        }
+        if let Ok(v) = env("SWEEP_FRACTION") {
+            config.risk.compounding.sweep_fraction = v.parse().unwrap_or(1.0);
+        }
+        if let Ok(addr) = env("SWEEP_ADDRESS") {
+            if !addr.is_empty() && addr != "your_cold_wallet_address" {
+                config.risk.compounding.sweep_address = Some(addr);
             }
         }
 
+        // Per-strategy capital buckets
+        if let Ok(v) = env("STRATEGY_BUCKETS") {
+            config.risk.buckets.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = env("BUCKET_REBALANCE_SECS") {
+            config.risk.buckets.rebalance_interval_secs = v.parse().unwrap_or(0);
+        }
+
+        // Simulation seed
+        if let Ok(v) = env("SIM_SEED") {
+            config.sim.seed = v.parse().ok();
+        }
+        if let Some(v) = env("SIM_IMPACT_HALF_LIFE_SECS").ok().and_then(|v| v.parse().ok()) {
+            config.sim.impact_half_life_secs = v;
+        }
+
         // Log level
-        if let Ok(level) = std::env::var("RUST_LOG") {
+        if let Ok(level) = env("RUST_LOG") {
             config.telemetry.log_level = level;
         }
 
         // Dry run mode — use random key if no real key provided
-        let dry_run = std::env::var("DRY_RUN")
+        let dry_run = env("DRY_RUN")
             .map(|v| v == "true" || v == "1")
             .unwrap_or(false);
 
@@ -281,26 +2071,552 @@ impl Config {
                 .unwrap_or(false)
     }
 
+    /// Fails with every error from `diagnose`.
     pub fn validate(&self) -> anyhow::Result<()> {
-        if self.is_dry_run() {
-            tracing::info!("Dry-run mode — skipping private key validation");
+        let report = self.diagnose();
+        let errors: Vec<String> = report.errors().map(|i| i.message.clone()).collect();
+        anyhow::ensure!(errors.is_empty(), "{}", errors.join("; "));
+        Ok(())
+    }
+
+    /// Every problem with this config, by category. Credential and wallet
+    /// problems are errors when trading live and warnings in dry-run.
+    pub fn diagnose(&self) -> ConfigReport {
+        use ConfigCategory::*;
+        let mut r = ConfigReport::default();
+        let dry_run = self.is_dry_run();
+        let live = if dry_run { IssueSeverity::Warning } else { IssueSeverity::Error };
+
+        // Credentials
+        let pm = &self.polymarket;
+        if pm.private_key.is_empty() {
+            r.warn(Credentials, "POLYMARKET_PRIVATE_KEY is not set — running dry-run with a random signing key");
         } else {
-            anyhow::ensure!(
-                !self.polymarket.private_key.is_empty(),
-                "POLYMARKET_PRIVATE_KEY must be set (or set DRY_RUN=true)"
-            );
+            let key_hex = pm.private_key.strip_prefix("0x").unwrap_or(&pm.private_key);
+            if key_hex.len() != 64 || hex::decode(key_hex).is_err() {
+                r.push(live, Credentials, "POLYMARKET_PRIVATE_KEY must be 32 bytes of hex (64 characters, optional 0x prefix)");
+            } else if key_hex.parse::<alloy_signer_local::PrivateKeySigner>().is_err() {
+                r.push(live, Credentials, "POLYMARKET_PRIVATE_KEY is not a valid secp256k1 key");
+            }
+        }
+        match (pm.signature_type, &pm.funder_address) {
+            (0, Some(_)) => r.push(
+                live,
+                Credentials,
+                "POLYMARKET_FUNDER_ADDRESS is set but POLYMARKET_SIGNATURE_TYPE=0 (EOA) signs for the key's own address — \
+                 unset the funder or use signature type 1 (Poly proxy) / 2 (Gnosis safe)",
+            ),
+            (2, None) => r.push(
+                live,
+                Credentials,
+                "POLYMARKET_SIGNATURE_TYPE=2 (Gnosis safe) needs POLYMARKET_FUNDER_ADDRESS set to the safe's address",
+            ),
+            (0..=2, _) => {}
+            (other, _) => r.push(
+                live,
+                Credentials,
+                format!("POLYMARKET_SIGNATURE_TYPE must be 0 (EOA), 1 (Poly proxy) or 2 (Gnosis safe), got {other}"),
+            ),
+        }
+
+        // Addresses
+        if let Some(addr) = &pm.funder_address {
+            if let Err(e) = check_address(addr) {
+                r.push(live, Addresses, format!("POLYMARKET_FUNDER_ADDRESS {e}"));
+            }
+        }
+        let comp = &self.risk.compounding;
+        if let Some(addr) = &comp.sweep_address {
+            if let Err(e) = check_address(addr) {
+                r.error(Addresses, format!("SWEEP_ADDRESS {e}"));
+            }
         }
-        anyhow::ensure!(
+
+        // Execution
+        r.check(
+            Execution,
+            self.polymarket.prewarm_secs < crate::execution::clob_client::POOL_IDLE_TIMEOUT.as_secs(),
+            format!(
+                "CLOB_PREWARM_SECS must be below the {}s pool idle timeout",
+                crate::execution::clob_client::POOL_IDLE_TIMEOUT.as_secs()
+            ),
+        );
+        r.check(Execution, self.polymarket.retry.max_in_flight > 0, "CLOB_MAX_IN_FLIGHT must be at least 1");
+        r.check(Execution, self.sim.impact_half_life_secs >= 0.0, "SIM_IMPACT_HALF_LIFE_SECS must be non-negative");
+        r.check(Execution, self.polymarket.matic_usd >= 0.0, "MATIC_USD must be non-negative");
+
+        // Risk
+        r.check(
+            Risk,
             self.risk.max_exposure_pct > 0.0 && self.risk.max_exposure_pct <= 1.0,
-            "max_exposure_pct must be between 0 and 1"
+            "max_exposure_pct must be between 0 and 1",
+        );
+        r.check(
+            Risk,
+            self.risk.max_daily_loss_pct > 0.0 && self.risk.max_daily_loss_pct <= 1.0,
+            "max_daily_loss_pct must be between 0 and 1",
+        );
+        r.check(
+            Risk,
+            comp.sweep_fraction > 0.0 && comp.sweep_fraction <= 1.0,
+            "sweep_fraction must be between 0 and 1",
+        );
+        if self.risk.buckets.enabled {
+            let bucket_total: f64 = self.risk.buckets.shares().iter().map(|(_, p)| p).sum();
+            r.check(
+                Risk,
+                (bucket_total - 1.0).abs() < 0.01,
+                format!("Strategy buckets must sum to 1.0, got {bucket_total}"),
+            );
+        }
+        r.check(
+            Risk,
+            self.risk.exit_ladder.rungs.last().is_some_and(|r| r.is_unconditional()),
+            "EXIT_LADDER must end with a rung that has no conditions",
+        );
+        r.check(
+            Risk,
+            self.risk.safe_mode.size_mult > 0.0 && self.risk.safe_mode.size_mult <= 1.0,
+            "SAFE_MODE_SIZE_MULT must be in (0, 1]",
+        );
+        let canary = &self.risk.canary;
+        r.check(
+            Risk,
+            canary.max_order_usdc > 0.0 && canary.loss_budget_usdc > 0.0 && canary.min_hours >= 0.0,
+            "CANARY_MAX_ORDER_USDC and CANARY_LOSS_BUDGET_USDC must be positive, CANARY_MIN_HOURS non-negative",
+        );
+        r.check(Risk, (0.0..=1.0).contains(&canary.min_win_rate), "CANARY_MIN_WIN_RATE must be in [0, 1]");
+        const FAMILIES: [&str; 8] = ["straddle", "arb", "lag", "mm", "momentum", "late_gamma", "open_sniper", "other"];
+        for family in canary.strategies.iter().filter(|f| !FAMILIES.contains(&f.as_str())) {
+            r.warn(Risk, format!("CANARY_STRATEGIES: unknown strategy family \"{family}\" (expected one of {})", FAMILIES.join(", ")));
+        }
+
+        let approval = &self.risk.approval;
+        r.check(Risk, approval.min_notional_usdc >= 0.0, "APPROVAL_MIN_USDC must be non-negative");
+        r.check(
+            Risk,
+            self.risk.unwind_max_slippage > 0.0 && self.risk.unwind_max_slippage < 1.0,
+            "UNWIND_MAX_SLIPPAGE must be between 0 and 1",
         );
-        let alloc = &self.strategy.capital_allocation;
+        if approval.min_notional_usdc > 0.0 {
+            r.check(Risk, approval.timeout_secs > 0, "APPROVAL_TIMEOUT_SECS must be positive");
+            let telegram = self.telemetry.telegram_bot_token.is_some() && self.telemetry.telegram_chat_id.is_some();
+            if !telegram && self.telemetry.api_addr.is_none() {
+                r.warn(
+                    Risk,
+                    "APPROVAL_MIN_USDC is set but neither Telegram nor DASHBOARD_API_ADDR is configured — held entries can only time out",
+                );
+            }
+        }
+        let carry = &self.risk.carry;
+        if carry.enabled {
+            r.check(
+                Risk,
+                carry.retry_secs > 0 && carry.max_attempts > 0,
+                "CARRY_RETRY_SECS and CARRY_MAX_ATTEMPTS must be positive",
+            );
+            if dry_run {
+                r.warn(Risk, "CARRY has no effect in dry run — resolution payouts are credited at once");
+            }
+        }
+
+        // Strategy parameters
+        let st = &self.strategy;
+        let alloc = &st.capital_allocation;
         let total = alloc.btc_5m_pct + alloc.btc_15m_pct + alloc.eth_15m_pct
             + alloc.sol_15m_pct + alloc.xrp_15m_pct;
-        anyhow::ensure!(
+        r.check(
+            Strategy,
             (total - 1.0).abs() < 0.01,
-            "Capital allocation must sum to 1.0, got {total}"
+            format!("Capital allocation must sum to 1.0, got {total}"),
         );
-        Ok(())
+        r.check(
+            Strategy,
+            alloc.score_weight >= 0.0 && alloc.score_weight <= 1.0,
+            "score_weight must be between 0 and 1",
+        );
+        r.check(
+            Strategy,
+            alloc.min_market_pct >= 0.0 && alloc.min_market_pct * 5.0 <= 1.0,
+            "min_market_pct must be between 0 and 0.2",
+        );
+        r.check(
+            Strategy,
+            st.straddle_max_combined > 0.0 && st.straddle_max_combined <= 1.0,
+            "straddle_max_combined must be in (0, 1] — above 1 the pair costs more than it pays",
+        );
+        r.check(
+            Strategy,
+            [st.straddle_max_capital_pct, st.bias_max_capital_pct, st.mm_base_size_pct]
+                .iter()
+                .all(|p| *p > 0.0 && *p <= 1.0),
+            "straddle_max_capital_pct, bias_max_capital_pct and mm_base_size_pct must be in (0, 1]",
+        );
+        r.check(
+            Strategy,
+            st.lag_kelly_fraction > 0.0 && st.lag_kelly_fraction <= 1.0,
+            "lag_kelly_fraction must be in (0, 1] — above 1 bets more than full Kelly",
+        );
+        r.check(
+            Strategy,
+            (0.0..1.0).contains(&st.lag_min_edge) && (0.0..1.0).contains(&st.late_gamma_min_edge)
+                && (0.0..1.0).contains(&st.open_sniper_min_edge),
+            "lag_min_edge, late_gamma_min_edge and open_sniper_min_edge must be in [0, 1)",
+        );
+        r.check(
+            Strategy,
+            st.lockout_seconds_5m >= 0.0 && st.lockout_seconds_15m >= 0.0,
+            "Resolution lockouts must be non-negative",
+        );
+        r.check(Strategy, st.eval_budget_ms > 0.0, "eval_budget_ms must be positive");
+        r.check(Strategy, st.eval_overrun_streak > 0, "STRATEGY_OVERRUN_STREAK must be at least 1");
+        let throttle = &st.eval_throttle;
+        r.check(
+            Strategy,
+            throttle.endgame_secs >= 0.0 && throttle.endgame_mult > 0.0 && throttle.endgame_mult <= 1.0,
+            "EVAL_THROTTLE_ENDGAME_SECS must be non-negative and EVAL_THROTTLE_ENDGAME_MULT above 0 and at most 1",
+        );
+        // A zero cooldown re-evaluates on every price tick, spinning the loop
+        r.check(
+            Strategy,
+            [throttle.dead_ms, throttle.low_ms, throttle.medium_ms, throttle.high_ms, throttle.extreme_ms]
+                .iter()
+                .all(|&ms| ms > 0),
+            "EVAL_THROTTLE_MS_<REGIME> must be at least 1",
+        );
+        if throttle.extreme_ms > 5_000 || throttle.high_ms > 5_000 {
+            r.warn(Strategy, "EVAL_THROTTLE_MS_HIGH/EXTREME over 5s: fast markets will be evaluated on stale prices");
+        }
+        r.check(
+            Strategy,
+            st.late_gamma_order_usdc <= st.late_gamma_market_usdc,
+            "late_gamma_order_usdc must not exceed LATE_GAMMA_MARKET_USDC",
+        );
+        r.check(Strategy, st.open_sniper_window_secs > 0.0, "OPEN_SNIPER_WINDOW_SECS must be positive");
+        r.check(Strategy, st.open_sniper_max_book_age_ms > 0.0, "OPEN_SNIPER_MAX_BOOK_AGE_MS must be positive");
+        // The sniper takes asks up to 0.95 and orders need 5 shares
+        if st.open_sniper_enabled && st.open_sniper_order_usdc < 5.0 * 0.95 {
+            r.warn(Strategy, "OPEN_SNIPER_ORDER_USDC can't buy the 5-share order minimum at every ask the sniper takes");
+        }
+        let join = &st.join_policy;
+        r.check(
+            Strategy,
+            (0.0..=1.0).contains(&join.min_remaining_pct),
+            "JOIN_MIN_REMAINING_PCT must be between 0 and 1",
+        );
+        r.check(
+            Strategy,
+            join.mid_cycle_size_mult > 0.0 && join.mid_cycle_size_mult <= 1.0,
+            "MID_CYCLE_SIZE_MULT must be in (0, 1]",
+        );
+        let fq = &st.fill_quality;
+        r.check(
+            Strategy,
+            fq.min_size_mult > 0.0 && fq.min_size_mult <= 1.0,
+            "FILL_QUALITY_MIN_SIZE_MULT must be in (0, 1]",
+        );
+        r.check(Strategy, fq.max_adverse_selection > 0.0, "FILL_QUALITY_MAX_ADVERSE must be positive");
+        r.check(
+            Strategy,
+            (0.0..=1.0).contains(&st.straddle_min_leg_pct),
+            "STRADDLE_MIN_LEG_PCT must be between 0 and 1",
+        );
+        r.check(Strategy, st.mm_levels >= 1, "MM_LEVELS must be at least 1");
+        r.check(Strategy, st.mm_level_spacing >= 0.0, "MM_LEVEL_SPACING must not be negative");
+        r.check(Strategy, st.mm_level_size_mult > 0.0, "MM_LEVEL_SIZE_MULT must be positive");
+        let sc = &st.spread_control;
+        r.check(
+            Strategy,
+            sc.min_mult > 0.0 && sc.min_mult <= 1.0 && sc.max_mult >= 1.0,
+            "SPREAD_CONTROL_MIN_MULT must be in (0, 1] and SPREAD_CONTROL_MAX_MULT at least 1",
+        );
+        let depth = &st.depth_sizing;
+        r.check(
+            Strategy,
+            depth.max_depth_pct > 0.0 && depth.max_depth_pct <= 1.0,
+            "DEPTH_SIZING_MAX_PCT must be in (0, 1]",
+        );
+        r.check(Strategy, depth.slippage_band > 0.0, "DEPTH_SIZING_BAND must be positive");
+        let fv = &st.fair_value;
+        r.check(
+            Strategy,
+            fv.model_sigma > 0.0 && fv.book_sigma > 0.0 && fv.alt_sigma > 0.0,
+            "FV_*_SIGMA must be positive",
+        );
+        let edge = &st.edge;
+        r.check(
+            Strategy,
+            [edge.uncertainty_mult, edge.uncertainty_floor, edge.book_age_per_sec, edge.max_staleness_edge, edge.calibration_mult]
+                .iter()
+                .all(|v| *v >= 0.0),
+            "EDGE_* settings must be non-negative",
+        );
+        r.check(Strategy, st.basis.z_threshold > 0.0, "BASIS_Z_THRESHOLD must be positive");
+        let hl = &st.half_life;
+        r.check(
+            Strategy,
+            hl.min_samples >= 1 && hl.reference_secs > 0.0 && hl.min_edge_mult > 0.0 && hl.min_edge_mult <= hl.max_edge_mult
+                && hl.hold_half_lives > 0.0 && hl.min_hold_secs <= hl.max_hold_secs
+                && hl.remaining_half_lives > 0.0 && hl.min_remaining_secs <= hl.max_remaining_secs
+                && [hl.low_vol_mult, hl.high_vol_mult, hl.extreme_vol_mult].iter().all(|m| *m > 0.0),
+            "Half-life settings: HALF_LIFE_MIN_SAMPLES at least 1, multipliers positive and ordered, min hold/remaining <= max",
+        );
+        let projection = &st.projection;
+        r.check(
+            Strategy,
+            projection.latency_ms >= 0.0 && projection.max_horizon_ms >= 0.0,
+            "PRICE_PROJECTION_* latencies must be non-negative",
+        );
+        let scale_in = &st.scale_in;
+        r.check(
+            Strategy,
+            scale_in.max_total_cost_usdc >= 0.0 && scale_in.min_improvement >= 0.0,
+            "SCALE_IN_* settings must be non-negative",
+        );
+        let pre = &st.pre_position;
+        r.check(
+            Strategy,
+            !pre.enabled || (pre.lead_secs > 0.0 && pre.lead_secs <= NEXT_WINDOW_LEAD_SECS),
+            format!("PRE_POSITION_LEAD_SECS must be in (0, {NEXT_WINDOW_LEAD_SECS}]: the next window isn't subscribed earlier"),
+        );
+        if pre.enabled && !st.market_making_enabled {
+            r.warn(Strategy, "PRE_POSITION is on but no pre-position capable strategy (market making) is enabled");
+        }
+        let ml = &st.ml_filter;
+        r.check(
+            Strategy,
+            std::iter::once(&ml.min_win_prob).chain(ml.strategy_min_win_prob.values()).all(|p| (0.0..=1.0).contains(p)),
+            "ML_FILTER_MIN_PROB thresholds must be between 0 and 1",
+        );
+
+        // Conflicting flags
+        if !(st.straddle_enabled || st.arb_enabled || st.lag_exploit_enabled || st.market_making_enabled
+            || st.momentum_enabled || st.late_gamma_enabled || st.open_sniper_enabled)
+        {
+            r.warn(Conflicts, "Every strategy is disabled — the bot will only watch markets");
+        }
+        if comp.mode == CompoundingMode::Sweep && comp.sweep_address.is_none() {
+            r.warn(Conflicts, "COMPOUNDING_MODE=sweep without SWEEP_ADDRESS — profits will not be swept");
+        }
+        if st.basis.enabled && self.binance.spot_streams.is_empty() {
+            r.warn(Conflicts, "BASIS_SIGNAL is on but no Binance spot streams are configured — the signal never fires");
+        }
+        let has_mark_stream = self.binance.streams.iter().any(|s| s.contains("@markPrice"));
+        let wants_mark = std::iter::once(&st.price_series.default)
+            .chain(st.price_series.per_market.values())
+            .any(|s| *s != PriceSeries::Last);
+        if wants_mark && !has_mark_stream {
+            r.warn(Conflicts, "PRICE_SERIES asks for mark/index prices but no @markPrice stream is subscribed — falling back to last trade");
+        }
+        if self.telemetry.observer_mode && self.telemetry.journal_path.is_none() {
+            r.warn(Conflicts, "OBSERVER_MODE with TRADE_JOURNAL=off — opportunities are only summarized at shutdown");
+        }
+        if self.telemetry.shadow_ab && self.telemetry.observer_mode {
+            r.warn(Conflicts, "SHADOW_AB with OBSERVER_MODE — neither arm trades; both are only compared hypothetically");
+        }
+        if self.risk.resolution_guard.invert && !self.risk.resolution_guard.enabled {
+            r.warn(Conflicts, "RESOLUTION_GUARD_INVERT has no effect while the resolution guard is disabled");
+        }
+        r
+    }
+}
+
+/// Checks a 20-byte hex address; mixed-case addresses must carry a valid
+/// EIP-55 checksum.
+fn check_address(addr: &str) -> Result<(), String> {
+    let hex_part = addr.strip_prefix("0x").unwrap_or(addr);
+    let parsed = match hex_part.parse::<alloy_primitives::Address>() {
+        Ok(a) if hex_part.len() == 40 => a,
+        _ => return Err(format!("must be a 20-byte hex address, got {addr}")),
+    };
+    let mixed_case = hex_part.chars().any(|c| c.is_ascii_lowercase()) && hex_part.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && parsed.to_checksum(None)[2..] != *hex_part {
+        return Err(format!("{addr} fails its EIP-55 checksum — likely a typo; expected {}", parsed.to_checksum(None)));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    Warning,
+    Error,
+}
+
+/// What part of the config an issue is about; reports list issues in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigCategory {
+    Credentials,
+    Addresses,
+    Execution,
+    Risk,
+    Strategy,
+    Conflicts,
+}
+
+impl ConfigCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigCategory::Credentials => "credentials",
+            ConfigCategory::Addresses => "addresses",
+            ConfigCategory::Execution => "execution",
+            ConfigCategory::Risk => "risk",
+            ConfigCategory::Strategy => "strategy parameters",
+            ConfigCategory::Conflicts => "conflicting settings",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub severity: IssueSeverity,
+    pub category: ConfigCategory,
+    pub message: String,
+}
+
+/// Startup diagnostics from `Config::diagnose`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    fn push(&mut self, severity: IssueSeverity, category: ConfigCategory, message: impl Into<String>) {
+        self.issues.push(ConfigIssue { severity, category, message: message.into() });
+    }
+
+    fn error(&mut self, category: ConfigCategory, message: impl Into<String>) {
+        self.push(IssueSeverity::Error, category, message);
+    }
+
+    fn warn(&mut self, category: ConfigCategory, message: impl Into<String>) {
+        self.push(IssueSeverity::Warning, category, message);
+    }
+
+    /// An error unless `ok`.
+    fn check(&mut self, category: ConfigCategory, ok: bool, message: impl Into<String>) {
+        if !ok {
+            self.error(category, message);
+        }
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|i| i.severity == IssueSeverity::Error)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Log the report grouped by category, errors first within each.
+    pub fn log(&self) {
+        if self.issues.is_empty() {
+            tracing::info!("Config OK");
+            return;
+        }
+        let mut issues: Vec<_> = self.issues.iter().collect();
+        issues.sort_by_key(|i| (i.category, std::cmp::Reverse(i.severity)));
+        let warnings = self.issues.len() - self.errors().count();
+        tracing::info!("Config check: {} error(s), {warnings} warning(s)", self.errors().count());
+        for issue in issues {
+            match issue.severity {
+                IssueSeverity::Error => tracing::error!("  [{}] {}", issue.category.label(), issue.message),
+                IssueSeverity::Warning => tracing::warn!("  [{}] {}", issue.category.label(), issue.message),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn live() -> Config {
+        let mut config = Config::default();
+        config.polymarket.private_key = KEY.into();
+        config
+    }
+
+    fn messages(report: &ConfigReport, category: ConfigCategory) -> Vec<&str> {
+        report.errors().filter(|i| i.category == category).map(|i| i.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_default_config_has_no_errors() {
+        assert!(!Config::default().diagnose().has_errors());
+        assert!(!live().diagnose().has_errors());
+        assert!(live().validate().is_ok());
+    }
+
+    #[test]
+    fn test_credentials_and_addresses_are_checked() {
+        let mut config = live();
+        config.polymarket.private_key = "0x1234".into();
+        config.polymarket.signature_type = 0;
+        config.polymarket.funder_address = Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".into());
+        let report = config.diagnose();
+        assert_eq!(messages(&report, ConfigCategory::Credentials).len(), 2, "bad key + EOA with funder");
+        assert!(messages(&report, ConfigCategory::Addresses).is_empty(), "valid checksum");
+
+        // One flipped letter breaks the checksum
+        config.polymarket.private_key = KEY.into();
+        config.polymarket.signature_type = 1;
+        config.polymarket.funder_address = Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".into());
+        let report = config.diagnose();
+        assert!(messages(&report, ConfigCategory::Credentials).is_empty());
+        assert!(messages(&report, ConfigCategory::Addresses)[0].contains("EIP-55"));
+        assert!(config.validate().unwrap_err().to_string().contains("EIP-55"));
+
+        // Lowercase carries no checksum; a Gnosis safe needs its address
+        config.polymarket.funder_address = Some("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".into());
+        assert!(!config.diagnose().has_errors());
+        config.polymarket.signature_type = 2;
+        config.polymarket.funder_address = None;
+        assert_eq!(messages(&config.diagnose(), ConfigCategory::Credentials).len(), 1);
+    }
+
+    #[test]
+    fn test_ranges_and_conflicts() {
+        let mut config = Config::default();
+        config.strategy.lag_kelly_fraction = 1.5;
+        config.strategy.straddle_enabled = false;
+        config.strategy.arb_enabled = false;
+        config.strategy.lag_exploit_enabled = false;
+        config.strategy.market_making_enabled = false;
+        config.strategy.momentum_enabled = false;
+        config.strategy.late_gamma_enabled = false;
+        config.strategy.open_sniper_enabled = false;
+        let report = config.diagnose();
+        assert_eq!(messages(&report, ConfigCategory::Strategy).len(), 1);
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.category == ConfigCategory::Conflicts && i.severity == IssueSeverity::Warning));
+    }
+
+    #[test]
+    fn test_eval_throttle_by_regime_and_time_left() {
+        let vars = HashMap::from([("EVAL_THROTTLE_MS_EXTREME", "80"), ("EVAL_THROTTLE_ENDGAME_SECS", "30")]);
+        let config = Config::from_vars(|name| vars.get(name).map(|v| v.to_string()).ok_or(std::env::VarError::NotPresent));
+        let throttle = &config.strategy.eval_throttle;
+        let ms = |regime, remaining| throttle.cooldown(regime, remaining).as_millis();
+
+        assert_eq!(ms(VolRegime::Extreme, 200.0), 80);
+        assert_eq!(ms(VolRegime::Medium, 200.0), 200);
+        assert_eq!(ms(VolRegime::Dead, 200.0), 500);
+        // Near resolution everything speeds up
+        assert_eq!(ms(VolRegime::Dead, 30.0), 250);
+        assert_eq!(ms(VolRegime::Extreme, 10.0), 40);
+
+        let mut config = config;
+        config.strategy.eval_throttle.endgame_mult = 1.5;
+        assert_eq!(messages(&config.diagnose(), ConfigCategory::Strategy).len(), 1);
+        // Zero would leave no cooldown at all near resolution
+        config.strategy.eval_throttle.endgame_mult = 0.0;
+        assert_eq!(messages(&config.diagnose(), ConfigCategory::Strategy).len(), 1);
+        config.strategy.eval_throttle.endgame_mult = 0.5;
+        config.strategy.eval_throttle.extreme_ms = 0;
+        assert_eq!(messages(&config.diagnose(), ConfigCategory::Strategy).len(), 1);
     }
 }
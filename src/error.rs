@@ -0,0 +1,135 @@
+use crate::telemetry::alerts::AlertSeverity;
+use reqwest::StatusCode;
+
+/// Errors from the execution and feed paths, by what the caller should do
+/// about them rather than where they came from.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SattebaazError {
+    /// Network trouble or a 5xx: the same request may succeed if sent again
+    #[error("{0}")]
+    Transient(String),
+    /// 429: back off harder than for a transient failure
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+    /// 401/403: credentials missing, expired or revoked
+    #[error("auth expired: {0}")]
+    AuthExpired(String),
+    /// The API understood the request and refused it; sending it again won't help
+    #[error("rejected: {reason}")]
+    Rejected { reason: String },
+    /// Bugs, bad config, unparseable responses
+    #[error("{0}")]
+    Fatal(String),
+}
+
+pub type Result<T, E = SattebaazError> = std::result::Result<T, E>;
+
+impl SattebaazError {
+    /// Classify a non-success HTTP response. `detail` says what was asked
+    /// for and, ideally, what the API answered.
+    pub fn from_status(status: StatusCode, detail: impl std::fmt::Display) -> Self {
+        let msg = format!("HTTP {status} — {detail}");
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(msg),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::AuthExpired(msg),
+            s if s.is_server_error() || s == StatusCode::REQUEST_TIMEOUT => Self::Transient(msg),
+            s if s.is_client_error() => Self::Rejected { reason: msg },
+            _ => Self::Fatal(msg),
+        }
+    }
+
+    /// Classify a non-success response, reading its body for the detail.
+    pub async fn from_response(what: &str, resp: reqwest::Response) -> Self {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        Self::from_status(status, format!("{what}: {body}"))
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Transient(_) => "transient",
+            Self::RateLimited(_) => "rate_limited",
+            Self::AuthExpired(_) => "auth_expired",
+            Self::Rejected { .. } => "rejected",
+            Self::Fatal(_) => "fatal",
+        }
+    }
+
+    /// Whether sending the same request again could succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transient(_) | Self::RateLimited(_))
+    }
+
+    /// How loudly to alert when this reaches the top of a loop. Retryable
+    /// failures are expected noise and only logged; auth and fatal errors
+    /// stop trading until someone looks.
+    pub fn alert_severity(&self) -> Option<AlertSeverity> {
+        match self {
+            Self::Transient(_) | Self::RateLimited(_) => None,
+            Self::Rejected { .. } => Some(AlertSeverity::Warning),
+            Self::AuthExpired(_) | Self::Fatal(_) => Some(AlertSeverity::Critical),
+        }
+    }
+
+    /// Prefix the message with `context`, keeping the category.
+    pub fn context(self, context: impl std::fmt::Display) -> Self {
+        match self {
+            Self::Transient(m) => Self::Transient(format!("{context}: {m}")),
+            Self::RateLimited(m) => Self::RateLimited(format!("{context}: {m}")),
+            Self::AuthExpired(m) => Self::AuthExpired(format!("{context}: {m}")),
+            Self::Rejected { reason } => Self::Rejected { reason: format!("{context}: {reason}") },
+            Self::Fatal(m) => Self::Fatal(format!("{context}: {m}")),
+        }
+    }
+}
+
+impl From<reqwest::Error> for SattebaazError {
+    fn from(e: reqwest::Error) -> Self {
+        if let Some(status) = e.status() {
+            return Self::from_status(status, &e);
+        }
+        if e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() {
+            Self::Transient(e.to_string())
+        } else {
+            Self::Fatal(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for SattebaazError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Fatal(format!("invalid JSON: {e}"))
+    }
+}
+
+/// Signing, key handling and other internal steps still report through
+/// anyhow; none of them get better by retrying.
+impl From<anyhow::Error> for SattebaazError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Fatal(format!("{e:#}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_categories_drive_retry_and_alerts() {
+        let err = |code: u16| SattebaazError::from_status(StatusCode::from_u16(code).unwrap(), "post");
+        assert_eq!(err(503).category(), "transient");
+        assert_eq!(err(429).category(), "rate_limited");
+        assert_eq!(err(401).category(), "auth_expired");
+        assert_eq!(err(400).category(), "rejected");
+        assert!(err(502).is_retryable() && err(429).is_retryable());
+        assert!(!err(401).is_retryable() && !err(400).is_retryable());
+
+        assert_eq!(err(500).alert_severity(), None);
+        assert_eq!(err(400).alert_severity(), Some(AlertSeverity::Warning));
+        assert_eq!(err(403).alert_severity(), Some(AlertSeverity::Critical));
+
+        let wrapped = err(429).context("Order post");
+        assert_eq!(wrapped.category(), "rate_limited");
+        assert_eq!(wrapped.to_string(), "rate limited: Order post: HTTP 429 Too Many Requests — post");
+    }
+}
@@ -1,9 +1,17 @@
-use crate::execution::clob_client::ClobClient;
-use crate::execution::order_builder::OrderBuilder;
-use crate::models::order::{OrderIntent, OrderResult};
-use anyhow::Result;
+use crate::error::Result;
+use crate::execution::clob_client::{ClobClient, OpenOrder};
+use crate::execution::fill_tracker::{FillTracker, RestingQuote};
+use crate::execution::order_builder::{OrderBuilder, RoundConfig};
+use crate::execution::rejection::Remediation;
+use crate::models::market::Market;
+use crate::models::order::{FailurePolicy, IdempotencyKey, IntentGroup, OrderIntent, OrderResult, OrderSide, OrderStatus, OrderType};
+use crate::telemetry::events;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 /// Handles batch order submission with pre-flight validation.
 ///
@@ -12,6 +20,17 @@ use tracing::info;
 pub struct BatchSubmitter {
     order_builder: RwLock<OrderBuilder>,
     clob_client: ClobClient,
+    /// Keys of orders handed to `submit_groups`, until `dedup_ttl` runs out
+    recent: Mutex<HashMap<IdempotencyKey, Instant>>,
+    dedup_ttl: Duration,
+    /// Ticks a quote's target may move before `net` replaces it
+    requote_ticks: u32,
+    /// Orders sent by `unwind` to flatten legs that matched, until taken
+    hedges: Mutex<Vec<(OrderIntent, OrderResult)>>,
+    /// How far from its fill price `unwind` flattens a matched leg
+    unwind_slippage: Decimal,
+    /// Minimum order size per token, learned from size rejections
+    min_sizes: Mutex<HashMap<String, Decimal>>,
 }
 
 impl BatchSubmitter {
@@ -19,55 +38,393 @@ impl BatchSubmitter {
         Self {
             order_builder: RwLock::new(order_builder),
             clob_client,
+            recent: Mutex::new(HashMap::new()),
+            dedup_ttl: Duration::ZERO,
+            requote_ticks: 0,
+            hedges: Mutex::new(Vec::new()),
+            unwind_slippage: Decimal::new(5, 2),
+            min_sizes: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Submit a batch of order intents.
-    ///
-    /// 1. Build and sign all orders
-    /// 2. Submit as batch to CLOB
-    /// 3. Return results
+    /// Suppress repeats of an intent for `ttl` after it's let through
+    /// (see `dedupe`); zero disables.
+    pub fn with_dedup_ttl(mut self, ttl: Duration) -> Self {
+        self.dedup_ttl = ttl;
+        self
+    }
+
+    /// Let `net` leave a resting quote in place while its target price moves
+    /// by no more than `ticks` of the market's tick size; zero = exact.
+    pub fn with_requote_ticks(mut self, ticks: u32) -> Self {
+        self.requote_ticks = ticks;
+        self
+    }
+
+    /// Flatten matched legs of unwound trades at most `slippage` from the
+    /// price they filled at; whatever the book can't take inside that stays
+    /// on as a position for the exit logic.
+    pub fn with_unwind_slippage(mut self, slippage: Decimal) -> Self {
+        self.unwind_slippage = slippage;
+        self
+    }
+
+    /// Drop intents whose idempotency key was submitted within the TTL, or
+    /// that repeat an earlier intent in the same batch. Rapid re-evaluations
+    /// can emit the same order again before the first one acks; this stops
+    /// them spending twice. Keys are only taken once the order is handed to
+    /// `submit_groups`, so intents dropped in between can come back, and
+    /// those that end up rejected are released again.
+    pub fn dedupe(&self, intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        if self.dedup_ttl.is_zero() {
+            return intents;
+        }
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap();
+        recent.retain(|_, at| now.duration_since(*at) < self.dedup_ttl);
+        let before = intents.len();
+        let mut batch = HashSet::new();
+        let kept: Vec<_> = intents
+            .into_iter()
+            .filter(|intent| {
+                let key = intent.idempotency_key();
+                !recent.contains_key(&key) && batch.insert(key)
+            })
+            .collect();
+        if kept.len() < before {
+            debug!("Suppressed {} duplicate intents", before - kept.len());
+        }
+        kept
+    }
+
+    /// Hold these intents' keys against repeats for the TTL.
+    fn remember(&self, intents: &[OrderIntent]) {
+        if self.dedup_ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap();
+        for intent in intents {
+            recent.insert(intent.idempotency_key(), now);
+        }
+    }
+
+    /// Let these intents through `dedupe` again.
+    fn release(&self, intents: &[&OrderIntent]) {
+        if self.dedup_ttl.is_zero() {
+            return;
+        }
+        let mut recent = self.recent.lock().unwrap();
+        for intent in intents {
+            recent.remove(&intent.idempotency_key());
+        }
+    }
+
+    /// Submit a batch of order intents, keeping whatever is accepted.
     pub async fn submit(&self, intents: &[OrderIntent]) -> Result<Vec<OrderResult>> {
+        self.submit_with(intents, FailurePolicy::BestEffort).await
+    }
+
+    /// Submit a batch of order intents as one trade under `policy`.
+    pub async fn submit_with(&self, intents: &[OrderIntent], policy: FailurePolicy) -> Result<Vec<OrderResult>> {
+        self.submit_groups(&[IntentGroup::new(intents.to_vec(), policy)]).await
+    }
+
+    /// Submit trades in one batch; results follow the legs in group order.
+    ///
+    /// 1. Build and sign all orders, except legs under their token's known
+    ///    minimum size, which come back `Rejected` unsent
+    /// 2. Submit as batch to CLOB, exits ahead of entries
+    /// 3. Cancel the accepted legs of `AllOrNothing` groups that had a leg
+    ///    rejected, and flatten whatever they matched (see `take_hedges`)
+    /// 4. Return results
+    pub async fn submit_groups(&self, groups: &[IntentGroup]) -> Result<Vec<OrderResult>> {
+        let intents: Vec<OrderIntent> = groups.iter().flat_map(|g| g.legs.iter().cloned()).collect();
         if intents.is_empty() {
             return Ok(Vec::new());
         }
 
         info!("Submitting batch of {} orders", intents.len());
+        self.remember(&intents);
+
+        // Legs under their token's known minimum would only bounce off the exchange
+        let undersized: Vec<Option<Decimal>> = {
+            let min_sizes = self.min_sizes.lock().unwrap();
+            intents
+                .iter()
+                .map(|i| min_sizes.get(&i.token_id).copied().filter(|min| i.size < *min))
+                .collect()
+        };
+        let sendable: Vec<OrderIntent> = intents
+            .iter()
+            .zip(&undersized)
+            .filter(|(_, min)| min.is_none())
+            .map(|(i, _)| i.clone())
+            .collect();
 
         // Build and sign
         let builder = self.order_builder.read().await;
-        let signed = builder.build_batch(intents).await?;
+        let signed = builder.build_batch(&sendable).await?;
         drop(builder);
 
         // Pair with order types
         let orders: Vec<_> = signed
             .into_iter()
-            .zip(intents.iter())
+            .zip(sendable.iter())
             .map(|(s, i)| (s, i.order_type, i.post_only))
             .collect();
 
-        // Submit
-        let results = self.clob_client.post_orders(orders).await?;
+        // Submit; whatever didn't make it to the book may be retried
+        let mut posted = match self.clob_client.post_orders(orders).await {
+            Ok(results) => results.into_iter(),
+            Err(e) => {
+                self.release(&intents.iter().collect::<Vec<_>>());
+                return Err(e);
+            }
+        };
+        let mut results: Vec<OrderResult> = intents
+            .iter()
+            .zip(&undersized)
+            .map(|(intent, min)| match min {
+                Some(min) => undersized_result(intent, *min),
+                None => posted.next().expect("one result per posted order"),
+            })
+            .collect();
+        let rejected: Vec<_> = results
+            .iter()
+            .zip(&intents)
+            .filter(|(r, _)| r.status == OrderStatus::Rejected)
+            .map(|(_, i)| i)
+            .collect();
+        self.release(&rejected);
+
+        for (result, intent) in results.iter().zip(&intents) {
+            events::OrderSubmitted {
+                order_id: &result.order_id,
+                token_id: &intent.token_id,
+                side: intent.order_side,
+                price: intent.price,
+                size: intent.size,
+                order_type: intent.order_type,
+                strategy: &intent.strategy_tag,
+                status: result.status,
+                error: result.error_msg.as_deref(),
+            }
+            .emit();
+        }
 
         // Log summary
         let filled = results.iter().filter(|r| r.is_success()).count();
         let rejected = results.len() - filled;
         info!("Batch result: {filled} success, {rejected} rejected");
 
+        let mut start = 0;
+        for group in groups {
+            let legs = start..start + group.legs.len();
+            start = legs.end;
+            let accepted = results[legs.clone()].iter().filter(|r| r.is_success()).count();
+            if group.policy == FailurePolicy::AllOrNothing && accepted > 0 && accepted < legs.len() {
+                self.unwind(&mut results[legs.clone()], &group.legs).await;
+            }
+        }
         Ok(results)
     }
 
+    /// Take back the accepted legs of a trade that was partly rejected.
+    ///
+    /// Resting legs are cancelled and reported as `Cancelled`. A leg that
+    /// already matched — a FOK/FAK that took liquidity, or a partial fill
+    /// before the cancel — stays in the results so its fills are booked, and
+    /// the matched quantity is sold (or bought) back with a FAK bounded by
+    /// `unwind_slippage` from its fill, so the trade doesn't stay half on.
+    async fn unwind(&self, results: &mut [OrderResult], intents: &[OrderIntent]) {
+        let ids: Vec<String> = results
+            .iter()
+            .filter(|r| r.is_success() && r.remaining_size > Decimal::ZERO && !r.order_id.is_empty())
+            .map(|r| r.order_id.clone())
+            .collect();
+        let canceled = match self.clob_client.cancel_orders(&ids).await {
+            Ok(canceled) => canceled,
+            Err(e) => {
+                warn!("Batch unwind: cancelling {} accepted legs failed: {e}", ids.len());
+                Vec::new()
+            }
+        };
+
+        let mut released = Vec::new();
+        let mut hedges = Vec::new();
+        for (result, intent) in results.iter_mut().zip(intents) {
+            if !result.is_success() {
+                continue;
+            }
+            let cancelled = canceled.contains(&result.order_id);
+            if cancelled {
+                result.remaining_size = Decimal::ZERO;
+            }
+            let matched = match self.clob_client.get_order(&result.order_id).await {
+                Ok((_, matched)) => Decimal::from_f64_retain(matched).unwrap_or_default(),
+                Err(e) => {
+                    warn!("Batch unwind: {} leg {} state unknown, not flattened: {e}", intent.strategy_tag, result.order_id);
+                    continue;
+                }
+            };
+            if matched > Decimal::ZERO {
+                warn!("Batch unwind: {} leg {} already matched {matched}, flattening", intent.strategy_tag, result.order_id);
+                hedges.push(self.hedge(intent, result, matched).await);
+                continue;
+            }
+            if !cancelled {
+                warn!("Batch unwind: {} leg {} kept ({:?})", intent.strategy_tag, result.order_id, result.status);
+                continue;
+            }
+            result.status = OrderStatus::Cancelled;
+            result.error_msg = Some("cancelled: another leg was rejected".to_string());
+            released.push(intent);
+        }
+        self.release(&released);
+        info!("Batch unwind: cancelled {} of {} accepted legs", canceled.len(), ids.len());
+        if hedges.is_empty() {
+            return;
+        }
+
+        let builder = self.order_builder.read().await;
+        let signed = match builder.build_batch(&hedges).await {
+            Ok(signed) => signed,
+            Err(e) => {
+                warn!("Batch unwind: signing {} flattening orders failed: {e}", hedges.len());
+                return;
+            }
+        };
+        drop(builder);
+        match self.clob_client.post_orders(signed.into_iter().map(|s| (s, OrderType::FAK, false)).collect()).await {
+            Ok(posted) => {
+                for (hedge, result) in hedges.iter().zip(&posted) {
+                    if !result.is_success() {
+                        warn!("Batch unwind: flattening {:?} {} on {} rejected", hedge.order_side, hedge.size, hedge.token_id);
+                    }
+                }
+                self.hedges.lock().unwrap().extend(hedges.into_iter().zip(posted));
+            }
+            Err(e) => warn!("Batch unwind: flattening {} matched legs failed: {e}", hedges.len()),
+        }
+    }
+
+    /// A FAK taking `matched` of `leg` back off, priced `unwind_slippage`
+    /// through the leg's fill price (its limit if the post didn't report
+    /// one), so a thin book can't take it at any price.
+    async fn hedge(&self, leg: &OrderIntent, result: &OrderResult, matched: Decimal) -> OrderIntent {
+        let tick = Decimal::new(1, self.order_builder.read().await.round_config(&leg.token_id).price);
+        let filled_at = if result.avg_fill_price > Decimal::ZERO { result.avg_fill_price } else { leg.price };
+        let to_tick = |p: Decimal| (p / tick).round() * tick;
+        let (order_side, price) = match leg.order_side {
+            OrderSide::Buy => (OrderSide::Sell, to_tick(filled_at - self.unwind_slippage).max(tick)),
+            OrderSide::Sell => (OrderSide::Buy, to_tick(filled_at + self.unwind_slippage).min(Decimal::ONE - tick)),
+        };
+        OrderIntent {
+            order_side,
+            price,
+            size: matched,
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            ..leg.clone()
+        }
+    }
+
+    /// Orders `submit_groups` sent to flatten matched legs of trades it
+    /// unwound, since the last call. Register them like any other order so
+    /// their fills are attributed.
+    pub fn take_hedges(&self) -> Vec<(OrderIntent, OrderResult)> {
+        std::mem::take(&mut *self.hedges.lock().unwrap())
+    }
+
+    /// Net a batch against our resting orders so each strategy keeps a
+    /// single working order per token/side instead of stacking a new one
+    /// every evaluation. `plan.submit` is what still needs submitting; the
+    /// stale resting orders in `plan.cancel` stay up until `retire`.
+    pub async fn net(&self, intents: Vec<OrderIntent>, tracker: &FillTracker) -> NettingPlan {
+        let mut tolerance: HashMap<String, Decimal> = HashMap::new();
+        if self.requote_ticks > 0 {
+            let builder = self.order_builder.read().await;
+            for intent in &intents {
+                let tick = Decimal::new(1, builder.round_config(&intent.token_id).price);
+                tolerance.insert(intent.token_id.clone(), tick * Decimal::from(self.requote_ticks));
+            }
+        }
+        let plan = plan_netting_within(intents, tracker, |i| tolerance.get(&i.token_id).copied().unwrap_or_default());
+        if !plan.cancel.is_empty() || plan.kept > 0 {
+            debug!(
+                "Netting: kept {} resting, superseded {}, submitting {}",
+                plan.kept,
+                plan.cancel.len(),
+                plan.submit.len()
+            );
+        }
+        plan
+    }
+
+    /// Cancel the resting orders `plan` superseded, once `submitted` (what
+    /// went out of `plan.submit`) has its `results`. An order whose
+    /// replacement was rejected or never sent keeps working; one with no
+    /// replacement — the batch already covered its slot — goes regardless.
+    pub async fn retire(&self, plan: &NettingPlan, submitted: &[OrderIntent], results: &[OrderResult], tracker: &FillTracker) {
+        let same_slot = |q: &RestingQuote, i: &OrderIntent| {
+            q.token_id == i.token_id && q.side == i.order_side && q.strategy_tag == i.strategy_tag
+        };
+        for order_id in &plan.cancel {
+            // Gone since the plan: filled or cancelled elsewhere
+            let Some(quote) = tracker.quotes.get(order_id).map(|q| q.clone()) else { continue };
+            let replaced = plan.submit.iter().any(|i| same_slot(&quote, i));
+            let accepted = submitted.iter().zip(results).any(|(i, r)| same_slot(&quote, i) && r.is_success());
+            if replaced && !accepted {
+                debug!("Netting: keeping {order_id}, its replacement wasn't accepted");
+                continue;
+            }
+            match self.cancel_order(order_id).await {
+                Ok(()) => {
+                    tracker.quotes.remove(order_id);
+                    if let Some(mut order) = tracker.active_orders.get_mut(order_id) {
+                        order.status = OrderStatus::Cancelled;
+                    }
+                }
+                // Most likely filled or cancelled already; the fill path catches up
+                Err(e) => warn!("Netting: failed to cancel {order_id}: {e}"),
+            }
+        }
+    }
+
     /// Get the wallet address used for signing.
     pub fn address(&self) -> String {
         let builder = self.order_builder.blocking_read();
         format!("{:?}", builder.address())
     }
 
+    /// The wallet holding our conditional tokens.
+    pub async fn token_holder(&self) -> String {
+        format!("{:?}", self.order_builder.read().await.token_holder())
+    }
+
     /// Set the fee rate (bps) on the order builder.
     pub async fn set_fee_rate_bps(&self, bps: u32) {
         self.order_builder.write().await.set_fee_rate_bps(bps);
     }
 
+    /// Fee rate orders are signed with, in basis points.
+    pub async fn fee_rate_bps(&self) -> u32 {
+        self.order_builder.read().await.fee_rate_bps()
+    }
+
+    /// Set a token's tick size on the order builder, so amounts round to the
+    /// market's precision. Cheap when unchanged.
+    pub async fn set_tick_size(&self, token_id: &str, tick: Decimal) -> Result<()> {
+        let current = self.order_builder.read().await.round_config(token_id);
+        let unchanged = RoundConfig::for_tick(tick) == Some(current);
+        if !unchanged {
+            self.order_builder.write().await.set_tick_size(token_id, tick)?;
+        }
+        Ok(())
+    }
+
     /// Initialize CLOB authentication (derive L2 API key).
     pub async fn init_auth(&self) -> Result<()> {
         self.clob_client.init_auth().await
@@ -78,11 +435,63 @@ impl BatchSubmitter {
         self.clob_client.cancel_all().await
     }
 
+    /// Cancel our orders on one token only.
+    pub async fn cancel_token(&self, token_id: &str, tracker: &FillTracker) -> Result<Vec<String>> {
+        let canceled = self.clob_client.cancel_market_orders(None, Some(token_id)).await?;
+        tracker.forget_quotes(&canceled);
+        Ok(canceled)
+    }
+
+    /// Cancel our orders on one market, both tokens — by condition id in one
+    /// request when we know it, otherwise token by token.
+    pub async fn cancel_market(&self, market: &Market, tracker: &FillTracker) -> Result<Vec<String>> {
+        let canceled = match &market.condition_id {
+            Some(condition_id) => self.clob_client.cancel_market_orders(Some(condition_id), None).await?,
+            None => {
+                let mut canceled = self.clob_client.cancel_market_orders(None, Some(&market.yes_token_id)).await?;
+                canceled.extend(self.clob_client.cancel_market_orders(None, Some(&market.no_token_id)).await?);
+                canceled
+            }
+        };
+        tracker.forget_quotes(&canceled);
+        info!("Cancelled {} orders on {}", canceled.len(), market.slug);
+        Ok(canceled)
+    }
+
+    /// Cancel our resting buys on one market, both tokens, leaving the sells
+    /// that work our exits in place.
+    pub async fn cancel_entries(&self, market: &Market, tracker: &FillTracker) -> Result<Vec<String>> {
+        let ids: Vec<String> = [&market.yes_token_id, &market.no_token_id]
+            .into_iter()
+            .flat_map(|token| tracker.quotes_for(token))
+            .filter(|q| q.side == OrderSide::Buy)
+            .map(|q| q.order_id)
+            .collect();
+        let canceled = self.clob_client.cancel_orders(&ids).await?;
+        tracker.forget_quotes(&canceled);
+        info!("Cancelled {} resting entries on {}", canceled.len(), market.slug);
+        Ok(canceled)
+    }
+
+    /// Cancel the resting orders one strategy placed. The exchange doesn't
+    /// know our tags, so the orders come from `tracker`.
+    pub async fn cancel_strategy(&self, strategy_tag: &str, tracker: &FillTracker) -> Result<Vec<String>> {
+        let ids: Vec<String> = tracker.quotes_by_strategy(strategy_tag).into_iter().map(|q| q.order_id).collect();
+        let canceled = self.clob_client.cancel_orders(&ids).await?;
+        tracker.forget_quotes(&canceled);
+        Ok(canceled)
+    }
+
     /// Cancel a specific order.
     pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
         self.clob_client.cancel_order(order_id).await
     }
 
+    /// List all open orders on the account.
+    pub async fn list_open_orders(&self) -> Result<Vec<OpenOrder>> {
+        self.clob_client.list_open_orders().await
+    }
+
     /// Fetch real USDC balance from Polymarket.
     pub async fn fetch_balance(&self) -> Result<f64> {
         self.clob_client.fetch_balance().await
@@ -92,4 +501,269 @@ impl BatchSubmitter {
     pub async fn fetch_fee_rate(&self, token_id: &str) -> Result<u32> {
         self.clob_client.fetch_fee_rate(token_id).await
     }
+
+    /// Fetch the minimum tick size for a token from CLOB API.
+    pub async fn fetch_tick_size(&self, token_id: &str) -> Result<Decimal> {
+        self.clob_client.fetch_tick_size(token_id).await
+    }
+
+    /// Fetch the minimum order size for a token from CLOB API.
+    pub async fn fetch_min_order_size(&self, token_id: &str) -> Result<Decimal> {
+        self.clob_client.fetch_min_order_size(token_id).await
+    }
+
+    /// Refuse orders on `token_id` under `size` shares before they're sent.
+    pub fn set_min_order_size(&self, token_id: &str, size: Decimal) {
+        self.min_sizes.lock().unwrap().insert(token_id.to_string(), size);
+    }
+
+    /// Remediations queued by order rejections since the last call.
+    pub fn take_remediations(&self) -> Vec<(Remediation, String)> {
+        self.clob_client.rejections().take_remediations()
+    }
+
+    /// Keep the order-host connection warm; returns the round trip.
+    pub async fn prewarm(&self) -> Result<std::time::Duration> {
+        self.clob_client.prewarm().await
+    }
+
+    pub fn log_connection_summary(&self) {
+        self.clob_client.log_connection_summary();
+    }
+
+    pub fn log_rejection_summary(&self) {
+        self.clob_client.rejections().log_summary();
+    }
+}
+
+/// Rejected result for a leg kept back for being under its token's minimum.
+fn undersized_result(intent: &OrderIntent, min: Decimal) -> OrderResult {
+    OrderResult {
+        order_id: String::new(),
+        token_id: intent.token_id.clone(),
+        status: OrderStatus::Rejected,
+        filled_size: Decimal::ZERO,
+        avg_fill_price: Decimal::ZERO,
+        remaining_size: Decimal::ZERO,
+        timestamp: chrono::Utc::now(),
+        error_msg: Some(format!("not sent: size {} under the {min} minimum", intent.size)),
+    }
+}
+
+/// What netting a batch against our resting orders decided.
+#[derive(Debug, Default)]
+pub struct NettingPlan {
+    /// Intents left to submit (possibly shrunk to a top-up)
+    pub submit: Vec<OrderIntent>,
+    /// Resting orders superseded by the batch, for `retire` to cancel
+    pub cancel: Vec<String>,
+    /// Resting orders that already carry part of an intent
+    pub kept: usize,
+}
+
+/// Net `intents` against the resting quotes in `tracker`, per token, side
+/// and strategy tag — one strategy's batch never touches another's quotes:
+///
+///   - Resting orders at an intent's price that don't exceed its size stay
+///     (keeping queue priority) and the intent shrinks to the top-up, or is
+///     dropped when they already cover it.
+///   - Every other resting order in a slot the batch touches is superseded —
+///     including for taker intents, which replace the exposure.
+pub fn plan_netting(intents: Vec<OrderIntent>, tracker: &FillTracker) -> NettingPlan {
+    plan_netting_within(intents, tracker, |_| Decimal::ZERO)
+}
+
+/// `plan_netting`, except that a resting order (from the intent's own
+/// strategy tag, i.e. one ladder level) also stays when it sits within
+/// `tolerance(intent)` of the intent's price, so a target drifting by a tick
+/// doesn't cost the quote its queue position.
+pub fn plan_netting_within(
+    intents: Vec<OrderIntent>,
+    tracker: &FillTracker,
+    tolerance: impl Fn(&OrderIntent) -> Decimal,
+) -> NettingPlan {
+    let mut plan = NettingPlan::default();
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut touched: HashSet<(String, OrderSide, String)> = HashSet::new();
+
+    for mut intent in intents {
+        touched.insert((intent.token_id.clone(), intent.order_side, intent.strategy_tag.clone()));
+        if matches!(intent.order_type, OrderType::GTC | OrderType::GTD) {
+            let resting: Vec<_> = tracker
+                .quotes_for(&intent.token_id)
+                .into_iter()
+                .filter(|q| q.side == intent.order_side && q.strategy_tag == intent.strategy_tag)
+                .collect();
+            let tol = tolerance(&intent);
+            // Exact price first, then the same level's quote nearby
+            for nearby in [false, true] {
+                if nearby && tol <= Decimal::ZERO {
+                    break;
+                }
+                let matching: Vec<_> = resting
+                    .iter()
+                    .filter(|q| !claimed.contains(&q.order_id))
+                    .filter(|q| match nearby {
+                        false => q.price == intent.price,
+                        true => (q.price - intent.price).abs() <= tol,
+                    })
+                    .collect();
+                let working: Decimal = matching.iter().map(|q| q.remaining).sum();
+                if working > Decimal::ZERO && working <= intent.size {
+                    plan.kept += matching.len();
+                    claimed.extend(matching.into_iter().map(|q| q.order_id.clone()));
+                    intent.size -= working;
+                }
+            }
+            if intent.size <= Decimal::ZERO {
+                continue;
+            }
+        }
+        plan.submit.push(intent);
+    }
+
+    plan.cancel = tracker
+        .quotes
+        .iter()
+        .filter(|q| touched.contains(&(q.token_id.clone(), q.side, q.strategy_tag.clone())) && !claimed.contains(&q.order_id))
+        .map(|q| q.order_id.clone())
+        .collect();
+    plan.cancel.sort();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use rust_decimal_macros::dec;
+
+    fn intent(token: &str, side: OrderSide, price: Decimal, size: Decimal, order_type: OrderType) -> OrderIntent {
+        OrderIntent {
+            token_id: token.into(),
+            market_side: Side::Yes,
+            order_side: side,
+            price,
+            size,
+            order_type,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "mm_bid".into(),
+            group_id: None,
+        }
+    }
+
+    fn rest(tracker: &FillTracker, id: &str, order: &OrderIntent) {
+        let result = OrderResult {
+            order_id: id.into(),
+            token_id: order.token_id.clone(),
+            status: OrderStatus::Open,
+            filled_size: Decimal::ZERO,
+            avg_fill_price: Decimal::ZERO,
+            remaining_size: order.size,
+            timestamp: chrono::Utc::now(),
+            error_msg: None,
+        };
+        tracker.watch_quote(&result, order);
+    }
+
+    #[test]
+    fn test_new_price_replaces_resting_order() {
+        let tracker = FillTracker::new();
+        rest(&tracker, "old-bid", &intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC));
+        rest(&tracker, "ask", &intent("yes", OrderSide::Sell, dec!(0.55), dec!(10), OrderType::GTC));
+        rest(&tracker, "no-bid", &intent("no", OrderSide::Buy, dec!(0.40), dec!(10), OrderType::GTC));
+
+        let plan = plan_netting(
+            vec![intent("yes", OrderSide::Buy, dec!(0.46), dec!(10), OrderType::GTC)],
+            &tracker,
+        );
+        // Only the same token and side is superseded
+        assert_eq!(plan.cancel, ["old-bid"]);
+        assert_eq!(plan.submit.len(), 1);
+        assert_eq!(plan.kept, 0);
+    }
+
+    #[test]
+    fn test_same_price_keeps_resting_and_tops_up() {
+        let tracker = FillTracker::new();
+        let bid = intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC);
+        rest(&tracker, "bid", &bid);
+
+        // Unchanged quote: nothing to do
+        let plan = plan_netting(vec![bid.clone()], &tracker);
+        assert!(plan.submit.is_empty() && plan.cancel.is_empty());
+        assert_eq!(plan.kept, 1);
+
+        // Partly filled: top up the difference, keep queue priority
+        tracker.quotes.get_mut("bid").unwrap().remaining = dec!(4);
+        let plan = plan_netting(vec![bid.clone()], &tracker);
+        assert!(plan.cancel.is_empty());
+        assert_eq!(plan.submit[0].size, dec!(6));
+
+        // Resting more than wanted: replace
+        let smaller = intent("yes", OrderSide::Buy, dec!(0.45), dec!(2), OrderType::GTC);
+        let plan = plan_netting(vec![smaller], &tracker);
+        assert_eq!(plan.cancel, ["bid"]);
+        assert_eq!(plan.submit[0].size, dec!(2));
+    }
+
+    #[test]
+    fn test_quote_within_tolerance_keeps_its_place() {
+        let tracker = FillTracker::new();
+        rest(&tracker, "l1", &intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC));
+        let mut deeper = intent("yes", OrderSide::Buy, dec!(0.44), dec!(15), OrderType::GTC);
+        deeper.strategy_tag = "mm_bid_l2".into();
+        rest(&tracker, "l2", &deeper);
+        let one_tick = |_: &OrderIntent| dec!(0.01);
+
+        // Level 1 drifts a tick, level 2 moves three: only level 2 is replaced
+        let mut moved = deeper.clone();
+        moved.price = dec!(0.41);
+        let plan = plan_netting_within(
+            vec![intent("yes", OrderSide::Buy, dec!(0.46), dec!(10), OrderType::GTC), moved],
+            &tracker,
+            one_tick,
+        );
+        assert_eq!(plan.kept, 1);
+        assert_eq!(plan.cancel, ["l2"]);
+        assert_eq!(plan.submit.len(), 1);
+        assert_eq!(plan.submit[0].price, dec!(0.41));
+
+        // Another level's quote nearby doesn't count, and isn't touched
+        let mut other = intent("yes", OrderSide::Buy, dec!(0.45), dec!(15), OrderType::GTC);
+        other.strategy_tag = "mm_bid_l2".into();
+        other.price = dec!(0.46);
+        let plan = plan_netting_within(vec![other], &tracker, one_tick);
+        assert_eq!(plan.cancel, ["l2"]);
+    }
+
+    #[test]
+    fn test_other_strategies_quotes_left_alone() {
+        let tracker = FillTracker::new();
+        rest(&tracker, "mm", &intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC));
+        let mut lag = intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC);
+        lag.strategy_tag = "lag_exploit".into();
+
+        // Same price and size, but not the strategy's own order: submitted in full
+        let plan = plan_netting(vec![lag.clone()], &tracker);
+        assert!(plan.cancel.is_empty());
+        assert_eq!((plan.kept, plan.submit[0].size), (0, dec!(10)));
+
+        // A taker from another strategy doesn't pull the quote either
+        lag.order_type = OrderType::FOK;
+        assert!(plan_netting(vec![lag], &tracker).cancel.is_empty());
+    }
+
+    #[test]
+    fn test_taker_intent_cancels_resting_same_side() {
+        let tracker = FillTracker::new();
+        rest(&tracker, "bid", &intent("yes", OrderSide::Buy, dec!(0.45), dec!(10), OrderType::GTC));
+        let plan = plan_netting(
+            vec![intent("yes", OrderSide::Buy, dec!(0.50), dec!(5), OrderType::FOK)],
+            &tracker,
+        );
+        assert_eq!(plan.cancel, ["bid"]);
+        assert_eq!(plan.submit.len(), 1);
+    }
 }
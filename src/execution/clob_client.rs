@@ -1,23 +1,57 @@
 use crate::config::PolymarketConfig;
+use crate::error::{Result, SattebaazError};
 use crate::execution::clob_auth::ClobAuth;
+use crate::execution::connect_timing::{ConnectTimingLayer, ConnectionStats};
 use crate::execution::order_builder::SignedOrder;
-use crate::models::order::{OrderResult, OrderStatus, OrderType};
-use anyhow::Result;
+use crate::execution::priority::{Lane, LaneGate};
+use crate::execution::rejection::{RejectReason, RejectionStats};
+use crate::execution::retry::RetryPolicy;
+use crate::models::order::{OrderResult, OrderSide, OrderStatus, OrderType};
+use crate::telemetry::alerts::{AlertManager, AlertSeverity};
+use crate::telemetry::latency::LatencyTracker;
 use chrono::Utc;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, error, info, warn};
 
 /// REST client for Polymarket CLOB API.
 ///
 /// Handles order submission, cancellation, and book queries.
-/// Uses connection pooling and L1/L2 authentication.
+/// Uses connection pooling and L1/L2 authentication. Failures come back as
+/// [`SattebaazError`]s; transient ones and rate limits are retried under a
+/// shared [`RetryPolicy`], order posts only after confirming the order
+/// didn't land. Requests in flight are capped by a [`LaneGate`]: when it's
+/// full, cancels and sells wait in a lane that's served before new entries.
 pub struct ClobClient {
     config: PolymarketConfig,
     http: reqwest::Client,
     auth: Arc<RwLock<ClobAuth>>,
+    retry: RetryPolicy,
+    lanes: LaneGate,
+    latency: Arc<LatencyTracker>,
+    connections: Arc<ConnectionStats>,
+    rejections: RejectionStats,
+    /// Auth refusals in a row that re-deriving the key didn't fix
+    auth_failures: AtomicU32,
+    /// Bumped by each key refresh; a refusal seen under an older epoch is
+    /// already handled and only needs a replay
+    auth_epoch: AtomicU64,
+    /// Held while re-deriving, so concurrent refusals share one refresh
+    refreshing: Mutex<()>,
+    alerts: Option<Arc<AlertManager>>,
+}
+
+/// USDC available to the exchange.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collateral {
+    pub balance: f64,
+    /// None when the server didn't report one
+    pub allowance: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -41,24 +75,127 @@ struct PostOrderResponse {
     error: Option<String>,
 }
 
+/// A resting order as listed by `GET /data/orders`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenOrder {
+    pub id: String,
+    pub token_id: String,
+    pub side: OrderSide,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+impl OpenOrder {
+    fn from_json(v: &serde_json::Value) -> Option<Self> {
+        // Numeric fields arrive as strings or numbers depending on the endpoint version
+        let num = |key: &str| {
+            v.get(key)
+                .and_then(|x| x.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| x.as_f64()))
+        };
+        Some(Self {
+            id: v.get("id")?.as_str()?.to_string(),
+            token_id: v.get("asset_id").and_then(|x| x.as_str()).unwrap_or_default().to_string(),
+            side: match v.get("side").and_then(|x| x.as_str()) {
+                Some(s) if s.eq_ignore_ascii_case("SELL") => OrderSide::Sell,
+                _ => OrderSide::Buy,
+            },
+            price: num("price").unwrap_or(0.0),
+            original_size: num("original_size").unwrap_or(0.0),
+            size_matched: num("size_matched").unwrap_or(0.0),
+            created_at: num("created_at").unwrap_or(0.0) as i64,
+        })
+    }
+}
+
+/// `next_cursor` value marking the last page
+const END_CURSOR: &str = "LTE=";
+
+/// Server times outside this range (2024–2100, Unix seconds) are garbage,
+/// not a clock to sync to
+const PLAUSIBLE_SERVER_SECS: std::ops::Range<u64> = 1_704_067_200..4_102_444_800;
+
+/// LatencyTracker operation for pre-warm round trips
+pub const PREWARM_OP: &str = "clob_prewarm";
+
+/// Rejected result for an order whose post errored.
+fn failed_post(token_id: &str, err: &SattebaazError) -> OrderResult {
+    OrderResult {
+        order_id: String::new(),
+        token_id: token_id.to_string(),
+        status: OrderStatus::Rejected,
+        filled_size: Decimal::ZERO,
+        avg_fill_price: Decimal::ZERO,
+        remaining_size: Decimal::ZERO,
+        timestamp: Utc::now(),
+        error_msg: Some(format!("post failed: {err}")),
+    }
+}
+
+/// The exchange's complaint if a post was refused for our credentials,
+/// whether as a 401/403 or as an auth rejection in the body.
+fn auth_refusal(outcome: &Result<OrderResult>) -> Option<String> {
+    match outcome {
+        Err(e @ SattebaazError::AuthExpired(_)) => Some(e.to_string()),
+        Ok(r) if r.status == OrderStatus::Rejected => r
+            .error_msg
+            .clone()
+            .filter(|msg| RejectReason::parse(msg) == RejectReason::Auth),
+        _ => None,
+    }
+}
+
+/// Idle pooled connections are dropped after this; pre-warm more often
+pub const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
 impl ClobClient {
     pub fn new(config: PolymarketConfig) -> Self {
+        Self::with_latency(config, Arc::new(LatencyTracker::new(1000)))
+    }
+
+    /// Client recording connect and pre-warm times in `latency`.
+    ///
+    /// New connections are timed by a connector layer; requests served from
+    /// the pool never touch it. Reconnects resume the TLS session from
+    /// rustls's in-memory cache, which shows up as cheaper connect samples.
+    pub fn with_latency(config: PolymarketConfig, latency: Arc<LatencyTracker>) -> Self {
+        let connections = Arc::new(ConnectionStats::default());
         let http = reqwest::Client::builder()
             .pool_max_idle_per_host(8)
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
             .tcp_keepalive(Some(std::time::Duration::from_secs(30)))
+            .tcp_nodelay(true)
             .timeout(std::time::Duration::from_secs(10))
+            .connector_layer(ConnectTimingLayer::new(latency.clone(), connections.clone()))
             .build()
             .expect("Failed to build HTTP client");
 
         let auth = ClobAuth::new(&config.private_key, config.chain_id);
 
         Self {
+            retry: RetryPolicy::new(config.retry.clone()),
+            lanes: LaneGate::new(config.retry.max_in_flight),
             config,
             http,
             auth: Arc::new(RwLock::new(auth)),
+            latency,
+            connections,
+            rejections: RejectionStats::default(),
+            auth_failures: AtomicU32::new(0),
+            auth_epoch: AtomicU64::new(0),
+            refreshing: Mutex::new(()),
+            alerts: None,
         }
     }
 
+    /// Alert on auth failures that a key refresh didn't fix.
+    pub fn with_alerts(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
     /// Initialize authentication: derive API key for L2 auth.
     pub async fn init_auth(&self) -> Result<()> {
         let mut auth = self.auth.write().await;
@@ -74,6 +211,52 @@ impl ClobClient {
         }
     }
 
+    /// Re-derive the L2 key after the exchange refused the current one
+    /// (revoked, expired, or rotated elsewhere).
+    ///
+    /// `seen` is the [`auth_epoch`](Self::auth_epoch) the refused request was
+    /// signed under. Refusals racing each other queue on one refresh; those
+    /// that find it already done since `seen` just replay with the new key.
+    async fn refresh_auth(&self, cause: &str, seen: u64) -> Result<()> {
+        let _refreshing = self.refreshing.lock().await;
+        if self.auth_epoch() != seen {
+            debug!("CLOB refused our credentials ({cause}) — key already refreshed, replaying");
+            return Ok(());
+        }
+        warn!("CLOB refused our credentials ({cause}) — re-deriving L2 API key");
+        let derived = self.auth.write().await.derive_api_key(&self.config.clob_host).await;
+        match derived {
+            Ok(_) => {
+                self.auth_epoch.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(e) => Err(self.auth_failed(SattebaazError::AuthExpired(format!("key refresh failed: {e:#}"))).await),
+        }
+    }
+
+    /// Key refreshes so far.
+    fn auth_epoch(&self) -> u64 {
+        self.auth_epoch.load(Ordering::Relaxed)
+    }
+
+    /// Count an auth failure that survived a key refresh, and alert on it.
+    async fn auth_failed(&self, err: SattebaazError) -> SattebaazError {
+        let n = self.auth_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        error!("CLOB auth still failing after key refresh ({n} in a row): {err}");
+        if let Some(alerts) = &self.alerts {
+            alerts
+                .send_at(AlertSeverity::Critical, &format!("CLOB auth failing after key refresh ({n} in a row): {err}"))
+                .await;
+        }
+        err.context(format!("{n} consecutive auth failures"))
+    }
+
+    /// Auth refusals in a row that a key refresh didn't fix; 0 once a
+    /// request gets through.
+    pub fn auth_failures(&self) -> u32 {
+        self.auth_failures.load(Ordering::Relaxed)
+    }
+
     /// Build an authenticated request.
     async fn auth_request(
         &self,
@@ -83,6 +266,7 @@ impl ClobClient {
     ) -> Result<reqwest::RequestBuilder> {
         let url = format!("{}{}", self.config.clob_host, path);
         let auth = self.auth.read().await;
+        self.connections.on_request();
 
         let builder = match method.to_uppercase().as_str() {
             "POST" => self.http.post(&url),
@@ -103,12 +287,155 @@ impl ClobClient {
         }
     }
 
+    /// Build an unauthenticated GET.
+    fn public_request(&self, url: &str) -> reqwest::RequestBuilder {
+        self.connections.on_request();
+        self.http.get(url)
+    }
+
+    /// Keep a warm connection to the order host so the next order doesn't
+    /// pay TCP + TLS setup: one lightweight authenticated request. Returns
+    /// the round trip, also recorded as `clob_prewarm`.
+    pub async fn prewarm(&self) -> Result<Duration> {
+        let path = format!(
+            "/balance-allowance?asset_type=COLLATERAL&signature_type={}",
+            self.config.signature_type
+        );
+        let start = Instant::now();
+        let resp = self.auth_request("GET", &path, "").await?.send().await?;
+        let rtt = start.elapsed();
+        self.latency.record(PREWARM_OP, rtt);
+        if !resp.status().is_success() {
+            debug!("Pre-warm got HTTP {} (connection still warmed)", resp.status());
+        }
+        Ok(rtt)
+    }
+
+    /// Round trip of an authenticated `POST /order` the exchange refuses at
+    /// validation (empty order): the order path's auth and ack latency,
+    /// without a real order. Not retried.
+    pub async fn probe_order_ack(&self) -> Result<Duration> {
+        let start = Instant::now();
+        let resp = self
+            .auth_request("POST", "/order", "{}")
+            .await?
+            .header("Content-Type", "application/json")
+            .body("{}")
+            .send()
+            .await?;
+        let rtt = start.elapsed();
+        debug!("Order-ack probe: HTTP {}", resp.status());
+        Ok(rtt)
+    }
+
+    /// Connections opened vs requests sent.
+    pub fn connection_stats(&self) -> &ConnectionStats {
+        &self.connections
+    }
+
+    pub fn log_connection_summary(&self) {
+        let stats = &self.connections;
+        info!(
+            "CLOB connections: {} opened for {} requests ({:.1}% reused)",
+            stats.connects(),
+            stats.requests(),
+            stats.reuse_rate() * 100.0,
+        );
+    }
+
+    /// Rejection counts by reason, and the remediations still to run.
+    pub fn rejections(&self) -> &RejectionStats {
+        &self.rejections
+    }
+
+    /// Send an idempotent request, rebuilding it (fresh auth timestamp) for
+    /// each retry. Failures are retried while their category allows; once
+    /// that stops, an error response is returned for the caller to handle.
+    /// A 401/403 re-derives the API key and replays the request once. Each
+    /// attempt waits for a slot in `lane`; backoffs don't hold one.
+    async fn send_retrying<F, Fut>(&self, what: &str, lane: Lane, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<reqwest::RequestBuilder>>,
+    {
+        let mut attempt = 0;
+        let mut reauthed = false;
+        loop {
+            let epoch = self.auth_epoch();
+            let request = build().await?;
+            let sent = {
+                let _slot = self.lanes.acquire(lane).await;
+                request.send().await
+            };
+            match sent {
+                Ok(resp) if resp.status().is_success() => {
+                    self.auth_failures.store(0, Ordering::Relaxed);
+                    return Ok(resp);
+                }
+                Ok(resp) => {
+                    let err = SattebaazError::from_status(resp.status(), what);
+                    if let SattebaazError::AuthExpired(_) = err {
+                        if !reauthed {
+                            reauthed = true;
+                            self.refresh_auth(&err.to_string(), epoch).await?;
+                            continue;
+                        }
+                        self.auth_failed(err).await;
+                        return Ok(resp);
+                    }
+                    if !self.retry.wait(attempt, what, &err).await {
+                        return Ok(resp);
+                    }
+                }
+                Err(e) => {
+                    let err = SattebaazError::from(e);
+                    if !self.retry.wait(attempt, what, &err).await {
+                        return Err(err.context(what));
+                    }
+                }
+            }
+            attempt += 1;
+        }
+    }
+
     /// Submit a single order to the CLOB.
+    ///
+    /// If the exchange refuses our credentials, the API key is re-derived and
+    /// the order posted once more — it was refused before being looked at, so
+    /// it can't land twice. A second refusal is returned as `AuthExpired`.
     pub async fn post_order(
         &self,
         signed: SignedOrder,
         order_type: OrderType,
         post_only: bool,
+    ) -> Result<OrderResult> {
+        let epoch = self.auth_epoch();
+        let first = self.post_order_once(&signed, order_type, post_only).await;
+        let Some(cause) = auth_refusal(&first) else {
+            return first;
+        };
+        self.refresh_auth(&cause, epoch).await?;
+        let replay = self.post_order_once(&signed, order_type, post_only).await;
+        match auth_refusal(&replay) {
+            Some(cause) => Err(self.auth_failed(SattebaazError::AuthExpired(format!("Order post: {cause}"))).await),
+            None => {
+                info!("Order post succeeded after L2 key refresh");
+                replay
+            }
+        }
+    }
+
+    /// One post of a signed order.
+    ///
+    /// Re-posting a signed order is only safe if the first post never landed.
+    /// Connection failures and 429s weren't processed and are retried as-is;
+    /// after a timeout or 5xx the order is looked up by its hash first, and an
+    /// order that did land is reported as accepted instead of being re-sent.
+    async fn post_order_once(
+        &self,
+        signed: &SignedOrder,
+        order_type: OrderType,
+        post_only: bool,
     ) -> Result<OrderResult> {
         let ot_str = match order_type {
             OrderType::GTC => "GTC",
@@ -135,13 +462,63 @@ impl ClobClient {
         let original_size_dec = Decimal::from_f64_retain(original_size).unwrap_or(Decimal::ZERO);
 
         let body_json = serde_json::to_string(&req_body)?;
-        let request = self.auth_request("POST", "/order", &body_json).await?;
+        let accepted = |order_id: String| OrderResult {
+            order_id,
+            token_id: signed.token_id.clone(),
+            status: OrderStatus::Open,
+            filled_size: Decimal::ZERO,
+            avg_fill_price: Decimal::ZERO,
+            remaining_size: original_size_dec,
+            timestamp: Utc::now(),
+            error_msg: None,
+        };
 
-        let resp = request
-            .header("Content-Type", "application/json")
-            .body(body_json)
-            .send()
-            .await?;
+        let mut attempt = 0;
+        let resp = loop {
+            let request = self.auth_request("POST", "/order", &body_json).await?;
+            let sent = {
+                let _slot = self.lanes.acquire(Lane::of(signed)).await;
+                request.header("Content-Type", "application/json").body(body_json.clone()).send().await
+            };
+
+            let err = match sent {
+                Ok(resp) if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    SattebaazError::from_status(resp.status(), "Order post")
+                }
+                Ok(resp) if resp.status().is_server_error() && !signed.order_hash.is_empty() => {
+                    let err = SattebaazError::from_status(resp.status(), "Order post");
+                    if self.landed(&signed.order_hash, &err).await? {
+                        return Ok(accepted(signed.order_hash.clone()));
+                    }
+                    if !self.retry.wait(attempt, "Order post", &err).await {
+                        break resp;
+                    }
+                    attempt += 1;
+                    continue;
+                }
+                // Not an order rejection: nothing will be accepted until auth is fixed
+                Ok(resp) if matches!(
+                    resp.status(),
+                    reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+                ) => {
+                    return Err(SattebaazError::from_response("Order post", resp).await);
+                }
+                Ok(resp) => break resp,
+                Err(e) if e.is_connect() => e.into(),
+                Err(e) if !signed.order_hash.is_empty() => {
+                    let err = SattebaazError::from(e);
+                    if self.landed(&signed.order_hash, &err).await? {
+                        return Ok(accepted(signed.order_hash.clone()));
+                    }
+                    err
+                }
+                Err(e) => return Err(SattebaazError::from(e).context("Order post")),
+            };
+            if !self.retry.wait(attempt, "Order post", &err).await {
+                return Err(err.context("Order post"));
+            }
+            attempt += 1;
+        };
 
         let status_code = resp.status();
         let resp_text = resp.text().await?;
@@ -158,26 +535,19 @@ impl ClobClient {
         });
 
         if body.success.unwrap_or(false) {
+            self.auth_failures.store(0, Ordering::Relaxed);
             info!("Order submitted: id={}", body.order_id.as_deref().unwrap_or("?"));
-            Ok(OrderResult {
-                order_id: body.order_id.unwrap_or_default(),
-                token_id: signed.token_id,
-                status: OrderStatus::Open,
-                filled_size: Decimal::ZERO,
-                avg_fill_price: Decimal::ZERO,
-                remaining_size: original_size_dec,
-                timestamp: Utc::now(),
-                error_msg: None,
-            })
+            Ok(accepted(body.order_id.unwrap_or_default()))
         } else {
             // API returns "error" on rejections, "errorMsg" on other failures — check both
             let err = body.error
                 .or(body.error_msg)
                 .unwrap_or_else(|| format!("HTTP {status_code}"));
-            error!("Order rejected: {err}");
+            let reason = self.rejections.record(&err, &signed.token_id);
+            error!("Order rejected ({}): {err}", reason.label());
             Ok(OrderResult {
                 order_id: String::new(),
-                token_id: signed.token_id,
+                token_id: signed.token_id.clone(),
                 status: OrderStatus::Rejected,
                 filled_size: Decimal::ZERO,
                 avg_fill_price: Decimal::ZERO,
@@ -188,23 +558,59 @@ impl ClobClient {
         }
     }
 
-    /// Submit a batch of orders (preferred for arb legs).
+    /// Whether an order whose post failed ambiguously made it onto the
+    /// exchange. Errors if that can't be determined — re-posting blind could
+    /// double the position.
+    async fn landed(&self, order_hash: &str, cause: &SattebaazError) -> Result<bool> {
+        match self.fetch_order(order_hash).await {
+            Ok(Some(_)) => {
+                warn!("Order post failed ({cause}) but {order_hash} is on the book — not re-sending");
+                Ok(true)
+            }
+            Ok(None) => Ok(false),
+            Err(e) => Err(e.context(format!("order {order_hash} state unknown after failed post ({cause})"))),
+        }
+    }
+
+    /// Submit a batch of orders (preferred for arb legs). Exits go out
+    /// before entries; results come back in the order given.
+    ///
+    /// An order whose post fails is reported as rejected, so the orders
+    /// around it that were accepted still come back to be tracked. Only a
+    /// batch where every post failed is an error.
     pub async fn post_orders(
         &self,
         orders: Vec<(SignedOrder, OrderType, bool)>,
     ) -> Result<Vec<OrderResult>> {
+        let mut orders: Vec<_> = orders.into_iter().enumerate().collect();
+        orders.sort_by_key(|(_, (signed, _, _))| Lane::of(signed) != Lane::High);
         let mut results = Vec::with_capacity(orders.len());
-        for (signed, ot, po) in orders {
-            let result = self.post_order(signed, ot, po).await?;
-            results.push(result);
+        let mut first_err = None;
+        for (i, (signed, ot, po)) in orders {
+            let token_id = signed.token_id.clone();
+            let result = match self.post_order(signed, ot, po).await {
+                Ok(result) => result,
+                Err(e) => {
+                    error!("Order post failed: {e}");
+                    let result = failed_post(&token_id, &e);
+                    first_err.get_or_insert(e);
+                    result
+                }
+            };
+            results.push((i, result));
         }
-        Ok(results)
+        if let Some(e) = first_err.filter(|_| results.iter().all(|(_, r)| r.status == OrderStatus::Rejected)) {
+            return Err(e);
+        }
+        results.sort_by_key(|(i, _)| *i);
+        Ok(results.into_iter().map(|(_, r)| r).collect())
     }
 
     /// Cancel all open orders.
     pub async fn cancel_all(&self) -> Result<()> {
-        let request = self.auth_request("DELETE", "/cancel-all", "").await?;
-        let resp = request.send().await?;
+        let resp = self
+            .send_retrying("Cancel all", Lane::High, || self.auth_request("DELETE", "/cancel-all", ""))
+            .await?;
 
         if resp.status().is_success() {
             info!("All orders cancelled");
@@ -215,11 +621,52 @@ impl ClobClient {
         Ok(())
     }
 
+    /// Cancel our open orders on one market (condition id) and/or token via
+    /// `DELETE /cancel-market-orders`, leaving every other market alone.
+    /// Returns the IDs the exchange cancelled.
+    pub async fn cancel_market_orders(&self, market: Option<&str>, asset_id: Option<&str>) -> Result<Vec<String>> {
+        let body = serde_json::json!({ "market": market.unwrap_or(""), "asset_id": asset_id.unwrap_or("") }).to_string();
+        self.send_cancel("Cancel market orders", "/cancel-market-orders", body).await
+    }
+
+    /// Cancel several orders by ID in one request (`DELETE /orders`).
+    /// Returns the IDs the exchange cancelled.
+    pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<Vec<String>> {
+        if order_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send_cancel("Cancel orders", "/orders", serde_json::to_string(order_ids)?).await
+    }
+
+    async fn send_cancel(&self, what: &str, path: &str, body: String) -> Result<Vec<String>> {
+        let resp = self
+            .send_retrying(what, Lane::High, || async {
+                Ok(self
+                    .auth_request("DELETE", path, &body)
+                    .await?
+                    .header("Content-Type", "application/json")
+                    .body(body.clone()))
+            })
+            .await?;
+        if !resp.status().is_success() {
+            return Err(SattebaazError::from_response(what, resp).await);
+        }
+        let val: serde_json::Value = resp.json().await?;
+        let canceled: Vec<String> = val
+            .get("canceled")
+            .and_then(|c| c.as_array())
+            .map(|ids| ids.iter().filter_map(|id| id.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        debug!("{what}: {} cancelled", canceled.len());
+        Ok(canceled)
+    }
+
     /// Cancel a specific order by ID.
     pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
         let path = format!("/order/{}", order_id);
-        let request = self.auth_request("DELETE", &path, "").await?;
-        let resp = request.send().await?;
+        let resp = self
+            .send_retrying("Cancel", Lane::High, || self.auth_request("DELETE", &path, ""))
+            .await?;
 
         if resp.status().is_success() {
             debug!("Cancelled order {order_id}");
@@ -233,14 +680,24 @@ impl ClobClient {
     /// Get order status by ID. Returns (status_string, size_matched).
     /// Status: "LIVE", "MATCHED", "CANCELLED", "DELAYED", etc.
     pub async fn get_order(&self, order_id: &str) -> Result<(String, f64)> {
+        match self.fetch_order(order_id).await? {
+            Some(order) => Ok(order),
+            None => Err(SattebaazError::Rejected { reason: format!("Get order: {order_id} not found") }),
+        }
+    }
+
+    /// Like `get_order`, but an unknown order is `None` rather than an error.
+    async fn fetch_order(&self, order_id: &str) -> Result<Option<(String, f64)>> {
         let path = format!("/order/{}", order_id);
-        let request = self.auth_request("GET", &path, "").await?;
-        let resp = request.send().await?;
+        let resp = self
+            .send_retrying("Get order", Lane::Low, || self.auth_request("GET", &path, ""))
+            .await?;
 
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Get order failed: HTTP {status} — {body}");
+            return Err(SattebaazError::from_response("Get order", resp).await);
         }
 
         let val: serde_json::Value = resp.json().await?;
@@ -252,14 +709,57 @@ impl ClobClient {
             .and_then(|v| v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64()))
             .unwrap_or(0.0);
 
-        Ok((status, size_matched))
+        Ok(Some((status, size_matched)))
+    }
+
+    /// List every open order on the account, following pagination.
+    pub async fn list_open_orders(&self) -> Result<Vec<OpenOrder>> {
+        let mut orders = Vec::new();
+        let mut cursor: Option<String> = None;
+        // Bounded: a misbehaving cursor can't spin forever
+        for _ in 0..100 {
+            let resp = self
+                .send_retrying("List orders", Lane::Low, || async {
+                    let request = self.auth_request("GET", "/data/orders", "").await?;
+                    Ok(match &cursor {
+                        Some(c) => request.query(&[("next_cursor", c)]),
+                        None => request,
+                    })
+                })
+                .await?;
+            if !resp.status().is_success() {
+                return Err(SattebaazError::from_response("List orders", resp).await);
+            }
+
+            let val: serde_json::Value = resp.json().await?;
+            let page = val.get("data").and_then(|d| d.as_array()).cloned().unwrap_or_default();
+            orders.extend(page.iter().filter_map(OpenOrder::from_json));
+
+            match val.get("next_cursor").and_then(|c| c.as_str()) {
+                Some(next) if next != END_CURSOR && !next.is_empty() && !page.is_empty() => {
+                    cursor = Some(next.to_string())
+                }
+                _ => return Ok(orders),
+            }
+        }
+        Err(SattebaazError::Fatal("List orders: too many pages".into()))
     }
 
     /// Get server time (for clock synchronization).
     pub async fn get_server_time(&self) -> Result<u64> {
         let url = format!("{}/time", self.config.clob_host);
-        let resp: serde_json::Value = self.http.get(&url).send().await?.json().await?;
-        let ts = resp.as_f64().unwrap_or(0.0) as u64;
+        let resp: serde_json::Value = self
+            .send_retrying("Server time", Lane::Low, || async { Ok(self.public_request(&url)) })
+            .await?
+            .json()
+            .await?;
+        let ts = resp.as_u64()
+            .or_else(|| resp.as_f64().map(|t| t as u64))
+            .or_else(|| resp.as_str().and_then(|t| t.trim().parse().ok()))
+            .ok_or_else(|| SattebaazError::Fatal(format!("Server time: unparsable response {resp}")))?;
+        if !PLAUSIBLE_SERVER_SECS.contains(&ts) {
+            return Err(SattebaazError::Fatal(format!("Server time: implausible {ts}")));
+        }
         Ok(ts)
     }
 
@@ -267,7 +767,9 @@ impl ClobClient {
     /// Returns true for neg risk markets (e.g., multi-outcome), false otherwise.
     pub async fn fetch_neg_risk(&self, token_id: &str) -> Result<bool> {
         let url = format!("{}/neg-risk?token_id={}", self.config.clob_host, token_id);
-        let resp = self.http.get(&url).send().await?;
+        let resp = self
+            .send_retrying("Neg risk", Lane::Low, || async { Ok(self.public_request(&url)) })
+            .await?;
 
         if !resp.status().is_success() {
             info!("Neg risk endpoint returned {}, defaulting to false", resp.status());
@@ -292,7 +794,9 @@ impl ClobClient {
     /// Formula: fee_per_share = p × (1-p) × (fee_rate_bps / 10000)
     pub async fn fetch_fee_rate(&self, token_id: &str) -> Result<u32> {
         let url = format!("{}/fee-rate?token_id={}", self.config.clob_host, token_id);
-        let resp = self.http.get(&url).send().await?;
+        let resp = self
+            .send_retrying("Fee rate", Lane::Low, || async { Ok(self.public_request(&url)) })
+            .await?;
 
         if !resp.status().is_success() {
             info!("Fee rate endpoint returned {}, defaulting to 1000", resp.status());
@@ -322,38 +826,94 @@ impl ClobClient {
         Ok(bps)
     }
 
+    /// Fetch the minimum tick size for a token.
+    /// Response: { "minimum_tick_size": 0.01 }
+    pub async fn fetch_tick_size(&self, token_id: &str) -> Result<Decimal> {
+        let url = format!("{}/tick-size?token_id={}", self.config.clob_host, token_id);
+        let resp = self
+            .send_retrying("Tick size", Lane::Low, || async { Ok(self.public_request(&url)) })
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SattebaazError::from_response("Tick size", resp).await);
+        }
+
+        let val: serde_json::Value = resp.json().await?;
+        let tick = val
+            .get("minimum_tick_size")
+            .and_then(|v| v.as_str().map(str::to_string).or_else(|| v.as_f64().map(|f| f.to_string())))
+            .and_then(|s| s.parse::<Decimal>().ok())
+            .ok_or_else(|| SattebaazError::Fatal(format!("Tick size: unexpected response {val}")))?;
+        Ok(tick)
+    }
+
+    /// Fetch the smallest order a token's book takes, in shares.
+    /// Uses the public GET /book endpoint, which reports it as `min_order_size`.
+    pub async fn fetch_min_order_size(&self, token_id: &str) -> Result<Decimal> {
+        let url = format!("{}/book?token_id={}", self.config.clob_host, token_id);
+        let resp = self
+            .send_retrying("Min order size", Lane::Low, || async { Ok(self.public_request(&url)) })
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SattebaazError::from_response("Min order size", resp).await);
+        }
+
+        let val: serde_json::Value = resp.json().await?;
+        let size = val
+            .get("min_order_size")
+            .and_then(|v| v.as_str().map(str::to_string).or_else(|| v.as_f64().map(|f| f.to_string())))
+            .and_then(|s| s.parse::<Decimal>().ok())
+            .ok_or_else(|| SattebaazError::Fatal(format!("Min order size: unexpected response {val}")))?;
+        Ok(size)
+    }
+
     /// Fetch available USDC balance from Polymarket profile.
-    /// Uses authenticated GET /balance-allowance?asset_type=COLLATERAL endpoint.
-    /// Response: { "balance": "5.123456", "allowance": "..." }
     pub async fn fetch_balance(&self) -> Result<f64> {
+        Ok(self.fetch_collateral().await?.balance)
+    }
+
+    /// USDC balance and exchange allowance.
+    /// Uses authenticated GET /balance-allowance?asset_type=COLLATERAL endpoint.
+    /// Response: { "balance": "5.123456", "allowance": "..." } — newer servers
+    /// send "allowances": { <exchange>: "..." } instead.
+    pub async fn fetch_collateral(&self) -> Result<Collateral> {
         let sig_type = self.config.signature_type;
         let path = format!("/balance-allowance?asset_type=COLLATERAL&signature_type={sig_type}");
-        let request = self.auth_request("GET", &path, "").await?;
-        let resp = request.send().await?;
+        let resp = self
+            .send_retrying("Balance", Lane::Low, || self.auth_request("GET", &path, ""))
+            .await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Balance fetch failed: HTTP {status} — {body}");
+            return Err(SattebaazError::from_response("Balance fetch", resp).await);
         }
 
-        // Response: {"balance": "5.123456", "allowance": "..."}
         let text = resp.text().await?;
         let val: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+        let amount = |v: &serde_json::Value| {
+            v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64())
+        };
 
         let raw = if let Some(b) = val.get("balance") {
-            b.as_str()
-                .and_then(|s| s.parse::<f64>().ok())
-                .or_else(|| b.as_f64())
-                .unwrap_or(0.0)
+            amount(b).unwrap_or(0.0)
         } else if let Some(b) = val.as_f64() {
             b
         } else {
             text.trim().parse::<f64>().unwrap_or(0.0)
         };
+        // The tightest exchange allowance is the one that can block an order
+        let allowance = val.get("allowance").and_then(amount).or_else(|| {
+            val.get("allowances")?
+                .as_object()?
+                .values()
+                .filter_map(amount)
+                .reduce(f64::min)
+        });
 
-        // API returns balance in micro-units (USDC has 6 decimals)
-        let balance = raw / 1_000_000.0;
-        Ok(balance)
+        // API returns amounts in micro-units (USDC has 6 decimals)
+        Ok(Collateral {
+            balance: raw / 1_000_000.0,
+            allowance: allowance.map(|a| a / 1_000_000.0),
+        })
     }
 }
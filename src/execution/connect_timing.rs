@@ -0,0 +1,100 @@
+use crate::telemetry::latency::LatencyTracker;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+/// LatencyTracker operation for new CLOB connections (TCP + TLS handshake)
+pub const CONNECT_OP: &str = "clob_connect";
+
+/// Counts new connections against requests, so pool reuse can be checked:
+/// with keep-alive working, connects stay flat while requests climb.
+#[derive(Default)]
+pub struct ConnectionStats {
+    connects: AtomicU64,
+    requests: AtomicU64,
+}
+
+impl ConnectionStats {
+    pub fn on_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn connects(&self) -> u64 {
+        self.connects.load(Ordering::Relaxed)
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Share of requests that rode an existing connection.
+    pub fn reuse_rate(&self) -> f64 {
+        let requests = self.requests();
+        if requests == 0 {
+            return 0.0;
+        }
+        1.0 - (self.connects() as f64 / requests as f64).min(1.0)
+    }
+}
+
+/// reqwest connector layer timing each connection it opens. Reused pooled
+/// connections never reach the connector, so every sample is a cold connect.
+#[derive(Clone)]
+pub struct ConnectTimingLayer {
+    latency: Arc<LatencyTracker>,
+    stats: Arc<ConnectionStats>,
+}
+
+impl ConnectTimingLayer {
+    pub fn new(latency: Arc<LatencyTracker>, stats: Arc<ConnectionStats>) -> Self {
+        Self { latency, stats }
+    }
+}
+
+impl<S> Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner, layer: self.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct ConnectTiming<S> {
+    inner: S,
+    layer: ConnectTimingLayer,
+}
+
+impl<S, R> Service<R> for ConnectTiming<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+    S::Response: 'static,
+    S::Error: 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let start = Instant::now();
+        let connecting = self.inner.call(req);
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let conn = connecting.await;
+            if conn.is_ok() {
+                layer.latency.record(CONNECT_OP, start.elapsed());
+                layer.stats.connects.fetch_add(1, Ordering::Relaxed);
+            }
+            conn
+        })
+    }
+}
@@ -1,6 +1,8 @@
-use crate::models::order::{Fill, OrderResult, OrderStatus};
+use crate::feeds::user_ws::OrderUpdate;
+use crate::models::order::{Fill, OrderIntent, OrderResult, OrderSide, OrderStatus, OrderType};
 use dashmap::DashMap;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -12,6 +14,19 @@ pub struct FillTracker {
     pub active_orders: Arc<DashMap<String, OrderResult>>,
     /// Completed fills
     pub fills: Arc<DashMap<String, Vec<Fill>>>,
+    /// Our resting limit orders: order_id → where they sit on the book
+    pub quotes: Arc<DashMap<String, RestingQuote>>,
+}
+
+/// A resting GTC/GTD order's place on the book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestingQuote {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub remaining: Decimal,
+    pub strategy_tag: String,
 }
 
 impl FillTracker {
@@ -19,6 +34,7 @@ impl FillTracker {
         Self {
             active_orders: Arc::new(DashMap::new()),
             fills: Arc::new(DashMap::new()),
+            quotes: Arc::new(DashMap::new()),
         }
     }
 
@@ -30,6 +46,59 @@ impl FillTracker {
         }
     }
 
+    /// Remember where a successfully submitted limit order rests. Orders that
+    /// filled immediately or can't rest (FOK/FAK) are ignored.
+    pub fn watch_quote(&self, result: &OrderResult, intent: &OrderIntent) {
+        let rests = matches!(intent.order_type, OrderType::GTC | OrderType::GTD);
+        if !rests || result.order_id.is_empty() || result.remaining_size <= Decimal::ZERO {
+            return;
+        }
+        self.quotes.insert(
+            result.order_id.clone(),
+            RestingQuote {
+                order_id: result.order_id.clone(),
+                token_id: intent.token_id.clone(),
+                side: intent.order_side,
+                price: intent.price,
+                remaining: result.remaining_size,
+                strategy_tag: intent.strategy_tag.clone(),
+            },
+        );
+    }
+
+    /// Our resting orders on a token.
+    pub fn quotes_for(&self, token_id: &str) -> Vec<RestingQuote> {
+        self.quotes
+            .iter()
+            .filter(|q| q.token_id == token_id)
+            .map(|q| q.clone())
+            .collect()
+    }
+
+    /// Our resting orders placed by `strategy_tag`.
+    pub fn quotes_by_strategy(&self, strategy_tag: &str) -> Vec<RestingQuote> {
+        self.quotes
+            .iter()
+            .filter(|q| q.strategy_tag == strategy_tag)
+            .map(|q| q.clone())
+            .collect()
+    }
+
+    /// Forget resting quotes the exchange confirmed cancelled.
+    pub fn forget_quotes(&self, order_ids: &[String]) {
+        for id in order_ids {
+            self.quotes.remove(id);
+            if let Some(mut order) = self.active_orders.get_mut(id) {
+                order.status = OrderStatus::Cancelled;
+            }
+        }
+    }
+
+    /// Forget all resting quotes (after a cancel-all).
+    pub fn clear_quotes(&self) {
+        self.quotes.clear();
+    }
+
     /// Process a fill event (called from WebSocket handler).
     pub fn on_fill(&self, fill: Fill) {
         let order_id = fill.order_id.clone();
@@ -51,13 +120,48 @@ impl FillTracker {
             );
         }
 
+        let quote_done = match self.quotes.get_mut(&order_id) {
+            Some(mut quote) => {
+                quote.remaining -= fill.size;
+                quote.remaining <= Decimal::ZERO
+            }
+            None => false,
+        };
+        if quote_done {
+            self.quotes.remove(&order_id);
+        }
+
         // Store fill
         self.fills
             .entry(order_id)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(fill);
     }
 
+    /// Apply a status transition from the user channel. Fill sizes stay with
+    /// `on_fill`; this settles status, so a cancel or match is seen without
+    /// polling the order.
+    pub fn on_order_update(&self, update: &OrderUpdate) {
+        if let Some(mut order) = self.active_orders.get_mut(&update.order_id) {
+            match update.status {
+                OrderStatus::Cancelled | OrderStatus::Filled => {
+                    if order.status != update.status {
+                        info!("Order {} {:?} (matched {})", update.order_id, update.status, update.size_matched);
+                    }
+                    order.status = update.status;
+                    order.remaining_size = Decimal::ZERO;
+                }
+                OrderStatus::PartiallyFilled if order.status == OrderStatus::Open => {
+                    order.status = OrderStatus::PartiallyFilled;
+                }
+                _ => {}
+            }
+        }
+        if update.is_final() {
+            self.quotes.remove(&update.order_id);
+        }
+    }
+
     /// Check if an order is fully filled.
     pub fn is_filled(&self, order_id: &str) -> bool {
         self.active_orders
@@ -82,6 +186,8 @@ impl FillTracker {
                 OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
             )
         });
+        let active = &self.active_orders;
+        self.quotes.retain(|id, _| active.contains_key(id));
     }
 }
 
@@ -1,6 +1,14 @@
 pub mod order_builder;
 pub mod clob_auth;
 pub mod clob_client;
+pub mod connect_timing;
 pub mod batch_submitter;
 pub mod fill_tracker;
+pub mod order_sweeper;
 pub mod polygon_merger;
+pub mod rejection;
+pub mod priority;
+pub mod retry;
+pub mod session;
+pub mod settlement;
+pub mod order_registry;
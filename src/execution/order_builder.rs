@@ -1,11 +1,15 @@
 use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::sim::rng::SimRng;
 use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_sol_types::{sol, Eip712Domain, SolStruct};
 use anyhow::Result;
 use rand::Rng;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
 use tracing::debug;
 
 // Use alloy's sol! macro to get the canonical EIP-712 hash computation
@@ -72,6 +76,10 @@ pub struct OrderBuilder {
     signature_type: u8,
     use_neg_risk: bool,
     fee_rate_bps: u32,
+    /// Rounding per token for markets off the standard 0.01 tick
+    round_configs: HashMap<String, RoundConfig>,
+    /// Salt source for seeded runs; thread RNG otherwise
+    salt_rng: Option<Mutex<SimRng>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +98,9 @@ pub struct SignedOrder {
     pub side: String,
     pub signature_type: u8,
     pub signature: String,
+    /// EIP-712 digest — the CLOB's order ID. Not part of the posted payload.
+    #[serde(skip)]
+    pub order_hash: String,
 }
 
 /// Raw order struct for EIP-712 hashing
@@ -108,6 +119,24 @@ struct RawOrder {
     signature_type: u8,
 }
 
+/// The Polymarket proxy wallet for an EOA, via CREATE2 — matches the
+/// official rs-clob-client `derive_proxy_wallet()`.
+pub fn derive_proxy_wallet(eoa: Address) -> Address {
+    // CREATE2: salt = keccak256(eoa_address packed 20 bytes)
+    let salt = keccak256(eoa.as_slice());
+    let factory = PROXY_WALLET_FACTORY.parse::<Address>().unwrap();
+    let init_hash = B256::from(PROXY_INIT_CODE_HASH);
+
+    // CREATE2 address = keccak256(0xff ++ factory ++ salt ++ init_code_hash)[12..]
+    let mut create2_input = Vec::with_capacity(85);
+    create2_input.push(0xff);
+    create2_input.extend_from_slice(factory.as_slice());
+    create2_input.extend_from_slice(salt.as_slice());
+    create2_input.extend_from_slice(init_hash.as_slice());
+    let derived_hash = keccak256(&create2_input);
+    Address::from_slice(&derived_hash[12..])
+}
+
 impl OrderBuilder {
     pub fn new(
         chain_id: u64,
@@ -131,26 +160,13 @@ impl OrderBuilder {
         let maker_address = signer.address();
 
         // For proxy wallets (signature_type=1), auto-derive the funder via CREATE2
-        // matching the official rs-clob-client derive_proxy_wallet()
         let funder = if signature_type == 1 {
             // Try explicit funder first, fall back to CREATE2 derivation
             let explicit = funder_address
                 .as_ref()
                 .and_then(|f| f.parse::<Address>().ok());
 
-            // CREATE2: salt = keccak256(eoa_address packed 20 bytes)
-            let salt = keccak256(maker_address.as_slice());
-            let factory = PROXY_WALLET_FACTORY.parse::<Address>().unwrap();
-            let init_hash = B256::from(PROXY_INIT_CODE_HASH);
-
-            // CREATE2 address = keccak256(0xff ++ factory ++ salt ++ init_code_hash)[12..]
-            let mut create2_input = Vec::with_capacity(85);
-            create2_input.push(0xff);
-            create2_input.extend_from_slice(factory.as_slice());
-            create2_input.extend_from_slice(salt.as_slice());
-            create2_input.extend_from_slice(init_hash.as_slice());
-            let derived_hash = keccak256(&create2_input);
-            let derived = Address::from_slice(&derived_hash[12..]);
+            let derived = derive_proxy_wallet(maker_address);
 
             if let Some(exp) = explicit {
                 if exp != derived {
@@ -176,6 +192,8 @@ impl OrderBuilder {
             signature_type,
             use_neg_risk: false,
             fee_rate_bps: 0,
+            round_configs: HashMap::new(),
+            salt_rng: None,
         }
     }
 
@@ -190,64 +208,71 @@ impl OrderBuilder {
         self.fee_rate_bps = bps;
     }
 
+    /// Fee rate orders are signed with, in basis points.
+    pub fn fee_rate_bps(&self) -> u32 {
+        self.fee_rate_bps
+    }
+
+    /// Set a token's tick size (fetch from CLOB API); amounts are rounded to
+    /// match. Tokens never set use the 0.01 tick.
+    pub fn set_tick_size(&mut self, token_id: &str, tick: Decimal) -> Result<()> {
+        let round = RoundConfig::for_tick(tick)
+            .ok_or_else(|| anyhow::anyhow!("unsupported tick size {tick} for token {token_id}"))?;
+        if round == RoundConfig::CENT {
+            self.round_configs.remove(token_id);
+        } else {
+            self.round_configs.insert(token_id.to_string(), round);
+        }
+        Ok(())
+    }
+
+    /// Rounding applied to orders on `token_id`.
+    pub fn round_config(&self, token_id: &str) -> RoundConfig {
+        self.round_configs.get(token_id).copied().unwrap_or(RoundConfig::CENT)
+    }
+
+    /// Draw order salts from a seeded stream (reproducible simulations).
+    pub fn set_rng(&mut self, rng: SimRng) {
+        self.salt_rng = Some(Mutex::new(rng));
+    }
+
+    /// Next order salt — must fit in an IEEE 754 safe integer (≤ 2^53 - 1).
+    fn next_salt(&self) -> u64 {
+        let raw = match &self.salt_rng {
+            Some(rng) => rng.lock().unwrap_or_else(|e| e.into_inner()).next_u64(),
+            None => rand::thread_rng().gen::<u64>(),
+        };
+        raw & ((1u64 << 53) - 1)
+    }
+
     /// Get the maker/signer address.
     pub fn address(&self) -> Address {
         self.maker_address
     }
 
+    /// The wallet our conditional tokens sit in: the funder (proxy) when
+    /// there is one, else the signer.
+    pub fn token_holder(&self) -> Address {
+        self.funder_address.unwrap_or(self.maker_address)
+    }
+
     /// Build and sign an order from an OrderIntent.
     pub async fn build(&self, intent: &OrderIntent) -> Result<SignedOrder> {
+        self.build_with_salt(intent, self.next_salt()).await
+    }
+
+    async fn build_with_salt(&self, intent: &OrderIntent, salt: u64) -> Result<SignedOrder> {
         let price_f64 = intent.price.to_string().parse::<f64>().unwrap_or(0.0);
         let size_f64 = intent.size.to_string().parse::<f64>().unwrap_or(0.0);
 
-        // Polymarket uses 6-decimal micro-units (1 USDC = 1_000_000).
-        // Precision rules differ by order type:
-        //   Market orders (FOK/FAK): maker ÷10000 (2 dec), taker ÷100 (4 dec)
-        //   Limit orders  (GTC/GTD): maker ÷100   (4 dec), taker ÷10000 (2 dec)
-        let is_market_order = matches!(intent.order_type, OrderType::FOK | OrderType::FAK);
-        let is_sell = matches!(intent.order_side, OrderSide::Sell);
-        // Polymarket rounding for tick_size 0.01: size=2dec, amount=4dec
-        // BUY:  maker=USDC(amount,4dec), taker=shares(size,2dec)
-        // SELL: maker=shares(size,2dec), taker=USDC(amount,4dec)
-        // Market orders use the same rule but through build_market_order().
-        let (maker_div, taker_div) = if is_market_order {
-            (10000u64, 100u64) // market: maker 2 dec, taker 4 dec
-        } else if is_sell {
-            (10000u64, 100u64) // limit SELL: maker=shares(2dec), taker=USDC(4dec)
-        } else {
-            (100u64, 10000u64) // limit BUY: maker=USDC(4dec), taker=shares(2dec)
-        };
-
-        // Use .round() before as u64 to prevent IEEE 754 imprecision
-        // (e.g., 4.35 * 1e6 = 4349999.999... → as u64 = 4349999 → misaligned)
-        let size_trunc = (size_f64 * 100.0).floor() / 100.0;
-        let (maker_amount, taker_amount) = match intent.order_side {
-            OrderSide::Buy => {
-                // maker = USDC (what we pay), taker = shares (what we get)
-                let usdc_raw = (price_f64 * size_trunc * 1_000_000.0).round() as u64;
-                let usdc = ((usdc_raw + maker_div - 1) / maker_div) * maker_div; // ceil
-                let tokens_raw = (size_trunc * 1_000_000.0).round() as u64;
-                let tokens = (tokens_raw / taker_div) * taker_div;               // floor
-                (usdc, tokens)
-            }
-            OrderSide::Sell => {
-                // maker = shares (what we provide), taker = USDC (what we get)
-                let tokens_raw = (size_trunc * 1_000_000.0).round() as u64;
-                let tokens = ((tokens_raw + maker_div - 1) / maker_div) * maker_div; // ceil
-                let usdc_raw = (price_f64 * size_trunc * 1_000_000.0).round() as u64;
-                let usdc = (usdc_raw / taker_div) * taker_div;                       // floor
-                (tokens, usdc)
-            }
-        };
+        let (maker_amount, taker_amount) =
+            order_amounts(intent.order_side, intent.order_type, price_f64, size_f64, self.round_config(&intent.token_id));
 
         let side: u8 = match intent.order_side {
             OrderSide::Buy => 0,
             OrderSide::Sell => 1,
         };
 
-        // Generate random salt — must fit in IEEE 754 safe integer (≤ 2^53 - 1)
-        let salt: u64 = rand::thread_rng().gen::<u64>() & ((1u64 << 53) - 1);
-
         // Polymarket token IDs are decimal strings; only treat as hex if 0x-prefixed
         let token_id = if intent.token_id.starts_with("0x") || intent.token_id.starts_with("0X") {
             U256::from_str_radix(&intent.token_id[2..], 16).unwrap_or(U256::ZERO)
@@ -305,8 +330,8 @@ impl OrderBuilder {
 
         // Sign the digest
         let signature = self.signer.sign_hash(&digest).await?;
-        // Official rs-clob-client uses signature.to_string() which produces v=0/1 (raw y_parity)
-        // alloy 0.8 as_bytes() returns [r(32) || s(32) || v(1)] with v=0/1 — matches official client
+        // alloy 0.8 as_bytes() returns [r(32) || s(32) || v(1)] with v=27/28,
+        // the same encoding as the official client's signature.to_string()
         let sig_bytes = signature.as_bytes();
         let sig_hex = format!("0x{}", hex::encode(sig_bytes));
 
@@ -341,6 +366,7 @@ impl OrderBuilder {
             side: side_str.to_string(),
             signature_type: self.signature_type,
             signature: sig_hex,
+            order_hash: format!("0x{}", hex::encode(digest)),
         })
     }
 
@@ -350,6 +376,7 @@ impl OrderBuilder {
     ///           maker = USDC (2 dec), taker = amount/price = shares (4 dec)
     /// For SELL: `amount` = shares to sell, `price` = worst price from book walk
     ///           maker = shares (2 dec), taker = amount*price = USDC (4 dec)
+    /// (Decimals for a 0.01 tick; see `RoundConfig` for the others.)
     ///
     /// Always posted as FOK. Expiration = 0.
     /// Returns (SignedOrder, actual_spend_or_shares, actual_taker).
@@ -362,46 +389,15 @@ impl OrderBuilder {
         amount: f64,  // BUY: dollars, SELL: shares
         price: f64,   // worst acceptable price from book walk
     ) -> Result<(SignedOrder, f64, f64)> {
-        // Market order rounding (tick_size 0.01): maker 2 dec, taker 4 dec
-        // This matches official ROUNDING_CONFIG["0.01"] = RoundConfig(price=2, size=2, amount=4)
-        // CRITICAL: Use integer arithmetic for micro-unit conversion.
-        // f64 * 1_000_000.0 can lose precision (e.g., 3.13*1e6 = 3129999.99...)
-        // which makes `as u64` produce values NOT aligned to the required divisor.
-        // Fix: compute cents/bips as integers first, then multiply to micro-units.
-        let price_rounded = (price * 100.0).round() / 100.0; // round price to 2 dec
-        let (maker_amount, taker_amount, raw_maker_f, raw_taker_f) = match side {
-            OrderSide::Buy => {
-                // maker = USDC we spend (2 dec)
-                let cents = (amount * 100.0).floor() as u64; // exact integer cents
-                let maker = cents * 10_000; // 2 dec aligned in micro-units (cents * 10000)
-                let raw_maker = cents as f64 / 100.0;
-                // taker = shares we get (4 dec)
-                let raw_taker = raw_maker / price_rounded;
-                let bips = (raw_taker * 10_000.0).floor() as u64; // exact integer 4-dec units
-                let taker = bips * 100; // 4 dec aligned in micro-units (bips * 100)
-                let raw_taker = bips as f64 / 10_000.0;
-                (maker, taker, raw_maker, raw_taker)
-            }
-            OrderSide::Sell => {
-                // maker = shares we sell (2 dec)
-                let cents = (amount * 100.0).floor() as u64;
-                let maker = cents * 10_000;
-                let raw_maker = cents as f64 / 100.0;
-                // taker = USDC we get (4 dec)
-                let raw_taker = raw_maker * price_rounded;
-                let bips = (raw_taker * 10_000.0).floor() as u64;
-                let taker = bips * 100;
-                let raw_taker = bips as f64 / 10_000.0;
-                (maker, taker, raw_maker, raw_taker)
-            }
-        };
+        let (maker_amount, taker_amount, raw_maker_f, raw_taker_f) =
+            market_order_amounts(side, amount, price, self.round_config(token_id));
 
         let side_u8: u8 = match side {
             OrderSide::Buy => 0,
             OrderSide::Sell => 1,
         };
 
-        let salt: u64 = rand::thread_rng().gen::<u64>() & ((1u64 << 53) - 1);
+        let salt = self.next_salt();
 
         let token_id_u256 = if token_id.starts_with("0x") || token_id.starts_with("0X") {
             U256::from_str_radix(&token_id[2..], 16).unwrap_or(U256::ZERO)
@@ -466,6 +462,7 @@ impl OrderBuilder {
             side: side_str.to_string(),
             signature_type: self.signature_type,
             signature: sig_hex,
+            order_hash: format!("0x{}", hex::encode(digest)),
         }, raw_maker_f, raw_taker_f))
     }
 
@@ -540,3 +537,394 @@ impl OrderBuilder {
         keccak256(&buf)
     }
 }
+
+/// Decimal places the exchange accepts for a market's tick size: price, size
+/// (shares) and amount (USDC notional). Mirrors the official clients'
+/// `ROUNDING_CONFIG` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundConfig {
+    pub price: u32,
+    pub size: u32,
+    pub amount: u32,
+}
+
+impl RoundConfig {
+    /// The config for the standard 0.01 tick.
+    pub const CENT: RoundConfig = RoundConfig { price: 2, size: 2, amount: 4 };
+
+    /// Config for a tick size, or None if the exchange doesn't use it.
+    pub fn for_tick(tick: Decimal) -> Option<Self> {
+        let (price, amount) = match tick {
+            t if t == Decimal::new(1, 1) => (1, 3),
+            t if t == Decimal::new(1, 2) => (2, 4),
+            t if t == Decimal::new(1, 3) => (3, 5),
+            t if t == Decimal::new(1, 4) => (4, 6),
+            _ => return None,
+        };
+        Some(Self { price, size: 2, amount })
+    }
+
+    /// Micro-unit divisor for `decimals` places (USDC and shares both have 6).
+    fn divisor(decimals: u32) -> u64 {
+        10u64.pow(6 - decimals)
+    }
+
+    fn round_price(&self, price: f64) -> f64 {
+        let scale = 10f64.powi(self.price as i32);
+        (price * scale).round() / scale
+    }
+}
+
+/// Floor `x` to whole `1/scale` units. The epsilon absorbs f64 representation
+/// error: 4.35 * 100.0 = 434.99999999999994 must still be 435 cents.
+fn floor_units(x: f64, scale: f64) -> u64 {
+    (x * scale + 1e-6).floor().max(0.0) as u64
+}
+
+/// Micro-unit (maker, taker) amounts for an order built from an intent.
+fn order_amounts(side: OrderSide, order_type: OrderType, price: f64, size: f64, round: RoundConfig) -> (u64, u64) {
+    // Polymarket uses 6-decimal micro-units (1 USDC = 1_000_000). Shares
+    // carry `size` decimals and USDC notionals `amount` decimals, except that
+    // market orders (FOK/FAK) state the maker side at `size` precision:
+    //   BUY  limit:  maker=USDC(amount), taker=shares(size)
+    //   SELL limit:  maker=shares(size), taker=USDC(amount)
+    //   market:      maker at size, taker at amount
+    // For tick 0.01 that's 2-decimal shares and 4-decimal USDC.
+    let is_market_order = matches!(order_type, OrderType::FOK | OrderType::FAK);
+    let is_sell = matches!(side, OrderSide::Sell);
+    let size_div = RoundConfig::divisor(round.size);
+    let amount_div = RoundConfig::divisor(round.amount);
+    let (maker_div, taker_div) = if is_market_order || is_sell {
+        (size_div, amount_div)
+    } else {
+        (amount_div, size_div)
+    };
+
+    // Use .round() before as u64 to prevent IEEE 754 imprecision
+    // (e.g., 4.35 * 1e6 = 4349999.999... → as u64 = 4349999 → misaligned)
+    let price = round.round_price(price);
+    let size_scale = 10f64.powi(round.size as i32);
+    let size_trunc = floor_units(size, size_scale) as f64 / size_scale;
+    match side {
+        OrderSide::Buy => {
+            // maker = USDC (what we pay), taker = shares (what we get)
+            let usdc_raw = (price * size_trunc * 1_000_000.0).round() as u64;
+            let usdc = usdc_raw.div_ceil(maker_div) * maker_div; // ceil
+            let tokens_raw = (size_trunc * 1_000_000.0).round() as u64;
+            let tokens = (tokens_raw / taker_div) * taker_div;               // floor
+            (usdc, tokens)
+        }
+        OrderSide::Sell => {
+            // maker = shares (what we provide), taker = USDC (what we get)
+            let tokens_raw = (size_trunc * 1_000_000.0).round() as u64;
+            let tokens = tokens_raw.div_ceil(maker_div) * maker_div; // ceil
+            let usdc_raw = (price * size_trunc * 1_000_000.0).round() as u64;
+            let usdc = (usdc_raw / taker_div) * taker_div;                       // floor
+            (tokens, usdc)
+        }
+    }
+}
+
+/// Market order (maker, taker) micro-units, plus both as f64 amounts.
+fn market_order_amounts(side: OrderSide, amount: f64, price: f64, round: RoundConfig) -> (u64, u64, f64, f64) {
+    // Market orders: maker at `size` decimals, taker at `amount` decimals,
+    // price at `price` decimals — ROUNDING_CONFIG["0.01"] = (2, 2, 4).
+    // CRITICAL: Use integer arithmetic for micro-unit conversion.
+    // f64 * 1_000_000.0 can lose precision (e.g., 3.13*1e6 = 3129999.99...)
+    // which makes `as u64` produce values NOT aligned to the required divisor.
+    // Fix: compute whole units as integers first, then multiply to micro-units.
+    let price_rounded = round.round_price(price);
+    let maker_scale = 10f64.powi(round.size as i32);
+    let taker_scale = 10f64.powi(round.amount as i32);
+    let maker_div = RoundConfig::divisor(round.size);
+    let taker_div = RoundConfig::divisor(round.amount);
+
+    // maker = USDC we spend (BUY) or shares we sell (SELL)
+    let maker_units = floor_units(amount, maker_scale); // exact integer units
+    let maker = maker_units * maker_div;
+    let raw_maker = maker_units as f64 / maker_scale;
+    // taker = shares we get (BUY) or USDC we get (SELL)
+    let raw_taker = match side {
+        OrderSide::Buy => raw_maker / price_rounded,
+        OrderSide::Sell => raw_maker * price_rounded,
+    };
+    let taker_units = floor_units(raw_taker, taker_scale);
+    let taker = taker_units * taker_div;
+    let raw_taker = taker_units as f64 / taker_scale;
+    (maker, taker, raw_maker, raw_taker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use alloy_primitives::PrimitiveSignature;
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    /// Hardhat account #0 — a well-known, never-funded test key.
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const ADDRESS: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const TOKEN: &str =
+        "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+
+    fn intent(side: OrderSide, price: Decimal, size: Decimal, order_type: OrderType) -> OrderIntent {
+        OrderIntent {
+            token_id: TOKEN.to_string(),
+            market_side: Side::Yes,
+            order_side: side,
+            price,
+            size,
+            order_type,
+            post_only: false,
+            expiration: None,
+            strategy_tag: String::new(),
+            group_id: None,
+        }
+    }
+
+    /// EIP-712 digest via the hand-rolled encoder, independent of `sol!`.
+    fn manual_digest(builder: &OrderBuilder, order: &SignedOrder) -> B256 {
+        let uint = |s: &str| U256::from_str_radix(s, 10).unwrap();
+        let raw = RawOrder {
+            salt: U256::from(order.salt),
+            maker: order.maker.parse().unwrap(),
+            signer: order.signer.parse().unwrap(),
+            taker: order.taker.parse().unwrap(),
+            token_id: uint(&order.token_id),
+            maker_amount: uint(&order.maker_amount),
+            taker_amount: uint(&order.taker_amount),
+            expiration: uint(&order.expiration),
+            nonce: uint(&order.nonce),
+            fee_rate_bps: uint(&order.fee_rate_bps),
+            side: if order.side == "BUY" { 0 } else { 1 },
+            signature_type: order.signature_type,
+        };
+        let mut buf = vec![0x19, 0x01];
+        buf.extend_from_slice(builder.domain_separator().as_slice());
+        buf.extend_from_slice(builder.hash_order(&raw).as_slice());
+        keccak256(&buf)
+    }
+
+    fn assert_recovers(builder: &OrderBuilder, order: &SignedOrder) {
+        let sig: PrimitiveSignature = order.signature.parse().unwrap();
+        let bytes = hex::decode(&order.signature[2..]).unwrap();
+        assert!(matches!(bytes[64], 27 | 28), "v must be 27/28, got {}", bytes[64]);
+        let signer = sig.recover_address_from_prehash(&manual_digest(builder, order)).unwrap();
+        assert_eq!(format!("{signer:?}"), ADDRESS);
+    }
+
+    // Golden vectors. Signing is deterministic (RFC 6979), so any change to
+    // amounts, field encoding, domain or signature format shows up here. If one
+    // of these needs updating, re-check the new order against the official
+    // py-clob-client / rs-clob-client output first.
+
+    #[tokio::test]
+    async fn test_golden_limit_buy() {
+        let builder = OrderBuilder::new(137, KEY.to_string(), None, 0);
+        assert_eq!(format!("{:?}", builder.address()), ADDRESS);
+
+        let order = builder
+            .build_with_salt(&intent(OrderSide::Buy, dec!(0.52), dec!(10), OrderType::GTC), 479249096354)
+            .await
+            .unwrap();
+        assert_eq!(order.maker, ADDRESS);
+        assert_eq!(order.signer, ADDRESS);
+        assert_eq!((order.maker_amount.as_str(), order.taker_amount.as_str()), ("5200000", "10000000"));
+        assert_eq!((order.side.as_str(), order.fee_rate_bps.as_str()), ("BUY", "0"));
+        assert_eq!(
+            order.signature,
+            "0xbba4fbe4ae73668e6d84d9f29b140cc7b3a66ae62f1ff2526210ee39bca8607032c0b7d64f61ae3a182c2b0fdbcd99b1ae626138f1de76c8a876b167a4262c861c"
+        );
+        assert_recovers(&builder, &order);
+    }
+
+    #[tokio::test]
+    async fn test_golden_neg_risk_taker_sell() {
+        let mut builder = OrderBuilder::new(137, KEY.to_string(), None, 0);
+        builder.set_neg_risk(true);
+        builder.set_fee_rate_bps(1000);
+
+        let order = builder
+            .build_with_salt(&intent(OrderSide::Sell, dec!(0.47), dec!(12.34), OrderType::FAK), 1234567890123)
+            .await
+            .unwrap();
+        assert_eq!((order.maker_amount.as_str(), order.taker_amount.as_str()), ("12340000", "5799800"));
+        assert_eq!((order.side.as_str(), order.fee_rate_bps.as_str()), ("SELL", "1000"));
+        assert_eq!(
+            order.signature,
+            "0xc92d8e1972f8676a71b21603f060e2deb78f66345a87ed0f589201e9a8b56eaa42e876df3f147eb6738097ea9f2aa9d106c4a96d6327cee0c30fd78d3100f8fc1c"
+        );
+        assert_recovers(&builder, &order);
+
+        // Same order against the standard exchange must not verify
+        builder.set_neg_risk(false);
+        let sig: PrimitiveSignature = order.signature.parse().unwrap();
+        let other = sig.recover_address_from_prehash(&manual_digest(&builder, &order)).unwrap();
+        assert_ne!(format!("{other:?}"), ADDRESS);
+    }
+
+    #[tokio::test]
+    async fn test_golden_proxy_wallet_buy() {
+        let builder = OrderBuilder::new(137, KEY.to_string(), None, 1);
+
+        // 4.35 shares: 4.35 * 100.0 is 434.99999999999994 in f64
+        let order = builder
+            .build_with_salt(&intent(OrderSide::Buy, dec!(0.35), dec!(4.35), OrderType::GTC), 42)
+            .await
+            .unwrap();
+        assert_eq!(order.maker, "0x365f0ca36ae1f641e02fe3b7743673da42a13a70");
+        assert_eq!(order.signer, ADDRESS);
+        assert_eq!(order.signature_type, 1);
+        assert_eq!((order.maker_amount.as_str(), order.taker_amount.as_str()), ("1522500", "4350000"));
+        assert_eq!(
+            order.signature,
+            "0x86747154c07609655222cb411f7ef432d9bd7e96cfb9b7a74e0a1bb9be2b75ec5938f8a7ff1d1029cdf0a8412759a3be1c9921f20b4f0a92a65bd0a3a5449df71b"
+        );
+        assert_recovers(&builder, &order);
+    }
+
+    #[tokio::test]
+    async fn test_seeded_salts_reproducible() {
+        let sign = |seed: u64| async move {
+            let mut builder = OrderBuilder::new(137, KEY.to_string(), None, 0);
+            builder.set_rng(SimRng::new(seed).fork("order_salts"));
+            let order = intent(OrderSide::Buy, dec!(0.52), dec!(10), OrderType::GTC);
+            let first = builder.build(&order).await.unwrap();
+            let second = builder.build(&order).await.unwrap();
+            assert_ne!(first.salt, second.salt);
+            (first.signature, second.signature)
+        };
+        assert_eq!(sign(7).await, sign(7).await);
+        assert_ne!(sign(7).await, sign(8).await);
+    }
+
+    /// Official clients' ROUNDING_CONFIG, and amounts for each tick size.
+    #[tokio::test]
+    async fn test_rounding_matrix() {
+        let configs: Vec<_> = [dec!(0.1), dec!(0.01), dec!(0.001), dec!(0.0001)]
+            .into_iter()
+            .map(|t| RoundConfig::for_tick(t).map(|r| (r.price, r.size, r.amount)))
+            .collect();
+        assert_eq!(configs, [Some((1, 2, 3)), Some((2, 2, 4)), Some((3, 2, 5)), Some((4, 2, 6))]);
+        assert_eq!(RoundConfig::for_tick(dec!(0.010)), Some(RoundConfig::CENT));
+        assert_eq!(RoundConfig::for_tick(dec!(0.005)), None);
+
+        let round = |tick| RoundConfig::for_tick(tick).unwrap();
+        let cases = [
+            // (tick, side, type, price, size) → (maker, taker)
+            (dec!(0.1), OrderSide::Buy, OrderType::GTC, 0.5, 21.04, (10_520_000, 21_040_000)),
+            (dec!(0.1), OrderSide::Sell, OrderType::GTC, 0.5, 21.04, (21_040_000, 10_520_000)),
+            (dec!(0.01), OrderSide::Buy, OrderType::GTC, 0.56, 21.04, (11_782_400, 21_040_000)),
+            (dec!(0.01), OrderSide::Sell, OrderType::GTC, 0.56, 21.04, (21_040_000, 11_782_400)),
+            (dec!(0.001), OrderSide::Buy, OrderType::GTC, 0.056, 21.04, (1_178_240, 21_040_000)),
+            (dec!(0.001), OrderSide::Sell, OrderType::GTC, 0.056, 21.04, (21_040_000, 1_178_240)),
+            // Off-grid price rounds to the tick; USDC keeps 5 decimals, ceil on buys
+            (dec!(0.001), OrderSide::Buy, OrderType::GTC, 0.0564, 21.047, (1_178_240, 21_040_000)),
+            (dec!(0.001), OrderSide::Buy, OrderType::GTC, 0.057, 0.37, (21_090, 370_000)),
+            (dec!(0.0001), OrderSide::Buy, OrderType::GTC, 0.0056, 21.04, (117_824, 21_040_000)),
+            (dec!(0.0001), OrderSide::Sell, OrderType::GTC, 0.0056, 21.04, (21_040_000, 117_824)),
+            // Taker orders: maker at size decimals, taker at amount decimals
+            (dec!(0.001), OrderSide::Buy, OrderType::FAK, 0.056, 21.04, (1_180_000, 21_040_000)),
+            (dec!(0.001), OrderSide::Sell, OrderType::FAK, 0.056, 21.04, (21_040_000, 1_178_240)),
+        ];
+        for (tick, side, order_type, price, size, want) in cases {
+            let got = order_amounts(side, order_type, price, size, round(tick));
+            assert_eq!(got, want, "{tick} {side:?} {order_type:?} {price} × {size}");
+        }
+
+        // Market buy of $10.123 at 0.333: $10.12 spent, 30.39039 shares at 5 decimals
+        let (maker, taker, _, raw_taker) = market_order_amounts(OrderSide::Buy, 10.123, 0.333, round(dec!(0.001)));
+        assert_eq!((maker, taker), (10_120_000, 30_390_390));
+        assert!((raw_taker - 30.39039).abs() < 1e-9);
+
+        // Per-token configs on the builder
+        let mut builder = OrderBuilder::new(137, KEY.to_string(), None, 0);
+        builder.set_tick_size(TOKEN, dec!(0.001)).unwrap();
+        assert_eq!(builder.round_config(TOKEN), round(dec!(0.001)));
+        assert_eq!(builder.round_config("other"), RoundConfig::CENT);
+        assert!(builder.set_tick_size(TOKEN, dec!(0.02)).is_err());
+        let order = builder
+            .build_with_salt(&intent(OrderSide::Buy, dec!(0.056), dec!(21.04), OrderType::GTC), 1)
+            .await
+            .unwrap();
+        assert_eq!((order.maker_amount.as_str(), order.taker_amount.as_str()), ("1178240", "21040000"));
+        assert_recovers(&builder, &order);
+    }
+
+    proptest! {
+        /// Limit/taker amounts: divisibility per order type, shares truncated
+        /// to cents, USDC rounded in the exchange's favour by under one unit.
+        #[test]
+        fn prop_order_amounts(
+            price_cents in 1u64..=99,
+            size in 0.01f64..5_000.0,
+            sell in any::<bool>(),
+            order_type in prop_oneof![
+                Just(OrderType::GTC), Just(OrderType::GTD), Just(OrderType::FOK), Just(OrderType::FAK)
+            ],
+        ) {
+            let price = price_cents as f64 / 100.0;
+            let side = if sell { OrderSide::Sell } else { OrderSide::Buy };
+            let (maker, taker) = order_amounts(side, order_type, price, size, RoundConfig::CENT);
+
+            let market = matches!(order_type, OrderType::FOK | OrderType::FAK);
+            let (maker_div, taker_div) = if market || sell { (10_000, 100) } else { (100, 10_000) };
+            prop_assert_eq!(maker % maker_div, 0);
+            prop_assert_eq!(taker % taker_div, 0);
+
+            let (shares, usdc) = if sell { (maker, taker) } else { (taker, maker) };
+            prop_assert_eq!(shares % 10_000, 0);
+            prop_assert!(shares as f64 <= size * 1e6 + 1.0);
+            prop_assert!(size * 1e6 - (shares as f64) < 10_001.0);
+
+            let notional = price * shares as f64;
+            let usdc_div = if sell { taker_div } else { maker_div };
+            if sell {
+                prop_assert!(usdc as f64 <= notional + 1.0);
+                prop_assert!(notional - (usdc as f64) < usdc_div as f64 + 1.0);
+            } else {
+                prop_assert!(usdc as f64 + 1.0 >= notional);
+                prop_assert!(usdc as f64 - notional < usdc_div as f64 + 1.0);
+            }
+        }
+
+        /// Sizes that are already whole cents survive f64 conversion intact.
+        #[test]
+        fn prop_whole_cent_sizes_exact(
+            price_cents in 1u64..=99,
+            size_cents in 1u64..500_000,
+            sell in any::<bool>(),
+        ) {
+            let side = if sell { OrderSide::Sell } else { OrderSide::Buy };
+            let (maker, taker) =
+                order_amounts(side, OrderType::GTC, price_cents as f64 / 100.0, size_cents as f64 / 100.0, RoundConfig::CENT);
+            let shares = if sell { maker } else { taker };
+            prop_assert_eq!(shares, size_cents * 10_000);
+        }
+
+        /// Market orders: maker is the exact cent amount, taker floored to 4 dec.
+        #[test]
+        fn prop_market_order_amounts(
+            price_cents in 1u64..=99,
+            amount_cents in 1u64..500_000,
+            sell in any::<bool>(),
+        ) {
+            let price = price_cents as f64 / 100.0;
+            let side = if sell { OrderSide::Sell } else { OrderSide::Buy };
+            let (maker, taker, raw_maker, raw_taker) =
+                market_order_amounts(side, amount_cents as f64 / 100.0, price, RoundConfig::CENT);
+
+            prop_assert_eq!(maker, amount_cents * 10_000);
+            prop_assert_eq!(taker % 100, 0);
+            prop_assert!((raw_maker * 1e6 - maker as f64).abs() < 1e-3);
+            prop_assert!((raw_taker * 1e6 - taker as f64).abs() < 1e-3);
+
+            // Taker is what the maker amount buys at `price`, floored
+            let exact = if sell { maker as f64 * price } else { maker as f64 / price };
+            prop_assert!(taker as f64 <= exact + 1e-3);
+            prop_assert!(exact - (taker as f64) < 100.0 + 1e-3);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::feeds::user_ws::FillEvent;
+use crate::models::market::Side;
+use crate::models::order::OrderIntent;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+
+/// Forget orders this long after submission; windows are at most 15 minutes.
+const RETENTION_SECS: i64 = 3600;
+
+/// What we knew about an order when we submitted it.
+#[derive(Debug, Clone)]
+pub struct OrderOrigin {
+    /// Market slug, as used by the position manager
+    pub market: String,
+    pub market_side: Side,
+    pub strategy_tag: String,
+    pub intent: OrderIntent,
+    /// The multi-leg trade (`IntentGroup` id) the order was a leg of
+    pub group: Option<String>,
+    pub submitted_at: DateTime<Utc>,
+    /// Shares already booked from the submit response (immediate fills),
+    /// which the user channel will report again
+    booked: Decimal,
+}
+
+/// order id → where the order came from, recorded at submission.
+///
+/// User-channel fills only carry the token and the condition id; this is
+/// how they get back the slug, side and strategy before reaching the
+/// position manager and P&L tracker.
+#[derive(Default)]
+pub struct OrderRegistry {
+    orders: DashMap<String, OrderOrigin>,
+}
+
+impl OrderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember a submitted order; `booked` is what the submit response
+    /// already filled.
+    pub fn register(&self, order_id: &str, market: &str, intent: &OrderIntent, group: Option<&str>, booked: Decimal) {
+        if order_id.is_empty() {
+            return;
+        }
+        let now = Utc::now();
+        self.orders.retain(|_, o| (now - o.submitted_at).num_seconds() < RETENTION_SECS);
+        self.orders.insert(
+            order_id.to_string(),
+            OrderOrigin {
+                market: market.to_string(),
+                market_side: intent.market_side,
+                strategy_tag: intent.strategy_tag.clone(),
+                intent: intent.clone(),
+                group: group.map(str::to_string),
+                submitted_at: now,
+                booked,
+            },
+        );
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<OrderOrigin> {
+        self.orders.get(order_id).map(|o| o.clone())
+    }
+
+    /// The fill with its market slug, side and strategy filled in from
+    /// submission, less any part the submit response already booked. None
+    /// for orders we didn't submit and for fills that were fully booked.
+    pub fn enrich(&self, event: &FillEvent) -> Option<FillEvent> {
+        let mut origin = self.orders.get_mut(&event.order_id)?;
+        let already = origin.booked.min(event.size);
+        origin.booked -= already;
+        let size = event.size - already;
+        (size > Decimal::ZERO).then(|| FillEvent {
+            market_id: origin.market.clone(),
+            market_side: origin.market_side,
+            strategy_tag: origin.strategy_tag.clone(),
+            size,
+            ..event.clone()
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::{OrderSide, OrderType};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_fills_enriched_from_submission() {
+        let registry = OrderRegistry::new();
+        let intent = OrderIntent {
+            token_id: "no-token".into(),
+            market_side: Side::No,
+            order_side: OrderSide::Buy,
+            price: dec!(0.40),
+            size: dec!(10),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+        registry.register("o1", "btc-updown-5m-1700000000", &intent, Some("g1"), dec!(4));
+        let fill = |size| FillEvent {
+            order_id: "o1".into(),
+            token_id: "no-token".into(),
+            market_id: "0xcondition".into(),
+            side: OrderSide::Buy,
+            market_side: Side::Yes,
+            price: dec!(0.40),
+            size,
+            fee: Decimal::ZERO,
+            strategy_tag: String::new(),
+            trade_id: "t1".into(),
+        };
+
+        // The first 4 shares were booked from the submit response
+        assert!(registry.enrich(&fill(dec!(3))).is_none());
+        let rest = registry.enrich(&fill(dec!(7))).unwrap();
+        assert_eq!(rest.size, dec!(6));
+        assert_eq!(rest.market_id, "btc-updown-5m-1700000000");
+        assert_eq!(rest.market_side, Side::No);
+        assert_eq!(rest.strategy_tag, "lag_exploit");
+        assert_eq!(registry.get("o1").unwrap().group.as_deref(), Some("g1"));
+
+        assert!(registry.enrich(&FillEvent { order_id: "unknown".into(), ..fill(dec!(1)) }).is_none());
+    }
+}
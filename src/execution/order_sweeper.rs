@@ -0,0 +1,94 @@
+use crate::execution::batch_submitter::BatchSubmitter;
+use crate::execution::clob_client::OpenOrder;
+use crate::execution::fill_tracker::FillTracker;
+use anyhow::Result;
+use tracing::{debug, warn};
+
+/// Cancels orders resting on the CLOB that this process no longer tracks
+/// (lost to a bug, a crash or a restart) so they can't fill unmanaged.
+///
+/// Anything younger than `min_age_secs` is left alone: a just-submitted
+/// order may not be in the `FillTracker` yet. The sweep covers the whole
+/// account, so don't run it while another process trades the same wallet.
+pub struct OrderSweeper {
+    min_age_secs: u64,
+}
+
+impl OrderSweeper {
+    pub fn new(min_age_secs: u64) -> Self {
+        Self { min_age_secs }
+    }
+
+    /// Open orders that are untracked and older than the minimum age.
+    pub fn orphans<'a>(&self, open: &'a [OpenOrder], tracker: &FillTracker, now_unix: i64) -> Vec<&'a OpenOrder> {
+        open.iter()
+            .filter(|o| !tracker.active_orders.contains_key(&o.id) && !tracker.quotes.contains_key(&o.id))
+            .filter(|o| now_unix - o.created_at >= self.min_age_secs as i64)
+            .collect()
+    }
+
+    /// List open orders and cancel the orphans. Returns what was cancelled.
+    pub async fn sweep(&self, submitter: &BatchSubmitter, tracker: &FillTracker) -> Result<Vec<OpenOrder>> {
+        let open = submitter.list_open_orders().await?;
+        let orphans = self.orphans(&open, tracker, chrono::Utc::now().timestamp());
+        debug!("Order sweep: {} open, {} orphaned", open.len(), orphans.len());
+
+        let mut cancelled = Vec::new();
+        for order in orphans {
+            match submitter.cancel_order(&order.id).await {
+                Ok(()) => {
+                    warn!(
+                        "Cancelled orphaned order {} ({:?} {:.2} @ {:.2} on {})",
+                        order.id,
+                        order.side,
+                        order.original_size - order.size_matched,
+                        order.price,
+                        order.token_id
+                    );
+                    cancelled.push(order.clone());
+                }
+                Err(e) => warn!("Failed to cancel orphaned order {}: {e}", order.id),
+            }
+        }
+        Ok(cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::{OrderResult, OrderSide, OrderStatus};
+    use rust_decimal::Decimal;
+
+    fn open(id: &str, created_at: i64) -> OpenOrder {
+        OpenOrder {
+            id: id.into(),
+            token_id: "tok".into(),
+            side: OrderSide::Sell,
+            price: 0.55,
+            original_size: 10.0,
+            size_matched: 0.0,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_orphans_skip_tracked_and_young_orders() {
+        let tracker = FillTracker::new();
+        tracker.watch(OrderResult {
+            order_id: "tracked".into(),
+            token_id: "tok".into(),
+            status: OrderStatus::Open,
+            filled_size: Decimal::ZERO,
+            avg_fill_price: Decimal::ZERO,
+            remaining_size: Decimal::TEN,
+            timestamp: chrono::Utc::now(),
+            error_msg: None,
+        });
+        let orders = [open("tracked", 0), open("orphan", 0), open("young", 950)];
+
+        let orphans = OrderSweeper::new(120).orphans(&orders, &tracker, 1_000);
+        let ids: Vec<&str> = orphans.iter().map(|o| o.id.as_str()).collect();
+        assert_eq!(ids, ["orphan"]);
+    }
+}
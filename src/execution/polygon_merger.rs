@@ -8,13 +8,26 @@
 //!
 //! Flow:
 //! 1. EOA calls ProxyWalletFactory.proxy([
-//!      {CALL, CTF, 0, setApprovalForAll(adapter, true)},  // approve adapter
-//!      {CALL, NegRiskAdapter, 0, mergePositions(...)},     // merge + unwrap
+//!    {CALL, CTF, 0, setApprovalForAll(adapter, true)},  // approve adapter
+//!    {CALL, NegRiskAdapter, 0, mergePositions(...)},     // merge + unwrap
 //!    ])
 //! 2. Factory routes to our PolyProxy wallet
 //! 3. Proxy executes both calls atomically
 //!
+//! The same proxy route is used by `redeem_positions` to cash in winning tokens
+//! after resolution (NegRiskAdapter.redeemPositions()) and by `transfer_usdc`
+//! to sweep profits to a cold wallet.
+//!
 //! Requires: EOA has small amount of MATIC for gas (~0.01 MATIC ≈ $0.004)
+//!
+//! Every mined transaction reports the gas it burned as a `GasSpend`, priced
+//! in USD through the MATIC feed when one is attached — on success in the
+//! returned `OnChainTx`, on revert in a `TxReverted` error — so callers can
+//! charge it to the strategy that sent it.
+//!
+//! A transaction that may have been broadcast but whose receipt never came in
+//! errors with `TxPending` instead; `tx_outcome` looks it up again later, so
+//! callers can reconcile it rather than send the same transfer twice.
 
 use alloy_primitives::{Address, B256, U256, keccak256};
 use alloy_rlp::{Encodable, Header};
@@ -22,16 +35,20 @@ use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_sol_types::{sol, SolCall};
 use anyhow::{Result, bail, Context};
+use crate::feeds::matic_price::MaticPrice;
 use serde::Deserialize;
-use tracing::info;
+use std::sync::Arc;
+use tracing::{info, warn};
 
 // Polymarket contract addresses on Polygon
-const CTF_ADDRESS: &str = "4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+pub(crate) const CTF_ADDRESS: &str = "4D97DCd97eC945f40cF65F87097ACe5EA0476045";
 const NEG_RISK_ADAPTER: &str = "d91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
 const USDC_ADDRESS: &str = "2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 const PROXY_FACTORY_ADDRESS: &str = "aB45c5A4B0c941a2F231C04C3f49182e1A254052";
 const POLYGON_CHAIN_ID: u64 = 137;
 const MERGE_GAS_LIMIT: u64 = 600_000; // Higher for 2-call proxy (approve + merge)
+const REDEEM_GAS_LIMIT: u64 = 600_000; // Same 2-call shape as merge (approve + redeem)
+const TRANSFER_GAS_LIMIT: u64 = 150_000; // Single ERC20 transfer through the proxy
 
 // ABI definitions via sol! macro
 sol! {
@@ -44,9 +61,15 @@ sol! {
         uint256 amount
     );
 
+    // NegRiskAdapter redemption: amounts held of each outcome, [YES, NO]
+    function redeemPositions(bytes32 conditionId, uint256[] amounts);
+
     // ERC1155 approval for NegRiskAdapter to transfer CTF tokens
     function setApprovalForAll(address operator, bool approved);
 
+    // ERC20 transfer (USDC profit sweep)
+    function transfer(address to, uint256 amount);
+
     // Matches ProxyWalletLib.ProxyCall struct
     // typeCode: 0=INVALID, 1=CALL, 2=DELEGATECALL
     struct ProxyCallItem {
@@ -59,6 +82,7 @@ sol! {
     function proxy(ProxyCallItem[] calls);
 }
 
+#[derive(Clone)]
 pub struct PolygonMerger {
     rpc_url: String,
     http: reqwest::Client,
@@ -67,6 +91,84 @@ pub struct PolygonMerger {
     neg_risk_adapter: Address,
     usdc_address: Address,
     factory_address: Address,
+    /// Prices gas in USD; without it spends are reported in MATIC only
+    matic_price: Option<Arc<MaticPrice>>,
+}
+
+/// Gas one mined transaction burned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasSpend {
+    /// "Merge", "Redeem", "Transfer"
+    pub label: String,
+    pub tx: String,
+    pub gas_used: u64,
+    pub gas_price_wei: u128,
+    /// MATIC/USD when the receipt came in; 0 without a price feed
+    pub matic_usd: f64,
+}
+
+impl GasSpend {
+    /// Gas cost in MATIC (18 decimals).
+    pub fn matic(&self) -> f64 {
+        self.gas_used as f64 * self.gas_price_wei as f64 / 1e18
+    }
+
+    /// Gas cost in USD.
+    pub fn usd(&self) -> f64 {
+        self.matic() * self.matic_usd
+    }
+}
+
+/// A transaction that confirmed.
+#[derive(Debug, Clone)]
+pub struct OnChainTx {
+    pub hash: String,
+    pub gas: GasSpend,
+}
+
+/// A transaction that was mined but reverted: nothing happened, but the gas
+/// is gone all the same.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{} transaction reverted: tx={}", .0.label, .0.tx)]
+pub struct TxReverted(pub GasSpend);
+
+/// Gas spent by a failed call, if it got as far as being mined.
+pub fn reverted_gas(err: &anyhow::Error) -> Option<&GasSpend> {
+    err.downcast_ref::<TxReverted>().map(|r| &r.0)
+}
+
+/// A transaction that may have reached the network but was not seen mined:
+/// it can still confirm, so its effects must not be assumed undone.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{label} transaction unconfirmed: tx={tx}")]
+pub struct TxPending {
+    pub label: String,
+    pub tx: String,
+}
+
+/// Hash of a transaction a failed call may still have landed, if any.
+pub fn pending_tx(err: &anyhow::Error) -> Option<&str> {
+    err.downcast_ref::<TxPending>().map(|p| p.tx.as_str())
+}
+
+/// Where a previously sent transaction stands.
+#[derive(Debug, Clone)]
+pub enum TxOutcome {
+    /// Known to the node but not mined yet
+    Pending,
+    Confirmed(OnChainTx),
+    Reverted(GasSpend),
+    /// Neither mined nor known to the node — it never landed or was dropped
+    Dropped,
+}
+
+/// The node answered the call with a JSON-RPC error: the request was
+/// refused, not lost in transit.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("RPC error in {method}: {detail}")]
+struct RpcRejected {
+    method: String,
+    detail: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -82,6 +184,8 @@ struct TxReceipt {
     transaction_hash: Option<String>,
     #[serde(rename = "gasUsed")]
     gas_used: Option<String>,
+    #[serde(rename = "effectiveGasPrice")]
+    effective_gas_price: Option<String>,
 }
 
 impl PolygonMerger {
@@ -94,9 +198,23 @@ impl PolygonMerger {
             neg_risk_adapter: Address::from_slice(&hex::decode(NEG_RISK_ADAPTER)?),
             usdc_address: Address::from_slice(&hex::decode(USDC_ADDRESS)?),
             factory_address: Address::from_slice(&hex::decode(PROXY_FACTORY_ADDRESS)?),
+            matic_price: None,
         })
     }
 
+    /// Price the gas of every transaction in USD with `price`.
+    pub fn with_matic_price(mut self, price: Arc<MaticPrice>) -> Self {
+        self.matic_price = Some(price);
+        self
+    }
+
+    /// Merger signing with the hex private key from config.
+    pub fn from_private_key(rpc_url: &str, private_key: &str) -> Result<Self> {
+        let bytes = hex::decode(private_key.trim_start_matches("0x")).context("invalid private key hex")?;
+        let wallet = PrivateKeySigner::from_slice(&bytes).context("invalid private key")?;
+        Self::new(rpc_url, wallet)
+    }
+
     /// Check if EOA has enough MATIC for gas.
     pub async fn check_gas_balance(&self) -> Result<f64> {
         let eoa = self.wallet.address();
@@ -112,20 +230,13 @@ impl PolygonMerger {
     /// Merge YES + NO tokens into USDC via on-chain transaction.
     /// `condition_id_hex` is the market's conditionId from Gamma API.
     /// `amount_tokens` is the number of token pairs to merge (float, e.g. 1.5).
-    /// Returns the transaction hash on success.
+    /// Returns the confirmed transaction and its gas on success.
     pub async fn merge_positions(
         &self,
         condition_id_hex: &str,
         amount_tokens: f64,
-    ) -> Result<String> {
-        // Convert condition_id from hex string to B256
-        let cid_clean = condition_id_hex.trim_start_matches("0x");
-        let cid_bytes = hex::decode(cid_clean)
-            .context("invalid condition_id hex")?;
-        if cid_bytes.len() != 32 {
-            bail!("condition_id must be 32 bytes, got {}", cid_bytes.len());
-        }
-        let condition_id = B256::from_slice(&cid_bytes);
+    ) -> Result<OnChainTx> {
+        let condition_id = parse_condition_id(condition_id_hex)?;
 
         // Convert token amount to raw units (6 decimals for USDC-backed tokens)
         let amount_raw = (amount_tokens * 1_000_000.0) as u64;
@@ -172,11 +283,102 @@ impl PolygonMerger {
             data: merge_calldata.into(),
         };
 
-        let factory_calldata = proxyCall {
-            calls: vec![approve_call, merge_call],
+        self.send_proxy_calls(vec![approve_call, merge_call], MERGE_GAS_LIMIT, "Merge")
+            .await
+    }
+
+    /// Redeem resolved outcome tokens for USDC via on-chain transaction.
+    /// `yes_tokens` / `no_tokens` are the amounts held of each outcome; the
+    /// winning side pays $1 a token, the losing side nothing.
+    /// Reverts (and so errors) until the market's resolution is reported on-chain.
+    /// Returns the confirmed transaction and its gas on success.
+    pub async fn redeem_positions(
+        &self,
+        condition_id_hex: &str,
+        yes_tokens: f64,
+        no_tokens: f64,
+    ) -> Result<OnChainTx> {
+        let condition_id = parse_condition_id(condition_id_hex)?;
+        let yes_raw = (yes_tokens * 1_000_000.0).floor() as u64;
+        let no_raw = (no_tokens * 1_000_000.0).floor() as u64;
+        if yes_raw == 0 && no_raw == 0 {
+            bail!("redeem amount too small: {} YES / {} NO", yes_tokens, no_tokens);
+        }
+
+        info!(
+            "Redeeming {} YES / {} NO tokens for condition {}",
+            yes_tokens, no_tokens, condition_id_hex
+        );
+
+        let approve_call = ProxyCallItem {
+            typeCode: 1, // CALL
+            to: self.ctf_address,
+            value: U256::ZERO,
+            data: setApprovalForAllCall { operator: self.neg_risk_adapter, approved: true }
+                .abi_encode()
+                .into(),
+        };
+        let redeem_call = ProxyCallItem {
+            typeCode: 1, // CALL
+            to: self.neg_risk_adapter,
+            value: U256::ZERO,
+            data: redeemPositionsCall {
+                conditionId: condition_id,
+                amounts: vec![U256::from(yes_raw), U256::from(no_raw)],
+            }
+            .abi_encode()
+            .into(),
+        };
+
+        self.send_proxy_calls(vec![approve_call, redeem_call], REDEEM_GAS_LIMIT, "Redeem")
+            .await
+    }
+
+    /// Transfer USDC from the proxy wallet to `to_address` (e.g. a cold wallet).
+    /// `amount_usdc` is in dollars (6 decimals on-chain).
+    /// Returns the confirmed transaction and its gas on success.
+    pub async fn transfer_usdc(&self, to_address: &str, amount_usdc: f64) -> Result<OnChainTx> {
+        let to_bytes = hex::decode(to_address.trim_start_matches("0x"))
+            .context("invalid destination address hex")?;
+        if to_bytes.len() != 20 {
+            bail!("destination address must be 20 bytes, got {}", to_bytes.len());
+        }
+        let to = Address::from_slice(&to_bytes);
+
+        let amount_raw = (amount_usdc * 1_000_000.0).floor() as u64;
+        if amount_raw == 0 {
+            bail!("transfer amount too small: {}", amount_usdc);
+        }
+
+        info!("Transferring ${:.2} USDC (raw={}) to {}", amount_usdc, amount_raw, to_address);
+
+        let transfer_calldata = transferCall {
+            to,
+            amount: U256::from(amount_raw),
         }
         .abi_encode();
 
+        let transfer_call = ProxyCallItem {
+            typeCode: 1, // CALL
+            to: self.usdc_address,
+            value: U256::ZERO,
+            data: transfer_calldata.into(),
+        };
+
+        self.send_proxy_calls(vec![transfer_call], TRANSFER_GAS_LIMIT, "Transfer")
+            .await
+    }
+
+    /// Sign and send a ProxyWalletFactory.proxy() call, then wait for the receipt.
+    /// A revert errors with `TxReverted`, carrying the gas it burned.
+    async fn send_proxy_calls(
+        &self,
+        calls: Vec<ProxyCallItem>,
+        gas_limit: u64,
+        label: &str,
+    ) -> Result<OnChainTx> {
+        let factory_calldata = proxyCall { calls }.abi_encode();
+
         // 4. Get nonce and gas price from Polygon RPC
         let nonce = self.get_nonce().await?;
         let gas_price = self.get_gas_price().await?;
@@ -187,7 +389,7 @@ impl PolygonMerger {
 
         // RLP encode for signing (EIP-155): [nonce, gasPrice, gasLimit, to, value, data, chainId, 0, 0]
         let sign_rlp = rlp_encode_legacy_tx(
-            nonce, gas_price, MERGE_GAS_LIMIT, to, value, &factory_calldata,
+            nonce, gas_price, gas_limit, to, value, &factory_calldata,
             Some(POLYGON_CHAIN_ID),
         );
         let tx_hash = keccak256(&sign_rlp);
@@ -203,33 +405,79 @@ impl PolygonMerger {
 
         // RLP encode signed transaction: [nonce, gasPrice, gasLimit, to, value, data, v, r, s]
         let signed_rlp = rlp_encode_signed_legacy_tx(
-            nonce, gas_price, MERGE_GAS_LIMIT, to, value, &factory_calldata, v, r, s,
+            nonce, gas_price, gas_limit, to, value, &factory_calldata, v, r, s,
         );
 
-        // 6. Send raw transaction
+        // 6. Send raw transaction. The hash is known before sending, so a
+        // send that fails in transit can still be looked up afterwards.
         let raw_hex = format!("0x{}", hex::encode(&signed_rlp));
-        let send_resp = self.rpc_call(
-            "eth_sendRawTransaction",
-            serde_json::json!([raw_hex]),
-        ).await?;
+        let tx_hash_str = format!("{:?}", keccak256(&signed_rlp));
+        let pending = || TxPending { label: label.to_string(), tx: tx_hash_str.clone() };
+        match self.rpc_call("eth_sendRawTransaction", serde_json::json!([raw_hex])).await {
+            Ok(_) => info!("{} tx sent: {}", label, tx_hash_str),
+            Err(e) if e.is::<RpcRejected>() => return Err(e),
+            Err(e) => {
+                warn!("{} tx send failed in transit ({e}) — may still land: {}", label, tx_hash_str);
+                return Err(pending().into());
+            }
+        }
 
-        let tx_hash_str = send_resp.as_str()
-            .ok_or_else(|| anyhow::anyhow!("no tx hash in response: {:?}", send_resp))?
-            .to_string();
+        // 6. Wait for confirmation (up to 30 seconds)
+        let receipt = match self.wait_for_receipt(&tx_hash_str, 30).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                warn!("{} tx {} unconfirmed: {e}", label, tx_hash_str);
+                return Err(pending().into());
+            }
+        };
 
-        info!("Merge tx sent: {}", tx_hash_str);
+        match self.settle_receipt(receipt, &tx_hash_str, label, gas_limit, gas_price) {
+            TxOutcome::Confirmed(tx) => {
+                info!(
+                    "{} confirmed! tx={} gas={} ({:.5} MATIC, ${:.4})",
+                    label, tx.hash, tx.gas.gas_used, tx.gas.matic(), tx.gas.usd()
+                );
+                Ok(tx)
+            }
+            TxOutcome::Reverted(gas) => Err(TxReverted(gas).into()),
+            TxOutcome::Pending | TxOutcome::Dropped => Err(pending().into()),
+        }
+    }
 
-        // 6. Wait for confirmation (up to 30 seconds)
-        let receipt = self.wait_for_receipt(&tx_hash_str, 30).await?;
-
-        // Check status
-        let status = receipt.status.as_deref().unwrap_or("0x0");
-        if status == "0x1" {
-            let gas_used = receipt.gas_used.as_deref().unwrap_or("?");
-            info!("Merge confirmed! tx={} gas={}", tx_hash_str, gas_used);
-            Ok(tx_hash_str)
+    /// Look up a transaction sent earlier (one a call reported as `TxPending`).
+    pub async fn tx_outcome(&self, tx_hash: &str, label: &str) -> Result<TxOutcome> {
+        let receipt = self.rpc_call("eth_getTransactionReceipt", serde_json::json!([tx_hash])).await?;
+        if !receipt.is_null() {
+            let receipt: TxReceipt = serde_json::from_value(receipt)?;
+            return Ok(self.settle_receipt(receipt, tx_hash, label, 0, 0));
+        }
+        let known = self.rpc_call("eth_getTransactionByHash", serde_json::json!([tx_hash])).await?;
+        Ok(if known.is_null() { TxOutcome::Dropped } else { TxOutcome::Pending })
+    }
+
+    /// Read a mined receipt. Gas is paid whether or not the calls went through;
+    /// `gas_limit`/`gas_price` stand in for fields the receipt leaves out.
+    fn settle_receipt(
+        &self,
+        receipt: TxReceipt,
+        tx_hash: &str,
+        label: &str,
+        gas_limit: u64,
+        gas_price: u128,
+    ) -> TxOutcome {
+        let hex_u128 = |h: &str| u128::from_str_radix(h.trim_start_matches("0x"), 16).ok();
+        let gas = GasSpend {
+            label: label.to_string(),
+            tx: tx_hash.to_string(),
+            gas_used: receipt.gas_used.as_deref().and_then(hex_u128).unwrap_or(gas_limit as u128) as u64,
+            gas_price_wei: receipt.effective_gas_price.as_deref().and_then(hex_u128).unwrap_or(gas_price),
+            matic_usd: self.matic_price.as_ref().map(|p| p.get()).unwrap_or(0.0),
+        };
+
+        if receipt.status.as_deref().unwrap_or("0x0") == "0x1" {
+            TxOutcome::Confirmed(OnChainTx { hash: tx_hash.to_string(), gas })
         } else {
-            bail!("Merge transaction reverted: tx={}", tx_hash_str);
+            TxOutcome::Reverted(gas)
         }
     }
 
@@ -254,7 +502,7 @@ impl PolygonMerger {
             .await?;
 
         if let Some(err) = resp.error {
-            bail!("RPC error in {}: {:?}", method, err);
+            return Err(RpcRejected { method: method.to_string(), detail: format!("{:?}", err) }.into());
         }
 
         resp.result.ok_or_else(|| anyhow::anyhow!("no result in {} response", method))
@@ -303,6 +551,16 @@ impl PolygonMerger {
     }
 }
 
+/// A market's conditionId (hex, as the Gamma API gives it) as bytes32.
+fn parse_condition_id(condition_id_hex: &str) -> Result<B256> {
+    let cid_bytes = hex::decode(condition_id_hex.trim_start_matches("0x"))
+        .context("invalid condition_id hex")?;
+    if cid_bytes.len() != 32 {
+        bail!("condition_id must be 32 bytes, got {}", cid_bytes.len());
+    }
+    Ok(B256::from_slice(&cid_bytes))
+}
+
 // ═══════════════════════════════════════════════════════════════════
 // Legacy transaction RLP encoding
 // ═══════════════════════════════════════════════════════════════════
@@ -338,6 +596,7 @@ fn rlp_encode_legacy_tx(
 }
 
 /// RLP encode a signed legacy tx: [nonce, gasPrice, gasLimit, to, value, data, v, r, s]
+#[allow(clippy::too_many_arguments)]
 fn rlp_encode_signed_legacy_tx(
     nonce: u64,
     gas_price: u128,
@@ -0,0 +1,163 @@
+use crate::execution::order_builder::SignedOrder;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+/// Which queue a CLOB request waits in when every slot is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// Exits and cancels: take risk off
+    High,
+    /// Entries and reads
+    Low,
+}
+
+impl Lane {
+    /// Sells unwind inventory we hold; buys open it.
+    pub fn of(order: &SignedOrder) -> Self {
+        if order.side == "SELL" {
+            Lane::High
+        } else {
+            Lane::Low
+        }
+    }
+}
+
+/// Caps the CLOB requests in flight, serving waiters by lane.
+///
+/// When the slots are saturated — a burst of entries, or 429s stretching
+/// every call — a freed slot goes to the oldest high-lane waiter before any
+/// low-lane one, so a cancel or an exit never queues behind new entries.
+/// Requests already in flight are never interrupted.
+pub struct LaneGate {
+    slots: usize,
+    state: Mutex<GateState>,
+}
+
+#[derive(Default)]
+struct GateState {
+    in_flight: usize,
+    high: VecDeque<oneshot::Sender<()>>,
+    low: VecDeque<oneshot::Sender<()>>,
+}
+
+/// A held slot; frees it on drop.
+pub struct Permit<'a> {
+    gate: &'a LaneGate,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.gate.release();
+    }
+}
+
+/// A queued `acquire`. Dropped before it's granted (the caller gave up), it
+/// hands on a slot that was already on its way.
+struct Waiter<'a> {
+    gate: &'a LaneGate,
+    rx: oneshot::Receiver<()>,
+    granted: bool,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if !self.granted {
+            self.rx.close();
+            if self.rx.try_recv().is_ok() {
+                self.gate.release();
+            }
+        }
+    }
+}
+
+impl LaneGate {
+    /// At most `slots` requests at once; at least one.
+    pub fn new(slots: usize) -> Self {
+        Self { slots: slots.max(1), state: Mutex::new(GateState::default()) }
+    }
+
+    /// Wait for a slot in `lane`.
+    pub async fn acquire(&self, lane: Lane) -> Permit<'_> {
+        let rx = {
+            let mut state = self.state.lock().unwrap();
+            // Slots are handed straight to waiters, so a free one means nobody is queued
+            if state.in_flight < self.slots {
+                state.in_flight += 1;
+                return Permit { gate: self };
+            }
+            let (tx, rx) = oneshot::channel();
+            match lane {
+                Lane::High => state.high.push_back(tx),
+                Lane::Low => state.low.push_back(tx),
+            }
+            rx
+        };
+        let mut waiter = Waiter { gate: self, rx, granted: false };
+        // The sender only goes away by sending, or with the gate itself
+        let _ = (&mut waiter.rx).await;
+        waiter.granted = true;
+        Permit { gate: self }
+    }
+
+    /// Requests waiting for a slot, (high, low).
+    pub fn waiting(&self) -> (usize, usize) {
+        let state = self.state.lock().unwrap();
+        (state.high.len(), state.low.len())
+    }
+
+    /// Pass a freed slot to the next live waiter, high lane first.
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let Some(next) = state.high.pop_front().or_else(|| state.low.pop_front()) else {
+                state.in_flight -= 1;
+                return;
+            };
+            if next.send(()).is_ok() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_high_lane_served_before_queued_entries() {
+        let gate = Arc::new(LaneGate::new(1));
+        let held = gate.acquire(Lane::Low).await;
+
+        // Two entries queue first, then a cancel
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for (name, lane) in [("entry1", Lane::Low), ("entry2", Lane::Low), ("cancel", Lane::High)] {
+            let (g, order) = (gate.clone(), order.clone());
+            tasks.push(tokio::spawn(async move {
+                let _permit = g.acquire(lane).await;
+                order.lock().unwrap().push(name);
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }));
+            while gate.waiting().0 + gate.waiting().1 < tasks.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+        assert_eq!(gate.waiting(), (1, 2));
+
+        // A waiter that gives up doesn't keep a slot
+        let abandoned = tokio::time::timeout(Duration::from_millis(1), gate.acquire(Lane::High)).await;
+        assert!(abandoned.is_err());
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), ["cancel", "entry1", "entry2"]);
+        assert_eq!(gate.waiting(), (0, 0));
+        let _a = gate.acquire(Lane::Low).await;
+    }
+}
@@ -0,0 +1,195 @@
+use dashmap::DashMap;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Why the CLOB refused an order, parsed from its error string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RejectReason {
+    /// "not enough balance / allowance"
+    InsufficientBalance,
+    /// FOK not fully fillable or FAK with nothing to match — the book moved
+    NoMatch,
+    /// Post-only order would have crossed the book
+    PostOnlyCrossed,
+    /// Price off the market's tick grid
+    InvalidTick,
+    /// Amounts rounded wrong or under the minimum order size
+    InvalidAmount,
+    /// Order signed with a stale taker fee rate
+    InvalidFeeRate,
+    /// API key or HMAC refused, or the owner doesn't match the key
+    Auth,
+    InvalidSignature,
+    Duplicate,
+    /// Market closed or not accepting orders
+    MarketClosed,
+    Other,
+}
+
+/// What to fix before the next order, given a rejection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Remediation {
+    ResyncBalance,
+    RefetchTickSize,
+    /// Learn the token's minimum order size, so undersized orders stop
+    /// going out
+    RefetchMinSize,
+    RefetchFeeRate,
+}
+
+impl RejectReason {
+    /// Classify a CLOB error message. Matching is on the stable fragments
+    /// of the messages the API has been seen to send.
+    pub fn parse(msg: &str) -> Self {
+        let m = msg.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| m.contains(n));
+        if has(&["not enough balance", "allowance"]) {
+            Self::InsufficientBalance
+        } else if has(&["couldn't be fully filled", "no orders found to match", "no match"]) {
+            Self::NoMatch
+        } else if has(&["crosses book", "post-only", "post only"]) {
+            Self::PostOnlyCrossed
+        } else if has(&["tick size", "tick_size"]) {
+            Self::InvalidTick
+        } else if has(&["fee rate"]) {
+            Self::InvalidFeeRate
+        } else if has(&["invalid amount", "minimum", "min size", "size lower than"]) {
+            Self::InvalidAmount
+        } else if has(&["order signature", "signer", "invalid maker"]) {
+            Self::InvalidSignature
+        } else if has(&["unauthorized", "api key", "invalid hmac", "l1 signature"]) {
+            Self::Auth
+        } else if has(&["duplicate"]) {
+            Self::Duplicate
+        } else if has(&["market is closed", "closed market", "not accepting orders"]) {
+            Self::MarketClosed
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::InsufficientBalance => "insufficient_balance",
+            Self::NoMatch => "no_match",
+            Self::PostOnlyCrossed => "post_only_crossed",
+            Self::InvalidTick => "invalid_tick",
+            Self::InvalidAmount => "invalid_amount",
+            Self::InvalidFeeRate => "invalid_fee_rate",
+            Self::Auth => "auth",
+            Self::InvalidSignature => "invalid_signature",
+            Self::Duplicate => "duplicate",
+            Self::MarketClosed => "market_closed",
+            Self::Other => "other",
+        }
+    }
+
+    /// The fix that makes a retry worth attempting, if there is one. No-match
+    /// and post-only rejections are normal market outcomes and need none;
+    /// auth rejections are handled by the client re-deriving its key.
+    pub fn remediation(self) -> Option<Remediation> {
+        match self {
+            Self::InsufficientBalance => Some(Remediation::ResyncBalance),
+            Self::InvalidTick => Some(Remediation::RefetchTickSize),
+            Self::InvalidAmount => Some(Remediation::RefetchMinSize),
+            Self::InvalidFeeRate => Some(Remediation::RefetchFeeRate),
+            _ => None,
+        }
+    }
+}
+
+/// Rejection counts by reason, plus the remediations they call for, queued
+/// per token until the owner of the relevant state takes them.
+#[derive(Default)]
+pub struct RejectionStats {
+    counts: DashMap<RejectReason, u64>,
+    pending: Mutex<Vec<(Remediation, String)>>,
+}
+
+impl RejectionStats {
+    /// Count a rejection of an order on `token_id`; returns its reason.
+    pub fn record(&self, msg: &str, token_id: &str) -> RejectReason {
+        let reason = RejectReason::parse(msg);
+        *self.counts.entry(reason).or_insert(0) += 1;
+        if let Some(remedy) = reason.remediation() {
+            let mut pending = self.pending.lock().unwrap();
+            if !pending.iter().any(|(r, t)| *r == remedy && t == token_id) {
+                warn!("Order rejected ({}) — queueing {remedy:?}", reason.label());
+                pending.push((remedy, token_id.to_string()));
+            }
+        }
+        reason
+    }
+
+    /// Drain queued remediations, each with the token that triggered it.
+    pub fn take_remediations(&self) -> Vec<(Remediation, String)> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    /// Rejections so far, by reason.
+    pub fn counts(&self) -> Vec<(&'static str, u64)> {
+        let mut counts: Vec<_> = self.counts.iter().map(|e| (*e.key(), *e.value())).collect();
+        counts.sort();
+        counts.into_iter().map(|(r, n)| (r.label(), n)).collect()
+    }
+
+    pub fn log_summary(&self) {
+        let counts = self.counts();
+        if !counts.is_empty() {
+            let parts: Vec<String> = counts.iter().map(|(r, n)| format!("{r}={n}")).collect();
+            info!("Order rejections: {}", parts.join(" "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_messages() {
+        let cases = [
+            ("not enough balance / allowance", RejectReason::InsufficientBalance),
+            ("order couldn't be fully filled. FOK orders are fully filled or killed.", RejectReason::NoMatch),
+            ("no orders found to match with FAK order", RejectReason::NoMatch),
+            ("invalid post-only order: order crosses book", RejectReason::PostOnlyCrossed),
+            ("order 0x1 is invalid. Price (0.555) breaks minimum tick size rule: 0.01", RejectReason::InvalidTick),
+            ("invalid amounts, the market buy orders maker amount supports a max accuracy of 2 decimals", RejectReason::InvalidAmount),
+            ("Size (2) lower than the minimum: 5", RejectReason::InvalidAmount),
+            ("invalid fee rate (0), current market's taker fee: 1000", RejectReason::InvalidFeeRate),
+            ("Unauthorized/Invalid api key", RejectReason::Auth),
+            ("the order owner has to be the owner of the API KEY", RejectReason::Auth),
+            ("Unauthorized/Invalid HMAC signature", RejectReason::Auth),
+            ("invalid order signature", RejectReason::InvalidSignature),
+            ("order signer does not match API key address", RejectReason::InvalidSignature),
+            ("duplicate order", RejectReason::Duplicate),
+            ("the market is not accepting orders", RejectReason::MarketClosed),
+            ("HTTP 400 — something new", RejectReason::Other),
+        ];
+        for (msg, want) in cases {
+            assert_eq!(RejectReason::parse(msg), want, "{msg}");
+        }
+    }
+
+    #[test]
+    fn test_stats_count_and_queue_remediations_once() {
+        let stats = RejectionStats::default();
+        stats.record("not enough balance / allowance", "1");
+        stats.record("not enough balance / allowance", "1");
+        stats.record("no orders found to match with FAK order", "1");
+        stats.record("Unauthorized/Invalid api key", "2");
+        assert_eq!(stats.counts(), [("insufficient_balance", 2), ("no_match", 1), ("auth", 1)]);
+
+        stats.record("invalid fee rate (0), current market's taker fee: 1000", "2");
+        stats.record("Size (2) lower than the minimum: 5", "2");
+        assert_eq!(
+            stats.take_remediations(),
+            [
+                (Remediation::ResyncBalance, "1".to_string()),
+                (Remediation::RefetchFeeRate, "2".to_string()),
+                (Remediation::RefetchMinSize, "2".to_string()),
+            ]
+        );
+        assert!(stats.take_remediations().is_empty());
+    }
+}
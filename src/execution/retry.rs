@@ -0,0 +1,131 @@
+use crate::config::RestRetryConfig;
+use crate::error::SattebaazError;
+use rand::Rng;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Backoff and retry budget shared by every call on one client.
+///
+/// Each call gets up to `max_retries` retries with exponential, jittered
+/// backoff, but all calls draw from one token bucket refilled at
+/// `budget_per_min` — during an outage the client fails fast instead of
+/// multiplying load on the API.
+pub struct RetryPolicy {
+    config: RestRetryConfig,
+    budget: Mutex<Budget>,
+}
+
+struct Budget {
+    tokens: f64,
+    refilled_at: Instant,
+    /// Whether exhaustion has already been logged since the last refill
+    warned: bool,
+}
+
+impl RetryPolicy {
+    pub fn new(config: RestRetryConfig) -> Self {
+        Self {
+            budget: Mutex::new(Budget {
+                tokens: config.budget_per_min as f64,
+                refilled_at: Instant::now(),
+                warned: false,
+            }),
+            config,
+        }
+    }
+
+    /// Delay before retry number `attempt` (0-based): half the exponential
+    /// step fixed, half random, so concurrent callers don't retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let step = self
+            .config
+            .base_ms
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.config.max_backoff_ms);
+        let jitter = rand::thread_rng().gen_range(0..=step / 2);
+        Duration::from_millis(step - step / 2 + jitter)
+    }
+
+    /// Take one retry from the shared budget; false once it's spent.
+    pub fn try_acquire(&self) -> bool {
+        let mut budget = self.budget.lock().unwrap();
+        let cap = self.config.budget_per_min as f64;
+        let elapsed = budget.refilled_at.elapsed().as_secs_f64();
+        budget.tokens = (budget.tokens + elapsed * cap / 60.0).min(cap);
+        budget.refilled_at = Instant::now();
+        if budget.tokens >= 1.0 {
+            budget.tokens -= 1.0;
+            budget.warned = false;
+            return true;
+        }
+        if !budget.warned {
+            warn!("CLOB retry budget exhausted ({}/min) — failing fast", self.config.budget_per_min);
+            budget.warned = true;
+        }
+        false
+    }
+
+    /// Wait out the backoff before retry `attempt` of `what`. Returns false,
+    /// without sleeping, when the call should give up instead: the error
+    /// isn't retryable, or the attempts or budget are spent. Rate limits
+    /// back off one step further than transient failures.
+    pub async fn wait(&self, attempt: u32, what: &str, err: &SattebaazError) -> bool {
+        if !err.is_retryable() || attempt >= self.config.max_retries || !self.try_acquire() {
+            return false;
+        }
+        let step = attempt + matches!(err, SattebaazError::RateLimited(_)) as u32;
+        let delay = self.backoff(step);
+        warn!("{what} attempt {} failed ({err}), retrying in {}ms", attempt + 1, delay.as_millis());
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(budget_per_min: u32) -> RetryPolicy {
+        RetryPolicy::new(RestRetryConfig {
+            max_retries: 3,
+            base_ms: 100,
+            max_backoff_ms: 1_000,
+            budget_per_min,
+            ..RestRetryConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_backoff_grows_with_jitter_and_cap() {
+        let p = policy(30);
+        for _ in 0..50 {
+            let first = p.backoff(0).as_millis();
+            assert!((50..=100).contains(&first), "{first}");
+            let third = p.backoff(2).as_millis();
+            assert!((200..=400).contains(&third), "{third}");
+            assert!(p.backoff(10).as_millis() <= 1_000);
+        }
+    }
+
+    #[test]
+    fn test_budget_is_shared_and_bounded() {
+        let p = policy(2);
+        assert!(p.try_acquire());
+        assert!(p.try_acquire());
+        assert!(!p.try_acquire());
+        // Refills at 2/min: a few ms buys nothing back
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!p.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn test_wait_respects_attempt_limit_and_category() {
+        let p = policy(30);
+        let boom = SattebaazError::Transient("boom".into());
+        assert!(!p.wait(3, "test", &boom).await);
+        assert!(!p.wait(0, "test", &SattebaazError::Rejected { reason: "no".into() }).await);
+        let p = RetryPolicy::new(RestRetryConfig { max_retries: 0, ..RestRetryConfig::default() });
+        assert!(!p.wait(0, "test", &boom).await);
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Warm-restart snapshot: trader state written on a deliberate restart so the
+/// next process can pick up its resting exit orders instead of cancelling them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot<T> {
+    pub saved_at: DateTime<Utc>,
+    pub state: T,
+}
+
+/// Write `state` atomically (temp file + rename) so a crash mid-write never
+/// leaves a truncated snapshot behind.
+pub fn save<T: Serialize>(path: &Path, state: &T) -> Result<()> {
+    let snapshot = Snapshot { saved_at: Utc::now(), state };
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(&snapshot)?)
+        .with_context(|| format!("writing {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("renaming to {}", path.display()))?;
+    Ok(())
+}
+
+/// Load and delete the snapshot at `path`. A snapshot is consumed exactly
+/// once, so a later cold start never resumes stale state. Returns None when
+/// there is no snapshot or it is older than `max_age_secs`.
+pub fn take<T: DeserializeOwned>(path: &Path, max_age_secs: u64) -> Result<Option<Snapshot<T>>> {
+    take_fresh(path, |snapshot: &Snapshot<T>| (Utc::now() - snapshot.saved_at).num_seconds() <= max_age_secs as i64)
+}
+
+/// `take`, with the caller judging whether the snapshot is still usable —
+/// say, whether the markets it holds positions in are still open.
+pub fn take_fresh<T: DeserializeOwned>(
+    path: &Path,
+    is_fresh: impl FnOnce(&Snapshot<T>) -> bool,
+) -> Result<Option<Snapshot<T>>> {
+    let bytes = match std::fs::read(path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    std::fs::remove_file(path).with_context(|| format!("removing {}", path.display()))?;
+
+    let snapshot: Snapshot<T> =
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+    if !is_fresh(&snapshot) {
+        let age = (Utc::now() - snapshot.saved_at).num_seconds();
+        tracing::warn!("Ignoring session snapshot {} — stale ({age}s old)", path.display());
+        return Ok(None);
+    }
+    Ok(Some(snapshot))
+}
+
+/// What a restored order ID turned out to be, from the CLOB `GET /order` status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestingStatus {
+    /// Still on the book — keep managing it
+    Live,
+    /// Filled while we were down
+    Matched,
+    /// Cancelled, expired or unknown — needs replacing
+    Gone,
+}
+
+impl RestingStatus {
+    pub fn from_clob(status: &str) -> Self {
+        match status.to_ascii_uppercase().as_str() {
+            "LIVE" | "DELAYED" | "UNMATCHED" => Self::Live,
+            "MATCHED" => Self::Matched,
+            _ => Self::Gone,
+        }
+    }
+}
+
+/// Serde adapter for `tokio::time::Instant` fields: stored as seconds elapsed
+/// at save time, restored as that far before now.
+pub mod instant_age {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use tokio::time::{Duration, Instant};
+
+    pub fn serialize<S: Serializer>(at: &Instant, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_f64(at.elapsed().as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Instant, D::Error> {
+        let age = f64::deserialize(d)?.max(0.0);
+        let now = Instant::now();
+        Ok(now.checked_sub(Duration::from_secs_f64(age)).unwrap_or(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct State {
+        capital: f64,
+        sell_order_id: Option<String>,
+        #[serde(with = "instant_age")]
+        opened_at: tokio::time::Instant,
+    }
+
+    #[test]
+    fn test_save_then_take_once() {
+        let path = std::env::temp_dir().join(format!("sattebaaz-session-{}.json", uuid::Uuid::new_v4()));
+        let opened_at = tokio::time::Instant::now() - std::time::Duration::from_secs(40);
+        save(&path, &State { capital: 12.5, sell_order_id: Some("0xabc".into()), opened_at }).unwrap();
+
+        let snapshot: Snapshot<State> = take(&path, 60).unwrap().unwrap();
+        assert_eq!(snapshot.state.capital, 12.5);
+        assert_eq!(snapshot.state.sell_order_id.as_deref(), Some("0xabc"));
+        let held = snapshot.state.opened_at.elapsed().as_secs();
+        assert!((40..=41).contains(&held), "held {held}s");
+
+        // Consumed: a second start is a cold start
+        assert!(take::<State>(&path, 60).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stale_snapshot_ignored() {
+        let path = std::env::temp_dir().join(format!("sattebaaz-session-{}.json", uuid::Uuid::new_v4()));
+        let old = Snapshot { saved_at: Utc::now() - chrono::Duration::seconds(900), state: 1u32 };
+        std::fs::write(&path, serde_json::to_vec(&old).unwrap()).unwrap();
+        assert!(take::<u32>(&path, 600).unwrap().is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resting_status() {
+        assert_eq!(RestingStatus::from_clob("LIVE"), RestingStatus::Live);
+        assert_eq!(RestingStatus::from_clob("matched"), RestingStatus::Matched);
+        assert_eq!(RestingStatus::from_clob("CANCELED"), RestingStatus::Gone);
+        assert_eq!(RestingStatus::from_clob("UNKNOWN"), RestingStatus::Gone);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::error::SattebaazError;
+use crate::execution::clob_client::ClobClient;
+use crate::execution::session::RestingStatus;
+use crate::feeds::user_ws::FillEvent;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// An entry booked on an assumed fill, still waiting for the exchange to
+/// say what actually happened.
+#[derive(Debug, Clone)]
+struct Pending {
+    token_id: String,
+    assumed_shares: f64,
+    /// Shares seen on the user channel so far
+    ws_shares: f64,
+    /// `size_matched` from the last poll that found the order
+    last_matched: Option<f64>,
+    polls: u32,
+}
+
+/// How an assumed fill was finally settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementSource {
+    /// `GET /order` reported a final status
+    Poll,
+    /// User-channel fills covered the assumed size
+    UserWs,
+    /// Never resolved within the poll budget; cancelled and settled at what
+    /// had matched by then
+    GaveUp,
+}
+
+/// The outcome of an assumed fill.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    pub order_id: String,
+    pub token_id: String,
+    pub assumed_shares: f64,
+    pub actual_shares: f64,
+    pub source: SettlementSource,
+}
+
+impl Settlement {
+    /// Whether the assumption was wrong by more than share rounding.
+    pub fn needs_correction(&self) -> bool {
+        (self.actual_shares - self.assumed_shares).abs() >= 0.01
+    }
+
+    /// Fraction of the assumed position that really exists.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.assumed_shares > 0.0 {
+            self.actual_shares / self.assumed_shares
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Tracks orders whose fill was assumed after a short verification loop and
+/// keeps polling them in the background, reconciling with user-channel
+/// fills. Settled outcomes queue up for the owner of the positions to apply.
+pub struct SettlementTracker {
+    pending: Mutex<HashMap<String, Pending>>,
+    settled: Mutex<Vec<Settlement>>,
+    /// Polls that may still report LIVE before giving up
+    max_polls: u32,
+}
+
+impl SettlementTracker {
+    pub fn new(max_polls: u32) -> Self {
+        Self { pending: Mutex::new(HashMap::new()), settled: Mutex::new(Vec::new()), max_polls }
+    }
+
+    /// Start tracking an order booked as filled for `assumed_shares`.
+    pub fn track(&self, order_id: &str, token_id: &str, assumed_shares: f64) {
+        self.pending.lock().unwrap().insert(
+            order_id.to_string(),
+            Pending { token_id: token_id.to_string(), assumed_shares, ws_shares: 0.0, last_matched: None, polls: 0 },
+        );
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Drain settled outcomes.
+    pub fn take_settlements(&self) -> Vec<Settlement> {
+        std::mem::take(&mut *self.settled.lock().unwrap())
+    }
+
+    /// Count a user-channel fill; settles once fills cover the assumed size.
+    pub fn on_fill(&self, fill: &FillEvent) {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(p) = pending.get_mut(&fill.order_id) else { return };
+        p.ws_shares += fill.size.to_f64().unwrap_or(0.0);
+        if p.ws_shares + 1e-9 >= p.assumed_shares {
+            let p = pending.remove(&fill.order_id).unwrap();
+            drop(pending);
+            self.settle(&fill.order_id, p.token_id, p.assumed_shares, p.ws_shares, SettlementSource::UserWs);
+        }
+    }
+
+    /// Apply one `GET /order` result (status, size matched). Returns true
+    /// once the poll budget is spent on an order still LIVE: the caller
+    /// cancels it and then calls `give_up`.
+    pub fn on_status(&self, order_id: &str, status: &str, size_matched: f64) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(p) = pending.get_mut(order_id) else { return false };
+        match RestingStatus::from_clob(status) {
+            RestingStatus::Matched | RestingStatus::Gone => {
+                let actual = size_matched.max(p.ws_shares);
+                let p = pending.remove(order_id).unwrap();
+                drop(pending);
+                self.settle(order_id, p.token_id, p.assumed_shares, actual, SettlementSource::Poll);
+                false
+            }
+            RestingStatus::Live => {
+                p.last_matched = Some(size_matched);
+                p.polls += 1;
+                p.polls >= self.max_polls
+            }
+        }
+    }
+
+    /// A poll that got no status. Returns true once the poll budget is
+    /// spent, as `on_status` does.
+    fn on_unknown(&self, order_id: &str) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        let Some(p) = pending.get_mut(order_id) else { return false };
+        p.polls += 1;
+        p.polls >= self.max_polls
+    }
+
+    /// Settle an order we stopped waiting on and cancelled. Only what is
+    /// known to have matched counts — `final_matched` from a poll after the
+    /// cancel, else the last poll's, else nothing — never the assumed size,
+    /// which a resting remainder may never fill.
+    pub fn give_up(&self, order_id: &str, final_matched: Option<f64>) {
+        let Some(p) = self.pending.lock().unwrap().remove(order_id) else { return };
+        let matched = final_matched.or(p.last_matched).unwrap_or(0.0);
+        self.settle(order_id, p.token_id, p.assumed_shares, matched.max(p.ws_shares), SettlementSource::GaveUp);
+    }
+
+    fn settle(&self, order_id: &str, token_id: String, assumed_shares: f64, actual_shares: f64, source: SettlementSource) {
+        let settlement =
+            Settlement { order_id: order_id.to_string(), token_id, assumed_shares, actual_shares, source };
+        if settlement.needs_correction() {
+            warn!(
+                "Assumed fill on {order_id} was wrong: {assumed_shares:.2} booked, {actual_shares:.2} filled ({source:?})"
+            );
+        } else {
+            info!("Assumed fill on {order_id} confirmed: {actual_shares:.2} shares ({source:?})");
+        }
+        self.settled.lock().unwrap().push(settlement);
+    }
+
+    /// Poll every pending order once.
+    pub async fn poll_once(&self, clob: &ClobClient) {
+        let ids: Vec<String> = self.pending.lock().unwrap().keys().cloned().collect();
+        for id in ids {
+            let out_of_polls = match clob.get_order(&id).await {
+                Ok((status, size_matched)) => self.on_status(&id, &status, size_matched),
+                Err(SattebaazError::Rejected { .. }) => self.on_unknown(&id),
+                Err(e) => {
+                    warn!("Settlement poll for {id} failed ({}): {e}", e.category());
+                    self.on_unknown(&id)
+                }
+            };
+            if out_of_polls {
+                // Stop the remainder filling behind our back before settling
+                if let Err(e) = clob.cancel_order(&id).await {
+                    warn!("Cancel of unsettled order {id} failed ({}): {e}", e.category());
+                }
+                let final_matched = clob.get_order(&id).await.ok().map(|(_, matched)| matched);
+                self.give_up(&id, final_matched);
+            }
+        }
+    }
+
+    /// Poll pending orders every `period` and fold in user-channel fills
+    /// until shutdown.
+    pub fn spawn(
+        self: &Arc<Self>,
+        clob: Arc<ClobClient>,
+        mut fills: broadcast::Receiver<FillEvent>,
+        period: Duration,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => tracker.poll_once(&clob).await,
+                    fill = fills.recv() => match fill {
+                        Ok(fill) => tracker.on_fill(&fill),
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Settlement tracker missed {n} fills — polling covers them");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderSide;
+    use rust_decimal_macros::dec;
+
+    fn fill(order_id: &str, size: rust_decimal::Decimal) -> FillEvent {
+        FillEvent {
+            order_id: order_id.into(),
+            token_id: "t".into(),
+            market_id: String::new(),
+            side: OrderSide::Buy,
+            market_side: Side::Yes,
+            price: dec!(0.50),
+            size,
+            fee: dec!(0),
+            strategy_tag: String::new(),
+            trade_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_settles_from_status_and_fills() {
+        let tracker = SettlementTracker::new(3);
+        tracker.track("killed", "t", 10.0);
+        tracker.track("matched", "t", 10.0);
+        tracker.track("ws", "t", 10.0);
+        tracker.track("stuck", "t", 10.0);
+
+        tracker.on_status("killed", "CANCELED", 0.0);
+        tracker.on_status("matched", "MATCHED", 9.996);
+        tracker.on_fill(&fill("ws", dec!(4)));
+        assert_eq!(tracker.pending_count(), 2);
+        tracker.on_fill(&fill("ws", dec!(6)));
+        assert!(!tracker.on_status("stuck", "LIVE", 2.0));
+        assert!(!tracker.on_status("stuck", "LIVE", 4.0));
+        assert!(tracker.on_status("stuck", "LIVE", 4.0));
+        assert_eq!(tracker.pending_count(), 1, "Still LIVE: waits for the cancel");
+        tracker.give_up("stuck", None);
+        tracker.on_fill(&fill("untracked", dec!(1)));
+
+        let settled = tracker.take_settlements();
+        let by_id = |id: &str| settled.iter().find(|s| s.order_id == id).unwrap();
+        assert_eq!(by_id("killed").actual_shares, 0.0);
+        assert!(by_id("killed").needs_correction());
+        assert!(!by_id("matched").needs_correction());
+        assert_eq!(by_id("ws").source, SettlementSource::UserWs);
+        // Still LIVE past the budget: cancelled and settled at what had matched
+        assert_eq!(by_id("stuck").source, SettlementSource::GaveUp);
+        assert_eq!(by_id("stuck").actual_shares, 4.0);
+        assert_eq!(tracker.pending_count(), 0);
+        assert!(tracker.take_settlements().is_empty());
+    }
+
+    #[test]
+    fn test_unknown_orders_fall_back_to_seen_fills() {
+        let tracker = SettlementTracker::new(2);
+        tracker.track("o", "t", 10.0);
+        tracker.on_fill(&fill("o", dec!(3)));
+        assert!(!tracker.on_unknown("o"));
+        assert!(tracker.on_unknown("o"));
+        tracker.give_up("o", None);
+        tracker.track("never_seen", "t", 10.0);
+        tracker.give_up("never_seen", None);
+        let settled = tracker.take_settlements();
+        assert_eq!(settled[0].actual_shares, 3.0);
+        assert!((settled[0].fill_ratio() - 0.3).abs() < 1e-9);
+        // No evidence of a fill: nothing is booked
+        assert_eq!(settled[1].actual_shares, 0.0);
+    }
+}
@@ -1,11 +1,13 @@
-use crate::config::BinanceConfig;
+use crate::config::{BinanceConfig, PriceSeries};
+use crate::feeds::liquidations::{LiquidationAggregator, LiquidationVenue};
 use crate::models::market::Asset;
+use arc_swap::ArcSwapOption;
 use chrono::{DateTime, Utc};
 use futures_util::StreamExt;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use tokio_tungstenite::connect_async;
 use tracing::{debug, error, info, warn};
 
@@ -13,17 +15,43 @@ use tracing::{debug, error, info, warn};
 ///
 /// Connects to Binance WebSocket for:
 ///   - Aggregate trades (price updates every ~100ms)
+///   - Mark and index prices (every 1s)
 ///   - Forced liquidations (for cascade detection)
+///
+/// and, on a second connection, spot aggregate trades (for the basis signal).
 pub struct BinanceFeed {
     config: BinanceConfig,
     /// Latest prices per asset, updated on every aggTrade
-    pub prices: Arc<RwLock<HashMap<Asset, PriceState>>>,
+    pub prices: Arc<LatestPrices>,
+    /// Latest mark/index prices per asset, from `@markPrice`
+    pub mark_prices: Arc<RwLock<HashMap<Asset, MarkPriceState>>>,
     /// Latest funding rates per asset
     pub funding_rates: Arc<RwLock<HashMap<Asset, f64>>>,
-    /// Net liquidations per asset over rolling 60s window (positive = longs liquidated)
-    pub net_liquidations: Arc<RwLock<HashMap<Asset, f64>>>,
+    /// Net liquidations per asset over rolling 60s window (positive = longs liquidated),
+    /// shared with the other venues' liquidation feeds
+    pub liquidations: Arc<LiquidationAggregator>,
     /// Price update broadcast (asset, price) for downstream consumers
     pub price_tx: broadcast::Sender<(Asset, f64)>,
+    /// Latest spot prices per asset, from the spot connection
+    pub spot_prices: Arc<LatestPrices>,
+    pub spot_tx: broadcast::Sender<(Asset, f64)>,
+}
+
+/// Where one connection's parsed messages land.
+#[derive(Clone)]
+struct StreamSinks {
+    prices: Arc<LatestPrices>,
+    marks: Arc<RwLock<HashMap<Asset, MarkPriceState>>>,
+    liqs: Arc<LiquidationAggregator>,
+    price_tx: broadcast::Sender<(Asset, f64)>,
+}
+
+/// Futures mark price and the spot index it's built from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkPriceState {
+    pub mark: f64,
+    pub index: f64,
+    pub timestamp: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -43,29 +71,130 @@ impl PriceState {
     }
 }
 
+impl PriceState {
+    /// First print of a series: nothing to compare against yet.
+    fn first(price: f64, now: DateTime<Utc>) -> Self {
+        Self { price, price_1s_ago: price, timestamp: now, last_1s_update: now.timestamp_millis() }
+    }
+}
+
+/// Latest `PriceState` of one instrument. Reads are wait-free loads, so
+/// evaluation tasks never queue behind the feed's writes. A latest-value
+/// channel serves consumers that wait on a new price but only want the
+/// freshest one, not every print since.
+pub struct PriceSlot {
+    slot: ArcSwapOption<PriceState>,
+    watch: watch::Sender<Option<PriceState>>,
+}
+
+impl Default for PriceSlot {
+    fn default() -> Self {
+        Self { slot: Default::default(), watch: watch::channel(None).0 }
+    }
+}
+
+impl PriceSlot {
+    /// Watch the price. Updates coalesce: a slow reader skips straight to
+    /// the newest value.
+    pub fn watch(&self) -> watch::Receiver<Option<PriceState>> {
+        self.watch.subscribe()
+    }
+
+    pub fn get(&self) -> Option<PriceState> {
+        self.slot.load().as_deref().copied()
+    }
+
+    /// Record a print at `now`, rolling the 1s-ago snapshot once a second.
+    pub fn record(&self, price: f64, now: DateTime<Utc>) {
+        let now_ms = now.timestamp_millis();
+        let mut latest = None;
+        self.slot.rcu(|prev| {
+            let mut state = prev.as_deref().copied().unwrap_or(PriceState::first(price, now));
+            // Update 1-second ago snapshot every 1000ms
+            if now_ms - state.last_1s_update >= 1000 {
+                state.price_1s_ago = state.price;
+                state.last_1s_update = now_ms;
+            }
+            state.price = price;
+            state.timestamp = now;
+            latest = Some(state);
+            Some(Arc::new(state))
+        });
+        self.watch.send_replace(latest);
+    }
+}
+
+/// One `PriceSlot` per `Asset::ALL` entry.
+#[derive(Default)]
+pub struct LatestPrices {
+    slots: [PriceSlot; Asset::ALL.len()],
+}
+
+impl LatestPrices {
+    /// Watch an asset's price.
+    pub fn watch(&self, asset: Asset) -> watch::Receiver<Option<PriceState>> {
+        self.slots[asset.index()].watch()
+    }
+
+    pub fn get(&self, asset: Asset) -> Option<PriceState> {
+        self.slots[asset.index()].get()
+    }
+
+    /// Record a trade print for `asset` at `now`.
+    pub fn record(&self, asset: Asset, price: f64, now: DateTime<Utc>) {
+        self.slots[asset.index()].record(price, now)
+    }
+}
+
 impl BinanceFeed {
     pub fn new(config: BinanceConfig) -> Self {
         let (price_tx, _) = broadcast::channel(1024);
+        let (spot_tx, _) = broadcast::channel(1024);
         Self {
             config,
-            prices: Arc::new(RwLock::new(HashMap::new())),
+            prices: Arc::new(LatestPrices::default()),
+            mark_prices: Arc::new(RwLock::new(HashMap::new())),
             funding_rates: Arc::new(RwLock::new(HashMap::new())),
-            net_liquidations: Arc::new(RwLock::new(HashMap::new())),
+            liquidations: Arc::new(LiquidationAggregator::new()),
             price_tx,
+            spot_prices: Arc::new(LatestPrices::default()),
+            spot_tx,
+        }
+    }
+
+    /// Sinks for the futures connection.
+    fn sinks(&self) -> StreamSinks {
+        StreamSinks {
+            prices: self.prices.clone(),
+            marks: self.mark_prices.clone(),
+            liqs: self.liquidations.clone(),
+            price_tx: self.price_tx.clone(),
         }
     }
 
     /// Start the WebSocket feed. Spawns a background reconnecting task.
-    pub fn start(&self, mut shutdown: broadcast::Receiver<()>) {
-        let streams: Vec<String> = self.config.streams.clone();
-        let ws_base = self.config.ws_url.clone();
-        let prices = self.prices.clone();
-        let net_liqs = self.net_liquidations.clone();
-        let price_tx = self.price_tx.clone();
+    pub fn start(&self, shutdown: broadcast::Receiver<()>) {
+        let ws_url = format!("{}/stream?streams={}", self.config.ws_url, self.config.streams.join("/"));
+        Self::run_stream("binance", ws_url, shutdown, self.sinks());
+    }
 
+    /// Start the spot trade feed on its own connection; prints land in
+    /// `spot_prices` and on `spot_tx`.
+    pub fn start_spot(&self, shutdown: broadcast::Receiver<()>) {
+        if self.config.spot_streams.is_empty() {
+            return;
+        }
+        let ws_url = format!("{}/stream?streams={}", self.config.spot_ws_url, self.config.spot_streams.join("/"));
+        let sinks = StreamSinks {
+            prices: self.spot_prices.clone(),
+            price_tx: self.spot_tx.clone(),
+            ..self.sinks()
+        };
+        Self::run_stream("binance_spot", ws_url, shutdown, sinks);
+    }
+
+    fn run_stream(name: &'static str, ws_url: String, mut shutdown: broadcast::Receiver<()>, sinks: StreamSinks) {
         tokio::spawn(async move {
-            let combined = streams.join("/");
-            let ws_url = format!("{}/stream?streams={}", ws_base, combined);
             let mut backoff_ms: u64 = 500;
 
             loop {
@@ -74,14 +203,14 @@ impl BinanceFeed {
                 let conn = tokio::select! {
                     result = connect_async(&ws_url) => result,
                     _ = shutdown.recv() => {
-                        info!("Binance feed shutdown");
+                        info!("{name} feed shutdown");
                         return;
                     }
                 };
 
                 match conn {
                     Ok((ws_stream, _)) => {
-                        info!("Binance WS connected");
+                        info!("{name} WS connected");
                         backoff_ms = 500; // Reset backoff on success
 
                         let (_, mut read) = ws_stream.split();
@@ -90,43 +219,37 @@ impl BinanceFeed {
                             let msg = tokio::select! {
                                 msg = read.next() => msg,
                                 _ = shutdown.recv() => {
-                                    info!("Binance feed shutdown");
+                                    info!("{name} feed shutdown");
                                     return;
                                 }
                             };
 
                             match msg {
                                 Some(Ok(tokio_tungstenite::tungstenite::Message::Text(text))) => {
-                                    Self::handle_message(
-                                        &text,
-                                        &prices,
-                                        &net_liqs,
-                                        &price_tx,
-                                    )
-                                    .await;
+                                    Self::handle_message(&text, &sinks).await;
                                 }
                                 Some(Ok(tokio_tungstenite::tungstenite::Message::Ping(_))) => {
                                     debug!("Binance ping");
                                 }
                                 Some(Ok(_)) => {} // Binary, Pong, Close, Frame
                                 Some(Err(e)) => {
-                                    warn!("Binance WS error: {e}");
+                                    warn!("{name} WS error: {e}");
                                     break; // Reconnect
                                 }
                                 None => {
-                                    warn!("Binance WS stream ended");
+                                    warn!("{name} WS stream ended");
                                     break; // Reconnect
                                 }
                             }
                         }
                     }
                     Err(e) => {
-                        error!("Binance WS connection failed: {e}");
+                        error!("{name} WS connection failed: {e}");
                     }
                 }
 
                 // Exponential backoff reconnect
-                warn!("Reconnecting in {backoff_ms}ms...");
+                crate::telemetry::events::Reconnect { feed: name, backoff_ms }.emit();
                 tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
                 backoff_ms = (backoff_ms * 2).min(30_000);
             }
@@ -134,12 +257,7 @@ impl BinanceFeed {
     }
 
     /// Parse and route a combined stream message.
-    async fn handle_message(
-        text: &str,
-        prices: &Arc<RwLock<HashMap<Asset, PriceState>>>,
-        net_liqs: &Arc<RwLock<HashMap<Asset, f64>>>,
-        price_tx: &broadcast::Sender<(Asset, f64)>,
-    ) {
+    async fn handle_message(text: &str, sinks: &StreamSinks) {
         // Binance combined stream wraps in {"stream":"...", "data":{...}}
         let envelope: CombinedStreamMsg = match serde_json::from_str(text) {
             Ok(v) => v,
@@ -150,20 +268,24 @@ impl BinanceFeed {
 
         if stream.ends_with("@aggTrade") {
             if let Ok(trade) = serde_json::from_value::<AggTradeMsg>(envelope.data) {
-                Self::on_agg_trade(trade, prices, price_tx).await;
+                Self::on_agg_trade(trade, &sinks.prices, &sinks.price_tx);
+            }
+        } else if stream.contains("@markPrice") {
+            if let Ok(mark) = serde_json::from_value::<MarkPriceMsg>(envelope.data) {
+                Self::on_mark_price(mark, &sinks.marks).await;
             }
         } else if stream.contains("@forceOrder") {
             if let Ok(fo) = serde_json::from_value::<ForceOrderWrapper>(envelope.data) {
-                Self::on_force_order(fo.o, net_liqs).await;
+                Self::on_force_order(fo.o, &sinks.liqs);
             }
         }
         // kline messages can be added later
     }
 
     /// Process an aggregate trade update.
-    async fn on_agg_trade(
+    fn on_agg_trade(
         trade: AggTradeMsg,
-        prices: &Arc<RwLock<HashMap<Asset, PriceState>>>,
+        prices: &LatestPrices,
         price_tx: &broadcast::Sender<(Asset, f64)>,
     ) {
         let asset = match Self::symbol_to_asset(&trade.symbol) {
@@ -172,40 +294,33 @@ impl BinanceFeed {
         };
 
         let price: f64 = match trade.price.parse() {
-            Ok(p) => p,
-            Err(_) => return,
+            Ok(p) if f64::is_finite(p) && p > 0.0 => p,
+            _ => return,
         };
 
-        let now = Utc::now();
-        let now_ms = now.timestamp_millis();
-
-        let mut map = prices.write().await;
-        let state = map.entry(asset).or_insert(PriceState {
-            price,
-            price_1s_ago: price,
-            timestamp: now,
-            last_1s_update: now_ms,
-        });
-
-        // Update 1-second ago snapshot every 1000ms
-        if now_ms - state.last_1s_update >= 1000 {
-            state.price_1s_ago = state.price;
-            state.last_1s_update = now_ms;
-        }
-
-        state.price = price;
-        state.timestamp = now;
-        drop(map);
+        prices.record(asset, price, Utc::now());
 
         // Broadcast to downstream consumers (non-blocking, ignore if no receivers)
         let _ = price_tx.send((asset, price));
     }
 
+    /// Process a mark/index price update.
+    async fn on_mark_price(msg: MarkPriceMsg, marks: &RwLock<HashMap<Asset, MarkPriceState>>) {
+        let Some(asset) = Self::symbol_to_asset(&msg.symbol) else {
+            return;
+        };
+        let (Ok(mark), Ok(index)) = (msg.mark_price.parse::<f64>(), msg.index_price.parse::<f64>()) else {
+            return;
+        };
+        // Written so NaN fails too
+        if !(mark.is_finite() && mark > 0.0 && index.is_finite() && index > 0.0) {
+            return;
+        }
+        marks.write().await.insert(asset, MarkPriceState { mark, index, timestamp: Utc::now() });
+    }
+
     /// Process a forced liquidation event.
-    async fn on_force_order(
-        order: ForceOrderData,
-        net_liqs: &Arc<RwLock<HashMap<Asset, f64>>>,
-    ) {
+    fn on_force_order(order: ForceOrderData, liqs: &LiquidationAggregator) {
         let asset = match Self::symbol_to_asset(&order.symbol) {
             Some(a) => a,
             None => return,
@@ -214,39 +329,49 @@ impl BinanceFeed {
         let qty: f64 = order.quantity.parse().unwrap_or(0.0);
         let price: f64 = order.price.parse().unwrap_or(0.0);
         let notional = qty * price;
+        // A NaN or infinite notional would poison the aggregator for good
+        if !(notional.is_finite() && notional > 0.0) {
+            return;
+        }
 
         // side=SELL means long was liquidated (bearish), side=BUY means short liquidated (bullish)
-        let signed = if order.side == "SELL" {
-            notional // Longs liquidated = positive
-        } else {
-            -notional // Shorts liquidated = negative
-        };
+        liqs.record(asset, LiquidationVenue::Binance, notional, order.side == "SELL");
+    }
 
-        let mut map = net_liqs.write().await;
-        *map.entry(asset).or_insert(0.0) += signed;
+    /// Get current price for an asset.
+    pub fn get_price(&self, asset: Asset) -> Option<f64> {
+        self.prices.get(asset).map(|s| s.price)
+    }
 
-        debug!(
-            "Liquidation: {:?} {} ${:.0} (net={:.0})",
-            asset,
-            order.side,
-            notional,
-            map.get(&asset).unwrap_or(&0.0)
-        );
+    /// Latest mark and index price for an asset.
+    pub async fn get_mark_price(&self, asset: Asset) -> Option<MarkPriceState> {
+        self.mark_prices.read().await.get(&asset).copied()
     }
 
-    /// Get current price for an asset.
-    pub async fn get_price(&self, asset: Asset) -> Option<f64> {
-        self.prices.read().await.get(&asset).map(|s| s.price)
+    /// Price for `asset` on the chosen series, falling back to the last
+    /// trade until the first mark price update arrives.
+    pub async fn get_price_for(&self, asset: Asset, series: PriceSeries) -> Option<f64> {
+        let mark = match series {
+            PriceSeries::Last => None,
+            PriceSeries::Mark => self.get_mark_price(asset).await.map(|m| m.mark),
+            PriceSeries::Index => self.get_mark_price(asset).await.map(|m| m.index),
+        };
+        mark.or_else(|| self.get_price(asset))
+    }
+
+    /// Latest spot price for an asset.
+    pub fn get_spot_price(&self, asset: Asset) -> Option<f64> {
+        self.spot_prices.get(asset).map(|s| s.price)
+    }
+
+    /// Subscribe to every spot print.
+    pub fn subscribe_spot_prices(&self) -> broadcast::Receiver<(Asset, f64)> {
+        self.spot_tx.subscribe()
     }
 
     /// Get 1-second price move percentage for an asset.
-    pub async fn get_1s_move_pct(&self, asset: Asset) -> f64 {
-        self.prices
-            .read()
-            .await
-            .get(&asset)
-            .map(|s| s.move_pct_1s())
-            .unwrap_or(0.0)
+    pub fn get_1s_move_pct(&self, asset: Asset) -> f64 {
+        self.prices.get(asset).map(|s| s.move_pct_1s()).unwrap_or(0.0)
     }
 
     /// Get current funding rate for an asset.
@@ -259,29 +384,26 @@ impl BinanceFeed {
             .unwrap_or(0.0)
     }
 
-    /// Get net liquidations for an asset (positive = longs liquidated = bearish).
-    pub async fn get_net_liquidations(&self, asset: Asset) -> f64 {
-        self.net_liquidations
-            .read()
-            .await
-            .get(&asset)
-            .copied()
-            .unwrap_or(0.0)
+    /// Get net liquidations for an asset across all venues (positive = longs liquidated = bearish).
+    pub fn get_net_liquidations(&self, asset: Asset) -> f64 {
+        self.liquidations.net(asset)
     }
 
     /// Reset liquidation accumulator (call periodically, e.g. every 60s).
-    pub async fn reset_liquidations(&self) {
-        let mut map = self.net_liquidations.write().await;
-        for v in map.values_mut() {
-            *v *= 0.5; // Decay rather than reset, so recent liqs still have weight
-        }
+    pub fn reset_liquidations(&self) {
+        self.liquidations.decay(0.5); // Decay rather than reset, so recent liqs still have weight
     }
 
-    /// Subscribe to price updates.
+    /// Subscribe to every price update, for consumers that need each print.
     pub fn subscribe_prices(&self) -> broadcast::Receiver<(Asset, f64)> {
         self.price_tx.subscribe()
     }
 
+    /// Latest-value channel for one asset's price.
+    pub fn watch_price(&self, asset: Asset) -> watch::Receiver<Option<PriceState>> {
+        self.prices.watch(asset)
+    }
+
     /// Start periodic funding rate polling from Binance REST API (every 60s).
     pub fn start_funding_poller(&self, mut shutdown: broadcast::Receiver<()>) {
         let funding = self.funding_rates.clone();
@@ -358,6 +480,16 @@ struct AggTradeMsg {
     event_time: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct MarkPriceMsg {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "p")]
+    mark_price: String,
+    #[serde(rename = "i")]
+    index_price: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct ForceOrderWrapper {
     o: ForceOrderData,
@@ -374,3 +506,172 @@ struct ForceOrderData {
     #[serde(rename = "p")]
     price: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_latest_prices_roll_1s_snapshot() {
+        let prices = LatestPrices::default();
+        let t0 = Utc::now();
+        assert!(prices.get(Asset::BTC).is_none());
+
+        prices.record(Asset::BTC, 100_000.0, t0);
+        prices.record(Asset::BTC, 100_050.0, t0 + chrono::Duration::milliseconds(400));
+        let state = prices.get(Asset::BTC).unwrap();
+        assert_eq!((state.price, state.price_1s_ago), (100_050.0, 100_000.0));
+
+        // Past a second the previous print becomes the 1s-ago reference
+        prices.record(Asset::BTC, 100_100.0, t0 + chrono::Duration::milliseconds(1_100));
+        let state = prices.get(Asset::BTC).unwrap();
+        assert_eq!(state.price_1s_ago, 100_050.0);
+        assert!((state.move_pct_1s() - 50.0 / 100_050.0).abs() < 1e-12);
+        assert!(prices.get(Asset::ETH).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mark_price_stream_and_series_choice() {
+        let feed = BinanceFeed::new(crate::config::Config::default().binance);
+        let msg = r#"{"stream":"btcusdt@markPrice@1s","data":{"e":"markPriceUpdate","E":1700000000000,"s":"BTCUSDT","p":"100010.50","i":"100002.25","P":"100003.00","r":"0.00010000","T":1700006400000}}"#;
+        BinanceFeed::handle_message(msg, &feed.sinks()).await;
+
+        let mark = feed.get_mark_price(Asset::BTC).await.unwrap();
+        assert_eq!((mark.mark, mark.index), (100_010.5, 100_002.25));
+        assert!(feed.get_mark_price(Asset::ETH).await.is_none());
+
+        feed.prices.record(Asset::BTC, 100_020.0, Utc::now());
+        assert_eq!(feed.get_price_for(Asset::BTC, PriceSeries::Last).await, Some(100_020.0));
+        assert_eq!(feed.get_price_for(Asset::BTC, PriceSeries::Mark).await, Some(100_010.5));
+        assert_eq!(feed.get_price_for(Asset::BTC, PriceSeries::Index).await, Some(100_002.25));
+
+        // No mark yet: fall back to the last trade
+        feed.prices.record(Asset::ETH, 3_000.0, Utc::now());
+        assert_eq!(feed.get_price_for(Asset::ETH, PriceSeries::Index).await, Some(3_000.0));
+    }
+
+    #[test]
+    fn test_price_watch_coalesces_to_latest() {
+        let prices = LatestPrices::default();
+        let mut btc = prices.watch(Asset::BTC);
+        let eth = prices.watch(Asset::ETH);
+        assert!(btc.borrow().is_none());
+
+        let t0 = Utc::now();
+        for (i, price) in [100_000.0, 100_010.0, 100_020.0].into_iter().enumerate() {
+            prices.record(Asset::BTC, price, t0 + chrono::Duration::milliseconds(i as i64 * 100));
+        }
+        assert!(btc.has_changed().unwrap());
+        assert_eq!(btc.borrow_and_update().unwrap().price, 100_020.0);
+        assert!(!btc.has_changed().unwrap());
+        assert!(!eth.has_changed().unwrap());
+    }
+
+    /// Field values as the wire might garble them: numbers as strings or
+    /// not, non-finite and out-of-range numerics, wrong types.
+    fn wire_value() -> impl Strategy<Value = serde_json::Value> {
+        use serde_json::json;
+        // Mostly strings: one mistyped field drops the whole message
+        prop_oneof![
+            12 => prop::sample::select(vec![
+                "100000.5", "0", "-1", "NaN", "inf", "-inf", "1e300", "1e400", "", "BTCUSDT", "SELL", "BUY", "é漢",
+            ])
+            .prop_map(|s| json!(s)),
+            2 => any::<String>().prop_map(|s| json!(s)),
+            1 => any::<f64>().prop_map(|f| json!(f)),
+            1 => any::<i64>().prop_map(|i| json!(i)),
+            1 => Just(json!(null)),
+            1 => Just(json!(true)),
+            1 => Just(json!({"p": "1"})),
+        ]
+    }
+
+    /// One combined-stream message whose data carries every field any of
+    /// the three handled streams reads.
+    fn stream_msg(streams: Vec<&'static str>) -> impl Strategy<Value = String> {
+        let symbol = prop_oneof![
+            4 => prop::sample::select(vec!["BTCUSDT", "ETHUSDT", "SOLUSDT", "XRPUSDT", "DOGEUSDT"])
+                .prop_map(serde_json::Value::from),
+            1 => wire_value(),
+        ];
+        let maker = prop_oneof![4 => any::<bool>().prop_map(serde_json::Value::from), 1 => wire_value()];
+        (prop::sample::select(streams), symbol, maker, prop::collection::vec(wire_value(), 6)).prop_map(
+            |(stream, symbol, maker, v)| {
+                serde_json::json!({
+                    "stream": stream,
+                    "data": {
+                        "e": "event", "E": 1700000000000u64, "T": 1700000000000u64,
+                        "s": symbol, "p": v[0], "q": v[1], "i": v[2], "m": maker,
+                        "o": { "s": symbol, "S": v[3], "q": v[4], "p": v[5] },
+                    }
+                })
+                .to_string()
+            },
+        )
+    }
+
+    fn assert_sane(feed: &BinanceFeed, rt: &tokio::runtime::Runtime) {
+        for asset in Asset::ALL {
+            if let Some(p) = feed.get_price(asset) {
+                assert!(p.is_finite() && p > 0.0, "{asset:?} price {p}");
+            }
+            if let Some(m) = rt.block_on(feed.get_mark_price(asset)) {
+                assert!(m.mark.is_finite() && m.index.is_finite(), "{asset:?} {m:?}");
+            }
+            assert!(feed.get_net_liquidations(asset).is_finite(), "{asset:?} liquidations");
+        }
+    }
+
+    /// `text` cut at `cut`, backed off to a char boundary.
+    fn truncated(text: &str, cut: prop::sample::Index) -> &str {
+        let mut n = cut.index(text.len() + 1);
+        while !text.is_char_boundary(n) {
+            n -= 1;
+        }
+        &text[..n]
+    }
+
+    proptest! {
+        /// Garbage, truncated and mistyped stream messages never panic the
+        /// feed task or record a non-finite price.
+        #[test]
+        fn prop_stream_messages_never_panic(
+            msgs in prop::collection::vec(
+                stream_msg(vec!["btcusdt@aggTrade", "ethusdt@markPrice@1s", "solusdt@forceOrder"]),
+                1..8,
+            ),
+            junk in any::<String>(),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            let feed = BinanceFeed::new(crate::config::Config::default().binance);
+            let sinks = feed.sinks();
+            rt.block_on(async {
+                BinanceFeed::handle_message(&junk, &sinks).await;
+                for msg in &msgs {
+                    BinanceFeed::handle_message(msg, &sinks).await;
+                    BinanceFeed::handle_message(truncated(msg, cut), &sinks).await;
+                }
+            });
+            assert_sane(&feed, &rt);
+        }
+
+        /// Streams we don't handle record nothing, whatever they carry.
+        #[test]
+        fn prop_unhandled_streams_ignored(
+            msg in stream_msg(vec!["btcusdt@kline_1m", "btcusdt@depth", "btcusdt@bookTicker", ""]),
+        ) {
+            let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            let feed = BinanceFeed::new(crate::config::Config::default().binance);
+            let mut prints = feed.subscribe_prices();
+            rt.block_on(BinanceFeed::handle_message(&msg, &feed.sinks()));
+            for asset in Asset::ALL {
+                prop_assert!(feed.get_price(asset).is_none());
+                prop_assert!(rt.block_on(feed.get_mark_price(asset)).is_none());
+                prop_assert_eq!(feed.get_net_liquidations(asset), 0.0);
+            }
+            prop_assert!(prints.try_recv().is_err());
+        }
+    }
+}
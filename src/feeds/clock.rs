@@ -0,0 +1,199 @@
+use crate::execution::clob_client::ClobClient;
+use crate::models::market::Duration;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// How often the running process re-measures its offset from the CLOB.
+const RESYNC_SECS: u64 = 300;
+/// Offsets beyond this are worth a warning: the host clock needs fixing.
+const WARN_OFFSET_MS: i64 = 1000;
+
+/// Wall-clock time for market window math.
+///
+/// Time advances on the monotonic clock from a single anchor to the system
+/// clock, so an NTP step or a manual clock change mid-session can't move a
+/// window boundary under us. On top of that sits an offset measured against
+/// the CLOB's `/time`, which disciplines the anchor to the exchange's clock
+/// (windows open and close by its clock, not ours) and absorbs slow drift
+/// and leap seconds, which Unix time doesn't count but a monotonic clock
+/// does, at the next resync.
+pub struct MarketClock {
+    anchor_wall: DateTime<Utc>,
+    anchor_mono: Instant,
+    /// Server minus monotonic-anchored time, in ms
+    offset_ms: AtomicI64,
+}
+
+impl MarketClock {
+    pub fn new() -> Self {
+        Self::anchored(Utc::now(), Instant::now())
+    }
+
+    /// Clock reading `wall` at monotonic instant `mono`.
+    pub fn anchored(wall: DateTime<Utc>, mono: Instant) -> Self {
+        Self { anchor_wall: wall, anchor_mono: mono, offset_ms: AtomicI64::new(0) }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now_at(Instant::now())
+    }
+
+    /// The time at monotonic instant `mono`, server offset applied.
+    pub fn now_at(&self, mono: Instant) -> DateTime<Utc> {
+        self.local_at(mono) + chrono::Duration::milliseconds(self.offset_ms())
+    }
+
+    /// Anchor plus monotonic elapsed, without the server offset.
+    fn local_at(&self, mono: Instant) -> DateTime<Utc> {
+        let elapsed = mono.saturating_duration_since(self.anchor_mono);
+        self.anchor_wall + chrono::Duration::from_std(elapsed).unwrap_or_default()
+    }
+
+    pub fn offset_ms(&self) -> i64 {
+        self.offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Adopt a server reading: `server_secs` (whole seconds, as `/time`
+    /// returns) was read between monotonic instants `sent` and `received`.
+    /// The server stamped it somewhere in that round trip and truncated it,
+    /// so it only pins the offset to a band a second and a round trip wide.
+    /// An offset already inside the band is kept; one outside moves to its
+    /// nearest edge — so truncation noise never moves the clock, and a run
+    /// of readings narrows it onto the server's.
+    pub fn observe_server(&self, server_secs: u64, sent: Instant, received: Instant) -> i64 {
+        let server_ms = server_secs as i64 * 1000;
+        let lowest = server_ms - self.local_at(received).timestamp_millis();
+        let highest = server_ms + 1000 - self.local_at(sent).timestamp_millis();
+        let offset = self.offset_ms().clamp(lowest, highest);
+        self.offset_ms.store(offset, Ordering::Relaxed);
+        offset
+    }
+
+    /// Measure the offset from the CLOB's clock once.
+    pub async fn sync(&self, client: &ClobClient) -> Result<i64> {
+        let sent = Instant::now();
+        let server = client.get_server_time().await?;
+        let offset = self.observe_server(server, sent, Instant::now());
+        if offset.abs() > WARN_OFFSET_MS {
+            warn!("Clock is {:+.1}s off the CLOB; market windows follow the CLOB", offset as f64 / 1000.0);
+        } else {
+            debug!("Clock offset vs CLOB: {offset:+}ms");
+        }
+        Ok(offset)
+    }
+
+    /// Resync every few minutes until shutdown.
+    pub fn spawn_sync(&'static self, client: ClobClient, mut shutdown: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            let every = std::time::Duration::from_secs(RESYNC_SECS);
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + every, every);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = self.sync(&client).await {
+                            warn!("Clock sync against the CLOB failed: {e}");
+                        }
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+}
+
+impl Default for MarketClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The process-wide clock.
+pub fn global() -> &'static MarketClock {
+    static CLOCK: OnceLock<MarketClock> = OnceLock::new();
+    CLOCK.get_or_init(MarketClock::new)
+}
+
+/// Sync the process-wide clock with the CLOB now, then keep it synced.
+pub async fn start(client: ClobClient, shutdown: broadcast::Receiver<()>) {
+    if let Err(e) = global().sync(&client).await {
+        warn!("Clock sync against the CLOB failed, using the local clock: {e}");
+    }
+    global().spawn_sync(client, shutdown);
+}
+
+/// Now, by the process-wide clock.
+pub fn now() -> DateTime<Utc> {
+    global().now()
+}
+
+/// Unix start of the `duration` window containing `now`. A window includes
+/// its start instant and excludes its end.
+pub fn window_start(duration: Duration, now: DateTime<Utc>) -> u64 {
+    let interval_ms = duration.interval_seconds() as i64 * 1000;
+    (now.timestamp_millis().div_euclid(interval_ms) * interval_ms / 1000) as u64
+}
+
+/// Seconds (to the millisecond) left in the `duration` window containing `now`.
+pub fn window_remaining(duration: Duration, now: DateTime<Utc>) -> f64 {
+    let end_ms = (window_start(duration, now) + duration.interval_seconds()) as i64 * 1000;
+    (end_ms - now.timestamp_millis()) as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: i64, ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(secs * 1000 + ms).unwrap()
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        let start = 1_770_933_900; // a 5m boundary
+        let five = Duration::FiveMin;
+
+        assert_eq!(window_start(five, at(start, 0)), start as u64, "boundary opens the new window");
+        assert_eq!(window_remaining(five, at(start, 0)), 300.0);
+        assert_eq!(window_start(five, at(start, -1)), start as u64 - 300, "1ms before is the old one");
+        assert_eq!(window_remaining(five, at(start, -1)), 0.001);
+        // Sub-second precision: 2.4s left is not reported as 3
+        assert_eq!(window_remaining(five, at(start + 297, 600)), 2.4);
+        let start = 1_768_502_700; // a 15m boundary
+        assert_eq!(window_start(Duration::FifteenMin, at(start + 899, 999)), start as u64);
+        assert_eq!(window_remaining(Duration::FifteenMin, at(start + 899, 999)), 0.001);
+        assert_eq!(window_start(Duration::FifteenMin, at(start + 900, 0)), start as u64 + 900);
+    }
+
+    #[test]
+    fn test_clock_ignores_wall_steps_and_follows_server() {
+        let mono = Instant::now();
+        let clock = MarketClock::anchored(at(1_770_933_890, 0), mono);
+        let later = |ms: u64| mono + std::time::Duration::from_millis(ms);
+
+        // Monotonic: 9.5s on is 9.5s on, whatever the system clock did meanwhile
+        assert_eq!(clock.now_at(later(9_500)), at(1_770_933_899, 500));
+
+        // Local clock behind the CLOB: it said 900 over a 200ms round trip ending at 899.5 local
+        let offset = clock.observe_server(1_770_933_900, later(9_300), later(9_500));
+        assert_eq!(offset, 500, "at least 900.0 by 899.5 local");
+        assert_eq!(window_remaining(Duration::FiveMin, at(1_770_933_899, 500)), 0.5, "locally the old window's last 0.5s");
+        let t = clock.now_at(later(9_500));
+        assert_eq!(window_start(Duration::FiveMin, t), 1_770_933_900, "by the CLOB the next window is open");
+        assert_eq!(window_remaining(Duration::FiveMin, t), 300.0);
+
+        // A reading consistent with the offset leaves it alone: no truncation jitter
+        assert_eq!(clock.observe_server(1_770_933_910, later(19_490), later(19_510)), 500);
+        assert_eq!(clock.observe_server(1_770_933_911, later(20_490), later(20_510)), 500);
+
+        // A leap second counted by the monotonic clock but not by Unix time: resync pulls it back
+        let offset = clock.observe_server(1_770_933_999, later(109_999), later(110_001));
+        assert_eq!(offset, 1, "before 1000.0 by 999.999 local");
+        assert_eq!(clock.now_at(later(110_000)), at(1_770_934_000, 1));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::error::{Result, SattebaazError};
+use crate::feeds::polymarket::MarketInfo;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// Serve cached metadata without revalidating for this long.
+const FRESH_TTL: Duration = Duration::from_secs(60);
+/// Skip re-querying a slug Gamma doesn't know about yet for this long.
+const NEGATIVE_TTL: Duration = Duration::from_secs(10);
+
+/// Caching layer for Gamma API market metadata.
+///
+/// - Fresh entries are served from memory
+/// - Stale entries are revalidated with If-None-Match / If-Modified-Since
+/// - Slugs that don't exist yet are negatively cached for a short time
+/// - Concurrent lookups of the same slug share a single request
+pub struct GammaCache {
+    http: reqwest::Client,
+    gamma_host: String,
+    entries: DashMap<String, CachedResponse>,
+    /// Slug → time until which we treat it as not-yet-created
+    negative: DashMap<String, Instant>,
+    /// Per-slug lock so concurrent callers coalesce onto one request
+    inflight: DashMap<String, Arc<Mutex<()>>>,
+    stats: CacheStats,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct CacheStats {
+    hits: AtomicU64,
+    negative_hits: AtomicU64,
+    not_modified: AtomicU64,
+    fetches: AtomicU64,
+}
+
+impl GammaCache {
+    pub fn new(http: reqwest::Client, gamma_host: &str) -> Self {
+        Self {
+            http,
+            gamma_host: gamma_host.to_string(),
+            entries: DashMap::new(),
+            negative: DashMap::new(),
+            inflight: DashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Look up markets for a slug. Returns an empty vec if Gamma has no
+    /// market for it yet (or it is negatively cached).
+    pub async fn markets_by_slug(&self, slug: &str) -> Result<Vec<MarketInfo>> {
+        if let Some(body) = self.cached(slug) {
+            return Ok(parse(&body));
+        }
+
+        let lock = self
+            .inflight
+            .entry(slug.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone();
+        let _guard = lock.lock().await;
+
+        // Another caller may have completed the request while we waited
+        if let Some(body) = self.cached(slug) {
+            return Ok(parse(&body));
+        }
+
+        let result = self.fetch(slug).await;
+        // Only the last holder retires the lock, and does so before releasing
+        // it: a waiter still queued on it keeps the entry, so a caller arriving
+        // now joins that queue rather than starting a second request
+        self.inflight.remove_if(slug, |_, l| Arc::strong_count(l) == 2);
+        drop(_guard);
+        result.map(|body| parse(&body))
+    }
+
+    /// Serve from the fresh or negative cache, if possible.
+    fn cached(&self, slug: &str) -> Option<String> {
+        if let Some(until) = self.negative.get(slug) {
+            if Instant::now() < *until {
+                self.stats.negative_hits.fetch_add(1, Ordering::Relaxed);
+                return Some(String::new());
+            }
+        }
+        let entry = self.entries.get(slug)?;
+        if entry.fetched_at.elapsed() < FRESH_TTL {
+            self.stats.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(entry.body.clone());
+        }
+        None
+    }
+
+    /// Fetch (or revalidate) a slug from Gamma and update the cache.
+    async fn fetch(&self, slug: &str) -> Result<String> {
+        let url = format!("{}/markets?slug={}", self.gamma_host, slug);
+        let mut req = self.http.get(&url);
+
+        let previous = self.entries.get(slug).map(|e| e.clone());
+        if let Some(prev) = &previous {
+            if let Some(etag) = &prev.etag {
+                req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(lm) = &prev.last_modified {
+                req = req.header(reqwest::header::IF_MODIFIED_SINCE, lm);
+            }
+        }
+
+        self.stats.fetches.fetch_add(1, Ordering::Relaxed);
+        let resp = req.send().await?;
+
+        if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(prev) = previous {
+                self.stats.not_modified.fetch_add(1, Ordering::Relaxed);
+                self.entries.insert(
+                    slug.to_string(),
+                    CachedResponse {
+                        fetched_at: Instant::now(),
+                        ..prev.clone()
+                    },
+                );
+                return Ok(prev.body);
+            }
+        }
+
+        if !resp.status().is_success() {
+            // Outages and rate limits are errors; anything else reads as "no market yet"
+            let err = SattebaazError::from_status(resp.status(), format!("Gamma {slug}"));
+            if err.is_retryable() {
+                return Err(err);
+            }
+            debug!("Gamma returned {} for {slug}", resp.status());
+            return Ok(String::new());
+        }
+
+        let header = |name: reqwest::header::HeaderName| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let body = resp.text().await?;
+
+        if parse(&body).is_empty() {
+            // Not created yet — don't ask again for a while
+            self.negative
+                .insert(slug.to_string(), Instant::now() + NEGATIVE_TTL);
+            self.entries.remove(slug);
+        } else {
+            self.negative.remove(slug);
+            self.entries.insert(
+                slug.to_string(),
+                CachedResponse {
+                    body: body.clone(),
+                    etag,
+                    last_modified,
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+
+        Ok(body)
+    }
+
+    /// Drop cached entries for slugs whose markets have expired.
+    pub fn evict(&self, slug: &str) {
+        self.entries.remove(slug);
+        self.negative.remove(slug);
+    }
+
+    /// Number of requests actually sent to Gamma.
+    pub fn fetch_count(&self) -> u64 {
+        self.stats.fetches.load(Ordering::Relaxed)
+    }
+
+    pub fn log_summary(&self) {
+        info!(
+            "Gamma cache: {} hits, {} negative hits, {} not-modified, {} fetches ({} entries)",
+            self.stats.hits.load(Ordering::Relaxed),
+            self.stats.negative_hits.load(Ordering::Relaxed),
+            self.stats.not_modified.load(Ordering::Relaxed),
+            self.stats.fetches.load(Ordering::Relaxed),
+            self.entries.len(),
+        );
+    }
+}
+
+fn parse(body: &str) -> Vec<MarketInfo> {
+    if body.is_empty() {
+        return Vec::new();
+    }
+    serde_json::from_str(body).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const MARKET_JSON: &str = r#"[{"slug":"btc-updown-5m-1","conditionId":"0xabc","clobTokenIds":"[\"1\",\"2\"]","outcomes":"[\"Up\",\"Down\"]"}]"#;
+
+    /// Minimal HTTP server: serves `body` for every request (empty body = `[]`),
+    /// answering 304 when the request carries our ETag. Counts requests.
+    async fn serve(body: &'static str, delay_ms: u64) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut sock, _)) = listener.accept().await else { break };
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = sock.read(&mut buf).await.unwrap_or(0);
+                    let req = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+
+                    let resp = if req.contains("if-none-match: \"v1\"") {
+                        "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nContent-Length: 0\r\n\r\n".to_string()
+                    } else {
+                        let body = if body.is_empty() { "[]" } else { body };
+                        format!(
+                            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    };
+                    let _ = sock.write_all(resp.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), count)
+    }
+
+    #[tokio::test]
+    async fn test_fresh_hit_and_conditional_revalidation() {
+        let (host, count) = serve(MARKET_JSON, 0).await;
+        let cache = GammaCache::new(reqwest::Client::new(), &host);
+
+        let first = cache.markets_by_slug("btc-updown-5m-1").await.unwrap();
+        assert_eq!(first.len(), 1);
+        let second = cache.markets_by_slug("btc-updown-5m-1").await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 1, "fresh entry served from memory");
+
+        // Age the entry so the next lookup revalidates
+        cache.entries.get_mut("btc-updown-5m-1").unwrap().fetched_at =
+            Instant::now() - FRESH_TTL;
+        let third = cache.markets_by_slug("btc-updown-5m-1").await.unwrap();
+        assert_eq!(third.len(), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.stats.not_modified.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache() {
+        let (host, count) = serve("", 0).await;
+        let cache = GammaCache::new(reqwest::Client::new(), &host);
+
+        for _ in 0..3 {
+            assert!(cache.markets_by_slug("btc-updown-5m-2").await.unwrap().is_empty());
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats.negative_hits.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_coalesce() {
+        let (host, count) = serve(MARKET_JSON, 100).await;
+        let cache = Arc::new(GammaCache::new(reqwest::Client::new(), &host));
+
+        let handles: Vec<_> = (0..5)
+            .map(|_| {
+                let cache = cache.clone();
+                tokio::spawn(async move { cache.markets_by_slug("btc-updown-5m-3").await })
+            })
+            .collect();
+        for h in handles {
+            assert_eq!(h.await.unwrap().unwrap().len(), 1);
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::feeds::clock;
+use crate::models::market::{Asset, Duration, LifecyclePhase, Market};
+use crate::telemetry::events;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// How often tracked markets are checked for phase changes.
+const TICK_MS: u64 = 250;
+
+/// Milestones in a market window, in the order they happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketEventKind {
+    /// Window started (or, joining late, first seen after it started)
+    Opened,
+    PrimeZone,
+    Lockout,
+    /// Past close time; the market resolves on the price now
+    Closed,
+}
+
+impl MarketEventKind {
+    /// The milestone a window in `phase` has most recently passed.
+    fn reached(phase: LifecyclePhase) -> Self {
+        match phase {
+            LifecyclePhase::AlphaWindow | LifecyclePhase::EarlyArbs => Self::Opened,
+            LifecyclePhase::PrimeZone | LifecyclePhase::MaturePhase | LifecyclePhase::PreResolution => {
+                Self::PrimeZone
+            }
+            LifecyclePhase::Lockout => Self::Lockout,
+            LifecyclePhase::Resolved => Self::Closed,
+        }
+    }
+
+    const ALL: [Self; 4] = [Self::Opened, Self::PrimeZone, Self::Lockout, Self::Closed];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarketEvent {
+    pub kind: MarketEventKind,
+    pub slug: String,
+    pub asset: Asset,
+    pub duration: Duration,
+    pub at: DateTime<Utc>,
+    /// Seconds since the window opened when the event fired; well past the
+    /// milestone means we joined (or noticed) late
+    pub elapsed_secs: f64,
+}
+
+/// Market open/close event bus.
+///
+/// Watches the markets the Polymarket feed has discovered and broadcasts one
+/// event per milestone per market, so the components that care about window
+/// boundaries (reference-price capture, the orchestrator, resolution) react
+/// to them instead of each polling the clock. Every milestone fires exactly
+/// once and in order: a market first seen mid-window gets the ones it
+/// missed back to back.
+pub struct MarketEvents {
+    tx: broadcast::Sender<MarketEvent>,
+    /// Last milestone fired per slug
+    reached: Mutex<HashMap<String, MarketEventKind>>,
+}
+
+impl MarketEvents {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(256);
+        Self { tx, reached: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<MarketEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Fire the milestones `market` has passed by `now` since it was last
+    /// observed. Returns them, in order, as broadcast.
+    pub fn observe(&self, market: &Market, now: DateTime<Utc>) -> Vec<MarketEvent> {
+        if now < market.open_time {
+            return Vec::new();
+        }
+        let target = MarketEventKind::reached(market.phase_at(now));
+        let mut reached = self.reached.lock().unwrap();
+        let last = reached.get(&market.slug).copied();
+        if last.is_some_and(|l| l >= target) {
+            return Vec::new();
+        }
+        reached.insert(market.slug.clone(), target);
+        drop(reached);
+
+        let elapsed_secs = (now - market.open_time).num_milliseconds() as f64 / 1000.0;
+        MarketEventKind::ALL
+            .into_iter()
+            .filter(|&k| last.is_none_or(|l| k > l) && k <= target)
+            .map(|kind| {
+                let event = MarketEvent {
+                    kind,
+                    slug: market.slug.clone(),
+                    asset: market.asset,
+                    duration: market.duration,
+                    at: now,
+                    elapsed_secs,
+                };
+                events::MarketLifecycle { market: &event.slug, kind, elapsed_secs }.emit();
+                let _ = self.tx.send(event.clone());
+                event
+            })
+            .collect()
+    }
+
+    /// Observe every tracked market; forget markets no longer tracked.
+    pub fn scan(&self, markets: &DashMap<String, Market>, now: DateTime<Utc>) -> Vec<MarketEvent> {
+        self.reached.lock().unwrap().retain(|slug, _| markets.contains_key(slug));
+        let mut fired: Vec<MarketEvent> = Vec::new();
+        for market in markets.iter() {
+            fired.extend(self.observe(market.value(), now));
+        }
+        fired
+    }
+
+    /// Scan `markets` every tick until shutdown.
+    pub fn spawn(self: &Arc<Self>, markets: Arc<DashMap<String, Market>>, mut shutdown: broadcast::Receiver<()>) {
+        let bus = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(TICK_MS));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        bus.scan(&markets, clock::now());
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+}
+
+impl Default for MarketEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_milestones_fire_once_in_order() {
+        let mut market = Market::new("btc-updown-5m-1".into(), Asset::BTC, Duration::FiveMin, "y".into(), "n".into());
+        let open = DateTime::from_timestamp(1_770_933_900, 0).unwrap();
+        market.open_time = open;
+        market.close_time = open + chrono::Duration::seconds(300);
+        let bus = MarketEvents::new();
+        let mut rx = bus.subscribe();
+        let at = |secs: i64| open + chrono::Duration::seconds(secs);
+        let kinds = |events: Vec<MarketEvent>| events.into_iter().map(|e| e.kind).collect::<Vec<_>>();
+
+        assert!(bus.observe(&market, at(-5)).is_empty(), "not open yet");
+        assert_eq!(kinds(bus.observe(&market, at(0))), [MarketEventKind::Opened]);
+        assert!(bus.observe(&market, at(10)).is_empty());
+        assert_eq!(kinds(bus.observe(&market, at(30))), [MarketEventKind::PrimeZone]);
+        assert!(bus.observe(&market, at(250)).is_empty(), "mature and pre-resolution aren't milestones");
+        assert_eq!(kinds(bus.observe(&market, at(270))), [MarketEventKind::Lockout]);
+        assert_eq!(kinds(bus.observe(&market, at(300))), [MarketEventKind::Closed]);
+        assert!(bus.observe(&market, at(400)).is_empty());
+        assert_eq!(rx.try_recv().unwrap().kind, MarketEventKind::Opened);
+
+        // Joined late: the missed milestones fire back to back
+        let late = MarketEvents::new();
+        let events = late.observe(&market, at(280));
+        assert_eq!(kinds(events.clone()), [MarketEventKind::Opened, MarketEventKind::PrimeZone, MarketEventKind::Lockout]);
+        assert_eq!(events[0].elapsed_secs, 280.0);
+
+        // Untracked markets are forgotten
+        let markets = DashMap::new();
+        late.scan(&markets, at(300));
+        markets.insert(market.slug.clone(), market);
+        assert_eq!(late.scan(&markets, at(300)).len(), 4, "seen afresh");
+    }
+}
@@ -0,0 +1,344 @@
+use crate::config::LiquidationFeedConfig;
+use crate::feeds::binance::BinanceFeed;
+use crate::models::market::Asset;
+use dashmap::DashMap;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_tungstenite::connect_async;
+use tracing::{debug, error, info, warn};
+
+/// Exchange a forced liquidation was reported on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiquidationVenue {
+    Binance,
+    Bybit,
+    Okx,
+}
+
+impl LiquidationVenue {
+    pub const ALL: [LiquidationVenue; 3] = [Self::Binance, Self::Bybit, Self::Okx];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Binance => "binance",
+            Self::Bybit => "bybit",
+            Self::Okx => "okx",
+        }
+    }
+}
+
+/// Net forced liquidations per asset across venues, in USD (positive = longs
+/// liquidated). Binance alone sees a fraction of a cascade; summing the big
+/// perp venues makes the signal trip earlier and more reliably.
+///
+/// Counters decay rather than reset, so recent liquidations keep some weight.
+#[derive(Default)]
+pub struct LiquidationAggregator {
+    net: DashMap<(Asset, LiquidationVenue), f64>,
+}
+
+impl LiquidationAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one liquidation; `long_liquidated` means a long was force-closed.
+    pub fn record(&self, asset: Asset, venue: LiquidationVenue, notional: f64, long_liquidated: bool) {
+        let signed = if long_liquidated { notional } else { -notional };
+        *self.net.entry((asset, venue)).or_insert(0.0) += signed;
+        debug!(
+            "Liquidation: {asset:?} {} {} ${notional:.0} (net={:.0})",
+            venue.name(),
+            if long_liquidated { "long" } else { "short" },
+            self.net(asset)
+        );
+    }
+
+    /// Net liquidations for an asset, all venues combined.
+    pub fn net(&self, asset: Asset) -> f64 {
+        LiquidationVenue::ALL.iter().map(|v| self.venue_net(asset, *v)).sum()
+    }
+
+    pub fn venue_net(&self, asset: Asset, venue: LiquidationVenue) -> f64 {
+        self.net.get(&(asset, venue)).map(|n| *n).unwrap_or(0.0)
+    }
+
+    /// Scale every counter by `factor` (e.g. 0.5 once a minute).
+    pub fn decay(&self, factor: f64) {
+        for mut n in self.net.iter_mut() {
+            *n *= factor;
+        }
+    }
+}
+
+/// Bybit and OKX forced-liquidation WebSockets, feeding the same aggregator
+/// as Binance's `@forceOrder` stream.
+///
+/// Bybit: `allLiquidation.<SYMBOL>` on the v5 linear public socket.
+/// OKX: `liquidation-orders` for SWAP instruments on the v5 public socket.
+pub struct LiquidationFeed {
+    config: LiquidationFeedConfig,
+    aggregator: Arc<LiquidationAggregator>,
+}
+
+/// OKX sizes are in contracts; USDT swap contract value in the base asset.
+fn okx_contract_value(asset: Asset) -> f64 {
+    match asset {
+        Asset::BTC => 0.01,
+        Asset::ETH => 0.1,
+        Asset::SOL => 1.0,
+        Asset::XRP => 100.0,
+    }
+}
+
+impl LiquidationFeed {
+    pub fn new(config: LiquidationFeedConfig, aggregator: Arc<LiquidationAggregator>) -> Self {
+        Self { config, aggregator }
+    }
+
+    /// Start each enabled venue's socket with reconnection logic.
+    pub fn start(&self, shutdown_tx: &broadcast::Sender<()>) {
+        if self.config.bybit_enabled {
+            let args: Vec<String> = Asset::ALL
+                .iter()
+                .map(|a| format!("allLiquidation.{}USDT", a.slug_prefix().to_uppercase()))
+                .collect();
+            let subscribe = serde_json::json!({ "op": "subscribe", "args": args });
+            Self::spawn_socket(
+                LiquidationVenue::Bybit,
+                self.config.bybit_ws_url.clone(),
+                subscribe.to_string(),
+                r#"{"op":"ping"}"#,
+                self.aggregator.clone(),
+                shutdown_tx.subscribe(),
+            );
+        }
+        if self.config.okx_enabled {
+            let subscribe = serde_json::json!({
+                "op": "subscribe",
+                "args": [{ "channel": "liquidation-orders", "instType": "SWAP" }],
+            });
+            Self::spawn_socket(
+                LiquidationVenue::Okx,
+                self.config.okx_ws_url.clone(),
+                subscribe.to_string(),
+                "ping",
+                self.aggregator.clone(),
+                shutdown_tx.subscribe(),
+            );
+        }
+    }
+
+    fn spawn_socket(
+        venue: LiquidationVenue,
+        ws_url: String,
+        subscribe: String,
+        ping_text: &'static str,
+        aggregator: Arc<LiquidationAggregator>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let name = venue.name();
+        tokio::spawn(async move {
+            let mut backoff_ms = 1000u64;
+
+            loop {
+                info!("Connecting to {name} liquidation WS: {ws_url}");
+
+                match connect_async(&ws_url).await {
+                    Ok((ws_stream, _)) => {
+                        info!("{name} liquidation WS connected");
+                        backoff_ms = 1000;
+
+                        let (mut write, mut read) = ws_stream.split();
+                        if let Err(e) = write
+                            .send(tokio_tungstenite::tungstenite::Message::Text(subscribe.clone()))
+                            .await
+                        {
+                            error!("Failed to subscribe {name} liquidation WS: {e}");
+                            continue;
+                        }
+
+                        // Both venues drop sockets that go quiet for ~30s
+                        let mut ping = tokio::time::interval(tokio::time::Duration::from_secs(20));
+
+                        loop {
+                            tokio::select! {
+                                msg = read.next() => {
+                                    match msg {
+                                        Some(Ok(tokio_tungstenite::tungstenite::Message::Text(text))) => {
+                                            Self::handle_message(venue, &text, &aggregator);
+                                        }
+                                        Some(Ok(tokio_tungstenite::tungstenite::Message::Ping(data))) => {
+                                            let _ = write.send(
+                                                tokio_tungstenite::tungstenite::Message::Pong(data)
+                                            ).await;
+                                        }
+                                        Some(Ok(_)) => {}
+                                        Some(Err(e)) => {
+                                            warn!("{name} liquidation WS error: {e}");
+                                            break;
+                                        }
+                                        None => {
+                                            warn!("{name} liquidation WS stream ended");
+                                            break;
+                                        }
+                                    }
+                                }
+                                _ = ping.tick() => {
+                                    let _ = write.send(
+                                        tokio_tungstenite::tungstenite::Message::Text(ping_text.into())
+                                    ).await;
+                                }
+                                _ = shutdown_rx.recv() => {
+                                    info!("{name} liquidation WS shutting down");
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("{name} liquidation WS connect failed: {e}");
+                    }
+                }
+
+                crate::telemetry::events::Reconnect { feed: name, backoff_ms }.emit();
+                tokio::select! {
+                    _ = tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)) => {}
+                    _ = shutdown_rx.recv() => return,
+                }
+                backoff_ms = (backoff_ms * 2).min(30_000);
+            }
+        });
+    }
+
+    fn handle_message(venue: LiquidationVenue, text: &str, aggregator: &LiquidationAggregator) {
+        match venue {
+            LiquidationVenue::Bybit => Self::on_bybit(text, aggregator),
+            LiquidationVenue::Okx => Self::on_okx(text, aggregator),
+            LiquidationVenue::Binance => {}
+        }
+    }
+
+    fn on_bybit(text: &str, aggregator: &LiquidationAggregator) {
+        let Ok(msg) = serde_json::from_str::<BybitMessage>(text) else {
+            return; // pong, subscribe acks
+        };
+        if !msg.topic.is_some_and(|t| t.starts_with("allLiquidation.")) {
+            return;
+        }
+        for liq in msg.data {
+            let Some(asset) = BinanceFeed::symbol_to_asset(&liq.symbol) else {
+                continue;
+            };
+            let (Ok(qty), Ok(price)) = (liq.size.parse::<f64>(), liq.price.parse::<f64>()) else {
+                continue;
+            };
+            // Bybit reports the liquidated position's side: Buy = a long
+            aggregator.record(asset, LiquidationVenue::Bybit, qty * price, liq.side == "Buy");
+        }
+    }
+
+    fn on_okx(text: &str, aggregator: &LiquidationAggregator) {
+        let Ok(msg) = serde_json::from_str::<OkxMessage>(text) else {
+            return;
+        };
+        for inst in msg.data {
+            // "BTC-USDT-SWAP" — USDT-margined perps only
+            let mut parts = inst.inst_id.split('-');
+            let (Some(base), Some("USDT"), Some("SWAP")) = (parts.next(), parts.next(), parts.next()) else {
+                continue;
+            };
+            let Some(asset) = Asset::ALL.into_iter().find(|a| a.slug_prefix() == base.to_lowercase()) else {
+                continue;
+            };
+            for d in inst.details {
+                let (Ok(contracts), Ok(price)) = (d.size.parse::<f64>(), d.bankruptcy_price.parse::<f64>()) else {
+                    continue;
+                };
+                let notional = contracts * okx_contract_value(asset) * price;
+                let long_liquidated = match d.pos_side.as_str() {
+                    "long" => true,
+                    "short" => false,
+                    // Net mode: closing a long is a sell
+                    _ => d.side == "sell",
+                };
+                aggregator.record(asset, LiquidationVenue::Okx, notional, long_liquidated);
+            }
+        }
+    }
+}
+
+// --- Venue message types ---
+
+#[derive(Debug, Deserialize)]
+struct BybitMessage {
+    topic: Option<String>,
+    #[serde(default)]
+    data: Vec<BybitLiquidation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitLiquidation {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "S")]
+    side: String,
+    #[serde(rename = "v")]
+    size: String,
+    #[serde(rename = "p")]
+    price: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxMessage {
+    data: Vec<OkxInstrumentLiquidations>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxInstrumentLiquidations {
+    #[serde(rename = "instId")]
+    inst_id: String,
+    details: Vec<OkxLiquidation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxLiquidation {
+    #[serde(rename = "bkPx")]
+    bankruptcy_price: String,
+    #[serde(rename = "posSide")]
+    pos_side: String,
+    side: String,
+    #[serde(rename = "sz")]
+    size: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_venues_merge_into_one_net_per_asset() {
+        let agg = LiquidationAggregator::new();
+        agg.record(Asset::BTC, LiquidationVenue::Binance, 40_000.0, true);
+
+        let bybit = r#"{"topic":"allLiquidation.BTCUSDT","type":"snapshot","ts":1739502303204,"data":[{"T":1739502302929,"s":"BTCUSDT","S":"Buy","v":"0.5","p":"100000.00"},{"T":1739502302930,"s":"BTCUSDT","S":"Sell","v":"0.1","p":"100000.00"}]}"#;
+        LiquidationFeed::handle_message(LiquidationVenue::Bybit, bybit, &agg);
+        assert_eq!(agg.venue_net(Asset::BTC, LiquidationVenue::Bybit), 40_000.0);
+
+        // 300 contracts × 0.01 BTC × $100k, a long closed in net mode
+        let okx = r#"{"arg":{"channel":"liquidation-orders","instType":"SWAP"},"data":[{"details":[{"bkLoss":"0","bkPx":"100000","ccy":"","posSide":"net","side":"sell","sz":"30","ts":"1723892524781"}],"instFamily":"BTC-USDT","instId":"BTC-USDT-SWAP","instType":"SWAP","uly":"BTC-USDT"},{"details":[{"bkLoss":"0","bkPx":"1","ccy":"","posSide":"short","side":"buy","sz":"5","ts":"1723892524781"}],"instFamily":"BTC-USD","instId":"BTC-USD-SWAP","instType":"SWAP","uly":"BTC-USD"}]}"#;
+        LiquidationFeed::handle_message(LiquidationVenue::Okx, okx, &agg);
+        assert_eq!(agg.venue_net(Asset::BTC, LiquidationVenue::Okx), 30_000.0);
+
+        assert_eq!(agg.net(Asset::BTC), 110_000.0);
+        assert_eq!(agg.net(Asset::ETH), 0.0);
+
+        // Acks and pongs are ignored; counters decay together
+        LiquidationFeed::handle_message(LiquidationVenue::Bybit, r#"{"success":true,"op":"pong"}"#, &agg);
+        LiquidationFeed::handle_message(LiquidationVenue::Okx, "pong", &agg);
+        agg.decay(0.5);
+        assert_eq!(agg.net(Asset::BTC), 55_000.0);
+    }
+}
@@ -1,5 +1,5 @@
+use crate::feeds::clock::{self, window_remaining, window_start};
 use crate::models::market::{Asset, Duration, Market};
-use chrono::Utc;
 
 /// Generates market slugs and discovers active markets.
 ///
@@ -7,22 +7,26 @@ use chrono::Utc;
 ///   {asset}-updown-{duration}-{unix_timestamp}
 ///
 /// Where unix_timestamp is the interval start time, aligned to clean boundaries.
+/// Window math runs on the process clock (`feeds::clock`), which follows
+/// the CLOB's time rather than this host's.
 pub struct MarketDiscovery;
 
 impl MarketDiscovery {
     /// Generate the slug for the currently active market.
     pub fn current_slug(asset: Asset, duration: Duration) -> String {
-        let now = Utc::now().timestamp() as u64;
-        let interval = duration.interval_seconds();
-        let interval_start = (now / interval) * interval;
-        Market::generate_slug(asset, duration, interval_start)
+        Market::generate_slug(asset, duration, window_start(duration, clock::now()))
+    }
+
+    /// Generate the slug for the window after the current one.
+    pub fn next_slug(asset: Asset, duration: Duration) -> String {
+        let start = window_start(duration, clock::now()) + duration.interval_seconds();
+        Market::generate_slug(asset, duration, start)
     }
 
     /// Generate slugs for the next N upcoming markets.
     pub fn upcoming_slugs(asset: Asset, duration: Duration, count: usize) -> Vec<String> {
-        let now = Utc::now().timestamp() as u64;
         let interval = duration.interval_seconds();
-        let current_start = (now / interval) * interval;
+        let current_start = window_start(duration, clock::now());
 
         (0..count)
             .map(|i| {
@@ -40,9 +44,8 @@ impl MarketDiscovery {
         past_count: usize,
         future_count: usize,
     ) -> Vec<(String, u64)> {
-        let now = Utc::now().timestamp() as u64;
         let interval = duration.interval_seconds();
-        let current_start = (now / interval) * interval;
+        let current_start = window_start(duration, clock::now());
 
         let mut slugs = Vec::new();
 
@@ -64,13 +67,9 @@ impl MarketDiscovery {
         slugs
     }
 
-    /// Calculate time remaining in the current interval.
+    /// Calculate time remaining in the current interval, to the millisecond.
     pub fn time_remaining_in_current(duration: Duration) -> f64 {
-        let now = Utc::now().timestamp() as u64;
-        let interval = duration.interval_seconds();
-        let current_start = (now / interval) * interval;
-        let current_end = current_start + interval;
-        (current_end - now) as f64
+        window_remaining(duration, clock::now())
     }
 
     /// Calculate seconds until the next interval starts.
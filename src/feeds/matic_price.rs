@@ -0,0 +1,126 @@
+use crate::feeds::binance::{PriceSlot, PriceState};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, warn};
+
+/// How often the ticker is re-read; gas is cents, so minutes-old is fine.
+const REFRESH_SECS: u64 = 300;
+
+/// MATIC (now POL) in USD, for pricing the gas our on-chain transactions
+/// burn and sizing gas top-ups.
+///
+/// Polls a Binance-style ticker (`{"symbol": "POLUSDT", "price": "0.2315"}`)
+/// every few minutes into a `PriceSlot`, so it reads and watches like the
+/// traded assets' prices. Until the first read succeeds — or for good, when
+/// no URL is configured — the configured fallback price is used.
+pub struct MaticPrice {
+    url: String,
+    http: reqwest::Client,
+    slot: PriceSlot,
+    fallback_usd: f64,
+}
+
+impl MaticPrice {
+    pub fn new(url: &str, fallback_usd: f64) -> Self {
+        Self { url: url.to_string(), http: reqwest::Client::new(), slot: PriceSlot::default(), fallback_usd }
+    }
+
+    /// Latest price in USD, or the fallback before any read.
+    pub fn get(&self) -> f64 {
+        self.slot.get().map_or(self.fallback_usd, |s| s.price)
+    }
+
+    /// Latest ticker read; None until one succeeds.
+    pub fn state(&self) -> Option<PriceState> {
+        self.slot.get()
+    }
+
+    /// Watch the price; only ticker reads are sent, never the fallback.
+    pub fn watch(&self) -> watch::Receiver<Option<PriceState>> {
+        self.slot.watch()
+    }
+
+    /// Record a price read at `now`.
+    pub fn set(&self, usd: f64, now: DateTime<Utc>) {
+        self.slot.record(usd, now);
+    }
+
+    /// USD value of `matic` at the latest price, e.g. what a gas top-up costs.
+    pub fn usd_for_matic(&self, matic: f64) -> f64 {
+        matic * self.get()
+    }
+
+    /// Read the ticker once.
+    pub async fn refresh(&self) -> Result<f64> {
+        let body: serde_json::Value = self.http.get(&self.url).send().await?.error_for_status()?.json().await?;
+        let usd = parse_ticker(&body).with_context(|| format!("no price in ticker response: {body}"))?;
+        self.set(usd, Utc::now());
+        Ok(usd)
+    }
+
+    /// Refresh every few minutes until shutdown; no-op without a URL.
+    pub fn spawn(self: &Arc<Self>, mut shutdown: broadcast::Receiver<()>) {
+        if self.url.is_empty() {
+            return;
+        }
+        let feed = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(REFRESH_SECS));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => match feed.refresh().await {
+                        Ok(usd) => debug!("MATIC/USD {usd}"),
+                        Err(e) => warn!("MATIC price refresh failed, keeping ${}: {e}", feed.get()),
+                    },
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+}
+
+/// Price from a ticker body; Binance quotes it as a string.
+fn parse_ticker(body: &serde_json::Value) -> Option<f64> {
+    let price = &body["price"];
+    price
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .or_else(|| price.as_f64())
+        .filter(|p: &f64| p.is_finite() && *p > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::polygon_merger::GasSpend;
+
+    #[test]
+    fn test_ticker_parse_slot_and_gas_in_usd() {
+        assert_eq!(parse_ticker(&serde_json::json!({ "symbol": "POLUSDT", "price": "0.2500" })), Some(0.25));
+        assert_eq!(parse_ticker(&serde_json::json!({ "price": 0.5 })), Some(0.5));
+        assert_eq!(parse_ticker(&serde_json::json!({ "price": "0" })), None);
+        assert_eq!(parse_ticker(&serde_json::json!({ "code": -1121, "msg": "Invalid symbol." })), None);
+
+        let price = MaticPrice::new("", 0.25);
+        let watch = price.watch();
+        assert_eq!((price.get(), price.state().map(|s| s.price)), (0.25, None), "fallback until the ticker is read");
+        assert_eq!(price.usd_for_matic(4.0), 1.0);
+        let now = Utc::now();
+        price.set(0.5, now);
+        assert_eq!(price.get(), 0.5);
+        assert_eq!(watch.borrow().map(|s| (s.price, s.timestamp)), Some((0.5, now)));
+        assert_eq!(price.usd_for_matic(4.0), 2.0);
+        // 200k gas at 50 gwei = 0.01 MATIC = $0.005
+        let gas = GasSpend {
+            label: "Merge".into(),
+            tx: "0xabc".into(),
+            gas_used: 200_000,
+            gas_price_wei: 50_000_000_000,
+            matic_usd: price.get(),
+        };
+        assert!((gas.matic() - 0.01).abs() < 1e-12);
+        assert!((gas.usd() - 0.005).abs() < 1e-12);
+    }
+}
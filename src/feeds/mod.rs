@@ -2,3 +2,11 @@ pub mod binance;
 pub mod polymarket;
 pub mod market_discovery;
 pub mod user_ws;
+pub mod gamma_cache;
+pub mod readiness;
+pub mod oracle;
+pub mod liquidations;
+pub mod clock;
+pub mod lifecycle;
+pub mod token_balances;
+pub mod matic_price;
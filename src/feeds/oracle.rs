@@ -0,0 +1,215 @@
+use crate::models::market::Asset;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_tungstenite::connect_async;
+use tracing::{debug, error, info, warn};
+
+/// Resolution oracle price feed.
+///
+/// Up/down markets resolve on Chainlink prices, not Binance. Polymarket's
+/// real-time data socket relays those Chainlink updates; we keep the latest
+/// per asset so near-close entries can be checked against the price that
+/// will actually settle the market.
+///
+/// WS endpoint: wss://ws-live-data.polymarket.com (topic crypto_prices_chainlink)
+pub struct OracleFeed {
+    ws_url: String,
+    prices: Arc<DashMap<Asset, OraclePrice>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OraclePrice {
+    pub price: f64,
+    /// Oracle report time
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OraclePrice {
+    pub fn age_secs(&self) -> f64 {
+        (Utc::now() - self.timestamp).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RtdsMessage {
+    topic: Option<String>,
+    payload: Option<RtdsPayload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RtdsPayload {
+    symbol: Option<String>,
+    value: Option<f64>,
+    /// Unix millis
+    timestamp: Option<i64>,
+}
+
+impl OracleFeed {
+    pub fn new(ws_url: &str) -> Self {
+        Self {
+            ws_url: ws_url.to_string(),
+            prices: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Latest oracle price for an asset.
+    pub fn get_price(&self, asset: Asset) -> Option<OraclePrice> {
+        self.prices.get(&asset).map(|p| *p)
+    }
+
+    /// Record an oracle price (used by the socket handler and in tests).
+    pub fn set_price(&self, asset: Asset, price: f64, timestamp: DateTime<Utc>) {
+        self.prices.insert(asset, OraclePrice { price, timestamp });
+    }
+
+    /// Start the oracle WebSocket with reconnection logic.
+    pub fn start(&self, shutdown_tx: &broadcast::Sender<()>) {
+        let ws_url = self.ws_url.clone();
+        let prices = self.prices.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            let mut backoff_ms = 1000u64;
+
+            loop {
+                info!("Connecting to oracle WS: {ws_url}");
+
+                match connect_async(&ws_url).await {
+                    Ok((ws_stream, _)) => {
+                        info!("Oracle WS connected");
+                        backoff_ms = 1000;
+
+                        let (mut write, mut read) = ws_stream.split();
+                        let subscribe_msg = serde_json::json!({
+                            "action": "subscribe",
+                            "subscriptions": [{
+                                "topic": "crypto_prices_chainlink",
+                                "type": "*",
+                                "filters": "",
+                            }],
+                        });
+                        if let Err(e) = write
+                            .send(tokio_tungstenite::tungstenite::Message::Text(
+                                subscribe_msg.to_string(),
+                            ))
+                            .await
+                        {
+                            error!("Failed to subscribe oracle WS: {e}");
+                            continue;
+                        }
+
+                        // The socket drops idle clients — keep it alive
+                        let mut ping = tokio::time::interval(tokio::time::Duration::from_secs(5));
+
+                        loop {
+                            tokio::select! {
+                                msg = read.next() => {
+                                    match msg {
+                                        Some(Ok(tokio_tungstenite::tungstenite::Message::Text(text))) => {
+                                            Self::handle_message(&text, &prices);
+                                        }
+                                        Some(Ok(tokio_tungstenite::tungstenite::Message::Ping(data))) => {
+                                            let _ = write.send(
+                                                tokio_tungstenite::tungstenite::Message::Pong(data)
+                                            ).await;
+                                        }
+                                        Some(Ok(_)) => {}
+                                        Some(Err(e)) => {
+                                            warn!("Oracle WS error: {e}");
+                                            break;
+                                        }
+                                        None => {
+                                            warn!("Oracle WS stream ended");
+                                            break;
+                                        }
+                                    }
+                                }
+                                _ = ping.tick() => {
+                                    let _ = write.send(
+                                        tokio_tungstenite::tungstenite::Message::Text("PING".into())
+                                    ).await;
+                                }
+                                _ = shutdown_rx.recv() => {
+                                    info!("Oracle WS shutting down");
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("Oracle WS connect failed: {e}");
+                    }
+                }
+
+                crate::telemetry::events::Reconnect { feed: "oracle", backoff_ms }.emit();
+                tokio::select! {
+                    _ = tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)) => {}
+                    _ = shutdown_rx.recv() => return,
+                }
+                backoff_ms = (backoff_ms * 2).min(30_000);
+            }
+        });
+    }
+
+    fn handle_message(text: &str, prices: &DashMap<Asset, OraclePrice>) {
+        let Ok(msg) = serde_json::from_str::<RtdsMessage>(text) else {
+            return; // PONG, acks
+        };
+        if msg.topic.as_deref() != Some("crypto_prices_chainlink") {
+            return;
+        }
+        let Some(payload) = msg.payload else { return };
+        let (Some(symbol), Some(value)) = (payload.symbol, payload.value) else {
+            return;
+        };
+        let Some(asset) = Self::symbol_to_asset(&symbol) else {
+            debug!("Oracle: ignoring {symbol}");
+            return;
+        };
+        let timestamp = payload
+            .timestamp
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_else(Utc::now);
+        prices.insert(asset, OraclePrice { price: value, timestamp });
+    }
+
+    /// Map an oracle symbol like "btc/usd" to an asset.
+    fn symbol_to_asset(symbol: &str) -> Option<Asset> {
+        let base = symbol.split('/').next()?.to_lowercase();
+        [Asset::BTC, Asset::ETH, Asset::SOL, Asset::XRP]
+            .into_iter()
+            .find(|a| a.slug_prefix() == base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_chainlink_update() {
+        let feed = OracleFeed::new("wss://example");
+        let msg = r#"{"topic":"crypto_prices_chainlink","type":"update","timestamp":1753314064237,"payload":{"symbol":"btc/usd","timestamp":1753314064213,"value":67234.5}}"#;
+        OracleFeed::handle_message(msg, &feed.prices);
+
+        let p = feed.get_price(Asset::BTC).unwrap();
+        assert_eq!(p.price, 67234.5);
+        assert_eq!(p.timestamp.timestamp_millis(), 1753314064213);
+        assert!(feed.get_price(Asset::ETH).is_none());
+
+        // Other topics and unknown symbols are ignored
+        OracleFeed::handle_message(
+            r#"{"topic":"crypto_prices","payload":{"symbol":"ethusdt","value":1.0}}"#,
+            &feed.prices,
+        );
+        OracleFeed::handle_message(
+            r#"{"topic":"crypto_prices_chainlink","payload":{"symbol":"doge/usd","value":1.0}}"#,
+            &feed.prices,
+        );
+        assert!(feed.get_price(Asset::ETH).is_none());
+    }
+}
@@ -1,16 +1,46 @@
 use crate::config::PolymarketConfig;
+use crate::error::SattebaazError;
+use crate::feeds::gamma_cache::GammaCache;
 use crate::feeds::market_discovery::MarketDiscovery;
-use crate::models::market::{Asset, Duration, Market, OrderBook};
+use crate::models::market::{Asset, BookDiff, BookSide, Duration, LevelChange, Market, OrderBook};
 use anyhow::Result;
 use dashmap::DashMap;
 use futures_util::StreamExt;
 use rust_decimal::Decimal;
 use serde::Deserialize;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc};
 use tokio_tungstenite::connect_async;
 use tracing::{debug, error, info, warn};
 
+/// In the last this-many seconds of a window, discovery looks for the next
+/// one every second instead of every five, ignoring Gamma's negative cache,
+/// so its books and WS subscription are ready before it opens.
+pub const NEXT_WINDOW_LEAD_SECS: f64 = 30.0;
+
+/// How often the REST loop refreshes a token's book. The WebSocket keeps
+/// streaming every token either way; this only spreads out the snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RefreshTier {
+    /// Every tick (2s): positions open or signals firing
+    #[default]
+    Active,
+    /// Every 5th tick
+    Watch,
+    /// Every 30th tick: nothing open, nothing firing, dead books
+    Hibernated,
+}
+
+impl RefreshTier {
+    fn every_ticks(self) -> u64 {
+        match self {
+            RefreshTier::Active => 1,
+            RefreshTier::Watch => 5,
+            RefreshTier::Hibernated => 30,
+        }
+    }
+}
+
 /// Polymarket CLOB data feed.
 ///
 /// Connects to:
@@ -18,17 +48,25 @@ use tracing::{debug, error, info, warn};
 ///   - WebSocket for real-time book/trade updates
 pub struct PolymarketFeed {
     config: PolymarketConfig,
-    /// Order books indexed by token_id
-    pub books: Arc<DashMap<String, OrderBook>>,
+    /// Order books indexed by token_id. Readers share a book by refcount;
+    /// an update copies it only while a reader still holds the old one.
+    pub books: Arc<DashMap<String, Arc<OrderBook>>>,
     /// Active markets indexed by market slug
     pub markets: Arc<DashMap<String, Market>>,
     /// Token IDs we're subscribed to
     pub subscribed_tokens: Arc<DashMap<String, ()>>,
-    /// Book update broadcast: (token_id) notifying downstream that a book changed
-    pub book_update_tx: broadcast::Sender<String>,
+    /// REST refresh tier per token; unlisted tokens are Active
+    refresh_tiers: Arc<DashMap<String, RefreshTier>>,
+    /// Book update broadcast: what changed in a book, including its new BBO
+    pub book_update_tx: broadcast::Sender<BookDiff>,
     http_client: reqwest::Client,
+    /// Cached Gamma API market metadata lookups
+    gamma: Arc<GammaCache>,
     /// Optional filter: only discover these market types. None = all.
     market_filter: Option<Vec<(Asset, Duration)>>,
+    /// Tokens discovered after the WS connected, to subscribe on the open socket
+    ws_subscribe_tx: mpsc::UnboundedSender<String>,
+    ws_subscribe_rx: Mutex<Option<mpsc::UnboundedReceiver<String>>>,
 }
 
 impl PolymarketFeed {
@@ -41,25 +79,43 @@ impl PolymarketFeed {
             .expect("Failed to build HTTP client");
 
         let (book_update_tx, _) = broadcast::channel(512);
+        let (ws_subscribe_tx, ws_subscribe_rx) = mpsc::unbounded_channel();
+        let gamma = Arc::new(GammaCache::new(http_client.clone(), &config.gamma_api_host));
 
         Self {
             config,
             books: Arc::new(DashMap::new()),
             markets: Arc::new(DashMap::new()),
             subscribed_tokens: Arc::new(DashMap::new()),
+            refresh_tiers: Arc::new(DashMap::new()),
             book_update_tx,
             http_client,
+            gamma,
             market_filter: None,
+            ws_subscribe_tx,
+            ws_subscribe_rx: Mutex::new(Some(ws_subscribe_rx)),
+        }
+    }
+
+    /// Set how often a token's book is refreshed over REST.
+    pub fn set_refresh_tier(&self, token_id: &str, tier: RefreshTier) {
+        let previous = self.refresh_tiers.insert(token_id.to_string(), tier);
+        if previous.is_some_and(|p| p != tier) {
+            debug!("Book refresh for {}: {tier:?}", &token_id[..8.min(token_id.len())]);
         }
     }
 
+    pub fn refresh_tier(&self, token_id: &str) -> RefreshTier {
+        self.refresh_tiers.get(token_id).map(|t| *t).unwrap_or_default()
+    }
+
     /// Restrict market discovery to specific asset/duration pairs.
     pub fn set_market_filter(&mut self, filter: Vec<(Asset, Duration)>) {
         self.market_filter = Some(filter);
     }
 
     /// Start the data feed. Spawns:
-    ///   1. Market discovery loop (every 5s)
+    ///   1. Market discovery loop (every 5s; every 1s for a window about to open)
     ///   2. WebSocket connection for real-time book updates
     ///   3. Book refresh loop (every 2s for active tokens)
     pub fn start(&self, shutdown_tx: &broadcast::Sender<()>) {
@@ -75,35 +131,48 @@ impl PolymarketFeed {
         self.spawn_book_refresh(shutdown_tx.subscribe());
     }
 
-    /// Spawn market discovery: discovers new markets every 5 seconds.
+    /// Spawn market discovery: discovers new markets every 5 seconds, and
+    /// every second for a next window that opens within `NEXT_WINDOW_LEAD_SECS`.
     fn spawn_market_discovery(&self, mut shutdown: broadcast::Receiver<()>) {
         let http = self.http_client.clone();
         let config = self.config.clone();
+        let gamma = self.gamma.clone();
         let markets = self.markets.clone();
         let books = self.books.clone();
         let subscribed = self.subscribed_tokens.clone();
+        let ws_subscribe = self.ws_subscribe_tx.clone();
         let market_types = self.market_filter.clone()
             .unwrap_or_else(MarketDiscovery::all_market_types);
 
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+            let mut ticks: u64 = 0;
 
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
+                        let full_scan = ticks.is_multiple_of(5);
+                        ticks += 1;
                         // Generate slugs for configured market types (current + next)
                         for (asset, duration) in market_types.iter().copied() {
+                            let next_due = MarketDiscovery::time_remaining_in_current(duration) <= NEXT_WINDOW_LEAD_SECS;
+                            if !full_scan && !next_due {
+                                continue;
+                            }
                             let slugs = MarketDiscovery::upcoming_slugs(asset, duration, 2);
-                            for slug in slugs {
+                            for (i, slug) in slugs.into_iter().enumerate() {
                                 // Skip if already tracked
                                 if markets.contains_key(&slug) {
                                     continue;
                                 }
+                                // The next window is about to open: ask Gamma now,
+                                // not when its "not yet" answer expires
+                                if next_due && i == 1 {
+                                    gamma.evict(&slug);
+                                }
 
                                 // Try to resolve via Gamma API
-                                match Self::resolve_market(
-                                    &http, &config.gamma_api_host, &slug, asset, duration,
-                                ).await {
+                                match Self::resolve_market(&gamma, &slug, asset, duration).await {
                                     Ok(Some(market)) => {
                                         info!(
                                             "Discovered market: {} (YES={}, NO={})",
@@ -117,8 +186,10 @@ impl PolymarketFeed {
                                             if let Ok(book) = Self::fetch_book_static(
                                                 &http, &config.clob_host, token_id,
                                             ).await {
-                                                books.insert(token_id.clone(), book);
-                                                subscribed.insert(token_id.clone(), ());
+                                                books.insert(token_id.clone(), Arc::new(book));
+                                            }
+                                            if subscribed.insert(token_id.clone(), ()).is_none() {
+                                                let _ = ws_subscribe.send(token_id.clone());
                                             }
                                         }
 
@@ -134,6 +205,9 @@ impl PolymarketFeed {
                             }
 
                             // Clean up expired markets
+                            if !full_scan {
+                                continue;
+                            }
                             let expired: Vec<String> = markets
                                 .iter()
                                 .filter(|entry| entry.value().time_remaining_secs() < -60.0)
@@ -146,6 +220,7 @@ impl PolymarketFeed {
                                     books.remove(&market.no_token_id);
                                     subscribed.remove(&market.yes_token_id);
                                     subscribed.remove(&market.no_token_id);
+                                    gamma.evict(&slug);
                                     debug!("Cleaned up expired market: {slug}");
                                 }
                             }
@@ -163,6 +238,7 @@ impl PolymarketFeed {
         let books = self.books.clone();
         let subscribed = self.subscribed_tokens.clone();
         let book_tx = self.book_update_tx.clone();
+        let mut new_tokens = self.ws_subscribe_rx.lock().unwrap().take();
 
         tokio::spawn(async move {
             let mut backoff_ms: u64 = 500;
@@ -192,28 +268,22 @@ impl PolymarketFeed {
                             .collect();
 
                         for token_id in &tokens {
-                            let sub_msg = serde_json::json!({
-                                "auth": {},
-                                "type": "subscribe",
-                                "channel": "market",
-                                "assets_ids": [token_id]
-                            });
-                            if let Ok(msg_str) = serde_json::to_string(&sub_msg) {
-                                use futures_util::SinkExt;
-                                let _ = write.send(
-                                    tokio_tungstenite::tungstenite::Message::Text(msg_str)
-                                ).await;
-                            }
+                            Self::send_subscribe(&mut write, token_id).await;
                         }
 
                         if !tokens.is_empty() {
                             info!("Subscribed to {} token books", tokens.len());
                         }
 
-                        // Read loop
+                        // Read loop; tokens discovered meanwhile are subscribed as they come
                         loop {
                             let msg = tokio::select! {
                                 msg = read.next() => msg,
+                                Some(token_id) = async { new_tokens.as_mut()?.recv().await } => {
+                                    Self::send_subscribe(&mut write, &token_id).await;
+                                    debug!("Subscribed to book {}", &token_id[..8.min(token_id.len())]);
+                                    continue;
+                                }
                                 _ = shutdown.recv() => {
                                     info!("Polymarket WS shutdown");
                                     return;
@@ -241,37 +311,64 @@ impl PolymarketFeed {
                     }
                 }
 
-                warn!("Polymarket WS reconnecting in {backoff_ms}ms...");
+                crate::telemetry::events::Reconnect { feed: "polymarket", backoff_ms }.emit();
                 tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
                 backoff_ms = (backoff_ms * 2).min(30_000);
             }
         });
     }
 
+    /// Subscribe the socket to one token's book.
+    async fn send_subscribe<S>(write: &mut S, token_id: &str)
+    where
+        S: futures_util::Sink<tokio_tungstenite::tungstenite::Message> + Unpin,
+    {
+        use futures_util::SinkExt;
+        let sub_msg = serde_json::json!({
+            "auth": {},
+            "type": "subscribe",
+            "channel": "market",
+            "assets_ids": [token_id]
+        });
+        if let Ok(msg_str) = serde_json::to_string(&sub_msg) {
+            let _ = write.send(tokio_tungstenite::tungstenite::Message::Text(msg_str)).await;
+        }
+    }
+
     /// Spawn periodic book refresh via REST (fallback for WS gaps).
     fn spawn_book_refresh(&self, mut shutdown: broadcast::Receiver<()>) {
         let http = self.http_client.clone();
         let clob_host = self.config.clob_host.clone();
         let books = self.books.clone();
         let subscribed = self.subscribed_tokens.clone();
+        let tiers = self.refresh_tiers.clone();
         let book_tx = self.book_update_tx.clone();
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(2));
+            let mut tick: u64 = 0;
 
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
+                        tick += 1;
                         let tokens: Vec<String> = subscribed
                             .iter()
                             .map(|e| e.key().clone())
+                            .filter(|t| {
+                                let tier = tiers.get(t).map(|t| *t).unwrap_or_default();
+                                tick.is_multiple_of(tier.every_ticks())
+                            })
                             .collect();
 
                         for token_id in tokens {
                             match Self::fetch_book_static(&http, &clob_host, &token_id).await {
                                 Ok(book) => {
-                                    books.insert(token_id.clone(), book);
-                                    let _ = book_tx.send(token_id);
+                                    let prev_bbo = books.get(&token_id).map(|b| b.bbo()).unwrap_or_default();
+                                    let diff = BookDiff::snapshot(&book, prev_bbo);
+                                    // Subscribers read the book on receipt, so it goes in first
+                                    books.insert(token_id, Arc::new(book));
+                                    let _ = book_tx.send(diff);
                                 }
                                 Err(e) => {
                                     debug!("Book refresh failed for {}: {e}", &token_id[..8.min(token_id.len())]);
@@ -288,8 +385,8 @@ impl PolymarketFeed {
     /// Handle a WebSocket message (book update).
     fn handle_ws_message(
         text: &str,
-        books: &Arc<DashMap<String, OrderBook>>,
-        book_tx: &broadcast::Sender<String>,
+        books: &Arc<DashMap<String, Arc<OrderBook>>>,
+        book_tx: &broadcast::Sender<BookDiff>,
     ) {
         // Polymarket WS sends book updates as:
         // [{"asset_id":"...","market":"...","bids":[...],"asks":[...],"timestamp":"...","hash":"..."}]
@@ -309,52 +406,35 @@ impl PolymarketFeed {
             let Some(asset_id) = update.asset_id else { continue };
 
             if let Some(mut book) = books.get_mut(&asset_id) {
-                // Apply delta updates to existing book
-                if let Some(bids) = update.bids {
-                    for level in bids {
-                        let price = level.price.parse::<Decimal>().unwrap_or_default();
-                        let size = level.size.parse::<Decimal>().unwrap_or_default();
-                        if size == Decimal::ZERO {
-                            book.bids.remove(&price);
-                        } else {
-                            book.bids.insert(price, size);
-                        }
-                    }
-                }
-                if let Some(asks) = update.asks {
-                    for level in asks {
-                        let price = level.price.parse::<Decimal>().unwrap_or_default();
-                        let size = level.size.parse::<Decimal>().unwrap_or_default();
-                        if size == Decimal::ZERO {
-                            book.asks.remove(&price);
-                        } else {
-                            book.asks.insert(price, size);
-                        }
-                    }
-                }
+                // Apply delta updates to existing book, skipping levels that
+                // don't parse as a price in [0, 1] and a non-negative size
+                let levels = |levels: Option<Vec<BookLevel>>, side| {
+                    levels.into_iter().flatten().filter_map(move |level| {
+                        let price = level.price.parse::<Decimal>().ok()?;
+                        let size = level.size.parse::<Decimal>().ok()?;
+                        let valid = (Decimal::ZERO..=Decimal::ONE).contains(&price) && size >= Decimal::ZERO;
+                        valid.then_some(LevelChange { side, price, size })
+                    })
+                };
+                let changes = levels(update.bids, BookSide::Bid)
+                    .chain(levels(update.asks, BookSide::Ask))
+                    .collect();
+                let diff = Arc::make_mut(&mut book).apply_changes(changes);
+                drop(book);
 
-                let _ = book_tx.send(asset_id);
+                let _ = book_tx.send(diff);
             }
         }
     }
 
-    /// Resolve a market slug to a Market struct via Gamma API.
+    /// Resolve a market slug to a Market struct via Gamma API (through the cache).
     async fn resolve_market(
-        http: &reqwest::Client,
-        gamma_host: &str,
+        gamma: &GammaCache,
         slug: &str,
         asset: Asset,
         duration: Duration,
     ) -> Result<Option<Market>> {
-        let url = format!("{}/markets?slug={}", gamma_host, slug);
-        let resp = http.get(&url).send().await?;
-
-        if !resp.status().is_success() {
-            return Ok(None);
-        }
-
-        let text = resp.text().await?;
-        let infos: Vec<MarketInfo> = serde_json::from_str(&text).unwrap_or_default();
+        let infos = gamma.markets_by_slug(slug).await?;
 
         let info = match infos.into_iter().next() {
             Some(i) => i,
@@ -402,7 +482,7 @@ impl PolymarketFeed {
             }
         };
 
-        let market = Market::with_condition_id(
+        let mut market = Market::with_condition_id(
             slug.to_string(),
             asset,
             duration,
@@ -410,6 +490,23 @@ impl PolymarketFeed {
             no_id,
             info.condition_id,
         );
+        if let Some(tick) = info.tick_size.and_then(|t| t.to_string().parse::<Decimal>().ok()) {
+            market.tick_size = tick;
+        }
+
+        // Upcoming markets are discovered before they open — take the window
+        // from the slug's start timestamp rather than the current interval.
+        if let Some(start) = slug.rsplit('-').next().and_then(|ts| ts.parse::<i64>().ok()) {
+            let end = start + duration.seconds() as i64;
+            if let (Some(open), Some(close)) = (
+                chrono::DateTime::from_timestamp(start, 0),
+                chrono::DateTime::from_timestamp(end, 0),
+            ) {
+                market.open_time = open;
+                market.close_time = close;
+            }
+        }
+        market.joined_at = Some(chrono::Utc::now());
 
         Ok(Some(market))
     }
@@ -419,15 +516,14 @@ impl PolymarketFeed {
         http: &reqwest::Client,
         clob_host: &str,
         token_id: &str,
-    ) -> Result<OrderBook> {
+    ) -> Result<OrderBook, SattebaazError> {
         let url = format!("{}/book?token_id={}", clob_host, token_id);
 
-        let resp: BookResponse = http
-            .get(&url)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let resp = http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(SattebaazError::from_response("Book fetch", resp).await);
+        }
+        let resp: BookResponse = resp.json().await?;
 
         let mut book = OrderBook::new(token_id.to_string());
 
@@ -447,14 +543,15 @@ impl PolymarketFeed {
     }
 
     /// Fetch order book snapshot via REST API (instance method).
-    pub async fn fetch_book(&self, token_id: &str) -> Result<OrderBook> {
-        let book = Self::fetch_book_static(&self.http_client, &self.config.clob_host, token_id).await?;
+    pub async fn fetch_book(&self, token_id: &str) -> Result<Arc<OrderBook>, SattebaazError> {
+        let book = Arc::new(Self::fetch_book_static(&self.http_client, &self.config.clob_host, token_id).await?);
         self.books.insert(token_id.to_string(), book.clone());
         Ok(book)
     }
 
-    /// Get cached order book for a token.
-    pub fn get_book(&self, token_id: &str) -> Option<OrderBook> {
+    /// Get cached order book for a token. Shares the cached book rather than
+    /// copying it; later updates don't change the returned snapshot.
+    pub fn get_book(&self, token_id: &str) -> Option<Arc<OrderBook>> {
         self.books.get(token_id).map(|b| b.clone())
     }
 
@@ -463,6 +560,15 @@ impl PolymarketFeed {
         self.markets.get(slug).map(|m| m.clone())
     }
 
+    /// Tracked markets of one series (asset and window length).
+    pub fn series_markets(&self, asset: Asset, duration: Duration) -> Vec<Market> {
+        self.markets
+            .iter()
+            .filter(|m| m.asset == asset && m.duration == duration)
+            .map(|m| m.clone())
+            .collect()
+    }
+
     /// Get the best ask price for a token from cache.
     pub fn best_ask(&self, token_id: &str) -> Option<(Decimal, Decimal)> {
         self.books.get(token_id)?.best_ask()
@@ -473,11 +579,25 @@ impl PolymarketFeed {
         self.books.get(token_id)?.best_bid()
     }
 
-    /// Subscribe to book update notifications.
-    pub fn subscribe_book_updates(&self) -> broadcast::Receiver<String> {
+    /// Update the tick size of every tracked market trading `token_id`.
+    pub fn set_tick_size(&self, token_id: &str, tick: Decimal) {
+        for mut market in self.markets.iter_mut() {
+            if market.yes_token_id == token_id || market.no_token_id == token_id {
+                market.tick_size = tick;
+            }
+        }
+    }
+
+    /// Subscribe to book diffs: levels changed plus the BBO before and after.
+    pub fn subscribe_book_updates(&self) -> broadcast::Receiver<BookDiff> {
         self.book_update_tx.subscribe()
     }
 
+    /// Gamma metadata cache (for telemetry).
+    pub fn gamma_cache(&self) -> Arc<GammaCache> {
+        self.gamma.clone()
+    }
+
     /// Get count of tracked markets.
     pub fn market_count(&self) -> usize {
         self.markets.len()
@@ -518,6 +638,9 @@ pub struct MarketInfo {
     /// JSON-encoded array of outcome labels, e.g. "[\"Up\", \"Down\"]"
     #[serde(default)]
     pub outcomes: Option<String>,
+    /// Minimum price increment: 0.01 for most markets, 0.001 near the extremes
+    #[serde(rename = "orderPriceMinTickSize", default)]
+    pub tick_size: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -536,3 +659,89 @@ struct WsBookUpdate {
     asks: Option<Vec<BookLevel>>,
     timestamp: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Bbo;
+    use proptest::prelude::*;
+    use rust_decimal_macros::dec;
+
+    fn books_with(token_id: &str) -> Arc<DashMap<String, Arc<OrderBook>>> {
+        let books = Arc::new(DashMap::new());
+        books.insert(token_id.to_string(), Arc::new(OrderBook::new(token_id.to_string())));
+        books
+    }
+
+    #[test]
+    fn test_ws_delta_applied_and_garbage_levels_skipped() {
+        let books = books_with("tok");
+        let (book_tx, mut diffs) = broadcast::channel(16);
+        let msg = r#"[{"asset_id":"tok","market":"0x1","bids":[{"price":"0.48","size":"10"},{"price":"1.5","size":"3"},{"price":"x","size":"1"}],"asks":[{"price":"0.52","size":"-4"},{"price":"0.53","size":"7"}]}]"#;
+        PolymarketFeed::handle_ws_message(msg, &books, &book_tx);
+
+        let diff = diffs.try_recv().unwrap();
+        assert_eq!(diff.changes.len(), 2, "{:?}", diff.changes);
+        assert_eq!(diff.bbo, Bbo { bid: Some((dec!(0.48), dec!(10))), ask: Some((dec!(0.53), dec!(7))) });
+
+        // Books we don't hold, acks and other message shapes change nothing
+        for other in [
+            r#"{"asset_id":"other","bids":[{"price":"0.1","size":"1"}]}"#,
+            r#"{"event_type":"price_change","market":"0x1"}"#,
+            r#"[]"#,
+            "PONG",
+        ] {
+            PolymarketFeed::handle_ws_message(other, &books, &book_tx);
+        }
+        assert!(diffs.try_recv().is_err());
+        assert_eq!(books.len(), 1);
+    }
+
+    /// A book level as the wire might garble it.
+    fn wire_level() -> impl Strategy<Value = serde_json::Value> {
+        // Mostly strings: one mistyped field drops the whole message
+        let value = prop_oneof![
+            8 => prop::sample::select(vec![
+                "0.5", "0", "1", "-0.01", "1.01", "NaN", "1e400", "79228162514264337593543950335", "", "é漢",
+            ])
+            .prop_map(serde_json::Value::from),
+            2 => any::<String>().prop_map(serde_json::Value::from),
+            1 => any::<f64>().prop_map(serde_json::Value::from),
+            1 => Just(serde_json::Value::Null),
+        ];
+        (value.clone(), value).prop_map(|(price, size)| serde_json::json!({ "price": price, "size": size }))
+    }
+
+    proptest! {
+        /// Garbage, truncated and mistyped book updates never panic the feed
+        /// task, and the book only ever holds levels in [0, 1] with a
+        /// non-negative size.
+        #[test]
+        fn prop_ws_messages_never_panic(
+            bids in prop::collection::vec(wire_level(), 0..6),
+            asks in prop::collection::vec(wire_level(), 0..6),
+            batched in any::<bool>(),
+            junk in any::<String>(),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let books = books_with("tok");
+            let (book_tx, _diffs) = broadcast::channel(64);
+            let update = serde_json::json!({ "asset_id": "tok", "market": "0x1", "bids": bids, "asks": asks });
+            let msg = if batched { serde_json::json!([update]) } else { update }.to_string();
+
+            let mut n = cut.index(msg.len() + 1);
+            while !msg.is_char_boundary(n) {
+                n -= 1;
+            }
+            for text in [junk.as_str(), &msg[..n], &msg] {
+                PolymarketFeed::handle_ws_message(text, &books, &book_tx);
+            }
+
+            let book = books.get("tok").unwrap();
+            for (price, size) in book.bids.iter().chain(book.asks.iter()) {
+                prop_assert!((Decimal::ZERO..=Decimal::ONE).contains(&price), "price {}", price);
+                prop_assert!(size >= Decimal::ZERO, "size {}", size);
+            }
+        }
+    }
+}
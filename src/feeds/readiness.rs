@@ -0,0 +1,177 @@
+use crate::feeds::binance::BinanceFeed;
+use crate::feeds::market_discovery::MarketDiscovery;
+use crate::feeds::polymarket::PolymarketFeed;
+use crate::models::market::{Asset, Duration};
+use std::time::Instant;
+
+/// Snapshot of how far the feeds are from being tradeable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessReport {
+    pub markets_ready: usize,
+    pub markets_total: usize,
+    pub books_ready: usize,
+    pub books_total: usize,
+    pub prices_ready: usize,
+    pub prices_total: usize,
+    /// Human-readable list of what is still missing
+    pub missing: Vec<String>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// One-line progress summary.
+    pub fn summary(&self) -> String {
+        format!(
+            "markets {}/{} | books {}/{} | prices {}/{}",
+            self.markets_ready,
+            self.markets_total,
+            self.books_ready,
+            self.books_total,
+            self.prices_ready,
+            self.prices_total,
+        )
+    }
+}
+
+/// Startup readiness barrier.
+///
+/// Trading is enabled only once every target market is discovered, each of
+/// its tokens has a first book snapshot, and each asset has a Binance price.
+pub struct ReadinessBarrier {
+    targets: Vec<(Asset, Duration)>,
+}
+
+impl ReadinessBarrier {
+    pub fn new(targets: Vec<(Asset, Duration)>) -> Self {
+        Self { targets }
+    }
+
+    /// Check current readiness without waiting.
+    pub async fn check(&self, poly: &PolymarketFeed, binance: &BinanceFeed) -> ReadinessReport {
+        let mut report = ReadinessReport {
+            markets_ready: 0,
+            markets_total: self.targets.len(),
+            books_ready: 0,
+            books_total: self.targets.len() * 2,
+            prices_ready: 0,
+            prices_total: 0,
+            missing: Vec::new(),
+        };
+
+        for (asset, duration) in &self.targets {
+            let slug = MarketDiscovery::current_slug(*asset, *duration);
+            let Some(market) = poly.get_market(&slug) else {
+                report.missing.push(format!("market {slug}"));
+                continue;
+            };
+            report.markets_ready += 1;
+
+            for (label, token_id) in [("YES", &market.yes_token_id), ("NO", &market.no_token_id)] {
+                if poly.get_book(token_id).is_some() {
+                    report.books_ready += 1;
+                } else {
+                    report.missing.push(format!("{label} book for {slug}"));
+                }
+            }
+        }
+
+        let mut assets: Vec<Asset> = Vec::new();
+        for (asset, _) in &self.targets {
+            if !assets.contains(asset) {
+                assets.push(*asset);
+            }
+        }
+        report.prices_total = assets.len();
+        for asset in assets {
+            if binance.get_price(asset).is_some() {
+                report.prices_ready += 1;
+            } else {
+                report.missing.push(format!("{asset:?} price"));
+            }
+        }
+
+        report
+    }
+
+    /// Poll until ready or `timeout` elapses, calling `on_progress` whenever
+    /// the report changes. Returns the final report (check `is_ready()`).
+    pub async fn wait<F>(
+        &self,
+        poly: &PolymarketFeed,
+        binance: &BinanceFeed,
+        timeout: std::time::Duration,
+        mut on_progress: F,
+    ) -> ReadinessReport
+    where
+        F: FnMut(&ReadinessReport),
+    {
+        let start = Instant::now();
+        let mut last: Option<ReadinessReport> = None;
+
+        loop {
+            let report = self.check(poly, binance).await;
+            if last.as_ref() != Some(&report) {
+                on_progress(&report);
+                last = Some(report.clone());
+            }
+            if report.is_ready() || start.elapsed() >= timeout {
+                return report;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::market::{Market, OrderBook};
+
+    #[tokio::test]
+    async fn test_reports_missing_feeds() {
+        let config = Config::default();
+        let poly = PolymarketFeed::new(config.polymarket.clone());
+        let binance = BinanceFeed::new(config.binance.clone());
+        let barrier = ReadinessBarrier::new(vec![
+            (Asset::BTC, Duration::FiveMin),
+            (Asset::BTC, Duration::FifteenMin),
+        ]);
+
+        let report = barrier.check(&poly, &binance).await;
+        assert!(!report.is_ready());
+        assert_eq!(report.markets_total, 2);
+        assert_eq!(report.prices_total, 1);
+        assert_eq!(report.markets_ready, 0);
+
+        // Discover the 5m market with only its YES book
+        let slug = MarketDiscovery::current_slug(Asset::BTC, Duration::FiveMin);
+        let market = Market::new(slug.clone(), Asset::BTC, Duration::FiveMin, "y".into(), "n".into());
+        poly.markets.insert(slug.clone(), market);
+        poly.books.insert("y".into(), std::sync::Arc::new(OrderBook::new("y".into())));
+
+        let report = barrier.check(&poly, &binance).await;
+        assert_eq!(report.markets_ready, 1);
+        assert_eq!(report.books_ready, 1);
+        assert!(report.missing.iter().any(|m| m == &format!("NO book for {slug}")));
+        assert!(report.missing.iter().any(|m| m == "BTC price"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_with_report() {
+        let config = Config::default();
+        let poly = PolymarketFeed::new(config.polymarket.clone());
+        let binance = BinanceFeed::new(config.binance.clone());
+        let barrier = ReadinessBarrier::new(vec![(Asset::ETH, Duration::FifteenMin)]);
+
+        let mut updates = 0;
+        let report = barrier
+            .wait(&poly, &binance, std::time::Duration::from_millis(300), |_| updates += 1)
+            .await;
+        assert!(!report.is_ready());
+        assert_eq!(updates, 1, "progress only reported on change");
+    }
+}
@@ -0,0 +1,296 @@
+use crate::execution::polygon_merger::CTF_ADDRESS;
+use crate::models::market::{Market, Side};
+use crate::models::order::{OrderIntent, OrderSide};
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolCall};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, warn};
+
+/// Conditional tokens carry the collateral's 6 decimals.
+const TOKEN_DECIMALS: u32 = 6;
+/// Differences below this many shares are rounding, not a mismatch.
+const MISMATCH_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+sol! {
+    // ERC1155 batch read on the CTF contract
+    function balanceOfBatch(address[] accounts, uint256[] ids) external view returns (uint256[]);
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<serde_json::Value>,
+    error: Option<serde_json::Value>,
+}
+
+/// What we think we hold of one token against what the chain says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceMismatch {
+    pub market: String,
+    pub side: Side,
+    pub token_id: String,
+    /// Shares PositionManager has booked
+    pub booked: Decimal,
+    pub on_chain: Decimal,
+}
+
+impl BalanceMismatch {
+    /// One line for a log or alert.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {:?}: booked {} shares, {} on-chain ({:+})",
+            self.market,
+            self.side,
+            self.booked,
+            self.on_chain,
+            self.on_chain - self.booked
+        )
+    }
+}
+
+/// Ground-truth inventory from Polygon.
+///
+/// Reads our ERC1155 conditional token balances for every open market with
+/// one `balanceOfBatch` call on the CTF contract. The reconciler compares
+/// them against what PositionManager has booked, and exits are capped to
+/// them so a sell never asks for tokens the wallet doesn't have. Fills land
+/// on-chain a few seconds after they match, so a mismatch is only reported
+/// once it has survived two polls in a row.
+pub struct TokenBalances {
+    rpc_url: String,
+    http: reqwest::Client,
+    ctf_address: Address,
+    /// The wallet holding the tokens: the proxy for proxy accounts
+    owner: Address,
+    balances: DashMap<String, Decimal>,
+    updated_at: Mutex<Option<DateTime<Utc>>>,
+    /// Tokens that mismatched on the last reconcile
+    suspect: Mutex<HashSet<String>>,
+}
+
+impl TokenBalances {
+    pub fn new(rpc_url: &str, owner: &str) -> Result<Self> {
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            http: reqwest::Client::new(),
+            ctf_address: Address::from_slice(&hex::decode(CTF_ADDRESS)?),
+            owner: owner.parse().with_context(|| format!("invalid token holder address {owner}"))?,
+            balances: DashMap::new(),
+            updated_at: Mutex::new(None),
+            suspect: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// Last balance read for `token_id`; None until it has been polled.
+    pub fn get(&self, token_id: &str) -> Option<Decimal> {
+        self.balances.get(token_id).map(|b| *b)
+    }
+
+    pub fn updated_at(&self) -> Option<DateTime<Utc>> {
+        *self.updated_at.lock().unwrap()
+    }
+
+    /// Record balances read at `now`.
+    pub fn set(&self, balances: impl IntoIterator<Item = (String, Decimal)>, now: DateTime<Utc>) {
+        for (token_id, shares) in balances {
+            self.balances.insert(token_id, shares);
+        }
+        *self.updated_at.lock().unwrap() = Some(now);
+    }
+
+    /// Read the balances of `token_ids` from chain, in order.
+    pub async fn fetch(&self, token_ids: &[String]) -> Result<Vec<Decimal>> {
+        let ids = token_ids
+            .iter()
+            .map(|id| U256::from_str_radix(id, 10).with_context(|| format!("invalid token id {id}")))
+            .collect::<Result<Vec<_>>>()?;
+        let call = balanceOfBatchCall { accounts: vec![self.owner; ids.len()], ids };
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_call",
+            "params": [
+                { "to": format!("{:?}", self.ctf_address), "data": format!("0x{}", hex::encode(call.abi_encode())) },
+                "latest"
+            ],
+            "id": 1
+        });
+        let resp: JsonRpcResponse = self.http.post(&self.rpc_url).json(&body).send().await?.json().await?;
+        if let Some(err) = resp.error {
+            bail!("RPC error in balanceOfBatch: {err:?}");
+        }
+        let data = resp.result.as_ref().and_then(|r| r.as_str()).context("no result in balanceOfBatch response")?;
+        decode_balances(data, token_ids.len())
+    }
+
+    /// Poll every token of the open markets in `markets`.
+    pub async fn refresh(&self, markets: &DashMap<String, Market>, now: DateTime<Utc>) -> Result<usize> {
+        let token_ids: Vec<String> = markets
+            .iter()
+            .filter(|m| m.close_time > now)
+            .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()])
+            .collect();
+        if token_ids.is_empty() {
+            return Ok(0);
+        }
+        let balances = self.fetch(&token_ids).await?;
+        let n = token_ids.len();
+        self.set(token_ids.into_iter().zip(balances), now);
+        Ok(n)
+    }
+
+    /// Compare `booked` (shares per token, from PositionManager) with the
+    /// last poll for the open markets in `markets`. Returns the mismatches
+    /// that were already there on the previous reconcile.
+    pub fn reconcile(
+        &self,
+        markets: &DashMap<String, Market>,
+        booked: &HashMap<String, Decimal>,
+        now: DateTime<Utc>,
+    ) -> Vec<BalanceMismatch> {
+        let mut found = Vec::new();
+        for market in markets.iter().filter(|m| m.close_time > now) {
+            for (side, token_id) in [(Side::Yes, &market.yes_token_id), (Side::No, &market.no_token_id)] {
+                let Some(on_chain) = self.get(token_id) else { continue };
+                let booked = booked.get(token_id).copied().unwrap_or_default();
+                if (on_chain - booked).abs() >= MISMATCH_TOLERANCE {
+                    found.push(BalanceMismatch {
+                        market: market.slug.clone(),
+                        side,
+                        token_id: token_id.clone(),
+                        booked,
+                        on_chain,
+                    });
+                }
+            }
+        }
+        let mut suspect = self.suspect.lock().unwrap();
+        let persistent = found.iter().filter(|m| suspect.contains(&m.token_id)).cloned().collect();
+        *suspect = found.into_iter().map(|m| m.token_id).collect();
+        persistent
+    }
+
+    /// Shrink sells in `orders` to the tokens the wallet holds, counting
+    /// earlier sells of the same token in the batch against it; drops sells
+    /// with nothing left to sell. Tokens not polled yet are left alone.
+    pub fn cap_sells(&self, orders: &mut Vec<OrderIntent>) {
+        let mut left: HashMap<String, Decimal> = HashMap::new();
+        for order in orders.iter_mut().filter(|o| o.order_side == OrderSide::Sell) {
+            let Some(held) = self.get(&order.token_id) else { continue };
+            let remaining = left.entry(order.token_id.clone()).or_insert(held);
+            if order.size > *remaining {
+                debug!("Sell of {} capped to on-chain {remaining} (wanted {})", order.token_id, order.size);
+                order.size = *remaining;
+            }
+            *remaining -= order.size;
+        }
+        orders.retain(|o| o.order_side != OrderSide::Sell || o.size > Decimal::ZERO);
+    }
+
+    /// Poll every `period` until shutdown, sending the time of each
+    /// successful refresh for whoever reconciles.
+    pub fn spawn(
+        self: &Arc<Self>,
+        markets: Arc<DashMap<String, Market>>,
+        period: std::time::Duration,
+        mut shutdown: broadcast::Receiver<()>,
+    ) -> mpsc::UnboundedReceiver<DateTime<Utc>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let feed = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        let now = Utc::now();
+                        match feed.refresh(&markets, now).await {
+                            Ok(n) if n > 0 => {
+                                let _ = tx.send(now);
+                            }
+                            Ok(_) => {}
+                            Err(e) => warn!("Token balance poll failed: {e}"),
+                        }
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+        rx
+    }
+}
+
+/// Decode a `balanceOfBatch` result of `expected` balances into shares.
+fn decode_balances(data: &str, expected: usize) -> Result<Vec<Decimal>> {
+    let bytes = hex::decode(data.trim_start_matches("0x")).context("invalid balanceOfBatch hex")?;
+    let raw = balanceOfBatchCall::abi_decode_returns(&bytes, true)?._0;
+    if raw.len() != expected {
+        bail!("balanceOfBatch returned {} balances for {expected} tokens", raw.len());
+    }
+    raw.into_iter()
+        .map(|b| {
+            let units = i64::try_from(b).context("token balance out of range")?;
+            Ok(Decimal::new(units, TOKEN_DECIMALS))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use alloy_sol_types::SolValue;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_balances_decode_reconcile_and_cap_sells() {
+        // 12.5 and 0 shares, as the chain encodes them
+        let data = format!("0x{}", hex::encode(vec![U256::from(12_500_000u64), U256::ZERO].abi_encode()));
+        assert_eq!(decode_balances(&data, 2).unwrap(), [dec!(12.5), Decimal::ZERO]);
+        assert!(decode_balances(&data, 3).is_err());
+
+        let feed = TokenBalances::new("http://localhost", "0x000000000000000000000000000000000000dEaD").unwrap();
+        let market = Market::new("btc-updown-5m-1".into(), Asset::BTC, Duration::FiveMin, "1".into(), "2".into());
+        let now = market.close_time - chrono::Duration::seconds(60);
+        let markets = DashMap::new();
+        markets.insert(market.slug.clone(), market.clone());
+        feed.set([("1".to_string(), dec!(12.5)), ("2".to_string(), Decimal::ZERO)], now);
+
+        // A mismatch is reported once it survives a second poll; rounding never is
+        let booked = HashMap::from([("1".to_string(), dec!(10)), ("2".to_string(), dec!(0.004))]);
+        assert!(feed.reconcile(&markets, &booked, now).is_empty(), "first sighting may be a fill in flight");
+        let mismatches = feed.reconcile(&markets, &booked, now);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!((mismatches[0].side, mismatches[0].booked, mismatches[0].on_chain), (Side::Yes, dec!(10), dec!(12.5)));
+        let settled = HashMap::from([("1".to_string(), dec!(12.5))]);
+        assert!(feed.reconcile(&markets, &settled, now).is_empty());
+        assert!(feed.reconcile(&markets, &booked, market.close_time).is_empty(), "closed markets aren't reconciled");
+
+        // Sells capped to what's held across the batch; none left drops the sell
+        let sell = |token: &str, size: Decimal| OrderIntent {
+            token_id: token.into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Sell,
+            price: dec!(0.60),
+            size,
+            order_type: crate::models::order::OrderType::GTC,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+        let mut orders = vec![sell("1", dec!(20)), sell("1", dec!(5)), sell("2", dec!(5)), sell("3", dec!(5))];
+        feed.cap_sells(&mut orders);
+        let sizes: Vec<_> = orders.iter().map(|o| (o.token_id.as_str(), o.size)).collect();
+        assert_eq!(sizes, [("1", dec!(12.5)), ("3", dec!(5))]);
+
+        let mut orders = vec![sell("1", dec!(10)), sell("1", dec!(5))];
+        feed.cap_sells(&mut orders);
+        let sizes: Vec<_> = orders.iter().map(|o| (o.token_id.as_str(), o.size)).collect();
+        assert_eq!(sizes, [("1", dec!(10)), ("1", dec!(2.5))]);
+    }
+}
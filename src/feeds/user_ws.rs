@@ -1,17 +1,19 @@
 use crate::models::market::Side;
-use crate::models::order::OrderSide;
+use crate::models::order::{taker_fee, OrderSide, OrderStatus};
 use futures_util::{SinkExt, StreamExt};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio_tungstenite::connect_async;
 use tracing::{debug, error, info, warn};
 
 /// WebSocket client for the Polymarket CLOB user channel.
 ///
-/// Receives real-time fill events for GTC/GTD orders that don't fill immediately.
-/// Also receives order status updates (cancelled, expired, etc.).
+/// Receives real-time fill events for GTC/GTD orders that don't fill immediately,
+/// and order status transitions (placed, matched, cancelled) on the same channel.
 ///
 /// WS endpoint: wss://ws-subscriptions-clob.polymarket.com/ws/user
 /// Auth: connect, then send auth message with L1 headers.
@@ -20,6 +22,12 @@ pub struct UserWsFeed {
     address: String,
     /// Broadcast channel for fill events
     fill_tx: broadcast::Sender<FillEvent>,
+    /// Broadcast channel for order status transitions
+    order_tx: broadcast::Sender<OrderUpdate>,
+    /// Broadcast channel for on-chain progress of matched trades
+    settle_tx: broadcast::Sender<TradeSettlement>,
+    /// Whether the socket is currently subscribed
+    connected: Arc<AtomicBool>,
 }
 
 /// A fill event received from the CLOB user WebSocket.
@@ -34,13 +42,59 @@ pub struct FillEvent {
     pub size: Decimal,
     pub fee: Decimal,
     pub strategy_tag: String,
+    /// Exchange trade id, or `size@price` when the message carried none;
+    /// with the order id it names this fill across redeliveries
+    pub trade_id: String,
+}
+
+/// An order status transition received from the CLOB user WebSocket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderUpdate {
+    pub order_id: String,
+    pub token_id: String,
+    /// Open, PartiallyFilled, Filled or Cancelled
+    pub status: OrderStatus,
+    pub original_size: Decimal,
+    pub size_matched: Decimal,
+}
+
+impl OrderUpdate {
+    /// Whether the order can no longer fill.
+    pub fn is_final(&self) -> bool {
+        matches!(self.status, OrderStatus::Filled | OrderStatus::Cancelled)
+    }
+}
+
+/// Where a matched trade is in its on-chain settlement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeStatus {
+    Mined,
+    Confirmed,
+    /// Transaction failed and is being resubmitted
+    Retrying,
+    /// Gave up: the trade never settles
+    Failed,
+}
+
+/// A status after MATCHED for one of our trades, received from the CLOB user WebSocket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeSettlement {
+    pub order_id: String,
+    pub status: TradeStatus,
 }
 
 /// Raw WS message from CLOB user channel.
 #[derive(Debug, Deserialize)]
 struct WsUserMessage {
+    /// "trade" or "order"
+    event_type: Option<String>,
+    /// Trades: "trade". Orders: "PLACEMENT", "UPDATE" or "CANCELLATION"
     #[serde(rename = "type")]
     msg_type: Option<String>,
+    // Order event
+    id: Option<String>,
+    original_size: Option<String>,
+    size_matched: Option<String>,
     // Trade/fill event
     order_id: Option<String>,
     token_id: Option<String>,
@@ -49,7 +103,10 @@ struct WsUserMessage {
     price: Option<String>,
     size: Option<String>,
     fee: Option<String>,
-    status: Option<String>,     // "MATCHED", "FILLED", "CANCELLED"
+    /// Rate the taker's order was signed with; fees are the taker's to pay
+    fee_rate_bps: Option<String>,
+    trader_side: Option<String>, // "TAKER" or "MAKER"
+    status: Option<String>,     // Orders: "MATCHED", "FILLED", "CANCELLED"; trades: "MATCHED", "MINED", "CONFIRMED", ...
     // Misc
     asset_id: Option<String>,
 }
@@ -57,6 +114,8 @@ struct WsUserMessage {
 impl UserWsFeed {
     pub fn new(ws_host: &str, address: &str) -> Self {
         let (fill_tx, _) = broadcast::channel(256);
+        let (order_tx, _) = broadcast::channel(256);
+        let (settle_tx, _) = broadcast::channel(256);
 
         // User channel endpoint
         let ws_url = if ws_host.ends_with("/ws/user") {
@@ -69,6 +128,9 @@ impl UserWsFeed {
             ws_host: ws_url,
             address: address.to_string(),
             fill_tx,
+            order_tx,
+            settle_tx,
+            connected: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -77,11 +139,30 @@ impl UserWsFeed {
         self.fill_tx.subscribe()
     }
 
+    /// Subscribe to order status transitions.
+    pub fn subscribe_order_updates(&self) -> broadcast::Receiver<OrderUpdate> {
+        self.order_tx.subscribe()
+    }
+
+    /// Subscribe to on-chain settlement of matched trades.
+    pub fn subscribe_settlements(&self) -> broadcast::Receiver<TradeSettlement> {
+        self.settle_tx.subscribe()
+    }
+
+    /// Whether the user channel is up. While it is, order updates arrive
+    /// without polling; while it isn't, consumers must poll.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
     /// Start the user WebSocket connection with reconnection logic.
     pub fn start(&self, shutdown_tx: &broadcast::Sender<()>) {
         let ws_host = self.ws_host.clone();
         let address = self.address.clone();
         let fill_tx = self.fill_tx.clone();
+        let order_tx = self.order_tx.clone();
+        let settle_tx = self.settle_tx.clone();
+        let connected = self.connected.clone();
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         tokio::spawn(async move {
@@ -117,6 +198,7 @@ impl UserWsFeed {
                             error!("Failed to subscribe user WS: {e}");
                             continue;
                         }
+                        connected.store(true, Ordering::Relaxed);
 
                         // Read messages until disconnect
                         loop {
@@ -124,7 +206,7 @@ impl UserWsFeed {
                                 msg = read.next() => {
                                     match msg {
                                         Some(Ok(tokio_tungstenite::tungstenite::Message::Text(text))) => {
-                                            Self::handle_message(&text, &fill_tx);
+                                            Self::handle_message(&text, &fill_tx, &order_tx, &settle_tx);
                                         }
                                         Some(Ok(tokio_tungstenite::tungstenite::Message::Ping(data))) => {
                                             let _ = write.send(
@@ -144,10 +226,12 @@ impl UserWsFeed {
                                 }
                                 _ = shutdown_rx.recv() => {
                                     info!("User WS shutting down");
+                                    connected.store(false, Ordering::Relaxed);
                                     return;
                                 }
                             }
                         }
+                        connected.store(false, Ordering::Relaxed);
                     }
                     Err(e) => {
                         error!("User WS connect failed: {e}");
@@ -155,7 +239,7 @@ impl UserWsFeed {
                 }
 
                 // Reconnect with backoff
-                info!("User WS reconnecting in {backoff_ms}ms...");
+                crate::telemetry::events::Reconnect { feed: "user", backoff_ms }.emit();
                 tokio::select! {
                     _ = tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)) => {}
                     _ = shutdown_rx.recv() => return,
@@ -166,12 +250,40 @@ impl UserWsFeed {
     }
 
     /// Handle an incoming user WS message.
-    fn handle_message(text: &str, fill_tx: &broadcast::Sender<FillEvent>) {
+    fn handle_message(
+        text: &str,
+        fill_tx: &broadcast::Sender<FillEvent>,
+        order_tx: &broadcast::Sender<OrderUpdate>,
+        settle_tx: &broadcast::Sender<TradeSettlement>,
+    ) {
         let msg: WsUserMessage = match serde_json::from_str(text) {
             Ok(m) => m,
             Err(_) => return, // Not a parseable message (heartbeat, etc)
         };
 
+        if let Some(update) = Self::parse_order_update(&msg) {
+            debug!(
+                "User WS order: order={} status={:?} matched={}/{}",
+                short(&update.order_id),
+                update.status,
+                update.size_matched,
+                update.original_size
+            );
+            let _ = order_tx.send(update);
+            return;
+        }
+
+        // Past MATCHED, a trade message is the same fill settling, not a new one
+        if let Some(settlement) = Self::parse_trade_settlement(&msg) {
+            debug!(
+                "User WS trade: order={} status={:?}",
+                short(&settlement.order_id),
+                settlement.status
+            );
+            let _ = settle_tx.send(settlement);
+            return;
+        }
+
         let msg_type = msg.msg_type.as_deref().unwrap_or("");
         let status = msg.status.as_deref().unwrap_or("");
 
@@ -201,10 +313,20 @@ impl UserWsFeed {
             .and_then(|s| Decimal::from_str(s).ok())
             .unwrap_or(Decimal::ZERO);
 
+        // Outcome shares trade between $0 and $1; anything else is garbage
+        if size <= Decimal::ZERO || price < Decimal::ZERO || price > Decimal::ONE {
+            return;
+        }
+
+        // An explicit fee wins; otherwise a taker pays the signed rate
         let fee = msg
             .fee
             .as_deref()
             .and_then(|s| Decimal::from_str(s).ok())
+            .or_else(|| {
+                let bps = msg.fee_rate_bps.as_deref()?.parse().ok()?;
+                (msg.trader_side.as_deref() == Some("TAKER")).then(|| taker_fee(price, size, bps))
+            })
             .unwrap_or(Decimal::ZERO);
 
         let order_side = match msg.side.as_deref() {
@@ -213,14 +335,10 @@ impl UserWsFeed {
             _ => OrderSide::Buy,
         };
 
-        if size == Decimal::ZERO {
-            return;
-        }
-
         info!(
             "User WS fill: order={} token={} side={:?} price={} size={} fee={}",
-            &order_id[..8.min(order_id.len())],
-            &token_id[..8.min(token_id.len())],
+            short(&order_id),
+            short(&token_id),
             order_side,
             price,
             size,
@@ -232,20 +350,81 @@ impl UserWsFeed {
             token_id,
             market_id,
             side: order_side,
-            market_side: Side::Yes, // Will be resolved by the consumer
+            market_side: Side::Yes, // Filled in from the OrderRegistry by the consumer
             price,
             size,
             fee,
-            strategy_tag: String::new(), // Will be resolved by the consumer
+            strategy_tag: String::new(), // Likewise
+            trade_id: msg.id.clone().filter(|id| !id.is_empty()).unwrap_or_else(|| format!("{size}@{price}")),
         };
 
         let _ = fill_tx.send(event);
     }
+
+    /// Parse a trade event's settlement progress; None for anything else,
+    /// including a trade that has only matched (that's the fill).
+    fn parse_trade_settlement(msg: &WsUserMessage) -> Option<TradeSettlement> {
+        let is_trade = msg.event_type.as_deref() == Some("trade")
+            || msg.msg_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case("trade"));
+        if !is_trade {
+            return None;
+        }
+        let status = match msg.status.as_deref().map(str::to_uppercase).as_deref() {
+            Some("MINED") => TradeStatus::Mined,
+            Some("CONFIRMED") => TradeStatus::Confirmed,
+            Some("RETRYING") => TradeStatus::Retrying,
+            Some("FAILED") => TradeStatus::Failed,
+            _ => return None,
+        };
+        let order_id = msg.order_id.clone().filter(|id| !id.is_empty())?;
+        Some(TradeSettlement { order_id, status })
+    }
+
+    /// Parse an order event. The status field wins when present; otherwise
+    /// the event type and matched size decide.
+    fn parse_order_update(msg: &WsUserMessage) -> Option<OrderUpdate> {
+        let kind = msg.msg_type.as_deref().unwrap_or("");
+        let is_order = msg.event_type.as_deref() == Some("order")
+            || matches!(kind, "PLACEMENT" | "UPDATE" | "CANCELLATION");
+        if !is_order {
+            return None;
+        }
+        let order_id = msg.id.clone().or(msg.order_id.clone()).filter(|id| !id.is_empty())?;
+        let decimal = |v: &Option<String>| {
+            v.as_deref().and_then(|s| Decimal::from_str(s).ok()).unwrap_or(Decimal::ZERO)
+        };
+        let original_size = decimal(&msg.original_size);
+        let size_matched = decimal(&msg.size_matched);
+        let fully_matched = original_size > Decimal::ZERO && size_matched >= original_size;
+
+        let status = match (msg.status.as_deref().map(str::to_uppercase).as_deref(), kind) {
+            (Some("CANCELED" | "CANCELLED"), _) | (_, "CANCELLATION") => OrderStatus::Cancelled,
+            (Some("MATCHED" | "FILLED"), _) => OrderStatus::Filled,
+            _ if fully_matched => OrderStatus::Filled,
+            _ if size_matched > Decimal::ZERO => OrderStatus::PartiallyFilled,
+            _ => OrderStatus::Open,
+        };
+
+        Some(OrderUpdate {
+            order_id,
+            token_id: msg.asset_id.clone().or(msg.token_id.clone()).unwrap_or_default(),
+            status,
+            original_size,
+            size_matched,
+        })
+    }
+}
+
+/// First 8 characters of an id for logging. Ids come off the wire, so cut
+/// on a char boundary rather than a byte offset.
+fn short(id: &str) -> &str {
+    id.char_indices().nth(8).map_or(id, |(i, _)| &id[..i])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_fill_message() {
@@ -263,22 +442,180 @@ mod tests {
             "status": "MATCHED"
         }"#;
 
-        UserWsFeed::handle_message(msg, &tx);
+        let (order_tx, mut orders) = broadcast::channel(16);
+        let (settle_tx, mut settlements) = broadcast::channel(16);
+        UserWsFeed::handle_message(msg, &tx, &order_tx, &settle_tx);
 
         let event = rx.try_recv().unwrap();
         assert_eq!(event.order_id, "0x123abc");
         assert_eq!(event.token_id, "tok_yes_001");
         assert_eq!(event.price, Decimal::from_str("0.52").unwrap());
         assert_eq!(event.size, Decimal::from_str("10.00").unwrap());
+        assert_eq!(event.fee, Decimal::from_str("0.01").unwrap());
+        assert_eq!(event.trade_id, "10.00@0.52", "no trade id: named by size and price");
+        assert!(orders.try_recv().is_err());
+
+        let with_id = msg.replace(r#""type": "trade","#, r#""type": "trade", "id": "trade-1","#);
+        UserWsFeed::handle_message(&with_id, &tx, &order_tx, &settle_tx);
+        assert_eq!(rx.try_recv().unwrap().trade_id, "trade-1");
+        assert!(settlements.try_recv().is_err());
+
+        // The same trade mining and confirming settles it; it isn't booked again
+        for (status, want) in [("MINED", TradeStatus::Mined), ("CONFIRMED", TradeStatus::Confirmed)] {
+            UserWsFeed::handle_message(&msg.replace("MATCHED", status), &tx, &order_tx, &settle_tx);
+            assert_eq!(settlements.try_recv().unwrap(), TradeSettlement { order_id: "0x123abc".into(), status: want });
+        }
+        assert!(rx.try_recv().is_err());
+
+        // Without an explicit fee, takers pay p(1-p) at the signed rate; makers nothing
+        let rated = msg.replace(r#""fee": "0.01""#, r#""fee_rate_bps": "1000", "trader_side": "TAKER""#);
+        UserWsFeed::handle_message(&rated, &tx, &order_tx, &settle_tx);
+        assert_eq!(rx.try_recv().unwrap().fee, Decimal::from_str("0.2496").unwrap());
+        UserWsFeed::handle_message(&rated.replace("TAKER", "MAKER"), &tx, &order_tx, &settle_tx);
+        assert_eq!(rx.try_recv().unwrap().fee, Decimal::ZERO);
+
+        // A fee too large to compute is zero, not a panic; prices off [0, 1] aren't fills
+        let huge = rated.replace("10.00", "79228162514264337593543950335").replace(r#""1000""#, r#""4294967295""#);
+        UserWsFeed::handle_message(&huge, &tx, &order_tx, &settle_tx);
+        assert_eq!(rx.try_recv().unwrap().fee, Decimal::ZERO);
+        UserWsFeed::handle_message(&msg.replace("0.52", "1.52"), &tx, &order_tx, &settle_tx);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_parse_order_updates() {
+        let (tx, mut fills) = broadcast::channel(16);
+        let (order_tx, mut rx) = broadcast::channel(16);
+        let (settle_tx, _) = broadcast::channel(16);
+        let order = |kind: &str, matched: &str, status: &str| {
+            let status = if status.is_empty() { String::new() } else { format!(r#","status":"{status}""#) };
+            format!(
+                r#"{{"event_type":"order","type":"{kind}","id":"0xabc","asset_id":"tok","original_size":"10","size_matched":"{matched}"{status}}}"#
+            )
+        };
+
+        let cases = [
+            (order("PLACEMENT", "0", "LIVE"), OrderStatus::Open),
+            (order("UPDATE", "4", ""), OrderStatus::PartiallyFilled),
+            (order("UPDATE", "10", ""), OrderStatus::Filled),
+            (order("UPDATE", "10", "MATCHED"), OrderStatus::Filled),
+            (order("CANCELLATION", "4", ""), OrderStatus::Cancelled),
+            (order("UPDATE", "0", "CANCELED"), OrderStatus::Cancelled),
+        ];
+        for (msg, want) in cases {
+            UserWsFeed::handle_message(&msg, &tx, &order_tx, &settle_tx);
+            let update = rx.try_recv().unwrap();
+            assert_eq!((update.order_id.as_str(), update.token_id.as_str()), ("0xabc", "tok"));
+            assert_eq!(update.status, want, "{msg}");
+        }
+        // A matched order event is not a fill; fills come as trades
+        assert!(fills.try_recv().is_err());
     }
 
     #[test]
     fn test_ignore_non_fill() {
         let (tx, mut rx) = broadcast::channel(16);
 
+        let (order_tx, mut orders) = broadcast::channel(16);
+        let (settle_tx, _) = broadcast::channel(16);
         let msg = r#"{"type": "heartbeat"}"#;
-        UserWsFeed::handle_message(msg, &tx);
+        UserWsFeed::handle_message(msg, &tx, &order_tx, &settle_tx);
 
         assert!(rx.try_recv().is_err());
+        assert!(orders.try_recv().is_err());
+    }
+
+    /// A string field as the wire might garble it.
+    fn wire_field() -> impl Strategy<Value = Option<String>> {
+        prop_oneof![
+            6 => prop::sample::select(vec![
+                "0.52", "10", "0", "-3", "1.5", "NaN", "1e400", "79228162514264337593543950335", "4294967295",
+                "", "0xé漢ü漢ü", "0x123abc", "BUY", "SELL", "TAKER", "MAKER",
+            ])
+            .prop_map(|s| Some(s.to_string())),
+            2 => any::<String>().prop_map(Some),
+            1 => Just(None),
+        ]
+    }
+
+    /// A user channel message: every field any parser reads, each missing,
+    /// garbled or plausible.
+    fn user_msg(
+        kinds: Vec<&'static str>,
+        statuses: Vec<&'static str>,
+    ) -> impl Strategy<Value = serde_json::Value> {
+        let kind = prop::option::of(prop::sample::select(kinds));
+        let status = prop::option::of(prop::sample::select(statuses));
+        (kind.clone(), kind, status, prop::collection::vec(wire_field(), 13)).prop_map(
+            |(event_type, msg_type, status, v)| {
+                let keys = [
+                    "id", "original_size", "size_matched", "order_id", "token_id", "market", "side", "price",
+                    "size", "fee", "fee_rate_bps", "trader_side", "asset_id",
+                ];
+                let mut msg: serde_json::Map<_, _> =
+                    keys.iter().zip(v).filter_map(|(k, v)| Some((k.to_string(), v?.into()))).collect();
+                for (key, value) in [("event_type", event_type), ("type", msg_type), ("status", status)] {
+                    if let Some(value) = value {
+                        msg.insert(key.into(), value.into());
+                    }
+                }
+                msg.into()
+            },
+        )
+    }
+
+    proptest! {
+        /// Garbage, truncated and mistyped user messages never panic the
+        /// feed task, and any fill that comes out is a real one.
+        #[test]
+        fn prop_user_messages_never_panic(
+            msg in user_msg(
+                vec!["trade", "TRADE", "order", "PLACEMENT", "UPDATE", "CANCELLATION"],
+                vec!["MATCHED", "FILLED", "MINED", "CONFIRMED", "RETRYING", "FAILED", "CANCELED", "LIVE", "matched"],
+            ),
+            junk in any::<String>(),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let (tx, mut fills) = broadcast::channel(16);
+            let (order_tx, _orders) = broadcast::channel(16);
+            let (settle_tx, _settlements) = broadcast::channel(16);
+            let text = msg.to_string();
+            let mut n = cut.index(text.len() + 1);
+            while !text.is_char_boundary(n) {
+                n -= 1;
+            }
+            // Log arguments are only evaluated under a subscriber that wants them
+            let subscriber = tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::TRACE)
+                .with_writer(std::io::sink)
+                .finish();
+            tracing::subscriber::with_default(subscriber, || {
+                for text in [junk.as_str(), &text[..n], &text] {
+                    UserWsFeed::handle_message(text, &tx, &order_tx, &settle_tx);
+                }
+            });
+            while let Ok(fill) = fills.try_recv() {
+                prop_assert!(!fill.order_id.is_empty());
+                prop_assert!(fill.size > Decimal::ZERO, "size {}", fill.size);
+                prop_assert!((Decimal::ZERO..=Decimal::ONE).contains(&fill.price), "price {}", fill.price);
+            }
+        }
+
+        /// Message types the feed doesn't handle send nothing anywhere.
+        #[test]
+        fn prop_unknown_user_messages_ignored(
+            msg in user_msg(
+                vec!["price_change", "book", "last_trade_price", "heartbeat", "subscribed", ""],
+                vec!["LIVE", "MINED", "CONFIRMED", "UNKNOWN", ""],
+            ),
+        ) {
+            let (tx, mut fills) = broadcast::channel(16);
+            let (order_tx, mut orders) = broadcast::channel(16);
+            let (settle_tx, mut settlements) = broadcast::channel(16);
+            UserWsFeed::handle_message(&msg.to_string(), &tx, &order_tx, &settle_tx);
+            prop_assert!(fills.try_recv().is_err());
+            prop_assert!(orders.try_recv().is_err());
+            prop_assert!(settlements.try_recv().is_err());
+        }
     }
 }
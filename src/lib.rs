@@ -1,10 +1,16 @@
 #![allow(dead_code)]
 
+pub mod cli;
 pub mod config;
+pub mod error;
 pub mod execution;
 pub mod feeds;
 pub mod models;
+#[cfg(feature = "python")]
+mod python;
 pub mod risk;
 pub mod signals;
+pub mod sim;
 pub mod strategies;
 pub mod telemetry;
+pub mod tui;
@@ -1,36 +1,56 @@
 #![allow(dead_code)]
 
+mod cli;
 mod config;
+mod error;
 mod execution;
 mod feeds;
 mod models;
 mod risk;
 mod signals;
+mod sim;
 mod strategies;
 mod telemetry;
+mod tui;
 
 use crate::config::Config;
 use crate::models::market::Asset;
+use crate::models::order::{taker_fee, FailurePolicy, IntentGroup, OrderIntent, OrderSide};
 use crate::execution::batch_submitter::BatchSubmitter;
 use crate::execution::clob_client::ClobClient;
 use crate::execution::fill_tracker::FillTracker;
 use crate::execution::order_builder::OrderBuilder;
-use crate::feeds::binance::BinanceFeed;
+use crate::execution::polygon_merger::{pending_tx, reverted_gas, GasSpend, PolygonMerger, TxOutcome};
+use crate::feeds::binance::{BinanceFeed, PriceState};
 use crate::feeds::market_discovery::MarketDiscovery;
+use crate::feeds::lifecycle::{MarketEventKind, MarketEvents};
+use crate::feeds::liquidations::LiquidationFeed;
+use crate::feeds::matic_price::MaticPrice;
+use crate::feeds::oracle::OracleFeed;
 use crate::feeds::polymarket::PolymarketFeed;
-use crate::feeds::user_ws::UserWsFeed;
+use crate::feeds::token_balances::TokenBalances;
+use crate::feeds::user_ws::{FillEvent, TradeStatus, UserWsFeed};
+use crate::risk::carry::{CarryBook, RedeemResult};
+use crate::risk::liveness::{VetoChange, WATCHDOG_INTERVAL};
 use crate::risk::position_manager::PositionManager;
+use crate::risk::resolution_guard::ResolutionGuard;
 use crate::risk::risk_manager::RiskManager;
-use crate::strategies::orchestrator::StrategyOrchestrator;
+use crate::risk::safe_mode::{SafeMode, SessionEnd, SessionStore};
+use crate::strategies::orchestrator::{StrategyOrchestrator, LEG_SETS};
+use crate::strategies::pre_position::OpeningQueue;
 use crate::signals::realtime_vol::RealtimeVolTracker;
-use crate::telemetry::alerts::AlertManager;
+use crate::telemetry::alerts::{AlertManager, AlertSeverity};
 use crate::telemetry::latency::LatencyTracker;
+use crate::telemetry::hold_time::ExitReason;
 use crate::telemetry::pnl::PnlTracker;
+use crate::telemetry::state::StateSources;
+use crate::telemetry::supervisor::Supervisor;
 
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tracing::{debug, error, info, warn};
 
 #[tokio::main]
@@ -38,15 +58,18 @@ async fn main() -> anyhow::Result<()> {
     // Load environment variables
     dotenv::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .with_target(false)
-        .with_thread_ids(true)
-        .init();
+    // `sattebaaz tui` runs the bot under the full-screen dashboard, with logs
+    // captured into its log panel instead of stdout
+    let tui_mode = std::env::args().skip(1).any(|a| a == "tui");
+    let log_buffer = tui::terminal::LogBuffer::new(500);
+
+    // Initialize tracing (--log-format json for structured output)
+    let log_format = telemetry::logging::LogFormat::from_args_or_env();
+    if tui_mode {
+        telemetry::logging::init_tracing_with_writer(log_format, "info", log_buffer.clone(), false);
+    } else {
+        telemetry::logging::init_tracing(log_format, "info");
+    }
 
     info!("================================================");
     info!("  SATTEBAAZ — Polymarket Trading Bot v0.1.0");
@@ -56,15 +79,32 @@ async fn main() -> anyhow::Result<()> {
     // Load and validate config (reads .env automatically)
     let config = Config::load_or_default();
 
-    if let Err(e) = config.validate() {
-        error!("Config validation failed: {e}");
-        info!("Running in dry-run / analysis mode...");
+    let config_report = config.diagnose();
+    config_report.log();
+
+    // `sattebaaz probe` measures endpoint latency from this host, then exits
+    if std::env::args().nth(1).as_deref() == Some("probe") {
+        return telemetry::probe::run(&config).await;
+    }
+    // `sattebaaz doctor` checks the wallet, funds, auth and connectivity, then exits
+    if std::env::args().nth(1).as_deref() == Some("doctor") {
+        return telemetry::doctor::run(&config).await;
     }
 
     let dry_run = config.is_dry_run();
+    if config_report.has_errors() {
+        let n = config_report.errors().count();
+        if !dry_run {
+            error!("Refusing to start live trading with {n} config error(s) — fix them or set DRY_RUN=true");
+            anyhow::bail!("invalid config: {n} error(s)");
+        }
+        warn!("Config has {n} error(s) — fix them before trading live");
+    }
     if dry_run {
         warn!("DRY RUN MODE — orders will be signed with random key");
     }
+    let sim_rng = config.sim.root_rng();
+    info!("RNG seed: {} (set SIM_SEED to replay)", sim_rng.seed());
 
     // Starting capital
     let starting_capital = Config::starting_capital();
@@ -81,17 +121,43 @@ async fn main() -> anyhow::Result<()> {
     // Data feeds
     let binance_feed = Arc::new(BinanceFeed::new(config.binance.clone()));
     let polymarket_feed = Arc::new(PolymarketFeed::new(config.polymarket.clone()));
+    let oracle_feed = Arc::new(OracleFeed::new(&config.polymarket.rtds_host));
 
-    // Position management
-    let position_mgr = Arc::new(PositionManager::new(starting_decimal));
+    // Position management; live resolution payouts are carried until redeemed
+    let carry = (config.risk.carry.enabled && !dry_run).then(|| Arc::new(CarryBook::new(&config.risk.carry)));
+    let mut position_mgr = PositionManager::from_config(starting_decimal, &config.risk);
+    if let Some(carry) = &carry {
+        position_mgr = position_mgr.with_carry(carry.clone());
+    }
+    let position_mgr = Arc::new(position_mgr);
+
+    // Session state: start in safe mode if the last run crashed or tripped the kill switch
+    let session_store = Arc::new(SessionStore::new(&config.risk.safe_mode.state_path));
+    let previous_end = match session_store.previous() {
+        Ok(previous) => previous.map(|s| s.state),
+        Err(e) => {
+            warn!("Session state unreadable, treating as a crash: {e}");
+            Some(SessionEnd::Running)
+        }
+    };
+    if let Err(e) = session_store.mark(SessionEnd::Running) {
+        warn!("Session state not recorded: {e}");
+    }
 
     // Risk management
-    let risk_mgr = Arc::new(RiskManager::new(
-        config.risk.clone(),
-        position_mgr.clone(),
+    let risk_mgr = Arc::new(
+        RiskManager::new(config.risk.clone(), position_mgr.clone())
+            .with_safe_mode(SafeMode::from_previous(&config.risk.safe_mode, previous_end)),
+    );
+    let seasonality = Arc::new(crate::signals::seasonality::Seasonality::load(
+        config.strategy.vol_calibration_path.as_deref(),
     ));
+    let resolution_guard = Arc::new(
+        ResolutionGuard::new(config.risk.resolution_guard.clone()).with_seasonality(seasonality.clone()),
+    );
 
-    // Execution
+    // Execution (connect/pre-warm times land in the shared latency tracker)
+    let latency_tracker = Arc::new(LatencyTracker::new(1000));
     let mut order_builder = OrderBuilder::new(
         config.polymarket.chain_id,
         config.polymarket.private_key.clone(),
@@ -100,20 +166,136 @@ async fn main() -> anyhow::Result<()> {
     );
     // All Polymarket up/down markets use the Neg Risk CTF Exchange adapter
     order_builder.set_neg_risk(true);
-    let clob_client = ClobClient::new(config.polymarket.clone());
-    let batch_submitter = Arc::new(BatchSubmitter::new(order_builder, clob_client));
+    // Seeded salts make dry runs replayable; live orders keep unpredictable ones
+    if dry_run {
+        order_builder.set_rng(sim_rng.fork("order_salts"));
+    }
+    let alert_mgr = Arc::new(AlertManager::new(config.telemetry.clone()));
+    info!("Alert sinks: {:?}", alert_mgr.sink_names());
+    let clob_client =
+        ClobClient::with_latency(config.polymarket.clone(), latency_tracker.clone()).with_alerts(alert_mgr.clone());
+    // Market windows follow the CLOB's clock, not this host's
+    crate::feeds::clock::start(ClobClient::new(config.polymarket.clone()), shutdown_tx.subscribe()).await;
+    let batch_submitter = Arc::new(
+        BatchSubmitter::new(order_builder, clob_client)
+            .with_dedup_ttl(std::time::Duration::from_millis(config.risk.intent_dedup_ms))
+            .with_requote_ticks(config.risk.requote_ticks)
+            .with_unwind_slippage(Decimal::from_f64_retain(config.risk.unwind_max_slippage).unwrap_or_default()),
+    );
     let fill_tracker = Arc::new(FillTracker::new());
+    let order_registry = Arc::new(crate::execution::order_registry::OrderRegistry::new());
 
     // Strategy orchestrator
-    let orchestrator = Arc::new(StrategyOrchestrator::new(config.strategy.clone()));
+    let mut orchestrator = StrategyOrchestrator::with_seasonality(config.strategy.clone(), seasonality.clone());
+    match crate::signals::ml_filter::MlFilter::load(&config.strategy.ml_filter, seasonality.clone()) {
+        Ok(Some(filter)) => orchestrator = orchestrator.with_ml_filter(filter),
+        Ok(None) => {}
+        Err(e) => warn!("ML entry filter disabled: {e}"),
+    }
+    let orchestrator = Arc::new(orchestrator);
+
+    // A/B: a shadow orchestrator on the SHADOW_* config, evaluated beside
+    // the live one but never trading
+    let ab = config.telemetry.shadow_ab.then(|| {
+        let shadow = Config::load_shadow();
+        if serde_json::to_value(&shadow.strategy).ok() == serde_json::to_value(&config.strategy).ok() {
+            warn!("SHADOW_AB is on but no SHADOW_* setting changes the strategy config — the arms are identical");
+        } else {
+            info!("A/B shadow orchestrator enabled");
+        }
+        let orchestrator = StrategyOrchestrator::with_seasonality(shadow.strategy, seasonality.clone());
+        Arc::new(telemetry::ab::AbHarness::new(Arc::new(orchestrator), config.risk.intent_dedup_ms))
+    });
 
     // Real-time volatility tracker
     let vol_tracker = Arc::new(RealtimeVolTracker::new());
 
     // Telemetry
-    let latency_tracker = Arc::new(LatencyTracker::new(1000));
     let pnl_tracker = Arc::new(PnlTracker::new(position_mgr.clone()));
-    let alert_mgr = Arc::new(AlertManager::new(config.telemetry.clone()));
+    let tca = Arc::new(telemetry::tca::TcaTracker::new());
+    let markouts = Arc::new(telemetry::markout::MarkoutTracker::new());
+    let observer = config.telemetry.observer_mode.then(|| {
+        warn!("OBSERVER MODE — strategies run but no orders will be submitted");
+        Arc::new(telemetry::observer::OpportunityObserver::new(config.telemetry.observer_gap_ms))
+    });
+    let journal = match &config.telemetry.journal_path {
+        Some(path) => match crate::telemetry::journal::TradeJournal::open(path) {
+            Ok(j) => Some(Arc::new(j)),
+            Err(e) => {
+                warn!("Trade journal disabled: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+    // Past observer sessions give the lag strategy its per-market half-lives
+    if let Some(entries) = journal.as_ref().and_then(|j| j.entries().ok()) {
+        orchestrator.half_life().load(&entries);
+        if let Some(ab) = &ab {
+            ab.shadow().half_life().load(&entries);
+        }
+    }
+    let recorder = match &config.telemetry.recording_path {
+        Some(path) => match crate::telemetry::recorder::MarketRecorder::open(path) {
+            Ok(r) => {
+                info!("Recording market data to {path}");
+                Some(Arc::new(r))
+            }
+            Err(e) => {
+                warn!("Market recording disabled: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+    // Restarts the background loops below if one panics
+    let supervisor = Arc::new(Supervisor::new(alert_mgr.clone(), shutdown_tx.clone()));
+    if let Some(cause) = risk_mgr.safe_mode.status().cause {
+        if config.telemetry.api_addr.is_none() {
+            warn!("Safe mode needs DASHBOARD_API_ADDR to confirm entries; only exits will trade this session");
+        }
+        alert_mgr
+            .send_at(
+                AlertSeverity::Warning,
+                &format!("Safe mode after {cause}: entries held until reconciled and confirmed (POST /safe-mode/confirm)"),
+            )
+            .await;
+    }
+
+    // Large entries wait for an operator (Telegram buttons or the dashboard API)
+    let approvals = (config.risk.approval.min_notional_usdc > 0.0).then(|| {
+        info!(
+            "Entries of ${:.2}+ need operator approval within {}s",
+            config.risk.approval.min_notional_usdc, config.risk.approval.timeout_secs
+        );
+        Arc::new(crate::risk::approval::ApprovalQueue::new(&config.risk.approval))
+    });
+    let approver = match (&approvals, &config.telemetry.telegram_bot_token, &config.telemetry.telegram_chat_id) {
+        (Some(queue), Some(token), Some(chat)) => {
+            let approver = Arc::new(telemetry::approvals::TelegramApprover::new(token, chat, queue.clone()));
+            tokio::spawn(approver.clone().run(shutdown_tx.subscribe()));
+            Some(approver)
+        }
+        _ => None,
+    };
+
+    // MATIC/USD: prices the gas of on-chain transactions for P&L
+    let matic_price = Arc::new(MaticPrice::new(&config.polymarket.matic_price_url, config.polymarket.matic_usd));
+    if !dry_run {
+        matic_price.spawn(shutdown_tx.subscribe());
+    }
+
+    // One view of the whole engine for the dashboards and state snapshots
+    let state_sources = StateSources {
+        poly: polymarket_feed.clone(),
+        binance: binance_feed.clone(),
+        pos_mgr: position_mgr.clone(),
+        tracker: fill_tracker.clone(),
+        risk: risk_mgr.clone(),
+        orchestrator: orchestrator.clone(),
+        approvals: approvals.clone(),
+        matic: (!dry_run).then(|| matic_price.clone()),
+    };
 
     // === Print market discovery info ===
     info!("--- Active market types ---");
@@ -140,6 +322,8 @@ async fn main() -> anyhow::Result<()> {
     info!("  Max exposure:    {}%", config.risk.max_exposure_pct * 100.0);
     info!("  Max daily loss:  {}%", config.risk.max_daily_loss_pct * 100.0);
     info!("  Loss streak cap: {} consecutive", config.risk.loss_streak_threshold);
+    info!("  Strategy buckets: {}", config.risk.buckets.enabled);
+    info!("  Resolution guard: {}", config.risk.resolution_guard.enabled);
 
     // === Initialize CLOB authentication ===
     // Try to derive L2 API key for faster auth on order submissions
@@ -149,11 +333,28 @@ async fn main() -> anyhow::Result<()> {
 
     // === Start data feeds ===
     binance_feed.start(shutdown_tx.subscribe());
+    binance_feed.start_spot(shutdown_tx.subscribe());
     binance_feed.start_funding_poller(shutdown_tx.subscribe());
     info!("Binance feed started (WS + funding poller)");
+    if let Some(recorder) = &recorder {
+        recorder.spawn_ticks(binance_feed.subscribe_prices(), 250, shutdown_tx.subscribe());
+    }
+
+    // Other venues' liquidations feed Binance's cascade counters
+    LiquidationFeed::new(config.liquidations.clone(), binance_feed.liquidations.clone()).start(&shutdown_tx);
 
     polymarket_feed.start(&shutdown_tx);
     info!("Polymarket feed started");
+    // Window milestones of discovered markets; scanning starts once every
+    // consumer has subscribed
+    let market_events = Arc::new(MarketEvents::new());
+    // Next-window opening orders, submitted as their window opens
+    let opening_queue = Arc::new(OpeningQueue::new());
+
+    if config.risk.resolution_guard.enabled {
+        oracle_feed.start(&shutdown_tx);
+        info!("Oracle feed started");
+    }
 
     // Start CLOB user WebSocket for real-time fill events
     let user_ws = UserWsFeed::new(
@@ -165,86 +366,251 @@ async fn main() -> anyhow::Result<()> {
 
     // === Spawn fill consumer (from user WS) ===
     {
-        let mut fill_rx = user_ws.subscribe_fills();
+        let fills = user_ws.subscribe_fills();
         let tracker = fill_tracker.clone();
         let pos_mgr = position_mgr.clone();
         let pnl = pnl_tracker.clone();
-        let mut shutdown_rx = shutdown_tx.subscribe();
+        let journal = journal.clone();
+        let tca = tca.clone();
+        let loops = risk_mgr.loops.clone();
+        let market_stop = risk_mgr.markets.clone();
+        let poly = polymarket_feed.clone();
+        let alerts = alert_mgr.clone();
+        let registry = order_registry.clone();
+        let markouts = markouts.clone();
+        let submitter = batch_submitter.clone();
+        let shutdown = shutdown_tx.clone();
+
+        supervisor.spawn("fill consumer", move || {
+            let mut fill_rx = fills.resubscribe();
+            let (tracker, pos_mgr, pnl, journal, tca) =
+                (tracker.clone(), pos_mgr.clone(), pnl.clone(), journal.clone(), tca.clone());
+            let (loops, market_stop, poly, alerts) = (loops.clone(), market_stop.clone(), poly.clone(), alerts.clone());
+            let (registry, markouts, submitter) = (registry.clone(), markouts.clone(), submitter.clone());
+            let mut shutdown_rx = shutdown.subscribe();
+            async move {
+                loop {
+                    tokio::select! {
+                        event = fill_rx.recv() => {
+                            let event = match event {
+                                Ok(e) => e,
+                                Err(broadcast::error::RecvError::Lagged(n)) => {
+                                    warn!("Fill channel lagged by {n} messages");
+                                    continue;
+                                }
+                                Err(_) => break,
+                            };
+                            // The channel only knows the token and condition id; the
+                            // slug, side and strategy come from when we submitted it
+                            let event = match registry.enrich(&event) {
+                                Some(event) => event,
+                                None if registry.get(&event.order_id).is_some() => {
+                                    debug!("User WS fill for {} already booked", event.order_id);
+                                    continue;
+                                }
+                                // Placed before a restart or by another client on this
+                                // key: still real inventory and cash, so book it in the
+                                // tracker and P&L, just without a market or strategy
+                                None => {
+                                    warn!(
+                                        "User WS fill for unregistered order {}: {:?} {} @ {} on {}, booking unattributed",
+                                        event.order_id, event.side, event.size, event.price, event.token_id
+                                    );
+                                    FillEvent {
+                                        market_id: String::new(),
+                                        strategy_tag: "unattributed".into(),
+                                        ..event
+                                    }
+                                }
+                            };
 
-        tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    event = fill_rx.recv() => {
-                        let event = match event {
-                            Ok(e) => e,
-                            Err(broadcast::error::RecvError::Lagged(n)) => {
-                                warn!("Fill channel lagged by {n} messages");
-                                continue;
+                            // Record in fill tracker
+                            let fill = crate::models::order::Fill {
+                                order_id: event.order_id.clone(),
+                                token_id: event.token_id.clone(),
+                                side: event.side,
+                                price: event.price,
+                                size: event.size,
+                                timestamp: chrono::Utc::now(),
+                                fee: event.fee,
+                            };
+                            tca.on_fill(&fill);
+                            markouts.on_fill(&fill, &event.trade_id, &event.market_id, &event.strategy_tag);
+                            tracker.on_fill(fill.clone());
+                            telemetry::events::Fill {
+                                order_id: &event.order_id,
+                                token_id: &event.token_id,
+                                market: &event.market_id,
+                                side: event.side,
+                                price: event.price,
+                                size: event.size,
+                                fee: event.fee,
+                                strategy: &event.strategy_tag,
+                                source: telemetry::events::FillSource::UserWs,
+                            }
+                            .emit();
+                            if let Some(journal) = &journal {
+                                let group = registry.get(&event.order_id).and_then(|o| o.group);
+                                journal.record(
+                                    &telemetry::journal::JournalEntry::from_fill(&fill, &event.trade_id, &event.market_id, &event.strategy_tag)
+                                        .with_group(group),
+                                );
                             }
-                            Err(_) => break,
-                        };
 
-                        // Record in fill tracker
-                        let fill = crate::models::order::Fill {
-                            order_id: event.order_id.clone(),
-                            token_id: event.token_id.clone(),
-                            side: event.side,
-                            price: event.price,
-                            size: event.size,
-                            timestamp: chrono::Utc::now(),
-                            fee: event.fee,
-                        };
-                        tracker.on_fill(fill.clone());
+                            // Record in position manager
+                            if !event.market_id.is_empty() {
+                                let realized = pos_mgr.record_fill(
+                                    &fill,
+                                    &event.market_id,
+                                    event.market_side,
+                                    &event.strategy_tag,
+                                ).await;
+                                let realized = realized.map(|p| p.to_string().parse::<f64>().unwrap_or(0.0));
+                                if let Some(realized) = realized {
+                                    pnl.record_exit(ExitReason::Strategy, realized);
+                                }
+                                if let (Some(realized), Some(market)) = (realized, poly.get_market(&event.market_id)) {
+                                    if let Some(trip) = market_stop.record_pnl(market.asset, market.duration, realized, fill.timestamp) {
+                                        alerts.send_at(AlertSeverity::Warning, &format!("Market stop: {trip}")).await;
+                                        cancel_series_entries(&poly, market.asset, market.duration, &submitter, &tracker).await;
+                                    }
+                                }
+                                if let Some(trip) = loops.on_fill(
+                                    &event.market_id,
+                                    &event.strategy_tag,
+                                    fill.side,
+                                    fill.size,
+                                    &fill.order_id,
+                                    fill.timestamp,
+                                ) {
+                                    alerts.send_at(AlertSeverity::Warning, &format!("Trade loop: {trip}")).await;
+                                }
+                            }
+
+                            // Track P&L
+                            pnl.record_fill(&event.token_id, event.price, event.size, event.side).await;
+                        }
+                        _ = shutdown_rx.recv() => break,
+                    }
+                }
+            }
+        });
+    }
 
-                        // Record in position manager
-                        if !event.market_id.is_empty() {
-                            pos_mgr.record_fill(
-                                &fill,
-                                &event.market_id,
-                                event.market_side,
-                                &event.strategy_tag,
-                            ).await;
+    // === Spawn settlement consumer (sell proceeds spendable once their trades confirm) ===
+    if config.risk.settlement_hold_secs > 0 {
+        let settlements = user_ws.subscribe_settlements();
+        let pos_mgr = position_mgr.clone();
+        let alerts = alert_mgr.clone();
+        let shutdown = shutdown_tx.clone();
+
+        supervisor.spawn("settlement consumer", move || {
+            let mut settle_rx = settlements.resubscribe();
+            let (pos_mgr, alerts) = (pos_mgr.clone(), alerts.clone());
+            let mut shutdown_rx = shutdown.subscribe();
+            async move {
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            pos_mgr.release_overdue_settlements(chrono::Utc::now()).await;
                         }
+                        settlement = settle_rx.recv() => match settlement {
+                            Ok(s) if s.status == TradeStatus::Confirmed => {
+                                pos_mgr.release_settlement(&s.order_id).await;
+                            }
+                            Ok(s) if s.status == TradeStatus::Failed => {
+                                if let Some(value) = pos_mgr.fail_settlement(&s.order_id).await {
+                                    alerts.send_at(AlertSeverity::Warning, &format!(
+                                        "Trade for order {} failed on-chain — ${value} of sell proceeds never arrived", s.order_id
+                                    )).await;
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("Settlement channel lagged by {n} messages");
+                            }
+                            Err(_) => break,
+                        },
+                        _ = shutdown_rx.recv() => break,
+                    }
+                }
+            }
+        });
+    }
 
-                        // Track P&L
-                        pnl.record_fill(&event.token_id, event.price, event.size, event.side).await;
+    // === Spawn order-status consumer (from user WS) ===
+    // Cancels and matches land in the fill tracker as they happen, so
+    // resting quotes drop out without polling each order.
+    {
+        let updates = user_ws.subscribe_order_updates();
+        let tracker = fill_tracker.clone();
+        let shutdown = shutdown_tx.clone();
+
+        supervisor.spawn("order-status consumer", move || {
+            let mut order_rx = updates.resubscribe();
+            let tracker = tracker.clone();
+            let mut shutdown_rx = shutdown.subscribe();
+            async move {
+                loop {
+                    tokio::select! {
+                        update = order_rx.recv() => match update {
+                            Ok(update) => tracker.on_order_update(&update),
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("Order-status channel lagged by {n} messages");
+                            }
+                            Err(_) => break,
+                        },
+                        _ = shutdown_rx.recv() => break,
                     }
-                    _ = shutdown_rx.recv() => break,
                 }
             }
         });
     }
 
-    // === Spawn risk watchdog (every 500ms) ===
+    // === Spawn risk watchdog (every 500ms; trading stops if it misses two ticks) ===
     {
         let risk = risk_mgr.clone();
         let submitter = batch_submitter.clone();
+        let tracker = fill_tracker.clone();
         let alerts = alert_mgr.clone();
-        let mut shutdown_rx = shutdown_tx.subscribe();
-
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(500));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        let action = risk.periodic_check().await;
-                        match action {
-                            crate::risk::risk_manager::RiskAction::KillSwitch => {
-                                error!("KILL SWITCH — cancelling all orders");
-                                let _ = submitter.cancel_all().await;
-                                alerts.send("KILL SWITCH activated").await;
-                            }
-                            crate::risk::risk_manager::RiskAction::Pause(secs) => {
-                                warn!("Risk pause for {secs}s");
-                                alerts.send(&format!("Risk pause for {secs}s")).await;
-                            }
-                            crate::risk::risk_manager::RiskAction::ReduceSize(mult) => {
-                                warn!("Size reduction active: {mult}x");
+        let store = session_store.clone();
+        let shutdown = shutdown_tx.clone();
+
+        risk_mgr.watchdog.start(crate::feeds::clock::now());
+        supervisor.spawn("risk watchdog", move || {
+            let (risk, submitter, tracker, alerts, store) =
+                (risk.clone(), submitter.clone(), tracker.clone(), alerts.clone(), store.clone());
+            let mut shutdown_rx = shutdown.subscribe();
+            async move {
+                let mut interval = tokio::time::interval(WATCHDOG_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            let action = risk.periodic_check().await;
+                            match action {
+                                crate::risk::risk_manager::RiskAction::KillSwitch => {
+                                    error!("KILL SWITCH — cancelling all orders");
+                                    if let Err(e) = store.mark(SessionEnd::KillSwitch) {
+                                        warn!("Session state not recorded: {e}");
+                                    }
+                                    if submitter.cancel_all().await.is_ok() {
+                                        tracker.clear_quotes();
+                                    }
+                                    alerts.send_at(AlertSeverity::Critical, "KILL SWITCH activated").await;
+                                }
+                                crate::risk::risk_manager::RiskAction::Pause(secs) => {
+                                    warn!("Risk pause for {secs}s");
+                                    alerts.send_at(AlertSeverity::Warning, &format!("Risk pause for {secs}s")).await;
+                                }
+                                crate::risk::risk_manager::RiskAction::ReduceSize(mult) => {
+                                    warn!("Size reduction active: {mult}x");
+                                }
+                                crate::risk::risk_manager::RiskAction::Continue => {}
                             }
-                            crate::risk::risk_manager::RiskAction::Continue => {}
                         }
+                        _ = shutdown_rx.recv() => break,
                     }
-                    _ = shutdown_rx.recv() => break,
                 }
             }
         });
@@ -254,7 +620,18 @@ async fn main() -> anyhow::Result<()> {
     {
         let pnl = pnl_tracker.clone();
         let latency = latency_tracker.clone();
+        let competition = orchestrator.competition();
+        let allocator = orchestrator.allocator();
+        let strategy_latency = orchestrator.strategy_latency();
+        let orch = orchestrator.clone();
+        let submitter = batch_submitter.clone();
+        let pos_mgr = position_mgr.clone();
+        let gamma = polymarket_feed.gamma_cache();
         let binance = binance_feed.clone();
+        let poly = polymarket_feed.clone();
+        let ab = ab.clone();
+        let state = state_sources.clone();
+        let state_path = config.telemetry.state_snapshot_path.clone();
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         tokio::spawn(async move {
@@ -264,8 +641,30 @@ async fn main() -> anyhow::Result<()> {
                     _ = interval.tick() => {
                         pnl.log_summary().await;
                         latency.log_summary();
+                        submitter.log_connection_summary();
+                        submitter.log_rejection_summary();
+                        competition.log_summary(chrono::Utc::now().timestamp_millis());
+                        allocator.log_summary();
+                        strategy_latency.log_summary();
+                        orch.screen().log_summary();
+                        orch.seasonality().log_summary(chrono::Utc::now());
+                        pos_mgr.log_bucket_summary().await;
+                        pos_mgr.log_entry_cohorts().await;
+                        if let Some(canary) = pos_mgr.canary() {
+                            canary.log_summary();
+                        }
+                        gamma.log_summary();
+                        if let Some(ab) = &ab {
+                            ab.report(|token| poly.get_book(token).and_then(|b| b.midpoint()).and_then(|m| m.to_f64()))
+                                .log_summary();
+                        }
+                        if let Some(path) = &state_path {
+                            if let Err(e) = state.save(std::path::Path::new(path)).await {
+                                warn!("Engine state not saved: {e:#}");
+                            }
+                        }
                         // Decay liquidation counters
-                        binance.reset_liquidations().await;
+                        binance.reset_liquidations();
                     }
                     _ = shutdown_rx.recv() => break,
                 }
@@ -277,24 +676,59 @@ async fn main() -> anyhow::Result<()> {
     {
         let submitter = batch_submitter.clone();
         let pos_mgr = position_mgr.clone();
-        let _alerts = alert_mgr.clone();
+        let safe_mode = risk_mgr.safe_mode.clone();
+        let shutdown = shutdown_tx.clone();
+
+        supervisor.spawn("balance sync", move || {
+            let (submitter, pos_mgr, safe_mode) = (submitter.clone(), pos_mgr.clone(), safe_mode.clone());
+            let mut shutdown_rx = shutdown.subscribe();
+            async move {
+                // Wait 5s for auth to initialize before first balance fetch
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15));
+
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            match submitter.fetch_balance().await {
+                                Ok(balance) => {
+                                    pos_mgr.sync_capital_from_balance(balance).await;
+                                    // Safe mode: clear whatever the dead session left resting
+                                    if safe_mode.awaiting_reconciliation() {
+                                        match submitter.cancel_all().await {
+                                            Ok(()) => {
+                                                info!("Safe mode reconciliation done: balance ${balance}, stale orders cancelled");
+                                                safe_mode.mark_reconciled();
+                                            }
+                                            Err(e) => warn!("Safe mode reconciliation failed, retrying: {e}"),
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    debug!("Balance fetch failed: {e}");
+                                }
+                            }
+                        }
+                        _ = shutdown_rx.recv() => break,
+                    }
+                }
+            }
+        });
+    }
+
+    // === Spawn order-host connection pre-warm (keeps TCP + TLS up between orders) ===
+    if config.polymarket.prewarm_secs > 0 {
+        let submitter = batch_submitter.clone();
+        let period = tokio::time::Duration::from_secs(config.polymarket.prewarm_secs);
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         tokio::spawn(async move {
-            // Wait 5s for auth to initialize before first balance fetch
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15));
-
+            let mut interval = tokio::time::interval(period);
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        match submitter.fetch_balance().await {
-                            Ok(balance) => {
-                                pos_mgr.sync_capital_from_balance(balance).await;
-                            }
-                            Err(e) => {
-                                debug!("Balance fetch failed: {e}");
-                            }
+                        if let Err(e) = submitter.prewarm().await {
+                            debug!("CLOB pre-warm failed: {e}");
                         }
                     }
                     _ = shutdown_rx.recv() => break,
@@ -303,101 +737,576 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
-    // === Spawn strategy execution loop (driven by price updates) ===
-    {
-        let mut price_rx = binance_feed.subscribe_prices();
-        let orch = orchestrator.clone();
-        let binance = binance_feed.clone();
-        let poly = polymarket_feed.clone();
-        let risk = risk_mgr.clone();
-        let submitter = batch_submitter.clone();
-        let tracker = fill_tracker.clone();
+    // === Spawn strategy bucket rebalancer (if configured) ===
+    if config.risk.buckets.enabled && config.risk.buckets.rebalance_interval_secs > 0 {
         let pos_mgr = position_mgr.clone();
-        let latency = latency_tracker.clone();
-        let alerts = alert_mgr.clone();
-        let vol = vol_tracker.clone();
+        let interval_secs = config.risk.buckets.rebalance_interval_secs;
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         tokio::spawn(async move {
-            // Throttle: evaluate at most once per 200ms per asset
-            let mut last_eval: HashMap<Asset, tokio::time::Instant> =
-                HashMap::new();
-            let eval_cooldown = tokio::time::Duration::from_millis(200);
-
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+            interval.tick().await; // skip immediate first tick
             loop {
                 tokio::select! {
-                    price_update = price_rx.recv() => {
-                        let (asset, binance_price) = match price_update {
-                            Ok(p) => p,
-                            Err(broadcast::error::RecvError::Lagged(n)) => {
-                                warn!("Price channel lagged by {n} messages");
+                    _ = interval.tick() => {
+                        pos_mgr.rebalance_buckets().await;
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    // === Spawn profit sweep loop (sweep policy + cold wallet configured) ===
+    let compounding = config.risk.compounding.clone();
+    if compounding.mode == crate::config::CompoundingMode::Sweep && !dry_run {
+        if let Some(sweep_address) = compounding.sweep_address.clone() {
+            let pos_mgr = position_mgr.clone();
+            let alerts = alert_mgr.clone();
+            let journal = journal.clone();
+            let matic_price = matic_price.clone();
+            let private_key = config.polymarket.private_key.clone();
+            let polygon_rpc_url = config.polymarket.polygon_rpc_url.clone();
+            let interval_secs = compounding.sweep_interval_secs.max(60);
+            let mut shutdown_rx = shutdown_tx.subscribe();
+            info!("Profit sweep active → {sweep_address}");
+
+            tokio::spawn(async move {
+                let merger = match PolygonMerger::from_private_key(&polygon_rpc_url, &private_key) {
+                    Ok(m) => m.with_matic_price(matic_price),
+                    Err(e) => {
+                        error!("Profit sweep disabled — wallet init failed: {e}");
+                        return;
+                    }
+                };
+
+                // The sweep is booked before it is sent and handed back only once
+                // it is known not to have happened; one whose receipt never came
+                // in is looked up again instead of being sent a second time.
+                let mut in_flight: Option<(f64, String)> = None;
+                let mut interval =
+                    tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            if let Some((amount, tx_hash)) = in_flight.clone() {
+                                match merger.tx_outcome(&tx_hash, "Transfer").await {
+                                    Ok(TxOutcome::Confirmed(tx)) => {
+                                        charge_gas(&pos_mgr, journal.as_deref(), "sweep", "", &tx.gas).await;
+                                        let total = pos_mgr.swept_total().await;
+                                        alerts.send(&format!(
+                                            "Swept ${amount:.2} profit to cold wallet (total ${total}) tx={}", tx.hash
+                                        )).await;
+                                    }
+                                    Ok(TxOutcome::Reverted(gas)) => {
+                                        pos_mgr.restore_sweep(amount).await;
+                                        charge_gas(&pos_mgr, journal.as_deref(), "sweep", "", &gas).await;
+                                        alerts.send_at(AlertSeverity::Warning, &format!(
+                                            "Profit sweep of ${amount:.2} reverted: tx={tx_hash}"
+                                        )).await;
+                                    }
+                                    Ok(TxOutcome::Dropped) => {
+                                        pos_mgr.restore_sweep(amount).await;
+                                        warn!("Profit sweep tx {tx_hash} was dropped — ${amount:.2} back in capital");
+                                    }
+                                    Ok(TxOutcome::Pending) => continue,
+                                    Err(e) => {
+                                        warn!("Profit sweep tx {tx_hash} lookup failed: {e}");
+                                        continue;
+                                    }
+                                }
+                                in_flight = None;
                                 continue;
                             }
-                            Err(_) => break,
-                        };
-
-                        // Feed price to vol tracker
-                        let now_ms = chrono::Utc::now().timestamp_millis();
-                        vol.on_price(asset, binance_price, now_ms).await;
 
-                        // Throttle per-asset
-                        let now = tokio::time::Instant::now();
-                        if let Some(last) = last_eval.get(&asset) {
-                            if now.duration_since(*last) < eval_cooldown {
+                            let amount = pos_mgr.sweepable_amount().await;
+                            if amount <= 0.0 {
                                 continue;
                             }
+                            pos_mgr.record_sweep(amount).await;
+                            match merger.transfer_usdc(&sweep_address, amount).await {
+                                Ok(tx) => {
+                                    charge_gas(&pos_mgr, journal.as_deref(), "sweep", "", &tx.gas).await;
+                                    let total = pos_mgr.swept_total().await;
+                                    alerts.send(&format!(
+                                        "Swept ${amount:.2} profit to cold wallet (total ${total}) tx={}", tx.hash
+                                    )).await;
+                                }
+                                Err(e) => {
+                                    if let Some(tx_hash) = pending_tx(&e) {
+                                        warn!("Profit sweep of ${amount:.2} unconfirmed — reconciling {tx_hash} next tick");
+                                        in_flight = Some((amount, tx_hash.to_string()));
+                                        continue;
+                                    }
+                                    pos_mgr.restore_sweep(amount).await;
+                                    if let Some(gas) = reverted_gas(&e) {
+                                        charge_gas(&pos_mgr, journal.as_deref(), "sweep", "", gas).await;
+                                    }
+                                    error!("Profit sweep of ${amount:.2} failed: {e}");
+                                    alerts.send_at(AlertSeverity::Warning, &format!("Profit sweep failed: {e}")).await;
+                                }
+                            }
                         }
-                        last_eval.insert(asset, now);
+                        _ = shutdown_rx.recv() => break,
+                    }
+                }
+            });
+        } else {
+            warn!("COMPOUNDING_MODE=sweep without SWEEP_ADDRESS — trading capped at watermark, nothing transferred");
+        }
+    }
 
-                        // Skip if kill switch active
-                        if risk.killed.load(std::sync::atomic::Ordering::Relaxed) {
-                            continue;
+    // === Spawn carry redeemer (resolution payouts redeemed on-chain) ===
+    if let Some(carry) = &carry {
+        match PolygonMerger::from_private_key(&config.polymarket.polygon_rpc_url, &config.polymarket.private_key) {
+            Ok(merger) => {
+                let mut results = carry.spawn_redeemer(merger.with_matic_price(matic_price.clone()), shutdown_tx.subscribe());
+                let pos_mgr = position_mgr.clone();
+                let alerts = alert_mgr.clone();
+                let journal = journal.clone();
+                info!("Carry active — {} resolved markets pending redemption", carry.pending().len());
+
+                tokio::spawn(async move {
+                    while let Some(result) = results.recv().await {
+                        match result {
+                            RedeemResult::Paid { redemption, tx } => {
+                                pos_mgr.record_redeemed(&redemption).await;
+                                charge_gas(&pos_mgr, journal.as_deref(), "carry", &redemption.market, &tx.gas).await;
+                                alerts.send(&format!(
+                                    "Redeemed {}: +${} tx={}", redemption.market, redemption.shares, tx.hash
+                                )).await;
+                            }
+                            RedeemResult::Reverted { market, gas } => {
+                                charge_gas(&pos_mgr, journal.as_deref(), "carry", &market, &gas).await;
+                            }
+                            RedeemResult::Abandoned(p) => {
+                                alerts.send_at(AlertSeverity::Warning, &format!(
+                                    "Redeem of {} gave up after {} attempts — ${} to redeem by hand ({})",
+                                    p.market, p.attempts, p.shares, p.last_error.as_deref().unwrap_or("?")
+                                )).await;
+                            }
                         }
+                    }
+                });
+            }
+            Err(e) => error!("Carry redeemer disabled — wallet init failed: {e}; payouts stay pending"),
+        }
+    }
 
-                        // Get market types for this asset
-                        let market_types: Vec<_> = MarketDiscovery::all_market_types()
-                            .into_iter()
-                            .filter(|(a, _)| *a == asset)
-                            .collect();
+    // === Spawn token balance poller + inventory reconciler (ground truth from Polygon) ===
+    let token_balances = if config.polymarket.token_balance_secs > 0 && !dry_run {
+        let holder = batch_submitter.token_holder().await;
+        match TokenBalances::new(&config.polymarket.polygon_rpc_url, &holder) {
+            Ok(feed) => Some(Arc::new(feed)),
+            Err(e) => {
+                error!("Token balance feed disabled: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(balances) = &token_balances {
+        let mut polled = balances.spawn(
+            polymarket_feed.markets.clone(),
+            std::time::Duration::from_secs(config.polymarket.token_balance_secs),
+            shutdown_tx.subscribe(),
+        );
+        let balances = balances.clone();
+        let markets = polymarket_feed.markets.clone();
+        let pos_mgr = position_mgr.clone();
+        let alerts = alert_mgr.clone();
+        info!("Token balances polled every {}s", config.polymarket.token_balance_secs);
 
-                        let available_capital = pos_mgr.available_capital().await;
+        tokio::spawn(async move {
+            while let Some(at) = polled.recv().await {
+                let booked = pos_mgr.token_holdings(&markets).await;
+                for m in balances.reconcile(&markets, &booked, at) {
+                    warn!("Inventory mismatch: {}", m.describe());
+                    alerts.send_at(AlertSeverity::Warning, &format!("Inventory mismatch: {}", m.describe())).await;
+                }
+            }
+        });
+    }
 
-                        for (_asset, duration) in &market_types {
-                            let slug = MarketDiscovery::current_slug(asset, *duration);
-                            let remaining = MarketDiscovery::time_remaining_in_current(*duration);
+    // === Spawn heartbeat (alive alerts + dead-man's-switch ping) ===
+    let heartbeat = crate::telemetry::heartbeat::Heartbeat::new(&config.telemetry);
+    if heartbeat.is_enabled() {
+        let pos_mgr = position_mgr.clone();
+        let alerts = alert_mgr.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+        info!(
+            "Heartbeat active: healthcheck={} alert_every={:?}",
+            heartbeat.has_healthcheck(),
+            heartbeat.alert_interval
+        );
 
-                            // Skip if too close to resolution
-                            if remaining < 10.0 {
-                                continue;
-                            }
+        tokio::spawn(async move {
+            let mut ping = tokio::time::interval(heartbeat.ping_interval);
+            // Skip the immediate first tick — no point announcing we're alive at startup
+            let alert_every = heartbeat.alert_interval.unwrap_or(tokio::time::Duration::from_secs(86_400));
+            let mut alert = tokio::time::interval_at(tokio::time::Instant::now() + alert_every, alert_every);
+            loop {
+                tokio::select! {
+                    _ = ping.tick(), if heartbeat.has_healthcheck() => {
+                        if let Err(e) = heartbeat.ping().await {
+                            warn!("Healthcheck ping failed: {e}");
+                        }
+                    }
+                    _ = alert.tick(), if heartbeat.alert_interval.is_some() => {
+                        let capital = pos_mgr.available_capital().await;
+                        let open = {
+                            let portfolio = pos_mgr.portfolio.read().await;
+                            portfolio.positions.len() + portfolio.straddles.len()
+                        };
+                        alerts.send(&heartbeat.summary(capital, open)).await;
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
 
-                            // Look up market from Polymarket feed cache
-                            let mut market = match poly.get_market(&slug) {
-                                Some(m) => m,
-                                None => continue, // Not yet discovered
-                            };
+    // === Spawn orphaned-order sweeper ===
+    if config.risk.orphan_sweep_secs > 0 {
+        let sweeper = crate::execution::order_sweeper::OrderSweeper::new(config.risk.orphan_min_age_secs);
+        let submitter = batch_submitter.clone();
+        let tracker = fill_tracker.clone();
+        let alerts = alert_mgr.clone();
+        let period = tokio::time::Duration::from_secs(config.risk.orphan_sweep_secs);
+        let mut shutdown_rx = shutdown_tx.subscribe();
 
-                            // Set reference price from Binance on first tick
-                            market.set_reference_price(binance_price);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        match sweeper.sweep(&submitter, &tracker).await {
+                            Ok(cancelled) if !cancelled.is_empty() => {
+                                let ids: Vec<&str> = cancelled.iter().map(|o| o.id.as_str()).collect();
+                                alerts.send_at(
+                                    AlertSeverity::Warning,
+                                    &format!("🧹 Cancelled {} orphaned orders: {}", cancelled.len(), ids.join(", ")),
+                                ).await;
+                            }
+                            Ok(_) => {}
+                            Err(e) => warn!("Order sweep failed: {e}"),
+                        }
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
 
-                            // Get order books
-                            let yes_book = match poly.get_book(&market.yes_token_id) {
-                                Some(b) => b,
-                                None => continue,
-                            };
-                            let no_book = match poly.get_book(&market.no_token_id) {
-                                Some(b) => b,
-                                None => continue,
-                            };
+    // === Spawn dashboard API (book depth with our quotes) ===
+    if let Some(addr) = config.telemetry.api_addr.clone() {
+        let api = crate::telemetry::api::DashboardApi::new(polymarket_feed.clone(), fill_tracker.clone())
+            .with_safe_mode(risk_mgr.safe_mode.clone())
+            .with_engine_state(state_sources.clone());
+        let api = match &approvals {
+            Some(queue) => api.with_approvals(queue.clone()),
+            None => api,
+        };
+        let shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            if let Err(e) = api.serve(&addr, shutdown_rx).await {
+                error!("Dashboard API on {addr} failed: {e}");
+            }
+        });
+    }
 
-                            // Compute signals
+    // === Spawn gRPC event stream (market data, signals, orders, fills, P&L) ===
+    let telemetry_hub = config.telemetry.grpc_addr.clone().map(|addr| {
+        let hub = crate::telemetry::grpc::TelemetryHub::new();
+        use crate::telemetry::grpc::{book_top, fill, order_update, price_tick};
+        hub.forward(binance_feed.subscribe_prices(), shutdown_tx.subscribe(), price_tick);
+        hub.forward(polymarket_feed.subscribe_book_updates(), shutdown_tx.subscribe(), book_top);
+        hub.forward(user_ws.subscribe_order_updates(), shutdown_tx.subscribe(), order_update);
+        hub.forward(user_ws.subscribe_fills(), shutdown_tx.subscribe(), fill);
+        hub.publish_pnl_every(position_mgr.clone(), std::time::Duration::from_secs(1), shutdown_tx.subscribe());
+
+        let server = hub.clone();
+        let shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            if let Err(e) = server.serve(&addr, shutdown_rx).await {
+                error!("Telemetry gRPC on {addr} failed: {e}");
+            }
+        });
+        hub
+    });
+
+    // === Spawn competitor watcher (book reactions to our resting quotes) ===
+    {
+        let mut book_rx = polymarket_feed.subscribe_book_updates();
+        let competition = orchestrator.competition();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = book_rx.recv() => {
+                        let diff = match update {
+                            Ok(d) => d,
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(_) => break,
+                        };
+                        competition.on_book_update(&diff);
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    // === Readiness barrier: only enable strategies once feeds are warm ===
+    let readiness = crate::feeds::readiness::ReadinessBarrier::new(MarketDiscovery::all_market_types())
+        .wait(
+            &polymarket_feed,
+            &binance_feed,
+            std::time::Duration::from_secs(30),
+            |r| info!("Warming up: {}", r.summary()),
+        )
+        .await;
+    if readiness.is_ready() {
+        info!("Feeds ready — enabling strategy loop");
+    } else {
+        warn!(
+            "Feeds not fully ready after 30s — starting anyway. Missing: {}",
+            readiness.missing.join(", ")
+        );
+    }
+
+    // === Spawn vol feeder: realized vol and price projection need every print ===
+    {
+        let mut price_rx = binance_feed.subscribe_prices();
+        let vol = vol_tracker.clone();
+        let orch = orchestrator.clone();
+        let ab = ab.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = price_rx.recv() => match update {
+                        Ok((asset, price)) => {
+                            let now = chrono::Utc::now();
+                            orch.observe_price(asset, price, now);
+                            if let Some(ab) = &ab {
+                                ab.shadow().observe_price(asset, price, now);
+                            }
+                            vol.on_price(asset, price, now.timestamp_millis()).await;
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Price channel lagged by {n} messages");
+                        }
+                        Err(_) => break,
+                    },
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    // === Spawn spot feeder: the perp-spot basis needs spot prints too ===
+    {
+        let mut spot_rx = binance_feed.subscribe_spot_prices();
+        let orch = orchestrator.clone();
+        let ab = ab.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = spot_rx.recv() => match update {
+                        Ok((asset, price)) => {
+                            let now = chrono::Utc::now();
+                            orch.observe_spot(asset, price, now);
+                            if let Some(ab) = &ab {
+                                ab.shadow().observe_spot(asset, price, now);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Spot price channel lagged by {n} messages");
+                        }
+                        Err(_) => break,
+                    },
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    // === Spawn strategy execution loop (driven by price updates) ===
+    // Evaluations only need the freshest price, so the loop waits on the
+    // per-asset latest-value channels rather than the every-print broadcast.
+    {
+        let mut price_watches: Vec<_> = Asset::ALL.iter().map(|&a| binance_feed.watch_price(a)).collect();
+        let orch = orchestrator.clone();
+        let binance = binance_feed.clone();
+        let poly = polymarket_feed.clone();
+        let risk = risk_mgr.clone();
+        let guard = resolution_guard.clone();
+        let oracle = oracle_feed.clone();
+        let submitter = batch_submitter.clone();
+        let tracker = fill_tracker.clone();
+        let pos_mgr = position_mgr.clone();
+        let latency = latency_tracker.clone();
+        let alerts = alert_mgr.clone();
+        let vol = vol_tracker.clone();
+        let journal = journal.clone();
+        let tca = tca.clone();
+        let markouts = markouts.clone();
+        let observer = observer.clone();
+        let ab = ab.clone();
+        let approvals = approvals.clone();
+        let approver = approver.clone();
+        let registry = order_registry.clone();
+        let pnl_tracker = pnl_tracker.clone();
+        let net_resting = config.risk.net_resting_orders;
+        let scale_in = config.strategy.scale_in.clone();
+        let pre_position = config.strategy.pre_position.clone();
+        let opening = opening_queue.clone();
+        let price_series = config.strategy.price_series.clone();
+        let eval_throttle = config.strategy.eval_throttle.clone();
+        let telemetry_hub = telemetry_hub.clone();
+        let recorder = recorder.clone();
+        let token_balances = token_balances.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            // Throttle: evaluate each asset at most once per its cooldown
+            let mut last_eval: HashMap<Asset, tokio::time::Instant> =
+                HashMap::new();
+
+            loop {
+                tokio::select! {
+                    changed = next_price_change(&mut price_watches) => {
+                        let Some(i) = changed else { break };
+                        let asset = Asset::ALL[i];
+                        let Some(binance_price) = price_watches[i].borrow_and_update().map(|s| s.price) else {
+                            continue;
+                        };
+
+                        // Throttle per-asset, by vol regime and the nearest resolution
+                        let now = tokio::time::Instant::now();
+                        if let Some(last) = last_eval.get(&asset) {
+                            let remaining = MarketDiscovery::all_market_types()
+                                .into_iter()
+                                .filter(|(a, _)| *a == asset)
+                                .map(|(_, d)| MarketDiscovery::time_remaining_in_current(d))
+                                .fold(f64::INFINITY, f64::min);
+                            if now.duration_since(*last) < eval_throttle.cooldown(vol.regime(asset).await, remaining) {
+                                continue;
+                            }
+                        }
+                        last_eval.insert(asset, now);
+
+                        // Skip if kill switch active
+                        if risk.killed.load(std::sync::atomic::Ordering::Relaxed) {
+                            continue;
+                        }
+
+                        // No new entries while the risk watchdog isn't checking in
+                        let clock_now = crate::feeds::clock::now();
+                        match risk.watchdog.poll(clock_now) {
+                            Some(VetoChange::Vetoed(age)) => {
+                                error!("Risk watchdog silent for {age}ms — refusing new entries until it recovers");
+                                alerts.send_at(
+                                    AlertSeverity::Critical,
+                                    &format!("Risk watchdog silent for {age}ms — entries halted until it recovers"),
+                                ).await;
+                            }
+                            Some(VetoChange::Recovered) => {
+                                info!("Risk watchdog recovered — trading resumed");
+                                alerts.send("Risk watchdog recovered — trading resumed").await;
+                            }
+                            None => {}
+                        }
+                        let exits_only = risk.watchdog.stale_ms(clock_now).is_some();
+
+                        // Get market types for this asset, best opportunity first
+                        // (only the overall top few in safe mode)
+                        let market_types: Vec<_> = orch
+                            .allocator()
+                            .priority()
+                            .into_iter()
+                            .take(risk.safe_mode.max_markets().unwrap_or(usize::MAX))
+                            .filter(|(a, _)| *a == asset)
+                            .collect();
+
+                        let available_capital = pos_mgr.available_capital().await;
+
+                        for (_asset, duration) in &market_types {
+                            // Series that lost their session allowance take no new entries
+                            let stopped = risk.markets.is_stopped(asset, *duration, chrono::Utc::now());
+                            let slug = MarketDiscovery::current_slug(asset, *duration);
+                            let remaining = MarketDiscovery::time_remaining_in_current(*duration);
+
+                            // Price the next window's opening orders before it opens
+                            if !stopped && pre_position.enabled && observer.is_none() && remaining <= pre_position.lead_secs {
+                                let next_slug = MarketDiscovery::next_slug(asset, *duration);
+                                if let (false, Some(next)) = (opening.is_planned(&next_slug), poly.get_market(&next_slug)) {
+                                    let orders = orch.opening_orders(&next, vol.regime(asset).await, available_capital);
+                                    opening.queue(&next, orders, chrono::Utc::now());
+                                }
+                            }
+
+                            // Skip if too close to resolution
+                            if remaining < 10.0 {
+                                continue;
+                            }
+
+                            // Look up market from Polymarket feed cache
+                            let market = match poly.get_market(&slug) {
+                                Some(m) => m,
+                                None => continue, // Not yet discovered
+                            };
+
+                            // Fair value runs off the series chosen for this market type
+                            let series = price_series.for_market(asset, *duration);
+                            let binance_price = binance.get_price_for(asset, series).await.unwrap_or(binance_price);
+
+                            // The reference is captured when the market opens; until
+                            // then there's nothing to price against
+                            if market.reference_price == 0.0 {
+                                continue;
+                            }
+
+                            // Get order books
+                            let yes_book = match poly.get_book(&market.yes_token_id) {
+                                Some(b) => b,
+                                None => continue,
+                            };
+                            let no_book = match poly.get_book(&market.no_token_id) {
+                                Some(b) => b,
+                                None => continue,
+                            };
+                            if let Some(recorder) = &recorder {
+                                recorder.record_books(&market, &yes_book, &no_book);
+                            }
+
+                            // Concentrate REST refreshes on markets we're actually trading
+                            let has_position = pos_mgr.position_count(&slug).await > 0;
+                            let tier = orch.allocator().refresh_tier(asset, *duration, &yes_book, &no_book, has_position);
+                            poly.set_refresh_tier(&market.yes_token_id, tier);
+                            poly.set_refresh_tier(&market.no_token_id, tier);
+
+                            // Compute signals
                             let vol_regime = vol.regime(asset).await;
-                            let move_1s = binance.get_1s_move_pct(asset).await;
-                            let net_liqs = binance.get_net_liquidations(asset).await;
+                            let move_1s = binance.get_1s_move_pct(asset);
+                            let net_liqs = binance.get_net_liquidations(asset);
                             let funding = binance.get_funding_rate(asset).await;
                             let liq_active = net_liqs.abs() > 100_000.0;
                             let inventory = pos_mgr.net_yes_inventory(&slug).await;
 
+                            if let Some(o) = oracle.get_price(asset) {
+                                orch.observe_cross_exchange(asset, o.price, o.timestamp);
+                                if let Some(ab) = &ab {
+                                    ab.shadow().observe_cross_exchange(asset, o.price, o.timestamp);
+                                }
+                            }
+
                             // Evaluate all strategies via orchestrator
                             let orders = orch.evaluate(
                                 &market,
@@ -415,17 +1324,109 @@ async fn main() -> anyhow::Result<()> {
                                 liq_active,
                             );
 
+                            // Near close, don't bet against the price the market resolves on
+                            let orders = guard.filter(
+                                &market,
+                                &yes_book,
+                                &no_book,
+                                binance_price,
+                                oracle.get_price(asset),
+                                orders,
+                            );
+                            let mut orders = orders;
+                            if exits_only || stopped {
+                                orders.retain(|o| o.order_side == OrderSide::Sell);
+                            }
+
+                            // A/B: the shadow config on the same inputs, both arms
+                            // scored as hypothetical taker fills
+                            if let Some(ab) = &ab {
+                                let shadow_orders = ab.shadow().evaluate(
+                                    &market,
+                                    &yes_book,
+                                    &no_book,
+                                    vol_regime,
+                                    available_capital,
+                                    binance_price,
+                                    None,
+                                    None,
+                                    None,
+                                    inventory,
+                                    move_1s,
+                                    funding,
+                                    liq_active,
+                                );
+                                let shadow_orders = guard.filter(
+                                    &market,
+                                    &yes_book,
+                                    &no_book,
+                                    binance_price,
+                                    oracle.get_price(asset),
+                                    shadow_orders,
+                                );
+                                let now = chrono::Utc::now();
+                                ab.record(telemetry::ab::Arm::Live, &market, &yes_book, &no_book, &orders, now);
+                                for line in ab.record(telemetry::ab::Arm::Shadow, &market, &yes_book, &no_book, &shadow_orders, now) {
+                                    if let Some(journal) = &journal {
+                                        journal.record(&line);
+                                    }
+                                }
+                            }
+
+                            // Buys into positions we already hold are capped add-ons
+                            let orders = pos_mgr.apply_scale_in(&slug, &scale_in, orders).await;
+
+                            // Observer mode: note what we would have sent, including
+                            // that it's gone when it stops showing up
+                            if let Some(observer) = &observer {
+                                let fair_up = orch.fair_value(&slug).map(|e| e.prob_up);
+                                for line in observer.observe(&market, &yes_book, &no_book, fair_up, &orders, chrono::Utc::now()) {
+                                    orch.half_life().record(&line);
+                                    if let Some(journal) = &journal {
+                                        journal.record(&line);
+                                    }
+                                }
+                            }
+
+                            // Never sell more than the wallet holds on-chain
+                            let mut orders = orders;
+                            if let Some(balances) = &token_balances {
+                                balances.cap_sells(&mut orders);
+                            }
+
                             if orders.is_empty() {
                                 continue;
                             }
+                            if let Some(hub) = &telemetry_hub {
+                                hub.publish_signals(&slug, &orders);
+                            }
+                            if let Some(recorder) = &recorder {
+                                recorder.record_signals(&slug, &orders);
+                            }
+                            if observer.is_some() {
+                                continue;
+                            }
 
-                            // Risk-check each order
+                            // Risk-check each trade; the legs of an all-or-nothing one pass or fail together
                             let mut approved_orders = Vec::new();
-                            for order in &orders {
-                                match risk.check_order(order).await {
-                                    Ok(()) => approved_orders.push(order.clone()),
-                                    Err(e) => {
-                                        debug!("Order rejected by risk: {e}");
+                            for group in IntentGroup::collect(orders, LEG_SETS) {
+                                if group.policy == FailurePolicy::AllOrNothing {
+                                    if !group.is_complete(LEG_SETS) {
+                                        debug!("Trade {} missing legs, dropped", group.legs[0].strategy_tag);
+                                        continue;
+                                    }
+                                    match risk.check_group(&slug, &group).await {
+                                        Ok(()) => approved_orders.extend(group.legs),
+                                        Err(e) => debug!("Trade rejected by risk: {e:#}"),
+                                    }
+                                    continue;
+                                }
+                                for order in group.legs {
+                                    match risk.check_market_order(&slug, &order).await {
+                                        Ok(()) => approved_orders.push(order),
+                                        Err(e) => {
+                                            debug!("Order rejected by risk: {e}");
+                                        }
                                     }
                                 }
                             }
@@ -444,20 +1445,141 @@ async fn main() -> anyhow::Result<()> {
                                 }
                             }
 
+                            // Canary strategies on probation trade a fixed small size
+                            if let Some(canary) = &risk.canary {
+                                let mut capped = Vec::with_capacity(approved_orders.len());
+                                for mut group in IntentGroup::collect(approved_orders, LEG_SETS) {
+                                    canary.cap(&mut group.legs);
+                                    capped.extend(group.legs);
+                                }
+                                approved_orders = capped;
+                                approved_orders.retain(|o| o.size > Decimal::ZERO);
+                                if approved_orders.is_empty() {
+                                    continue;
+                                }
+                            }
+
+                            // Hold large entries for the operator; send the ones they approved
+                            if let Some(approvals) = &approvals {
+                                let (pass, held) = approvals.hold(&slug, approved_orders, chrono::Utc::now());
+                                approved_orders = pass;
+                                for p in held {
+                                    match &approver {
+                                        Some(approver) => {
+                                            let approver = approver.clone();
+                                            tokio::spawn(async move {
+                                                if let Err(e) = approver.notify(&p).await {
+                                                    warn!("Approval request for {} not sent: {e:#}", p.id);
+                                                }
+                                            });
+                                        }
+                                        None => {
+                                            alerts
+                                                .send_at(
+                                                    AlertSeverity::Warning,
+                                                    &format!("Approval needed [{}]: {} (POST /approvals/{}/approve)", p.id, p.describe(), p.id),
+                                                )
+                                                .await;
+                                        }
+                                    }
+                                }
+                                let price = |o: &OrderIntent| {
+                                    let book = if o.token_id == no_book.token_id { &no_book } else { &yes_book };
+                                    let level = if o.order_side == OrderSide::Buy { book.best_ask() } else { book.best_bid() };
+                                    level.map(|(price, _)| price)
+                                };
+                                for legs in approvals.take_approved(&slug, chrono::Utc::now(), price) {
+                                    let policy = if legs.len() > 1 { FailurePolicy::AllOrNothing } else { FailurePolicy::BestEffort };
+                                    match risk.check_group(&slug, &IntentGroup::new(legs.clone(), policy)).await {
+                                        Ok(()) => approved_orders.extend(legs),
+                                        Err(e) => warn!("Approved trade rejected by risk: {e:#}"),
+                                    }
+                                }
+                                if approved_orders.is_empty() {
+                                    continue;
+                                }
+                            }
+
+                            // Round amounts to this market's tick rather than assume 0.01
+                            if let Err(e) = submitter.set_tick_size(&market.yes_token_id, market.tick_size).await {
+                                warn!("Skipping {slug}: {e}");
+                                continue;
+                            }
+                            if let Err(e) = submitter.set_tick_size(&market.no_token_id, market.tick_size).await {
+                                warn!("Skipping {slug}: {e}");
+                                continue;
+                            }
+
+                            // A repeat of an intent still in flight would spend twice
+                            approved_orders = submitter.dedupe(approved_orders);
+                            if approved_orders.is_empty() {
+                                continue;
+                            }
+
+                            // Replace or top up what's already resting rather than stack on it
+                            let mut netting = None;
+                            if net_resting {
+                                let plan = submitter.net(approved_orders, &tracker).await;
+                                if plan.submit.is_empty() {
+                                    submitter.retire(&plan, &[], &[], &tracker).await;
+                                    continue;
+                                }
+                                approved_orders = plan.submit.clone();
+                                netting = Some(plan);
+                            }
+
+                            // Trades that lost a leg to the steps above go out whole or not at all
+                            let groups: Vec<IntentGroup> = IntentGroup::collect(approved_orders, LEG_SETS)
+                                .into_iter()
+                                .filter(|g| {
+                                    let keep = g.policy == FailurePolicy::BestEffort || g.is_complete(LEG_SETS);
+                                    if !keep {
+                                        debug!("Trade {} lost a leg before submission, dropped", g.legs[0].strategy_tag);
+                                    }
+                                    keep
+                                })
+                                .collect();
+                            let approved_orders: Vec<_> = groups.iter().flat_map(|g| g.legs.iter().cloned()).collect();
+                            let leg_groups: Vec<Option<&str>> = groups
+                                .iter()
+                                .flat_map(|g| std::iter::repeat_n(g.is_multi_leg().then_some(g.id.as_str()), g.legs.len()))
+                                .collect();
+                            if approved_orders.is_empty() {
+                                if let Some(plan) = &netting {
+                                    submitter.retire(plan, &[], &[], &tracker).await;
+                                }
+                                continue;
+                            }
+
                             // Submit
                             let _timer = latency.start_timer("order_submit");
-                            match submitter.submit(&approved_orders).await {
+                            match submitter.submit_groups(&groups).await {
                                 Ok(results) => {
                                     let mut success = 0usize;
-                                    for (result, intent) in results.iter().zip(approved_orders.iter()) {
+                                    let competition = orch.competition();
+                                    let submitted_ms = chrono::Utc::now().timestamp_millis();
+                                    for ((result, intent), group) in results.iter().zip(approved_orders.iter()).zip(&leg_groups) {
+                                        competition.on_order_result(&market, intent, result);
                                         if result.is_success() {
+                                            orch.on_order_accepted(&market, intent);
+                                            competition.on_order_submitted(&market, intent, submitted_ms);
                                             tracker.watch(result.clone());
+                                            registry.register(&result.order_id, &slug, intent, *group, result.filled_size);
+                                            tracker.watch_quote(result, intent);
+                                            let book = if intent.token_id == no_book.token_id { &no_book } else { &yes_book };
+                                            tca.on_submit(result, intent, book);
                                             success += 1;
 
                                             // Record fill with position manager
                                             // For GTC/GTD orders, fills arrive later via WS.
                                             // For FOK/FAK, the initial result is the fill.
                                             if result.filled_size > Decimal::ZERO {
+                                                // Filled on arrival: we took liquidity
+                                                let fee = if intent.post_only {
+                                                    Decimal::ZERO
+                                                } else {
+                                                    taker_fee(result.avg_fill_price, result.filled_size, submitter.fee_rate_bps().await)
+                                                };
                                                 let fill = crate::models::order::Fill {
                                                     order_id: result.order_id.clone(),
                                                     token_id: result.token_id.clone(),
@@ -465,14 +1587,55 @@ async fn main() -> anyhow::Result<()> {
                                                     price: result.avg_fill_price,
                                                     size: result.filled_size,
                                                     timestamp: result.timestamp,
-                                                    fee: Decimal::ZERO, // CLOB charges taker fee separately
+                                                    fee,
                                                 };
-                                                pos_mgr.record_fill(
+                                                tca.on_fill(&fill);
+                                                // The fill in the post response, at most one per order
+                                                markouts.on_fill(&fill, "submit", &slug, &intent.strategy_tag);
+                                                telemetry::events::Fill {
+                                                    order_id: &fill.order_id,
+                                                    token_id: &fill.token_id,
+                                                    market: &slug,
+                                                    side: fill.side,
+                                                    price: fill.price,
+                                                    size: fill.size,
+                                                    fee: fill.fee,
+                                                    strategy: &intent.strategy_tag,
+                                                    source: telemetry::events::FillSource::Submit,
+                                                }
+                                                .emit();
+                                                if let Some(journal) = &journal {
+                                                    journal.record(
+                                                        &telemetry::journal::JournalEntry::from_fill(&fill, "submit", &slug, &intent.strategy_tag)
+                                                            .with_group(group.map(str::to_string)),
+                                                    );
+                                                }
+                                                let realized = pos_mgr.record_fill(
                                                     &fill,
                                                     &slug,
                                                     intent.market_side,
                                                     &intent.strategy_tag,
                                                 ).await;
+                                                let realized = realized.map(|p| p.to_string().parse::<f64>().unwrap_or(0.0));
+                                                if let Some(realized) = realized {
+                                                    pnl_tracker.record_exit(ExitReason::Strategy, realized);
+                                                }
+                                                if let Some(trip) = realized.and_then(|pnl| {
+                                                    risk.markets.record_pnl(market.asset, market.duration, pnl, fill.timestamp)
+                                                }) {
+                                                    alerts.send_at(AlertSeverity::Warning, &format!("Market stop: {trip}")).await;
+                                                    cancel_series_entries(&poly, market.asset, market.duration, &submitter, &tracker).await;
+                                                }
+                                                if let Some(trip) = risk.loops.on_fill(
+                                                    &slug,
+                                                    &intent.strategy_tag,
+                                                    fill.side,
+                                                    fill.size,
+                                                    &fill.order_id,
+                                                    fill.timestamp,
+                                                ) {
+                                                    alerts.send_at(AlertSeverity::Warning, &format!("Trade loop: {trip}")).await;
+                                                }
                                             }
                                         }
                                     }
@@ -482,12 +1645,34 @@ async fn main() -> anyhow::Result<()> {
                                             approved_orders.len()
                                         );
                                     }
+                                    // Quotes the batch replaced come down only now that the replacements are in
+                                    if let Some(plan) = &netting {
+                                        submitter.retire(plan, &approved_orders, &results, &tracker).await;
+                                    }
+                                    // Orders flattening the matched legs of unwound trades; fills come via WS
+                                    for (intent, result) in submitter.take_hedges() {
+                                        if result.is_success() {
+                                            tracker.watch(result.clone());
+                                            registry.register(&result.order_id, &slug, &intent, None, result.filled_size);
+                                        }
+                                    }
                                 }
                                 Err(e) => {
-                                    error!("Order submission failed for {slug}: {e}");
-                                    alerts.send(&format!("Submit error: {e}")).await;
+                                    error!("Order submission failed for {slug} ({}): {e}", e.category());
+                                    // Outages and rate limits were already retried; only page on the rest
+                                    if let Some(severity) = e.alert_severity() {
+                                        alerts.send_at(severity, &format!("Submit error: {e}")).await;
+                                    }
                                 }
                             }
+                            // The fixes are REST round trips; the next market shouldn't wait on them
+                            let remedies = submitter.take_remediations();
+                            if !remedies.is_empty() {
+                                let (submitter, pos_mgr, poly) = (submitter.clone(), pos_mgr.clone(), poly.clone());
+                                tokio::spawn(async move {
+                                    remediate_rejections(remedies, &submitter, &pos_mgr, &poly).await;
+                                });
+                            }
                         }
                     }
                     _ = shutdown_rx.recv() => break,
@@ -496,102 +1681,330 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
-    // === Spawn market resolution tracker (every 5s) ===
+    // === Spawn max-hold exit sweep ===
+    // Lag positions are sold once held past their market's half-life max hold
+    // (none without HALF_LIFE_ADAPT) on their own clock, so the gates that skip a market for new entries (safe mode, a
+    // missing reference price or one-sided book, the last seconds of the
+    // window) never strand one.
     {
+        let orch = orchestrator.clone();
         let poly = polymarket_feed.clone();
-        let binance = binance_feed.clone();
         let pos_mgr = position_mgr.clone();
-        let _pnl = pnl_tracker.clone();
-        let alerts = alert_mgr.clone();
+        let risk = risk_mgr.clone();
+        let submitter = batch_submitter.clone();
         let tracker = fill_tracker.clone();
+        let registry = order_registry.clone();
+        let token_balances = token_balances.clone();
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
-            // Track markets we've already resolved to avoid double-settling
-            let mut resolved_slugs: std::collections::HashSet<String> = std::collections::HashSet::new();
-
+            // Holds are tens of seconds; a second's slack doesn't matter
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        // Check all market types for resolution
-                        for (asset, duration) in MarketDiscovery::all_market_types() {
-                            let slug = MarketDiscovery::current_slug(asset, duration);
-                            let remaining = MarketDiscovery::time_remaining_in_current(duration);
-
-                            // Market has resolved (past close time)
-                            if remaining <= 0.0 && !resolved_slugs.contains(&slug) {
-                                // Check if we have positions in this market
-                                let pos_count = pos_mgr.position_count(&slug).await;
-                                if pos_count == 0 {
-                                    resolved_slugs.insert(slug.clone());
-                                    continue;
-                                }
+                        if risk.killed.load(std::sync::atomic::Ordering::Relaxed) {
+                            continue;
+                        }
+                        let positions = pos_mgr.portfolio.read().await.positions.clone();
+                        let mut slugs: Vec<&str> = positions.iter().map(|p| p.market_id.as_str()).collect();
+                        slugs.sort();
+                        slugs.dedup();
+                        for slug in slugs {
+                            let Some(market) = poly.get_market(slug) else { continue };
+                            let held: Vec<_> = positions.iter().filter(|p| p.market_id == slug).cloned().collect();
+                            let yes_book = poly.get_book(&market.yes_token_id);
+                            let no_book = poly.get_book(&market.no_token_id);
+                            let mut exits = orch.lag_exits(&market, &held, yes_book.as_deref(), no_book.as_deref(), chrono::Utc::now());
+                            if let Some(balances) = &token_balances {
+                                balances.cap_sells(&mut exits);
+                            }
+                            if !exits.is_empty() {
+                                submit_exits(&market, exits, &risk, &submitter, &tracker, &registry).await;
+                            }
+                        }
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
+
+    // === Spawn market lifecycle consumer ===
+    // Captures each market's reference price when it opens and submits its
+    // queued opening orders; on close clears the orchestrator's per-market
+    // state and any quotes left resting.
+    {
+        let mut lifecycle_rx = market_events.subscribe();
+        let poly = polymarket_feed.clone();
+        let binance = binance_feed.clone();
+        let orch = orchestrator.clone();
+        let submitter = batch_submitter.clone();
+        let tracker = fill_tracker.clone();
+        let risk = risk_mgr.clone();
+        let registry = order_registry.clone();
+        let approvals = approvals.clone();
+        let opening = opening_queue.clone();
+        let price_series = config.strategy.price_series.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
 
-                                // Get the market from Polymarket feed cache
-                                let market = match poly.get_market(&slug) {
-                                    Some(m) => m,
-                                    None => {
-                                        resolved_slugs.insert(slug.clone());
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = lifecycle_rx.recv() => match event {
+                        Ok(event) => {
+                            orch.on_market_event(&event);
+                            match event.kind {
+                                MarketEventKind::Opened if event.elapsed_secs < event.duration.seconds() as f64 => {
+                                    let queued = opening.take(&event.slug);
+                                    let stopped = risk.markets.is_stopped(event.asset, event.duration, chrono::Utc::now());
+                                    if let (false, false, Some(market)) = (queued.is_empty(), stopped, poly.get_market(&event.slug)) {
+                                        submit_opening_orders(&market, queued, &risk, approvals.as_deref(), &submitter, &tracker, &registry).await;
+                                    }
+                                    let series = price_series.for_market(event.asset, event.duration);
+                                    let Some(price) = binance.get_price_for(event.asset, series).await else {
+                                        warn!("No Binance price for {} at open, reference left unset", event.slug);
                                         continue;
+                                    };
+                                    if let Some(mut market) = poly.markets.get_mut(&event.slug) {
+                                        market.set_reference_price(price);
+                                        info!("Reference for {}: {:.2} ({:.1}s after open)", event.slug, market.reference_price, event.elapsed_secs);
                                     }
-                                };
-
-                                // Determine winner: compare current Binance price vs reference
-                                let current_price = match binance.get_price(asset).await {
-                                    Some(p) => p,
-                                    None => continue,
-                                };
-                                let ref_price = market.reference_price;
-
-                                if ref_price == 0.0 {
-                                    continue;
                                 }
+                                MarketEventKind::Closed => {
+                                    let Some(market) = poly.get_market(&event.slug) else { continue };
+                                    let resting = [&market.yes_token_id, &market.no_token_id]
+                                        .iter()
+                                        .any(|t| !tracker.quotes_for(t).is_empty());
+                                    if resting {
+                                        if let Err(e) = submitter.cancel_market(&market, &tracker).await {
+                                            warn!("Cancelling leftover orders on {} failed: {e}", market.slug);
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Market lifecycle consumer missed {n} events");
+                        }
+                        Err(_) => break,
+                    },
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+    }
 
-                                let winning_side = if current_price >= ref_price {
-                                    crate::models::market::Side::Yes // Price went up
-                                } else {
-                                    crate::models::market::Side::No  // Price went down
-                                };
-
-                                info!(
-                                    "Market resolved: {slug} ref={ref_price:.2} final={current_price:.2} winner={winning_side:?}"
-                                );
-
-                                // Settle positions
-                                pos_mgr.record_resolution(&slug, winning_side).await;
-
-                                // Clean up fill tracker
-                                tracker.cleanup_completed();
+    // === Spawn market resolution tracker (on close, retried every 5s) ===
+    {
+        let mut lifecycle_rx = market_events.subscribe();
+        let poly = polymarket_feed.clone();
+        let binance = binance_feed.clone();
+        let pos_mgr = position_mgr.clone();
+        let allocator = orchestrator.allocator();
+        let pnl_tracker = pnl_tracker.clone();
+        let alerts = alert_mgr.clone();
+        let tracker = fill_tracker.clone();
+        let tca = tca.clone();
+        let fill_quality = orchestrator.fill_quality();
+        let spread_control = orchestrator.spread_control();
+        let markouts = markouts.clone();
+        let ab = ab.clone();
+        let journal = journal.clone();
+        let recorder = recorder.clone();
+        let edge_policy = orchestrator.edge_policy();
+        let market_stop = risk_mgr.markets.clone();
+        let submitter = batch_submitter.clone();
+        let mut shutdown_rx = shutdown_tx.subscribe();
 
-                                // Alert
-                                let capital = pos_mgr.available_capital().await;
-                                alerts.send(&format!(
-                                    "Resolved {slug}: {winning_side:?} won | Capital: ${capital:.2}"
-                                )).await;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            // Closed markets not yet settled (waiting on a final price)
+            let mut closed: Vec<(String, Asset, crate::models::market::Duration)> = Vec::new();
+            // Closed events can be lost to a lagging channel: every minute,
+            // and on lag, markets past their close that still hold
+            // positions are queued as if their event had arrived
+            let mut ticks = 0u64;
+            let mut sweep = false;
 
-                                resolved_slugs.insert(slug);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        ticks += 1;
+                        sweep |= ticks.is_multiple_of(12);
+                        // Markouts for recent fills, fed back into strategy sizing
+                        let marked = tca.mark(|token| {
+                            poly.get_book(token)
+                                .and_then(|b| b.midpoint())
+                                .and_then(|m| m.to_string().parse::<f64>().ok())
+                        });
+                        for fill in marked {
+                            if let Some(adverse) = fill.adverse_selection {
+                                fill_quality.observe(&fill.strategy, adverse, fill.size);
                             }
-
-                            // Clean up old slugs from the set (keep it bounded)
-                            if resolved_slugs.len() > 100 {
-                                resolved_slugs.clear();
+                        }
+                        let marked = markouts.mark(std::time::Instant::now(), |token| {
+                            poly.get_book(token)
+                                .and_then(|b| b.midpoint())
+                                .and_then(|m| m.to_f64())
+                        });
+                        for fill in marked {
+                            if let Some(market) = poly.get_market(&fill.market) {
+                                spread_control.observe(market.asset, market.duration, &fill);
                             }
                         }
                     }
+                    event = lifecycle_rx.recv() => match event {
+                        Ok(event) if event.kind == MarketEventKind::Closed => {
+                            closed.push((event.slug, event.asset, event.duration));
+                        }
+                        Ok(_) => continue,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Resolution tracker missed {n} market events — sweeping for closed markets");
+                            sweep = true;
+                        }
+                        Err(_) => break,
+                    },
                     _ = shutdown_rx.recv() => break,
                 }
+
+                if std::mem::take(&mut sweep) {
+                    let now = crate::feeds::clock::now();
+                    for slug in pos_mgr.held_markets().await {
+                        if closed.iter().any(|(s, _, _)| *s == slug) {
+                            continue;
+                        }
+                        let Some(market) = poly.get_market(&slug) else { continue };
+                        if market.close_time <= now {
+                            info!("Resolution tracker: {slug} closed with positions and no event seen — settling it");
+                            closed.push((slug, market.asset, market.duration));
+                        }
+                    }
+                }
+
+                // Settle what has closed; a market still missing its final
+                // price waits for the next pass
+                for (slug, asset, duration) in std::mem::take(&mut closed) {
+                    let Some(market) = poly.get_market(&slug) else { continue };
+                    // Determine winner: compare current Binance price vs the reference taken at open
+                    let Some(final_price) = binance.get_price(asset) else {
+                        closed.push((slug, asset, duration));
+                        continue;
+                    };
+                    let ref_price = market.reference_price;
+                    let winner = if ref_price > 0.0 {
+                        if final_price >= ref_price {
+                            crate::models::market::Side::Yes // Price went up
+                        } else {
+                            crate::models::market::Side::No  // Price went down
+                        }
+                    } else if let Some(winner) = book_implied_winner(&poly, &market) {
+                        // No reference captured at open: the closed book has
+                        // already priced the outcome
+                        info!("No reference price for {slug}: settling {winner:?} from its book");
+                        winner
+                    } else {
+                        // Keep it queued rather than strand its positions
+                        if ticks.is_multiple_of(12) {
+                            warn!("No reference price for {slug} and its book hasn't settled — still waiting");
+                        }
+                        closed.push((slug, asset, duration));
+                        continue;
+                    };
+
+                    // Label every closed window, traded or not, for the
+                    // recording and the edge policy's calibration
+                    edge_policy.record_outcome(&slug, winner);
+                    let winning_token = match winner {
+                        crate::models::market::Side::Yes => &market.yes_token_id,
+                        crate::models::market::Side::No => &market.no_token_id,
+                    };
+                    for line in markouts.on_resolution(&slug, winning_token) {
+                        if let Some(journal) = &journal {
+                            journal.record(&line);
+                        }
+                    }
+                    if let Some(ab) = &ab {
+                        for line in ab.on_resolution(&slug, winning_token, chrono::Utc::now()) {
+                            if let Some(journal) = &journal {
+                                journal.record(&line);
+                            }
+                        }
+                    }
+                    if let Some(recorder) = &recorder {
+                        recorder.record(&telemetry::recorder::Recorded::Outcome {
+                            ts_ms: chrono::Utc::now().timestamp_millis(),
+                            market: slug.clone(),
+                            asset,
+                            reference_price: ref_price,
+                            final_price,
+                            winner,
+                        });
+                    }
+
+                    // Check if we have positions in this market
+                    if pos_mgr.position_count(&slug).await == 0 {
+                        continue;
+                    }
+
+                    // Settle positions
+                    let pnl = pos_mgr.record_market_resolution(&market, winner).await;
+                    telemetry::events::Resolution {
+                        market: &slug,
+                        reference_price: ref_price,
+                        final_price,
+                        winner,
+                        pnl,
+                    }
+                    .emit();
+                    pnl_tracker.record_exit(ExitReason::Resolution, pnl.to_string().parse::<f64>().unwrap_or(0.0));
+                    allocator.record_pnl(
+                        asset,
+                        duration,
+                        pnl.to_string().parse::<f64>().unwrap_or(0.0),
+                    );
+                    if let Some(trip) = market_stop.record_pnl(
+                        asset,
+                        duration,
+                        pnl.to_string().parse::<f64>().unwrap_or(0.0),
+                        chrono::Utc::now(),
+                    ) {
+                        alerts.send_at(AlertSeverity::Warning, &format!("Market stop: {trip}")).await;
+                        cancel_series_entries(&poly, asset, duration, &submitter, &tracker).await;
+                    }
+
+                    // Clean up fill tracker
+                    tracker.cleanup_completed();
+
+                    // Alert
+                    let capital = pos_mgr.available_capital().await;
+                    alerts.send(&format!(
+                        "Resolved {slug}: {winner:?} won | Capital: ${capital:.2}"
+                    )).await;
+                }
             }
         });
     }
 
+    market_events.spawn(polymarket_feed.markets.clone(), shutdown_tx.subscribe());
+
     info!("=== SATTEBAAZ running ===");
     info!("All systems active: Binance WS, Polymarket WS, strategies, risk, resolution tracker");
     info!("See docs/ for complete strategy documentation.");
     info!("Press Ctrl+C to shutdown.");
 
-    // Wait for shutdown signal
-    tokio::signal::ctrl_c().await?;
+    // Wait for shutdown signal (or `q` in the dashboard)
+    if tui_mode {
+        let view = DashboardSources {
+            state: state_sources.clone(),
+            logs: log_buffer,
+            min_edge: config.strategy.lag_min_edge,
+        };
+        run_tui(&view, &batch_submitter).await?;
+    } else {
+        tokio::signal::ctrl_c().await?;
+    }
     info!("Shutdown signal received. Cleaning up...");
     let _ = shutdown_tx.send(());
 
@@ -599,11 +2012,407 @@ async fn main() -> anyhow::Result<()> {
     if let Err(e) = batch_submitter.cancel_all().await {
         error!("Failed to cancel orders on shutdown: {e}");
     }
+    // A tripped kill switch stays on record so the next start is in safe mode
+    if !risk_mgr.killed.load(std::sync::atomic::Ordering::Relaxed) {
+        if let Err(e) = session_store.mark(SessionEnd::Clean) {
+            warn!("Session state not recorded: {e}");
+        }
+    }
 
     // Final P&L summary
     pnl_tracker.log_summary().await;
     latency_tracker.log_summary();
+    tca.log_summary();
+    for line in markouts.drain() {
+        if let Some(journal) = &journal {
+            journal.record(&line);
+        }
+    }
+    markouts.report().log_summary();
+    if let Some(observer) = &observer {
+        for line in observer.drain() {
+            if let Some(journal) = &journal {
+                journal.record(&line);
+            }
+        }
+        observer.report().log_summary();
+    }
+    if let Some(ab) = &ab {
+        ab.report(|token| polymarket_feed.get_book(token).and_then(|b| b.midpoint()).and_then(|m| m.to_f64()))
+            .log_summary();
+    }
+
+    if let Some(path) = &config.telemetry.state_snapshot_path {
+        if let Err(e) = state_sources.save(std::path::Path::new(path)).await {
+            warn!("Engine state not saved: {e:#}");
+        }
+    }
 
     info!("SATTEBAAZ shutdown complete.");
     Ok(())
 }
+
+/// Everything the TUI reads to build a frame.
+struct DashboardSources {
+    state: StateSources,
+    logs: tui::terminal::LogBuffer,
+    /// Edge above which a market's signal column lights up
+    min_edge: f64,
+}
+
+/// Fix whatever the last rejections pointed at, so the next orders on
+/// those tokens aren't refused for the same reason.
+/// Index of the next asset whose price changes; None once the feed is gone.
+async fn next_price_change(watches: &mut [watch::Receiver<Option<PriceState>>]) -> Option<usize> {
+    let changes = watches.iter_mut().map(|w| Box::pin(w.changed()));
+    match futures_util::future::select_all(changes).await {
+        (Ok(()), i, _) => Some(i),
+        (Err(_), _, _) => None,
+    }
+}
+
+/// Submit a window's queued opening orders as it opens, through the same
+/// risk checks, size multiplier and canary cap as evaluated orders.
+/// Pre-positioning never waits on an operator: entries that would need
+/// approval are dropped.
+async fn submit_opening_orders(
+    market: &crate::models::market::Market,
+    orders: Vec<crate::models::order::OrderIntent>,
+    risk: &RiskManager,
+    approvals: Option<&crate::risk::approval::ApprovalQueue>,
+    submitter: &BatchSubmitter,
+    tracker: &FillTracker,
+    registry: &crate::execution::order_registry::OrderRegistry,
+) {
+    let slug = &market.slug;
+    let size_mult = risk.current_size_multiplier().await;
+    let mut approved = Vec::new();
+    for mut order in orders {
+        if approvals.is_some_and(|q| q.needs_approval(std::slice::from_ref(&order))) {
+            debug!("Pre-position {slug}: opening order needs approval, dropped");
+            continue;
+        }
+        if let Err(e) = risk.check_market_order(slug, &order).await {
+            debug!("Opening order rejected by risk: {e}");
+            continue;
+        }
+        if size_mult < 1.0 {
+            let current = order.size.to_f64().unwrap_or(0.0);
+            order.size = Decimal::from_f64_retain(current * size_mult).unwrap_or(Decimal::ZERO);
+        }
+        if let Some(canary) = &risk.canary {
+            canary.cap(std::slice::from_mut(&mut order));
+        }
+        if order.size > Decimal::ZERO {
+            approved.push(order);
+        }
+    }
+    if approved.is_empty() {
+        return;
+    }
+    for token_id in [&market.yes_token_id, &market.no_token_id] {
+        if let Err(e) = submitter.set_tick_size(token_id, market.tick_size).await {
+            warn!("Skipping opening orders for {slug}: {e}");
+            return;
+        }
+    }
+    let approved = submitter.dedupe(approved);
+    match submitter.submit(&approved).await {
+        Ok(results) => {
+            let mut success = 0usize;
+            for (result, intent) in results.iter().zip(approved.iter()) {
+                if result.is_success() {
+                    tracker.watch(result.clone());
+                    registry.register(&result.order_id, slug, intent, None, result.filled_size);
+                    tracker.watch_quote(result, intent);
+                    success += 1;
+                }
+            }
+            info!("Pre-position {slug}: submitted {success}/{} opening orders", approved.len());
+        }
+        Err(e) => error!("Opening orders for {slug} failed ({}): {e}", e.category()),
+    }
+}
+
+/// A closed market's winner as its last book priced it — one side bid at
+/// least 0.95 — or None while the book is undecided.
+fn book_implied_winner(
+    poly: &PolymarketFeed,
+    market: &crate::models::market::Market,
+) -> Option<crate::models::market::Side> {
+    let decided = |token: &str| {
+        poly.best_bid(token).is_some_and(|(price, _)| price >= Decimal::new(95, 2))
+    };
+    match (decided(&market.yes_token_id), decided(&market.no_token_id)) {
+        (true, false) => Some(crate::models::market::Side::Yes),
+        (false, true) => Some(crate::models::market::Side::No),
+        _ => None,
+    }
+}
+
+/// A market stop took a series out of the active set: cancel the entries
+/// still resting on its markets, leaving the sells that work exits in place.
+async fn cancel_series_entries(
+    poly: &PolymarketFeed,
+    asset: Asset,
+    duration: crate::models::market::Duration,
+    submitter: &BatchSubmitter,
+    tracker: &FillTracker,
+) {
+    for market in poly.series_markets(asset, duration) {
+        if let Err(e) = submitter.cancel_entries(&market, tracker).await {
+            warn!("Market stop: resting entries on {} not cancelled: {e}", market.slug);
+        }
+    }
+}
+
+/// Submit exits for one market outside the evaluation loop, tracked and
+/// registered like any other order.
+async fn submit_exits(
+    market: &crate::models::market::Market,
+    exits: Vec<OrderIntent>,
+    risk: &RiskManager,
+    submitter: &BatchSubmitter,
+    tracker: &FillTracker,
+    registry: &crate::execution::order_registry::OrderRegistry,
+) {
+    let slug = &market.slug;
+    let mut approved = Vec::new();
+    for order in exits {
+        match risk.check_market_order(slug, &order).await {
+            Ok(()) => approved.push(order),
+            Err(e) => warn!("Exit on {slug} rejected by risk: {e}"),
+        }
+    }
+    for token_id in [&market.yes_token_id, &market.no_token_id] {
+        if let Err(e) = submitter.set_tick_size(token_id, market.tick_size).await {
+            warn!("Skipping exits for {slug}: {e}");
+            return;
+        }
+    }
+    // A FAK still in flight from the last sweep isn't sent twice
+    let approved = submitter.dedupe(approved);
+    if approved.is_empty() {
+        return;
+    }
+    match submitter.submit(&approved).await {
+        Ok(results) => {
+            for (result, intent) in results.iter().zip(approved.iter()) {
+                if result.is_success() {
+                    tracker.watch(result.clone());
+                    registry.register(&result.order_id, slug, intent, None, result.filled_size);
+                }
+            }
+        }
+        Err(e) => error!("Exits for {slug} failed ({}): {e}", e.category()),
+    }
+}
+
+async fn remediate_rejections(
+    remedies: Vec<(execution::rejection::Remediation, String)>,
+    submitter: &BatchSubmitter,
+    pos_mgr: &PositionManager,
+    poly: &PolymarketFeed,
+) {
+    use execution::rejection::Remediation;
+
+    for (remedy, token_id) in remedies {
+        let outcome = match remedy {
+            Remediation::ResyncBalance => match submitter.fetch_balance().await {
+                Ok(balance) => {
+                    pos_mgr.sync_capital_from_balance(balance).await;
+                    Ok(format!("balance ${balance:.2}"))
+                }
+                Err(e) => Err(e),
+            },
+            Remediation::RefetchTickSize => match submitter.fetch_tick_size(&token_id).await {
+                Ok(tick) => {
+                    poly.set_tick_size(&token_id, tick);
+                    submitter.set_tick_size(&token_id, tick).await.map(|()| format!("tick {tick}"))
+                }
+                Err(e) => Err(e),
+            },
+            Remediation::RefetchMinSize => match submitter.fetch_min_order_size(&token_id).await {
+                Ok(size) => {
+                    submitter.set_min_order_size(&token_id, size);
+                    Ok(format!("min size {size}"))
+                }
+                Err(e) => Err(e),
+            },
+            Remediation::RefetchFeeRate => match submitter.fetch_fee_rate(&token_id).await {
+                Ok(bps) => {
+                    submitter.set_fee_rate_bps(bps).await;
+                    Ok(format!("fee {bps} bps"))
+                }
+                Err(e) => Err(e),
+            },
+        };
+        match outcome {
+            Ok(done) => info!("Rejection remediation {remedy:?}: {done}"),
+            Err(e) => warn!("Rejection remediation {remedy:?} failed ({}): {e}", e.category()),
+        }
+    }
+}
+
+/// Charge an on-chain transaction's gas to `strategy` and journal it.
+async fn charge_gas(
+    pos_mgr: &PositionManager,
+    journal: Option<&telemetry::journal::TradeJournal>,
+    strategy: &str,
+    market: &str,
+    gas: &GasSpend,
+) {
+    pos_mgr.record_gas(strategy, market, gas).await;
+    if let Some(journal) = journal {
+        journal.record(&telemetry::journal::JournalEntry::from_gas(gas, market, strategy));
+    }
+}
+
+/// Redraw the dashboard and act on key presses until the user quits or
+/// Ctrl+C arrives.
+async fn run_tui(view: &DashboardSources, submitter: &BatchSubmitter) -> anyhow::Result<()> {
+    use tui::terminal::{Command, Tui};
+
+    let mut tui = Tui::enter()?;
+    let mut refresh = tokio::time::interval(tokio::time::Duration::from_millis(250));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = refresh.tick() => {}
+            _ = &mut ctrl_c => return Ok(()),
+        }
+
+        for command in tui.poll_commands()? {
+            match command {
+                Command::TogglePause => {
+                    if view.state.orchestrator.toggle_pause() {
+                        warn!("Strategies PAUSED from dashboard");
+                    } else {
+                        info!("Strategies resumed from dashboard");
+                    }
+                }
+                Command::CancelAll => {
+                    warn!("Cancel-all requested from dashboard");
+                    match submitter.cancel_all().await {
+                        Ok(()) => {
+                            view.state.tracker.clear_quotes();
+                            info!("All open orders cancelled");
+                        }
+                        Err(e) => error!("Cancel-all failed: {e}"),
+                    }
+                }
+                Command::Quit => return Ok(()),
+            }
+        }
+
+        let dashboard = dashboard_snapshot(view).await;
+        tui.draw(&dashboard)?;
+    }
+}
+
+async fn dashboard_snapshot(view: &DashboardSources) -> tui::dashboard::Dashboard {
+    use tui::dashboard::{BookTop, Dashboard, Ladder, MarketRow, PositionRow, TradingState};
+
+    let prob = crate::signals::probability::ProbabilityModel::new()
+        .with_seasonality(view.state.orchestrator.seasonality());
+    let mut markets = Vec::new();
+    for (asset, duration) in MarketDiscovery::all_market_types() {
+        let slug = MarketDiscovery::current_slug(asset, duration);
+        let remaining = MarketDiscovery::time_remaining_in_current(duration);
+        let spot = view.state.binance.get_price(asset).unwrap_or(0.0);
+        let mut row = MarketRow { slug: slug.clone(), remaining_secs: remaining, spot, fair_up: 0.5, ..Default::default() };
+
+        if let Some(market) = view.state.poly.get_market(&slug) {
+            row.reference = market.reference_price;
+            if market.reference_price > 0.0 && spot > 0.0 {
+                row.fair_up = prob.fair_prob_up(spot, market.reference_price, remaining / 60.0, prob.vol_per_minute(asset), 0.0);
+            }
+            for (label, token) in [("YES", &market.yes_token_id), ("NO", &market.no_token_id)] {
+                if let Some(book) = view.state.poly.get_book(token) {
+                    let top = BookTop::from_book(&book);
+                    if label == "YES" { row.yes = top } else { row.no = top }
+                    row.ladders.push(Ladder::from_book(label, &book, 8));
+                }
+            }
+            let (yes_edge, no_edge) = row.edges();
+            if row.reference > 0.0 && yes_edge.max(no_edge) > view.min_edge {
+                let (side, edge) = if yes_edge >= no_edge { ("YES", yes_edge) } else { ("NO", no_edge) };
+                row.signal = format!("{side} underpriced {:+.0}¢", edge * 100.0);
+            }
+        } else {
+            row.signal = "waiting for market".into();
+        }
+        markets.push(row);
+    }
+
+    let engine = view.state.get_state().await;
+    let f = |d: Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+    let now = engine.time;
+    let held = |t: chrono::DateTime<chrono::Utc>| (now - t).num_seconds().max(0) as u64;
+    let mut positions: Vec<PositionRow> = engine
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| PositionRow {
+            id: (i + 1).to_string(),
+            market: p.market_id.clone(),
+            side: p.side,
+            entry: f(p.avg_entry_price),
+            size: f(p.size),
+            held_secs: held(p.opened_at),
+            strategy: p.strategy_tag.clone(),
+        })
+        .collect();
+    for s in &engine.straddles {
+        for (side, entry, size) in [
+            (crate::models::market::Side::Yes, s.yes_avg_price, s.yes_size),
+            (crate::models::market::Side::No, s.no_avg_price, s.no_size),
+        ] {
+            positions.push(PositionRow {
+                id: (positions.len() + 1).to_string(),
+                market: s.market_id.clone(),
+                side,
+                entry: f(entry),
+                size: f(size),
+                held_secs: held(s.opened_at),
+                strategy: "straddle".into(),
+            });
+        }
+    }
+
+    let state = if engine.risk.killed {
+        TradingState::Killed
+    } else if engine.risk.paused {
+        TradingState::Paused
+    } else {
+        TradingState::Active
+    };
+
+    let c = &engine.capital;
+    Dashboard {
+        title: "LIVE".into(),
+        time: now,
+        capital: f(c.capital),
+        starting_capital: f(c.starting_capital),
+        realized_pnl: f(c.total_pnl),
+        fees: f(c.fees.charged),
+        gas: f(c.gas.charged),
+        exposure: f(c.exposure),
+        settling: f(c.pending_redemption + c.pending_settlement),
+        state,
+        size_mult: engine.risk.size_mult,
+        stats: vec![
+            format!("Daily P&L: {:>+.3}", f(c.daily_pnl)),
+            format!("Fees paid: ${:.3}", f(c.fees.total)),
+            format!("Gas paid:  ${:.3}", f(c.gas.total)),
+            format!("Trades:    {} ({:.0}% win)", c.total_trades, c.win_rate() * 100.0),
+            format!("Loss streak: {}", c.consecutive_losses),
+        ],
+        markets,
+        positions,
+        recent: Vec::new(),
+        logs: view.logs.tail(200),
+    }
+}
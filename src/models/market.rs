@@ -1,9 +1,13 @@
 use chrono::{DateTime, Utc};
+use crate::feeds::clock;
+use crate::models::order::OrderSide;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Asset {
     BTC,
     ETH,
@@ -12,6 +16,13 @@ pub enum Asset {
 }
 
 impl Asset {
+    pub const ALL: [Asset; 4] = [Asset::BTC, Asset::ETH, Asset::SOL, Asset::XRP];
+
+    /// Position in `ALL`, for fixed per-asset tables.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
     pub fn slug_prefix(&self) -> &'static str {
         match self {
             Asset::BTC => "btc",
@@ -101,6 +112,8 @@ pub struct Market {
     pub close_time: DateTime<Utc>,
     pub tick_size: Decimal,
     pub active: bool,
+    /// When this process first discovered the market (None if built locally)
+    pub joined_at: Option<DateTime<Utc>>,
 }
 
 impl Market {
@@ -122,10 +135,9 @@ impl Market {
         no_token_id: String,
         condition_id: Option<String>,
     ) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         let interval = duration.interval_seconds();
-        let now_unix = now.timestamp() as u64;
-        let interval_start = (now_unix / interval) * interval;
+        let interval_start = clock::window_start(duration, now);
         let open_time = DateTime::from_timestamp(interval_start as i64, 0)
             .unwrap_or(now);
         let close_time = DateTime::from_timestamp((interval_start + interval) as i64, 0)
@@ -144,6 +156,7 @@ impl Market {
             close_time,
             tick_size: Decimal::new(1, 2), // $0.01
             active: true,
+            joined_at: None,
         }
     }
 
@@ -155,7 +168,7 @@ impl Market {
     }
 
     pub fn time_remaining_secs(&self) -> f64 {
-        let now = Utc::now();
+        let now = clock::now();
         if now >= self.close_time {
             return 0.0;
         }
@@ -163,7 +176,7 @@ impl Market {
     }
 
     pub fn time_elapsed_secs(&self) -> f64 {
-        let now = Utc::now();
+        let now = clock::now();
         if now <= self.open_time {
             return 0.0;
         }
@@ -171,10 +184,15 @@ impl Market {
     }
 
     pub fn lifecycle_phase(&self) -> LifecyclePhase {
-        let elapsed = self.time_elapsed_secs();
+        self.phase_at(clock::now())
+    }
+
+    /// The phase the market is in at `now`.
+    pub fn phase_at(&self, now: DateTime<Utc>) -> LifecyclePhase {
+        let elapsed = ((now - self.open_time).num_milliseconds() as f64 / 1000.0).max(0.0);
         let total = self.duration.seconds() as f64;
 
-        if self.time_remaining_secs() <= 0.0 {
+        if now >= self.close_time {
             return LifecyclePhase::Resolved;
         }
 
@@ -230,11 +248,189 @@ pub struct PriceLevel {
     pub size: Decimal,
 }
 
+/// A price in integer ticks of 0.0001 — the finest tick size the CLOB lists.
+pub type Ticks = u32;
+
+/// Ticks per dollar of price.
+pub const TICKS_PER_DOLLAR: u32 = 10_000;
+
+/// Price → ticks, rounded to the nearest tick. None for negative or
+/// out-of-range prices.
+pub fn to_ticks(price: Decimal) -> Option<Ticks> {
+    price.checked_mul(Decimal::from(TICKS_PER_DOLLAR))?.round().to_u32()
+}
+
+/// Ticks → price, normalized so 0.52 prints as "0.52".
+pub fn from_ticks(ticks: Ticks) -> Decimal {
+    Decimal::new(ticks as i64, 4).normalize()
+}
+
+/// One side of a book: levels sorted ascending by integer price.
+///
+/// Books hold tens of levels, so a sorted vector beats a tree: updates are a
+/// binary search plus a short shift, iteration is a slice walk, and a clone is
+/// one allocation. Serializes as a price → size map, like the BTreeMap it replaced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ladder {
+    levels: Vec<(Ticks, Decimal)>,
+}
+
+impl Ladder {
+    /// Set a level's size, replacing any previous size.
+    pub fn insert(&mut self, price: Decimal, size: Decimal) {
+        let Some(tick) = to_ticks(price) else { return };
+        match self.levels.binary_search_by_key(&tick, |&(t, _)| t) {
+            Ok(i) => self.levels[i].1 = size,
+            Err(i) => self.levels.insert(i, (tick, size)),
+        }
+    }
+
+    pub fn remove(&mut self, price: &Decimal) -> Option<Decimal> {
+        let tick = to_ticks(*price)?;
+        let i = self.levels.binary_search_by_key(&tick, |&(t, _)| t).ok()?;
+        Some(self.levels.remove(i).1)
+    }
+
+    /// Apply one level of a delta: a zero size removes the level.
+    pub fn set(&mut self, price: Decimal, size: Decimal) {
+        if size == Decimal::ZERO {
+            self.remove(&price);
+        } else {
+            self.insert(price, size);
+        }
+    }
+
+    pub fn get(&self, price: &Decimal) -> Option<Decimal> {
+        let tick = to_ticks(*price)?;
+        let i = self.levels.binary_search_by_key(&tick, |&(t, _)| t).ok()?;
+        Some(self.levels[i].1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    /// Levels as (price, size), ascending by price.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Decimal, Decimal)> + ExactSizeIterator + '_ {
+        self.levels.iter().map(|&(t, s)| (from_ticks(t), s))
+    }
+
+    /// Levels as (ticks, size), ascending — the allocation-free view.
+    pub fn ticks(&self) -> &[(Ticks, Decimal)] {
+        &self.levels
+    }
+
+    /// Lowest-priced level.
+    pub fn first(&self) -> Option<(Decimal, Decimal)> {
+        self.levels.first().map(|&(t, s)| (from_ticks(t), s))
+    }
+
+    /// Highest-priced level.
+    pub fn last(&self) -> Option<(Decimal, Decimal)> {
+        self.levels.last().map(|&(t, s)| (from_ticks(t), s))
+    }
+}
+
+impl Serialize for Ladder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Ladder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = BTreeMap::<Decimal, Decimal>::deserialize(deserializer)?;
+        let mut ladder = Ladder::default();
+        for (price, size) in map {
+            ladder.insert(price, size);
+        }
+        Ok(ladder)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// One level set by a book update; a zero size removed the level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelChange {
+    pub side: BookSide,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// Best bid and ask as (price, size).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bbo {
+    pub bid: Option<(Decimal, Decimal)>,
+    pub ask: Option<(Decimal, Decimal)>,
+}
+
+impl Bbo {
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.ask?.0 - self.bid?.0)
+    }
+
+    /// Whether either best price differs; size changes at the top don't count.
+    pub fn prices_differ(&self, other: &Bbo) -> bool {
+        self.bid.map(|(p, _)| p) != other.bid.map(|(p, _)| p)
+            || self.ask.map(|(p, _)| p) != other.ask.map(|(p, _)| p)
+    }
+}
+
+/// What one update did to a book, broadcast so consumers can react to the
+/// change without copying the book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookDiff {
+    pub token_id: String,
+    /// Levels the update set. Empty for a snapshot, which replaced the book.
+    pub changes: Vec<LevelChange>,
+    pub snapshot: bool,
+    pub prev_bbo: Bbo,
+    pub bbo: Bbo,
+    /// When the update was applied to our copy of the book
+    pub at: DateTime<Utc>,
+}
+
+impl BookDiff {
+    /// A full replacement of the book (REST refresh).
+    pub fn snapshot(book: &OrderBook, prev_bbo: Bbo) -> Self {
+        Self {
+            token_id: book.token_id.clone(),
+            changes: Vec::new(),
+            snapshot: true,
+            prev_bbo,
+            bbo: book.bbo(),
+            at: book.timestamp,
+        }
+    }
+
+    /// Whether the best bid or ask price moved.
+    pub fn bbo_changed(&self) -> bool {
+        self.bbo.prices_differ(&self.prev_bbo)
+    }
+
+    pub fn spread(&self) -> Option<Decimal> {
+        self.bbo.spread()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBook {
     pub token_id: String,
-    pub bids: BTreeMap<Decimal, Decimal>, // price → size (descending by price)
-    pub asks: BTreeMap<Decimal, Decimal>, // price → size (ascending by price)
+    pub bids: Ladder, // best bid is the last level
+    pub asks: Ladder, // best ask is the first level
     pub timestamp: DateTime<Utc>,
 }
 
@@ -242,18 +438,71 @@ impl OrderBook {
     pub fn new(token_id: String) -> Self {
         Self {
             token_id,
-            bids: BTreeMap::new(),
-            asks: BTreeMap::new(),
+            bids: Ladder::default(),
+            asks: Ladder::default(),
             timestamp: Utc::now(),
         }
     }
 
+    /// A book with these (price, size) levels, for tests.
+    #[cfg(test)]
+    pub(crate) fn with_levels(token_id: &str, bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) -> Self {
+        let mut book = Self::new(token_id.into());
+        for &(p, s) in bids {
+            book.bids.insert(p, s);
+        }
+        for &(p, s) in asks {
+            book.asks.insert(p, s);
+        }
+        book
+    }
+
+    /// Apply a price-level delta: each (price, size) replaces that level and
+    /// a zero size removes it.
+    pub fn apply_delta(
+        &mut self,
+        bids: impl IntoIterator<Item = (Decimal, Decimal)>,
+        asks: impl IntoIterator<Item = (Decimal, Decimal)>,
+    ) {
+        for (price, size) in bids {
+            self.bids.set(price, size);
+        }
+        for (price, size) in asks {
+            self.asks.set(price, size);
+        }
+        self.timestamp = Utc::now();
+    }
+
+    /// Apply level changes and describe what they did.
+    pub fn apply_changes(&mut self, changes: Vec<LevelChange>) -> BookDiff {
+        let prev_bbo = self.bbo();
+        for change in &changes {
+            match change.side {
+                BookSide::Bid => self.bids.set(change.price, change.size),
+                BookSide::Ask => self.asks.set(change.price, change.size),
+            }
+        }
+        self.timestamp = Utc::now();
+        BookDiff {
+            token_id: self.token_id.clone(),
+            changes,
+            snapshot: false,
+            prev_bbo,
+            bbo: self.bbo(),
+            at: self.timestamp,
+        }
+    }
+
+    pub fn bbo(&self) -> Bbo {
+        Bbo { bid: self.best_bid(), ask: self.best_ask() }
+    }
+
     pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
-        self.bids.iter().next_back().map(|(&p, &s)| (p, s))
+        self.bids.last()
     }
 
     pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
-        self.asks.iter().next().map(|(&p, &s)| (p, s))
+        self.asks.first()
     }
 
     pub fn midpoint(&self) -> Option<Decimal> {
@@ -277,8 +526,9 @@ impl OrderBook {
         };
         let max_price = best + tolerance;
         self.asks
-            .range(..=max_price)
-            .map(|(_, &size)| size)
+            .iter()
+            .take_while(|&(price, _)| price <= max_price)
+            .map(|(_, size)| size)
             .sum()
     }
 
@@ -287,9 +537,9 @@ impl OrderBook {
     pub fn calculate_buy_market_price(&self, usdc_amount: f64) -> Option<(f64, f64)> {
         let mut cumulative_cost = 0.0;
         let mut worst_price = 0.0;
-        for (&price_dec, &size_dec) in self.asks.iter() {
-            let price = price_dec.to_string().parse::<f64>().unwrap_or(0.0);
-            let size = size_dec.to_string().parse::<f64>().unwrap_or(0.0);
+        for &(ticks, size_dec) in self.asks.ticks() {
+            let price = ticks as f64 / TICKS_PER_DOLLAR as f64;
+            let size = size_dec.to_f64().unwrap_or(0.0);
             if price <= 0.0 || size <= 0.0 { continue; }
             cumulative_cost += price * size;
             worst_price = price;
@@ -307,10 +557,10 @@ impl OrderBook {
         let mut cumulative_shares = 0.0;
         let mut cumulative_usdc = 0.0;
         let mut worst_price = 0.0;
-        // bids: BTreeMap ascending, iter().rev() gives best (highest) first
-        for (&price_dec, &size_dec) in self.bids.iter().rev() {
-            let price = price_dec.to_string().parse::<f64>().unwrap_or(0.0);
-            let size = size_dec.to_string().parse::<f64>().unwrap_or(0.0);
+        // bids are ascending, so reversed gives best (highest) first
+        for &(ticks, size_dec) in self.bids.ticks().iter().rev() {
+            let price = ticks as f64 / TICKS_PER_DOLLAR as f64;
+            let size = size_dec.to_f64().unwrap_or(0.0);
             if price <= 0.0 || size <= 0.0 { continue; }
             cumulative_shares += size;
             cumulative_usdc += price * size;
@@ -322,6 +572,68 @@ impl OrderBook {
         if worst_price > 0.0 { Some((worst_price, cumulative_usdc)) } else { None }
     }
 
+    /// Levels a `side` taker order walks, best first: asks for a buy, bids for a sell.
+    fn taker_levels(&self, side: OrderSide) -> Vec<(Decimal, Decimal)> {
+        match side {
+            OrderSide::Buy => self.asks.iter().collect(),
+            OrderSide::Sell => self.bids.iter().rev().collect(),
+        }
+    }
+
+    /// Average price of taking `shares` with a `side` order. None if the
+    /// book can't fill that many.
+    pub fn vwap_to_size(&self, side: OrderSide, shares: Decimal) -> Option<Decimal> {
+        if shares <= Decimal::ZERO {
+            return None;
+        }
+        let mut filled = Decimal::ZERO;
+        let mut cost = Decimal::ZERO;
+        for (price, size) in self.taker_levels(side) {
+            let take = (shares - filled).min(size);
+            filled += take;
+            cost += take * price;
+            if filled >= shares {
+                return Some(cost / shares);
+            }
+        }
+        None
+    }
+
+    /// Most shares a `side` order can take while its VWAP stays within
+    /// `band` of the best price.
+    pub fn size_within_vwap_band(&self, side: OrderSide, band: Decimal) -> Decimal {
+        let levels = self.taker_levels(side);
+        let Some(&(best, _)) = levels.first() else {
+            return Decimal::ZERO;
+        };
+        let limit = match side {
+            OrderSide::Buy => best + band,
+            OrderSide::Sell => best - band,
+        };
+        // VWAP stays within the limit while the cost saved on levels inside
+        // it covers the extra paid on levels beyond it
+        let mut shares = Decimal::ZERO;
+        let mut slack = Decimal::ZERO;
+        for (price, size) in levels {
+            let worse_by = match side {
+                OrderSide::Buy => price - limit,
+                OrderSide::Sell => limit - price,
+            };
+            if worse_by <= Decimal::ZERO {
+                shares += size;
+                slack -= worse_by * size;
+                continue;
+            }
+            let take = (slack / worse_by).min(size);
+            shares += take;
+            slack -= take * worse_by;
+            if take < size {
+                break;
+            }
+        }
+        shares
+    }
+
     /// Available depth within `tolerance` of the best price on bid side
     pub fn bid_depth_within(&self, tolerance: Decimal) -> Decimal {
         let Some((best, _)) = self.best_bid() else {
@@ -329,8 +641,102 @@ impl OrderBook {
         };
         let min_price = best - tolerance;
         self.bids
-            .range(min_price..)
-            .map(|(_, &size)| size)
+            .iter()
+            .rev()
+            .take_while(|&(price, _)| price >= min_price)
+            .map(|(_, size)| size)
             .sum()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_ladder_keeps_levels_sorted_on_the_tick_grid() {
+        let mut ladder = Ladder::default();
+        ladder.insert(dec!(0.52), dec!(10));
+        ladder.insert(dec!(0.4995), dec!(5));
+        ladder.insert(dec!(0.50), dec!(7));
+        ladder.insert(dec!(0.520), dec!(12)); // same level, new size
+        assert_eq!(
+            ladder.iter().collect::<Vec<_>>(),
+            [(dec!(0.4995), dec!(5)), (dec!(0.5), dec!(7)), (dec!(0.52), dec!(12))]
+        );
+        assert_eq!(ladder.ticks()[0], (4995, dec!(5)));
+        assert_eq!(ladder.last().unwrap().0.to_string(), "0.52");
+
+        ladder.set(dec!(0.50), Decimal::ZERO);
+        assert_eq!(ladder.get(&dec!(0.50)), None);
+        assert_eq!(ladder.len(), 2);
+
+        let json = serde_json::to_string(&ladder).unwrap();
+        assert_eq!(json, r#"{"0.4995":"5","0.52":"12"}"#);
+        assert_eq!(serde_json::from_str::<Ladder>(&json).unwrap(), ladder);
+    }
+
+    #[test]
+    fn test_depth_within_tolerance() {
+        let mut book = OrderBook::new("t".into());
+        book.apply_delta(
+            [(dec!(0.48), dec!(10)), (dec!(0.47), dec!(20)), (dec!(0.45), dec!(40))],
+            [(dec!(0.52), dec!(5)), (dec!(0.53), dec!(15)), (dec!(0.60), dec!(50))],
+        );
+        assert_eq!(book.bid_depth_within(dec!(0.01)), dec!(30));
+        assert_eq!(book.ask_depth_within(dec!(0.01)), dec!(20));
+        assert_eq!(book.best_bid(), Some((dec!(0.48), dec!(10))));
+        assert_eq!(book.best_ask(), Some((dec!(0.52), dec!(5))));
+    }
+
+    #[test]
+    fn test_vwap_to_size_and_band() {
+        let mut book = OrderBook::new("t".into());
+        book.apply_delta(
+            [(dec!(0.48), dec!(10)), (dec!(0.45), dec!(40))],
+            [(dec!(0.52), dec!(10)), (dec!(0.54), dec!(10)), (dec!(0.60), dec!(50))],
+        );
+        assert_eq!(book.vwap_to_size(OrderSide::Buy, dec!(5)), Some(dec!(0.52)));
+        assert_eq!(book.vwap_to_size(OrderSide::Buy, dec!(20)), Some(dec!(0.53)));
+        assert_eq!(book.vwap_to_size(OrderSide::Sell, dec!(20)), Some(dec!(0.465)));
+        assert_eq!(book.vwap_to_size(OrderSide::Buy, dec!(71)), None);
+
+        // 1c band: all of 0.52 and 0.54 averages exactly 0.53, so none of 0.60
+        assert_eq!(book.size_within_vwap_band(OrderSide::Buy, dec!(0.01)), dec!(20));
+        // 3c band: a VWAP of 0.55 is reached partway into the 0.60 level
+        let size = book.size_within_vwap_band(OrderSide::Buy, dec!(0.03));
+        assert_eq!(size, dec!(28));
+        assert_eq!(book.vwap_to_size(OrderSide::Buy, size), Some(dec!(0.55)));
+        assert_eq!(book.size_within_vwap_band(OrderSide::Sell, dec!(0.015)), dec!(20));
+        assert_eq!(OrderBook::new("e".into()).size_within_vwap_band(OrderSide::Buy, dec!(0.02)), dec!(0));
+    }
+
+    #[test]
+    fn test_changes_report_bbo_moves() {
+        let mut book = OrderBook::new("t".into());
+        book.apply_delta([(dec!(0.48), dec!(10))], [(dec!(0.52), dec!(5))]);
+        let change = |side, price, size| LevelChange { side, price, size };
+
+        // Size at the top changes, prices don't
+        let diff = book.apply_changes(vec![change(BookSide::Bid, dec!(0.48), dec!(30))]);
+        assert!(!diff.bbo_changed());
+        assert_eq!(diff.bbo.bid, Some((dec!(0.48), dec!(30))));
+
+        // Ask lifted, a new bid improves
+        let diff = book.apply_changes(vec![
+            change(BookSide::Ask, dec!(0.52), Decimal::ZERO),
+            change(BookSide::Ask, dec!(0.53), dec!(8)),
+            change(BookSide::Bid, dec!(0.49), dec!(4)),
+        ]);
+        assert!(diff.bbo_changed());
+        assert_eq!(diff.prev_bbo.spread(), Some(dec!(0.04)));
+        assert_eq!(diff.spread(), Some(dec!(0.04)));
+        assert_eq!(diff.bbo.ask, Some((dec!(0.53), dec!(8))));
+        assert_eq!(diff.changes.len(), 3);
+
+        let snap = BookDiff::snapshot(&OrderBook::new("t".into()), diff.bbo);
+        assert!(snap.snapshot && snap.bbo_changed());
+        assert_eq!(snap.spread(), None);
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use super::market::Side;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum OrderType {
     GTC, // Good-Til-Cancelled: standard limit
     GTD, // Good-Til-Date: expires at timestamp
@@ -12,7 +13,7 @@ pub enum OrderType {
     FAK, // Fill-And-Kill: partial fills OK, rest cancelled
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderSide {
     Buy,
     Sell,
@@ -28,6 +29,9 @@ pub enum OrderStatus {
     Rejected,
 }
 
+/// Smallest order the CLOB takes on these markets, in shares
+pub const MIN_ORDER_SHARES: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderIntent {
     pub token_id: String,
@@ -39,6 +43,118 @@ pub struct OrderIntent {
     pub post_only: bool,
     pub expiration: Option<u64>,
     pub strategy_tag: String,
+    /// Shared by the legs of one multi-leg trade, set by the strategy that
+    /// emits them; `None` for a lone order.
+    #[serde(default)]
+    pub group_id: Option<String>,
+}
+
+/// Identifies "the same order" across re-evaluations: the token pins the
+/// market, side and window; the price is bucketed to the cent and size is
+/// left out, so a resized repeat still counts as a duplicate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey {
+    pub token_id: String,
+    pub order_side: OrderSide,
+    pub price_cents: i64,
+    pub strategy_tag: String,
+}
+
+impl OrderIntent {
+    pub fn idempotency_key(&self) -> IdempotencyKey {
+        IdempotencyKey {
+            token_id: self.token_id.clone(),
+            order_side: self.order_side,
+            price_cents: (self.price * Decimal::ONE_HUNDRED).round().try_into().unwrap_or(0),
+            strategy_tag: self.strategy_tag.clone(),
+        }
+    }
+}
+
+/// What a multi-order submission does when only some of its legs are
+/// accepted — one leg of a straddle on the book, the other rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailurePolicy {
+    /// Keep whatever was accepted
+    #[default]
+    BestEffort,
+    /// Every leg or none: accepted legs are cancelled when another is
+    /// rejected. Shares already matched can't be taken back
+    AllOrNothing,
+}
+
+/// The tags of a strategy's orders that are legs of one trade, and what to
+/// do when only some of them go through. Declared by the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegSet {
+    pub tags: &'static [&'static str],
+    pub policy: FailurePolicy,
+}
+
+/// Orders that are legs of one trade — an arb pair, a straddle — checked by
+/// risk as a unit, submitted under one failure policy and journaled under
+/// one id. A lone order is a group of one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentGroup {
+    pub id: String,
+    pub legs: Vec<OrderIntent>,
+    pub policy: FailurePolicy,
+}
+
+impl IntentGroup {
+    pub fn new(legs: Vec<OrderIntent>, policy: FailurePolicy) -> Self {
+        Self { id: uuid::Uuid::new_v4().to_string(), legs, policy }
+    }
+
+    pub fn single(intent: OrderIntent) -> Self {
+        Self::new(vec![intent], FailurePolicy::BestEffort)
+    }
+
+    /// Split a flat batch into trades: intents tagged with a `LegSet`'s
+    /// tags and sharing a `group_id` form one group, every other intent its
+    /// own. A set arriving with legs missing — one was filtered out upstream —
+    /// is still one group; `is_complete` tells the caller.
+    pub fn collect(intents: Vec<OrderIntent>, sets: &[LegSet]) -> Vec<IntentGroup> {
+        let mut groups: Vec<IntentGroup> = Vec::new();
+        let mut open: Vec<(usize, Option<String>, usize)> = Vec::new();
+        for intent in intents {
+            let Some(s) = sets.iter().position(|set| set.tags.contains(&intent.strategy_tag.as_str())) else {
+                groups.push(Self::single(intent));
+                continue;
+            };
+            match open.iter().find(|(set, id, _)| *set == s && *id == intent.group_id) {
+                Some(&(_, _, g)) => groups[g].legs.push(intent),
+                None => {
+                    open.push((s, intent.group_id.clone(), groups.len()));
+                    let mut group = Self::new(vec![intent], sets[s].policy);
+                    if let Some(id) = &group.legs[0].group_id {
+                        group.id = id.clone();
+                    }
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Whether every leg `sets` declares for this group is present.
+    pub fn is_complete(&self, sets: &[LegSet]) -> bool {
+        let Some(first) = self.legs.first() else { return false };
+        match sets.iter().find(|set| set.tags.contains(&first.strategy_tag.as_str())) {
+            Some(set) => set.tags.iter().all(|tag| self.legs.iter().any(|leg| leg.strategy_tag == *tag)),
+            None => true,
+        }
+    }
+
+    /// Notional of all legs.
+    pub fn cost(&self) -> Decimal {
+        self.legs.iter().map(|leg| leg.price * leg.size).sum()
+    }
+
+    /// More than one leg: worth tracking as a trade of its own.
+    pub fn is_multi_leg(&self) -> bool {
+        self.legs.len() > 1
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +200,18 @@ pub struct Fill {
     pub fee: Decimal,
 }
 
+/// Taker fee for `size` shares at `price`: p × (1-p) × rate per share, so
+/// it peaks at 50¢ and vanishes toward either end. Makers pay nothing.
+/// Zero if the product overflows, which only garbage inputs can do.
+pub fn taker_fee(price: Decimal, size: Decimal, fee_rate_bps: u32) -> Decimal {
+    let per_share = Decimal::ONE
+        .checked_sub(price)
+        .and_then(|q| q.checked_mul(price))
+        .and_then(|pq| pq.checked_mul(Decimal::from(fee_rate_bps)))
+        .map(|f| f / Decimal::from(10_000));
+    per_share.and_then(|f| f.checked_mul(size)).map_or(Decimal::ZERO, |fee| fee.round_dp(6))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchOrderRequest {
     pub orders: Vec<OrderIntent>,
@@ -93,3 +221,63 @@ pub struct BatchOrderRequest {
 pub struct BatchOrderResponse {
     pub results: Vec<OrderResult>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const PAIR: LegSet = LegSet { tags: &["arb_yes", "arb_no"], policy: FailurePolicy::AllOrNothing };
+
+    fn intent(tag: &str, price: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: tag.to_string(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size: dec!(10),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: tag.to_string(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_legs_collected_into_one_trade() {
+        let groups = IntentGroup::collect(
+            vec![intent("arb_yes", dec!(0.48)), intent("mm_bid", dec!(0.40)), intent("arb_no", dec!(0.49))],
+            &[PAIR],
+        );
+        assert_eq!(groups.len(), 2);
+        let (arb, mm) = (&groups[0], &groups[1]);
+        assert_eq!(arb.policy, FailurePolicy::AllOrNothing);
+        assert_eq!(arb.legs.iter().map(|l| l.strategy_tag.as_str()).collect::<Vec<_>>(), ["arb_yes", "arb_no"]);
+        assert_eq!(arb.cost(), dec!(9.70));
+        assert!(arb.is_multi_leg() && arb.is_complete(&[PAIR]));
+        assert_eq!(mm.policy, FailurePolicy::BestEffort);
+        assert!(!mm.is_multi_leg() && mm.is_complete(&[PAIR]));
+        assert_ne!(arb.id, mm.id);
+
+        // A pair that lost a leg upstream is still one group, flagged incomplete
+        let lone = IntentGroup::collect(vec![intent("arb_no", dec!(0.49))], &[PAIR]);
+        assert_eq!(lone.len(), 1);
+        assert!(!lone[0].is_complete(&[PAIR]));
+    }
+
+    #[test]
+    fn test_pairs_from_one_strategy_stay_apart() {
+        let leg = |tag: &str, pair: &str| OrderIntent { group_id: Some(pair.to_string()), ..intent(tag, dec!(0.48)) };
+        let groups = IntentGroup::collect(
+            vec![leg("arb_yes", "btc"), leg("arb_yes", "eth"), leg("arb_no", "btc"), leg("arb_no", "eth")],
+            &[PAIR],
+        );
+        assert_eq!(groups.len(), 2);
+        for (group, pair) in groups.iter().zip(["btc", "eth"]) {
+            assert_eq!(group.id, pair);
+            assert!(group.is_complete(&[PAIR]));
+            assert!(group.legs.iter().all(|l| l.group_id.as_deref() == Some(pair)));
+        }
+    }
+}
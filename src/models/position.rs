@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::market::Side;
 
@@ -14,6 +15,12 @@ pub struct Position {
     pub unrealized_pnl: Decimal,
     pub strategy_tag: String,
     pub opened_at: DateTime<Utc>,
+    /// Scale-in fills averaged into this position after the first
+    #[serde(default)]
+    pub adds: u32,
+    /// Fees paid opening it, charged against P&L as it closes
+    #[serde(default)]
+    pub entry_fees: Decimal,
 }
 
 impl Position {
@@ -40,6 +47,14 @@ pub struct StraddlePosition {
     pub combined_cost: Decimal,
     pub guaranteed_profit: Decimal, // min(yes_size, no_size) * (1.0 - combined_price)
     pub opened_at: DateTime<Utc>,
+    /// Strategy that built the pair; state saved before it was recorded
+    /// came from the straddle engine
+    #[serde(default = "straddle_tag")]
+    pub strategy_tag: String,
+}
+
+fn straddle_tag() -> String {
+    "straddle".to_string()
 }
 
 impl StraddlePosition {
@@ -49,6 +64,7 @@ impl StraddlePosition {
         no_size: Decimal,
         yes_avg_price: Decimal,
         no_avg_price: Decimal,
+        strategy_tag: String,
     ) -> Self {
         let matched = yes_size.min(no_size);
         let combined_price = yes_avg_price + no_avg_price;
@@ -63,6 +79,7 @@ impl StraddlePosition {
             combined_cost: yes_size * yes_avg_price + no_size * no_avg_price,
             guaranteed_profit: guaranteed,
             opened_at: Utc::now(),
+            strategy_tag,
         }
     }
 
@@ -92,6 +109,148 @@ impl StraddlePosition {
     }
 }
 
+/// Suffix appended to the strategy tag of orders placed in a market we
+/// joined mid-window, so fresh and mid-cycle entries can be compared.
+pub const MID_CYCLE_TAG: &str = "@mid";
+
+/// Whether a strategy tag marks a mid-cycle entry.
+pub fn is_mid_cycle(strategy_tag: &str) -> bool {
+    strategy_tag.ends_with(MID_CYCLE_TAG)
+}
+
+/// Marks an add-on to a position the strategy already holds (before any
+/// mid-cycle suffix).
+pub const SCALE_IN_TAG: &str = "+add";
+
+/// Whether a strategy tag marks a scale-in add-on.
+pub fn is_scale_in(strategy_tag: &str) -> bool {
+    strategy_tag.trim_end_matches(MID_CYCLE_TAG).ends_with(SCALE_IN_TAG)
+}
+
+/// The tag an add-on for `strategy_tag` is submitted under.
+pub fn scale_in_tag(strategy_tag: &str) -> String {
+    if is_scale_in(strategy_tag) {
+        return strategy_tag.to_string();
+    }
+    match strategy_tag.strip_suffix(MID_CYCLE_TAG) {
+        Some(base) => format!("{base}{SCALE_IN_TAG}{MID_CYCLE_TAG}"),
+        None => format!("{strategy_tag}{SCALE_IN_TAG}"),
+    }
+}
+
+/// Joins a market-making tag to its ladder level for levels past the
+/// first, e.g. "mm_bid_l2".
+pub const QUOTE_LEVEL_TAG: &str = "_l";
+
+/// The tag a market-making quote at `level` (1 = nearest fair value) is
+/// submitted under; `base` is "mm_bid" or "mm_ask".
+pub fn quote_level_tag(base: &str, level: usize) -> String {
+    if level <= 1 {
+        base.to_string()
+    } else {
+        format!("{base}{QUOTE_LEVEL_TAG}{level}")
+    }
+}
+
+/// Ladder level of a market-making quote's tag; None for other strategies.
+pub fn quote_level(strategy_tag: &str) -> Option<usize> {
+    let tag = strategy_tag.trim_end_matches(MID_CYCLE_TAG);
+    let rest = tag.strip_prefix("mm_bid").or_else(|| tag.strip_prefix("mm_ask"))?;
+    match rest.strip_prefix(QUOTE_LEVEL_TAG) {
+        Some(level) => level.parse().ok().filter(|l| *l > 1),
+        None => rest.is_empty().then_some(1),
+    }
+}
+
+/// Map a strategy tag to its capital bucket name.
+pub fn strategy_bucket(strategy_tag: &str) -> &'static str {
+    match strategy_tag.trim_end_matches(MID_CYCLE_TAG).trim_end_matches(SCALE_IN_TAG) {
+        t if t.starts_with("straddle") || t == "bias_amplify" => "straddle",
+        t if t.starts_with("arb") => "arb",
+        t if t.starts_with("lag") => "lag",
+        t if t.starts_with("mm") => "mm",
+        t if t.starts_with("momentum") => "momentum",
+        "late_gamma" => "late_gamma",
+        "open_sniper" => "open_sniper",
+        _ => "other",
+    }
+}
+
+/// Virtual capital bucket owned by one strategy family.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CapitalBucket {
+    pub allocated: Decimal,
+    pub realized_pnl: Decimal,
+    pub trades: u64,
+}
+
+impl CapitalBucket {
+    pub fn equity(&self) -> Decimal {
+        self.allocated + self.realized_pnl
+    }
+}
+
+/// Realized results for one entry cohort (fresh or mid-cycle joins).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryCohort {
+    pub trades: u64,
+    pub wins: u64,
+    pub pnl: Decimal,
+}
+
+impl EntryCohort {
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64
+    }
+}
+
+/// Trading fees paid, accrued as fills happen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeLedger {
+    pub total: Decimal,
+    /// Part of `total` already charged against realized P&L; the rest sits
+    /// in open positions
+    pub charged: Decimal,
+    pub by_strategy: HashMap<String, Decimal>,
+    pub by_market: HashMap<String, Decimal>,
+}
+
+impl FeeLedger {
+    /// Accrue a fill's fee to its strategy and market.
+    pub fn accrue(&mut self, strategy_tag: &str, market_id: &str, fee: Decimal) {
+        if fee.is_zero() {
+            return;
+        }
+        self.total += fee;
+        *self.by_strategy.entry(strategy_tag.to_string()).or_default() += fee;
+        *self.by_market.entry(market_id.to_string()).or_default() += fee;
+    }
+}
+
+/// Why owned value isn't spendable yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlingKind {
+    /// Winning tokens of a resolved market, waiting to be redeemed on-chain
+    Redemption,
+    /// Sell proceeds matched on the CLOB, waiting for the trade to confirm on-chain
+    Settlement,
+}
+
+/// Value we own that isn't spendable USDC yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettlingAsset {
+    /// The market slug for a redemption, the order id for a settlement
+    pub id: String,
+    pub market_id: String,
+    pub kind: SettlingKind,
+    pub value: Decimal,
+    pub since: DateTime<Utc>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Portfolio {
     pub capital: Decimal,
@@ -103,6 +262,22 @@ pub struct Portfolio {
     pub consecutive_losses: u32,
     pub total_trades: u64,
     pub winning_trades: u64,
+    /// Profit moved out of trading capital by the sweep policy
+    pub swept_total: Decimal,
+    /// Per-strategy capital buckets (empty when segregation is disabled)
+    pub buckets: HashMap<String, CapitalBucket>,
+    /// Realized results of entries made at a fresh market open
+    pub fresh_entries: EntryCohort,
+    /// Realized results of entries made after joining mid-window
+    pub mid_cycle_entries: EntryCohort,
+    /// Value awaiting redemption or settlement; not part of `capital` until it lands
+    pub settling: Vec<SettlingAsset>,
+    /// Fees paid; `daily_pnl` and `total_pnl` are net of the charged part
+    pub fees: FeeLedger,
+    /// Gas for on-chain transactions, in USD; paid in MATIC so never out of
+    /// `capital`, but charged against P&L as it's spent
+    #[serde(default)]
+    pub gas: FeeLedger,
 }
 
 impl Portfolio {
@@ -119,11 +294,94 @@ impl Portfolio {
             + self.straddles.iter().map(|s| s.combined_cost).sum::<Decimal>()
     }
 
+    /// Realized P&L before fees and gas.
+    pub fn gross_pnl(&self) -> Decimal {
+        self.total_pnl + self.fees.charged + self.gas.charged
+    }
+
+    /// Everything settling, or only `kind`.
+    pub fn settling_value(&self, kind: Option<SettlingKind>) -> Decimal {
+        self.settling
+            .iter()
+            .filter(|a| kind.is_none_or(|k| a.kind == k))
+            .map(|a| a.value)
+            .sum()
+    }
+
+    /// Add to what's settling; more of the same id adds to its value.
+    pub fn hold_settling(&mut self, asset: SettlingAsset) {
+        match self.settling.iter_mut().find(|a| a.id == asset.id && a.kind == asset.kind) {
+            Some(existing) => existing.value += asset.value,
+            None => self.settling.push(asset),
+        }
+    }
+
+    /// Remove a settling asset; returns its value.
+    pub fn take_settling(&mut self, id: &str, kind: SettlingKind) -> Option<Decimal> {
+        let i = self.settling.iter().position(|a| a.id == id && a.kind == kind)?;
+        Some(self.settling.remove(i).value)
+    }
+
+    /// Capital plus value on its way to becoming capital: what exposure is
+    /// measured against.
+    pub fn owned_capital(&self) -> Decimal {
+        self.capital + self.settling_value(None)
+    }
+
+    /// Open exposure attributed to a capital bucket.
+    pub fn bucket_exposure(&self, bucket: &str) -> Decimal {
+        let positions: Decimal = self
+            .positions
+            .iter()
+            .filter(|p| strategy_bucket(&p.strategy_tag) == bucket)
+            .map(|p| p.cost_basis())
+            .sum();
+        let straddles: Decimal = self
+            .straddles
+            .iter()
+            .filter(|s| strategy_bucket(&s.strategy_tag) == bucket)
+            .map(|s| s.combined_cost)
+            .sum();
+        positions + straddles
+    }
+
+    /// Capital a strategy may still commit from its bucket.
+    /// None when segregation is disabled or the tag has no bucket.
+    pub fn bucket_available(&self, strategy_tag: &str) -> Option<Decimal> {
+        let name = strategy_bucket(strategy_tag);
+        self.buckets
+            .get(name)
+            .map(|b| b.equity() - self.bucket_exposure(name))
+    }
+
+    /// Attribute realized P&L to the bucket owning a strategy tag.
+    pub fn record_bucket_pnl(&mut self, strategy_tag: &str, pnl: Decimal) {
+        if let Some(bucket) = self.buckets.get_mut(strategy_bucket(strategy_tag)) {
+            bucket.realized_pnl += pnl;
+            bucket.trades += 1;
+        }
+    }
+
+    /// Count a closed trade toward the cohort its strategy tag marks.
+    pub fn record_entry_pnl(&mut self, strategy_tag: &str, pnl: Decimal) {
+        let cohort = if is_mid_cycle(strategy_tag) {
+            &mut self.mid_cycle_entries
+        } else {
+            &mut self.fresh_entries
+        };
+        cohort.trades += 1;
+        cohort.pnl += pnl;
+        if pnl > Decimal::ZERO {
+            cohort.wins += 1;
+        }
+    }
+
     pub fn exposure_ratio(&self) -> Decimal {
-        if self.capital == Decimal::ZERO {
+        let owned = self.owned_capital();
+        if owned == Decimal::ZERO {
             return Decimal::ZERO;
         }
-        self.total_exposure() / self.capital
+        self.total_exposure() / owned
     }
 
     pub fn win_rate(&self) -> f64 {
@@ -0,0 +1,317 @@
+//! Python bindings (`--features python`), so research notebooks run the same
+//! fair-value, volatility and cost math as the bot.
+//!
+//! ```text
+//! pip install maturin && maturin develop --release
+//! >>> import sattebaaz
+//! >>> sattebaaz.ProbabilityModel().fair_prob_up(100_050, 100_000, 3.2, 0.0008)
+//! ```
+
+use crate::models::candle::{Candle, IndicatorEngine};
+use crate::models::market::{Asset, Side};
+use crate::models::order::OrderSide;
+use crate::models::signal::VolRegime;
+use crate::signals::probability;
+use crate::signals::realtime_vol;
+use crate::signals::seasonality::{Seasonality, VolCalibration};
+use crate::sim::sensitivity::{self, SensitivityReport};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn parse_asset(asset: &str) -> PyResult<Asset> {
+    Asset::ALL
+        .into_iter()
+        .find(|a| a.slug_prefix().eq_ignore_ascii_case(asset))
+        .ok_or_else(|| PyValueError::new_err(format!("unknown asset {asset:?} (btc, eth, sol, xrp)")))
+}
+
+fn parse_outcome(outcome: &str) -> PyResult<Side> {
+    match outcome.to_ascii_lowercase().as_str() {
+        "yes" | "up" => Ok(Side::Yes),
+        "no" | "down" => Ok(Side::No),
+        _ => Err(PyValueError::new_err(format!("unknown outcome {outcome:?} (yes/up, no/down)"))),
+    }
+}
+
+fn parse_side(side: &str) -> PyResult<OrderSide> {
+    match side.to_ascii_lowercase().as_str() {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(PyValueError::new_err(format!("unknown order side {side:?} (buy, sell)"))),
+    }
+}
+
+fn regime_name(regime: VolRegime) -> &'static str {
+    match regime {
+        VolRegime::Dead => "dead",
+        VolRegime::Low => "low",
+        VolRegime::Medium => "medium",
+        VolRegime::High => "high",
+        VolRegime::Extreme => "extreme",
+    }
+}
+
+/// `signals::probability::ProbabilityModel`, optionally with the bot's
+/// intraday vol calibration file.
+#[pyclass(name = "ProbabilityModel")]
+struct PyProbabilityModel {
+    inner: probability::ProbabilityModel,
+    seasonality: Arc<Seasonality>,
+}
+
+#[pymethods]
+impl PyProbabilityModel {
+    #[new]
+    #[pyo3(signature = (calibration_path=None))]
+    fn new(calibration_path: Option<&str>) -> PyResult<Self> {
+        let calibration = calibration_path
+            .map(VolCalibration::load)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let seasonality = Arc::new(Seasonality::new(calibration));
+        let inner = probability::ProbabilityModel::new().with_seasonality(seasonality.clone());
+        Ok(Self { inner, seasonality })
+    }
+
+    #[pyo3(signature = (current_price, open_price, minutes_remaining, vol_per_min, momentum_adj=0.0))]
+    fn fair_prob_up(
+        &self,
+        current_price: f64,
+        open_price: f64,
+        minutes_remaining: f64,
+        vol_per_min: f64,
+        momentum_adj: f64,
+    ) -> f64 {
+        self.inner.fair_prob_up(current_price, open_price, minutes_remaining, vol_per_min, momentum_adj)
+    }
+
+    #[pyo3(signature = (current_price, open_price, minutes_remaining, vol_per_min, momentum_adj=0.0))]
+    fn fair_prob_down(
+        &self,
+        current_price: f64,
+        open_price: f64,
+        minutes_remaining: f64,
+        vol_per_min: f64,
+        momentum_adj: f64,
+    ) -> f64 {
+        self.inner.fair_prob_down(current_price, open_price, minutes_remaining, vol_per_min, momentum_adj)
+    }
+
+    /// `(yes_mispricing, no_mispricing)`; positive means underpriced.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (current_price, open_price, minutes_remaining, vol_per_min, yes_price, no_price, momentum_adj=0.0))]
+    fn mispricing(
+        &self,
+        current_price: f64,
+        open_price: f64,
+        minutes_remaining: f64,
+        vol_per_min: f64,
+        yes_price: f64,
+        no_price: f64,
+        momentum_adj: f64,
+    ) -> (f64, f64) {
+        self.inner.mispricing(
+            current_price,
+            open_price,
+            minutes_remaining,
+            vol_per_min,
+            momentum_adj,
+            yes_price,
+            no_price,
+        )
+    }
+
+    fn kelly_size(&self, edge: f64, market_price: f64, base_win_prob: f64, kelly_fraction: f64) -> f64 {
+        self.inner.kelly_size(edge, market_price, base_win_prob, kelly_fraction)
+    }
+
+    /// Per-minute vol for `asset` at `timestamp_ms` (default: now), from
+    /// the calibration curve when loaded.
+    #[pyo3(signature = (asset, timestamp_ms=None))]
+    fn vol_per_minute(&self, asset: &str, timestamp_ms: Option<i64>) -> PyResult<f64> {
+        let asset = parse_asset(asset)?;
+        let at = match timestamp_ms {
+            Some(ms) => chrono::DateTime::from_timestamp_millis(ms)
+                .ok_or_else(|| PyValueError::new_err(format!("timestamp {ms} out of range")))?,
+            None => chrono::Utc::now(),
+        };
+        Ok(self.seasonality.vol_per_minute(asset, at))
+    }
+}
+
+/// ATR(1m) and regime from raw ticks, as the live vol tracker computes them.
+#[pyfunction]
+fn tick_atr_1m(prices: Vec<f64>, timestamps_ms: Vec<i64>) -> PyResult<(f64, &'static str)> {
+    if prices.len() != timestamps_ms.len() {
+        return Err(PyValueError::new_err("prices and timestamps_ms differ in length"));
+    }
+    let ticks: Vec<(f64, i64)> = prices.into_iter().zip(timestamps_ms).collect();
+    let (atr, regime) = realtime_vol::atr_from_ticks(&ticks);
+    Ok((atr, regime_name(regime)))
+}
+
+/// ATR over the last `period` 1m candles; None with fewer than `period + 1`.
+#[pyfunction]
+#[pyo3(signature = (highs, lows, closes, period=14))]
+fn candle_atr(highs: Vec<f64>, lows: Vec<f64>, closes: Vec<f64>, period: usize) -> PyResult<Option<f64>> {
+    if highs.len() != lows.len() || highs.len() != closes.len() {
+        return Err(PyValueError::new_err("highs, lows and closes differ in length"));
+    }
+    let mut engine = IndicatorEngine::new(closes.len().max(1));
+    let mut prev_close = closes.first().copied().unwrap_or_default();
+    let epoch = chrono::DateTime::UNIX_EPOCH;
+    for ((high, low), close) in highs.into_iter().zip(lows).zip(closes) {
+        engine.push(Candle {
+            open: prev_close,
+            high,
+            low,
+            close,
+            volume: 0.0,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            trades: 0,
+            open_time: epoch,
+            close_time: epoch,
+        });
+        prev_close = close;
+    }
+    Ok(engine.atr(period))
+}
+
+/// Vol regime for an ATR(1m) in `asset` price units.
+#[pyfunction]
+fn vol_regime(asset: &str, atr_1m: f64) -> PyResult<&'static str> {
+    Ok(regime_name(VolRegime::from_atr(parse_asset(asset)?, atr_1m)))
+}
+
+/// `sim::sensitivity::CostModel`: taker fee and slippage assumptions.
+#[pyclass(name = "CostModel")]
+#[derive(Clone, Copy)]
+struct PyCostModel(sensitivity::CostModel);
+
+#[pymethods]
+impl PyCostModel {
+    #[new]
+    #[pyo3(signature = (fee_bps=0.0, slippage=0.0))]
+    fn new(fee_bps: f64, slippage: f64) -> Self {
+        Self(sensitivity::CostModel { fee_bps, slippage })
+    }
+
+    #[getter]
+    fn fee_bps(&self) -> f64 {
+        self.0.fee_bps
+    }
+
+    #[getter]
+    fn slippage(&self) -> f64 {
+        self.0.slippage
+    }
+
+    #[pyo3(signature = (price, post_only=false))]
+    fn fee_per_share(&self, price: f64, post_only: bool) -> f64 {
+        self.0.fee_per_share(price, post_only)
+    }
+
+    #[pyo3(signature = (price, side, post_only=false))]
+    fn fill_price(&self, price: f64, side: &str, post_only: bool) -> PyResult<f64> {
+        Ok(self.0.fill_price(price, parse_side(side)?, post_only))
+    }
+}
+
+/// `sim::sensitivity::SimFill`: one backtest fill, settled against the
+/// winner of window `window`.
+#[pyclass(name = "SimFill")]
+#[derive(Clone)]
+struct PySimFill(sensitivity::SimFill);
+
+#[pymethods]
+impl PySimFill {
+    #[new]
+    #[pyo3(signature = (strategy, window, outcome, side, price, size, post_only=false))]
+    fn new(
+        strategy: String,
+        window: usize,
+        outcome: &str,
+        side: &str,
+        price: f64,
+        size: f64,
+        post_only: bool,
+    ) -> PyResult<Self> {
+        Ok(Self(sensitivity::SimFill {
+            strategy,
+            window,
+            market_side: parse_outcome(outcome)?,
+            order_side: parse_side(side)?,
+            price,
+            size,
+            post_only,
+        }))
+    }
+
+    fn pnl(&self, winner: &str, cost: &PyCostModel) -> PyResult<f64> {
+        Ok(self.0.pnl(parse_outcome(winner)?, &cost.0))
+    }
+}
+
+fn replay_inputs(fills: Vec<PySimFill>, outcomes: Vec<String>) -> PyResult<(Vec<sensitivity::SimFill>, Vec<Side>)> {
+    let outcomes = outcomes.iter().map(|o| parse_outcome(o)).collect::<PyResult<Vec<_>>>()?;
+    if let Some(f) = fills.iter().find(|f| f.0.window >= outcomes.len()) {
+        return Err(PyValueError::new_err(format!(
+            "fill window {} has no outcome ({} given)",
+            f.0.window,
+            outcomes.len()
+        )));
+    }
+    Ok((fills.into_iter().map(|f| f.0).collect(), outcomes))
+}
+
+/// Net P&L per strategy under one cost model; `outcomes[w]` is the winner
+/// of window `w`.
+#[pyfunction]
+fn strategy_pnl(fills: Vec<PySimFill>, outcomes: Vec<String>, cost: PyCostModel) -> PyResult<BTreeMap<String, f64>> {
+    let (fills, outcomes) = replay_inputs(fills, outcomes)?;
+    Ok(sensitivity::strategy_pnl(&fills, &outcomes, &cost.0))
+}
+
+/// Replay fills over a fee × slippage grid:
+/// `{strategy: {"pnl": [[slip][fee]], "breakeven_fee_bps": [per slip]}}`.
+#[pyfunction]
+fn sensitivity_sweep<'py>(
+    py: Python<'py>,
+    fills: Vec<PySimFill>,
+    outcomes: Vec<String>,
+    fee_bps: Vec<f64>,
+    slippage: Vec<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let (fills, outcomes) = replay_inputs(fills, outcomes)?;
+    let report = SensitivityReport::sweep(&fills, &outcomes, &fee_bps, &slippage);
+    let out = PyDict::new(py);
+    for strategy in report.strategies() {
+        let grid: Vec<Vec<f64>> = (0..slippage.len())
+            .map(|s| (0..fee_bps.len()).map(|f| report.pnl(strategy, s, f)).collect())
+            .collect();
+        let breakeven: Vec<Option<f64>> =
+            (0..slippage.len()).map(|s| report.breakeven_fee_bps(strategy, s)).collect();
+        let entry = PyDict::new(py);
+        entry.set_item("pnl", grid)?;
+        entry.set_item("breakeven_fee_bps", breakeven)?;
+        out.set_item(strategy, entry)?;
+    }
+    Ok(out)
+}
+
+#[pymodule]
+fn sattebaaz(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyProbabilityModel>()?;
+    m.add_class::<PyCostModel>()?;
+    m.add_class::<PySimFill>()?;
+    m.add_function(wrap_pyfunction!(tick_atr_1m, m)?)?;
+    m.add_function(wrap_pyfunction!(candle_atr, m)?)?;
+    m.add_function(wrap_pyfunction!(vol_regime, m)?)?;
+    m.add_function(wrap_pyfunction!(strategy_pnl, m)?)?;
+    m.add_function(wrap_pyfunction!(sensitivity_sweep, m)?)?;
+    Ok(())
+}
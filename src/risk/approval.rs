@@ -0,0 +1,305 @@
+use crate::config::ApprovalConfig;
+use crate::models::order::{IntentGroup, OrderIntent, OrderSide, OrderType};
+use crate::strategies::orchestrator::LEG_SETS;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// What became of a held order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Approved,
+    Rejected,
+    /// Timed out before anyone answered
+    Expired,
+}
+
+/// An entry waiting for an operator: one trade, with every leg of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingOrder {
+    pub id: String,
+    pub market: String,
+    /// The trade's legs (both sides of an arb or straddle), approved together
+    pub orders: Vec<OrderIntent>,
+    /// Price × size of the buy legs
+    pub notional: Decimal,
+    pub held_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// When the operator said yes
+    pub approved_at: Option<DateTime<Utc>>,
+}
+
+impl PendingOrder {
+    /// One line for a chat message or log.
+    pub fn describe(&self) -> String {
+        let legs: Vec<String> = self
+            .orders
+            .iter()
+            .map(|o| format!("{} {:?} {:?} x{} @ {}", o.strategy_tag, o.order_side, o.market_side, o.size, o.price))
+            .collect();
+        format!("{} (${:.2}) on {}", legs.join(" + "), self.notional, self.market)
+    }
+
+    /// Same legs — token, direction and strategy — on the same market.
+    fn same_intent(&self, market: &str, orders: &[OrderIntent]) -> bool {
+        self.market == market
+            && self.orders.len() == orders.len()
+            && self.orders.iter().zip(orders).all(|(a, b)| {
+                a.token_id == b.token_id && a.order_side == b.order_side && a.strategy_tag == b.strategy_tag
+            })
+    }
+}
+
+/// Human-in-the-loop confirmation for large entries.
+///
+/// Entries costing at least `min_notional_usdc` are held instead of sent,
+/// and the operator approves or rejects each one (Telegram buttons or
+/// `POST /approvals/{id}/approve|reject`). The legs of an arb or straddle
+/// are one trade: held, counted against the threshold and approved
+/// together. Approved trades are handed back on the market's next
+/// evaluation and re-checked by risk; one whose taker legs the market has
+/// since moved through, or left waiting another `timeout_secs`, expires
+/// instead. Unanswered ones expire after `timeout_secs`. While a trade is
+/// held, repeats of it from later evaluations are dropped rather than
+/// queued again. Exits are never held.
+pub struct ApprovalQueue {
+    config: ApprovalConfig,
+    pending: Mutex<Vec<PendingOrder>>,
+    /// Approved, waiting for their market's next evaluation
+    approved: Mutex<Vec<PendingOrder>>,
+}
+
+impl ApprovalQueue {
+    pub fn new(config: &ApprovalConfig) -> Self {
+        Self { config: config.clone(), pending: Mutex::new(Vec::new()), approved: Mutex::new(Vec::new()) }
+    }
+
+    /// Split one evaluation's orders for `market` into those that go
+    /// straight through and the entries newly held for approval.
+    pub fn hold(
+        &self,
+        market: &str,
+        orders: Vec<OrderIntent>,
+        now: DateTime<Utc>,
+    ) -> (Vec<OrderIntent>, Vec<PendingOrder>) {
+        if self.min_notional() <= Decimal::ZERO {
+            return (orders, Vec::new());
+        }
+        self.expire(now);
+
+        let mut pending = self.pending.lock().unwrap();
+        let mut pass = Vec::new();
+        let mut held = Vec::new();
+        for group in IntentGroup::collect(orders, LEG_SETS) {
+            if !self.needs_approval(&group.legs) {
+                pass.extend(group.legs);
+                continue;
+            }
+            if pending.iter().any(|p| p.same_intent(market, &group.legs)) {
+                continue;
+            }
+            let p = PendingOrder {
+                id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+                market: market.to_string(),
+                notional: entry_cost(&group.legs),
+                orders: group.legs,
+                held_at: now,
+                expires_at: now + chrono::Duration::seconds(self.config.timeout_secs as i64),
+                approved_at: None,
+            };
+            info!("Approval needed [{}]: {}", p.id, p.describe());
+            pending.push(p.clone());
+            held.push(p);
+        }
+        (pass, held)
+    }
+
+    /// Whether the trade made of `legs` is an entry big enough to be held.
+    pub fn needs_approval(&self, legs: &[OrderIntent]) -> bool {
+        let min = self.min_notional();
+        min > Decimal::ZERO && entry_cost(legs) >= min
+    }
+
+    fn min_notional(&self) -> Decimal {
+        Decimal::from_f64_retain(self.config.min_notional_usdc).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Operator's answer for a held order. None if no such order is waiting.
+    pub fn decide(&self, id: &str, approve: bool, now: DateTime<Utc>) -> Option<Decision> {
+        let mut pending = self.pending.lock().unwrap();
+        let i = pending.iter().position(|p| p.id == id)?;
+        let mut p = pending.remove(i);
+        drop(pending);
+        let decision = if now >= p.expires_at {
+            Decision::Expired
+        } else if approve {
+            Decision::Approved
+        } else {
+            Decision::Rejected
+        };
+        info!("Approval [{}] {decision:?}: {}", p.id, p.describe());
+        if decision == Decision::Approved {
+            p.approved_at = Some(now);
+            self.approved.lock().unwrap().push(p);
+        }
+        Some(decision)
+    }
+
+    /// Drop held orders past their timeout; returns them.
+    pub fn expire(&self, now: DateTime<Utc>) -> Vec<PendingOrder> {
+        let mut pending = self.pending.lock().unwrap();
+        let (expired, keep): (Vec<_>, Vec<_>) = pending.drain(..).partition(|p| now >= p.expires_at);
+        *pending = keep;
+        for p in &expired {
+            warn!("Approval [{}] expired unanswered: {}", p.id, p.describe());
+        }
+        expired
+    }
+
+    /// Approved trades for `market`, removed from the queue, that still
+    /// stand at `now`. `price` is what a leg would trade at now (best ask for
+    /// a buy, best bid for a sell). A trade expires if it's waited
+    /// `timeout_secs` since approval, or if the market has moved through a
+    /// taker leg's approved limit — the operator said yes to that price, not
+    /// to chasing it.
+    pub fn take_approved(
+        &self,
+        market: &str,
+        now: DateTime<Utc>,
+        price: impl Fn(&OrderIntent) -> Option<Decimal>,
+    ) -> Vec<Vec<OrderIntent>> {
+        let mut approved = self.approved.lock().unwrap();
+        let (mine, rest): (Vec<_>, Vec<_>) = approved.drain(..).partition(|p| p.market == market);
+        *approved = rest;
+        drop(approved);
+
+        let ttl = chrono::Duration::seconds(self.config.timeout_secs as i64);
+        let mut trades = Vec::new();
+        for p in mine {
+            if p.approved_at.is_some_and(|at| now >= at + ttl) {
+                warn!("Approval [{}] expired before its market came round: {}", p.id, p.describe());
+                continue;
+            }
+            let mut takers = p.orders.iter().filter(|o| matches!(o.order_type, OrderType::FOK | OrderType::FAK));
+            let moved = takers.any(|o| match (price(o), o.order_side) {
+                (Some(current), OrderSide::Buy) => current > o.price,
+                (Some(current), OrderSide::Sell) => current < o.price,
+                (None, _) => true,
+            });
+            if moved {
+                warn!("Approval [{}] expired, the market moved past its price: {}", p.id, p.describe());
+                continue;
+            }
+            trades.push(p.orders);
+        }
+        trades
+    }
+
+    /// Orders waiting for an answer, oldest first.
+    pub fn pending(&self) -> Vec<PendingOrder> {
+        self.pending.lock().unwrap().clone()
+    }
+}
+
+/// Cost of the buy legs of a trade.
+fn entry_cost(legs: &[OrderIntent]) -> Decimal {
+    legs.iter().filter(|o| o.order_side == OrderSide::Buy).map(|o| o.price * o.size).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use rust_decimal_macros::dec;
+
+    fn order(side: OrderSide, price: Decimal, size: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: side,
+            price,
+            size,
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_hold_approve_reject_expire() {
+        let queue = ApprovalQueue::new(&ApprovalConfig { min_notional_usdc: 25.0, timeout_secs: 60 });
+        let now = Utc::now();
+        let big = order(OrderSide::Buy, dec!(0.50), dec!(100));
+
+        // Small entries and exits pass; the big entry is held once, repeats dropped
+        let (pass, held) = queue.hold(
+            "m1",
+            vec![order(OrderSide::Buy, dec!(0.50), dec!(10)), order(OrderSide::Sell, dec!(0.50), dec!(100)), big.clone()],
+            now,
+        );
+        assert_eq!((pass.len(), held.len()), (2, 1));
+        assert_eq!(held[0].notional, dec!(50));
+        let (pass, held_again) = queue.hold("m1", vec![big.clone()], now);
+        assert!(pass.is_empty() && held_again.is_empty());
+
+        // Approved: handed back for its own market only
+        let ask = |_: &OrderIntent| Some(dec!(0.49));
+        assert_eq!(queue.decide(&held[0].id, true, now), Some(Decision::Approved));
+        assert_eq!(queue.decide(&held[0].id, true, now), None, "answered once");
+        assert!(queue.take_approved("m2", now, ask).is_empty());
+        let trades = queue.take_approved("m1", now, ask);
+        assert_eq!((trades.len(), trades[0].len(), trades[0][0].size), (1, 1, dec!(100)));
+        assert!(queue.take_approved("m1", now, ask).is_empty());
+
+        // Rejected, and an unanswered one times out
+        let (_, held) = queue.hold("m1", vec![big.clone()], now);
+        assert_eq!(queue.decide(&held[0].id, false, now), Some(Decision::Rejected));
+        let (_, held) = queue.hold("m1", vec![big], now);
+        let late = now + chrono::Duration::seconds(61);
+        assert_eq!(queue.decide(&held[0].id, true, late), Some(Decision::Expired));
+        assert!(queue.take_approved("m1", late, ask).is_empty());
+        assert!(queue.pending().is_empty());
+    }
+
+    #[test]
+    fn test_arb_pair_held_and_approved_together() {
+        let queue = ApprovalQueue::new(&ApprovalConfig { min_notional_usdc: 25.0, timeout_secs: 60 });
+        let now = Utc::now();
+        // Each leg is under the threshold; the trade isn't
+        let leg = |tag: &str, token: &str, price| OrderIntent {
+            token_id: token.into(),
+            strategy_tag: tag.into(),
+            ..order(OrderSide::Buy, price, dec!(30))
+        };
+        let pair = vec![leg("arb_yes", "yes", dec!(0.45)), leg("arb_no", "no", dec!(0.50))];
+
+        let (pass, held) = queue.hold("m1", pair.clone(), now);
+        assert!(pass.is_empty());
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].orders.len(), 2);
+        assert_eq!(held[0].notional, dec!(28.50));
+
+        // The market moved through the YES leg's limit: the whole trade expires
+        queue.decide(&held[0].id, true, now);
+        let moved = |o: &OrderIntent| Some(if o.token_id == "yes" { dec!(0.47) } else { dec!(0.50) });
+        assert!(queue.take_approved("m1", now, moved).is_empty());
+
+        // Still at its price: both legs come back
+        let (_, held) = queue.hold("m1", pair.clone(), now);
+        queue.decide(&held[0].id, true, now);
+        let same = |o: &OrderIntent| Some(o.price);
+        let trades = queue.take_approved("m1", now, same);
+        let tags: Vec<_> = trades[0].iter().map(|o| o.strategy_tag.as_str()).collect();
+        assert_eq!((trades.len(), tags), (1, vec!["arb_yes", "arb_no"]));
+
+        // Approved but its market never came round in time
+        let (_, held) = queue.hold("m1", pair, now);
+        queue.decide(&held[0].id, true, now);
+        assert!(queue.take_approved("m1", now + chrono::Duration::seconds(60), same).is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::config::CanaryConfig;
+use crate::execution::session::{self, Snapshot};
+use crate::models::order::{OrderIntent, OrderSide, MIN_ORDER_SHARES};
+use crate::models::position::strategy_bucket;
+use crate::telemetry::events::{self, RiskActionKind};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryStatus {
+    /// Small entries until the family proves itself
+    Probation,
+    /// Cleared the thresholds; trades at normal size
+    Graduated,
+    /// Spent its loss budget; no entries until taken off the canary list
+    Halted,
+}
+
+/// One family's probation so far.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanaryRecord {
+    pub started_at: DateTime<Utc>,
+    pub trades: u64,
+    pub wins: u64,
+    pub pnl: f64,
+    pub status: CanaryStatus,
+}
+
+impl CanaryRecord {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self { started_at: now, trades: 0, wins: 0, pnl: 0.0, status: CanaryStatus::Probation }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64
+        }
+    }
+}
+
+/// Canary rollout for strategy families listed in `CANARY_STRATEGIES`.
+///
+/// While on probation a family's entries are shrunk to `max_order_usdc` —
+/// a whole trade at once, so an arb's legs keep one share count — and its
+/// realized losses are held to their own `loss_budget_usdc`,
+/// separate from the portfolio limits. Once it has traded for `min_hours`
+/// and closed `min_trades`, it graduates to normal sizing if its win rate
+/// and P&L clear the thresholds; otherwise it stays on probation and is
+/// re-checked after every close. Progress survives restarts in
+/// `state_path`. Exits are never blocked or shrunk.
+pub struct CanaryGate {
+    config: CanaryConfig,
+    path: PathBuf,
+    records: Mutex<HashMap<String, CanaryRecord>>,
+}
+
+impl CanaryGate {
+    /// Gate for the configured families, resuming saved progress.
+    pub fn new(config: &CanaryConfig) -> Self {
+        let path = PathBuf::from(&config.state_path);
+        let mut records = if config.strategies.is_empty() {
+            HashMap::new()
+        } else {
+            load(&path).unwrap_or_else(|e| {
+                warn!("Canary state unreadable, starting probation afresh: {e}");
+                HashMap::new()
+            })
+        };
+        let now = Utc::now();
+        for family in &config.strategies {
+            records.entry(family.clone()).or_insert_with(|| CanaryRecord::new(now));
+        }
+        Self { config: config.clone(), path, records: Mutex::new(records) }
+    }
+
+    /// Status of `strategy_tag`'s family; None if it isn't a canary.
+    pub fn status(&self, strategy_tag: &str) -> Option<CanaryStatus> {
+        let family = strategy_bucket(strategy_tag);
+        if !self.config.strategies.iter().any(|s| s == family) {
+            return None;
+        }
+        self.records.lock().unwrap().get(family).map(|r| r.status)
+    }
+
+    /// Reject entries by a family that spent its loss budget.
+    pub fn check(&self, order: &OrderIntent) -> Result<()> {
+        if order.order_side == OrderSide::Buy && self.status(&order.strategy_tag) == Some(CanaryStatus::Halted) {
+            anyhow::bail!("Canary {}: loss budget spent, entries halted", strategy_bucket(&order.strategy_tag));
+        }
+        Ok(())
+    }
+
+    /// Shrink a probation trade (one order, or every leg of an arb or
+    /// straddle) to the canary cost cap. The legs are cut to one share
+    /// count, so the pair stays hedged; if that's under the venue's minimum
+    /// order size the whole trade is zeroed for the caller to drop.
+    pub fn cap(&self, legs: &mut [OrderIntent]) {
+        let on_probation = |o: &OrderIntent| {
+            o.order_side == OrderSide::Buy && self.status(&o.strategy_tag) == Some(CanaryStatus::Probation)
+        };
+        if !legs.iter().any(on_probation) {
+            return;
+        }
+        let buys = || legs.iter().filter(|o| o.order_side == OrderSide::Buy);
+        let cost: Decimal = buys().map(|o| o.price * o.size).sum();
+        let per_share: Decimal = buys().map(|o| o.price).sum();
+        let max_cost = Decimal::from_f64_retain(self.config.max_order_usdc).unwrap_or(Decimal::ZERO);
+        if cost <= max_cost || per_share <= Decimal::ZERO {
+            return;
+        }
+        let shares = (max_cost / per_share).round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        let shares = if shares < MIN_ORDER_SHARES {
+            info!(
+                "Canary {}: ${:.2} buys {shares} shares, under the {MIN_ORDER_SHARES} minimum — trade dropped",
+                strategy_bucket(&legs[0].strategy_tag),
+                max_cost
+            );
+            Decimal::ZERO
+        } else {
+            shares
+        };
+        for leg in legs.iter_mut() {
+            leg.size = leg.size.min(shares);
+        }
+    }
+
+    /// Add one closed trade's realized P&L; may graduate or halt the family.
+    pub fn record_pnl(&self, strategy_tag: &str, pnl: f64, now: DateTime<Utc>) -> Option<CanaryStatus> {
+        let family = strategy_bucket(strategy_tag);
+        if !self.config.strategies.iter().any(|s| s == family) {
+            return None;
+        }
+        let mut records = self.records.lock().unwrap();
+        let record = records.get_mut(family).filter(|r| r.status == CanaryStatus::Probation)?;
+        record.trades += 1;
+        record.pnl += pnl;
+        if pnl > 0.0 {
+            record.wins += 1;
+        }
+
+        let c = &self.config;
+        let hours = (now - record.started_at).num_seconds() as f64 / 3600.0;
+        let changed = if record.pnl <= -c.loss_budget_usdc {
+            record.status = CanaryStatus::Halted;
+            let reason = format!("{family} lost ${:.2} on probation, entries halted", -record.pnl);
+            events::RiskAction { action: RiskActionKind::CanaryHalted, reason: &reason, market: "" }.emit();
+            Some(CanaryStatus::Halted)
+        } else if hours >= c.min_hours && record.trades >= c.min_trades {
+            if record.win_rate() >= c.min_win_rate && record.pnl >= c.min_pnl_usdc {
+                record.status = CanaryStatus::Graduated;
+                let reason = format!(
+                    "{family} graduated after {hours:.1}h: {} trades, {:.0}% wins, ${:+.2}",
+                    record.trades,
+                    record.win_rate() * 100.0,
+                    record.pnl
+                );
+                events::RiskAction { action: RiskActionKind::CanaryGraduated, reason: &reason, market: "" }.emit();
+                Some(CanaryStatus::Graduated)
+            } else {
+                info!(
+                    "Canary {family}: {} trades, {:.0}% wins, ${:+.2} — below graduation thresholds, staying on probation",
+                    record.trades,
+                    record.win_rate() * 100.0,
+                    record.pnl
+                );
+                None
+            }
+        } else {
+            None
+        };
+
+        if let Err(e) = session::save(&self.path, &*records) {
+            warn!("Canary state not saved: {e}");
+        }
+        changed
+    }
+
+    /// Progress of every configured family.
+    pub fn records(&self) -> Vec<(String, CanaryRecord)> {
+        let records = self.records.lock().unwrap();
+        self.config
+            .strategies
+            .iter()
+            .filter_map(|f| records.get(f).map(|r| (f.clone(), r.clone())))
+            .collect()
+    }
+
+    pub fn log_summary(&self) {
+        for (family, r) in self.records() {
+            info!(
+                "Canary [{family}] {:?} since {}: {} trades, {:.0}% wins, ${:+.2}",
+                r.status,
+                r.started_at.format("%Y-%m-%d %H:%M"),
+                r.trades,
+                r.win_rate() * 100.0,
+                r.pnl
+            );
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<HashMap<String, CanaryRecord>> {
+    let bytes = match std::fs::read(path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    let snapshot: Snapshot<HashMap<String, CanaryRecord>> =
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+    Ok(snapshot.state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    fn buy(tag: &str, price: Decimal, size: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size,
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: tag.into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_probation_caps_then_graduates_or_halts() {
+        let dir = std::env::temp_dir().join(format!("canary-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = CanaryConfig {
+            strategies: vec!["momentum".into(), "lag".into(), "arb".into()],
+            state_path: dir.join("canary.json").to_string_lossy().into(),
+            max_order_usdc: 5.0,
+            loss_budget_usdc: 2.0,
+            min_hours: 1.0,
+            min_trades: 3,
+            min_win_rate: 0.5,
+            min_pnl_usdc: 0.0,
+        };
+        let gate = CanaryGate::new(&config);
+        let now = Utc::now();
+
+        // Probation entries shrink to $5; other families are untouched
+        let mut order = [buy("momentum_capture", dec!(0.50), dec!(30))];
+        gate.cap(&mut order);
+        assert_eq!(order[0].size, dec!(10));
+        let mut other = [buy("straddle_yes", dec!(0.50), dec!(30))];
+        gate.cap(&mut other);
+        assert_eq!(other[0].size, dec!(30));
+
+        // An arb pair keeps one share count; one too small to trade is dropped
+        let mut pair = [buy("arb_yes", dec!(0.45), dec!(20)), buy("arb_no", dec!(0.50), dec!(12))];
+        gate.cap(&mut pair);
+        assert_eq!((pair[0].size, pair[1].size), (dec!(5.26), dec!(5.26)));
+        let mut pricey = [buy("arb_yes", dec!(0.60), dec!(20)), buy("arb_no", dec!(0.45), dec!(20))];
+        gate.cap(&mut pricey);
+        assert!(pricey.iter().all(|o| o.size.is_zero()));
+
+        // Enough trades but not enough time: still on probation
+        for pnl in [0.3, 0.2, -0.1] {
+            assert_eq!(gate.record_pnl("momentum_capture", pnl, now), None);
+        }
+        assert_eq!(gate.status("momentum_capture"), Some(CanaryStatus::Probation));
+        let later = now + chrono::Duration::hours(2);
+        assert_eq!(gate.record_pnl("momentum_capture", 0.1, later), Some(CanaryStatus::Graduated));
+        let mut order = [buy("momentum_capture", dec!(0.50), dec!(30))];
+        gate.cap(&mut order);
+        assert_eq!(order[0].size, dec!(30), "graduated: normal size");
+
+        // Lag burns its budget
+        assert_eq!(gate.record_pnl("lag_exploit", -1.5, now), None);
+        assert_eq!(gate.record_pnl("lag_exploit", -0.5, now), Some(CanaryStatus::Halted));
+        assert!(gate.check(&buy("lag_exploit", dec!(0.50), dec!(1))).is_err());
+
+        // Progress survives a restart
+        let resumed = CanaryGate::new(&config);
+        assert_eq!(resumed.status("momentum_capture"), Some(CanaryStatus::Graduated));
+        assert_eq!(resumed.status("lag_exploit"), Some(CanaryStatus::Halted));
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::CarryConfig;
+use crate::execution::polygon_merger::{pending_tx, reverted_gas, GasSpend, OnChainTx, PolygonMerger, TxOutcome};
+use crate::execution::session::{self, Snapshot};
+use crate::models::market::Side;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+
+/// How often the redeemer looks for redemptions that are due.
+const REDEEM_TICK_SECS: u64 = 10;
+
+/// Winning tokens from a resolved market, waiting to be redeemed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingRedemption {
+    pub market: String,
+    /// None when the market's conditionId was never known: redeem by hand
+    pub condition_id: Option<String>,
+    pub winning_side: Side,
+    /// Winning tokens held; each redeems for $1
+    pub shares: Decimal,
+    pub resolved_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    /// Redeem sent but not seen mined: looked up before anything is resent
+    #[serde(default)]
+    pub pending_tx: Option<String>,
+}
+
+/// What became of a redemption the redeemer took on.
+#[derive(Debug, Clone)]
+pub enum RedeemResult {
+    /// USDC is in the wallet: credit `shares` to capital
+    Paid { redemption: PendingRedemption, tx: OnChainTx },
+    /// An attempt reverted on-chain; it stays pending, but its gas is spent
+    Reverted { market: String, gas: GasSpend },
+    /// Out of attempts; stays pending until redeemed by hand
+    Abandoned(PendingRedemption),
+}
+
+/// Cross-window inventory carry.
+///
+/// Tokens still held when a market resolves — arb pairs that never merged,
+/// inventory that never sold — are only worth their payout once redeemed
+/// on-chain. Instead of writing them off (or booking the payout as cash it
+/// isn't yet), the winning side is carried here as pending redemption:
+/// counted in equity and P&L, not in spendable capital. The redeemer
+/// first tries `retry_secs` after resolution, once the outcome has had time
+/// to be reported on-chain, and retries on that interval until it succeeds
+/// or `max_attempts` run out. Survives restarts in `state_path`.
+pub struct CarryBook {
+    config: CarryConfig,
+    path: PathBuf,
+    pending: Mutex<Vec<PendingRedemption>>,
+}
+
+impl CarryBook {
+    /// Book for `config`, resuming redemptions saved by an earlier run.
+    pub fn new(config: &CarryConfig) -> Self {
+        let path = PathBuf::from(&config.state_path);
+        let pending = if config.enabled {
+            load(&path).unwrap_or_else(|e| {
+                warn!("Carry state unreadable, starting with nothing pending: {e}");
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        if !pending.is_empty() {
+            let value: Decimal = pending.iter().map(|p| p.shares).sum();
+            info!("Carry: {} resolved markets (${value}) still to redeem", pending.len());
+        }
+        Self { config: config.clone(), path, pending: Mutex::new(pending) }
+    }
+
+    /// Carry `shares` winning tokens of a market that resolved at `now`.
+    pub fn hold(&self, market: &str, condition_id: Option<&str>, winning_side: Side, shares: Decimal, now: DateTime<Utc>) {
+        if shares <= Decimal::ZERO {
+            return;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(p) = pending.iter_mut().find(|p| p.market == market) {
+            p.shares += shares;
+        } else {
+            pending.push(PendingRedemption {
+                market: market.to_string(),
+                condition_id: condition_id.map(str::to_string),
+                winning_side,
+                shares,
+                resolved_at: now,
+                attempts: 0,
+                next_attempt_at: now + self.retry(),
+                last_error: None,
+                pending_tx: None,
+            });
+        }
+        if condition_id.is_none() {
+            warn!("Carry {market}: no conditionId known — {shares} {winning_side:?} tokens must be redeemed by hand");
+        } else {
+            info!("Carry {market}: {shares} {winning_side:?} tokens pending redemption");
+        }
+        self.save(&pending);
+    }
+
+    /// Redemptions to try now.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<PendingRedemption> {
+        self.pending
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.condition_id.is_some() && p.attempts < self.config.max_attempts && p.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// `market` was redeemed; removes and returns it.
+    pub fn redeemed(&self, market: &str) -> Option<PendingRedemption> {
+        let mut pending = self.pending.lock().unwrap();
+        let i = pending.iter().position(|p| p.market == market)?;
+        let p = pending.remove(i);
+        self.save(&pending);
+        Some(p)
+    }
+
+    /// A redeem of `market` failed. Returns it once it's out of attempts.
+    pub fn failed(&self, market: &str, error: &str, now: DateTime<Utc>) -> Option<PendingRedemption> {
+        let mut pending = self.pending.lock().unwrap();
+        let p = pending.iter_mut().find(|p| p.market == market)?;
+        p.attempts += 1;
+        p.next_attempt_at = now + self.retry();
+        p.last_error = Some(error.to_string());
+        let abandoned = (p.attempts >= self.config.max_attempts).then(|| p.clone());
+        self.save(&pending);
+        abandoned
+    }
+
+    /// Record (or with None, clear) a redeem of `market` that was sent but
+    /// not seen mined.
+    pub fn in_flight(&self, market: &str, tx: Option<&str>) {
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(p) = pending.iter_mut().find(|p| p.market == market) {
+            p.pending_tx = tx.map(str::to_string);
+            self.save(&pending);
+        }
+    }
+
+    /// Everything still waiting, oldest first.
+    pub fn pending(&self) -> Vec<PendingRedemption> {
+        self.pending.lock().unwrap().clone()
+    }
+
+    /// Dollars owed by redemptions not yet made.
+    pub fn pending_value(&self) -> Decimal {
+        self.pending.lock().unwrap().iter().map(|p| p.shares).sum()
+    }
+
+    /// Redeem what's due through `merger`. A redeem left unconfirmed is
+    /// looked up first, and only counts as a failed attempt once it reverts.
+    pub async fn redeem_due(&self, merger: &PolygonMerger, now: DateTime<Utc>) -> Vec<RedeemResult> {
+        let mut results = Vec::new();
+        for p in self.due(now) {
+            let Some(cid) = &p.condition_id else { continue };
+            if let Some(tx_hash) = &p.pending_tx {
+                match merger.tx_outcome(tx_hash, "Redeem").await {
+                    Ok(TxOutcome::Confirmed(tx)) => {
+                        if let Some(redemption) = self.redeemed(&p.market) {
+                            info!("Carry {}: redeemed ${} tx={}", p.market, redemption.shares, tx.hash);
+                            results.push(RedeemResult::Paid { redemption, tx });
+                        }
+                    }
+                    Ok(TxOutcome::Reverted(gas)) => {
+                        warn!("Carry {}: redeem attempt {} reverted: tx={tx_hash}", p.market, p.attempts + 1);
+                        results.push(RedeemResult::Reverted { market: p.market.clone(), gas });
+                        self.in_flight(&p.market, None);
+                        if let Some(p) = self.failed(&p.market, "execution reverted", Utc::now()) {
+                            warn!("Carry {}: giving up after {} attempts — ${} left to redeem by hand", p.market, p.attempts, p.shares);
+                            results.push(RedeemResult::Abandoned(p));
+                        }
+                    }
+                    Ok(TxOutcome::Dropped) => {
+                        warn!("Carry {}: redeem tx {tx_hash} was dropped — resending", p.market);
+                        self.in_flight(&p.market, None);
+                    }
+                    Ok(TxOutcome::Pending) => {}
+                    Err(e) => warn!("Carry {}: redeem tx {tx_hash} lookup failed: {e}", p.market),
+                }
+                continue;
+            }
+            let shares = p.shares.to_f64().unwrap_or(0.0);
+            let (yes, no) = match p.winning_side {
+                Side::Yes => (shares, 0.0),
+                Side::No => (0.0, shares),
+            };
+            match merger.redeem_positions(cid, yes, no).await {
+                Ok(tx) => {
+                    if let Some(redemption) = self.redeemed(&p.market) {
+                        info!("Carry {}: redeemed ${} tx={}", p.market, redemption.shares, tx.hash);
+                        results.push(RedeemResult::Paid { redemption, tx });
+                    }
+                }
+                Err(e) if pending_tx(&e).is_some() => {
+                    warn!("Carry {}: redeem unconfirmed ({e}) — checking it before retrying", p.market);
+                    self.in_flight(&p.market, pending_tx(&e));
+                }
+                Err(e) => {
+                    warn!("Carry {}: redeem attempt {} failed: {e}", p.market, p.attempts + 1);
+                    if let Some(gas) = reverted_gas(&e) {
+                        results.push(RedeemResult::Reverted { market: p.market.clone(), gas: gas.clone() });
+                    }
+                    if let Some(p) = self.failed(&p.market, &e.to_string(), Utc::now()) {
+                        warn!("Carry {}: giving up after {} attempts — ${} left to redeem by hand", p.market, p.attempts, p.shares);
+                        results.push(RedeemResult::Abandoned(p));
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    /// Redeem due redemptions every few seconds until shutdown, reporting
+    /// each outcome on the returned channel.
+    pub fn spawn_redeemer(
+        self: &Arc<Self>,
+        merger: PolygonMerger,
+        mut shutdown: broadcast::Receiver<()>,
+    ) -> mpsc::UnboundedReceiver<RedeemResult> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let book = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(REDEEM_TICK_SECS));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        for result in book.redeem_due(&merger, Utc::now()).await {
+                            let _ = tx.send(result);
+                        }
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+        rx
+    }
+
+    fn retry(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.config.retry_secs as i64)
+    }
+
+    fn save(&self, pending: &[PendingRedemption]) {
+        if let Err(e) = session::save(&self.path, &pending) {
+            warn!("Carry state not saved: {e}");
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<Vec<PendingRedemption>> {
+    let bytes = match std::fs::read(path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    let snapshot: Snapshot<Vec<PendingRedemption>> =
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+    Ok(snapshot.state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_carry_retry_give_up_and_resume() {
+        let dir = std::env::temp_dir().join(format!("carry-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = CarryConfig {
+            enabled: true,
+            state_path: dir.join("carry.json").to_string_lossy().into(),
+            retry_secs: 60,
+            max_attempts: 2,
+        };
+        let book = CarryBook::new(&config);
+        let now = Utc::now();
+        let secs = |s: i64| now + chrono::Duration::seconds(s);
+
+        // Same market twice adds up; nothing is due before the first retry interval
+        book.hold("m1", Some("0xabc"), Side::Yes, dec!(4), now);
+        book.hold("m1", Some("0xabc"), Side::Yes, dec!(1.5), now);
+        book.hold("m2", None, Side::No, dec!(3), now);
+        book.hold("m3", Some("0xdef"), Side::No, Decimal::ZERO, now);
+        assert_eq!(book.pending_value(), dec!(8.5));
+        assert!(book.due(secs(30)).is_empty());
+        let due = book.due(secs(60));
+        assert_eq!(due.len(), 1, "no conditionId: never redeemed automatically");
+        assert_eq!(due[0].shares, dec!(5.5));
+
+        // A redeem sent but unconfirmed is remembered across restarts and
+        // costs no attempt
+        book.in_flight("m1", Some("0xfeed"));
+        assert_eq!(CarryBook::new(&config).due(secs(60))[0].pending_tx.as_deref(), Some("0xfeed"));
+        assert_eq!(book.due(secs(60))[0].attempts, 0);
+        book.in_flight("m1", None);
+
+        // A failure pushes the next try out; the last allowed failure abandons it
+        assert_eq!(book.failed("m1", "execution reverted", secs(60)), None);
+        assert!(book.due(secs(90)).is_empty());
+        let abandoned = book.failed("m1", "execution reverted", secs(120)).unwrap();
+        assert_eq!(abandoned.attempts, 2);
+        assert!(book.due(secs(600)).is_empty());
+        assert_eq!(book.pending_value(), dec!(8.5), "abandoned is still owed");
+
+        // Restart resumes what's pending; redeeming removes it
+        let book = CarryBook::new(&config);
+        assert_eq!(book.pending().len(), 2);
+        assert_eq!(book.redeemed("m2").map(|p| p.shares), Some(dec!(3)));
+        assert_eq!(CarryBook::new(&config).pending_value(), dec!(5.5));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,153 @@
+use crate::config::{ExitLadderConfig, ExitPrice, ExitRung, ExitStyle};
+use crate::telemetry::hold_time::ExitReason;
+
+/// Where a position's resting exit should be.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitDecision {
+    pub style: ExitStyle,
+    pub price: f64,
+    pub reason: ExitReason,
+}
+
+/// What the ladder looks at for one position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitInputs {
+    pub remaining_secs: f64,
+    pub hold_secs: f64,
+    pub entry_price: f64,
+    pub bid: f64,
+    /// Learned max hold for this market; when set it replaces the
+    /// `hold_at_least` of the ladder's time-exit rungs
+    pub max_hold_secs: Option<f64>,
+}
+
+impl ExitInputs {
+    /// Bid relative to entry, e.g. -0.2 for 20% under.
+    pub fn pct_change(&self) -> f64 {
+        if self.entry_price > 0.0 {
+            (self.bid - self.entry_price) / self.entry_price
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Interprets the exit ladder from config: picks the first rung whose
+/// conditions hold and prices the exit it calls for.
+pub struct ExitManager {
+    rungs: Vec<ExitRung>,
+}
+
+impl ExitManager {
+    /// A ladder without an unconditional last rung gets the default one.
+    pub fn new(config: &ExitLadderConfig) -> Self {
+        let mut rungs = config.rungs.clone();
+        if !rungs.last().is_some_and(|r| r.is_unconditional()) {
+            rungs.extend(ExitLadderConfig::default().rungs.pop());
+        }
+        Self { rungs }
+    }
+
+    /// The exit the ladder wants for this position right now.
+    pub fn decide(&self, inputs: &ExitInputs) -> ExitDecision {
+        let pct = inputs.pct_change();
+        // Thresholds are nudged so a bid exactly on one isn't lost to float error
+        let hold_at_least = |r: &ExitRung| match inputs.max_hold_secs {
+            Some(max) if r.reason == ExitReason::Time && r.hold_at_least.is_some() => Some(max),
+            _ => r.hold_at_least,
+        };
+        let matches = |r: &&ExitRung| {
+            r.remaining_below.is_none_or(|s| inputs.remaining_secs < s)
+                && hold_at_least(r).is_none_or(|s| inputs.hold_secs >= s)
+                && r.pct_at_most.is_none_or(|p| pct <= p + 1e-9)
+                && r.pct_above.is_none_or(|p| pct > p + 1e-9)
+        };
+        let rung = self.rungs.iter().find(matches).unwrap_or(&self.rungs[self.rungs.len() - 1]);
+        let raw = match rung.price {
+            ExitPrice::Entry(mult) => inputs.entry_price * mult,
+            ExitPrice::Bid(mult) => inputs.bid * mult,
+            ExitPrice::Fixed(price) => price,
+        };
+        ExitDecision {
+            style: rung.style,
+            // Sells round down to the cent
+            price: ((raw * 100.0 + 1e-9).floor() / 100.0).clamp(0.01, 0.99),
+            reason: rung.reason,
+        }
+    }
+
+    /// The exit to rest right after entering at `entry_price`.
+    pub fn opening(&self, entry_price: f64) -> ExitDecision {
+        self.decide(&ExitInputs { remaining_secs: f64::INFINITY, hold_secs: 0.0, entry_price, bid: entry_price, max_hold_secs: None })
+    }
+
+    /// Whether a resting exit of style `current` (None if there's none)
+    /// should be replaced by `desired`: exits only escalate.
+    pub fn should_replace(current: Option<ExitStyle>, desired: ExitStyle) -> bool {
+        current.is_none_or(|c| desired > c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskConfig;
+
+    #[test]
+    fn test_default_ladder_matches_rungs_in_order() {
+        let mgr = ExitManager::new(&RiskConfig::default().exit_ladder);
+        let at = |remaining_secs, hold_secs, bid| {
+            mgr.decide(&ExitInputs { remaining_secs, hold_secs, entry_price: 0.50, bid, max_hold_secs: None })
+        };
+
+        let tp = at(200.0, 10.0, 0.50);
+        assert_eq!((tp.style, tp.price, tp.reason), (ExitStyle::Tp, 0.55, ExitReason::TakeProfit));
+        assert_eq!(mgr.opening(0.50), tp);
+        let sl = at(200.0, 10.0, 0.40);
+        assert_eq!((sl.style, sl.price, sl.reason), (ExitStyle::Sl, 0.20, ExitReason::StopLoss));
+        assert_eq!(at(80.0, 10.0, 0.52).reason, ExitReason::PreResolve);
+        assert_eq!(at(80.0, 10.0, 0.50).reason, ExitReason::TakeProfit);
+        assert_eq!(at(200.0, 120.0, 0.60).reason, ExitReason::Time);
+        // The deadline outranks everything
+        let force = at(20.0, 130.0, 0.30);
+        assert_eq!((force.style, force.price, force.reason), (ExitStyle::Force, 0.01, ExitReason::Force));
+
+        assert!(ExitManager::should_replace(None, ExitStyle::Tp));
+        assert!(ExitManager::should_replace(Some(ExitStyle::Tp), ExitStyle::Sl));
+        assert!(!ExitManager::should_replace(Some(ExitStyle::Force), ExitStyle::Sl));
+        assert!(!ExitManager::should_replace(Some(ExitStyle::Sl), ExitStyle::Sl));
+
+        // A ladder from config JSON
+        let json = r#"{"rungs": [
+            {"hold_at_least": 30, "style": "sl", "price": {"bid": 1.0}, "reason": "time"},
+            {"style": "tp", "price": {"entry": 1.5}, "reason": "tp"}
+        ]}"#;
+        let custom = ExitManager::new(&serde_json::from_str(json).unwrap());
+        let inputs = ExitInputs { remaining_secs: 200.0, hold_secs: 5.0, entry_price: 0.70, bid: 0.68, max_hold_secs: None };
+        assert_eq!(custom.decide(&inputs).price, 0.99);
+        let held = custom.decide(&ExitInputs { hold_secs: 30.0, ..inputs });
+        assert_eq!((held.style, held.price), (ExitStyle::Sl, 0.68));
+
+        // Without a catch-all rung, the default one is appended
+        let json = r#"{"rungs": [{"remaining_below": 60, "style": "force", "price": {"fixed": 0.01}, "reason": "force"}]}"#;
+        let partial = ExitManager::new(&serde_json::from_str(json).unwrap());
+        assert_eq!(partial.decide(&inputs).reason, ExitReason::TakeProfit);
+    }
+
+    #[test]
+    fn test_learned_max_hold_replaces_time_rung() {
+        let mgr = ExitManager::new(&RiskConfig::default().exit_ladder);
+        let at = |hold_secs, max_hold_secs| {
+            mgr.decide(&ExitInputs { remaining_secs: 200.0, hold_secs, entry_price: 0.50, bid: 0.50, max_hold_secs })
+                .reason
+        };
+
+        // A short half-life cuts the hold well before the ladder's 120s rung
+        assert_eq!(at(50.0, Some(45.0)), ExitReason::Time);
+        assert_eq!(at(40.0, Some(45.0)), ExitReason::TakeProfit);
+        // A long one keeps the position past it
+        assert_eq!(at(150.0, Some(300.0)), ExitReason::TakeProfit);
+        // Unknown: the ladder's own rung
+        assert_eq!(at(120.0, None), ExitReason::Time);
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+
+/// How often the risk watchdog runs `periodic_check`.
+pub const WATCHDOG_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// `last_beat_ms` before the watchdog starts
+const NOT_STARTED: i64 = i64::MIN;
+
+/// The veto flipped: trading is blocked, or allowed again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VetoChange {
+    /// The watchdog's last check is this many ms old
+    Vetoed(i64),
+    Recovered,
+}
+
+/// Proof of life from the risk watchdog.
+///
+/// Every completed `periodic_check` renews the token. If it hasn't been
+/// renewed within twice the watchdog interval — the watchdog stalled on a
+/// lock, or crashed and is waiting out its restart backoff — nothing is
+/// guarding drawdown and exposure, so new entries are vetoed until it
+/// checks in again; exits still go out. Nothing is vetoed until the
+/// watchdog is `start`ed — a risk manager no watchdog drives (a backtest,
+/// a tool) never goes stale — and starting renews it, so the first tick has
+/// time to run. Callers read time from the market clock, which can't be
+/// stepped by NTP into a false stall.
+pub struct Liveness {
+    last_beat_ms: AtomicI64,
+    max_age_ms: i64,
+    vetoing: AtomicBool,
+}
+
+impl Liveness {
+    pub fn new(interval: std::time::Duration) -> Self {
+        Self {
+            last_beat_ms: AtomicI64::new(NOT_STARTED),
+            max_age_ms: 2 * interval.as_millis() as i64,
+            vetoing: AtomicBool::new(false),
+        }
+    }
+
+    /// The watchdog task is running as of `now`: from here on, a missed
+    /// check vetoes.
+    pub fn start(&self, now: DateTime<Utc>) {
+        self.beat(now);
+    }
+
+    /// The watchdog finished a check at `now`.
+    pub fn beat(&self, now: DateTime<Utc>) {
+        self.last_beat_ms.store(now.timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Age of the last check in ms when it's too old to trade on.
+    pub fn stale_ms(&self, now: DateTime<Utc>) -> Option<i64> {
+        let last = self.last_beat_ms.load(Ordering::Relaxed);
+        if last == NOT_STARTED {
+            return None;
+        }
+        let age = now.timestamp_millis() - last;
+        (age > self.max_age_ms).then_some(age)
+    }
+
+    /// Refuse trading while the token is stale.
+    pub fn check(&self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        match self.stale_ms(now) {
+            Some(age) => anyhow::bail!("Risk watchdog last checked {age}ms ago — no new entries"),
+            None => Ok(()),
+        }
+    }
+
+    /// Poll the veto; returns the change when it starts or ends, so the
+    /// caller alerts once per stall rather than once per evaluation.
+    pub fn poll(&self, now: DateTime<Utc>) -> Option<VetoChange> {
+        let stale = self.stale_ms(now);
+        let was = self.vetoing.swap(stale.is_some(), Ordering::Relaxed);
+        match (was, stale) {
+            (false, Some(age)) => Some(VetoChange::Vetoed(age)),
+            (true, None) => Some(VetoChange::Recovered),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_veto_after_two_missed_intervals() {
+        let t0 = Utc::now();
+        let ms = |n: i64| t0 + chrono::Duration::milliseconds(n);
+        let live = Liveness::new(WATCHDOG_INTERVAL);
+
+        // Not started: nothing to veto on, however long it's been
+        assert!(live.check(ms(60_000)).is_ok());
+        live.start(t0);
+
+        // Up to 2× the interval since the last check is fine
+        assert!(live.check(ms(1_000)).is_ok());
+        assert_eq!(live.poll(ms(1_000)), None);
+
+        // Stalled: vetoed, reported once
+        assert_eq!(live.stale_ms(ms(1_200)), Some(1_200));
+        assert!(live.check(ms(1_200)).is_err());
+        assert_eq!(live.poll(ms(1_200)), Some(VetoChange::Vetoed(1_200)));
+        assert_eq!(live.poll(ms(5_000)), None);
+
+        // A completed check lifts it
+        live.beat(ms(5_100));
+        assert!(live.check(ms(5_300)).is_ok());
+        assert_eq!(live.poll(ms(5_300)), Some(VetoChange::Recovered));
+        assert_eq!(live.poll(ms(5_400)), None);
+    }
+}
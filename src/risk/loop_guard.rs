@@ -0,0 +1,198 @@
+use crate::config::RiskConfig;
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::models::position::strategy_bucket;
+use crate::telemetry::events::{self, RiskActionKind};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// An open → flat cycle on one market for one strategy family.
+#[derive(Debug, Clone)]
+struct RoundTrip {
+    closed_at: DateTime<Utc>,
+    trade_ids: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Churn {
+    /// Shares held from this family's fills, and the trades since it was last flat
+    held: Decimal,
+    open_trades: Vec<String>,
+    trips: VecDeque<RoundTrip>,
+    cooloff_until: Option<DateTime<Utc>>,
+}
+
+/// A loop the detector just broke.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopTrip {
+    pub market: String,
+    /// Strategy family (see `strategy_bucket`)
+    pub strategy: String,
+    pub round_trips: usize,
+    /// Fills making up the round trips, oldest first
+    pub trade_ids: Vec<String>,
+    pub cooloff_until: DateTime<Utc>,
+}
+
+impl std::fmt::Display for LoopTrip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} round trips by {} on {}, no entries until {} (trades: {})",
+            self.round_trips,
+            self.strategy,
+            self.market,
+            self.cooloff_until.format("%H:%M:%S"),
+            self.trade_ids.join(", ")
+        )
+    }
+}
+
+/// Catches enter → stop → re-enter churn: counts round trips per (market,
+/// strategy family) over a sliding window and, past the limit, blocks that
+/// family's entries on the market for a cooloff. Exits are never blocked.
+pub struct LoopDetector {
+    max_round_trips: usize,
+    window: chrono::Duration,
+    cooloff: chrono::Duration,
+    exempt: Vec<String>,
+    state: Mutex<HashMap<(String, &'static str), Churn>>,
+}
+
+impl LoopDetector {
+    pub fn new(config: &RiskConfig) -> Self {
+        Self {
+            max_round_trips: config.loop_max_round_trips,
+            window: chrono::Duration::seconds(config.loop_window_secs as i64),
+            cooloff: chrono::Duration::seconds(config.loop_cooloff_secs as i64),
+            exempt: config.loop_exempt.clone(),
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn watches(&self, family: &str) -> bool {
+        self.max_round_trips > 0 && !self.exempt.iter().any(|e| e == family)
+    }
+
+    /// Feed a fill; returns the loop when this fill completes one round trip
+    /// too many.
+    pub fn on_fill(
+        &self,
+        market: &str,
+        strategy_tag: &str,
+        side: OrderSide,
+        size: Decimal,
+        trade_id: &str,
+        at: DateTime<Utc>,
+    ) -> Option<LoopTrip> {
+        let family = strategy_bucket(strategy_tag);
+        if !self.watches(family) {
+            return None;
+        }
+        let mut state = self.state.lock().unwrap();
+        let churn = state.entry((market.to_string(), family)).or_default();
+        match side {
+            OrderSide::Buy => {
+                churn.held += size;
+                churn.open_trades.push(trade_id.to_string());
+                return None;
+            }
+            // Nothing of ours open: not the end of a round trip
+            OrderSide::Sell if churn.held <= Decimal::ZERO => return None,
+            OrderSide::Sell => {
+                churn.held -= size;
+                churn.open_trades.push(trade_id.to_string());
+            }
+        }
+        if churn.held > Decimal::ZERO {
+            return None;
+        }
+
+        churn.held = Decimal::ZERO;
+        churn.trips.push_back(RoundTrip { closed_at: at, trade_ids: std::mem::take(&mut churn.open_trades) });
+        while churn.trips.front().is_some_and(|t| at - t.closed_at > self.window) {
+            churn.trips.pop_front();
+        }
+        if churn.trips.len() <= self.max_round_trips {
+            return None;
+        }
+
+        let until = at + self.cooloff;
+        churn.cooloff_until = Some(until);
+        let trip = LoopTrip {
+            market: market.to_string(),
+            strategy: family.to_string(),
+            round_trips: churn.trips.len(),
+            trade_ids: churn.trips.drain(..).flat_map(|t| t.trade_ids).collect(),
+            cooloff_until: until,
+        };
+        events::RiskAction { action: RiskActionKind::LoopCooloff, reason: &trip.to_string(), market }.emit();
+        Some(trip)
+    }
+
+    /// Whether the family behind `strategy_tag` is cooling off on `market`.
+    pub fn is_cooling(&self, market: &str, strategy_tag: &str, now: DateTime<Utc>) -> bool {
+        let key = (market.to_string(), strategy_bucket(strategy_tag));
+        self.state
+            .lock()
+            .unwrap()
+            .get(&key)
+            .and_then(|c| c.cooloff_until)
+            .is_some_and(|until| now < until)
+    }
+
+    /// Reject entries from a family that's cooling off on `market`.
+    pub fn check(&self, market: &str, order: &OrderIntent) -> anyhow::Result<()> {
+        if order.order_side == OrderSide::Buy && self.is_cooling(market, &order.strategy_tag, Utc::now()) {
+            anyhow::bail!("{} is cooling off on {market} after a trade loop", strategy_bucket(&order.strategy_tag));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_churn_trips_cooloff() {
+        let config = RiskConfig { loop_max_round_trips: 2, ..RiskConfig::default() };
+        let detector = LoopDetector::new(&config);
+        let t0 = Utc::now();
+        let at = |s: i64| t0 + chrono::Duration::seconds(s);
+
+        // Two round trips, one scaled out in pieces, are within the limit
+        assert!(detector.on_fill("m", "lag_exploit", OrderSide::Buy, dec!(10), "b1", at(0)).is_none());
+        assert!(detector.on_fill("m", "lag_exploit", OrderSide::Sell, dec!(10), "s1", at(5)).is_none());
+        assert!(detector.on_fill("m", "lag_exploit", OrderSide::Buy, dec!(10), "b2", at(10)).is_none());
+        assert!(detector.on_fill("m", "lag_exploit", OrderSide::Sell, dec!(4), "s2", at(12)).is_none());
+        assert!(detector.on_fill("m", "lag_exploit", OrderSide::Sell, dec!(6), "s3", at(15)).is_none());
+        assert!(!detector.is_cooling("m", "lag_exploit", at(15)));
+
+        // The third trips it
+        detector.on_fill("m", "lag_exploit", OrderSide::Buy, dec!(10), "b3", at(20));
+        let trip = detector.on_fill("m", "lag_exploit", OrderSide::Sell, dec!(10), "s4", at(25)).unwrap();
+        assert_eq!(trip.round_trips, 3);
+        assert_eq!(trip.trade_ids, ["b1", "s1", "b2", "s2", "s3", "b3", "s4"]);
+        assert!(detector.is_cooling("m", "lag_exploit", at(26)));
+        assert!(!detector.is_cooling("m", "lag_exploit", trip.cooloff_until));
+        assert!(!detector.is_cooling("other", "lag_exploit", at(26)));
+        assert!(!detector.is_cooling("m", "momentum", at(26)));
+
+        // Market making round-trips by design
+        for i in 0..10 {
+            detector.on_fill("m", "mm_bid", OrderSide::Buy, dec!(5), "mb", at(i));
+            assert!(detector.on_fill("m", "mm_ask", OrderSide::Sell, dec!(5), "ma", at(i)).is_none());
+        }
+
+        // Round trips spread beyond the window don't add up
+        let slow = LoopDetector::new(&config);
+        for i in 0..5 {
+            let t = at(i * (config.loop_window_secs as i64 + 1));
+            slow.on_fill("m", "momentum", OrderSide::Buy, dec!(1), "b", t);
+            assert!(slow.on_fill("m", "momentum", OrderSide::Sell, dec!(1), "s", t).is_none());
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::config::RiskConfig;
+use crate::models::market::{Asset, Duration};
+use crate::telemetry::events::{self, RiskActionKind};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Debug, Default)]
+struct SeriesLoss {
+    /// Realized P&L since the session started or the last stop expired
+    pnl: f64,
+    /// None = trading; MAX_UTC = stopped for the session
+    stopped_until: Option<DateTime<Utc>>,
+}
+
+/// A market series the stop just took out of rotation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketStopTrip {
+    pub asset: Asset,
+    pub duration: Duration,
+    pub pnl: f64,
+    /// None when stopped for the rest of the session
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl std::fmt::Display for MarketStopTrip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{} lost ${:.2}, ", self.asset.slug_prefix(), self.duration.slug_suffix(), -self.pnl)?;
+        match self.until {
+            Some(until) => write!(f, "abandoned until {}", until.format("%H:%M:%S")),
+            None => write!(f, "abandoned for the session"),
+        }
+    }
+}
+
+/// Per-series session loss limit: once realized losses in one (asset,
+/// duration) series reach `market_max_loss_usdc`, that series takes no new
+/// entries for `market_stop_mins` (0 = the rest of the session), independent
+/// of the portfolio-wide daily loss limit. Positions it already holds are
+/// still exited.
+pub struct MarketStop {
+    max_loss: f64,
+    stop_for: Option<chrono::Duration>,
+    series: Mutex<HashMap<(Asset, Duration), SeriesLoss>>,
+}
+
+impl MarketStop {
+    pub fn new(config: &RiskConfig) -> Self {
+        Self {
+            max_loss: config.market_max_loss_usdc,
+            stop_for: (config.market_stop_mins > 0).then(|| chrono::Duration::minutes(config.market_stop_mins as i64)),
+            series: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Add realized P&L for a series; returns the stop when this pushes its
+    /// losses over the limit.
+    pub fn record_pnl(&self, asset: Asset, duration: Duration, pnl: f64, now: DateTime<Utc>) -> Option<MarketStopTrip> {
+        if self.max_loss <= 0.0 {
+            return None;
+        }
+        let mut series = self.series.lock().unwrap();
+        let loss = series.entry((asset, duration)).or_default();
+        if loss.stopped_until.is_some_and(|until| now >= until) {
+            *loss = SeriesLoss::default();
+        }
+        loss.pnl += pnl;
+        if loss.stopped_until.is_some() || loss.pnl > -self.max_loss {
+            return None;
+        }
+
+        let until = self.stop_for.map(|d| now + d);
+        loss.stopped_until = Some(until.unwrap_or(DateTime::<Utc>::MAX_UTC));
+        let trip = MarketStopTrip { asset, duration, pnl: loss.pnl, until };
+        let market = format!("{}-{}", asset.slug_prefix(), duration.slug_suffix());
+        events::RiskAction { action: RiskActionKind::MarketStop, reason: &trip.to_string(), market: &market }.emit();
+        Some(trip)
+    }
+
+    /// Whether the series is barred from new entries.
+    pub fn is_stopped(&self, asset: Asset, duration: Duration, now: DateTime<Utc>) -> bool {
+        self.series
+            .lock()
+            .unwrap()
+            .get(&(asset, duration))
+            .and_then(|l| l.stopped_until)
+            .is_some_and(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_stops_after_loss_limit() {
+        let config = RiskConfig { market_max_loss_usdc: 2.0, market_stop_mins: 30, ..RiskConfig::default() };
+        let stop = MarketStop::new(&config);
+        let now = Utc::now();
+
+        assert!(stop.record_pnl(Asset::BTC, Duration::FiveMin, -1.5, now).is_none());
+        assert!(stop.record_pnl(Asset::BTC, Duration::FiveMin, 0.5, now).is_none());
+        // The global book doesn't matter: another series' wins don't offset
+        assert!(stop.record_pnl(Asset::ETH, Duration::FiveMin, 10.0, now).is_none());
+        let trip = stop.record_pnl(Asset::BTC, Duration::FiveMin, -1.0, now).unwrap();
+        assert_eq!(trip.pnl, -2.0);
+        assert!(stop.is_stopped(Asset::BTC, Duration::FiveMin, now));
+        assert!(!stop.is_stopped(Asset::BTC, Duration::FifteenMin, now));
+        assert!(stop.record_pnl(Asset::BTC, Duration::FiveMin, -1.0, now).is_none(), "trips once");
+
+        // Back in rotation with a clean slate once the stop expires
+        let later = now + chrono::Duration::minutes(31);
+        assert!(!stop.is_stopped(Asset::BTC, Duration::FiveMin, later));
+        assert!(stop.record_pnl(Asset::BTC, Duration::FiveMin, -1.0, later).is_none());
+
+        // 0 minutes = the rest of the session
+        let session = MarketStop::new(&RiskConfig { market_stop_mins: 0, ..config });
+        assert_eq!(session.record_pnl(Asset::SOL, Duration::FifteenMin, -5.0, now).unwrap().until, None);
+        assert!(session.is_stopped(Asset::SOL, Duration::FifteenMin, now + chrono::Duration::days(1)));
+    }
+}
@@ -1,3 +1,12 @@
 pub mod position_manager;
 pub mod risk_manager;
+pub mod liveness;
 pub mod sizing;
+pub mod resolution_guard;
+pub mod loop_guard;
+pub mod market_stop;
+pub mod exit_manager;
+pub mod safe_mode;
+pub mod canary;
+pub mod approval;
+pub mod carry;
@@ -1,28 +1,119 @@
-use crate::models::market::Side;
-use crate::models::order::{Fill, OrderSide};
-use crate::models::position::{Portfolio, Position};
-use chrono::Utc;
-use rust_decimal::Decimal;
+use crate::config::{CapitalBucketConfig, CompoundingConfig, CompoundingMode, RiskConfig, ScaleInConfig};
+use crate::execution::polygon_merger::GasSpend;
+use crate::models::market::{Market, Side};
+use crate::models::order::{Fill, OrderIntent, OrderSide};
+use crate::models::position::{
+    is_scale_in, scale_in_tag, strategy_bucket, CapitalBucket, Portfolio, Position, SettlingAsset, SettlingKind,
+};
+use crate::risk::canary::CanaryGate;
+use crate::risk::carry::{CarryBook, PendingRedemption};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 /// Tracks all positions across all active markets.
 ///
 /// Thread-safe via RwLock — reads are concurrent, writes are serialized.
 pub struct PositionManager {
     pub portfolio: Arc<RwLock<Portfolio>>,
+    compounding: CompoundingConfig,
+    buckets: CapitalBucketConfig,
+    /// Probation for canary strategy families; fed every realized close
+    canary: Option<Arc<CanaryGate>>,
+    /// When set, resolution payouts wait here for on-chain redemption
+    carry: Option<Arc<CarryBook>>,
+    /// Longest sell proceeds stay settling before they're spendable; zero = at once
+    settlement_hold: chrono::Duration,
 }
 
 impl PositionManager {
     pub fn new(starting_capital: Decimal) -> Self {
+        Self::build(starting_capital, CompoundingConfig::default(), CapitalBucketConfig::default())
+    }
+
+    pub fn with_compounding(starting_capital: Decimal, compounding: CompoundingConfig) -> Self {
+        Self::build(starting_capital, compounding, CapitalBucketConfig::default())
+    }
+
+    /// Build with the compounding policy and strategy buckets from risk config.
+    pub fn from_config(starting_capital: Decimal, config: &RiskConfig) -> Self {
+        let mut pm = Self::build(starting_capital, config.compounding.clone(), config.buckets.clone());
+        pm.settlement_hold = chrono::Duration::seconds(config.settlement_hold_secs as i64);
+        if !config.canary.strategies.is_empty() {
+            pm.canary = Some(Arc::new(CanaryGate::new(&config.canary)));
+        }
+        pm
+    }
+
+    /// Carry resolution payouts as pending redemption instead of crediting
+    /// them as cash; `record_redeemed` credits them once redeemed.
+    /// Redemptions an earlier run left pending are settling from the start.
+    pub fn with_carry(mut self, carry: Arc<CarryBook>) -> Self {
+        if let Ok(mut portfolio) = self.portfolio.try_write() {
+            for p in carry.pending() {
+                portfolio.settling.push(SettlingAsset {
+                    id: p.market.clone(),
+                    market_id: p.market,
+                    kind: SettlingKind::Redemption,
+                    value: p.shares,
+                    since: p.resolved_at,
+                });
+            }
+        }
+        self.carry = Some(carry);
+        self
+    }
+
+    pub fn carry(&self) -> Option<Arc<CarryBook>> {
+        self.carry.clone()
+    }
+
+    /// Canary probation, when any strategy family is on it.
+    pub fn canary(&self) -> Option<Arc<CanaryGate>> {
+        self.canary.clone()
+    }
+
+    fn record_canary(&self, strategy_tag: &str, pnl: Decimal) {
+        if let Some(canary) = &self.canary {
+            canary.record_pnl(strategy_tag, pnl.to_string().parse().unwrap_or(0.0), Utc::now());
+        }
+    }
+
+    fn build(
+        starting_capital: Decimal,
+        compounding: CompoundingConfig,
+        buckets: CapitalBucketConfig,
+    ) -> Self {
+        let mut portfolio = Portfolio::new(starting_capital);
+        if buckets.enabled {
+            for (name, pct) in buckets.shares() {
+                portfolio.buckets.insert(
+                    name.to_string(),
+                    CapitalBucket {
+                        allocated: starting_capital
+                            * Decimal::from_f64(pct).unwrap_or(Decimal::ZERO),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
         Self {
-            portfolio: Arc::new(RwLock::new(Portfolio::new(starting_capital))),
+            portfolio: Arc::new(RwLock::new(portfolio)),
+            compounding,
+            buckets,
+            canary: None,
+            carry: None,
+            settlement_hold: chrono::Duration::zero(),
         }
     }
 
-    /// Record a new fill and update positions.
-    pub async fn record_fill(&self, fill: &Fill, market_id: &str, side: Side, strategy_tag: &str) {
+    /// Record a new fill and update positions. Returns the P&L a sell realized.
+    pub async fn record_fill(&self, fill: &Fill, market_id: &str, side: Side, strategy_tag: &str) -> Option<Decimal> {
         let mut portfolio = self.portfolio.write().await;
 
         // Check if we already have a position in this token
@@ -37,9 +128,13 @@ impl PositionManager {
                     // Add to existing position (average in)
                     let total_cost = pos.avg_entry_price * pos.size + fill.price * fill.size;
                     pos.size += fill.size;
+                    pos.entry_fees += fill.fee;
                     if pos.size > Decimal::ZERO {
                         pos.avg_entry_price = total_cost / pos.size;
                     }
+                    if is_scale_in(strategy_tag) {
+                        pos.adds += 1;
+                    }
                 } else {
                     // New position
                     portfolio.positions.push(Position {
@@ -51,23 +146,49 @@ impl PositionManager {
                         unrealized_pnl: Decimal::ZERO,
                         strategy_tag: strategy_tag.to_string(),
                         opened_at: Utc::now(),
+                        adds: 0,
+                        entry_fees: fill.fee,
                     });
                 }
 
                 // Deduct capital
                 let cost = fill.price * fill.size + fill.fee;
                 portfolio.capital -= cost;
+                portfolio.fees.accrue(strategy_tag, market_id, fill.fee);
             }
             OrderSide::Sell => {
                 if let Some(pos) = existing {
                     let sell_proceeds = fill.price * fill.size - fill.fee;
                     let cost_basis = pos.avg_entry_price * fill.size;
-                    let pnl = sell_proceeds - cost_basis;
+                    // The sold part's share of what entering cost in fees
+                    let entry_fee = if pos.size > Decimal::ZERO {
+                        pos.entry_fees * fill.size.min(pos.size) / pos.size
+                    } else {
+                        Decimal::ZERO
+                    };
+                    let pnl = sell_proceeds - cost_basis - entry_fee;
+                    let strategy_tag = pos.strategy_tag.clone();
 
                     pos.size -= fill.size;
+                    pos.entry_fees -= entry_fee;
+                    portfolio.fees.accrue(&strategy_tag, market_id, fill.fee);
+                    portfolio.fees.charged += fill.fee + entry_fee;
+                    portfolio.record_bucket_pnl(&strategy_tag, pnl);
+                    portfolio.record_entry_pnl(&strategy_tag, pnl);
+                    self.record_canary(&strategy_tag, pnl);
 
-                    // Add proceeds back to capital
-                    portfolio.capital += sell_proceeds;
+                    // Add proceeds back to capital, or hold them until the trade settles
+                    if self.settlement_hold > chrono::Duration::zero() {
+                        portfolio.hold_settling(SettlingAsset {
+                            id: fill.order_id.clone(),
+                            market_id: market_id.to_string(),
+                            kind: SettlingKind::Settlement,
+                            value: sell_proceeds,
+                            since: fill.timestamp,
+                        });
+                    } else {
+                        portfolio.capital += sell_proceeds;
+                    }
                     portfolio.daily_pnl += pnl;
                     portfolio.total_pnl += pnl;
                     portfolio.total_trades += 1;
@@ -80,23 +201,103 @@ impl PositionManager {
                     }
 
                     info!(
-                        "Closed position: market={market_id} pnl={pnl} daily_pnl={}",
+                        "Closed position: market={market_id} pnl={pnl} fees={} daily_pnl={}",
+                        fill.fee + entry_fee,
                         portfolio.daily_pnl
                     );
+                    return Some(pnl);
                 }
             }
         }
+        None
+    }
+
+    /// Vet buys into positions their strategy family already holds on
+    /// `market_id`. For families allowed to scale in, each becomes an add-on
+    /// (tagged with `SCALE_IN_TAG`) that must beat the blended entry price by
+    /// `min_improvement` and fit within `max_adds` and `max_total_cost_usdc`,
+    /// trimmed to the remaining budget; anything else into a held position is
+    /// dropped. Intents opening new positions pass through.
+    pub async fn apply_scale_in(&self, market_id: &str, config: &ScaleInConfig, intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        if !config.enabled {
+            return intents;
+        }
+        let portfolio = self.portfolio.read().await;
+        let max_cost = Decimal::from_f64_retain(config.max_total_cost_usdc).unwrap_or(Decimal::ZERO);
+        let improvement = Decimal::from_f64_retain(config.min_improvement).unwrap_or(Decimal::ZERO);
+        // Adds and cost committed per token earlier in this batch
+        let mut pending: HashMap<String, (u32, Decimal)> = HashMap::new();
+
+        let mut kept = Vec::with_capacity(intents.len());
+        for mut intent in intents {
+            let family = strategy_bucket(&intent.strategy_tag);
+            let held = portfolio.positions.iter().find(|p| {
+                p.market_id == market_id
+                    && p.token_id == intent.token_id
+                    && p.size > Decimal::ZERO
+                    && strategy_bucket(&p.strategy_tag) == family
+            });
+            let Some(pos) = held.filter(|_| intent.order_side == OrderSide::Buy) else {
+                kept.push(intent);
+                continue;
+            };
+            if !config.strategies.iter().any(|s| s == family) {
+                kept.push(intent);
+                continue;
+            }
+
+            let (adds, cost) = pending.entry(intent.token_id.clone()).or_default();
+            let budget = max_cost - pos.cost_basis() - *cost;
+            if pos.adds + *adds >= config.max_adds
+                || intent.price > pos.avg_entry_price - improvement
+                || intent.price <= Decimal::ZERO
+                || budget <= Decimal::ZERO
+            {
+                debug!(
+                    "Scale-in rejected: {} {}@{} vs avg {} (adds={}, cost={})",
+                    intent.strategy_tag,
+                    intent.size,
+                    intent.price,
+                    pos.avg_entry_price,
+                    pos.adds + *adds,
+                    pos.cost_basis() + *cost
+                );
+                continue;
+            }
+            let max_size = (budget / intent.price).round_dp_with_strategy(2, RoundingStrategy::ToZero);
+            intent.size = intent.size.min(max_size);
+            if intent.size <= Decimal::ZERO {
+                continue;
+            }
+            *adds += 1;
+            *cost += intent.price * intent.size;
+            intent.strategy_tag = scale_in_tag(&intent.strategy_tag);
+            kept.push(intent);
+        }
+        kept
     }
 
     /// Record a market resolution (payout).
     /// - If we hold YES tokens and market resolves UP: payout = size * $1
     /// - If we hold NO tokens and market resolves DOWN: payout = size * $1
     /// - Otherwise: tokens are worthless
+    ///
+    /// Returns the realized P&L for the market.
     pub async fn record_resolution(
         &self,
         market_id: &str,
         winning_side: Side,
-    ) {
+    ) -> Decimal {
+        self.resolve(market_id, None, winning_side).await
+    }
+
+    /// `record_resolution` for a market whose conditionId is known, so a
+    /// carried payout can be redeemed automatically.
+    pub async fn record_market_resolution(&self, market: &Market, winning_side: Side) -> Decimal {
+        self.resolve(&market.slug, market.condition_id.as_deref(), winning_side).await
+    }
+
+    async fn resolve(&self, market_id: &str, condition_id: Option<&str>, winning_side: Side) -> Decimal {
         let mut portfolio = self.portfolio.write().await;
 
         let mut pnl = Decimal::ZERO;
@@ -104,25 +305,31 @@ impl PositionManager {
         let mut wins: u64 = 0;
         let mut losses: u32 = 0;
         let mut trades: u64 = 0;
+        let mut bucket_pnl: Vec<(String, Decimal)> = Vec::new();
 
         // First pass: compute resolution results from positions
+        let mut fees = Decimal::ZERO;
         for pos in portfolio.positions.iter().filter(|p| p.market_id == market_id) {
             trades += 1;
+            fees += pos.entry_fees;
             if pos.side == winning_side {
                 let payout = pos.size;
-                let profit = payout - pos.cost_basis();
+                let profit = payout - pos.cost_basis() - pos.entry_fees;
                 pnl += profit;
                 capital_delta += payout;
                 wins += 1;
+                bucket_pnl.push((pos.strategy_tag.clone(), profit));
             } else {
-                let loss = pos.cost_basis();
+                let loss = pos.cost_basis() + pos.entry_fees;
                 pnl -= loss;
                 losses += 1;
+                bucket_pnl.push((pos.strategy_tag.clone(), -loss));
             }
         }
 
         // Remove resolved positions
         portfolio.positions.retain(|p| p.market_id != market_id);
+        portfolio.fees.charged += fees;
 
         // First pass: compute resolution results from straddles
         for s in portfolio.straddles.iter().filter(|s| s.market_id == market_id) {
@@ -152,13 +359,33 @@ impl PositionManager {
 
             pnl += straddle_profit + excess_pnl - excess_cost;
             capital_delta += straddle_payout + excess_pnl;
+            bucket_pnl.push((s.strategy_tag.clone(), straddle_profit + excess_pnl - excess_cost));
         }
 
         // Remove resolved straddles
         portfolio.straddles.retain(|s| s.market_id != market_id);
 
         // Apply aggregated mutations
-        portfolio.capital += capital_delta;
+        for (tag, bucket_delta) in &bucket_pnl {
+            portfolio.record_bucket_pnl(tag, *bucket_delta);
+            portfolio.record_entry_pnl(tag, *bucket_delta);
+            self.record_canary(tag, *bucket_delta);
+        }
+        // Winning tokens pay out only once redeemed; until then they're carried
+        match &self.carry {
+            Some(carry) if capital_delta > Decimal::ZERO => {
+                let now = Utc::now();
+                carry.hold(market_id, condition_id, winning_side, capital_delta, now);
+                portfolio.hold_settling(SettlingAsset {
+                    id: market_id.to_string(),
+                    market_id: market_id.to_string(),
+                    kind: SettlingKind::Redemption,
+                    value: capital_delta,
+                    since: now,
+                });
+            }
+            _ => portfolio.capital += capital_delta,
+        }
         portfolio.total_trades += trades;
         portfolio.winning_trades += wins;
         if losses > 0 {
@@ -173,16 +400,237 @@ impl PositionManager {
             "Resolution: market={market_id} winner={:?} pnl={pnl} capital={}",
             winning_side, portfolio.capital
         );
+
+        pnl
+    }
+
+    /// Credit a carried payout that has been redeemed on-chain.
+    pub async fn record_redeemed(&self, redemption: &PendingRedemption) {
+        let mut portfolio = self.portfolio.write().await;
+        portfolio.take_settling(&redemption.market, SettlingKind::Redemption);
+        portfolio.capital += redemption.shares;
+        info!(
+            "Redeemed {}: +${} capital={}",
+            redemption.market, redemption.shares, portfolio.capital
+        );
+    }
+
+    /// A sell's trade confirmed on-chain: its proceeds are spendable.
+    pub async fn release_settlement(&self, order_id: &str) -> Option<Decimal> {
+        let mut portfolio = self.portfolio.write().await;
+        let value = portfolio.take_settling(order_id, SettlingKind::Settlement)?;
+        portfolio.capital += value;
+        debug!("Settled {order_id}: +${value} capital={}", portfolio.capital);
+        Some(value)
+    }
+
+    /// A sell's trade failed on-chain: its proceeds never arrive. The tokens
+    /// weren't sold either, but the position isn't restored — the next
+    /// balance sync corrects capital.
+    pub async fn fail_settlement(&self, order_id: &str) -> Option<Decimal> {
+        let value = self.portfolio.write().await.take_settling(order_id, SettlingKind::Settlement)?;
+        warn!("Trade for {order_id} failed on-chain — ${value} of sell proceeds dropped");
+        Some(value)
+    }
+
+    /// Credit sell proceeds whose confirmation hasn't been seen within the
+    /// settlement hold; they're assumed settled. Returns the total released.
+    pub async fn release_overdue_settlements(&self, now: DateTime<Utc>) -> Decimal {
+        let cutoff = now - self.settlement_hold;
+        let mut portfolio = self.portfolio.write().await;
+        let (overdue, keep): (Vec<_>, Vec<_>) = std::mem::take(&mut portfolio.settling)
+            .into_iter()
+            .partition(|a| a.kind == SettlingKind::Settlement && a.since <= cutoff);
+        portfolio.settling = keep;
+        let released: Decimal = overdue.iter().map(|a| a.value).sum();
+        portfolio.capital += released;
+        if !overdue.is_empty() {
+            debug!("{} unconfirmed sells assumed settled: +${released}", overdue.len());
+        }
+        released
     }
 
     /// Get current available capital.
+    /// Under the sweep policy, trading capital is capped at the watermark.
     pub async fn available_capital(&self) -> f64 {
         let portfolio = self.portfolio.read().await;
-        portfolio
+        let capital = portfolio
             .capital
             .to_string()
             .parse::<f64>()
-            .unwrap_or(0.0)
+            .unwrap_or(0.0);
+
+        match self.compounding.mode {
+            CompoundingMode::Compound => capital,
+            CompoundingMode::Sweep => capital.min(self.watermark(&portfolio)),
+        }
+    }
+
+    /// Capital level above which profits are swept.
+    fn watermark(&self, portfolio: &Portfolio) -> f64 {
+        if self.compounding.watermark > 0.0 {
+            self.compounding.watermark
+        } else {
+            portfolio
+                .starting_capital
+                .to_string()
+                .parse::<f64>()
+                .unwrap_or(0.0)
+        }
+    }
+
+    /// Profit currently eligible for a sweep (0 if none or below the minimum).
+    /// Only free cash counts — open positions are never swept.
+    pub async fn sweepable_amount(&self) -> f64 {
+        if self.compounding.mode != CompoundingMode::Sweep {
+            return 0.0;
+        }
+        let portfolio = self.portfolio.read().await;
+        let capital = portfolio.capital.to_string().parse::<f64>().unwrap_or(0.0);
+        let excess = capital - self.watermark(&portfolio);
+        let amount = (excess * self.compounding.sweep_fraction * 100.0).floor() / 100.0;
+        if amount < self.compounding.min_sweep_usdc {
+            return 0.0;
+        }
+        amount
+    }
+
+    /// Charge the gas of an on-chain transaction to `strategy_tag`'s P&L.
+    /// The MATIC came out of the signing wallet, not trading capital.
+    pub async fn record_gas(&self, strategy_tag: &str, market_id: &str, gas: &GasSpend) -> Decimal {
+        let usd = Decimal::from_f64_retain(gas.usd()).unwrap_or(Decimal::ZERO).round_dp(6);
+        if usd.is_zero() {
+            return usd;
+        }
+        let mut portfolio = self.portfolio.write().await;
+        portfolio.gas.accrue(strategy_tag, market_id, usd);
+        portfolio.gas.charged += usd;
+        portfolio.daily_pnl -= usd;
+        portfolio.total_pnl -= usd;
+        if let Some(bucket) = portfolio.buckets.get_mut(strategy_bucket(strategy_tag)) {
+            bucket.realized_pnl -= usd;
+        }
+        info!(
+            "{} gas: ${usd} ({:.5} MATIC) charged to {strategy_tag} tx={} total_pnl={}",
+            gas.label, gas.matic(), gas.tx, portfolio.total_pnl
+        );
+        usd
+    }
+
+    /// Record a completed sweep: remove the amount from trading capital.
+    pub async fn record_sweep(&self, amount: f64) {
+        let amount = Decimal::from_f64_retain(amount).unwrap_or(Decimal::ZERO);
+        let mut portfolio = self.portfolio.write().await;
+        portfolio.capital -= amount;
+        portfolio.swept_total += amount;
+        info!(
+            "Swept ${amount} — capital={} swept_total={}",
+            portfolio.capital, portfolio.swept_total
+        );
+    }
+
+    /// Undo `record_sweep` for a transfer known not to have gone through.
+    pub async fn restore_sweep(&self, amount: f64) {
+        let amount = Decimal::from_f64_retain(amount).unwrap_or(Decimal::ZERO);
+        let mut portfolio = self.portfolio.write().await;
+        portfolio.capital += amount;
+        portfolio.swept_total -= amount;
+        info!(
+            "Sweep of ${amount} undone — capital={} swept_total={}",
+            portfolio.capital, portfolio.swept_total
+        );
+    }
+
+    /// Total profit swept out of trading capital.
+    pub async fn swept_total(&self) -> Decimal {
+        self.portfolio.read().await.swept_total
+    }
+
+    /// Capital a strategy may still commit from its bucket (None if unsegregated).
+    pub async fn bucket_available(&self, strategy_tag: &str) -> Option<f64> {
+        self.portfolio
+            .read()
+            .await
+            .bucket_available(strategy_tag)
+            .map(|d| d.to_string().parse::<f64>().unwrap_or(0.0))
+    }
+
+    /// Reset bucket allocations to their target shares of total bucket equity,
+    /// folding realized P&L back in. Only runs when some bucket has drifted
+    /// more than `rebalance_min_drift` from its target. Returns true if rebalanced.
+    pub async fn rebalance_buckets(&self) -> bool {
+        if !self.buckets.enabled {
+            return false;
+        }
+        let mut portfolio = self.portfolio.write().await;
+        let total_equity: Decimal = portfolio.buckets.values().map(|b| b.equity()).sum();
+        if total_equity <= Decimal::ZERO {
+            return false;
+        }
+
+        let targets: Vec<(&str, Decimal)> = self
+            .buckets
+            .shares()
+            .iter()
+            .map(|(name, pct)| {
+                (*name, total_equity * Decimal::from_f64(*pct).unwrap_or(Decimal::ZERO))
+            })
+            .collect();
+
+        let drift = Decimal::from_f64_retain(self.buckets.rebalance_min_drift).unwrap_or(Decimal::ZERO);
+        let drifted = targets.iter().any(|(name, target)| {
+            let equity = portfolio.buckets.get(*name).map(|b| b.equity()).unwrap_or_default();
+            *target > Decimal::ZERO && ((equity - *target).abs() / *target) > drift
+        });
+        if !drifted {
+            return false;
+        }
+
+        for (name, target) in targets {
+            let bucket = portfolio.buckets.entry(name.to_string()).or_default();
+            bucket.allocated = target;
+            bucket.realized_pnl = Decimal::ZERO;
+        }
+        info!("Rebalanced strategy buckets across equity={total_equity}");
+        true
+    }
+
+    /// Log allocation, exposure and P&L per strategy bucket.
+    pub async fn log_bucket_summary(&self) {
+        let portfolio = self.portfolio.read().await;
+        let mut names: Vec<&String> = portfolio.buckets.keys().collect();
+        names.sort();
+        for name in names {
+            let bucket = &portfolio.buckets[name];
+            info!(
+                "  Bucket {:<9} alloc=${:.2} pnl=${:.2} exposure=${:.2} trades={}",
+                name,
+                bucket.allocated,
+                bucket.realized_pnl,
+                portfolio.bucket_exposure(name),
+                bucket.trades,
+            );
+        }
+    }
+
+    /// Log realized results of fresh vs mid-cycle entries.
+    pub async fn log_entry_cohorts(&self) {
+        let portfolio = self.portfolio.read().await;
+        for (label, cohort) in [
+            ("fresh", &portfolio.fresh_entries),
+            ("mid-cycle", &portfolio.mid_cycle_entries),
+        ] {
+            if cohort.trades == 0 {
+                continue;
+            }
+            info!(
+                "  Entries {:<9} trades={} win={:.0}% pnl=${:.2}",
+                label,
+                cohort.trades,
+                cohort.win_rate() * 100.0,
+                cohort.pnl,
+            );
+        }
     }
 
     /// Get total exposure.
@@ -219,6 +667,23 @@ impl PositionManager {
         net
     }
 
+    /// Shares booked per token id across `markets`, straddle legs included.
+    /// What the chain should show for the token balances we hold.
+    pub async fn token_holdings(&self, markets: &DashMap<String, Market>) -> HashMap<String, Decimal> {
+        let portfolio = self.portfolio.read().await;
+        let mut held: HashMap<String, Decimal> = HashMap::new();
+        for pos in &portfolio.positions {
+            *held.entry(pos.token_id.clone()).or_default() += pos.size;
+        }
+        for s in &portfolio.straddles {
+            if let Some(market) = markets.get(&s.market_id) {
+                *held.entry(market.yes_token_id.clone()).or_default() += s.yes_size;
+                *held.entry(market.no_token_id.clone()).or_default() += s.no_size;
+            }
+        }
+        held
+    }
+
     /// Sync capital from on-chain USDC balance (for compounding).
     /// Only updates if the fetched balance is reasonable (>0 and different from current).
     pub async fn sync_capital_from_balance(&self, on_chain_balance: f64) {
@@ -229,15 +694,25 @@ impl PositionManager {
         let mut portfolio = self.portfolio.write().await;
         let current = portfolio.capital.to_string().parse::<f64>().unwrap_or(0.0);
 
+        // Sell proceeds and carried payouts are credited when they're released
+        // or redeemed; if the chain already shows them, taking them as capital
+        // now would count them twice
+        let settling = portfolio
+            .settling_value(None)
+            .to_string()
+            .parse::<f64>()
+            .unwrap_or(0.0);
+        let free = on_chain_balance - settling;
+
         // Only sync if there's a meaningful difference (>1 cent)
         // This prevents overwriting in-flight capital deductions
         let exposure = portfolio.total_exposure().to_string().parse::<f64>().unwrap_or(0.0);
-        let expected = on_chain_balance + exposure; // on-chain = free cash, we track cash + positions
+        let expected = free + exposure; // on-chain = free cash, we track cash + positions
 
         if (expected - current).abs() > 0.01 {
-            let new_capital = Decimal::from_f64_retain(on_chain_balance).unwrap_or(portfolio.capital);
+            let new_capital = Decimal::from_f64_retain(free).unwrap_or(portfolio.capital);
             tracing::info!(
-                "Capital sync: on_chain=${on_chain_balance:.2} exposure=${exposure:.2} old=${current:.2} new={}",
+                "Capital sync: on_chain=${on_chain_balance:.2} settling=${settling:.2} exposure=${exposure:.2} old=${current:.2} new={}",
                 new_capital
             );
             portfolio.capital = new_capital;
@@ -251,6 +726,26 @@ impl PositionManager {
         portfolio.consecutive_losses = 0;
     }
 
+    /// Open positions on a market.
+    pub async fn positions(&self, market_id: &str) -> Vec<Position> {
+        let portfolio = self.portfolio.read().await;
+        portfolio.positions.iter().filter(|p| p.market_id == market_id).cloned().collect()
+    }
+
+    /// Markets we hold positions or straddles in, each once.
+    pub async fn held_markets(&self) -> Vec<String> {
+        let portfolio = self.portfolio.read().await;
+        let mut markets: Vec<String> = portfolio
+            .positions
+            .iter()
+            .map(|p| p.market_id.clone())
+            .chain(portfolio.straddles.iter().map(|s| s.market_id.clone()))
+            .collect();
+        markets.sort();
+        markets.dedup();
+        markets
+    }
+
     /// Get count of open positions for a market.
     pub async fn position_count(&self, market_id: &str) -> usize {
         let portfolio = self.portfolio.read().await;
@@ -266,3 +761,350 @@ impl PositionManager {
                 .count()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::position::StraddlePosition;
+    use rust_decimal_macros::dec;
+
+    fn sweep_config(watermark: f64, fraction: f64) -> CompoundingConfig {
+        CompoundingConfig {
+            mode: CompoundingMode::Sweep,
+            watermark,
+            sweep_fraction: fraction,
+            min_sweep_usdc: 1.0,
+            ..CompoundingConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compound_never_sweeps() {
+        let mgr = PositionManager::new(dec!(100));
+        mgr.portfolio.write().await.capital = dec!(250);
+        assert_eq!(mgr.sweepable_amount().await, 0.0);
+        assert_eq!(mgr.available_capital().await, 250.0);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_above_watermark() {
+        let mgr = PositionManager::with_compounding(dec!(100), sweep_config(0.0, 0.5));
+        mgr.portfolio.write().await.capital = dec!(140);
+
+        // Watermark defaults to starting capital; half of the $40 profit
+        assert_eq!(mgr.sweepable_amount().await, 20.0);
+        // Trading is capped at the watermark
+        assert_eq!(mgr.available_capital().await, 100.0);
+
+        mgr.record_sweep(20.0).await;
+        assert_eq!(mgr.portfolio.read().await.capital, dec!(120));
+        assert_eq!(mgr.swept_total().await, dec!(20));
+
+        // A confirmed revert hands it back
+        mgr.restore_sweep(20.0).await;
+        assert_eq!(mgr.portfolio.read().await.capital, dec!(140));
+        assert_eq!(mgr.swept_total().await, dec!(0));
+    }
+
+    fn bucket_manager() -> PositionManager {
+        let config = RiskConfig {
+            buckets: CapitalBucketConfig {
+                enabled: true,
+                rebalance_min_drift: 0.10,
+                ..CapitalBucketConfig::default()
+            },
+            ..RiskConfig::default()
+        };
+        PositionManager::from_config(dec!(100), &config)
+    }
+
+    fn buy(token: &str, price: Decimal, size: Decimal) -> Fill {
+        Fill {
+            order_id: "o".to_string(),
+            token_id: token.to_string(),
+            side: OrderSide::Buy,
+            price,
+            size,
+            timestamp: Utc::now(),
+            fee: Decimal::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scale_in_caps_and_blends() {
+        let mgr = PositionManager::new(dec!(100));
+        let config = ScaleInConfig { enabled: true, max_total_cost_usdc: 6.0, ..ScaleInConfig::default() };
+        let intent = |price, size| OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size,
+            order_type: crate::models::order::OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+
+        // Nothing held: a plain entry
+        let out = mgr.apply_scale_in("m1", &config, vec![intent(dec!(0.50), dec!(6))]).await;
+        assert_eq!(out[0].strategy_tag, "lag_exploit");
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(6)), "m1", Side::Yes, "lag_exploit").await;
+
+        // Not enough better, then better but trimmed to the $3 left in the budget
+        assert!(mgr.apply_scale_in("m1", &config, vec![intent(dec!(0.495), dec!(6))]).await.is_empty());
+        let out = mgr.apply_scale_in("m1", &config, vec![intent(dec!(0.40), dec!(10))]).await;
+        assert_eq!(out[0].strategy_tag, "lag_exploit+add");
+        assert_eq!(out[0].size, dec!(7.5));
+        mgr.record_fill(&buy("yes", dec!(0.40), dec!(5)), "m1", Side::Yes, &out[0].strategy_tag).await;
+
+        {
+            let portfolio = mgr.portfolio.read().await;
+            let pos = &portfolio.positions[0];
+            assert_eq!(portfolio.positions.len(), 1);
+            assert_eq!(pos.adds, 1);
+            assert_eq!(pos.size, dec!(11));
+            assert_eq!(pos.cost_basis().round_dp(6), dec!(5));
+        }
+
+        // Second add uses up max_adds, even within one batch
+        let out = mgr.apply_scale_in("m1", &config, vec![intent(dec!(0.30), dec!(1)), intent(dec!(0.30), dec!(1))]).await;
+        assert_eq!(out.len(), 1);
+
+        // Off: passes untouched
+        let off = ScaleInConfig::default();
+        let out = mgr.apply_scale_in("m1", &off, vec![intent(dec!(0.60), dec!(6))]).await;
+        assert_eq!(out[0].strategy_tag, "lag_exploit");
+    }
+
+    #[tokio::test]
+    async fn test_buckets_disabled_by_default() {
+        let mgr = PositionManager::new(dec!(100));
+        assert_eq!(mgr.bucket_available("arb_yes").await, None);
+        assert!(!mgr.rebalance_buckets().await);
+    }
+
+    #[tokio::test]
+    async fn test_bucket_exposure_is_segregated() {
+        let mgr = bucket_manager();
+        assert_eq!(mgr.bucket_available("arb_yes").await, Some(25.0));
+
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(40)), "m1", Side::Yes, "arb_yes").await;
+        assert_eq!(mgr.bucket_available("arb_no").await, Some(5.0));
+        // Lag still has its full share, as do the window-edge strategies
+        assert_eq!(mgr.bucket_available("lag_exploit").await, Some(20.0));
+        assert_eq!(mgr.bucket_available("late_gamma").await, Some(5.0));
+        assert_eq!(mgr.bucket_available("open_sniper@mid").await, Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn test_resolution_pnl_and_rebalance() {
+        let mgr = bucket_manager();
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(40)), "m1", Side::Yes, "lag_exploit").await;
+        mgr.record_resolution("m1", Side::Yes).await;
+
+        {
+            let portfolio = mgr.portfolio.read().await;
+            assert_eq!(portfolio.buckets["lag"].realized_pnl, dec!(20));
+            assert_eq!(portfolio.buckets["lag"].trades, 1);
+        }
+        assert_eq!(mgr.bucket_available("lag_exploit").await, Some(40.0));
+
+        // Lag drifted 67% above its target → rebalance spreads the profit
+        assert!(mgr.rebalance_buckets().await);
+        assert_eq!(mgr.bucket_available("lag_exploit").await, Some(24.0));
+        assert_eq!(mgr.bucket_available("arb_yes").await, Some(30.0));
+        assert!(!mgr.rebalance_buckets().await);
+    }
+
+    #[tokio::test]
+    async fn test_straddle_resolution_credits_its_strategy() {
+        let mgr = bucket_manager();
+        mgr.portfolio.write().await.straddles.push(StraddlePosition::new(
+            "m1".into(), dec!(10), dec!(10), dec!(0.45), dec!(0.50), "arb_pair".into(),
+        ));
+        assert_eq!(mgr.portfolio.read().await.bucket_exposure("arb"), dec!(9.5));
+        mgr.record_resolution("m1", Side::Yes).await;
+
+        let portfolio = mgr.portfolio.read().await;
+        assert_eq!(portfolio.buckets["arb"].realized_pnl, dec!(0.5));
+        assert_eq!(portfolio.buckets["straddle"].trades, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mid_cycle_entries_tracked_separately() {
+        let mgr = bucket_manager();
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(10)), "m1", Side::Yes, "lag_exploit").await;
+        mgr.record_fill(&buy("no", dec!(0.40), dec!(10)), "m1", Side::No, "lag_exploit@mid").await;
+        // Mid-cycle tag still draws from the strategy's own bucket
+        assert_eq!(mgr.bucket_available("lag_exploit").await, Some(11.0));
+
+        mgr.record_resolution("m1", Side::Yes).await;
+        let portfolio = mgr.portfolio.read().await;
+        assert_eq!(portfolio.fresh_entries.trades, 1);
+        assert_eq!(portfolio.fresh_entries.pnl, dec!(5));
+        assert_eq!(portfolio.mid_cycle_entries.trades, 1);
+        assert_eq!(portfolio.mid_cycle_entries.pnl, dec!(-4));
+        assert_eq!(portfolio.mid_cycle_entries.win_rate(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_respects_minimum() {
+        let mgr = PositionManager::with_compounding(dec!(100), sweep_config(100.0, 1.0));
+        mgr.portfolio.write().await.capital = dec!(100.50);
+        assert_eq!(mgr.sweepable_amount().await, 0.0);
+
+        mgr.portfolio.write().await.capital = dec!(80);
+        assert_eq!(mgr.sweepable_amount().await, 0.0);
+        assert_eq!(mgr.available_capital().await, 80.0);
+    }
+
+    #[tokio::test]
+    async fn test_carried_payout_credited_once_redeemed() {
+        let dir = std::env::temp_dir().join(format!("carry-pm-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let carry = Arc::new(CarryBook::new(&crate::config::CarryConfig {
+            enabled: true,
+            state_path: dir.join("carry.json").to_string_lossy().into(),
+            ..Default::default()
+        }));
+        let mgr = PositionManager::new(dec!(100)).with_carry(carry.clone());
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(10)), "m1", Side::Yes, "lag_exploit").await;
+        mgr.record_fill(&buy("no", dec!(0.40), dec!(5)), "m1", Side::No, "lag_exploit").await;
+
+        // The winner's $10 is owed, not cash; P&L counts it all the same
+        let market = Market::with_condition_id(
+            "m1".into(), crate::models::market::Asset::BTC, crate::models::market::Duration::FiveMin,
+            "yes".into(), "no".into(), Some("0xabc".into()),
+        );
+        assert_eq!(mgr.record_market_resolution(&market, Side::Yes).await, dec!(3));
+        assert_eq!(mgr.available_capital().await, 93.0);
+        let redemption = Some(SettlingKind::Redemption);
+        assert_eq!(mgr.portfolio.read().await.settling_value(redemption), dec!(10));
+        assert_eq!(carry.pending()[0].condition_id.as_deref(), Some("0xabc"));
+
+        let redeemed = carry.redeemed("m1").unwrap();
+        mgr.record_redeemed(&redeemed).await;
+        assert_eq!(mgr.available_capital().await, 103.0);
+        assert_eq!(mgr.portfolio.read().await.settling_value(redemption), Decimal::ZERO);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_balance_sync_leaves_carried_payout_to_redemption() {
+        let dir = std::env::temp_dir().join(format!("carry-sync-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let carry = Arc::new(CarryBook::new(&crate::config::CarryConfig {
+            enabled: true,
+            state_path: dir.join("carry.json").to_string_lossy().into(),
+            ..Default::default()
+        }));
+        let mgr = PositionManager::new(dec!(100)).with_carry(carry.clone());
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(10)), "m1", Side::Yes, "lag_exploit").await;
+        let market = Market::with_condition_id(
+            "m1".into(), crate::models::market::Asset::BTC, crate::models::market::Duration::FiveMin,
+            "yes".into(), "no".into(), Some("0xabc".into()),
+        );
+        mgr.record_market_resolution(&market, Side::Yes).await;
+
+        // Redemption landed on-chain before we saw it confirmed
+        mgr.sync_capital_from_balance(105.0).await;
+        assert_eq!(mgr.available_capital().await, 95.0);
+        mgr.record_redeemed(&carry.redeemed("m1").unwrap()).await;
+        assert_eq!(mgr.available_capital().await, 105.0);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_sell_proceeds_settle_before_spendable() {
+        let config = RiskConfig { settlement_hold_secs: 60, ..RiskConfig::default() };
+        let mgr = PositionManager::from_config(dec!(100), &config);
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(20)), "m1", Side::Yes, "lag_exploit").await;
+        let sell = |order_id: &str, size: Decimal| Fill {
+            order_id: order_id.into(),
+            side: OrderSide::Sell,
+            ..buy("yes", dec!(0.60), size)
+        };
+
+        // Matched sells are owned but not spendable until their trades confirm
+        mgr.record_fill(&sell("s1", dec!(10)), "m1", Side::Yes, "lag_exploit").await;
+        mgr.record_fill(&sell("s2", dec!(10)), "m1", Side::Yes, "lag_exploit").await;
+        assert_eq!(mgr.available_capital().await, 90.0);
+        assert_eq!(mgr.portfolio.read().await.owned_capital(), dec!(102));
+        assert_eq!(mgr.release_settlement("s1").await, Some(dec!(6)));
+        assert_eq!(mgr.release_settlement("s1").await, None);
+        assert_eq!(mgr.available_capital().await, 96.0);
+
+        // Unconfirmed past the hold: assumed settled
+        let later = Utc::now() + chrono::Duration::seconds(61);
+        assert_eq!(mgr.release_overdue_settlements(Utc::now()).await, Decimal::ZERO);
+        assert_eq!(mgr.release_overdue_settlements(later).await, dec!(6));
+        assert_eq!(mgr.available_capital().await, 102.0);
+        assert!(mgr.portfolio.read().await.settling.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_balance_sync_leaves_settling_proceeds_to_release() {
+        let config = RiskConfig { settlement_hold_secs: 60, ..RiskConfig::default() };
+        let mgr = PositionManager::from_config(dec!(100), &config);
+        mgr.record_fill(&buy("yes", dec!(0.50), dec!(20)), "m1", Side::Yes, "lag_exploit").await;
+        let sell = Fill { order_id: "s1".into(), side: OrderSide::Sell, ..buy("yes", dec!(0.60), dec!(20)) };
+        mgr.record_fill(&sell, "m1", Side::Yes, "lag_exploit").await;
+
+        // The chain already shows the $12 of proceeds the release will credit
+        mgr.sync_capital_from_balance(102.0).await;
+        assert_eq!(mgr.available_capital().await, 90.0);
+        assert_eq!(mgr.release_settlement("s1").await, Some(dec!(12)));
+        assert_eq!(mgr.available_capital().await, 102.0);
+        mgr.sync_capital_from_balance(102.0).await;
+        assert_eq!(mgr.available_capital().await, 102.0);
+    }
+
+    #[tokio::test]
+    async fn test_fees_accrue_and_net_out_of_pnl() {
+        use crate::models::order::taker_fee;
+        let mgr = PositionManager::new(dec!(100));
+        let fee = taker_fee(dec!(0.50), dec!(20), 1000);
+        assert_eq!(fee, dec!(0.5), "0.50 × 0.50 × 10% per share");
+        mgr.record_fill(&Fill { fee, ..buy("yes", dec!(0.50), dec!(20)) }, "m1", Side::Yes, "lag_exploit").await;
+
+        // Half sold as a taker: pays its own fee and half the entry's
+        let sell = Fill {
+            side: OrderSide::Sell,
+            fee: taker_fee(dec!(0.60), dec!(10), 1000),
+            ..buy("yes", dec!(0.60), dec!(10))
+        };
+        let pnl = mgr.record_fill(&sell, "m1", Side::Yes, "lag_exploit").await.unwrap();
+        assert_eq!(pnl, dec!(1) - dec!(0.24) - dec!(0.25));
+
+        // The rest resolves a winner, carrying the other half of the entry fee
+        let pnl = mgr.record_resolution("m1", Side::Yes).await;
+        assert_eq!(pnl, dec!(5) - dec!(0.25));
+        let p = mgr.portfolio.read().await;
+        assert_eq!(p.fees.total, dec!(0.74));
+        assert_eq!(p.fees.charged, dec!(0.74));
+        assert_eq!((p.total_pnl, p.gross_pnl()), (dec!(5.26), dec!(6)));
+        assert_eq!(p.fees.by_strategy["lag_exploit"], dec!(0.74));
+        assert_eq!(p.fees.by_market["m1"], dec!(0.74));
+        assert_eq!(p.capital, dec!(100) + dec!(5.26), "cash moved by exactly net P&L");
+    }
+
+    #[tokio::test]
+    async fn test_gas_charged_to_pnl_not_capital() {
+        let mgr = PositionManager::new(dec!(100));
+        let gas = GasSpend {
+            label: "Redeem".into(),
+            tx: "0xabc".into(),
+            gas_used: 200_000,
+            gas_price_wei: 50_000_000_000,
+            matic_usd: 0.5,
+        };
+        assert_eq!(mgr.record_gas("carry", "m1", &gas).await, dec!(0.005));
+        assert_eq!(mgr.record_gas("carry", "m1", &GasSpend { matic_usd: 0.0, ..gas }).await, Decimal::ZERO);
+        let p = mgr.portfolio.read().await;
+        assert_eq!((p.total_pnl, p.daily_pnl, p.gross_pnl()), (dec!(-0.005), dec!(-0.005), Decimal::ZERO));
+        assert_eq!(p.gas.by_strategy["carry"], dec!(0.005));
+        assert_eq!(p.capital, dec!(100), "paid in MATIC");
+    }
+}
@@ -0,0 +1,328 @@
+use crate::config::ResolutionGuardConfig;
+use crate::feeds::oracle::OraclePrice;
+use crate::models::market::{Market, OrderBook, Side};
+use crate::models::order::{IntentGroup, OrderIntent, OrderSide};
+use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
+use crate::strategies::orchestrator::LEG_SETS;
+use crate::telemetry::events::{self, RiskActionKind};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Outcome of comparing Binance-derived fair value with the oracle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuardVerdict {
+    /// Outside the guard window, no usable oracle price, or the sources agree
+    Clear,
+    /// Sources disagree; the oracle points to `oracle_favors`
+    Diverged { oracle_favors: Side, divergence: f64 },
+}
+
+/// Resolution sniping guard.
+///
+/// Near close the book sometimes looks "wrong" against Binance because
+/// traders are pricing off the oracle the market actually resolves on. When
+/// fair value from Binance and from the oracle diverge materially, entries
+/// on the side only Binance favours are blocked (or flipped to the other side).
+/// Exits are never touched. The legs of a hedged trade are judged together:
+/// the pair passes unless it leans against the oracle, in which case all of
+/// it is dropped — a leg of a pair is never flipped on its own.
+pub struct ResolutionGuard {
+    config: ResolutionGuardConfig,
+    prob_model: ProbabilityModel,
+}
+
+impl ResolutionGuard {
+    pub fn new(config: ResolutionGuardConfig) -> Self {
+        Self {
+            config,
+            prob_model: ProbabilityModel::new(),
+        }
+    }
+
+    /// Judge resolution odds with time-of-day vol rather than the flat asset constant.
+    pub fn with_seasonality(mut self, seasonality: Arc<Seasonality>) -> Self {
+        self.prob_model = ProbabilityModel::new().with_seasonality(seasonality);
+        self
+    }
+
+    pub fn verdict(
+        &self,
+        market: &Market,
+        binance_price: f64,
+        oracle: Option<OraclePrice>,
+    ) -> GuardVerdict {
+        let remaining = market.time_remaining_secs();
+        if !self.config.enabled
+            || remaining > self.config.window_secs
+            || market.reference_price == 0.0
+        {
+            return GuardVerdict::Clear;
+        }
+        let Some(oracle) = oracle.filter(|o| o.age_secs() <= self.config.max_oracle_age_secs) else {
+            debug!("Resolution guard: no fresh oracle price for {}", market.slug);
+            return GuardVerdict::Clear;
+        };
+
+        let fair = |price: f64| {
+            self.prob_model.fair_prob_up(
+                price,
+                market.reference_price,
+                remaining / 60.0,
+                self.prob_model.vol_per_minute(market.asset),
+                0.0,
+            )
+        };
+        let fair_binance = fair(binance_price);
+        let fair_oracle = fair(oracle.price);
+        let divergence = (fair_binance - fair_oracle).abs();
+
+        if divergence < self.config.max_divergence {
+            return GuardVerdict::Clear;
+        }
+        GuardVerdict::Diverged {
+            oracle_favors: if fair_oracle > fair_binance { Side::Yes } else { Side::No },
+            divergence,
+        }
+    }
+
+    /// Drop (or invert) buys that bet against the oracle near close.
+    pub fn filter(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        oracle: Option<OraclePrice>,
+        orders: Vec<OrderIntent>,
+    ) -> Vec<OrderIntent> {
+        let GuardVerdict::Diverged { oracle_favors, divergence } =
+            self.verdict(market, binance_price, oracle)
+        else {
+            return orders;
+        };
+
+        let mut kept = Vec::with_capacity(orders.len());
+        for group in IntentGroup::collect(orders, LEG_SETS) {
+            if group.is_multi_leg() {
+                let against: Decimal = group
+                    .legs
+                    .iter()
+                    .filter(|l| l.order_side == OrderSide::Buy)
+                    .map(|l| if l.market_side == oracle_favors { -l.size } else { l.size })
+                    .sum();
+                if against <= Decimal::ZERO {
+                    kept.extend(group.legs);
+                } else {
+                    events::RiskAction {
+                        action: RiskActionKind::EntryBlocked,
+                        reason: &format!(
+                            "resolution guard: blocked {} trade leaning {against} shares against the oracle (divergence {divergence:.2})",
+                            group.legs[0].strategy_tag
+                        ),
+                        market: &market.slug,
+                    }
+                    .emit();
+                }
+                continue;
+            }
+            for order in group.legs {
+                if let Some(order) = self.filter_entry(market, yes_book, no_book, order, oracle_favors, divergence) {
+                    kept.push(order);
+                }
+            }
+        }
+        kept
+    }
+
+    /// A lone order: kept if it's an exit or with the oracle, otherwise
+    /// flipped (in invert mode) or blocked.
+    fn filter_entry(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        order: OrderIntent,
+        oracle_favors: Side,
+        divergence: f64,
+    ) -> Option<OrderIntent> {
+        if order.order_side == OrderSide::Sell || order.market_side == oracle_favors {
+            return Some(order);
+        }
+
+        if self.config.invert {
+            if let Some(flipped) = Self::invert(market, yes_book, no_book, &order, oracle_favors) {
+                events::RiskAction {
+                    action: RiskActionKind::EntryFlipped,
+                    reason: &format!(
+                        "resolution guard: {:?} entry flipped to {:?} (divergence {divergence:.2})",
+                        order.market_side, oracle_favors
+                    ),
+                    market: &market.slug,
+                }
+                .emit();
+                return Some(flipped);
+            }
+        }
+        events::RiskAction {
+            action: RiskActionKind::EntryBlocked,
+            reason: &format!(
+                "resolution guard: blocked {:?} entry from {} (divergence {divergence:.2})",
+                order.market_side, order.strategy_tag
+            ),
+            market: &market.slug,
+        }
+        .emit();
+        None
+    }
+
+    /// Same notional on the other side, at that side's best ask.
+    fn invert(
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        order: &OrderIntent,
+        side: Side,
+    ) -> Option<OrderIntent> {
+        let (token_id, book) = match side {
+            Side::Yes => (&market.yes_token_id, yes_book),
+            Side::No => (&market.no_token_id, no_book),
+        };
+        let (ask, _) = book.best_ask()?;
+        if ask <= Decimal::ZERO {
+            return None;
+        }
+        let size = (order.price * order.size / ask).round_dp(2);
+        if size <= Decimal::ZERO {
+            return None;
+        }
+        Some(OrderIntent {
+            token_id: token_id.clone(),
+            market_side: side,
+            price: ask,
+            size,
+            ..order.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use crate::models::order::OrderType;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn closing_market() -> Market {
+        let mut m = Market::new(
+            "btc-updown-5m-0".to_string(),
+            Asset::BTC,
+            Duration::FiveMin,
+            "yes".to_string(),
+            "no".to_string(),
+        );
+        let now = Utc::now();
+        m.open_time = now - chrono::Duration::seconds(260);
+        m.close_time = now + chrono::Duration::seconds(40);
+        m.reference_price = 100_000.0;
+        m
+    }
+
+    fn book(token: &str, bid: Decimal, ask: Decimal) -> OrderBook {
+        let mut b = OrderBook::new(token.to_string());
+        b.bids.insert(bid, dec!(100));
+        b.asks.insert(ask, dec!(100));
+        b
+    }
+
+    fn buy(side: Side, price: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: if side == Side::Yes { "yes" } else { "no" }.to_string(),
+            market_side: side,
+            order_side: OrderSide::Buy,
+            price,
+            size: dec!(10),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".to_string(),
+            group_id: None,
+        }
+    }
+
+    fn oracle(price: f64) -> Option<OraclePrice> {
+        Some(OraclePrice { price, timestamp: Utc::now() })
+    }
+
+    #[test]
+    fn test_blocks_entries_against_oracle() {
+        let guard = ResolutionGuard::new(ResolutionGuardConfig::default());
+        let market = closing_market();
+        let (yes, no) = (book("yes", dec!(0.40), dec!(0.42)), book("no", dec!(0.56), dec!(0.58)));
+
+        // Binance says up, oracle says down
+        let orders = vec![buy(Side::Yes, dec!(0.42)), buy(Side::No, dec!(0.58))];
+        let kept = guard.filter(&market, &yes, &no, 100_080.0, oracle(99_950.0), orders);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].market_side, Side::No);
+
+        // Sources agree → untouched
+        let orders = vec![buy(Side::Yes, dec!(0.42))];
+        assert_eq!(guard.filter(&market, &yes, &no, 100_080.0, oracle(100_075.0), orders).len(), 1);
+
+        // Stale oracle → can't judge, untouched
+        let stale = Some(OraclePrice {
+            price: 99_950.0,
+            timestamp: Utc::now() - chrono::Duration::seconds(60),
+        });
+        let orders = vec![buy(Side::Yes, dec!(0.42))];
+        assert_eq!(guard.filter(&market, &yes, &no, 100_080.0, stale, orders).len(), 1);
+    }
+
+    #[test]
+    fn test_invert_flips_to_oracle_side() {
+        let guard = ResolutionGuard::new(ResolutionGuardConfig {
+            invert: true,
+            ..ResolutionGuardConfig::default()
+        });
+        let market = closing_market();
+        let (yes, no) = (book("yes", dec!(0.40), dec!(0.42)), book("no", dec!(0.48), dec!(0.50)));
+
+        let kept = guard.filter(
+            &market, &yes, &no, 100_080.0, oracle(99_950.0),
+            vec![buy(Side::Yes, dec!(0.42))],
+        );
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].market_side, Side::No);
+        assert_eq!(kept[0].token_id, "no");
+        assert_eq!(kept[0].price, dec!(0.50));
+        assert_eq!(kept[0].size, dec!(8.40)); // same $4.20 notional
+    }
+
+    #[test]
+    fn test_arb_pair_judged_as_one_trade() {
+        let guard = ResolutionGuard::new(ResolutionGuardConfig {
+            invert: true,
+            ..ResolutionGuardConfig::default()
+        });
+        let market = closing_market();
+        let (yes, no) = (book("yes", dec!(0.40), dec!(0.42)), book("no", dec!(0.48), dec!(0.50)));
+        let leg = |side, price, size, tag: &str| OrderIntent { size, strategy_tag: tag.to_string(), ..buy(side, price) };
+
+        // A balanced pair is hedged: both legs pass untouched, neither is flipped
+        let pair = vec![leg(Side::Yes, dec!(0.42), dec!(10), "arb_yes"), leg(Side::No, dec!(0.50), dec!(10), "arb_no")];
+        let kept = guard.filter(&market, &yes, &no, 100_080.0, oracle(99_950.0), pair.clone());
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept.iter().map(|o| (o.market_side, o.size)).collect::<Vec<_>>(), [(Side::Yes, dec!(10)), (Side::No, dec!(10))]);
+
+        // Leaning against the oracle drops the whole pair, not one leg
+        let lean = vec![leg(Side::Yes, dec!(0.42), dec!(15), "straddle_yes"), leg(Side::No, dec!(0.50), dec!(10), "straddle_no")];
+        assert!(guard.filter(&market, &yes, &no, 100_080.0, oracle(99_950.0), lean).is_empty());
+
+        // Leaning with it is fine
+        let lean = vec![leg(Side::Yes, dec!(0.42), dec!(5), "straddle_yes"), leg(Side::No, dec!(0.50), dec!(10), "straddle_no")];
+        assert_eq!(guard.filter(&market, &yes, &no, 100_080.0, oracle(99_950.0), lean).len(), 2);
+    }
+}
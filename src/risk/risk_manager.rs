@@ -1,12 +1,19 @@
 use crate::config::RiskConfig;
-use crate::models::order::OrderIntent;
+use crate::feeds::clock;
+use crate::models::order::{IntentGroup, OrderIntent, OrderSide};
+use crate::models::position::Portfolio;
+use crate::risk::canary::CanaryGate;
+use crate::risk::liveness::{Liveness, WATCHDOG_INTERVAL};
+use crate::risk::loop_guard::LoopDetector;
+use crate::risk::market_stop::MarketStop;
 use crate::risk::position_manager::PositionManager;
-use anyhow::Result;
+use crate::risk::safe_mode::SafeMode;
+use crate::telemetry::events::{self, RiskActionKind};
+use anyhow::{Context, Result};
 use rust_decimal::Decimal;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{error, info, warn};
 
 /// Risk manager with kill switch, exposure limits, and drawdown protection.
 ///
@@ -19,11 +26,26 @@ pub struct RiskManager {
     /// Whether we're in a loss-streak size reduction mode
     pub size_reduction_active: Arc<AtomicBool>,
     pub size_multiplier: Arc<RwLock<f64>>,
+    /// Cools off strategies caught churning in and out of a market
+    pub loops: Arc<LoopDetector>,
+    /// Takes market series that keep losing out of the active set
+    pub markets: Arc<MarketStop>,
+    /// Reduced-risk start after an abnormal shutdown
+    pub safe_mode: Arc<SafeMode>,
+    /// Probation for canary strategy families (shared with the position manager)
+    pub canary: Option<Arc<CanaryGate>>,
+    /// Renewed by every `periodic_check` once started; stale means no new orders
+    pub watchdog: Arc<Liveness>,
 }
 
 impl RiskManager {
     pub fn new(config: RiskConfig, position_mgr: Arc<PositionManager>) -> Self {
         Self {
+            loops: Arc::new(LoopDetector::new(&config)),
+            markets: Arc::new(MarketStop::new(&config)),
+            safe_mode: Arc::new(SafeMode::inactive()),
+            canary: position_mgr.canary(),
+            watchdog: Arc::new(Liveness::new(WATCHDOG_INTERVAL)),
             config,
             position_mgr,
             killed: Arc::new(AtomicBool::new(false)),
@@ -32,6 +54,11 @@ impl RiskManager {
         }
     }
 
+    pub fn with_safe_mode(mut self, safe_mode: SafeMode) -> Self {
+        self.safe_mode = Arc::new(safe_mode);
+        self
+    }
+
     /// Pre-flight check before submitting an order.
     /// Returns Ok(()) if order is safe to submit, Err otherwise.
     pub async fn check_order(&self, order: &OrderIntent) -> Result<()> {
@@ -47,9 +74,7 @@ impl RiskManager {
         let current_exposure = portfolio.total_exposure();
         let order_cost = order.price * order.size;
         let new_exposure = current_exposure + order_cost;
-        let base_capital = portfolio.starting_capital.max(portfolio.capital);
-        let max_exposure =
-            base_capital * Decimal::from_f64_retain(self.config.max_exposure_pct).unwrap_or(Decimal::ONE);
+        let max_exposure = self.max_exposure(&portfolio);
 
         if new_exposure > max_exposure {
             anyhow::bail!(
@@ -77,11 +102,75 @@ impl RiskManager {
             );
         }
 
+        // Strategy bucket check — one strategy can't spend another's capital
+        if let Some(available) = portfolio.bucket_available(&order.strategy_tag) {
+            if required > available {
+                anyhow::bail!(
+                    "Bucket limit for {}: need={required} available={available}",
+                    order.strategy_tag
+                );
+            }
+        }
+
         Ok(())
     }
 
-    /// Periodic risk check (called every 500ms by watchdog task).
+    /// Every leg of a trade through `check_market_order`, then the legs'
+    /// combined cost against exposure and balance — two legs that each fit
+    /// can still overshoot together.
+    pub async fn check_group(&self, market: &str, group: &IntentGroup) -> Result<()> {
+        for leg in &group.legs {
+            self.check_market_order(market, leg)
+                .await
+                .with_context(|| format!("leg {}", leg.strategy_tag))?;
+        }
+        if !group.is_multi_leg() {
+            return Ok(());
+        }
+
+        let portfolio = self.position_mgr.portfolio.read().await;
+        let cost = group.cost();
+        let current_exposure = portfolio.total_exposure();
+        let max_exposure = self.max_exposure(&portfolio);
+        if current_exposure + cost > max_exposure {
+            anyhow::bail!("Exposure limit: current={current_exposure} + trade={cost} > max={max_exposure}");
+        }
+        if cost > portfolio.capital {
+            anyhow::bail!("Insufficient balance for trade: need={cost} have={}", portfolio.capital);
+        }
+        Ok(())
+    }
+
+    /// Exposure allowed on `portfolio`. Value still settling is ours; only
+    /// the balance check needs it spendable.
+    fn max_exposure(&self, portfolio: &Portfolio) -> Decimal {
+        let base_capital = portfolio.starting_capital.max(portfolio.owned_capital());
+        base_capital * Decimal::from_f64_retain(self.config.max_exposure_pct).unwrap_or(Decimal::ONE)
+    }
+
+    /// `check_order` plus the checks that need to know the market.
+    pub async fn check_market_order(&self, market: &str, order: &OrderIntent) -> Result<()> {
+        // Sells take risk off, so a stalled watchdog doesn't hold them back
+        if order.order_side == OrderSide::Buy {
+            self.watchdog.check(clock::now())?;
+        }
+        self.safe_mode.check(order)?;
+        self.loops.check(market, order)?;
+        if let Some(canary) = &self.canary {
+            canary.check(order)?;
+        }
+        self.check_order(order).await
+    }
+
+    /// Periodic risk check (called every `WATCHDOG_INTERVAL` by the
+    /// watchdog task). Renews the watchdog's liveness once it completes.
     pub async fn periodic_check(&self) -> RiskAction {
+        let action = self.evaluate().await;
+        self.watchdog.beat(clock::now());
+        action
+    }
+
+    async fn evaluate(&self) -> RiskAction {
         let portfolio = self.position_mgr.portfolio.read().await;
 
         // Check exposure
@@ -89,9 +178,12 @@ impl RiskManager {
         let max_ratio =
             Decimal::from_f64_retain(self.config.max_exposure_pct).unwrap_or(Decimal::ONE);
         if exposure_ratio > max_ratio {
-            error!(
-                "RISK: Exposure ratio {exposure_ratio} exceeds max {max_ratio} — KILLING"
-            );
+            events::RiskAction {
+                action: RiskActionKind::KillSwitch,
+                reason: &format!("exposure ratio {exposure_ratio} exceeds max {max_ratio}"),
+                market: "",
+            }
+            .emit();
             self.killed.store(true, Ordering::Relaxed);
             return RiskAction::KillSwitch;
         }
@@ -100,19 +192,26 @@ impl RiskManager {
         let daily_loss_limit = portfolio.starting_capital
             * Decimal::from_f64_retain(self.config.max_daily_loss_pct).unwrap_or(Decimal::ONE);
         if portfolio.daily_pnl < -daily_loss_limit {
-            warn!(
-                "RISK: Daily loss {:.2} exceeds limit {:.2} — PAUSING",
-                portfolio.daily_pnl, daily_loss_limit
-            );
+            events::RiskAction {
+                action: RiskActionKind::Pause,
+                reason: &format!(
+                    "daily loss {:.2} exceeds limit {:.2}",
+                    portfolio.daily_pnl, daily_loss_limit
+                ),
+                market: "",
+            }
+            .emit();
             return RiskAction::Pause(self.config.pause_duration_secs);
         }
 
         // Check loss streak
         if portfolio.consecutive_losses >= self.config.loss_streak_threshold {
-            warn!(
-                "RISK: {} consecutive losses — reducing size",
-                portfolio.consecutive_losses
-            );
+            events::RiskAction {
+                action: RiskActionKind::ReduceSize,
+                reason: &format!("{} consecutive losses", portfolio.consecutive_losses),
+                market: "",
+            }
+            .emit();
             self.size_reduction_active.store(true, Ordering::Relaxed);
             *self.size_multiplier.write().await = self.config.loss_streak_size_mult;
             return RiskAction::ReduceSize(self.config.loss_streak_size_mult);
@@ -125,20 +224,21 @@ impl RiskManager {
         RiskAction::Continue
     }
 
-    /// Get current size multiplier (for strategies to query).
+    /// Get current size multiplier (for strategies to query), including
+    /// any safe-mode reduction.
     pub async fn current_size_multiplier(&self) -> f64 {
-        *self.size_multiplier.read().await
+        *self.size_multiplier.read().await * self.safe_mode.size_mult()
     }
 
     /// Manually trigger kill switch.
     pub fn kill(&self) {
-        error!("RISK: Manual kill switch activated");
+        events::RiskAction { action: RiskActionKind::ManualKill, reason: "manual", market: "" }.emit();
         self.killed.store(true, Ordering::Relaxed);
     }
 
     /// Reset kill switch (manual recovery).
     pub fn reset_kill(&self) {
-        info!("RISK: Kill switch reset");
+        events::RiskAction { action: RiskActionKind::KillReset, reason: "manual", market: "" }.emit();
         self.killed.store(false, Ordering::Relaxed);
     }
 }
@@ -0,0 +1,235 @@
+use crate::config::SafeModeConfig;
+use crate::execution::session::{self, Snapshot};
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::telemetry::events::{self, RiskActionKind};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// How a session stands, as last written to the session state file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEnd {
+    /// Still running — read at startup, the process died without shutting down
+    Running,
+    /// Ctrl+C / dashboard quit, orders cancelled
+    Clean,
+    KillSwitch,
+}
+
+impl std::fmt::Display for SessionEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SessionEnd::Running => "crashed",
+            SessionEnd::Clean => "clean shutdown",
+            SessionEnd::KillSwitch => "kill switch",
+        })
+    }
+}
+
+/// The session state file: `Running` while the bot trades, overwritten on
+/// the way out. Whatever the next start finds there says how we stopped.
+pub struct SessionStore {
+    path: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+
+    /// How the previous session ended; None on a first run.
+    pub fn previous(&self) -> Result<Option<Snapshot<SessionEnd>>> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("reading {}", self.path.display())),
+        };
+        let snapshot = serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", self.path.display()))?;
+        Ok(Some(snapshot))
+    }
+
+    pub fn mark(&self, end: SessionEnd) -> Result<()> {
+        session::save(&self.path, &end)
+    }
+}
+
+/// What the control API reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeModeStatus {
+    pub active: bool,
+    /// How the previous session ended, when that put us in safe mode
+    pub cause: Option<SessionEnd>,
+    pub reconciled: bool,
+    pub confirmed: bool,
+    pub entries_allowed: bool,
+    pub size_mult: f64,
+    pub max_markets: Option<usize>,
+}
+
+/// Reduced-risk start after an abnormal shutdown: smaller orders and fewer
+/// markets for the session, and no entries at all until open orders and
+/// balance have been reconciled and an operator has confirmed through the
+/// control API. Exits are never blocked.
+pub struct SafeMode {
+    cause: Option<SessionEnd>,
+    size_mult: f64,
+    max_markets: usize,
+    reconciled: AtomicBool,
+    confirmed: AtomicBool,
+}
+
+impl SafeMode {
+    /// Normal trading.
+    pub fn inactive() -> Self {
+        Self {
+            cause: None,
+            size_mult: 1.0,
+            max_markets: usize::MAX,
+            reconciled: AtomicBool::new(true),
+            confirmed: AtomicBool::new(true),
+        }
+    }
+
+    /// Safe mode if the previous session didn't end cleanly.
+    pub fn from_previous(config: &SafeModeConfig, previous: Option<SessionEnd>) -> Self {
+        match previous {
+            Some(cause) if config.enabled && cause != SessionEnd::Clean => {
+                events::RiskAction {
+                    action: RiskActionKind::SafeMode,
+                    reason: &format!("previous session ended abnormally ({cause}), entries held for reconciliation"),
+                    market: "",
+                }
+                .emit();
+                Self {
+                    cause: Some(cause),
+                    size_mult: config.size_mult,
+                    max_markets: config.max_markets,
+                    reconciled: AtomicBool::new(false),
+                    confirmed: AtomicBool::new(false),
+                }
+            }
+            _ => Self::inactive(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.cause.is_some()
+    }
+
+    pub fn awaiting_reconciliation(&self) -> bool {
+        !self.reconciled.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_reconciled(&self) {
+        if !self.reconciled.swap(true, Ordering::Relaxed) {
+            self.log_if_cleared();
+        }
+    }
+
+    /// Operator sign-off; entries resume once reconciliation is also done.
+    pub fn confirm(&self) {
+        if !self.confirmed.swap(true, Ordering::Relaxed) {
+            self.log_if_cleared();
+        }
+    }
+
+    fn log_if_cleared(&self) {
+        if self.entries_allowed() {
+            events::RiskAction {
+                action: RiskActionKind::SafeModeCleared,
+                reason: &format!("entries resumed at {}x size, top {} markets", self.size_mult, self.max_markets),
+                market: "",
+            }
+            .emit();
+        }
+    }
+
+    pub fn entries_allowed(&self) -> bool {
+        self.reconciled.load(Ordering::Relaxed) && self.confirmed.load(Ordering::Relaxed)
+    }
+
+    pub fn size_mult(&self) -> f64 {
+        self.size_mult
+    }
+
+    /// How many markets, by allocator priority, may be traded; None = all.
+    pub fn max_markets(&self) -> Option<usize> {
+        self.is_active().then_some(self.max_markets)
+    }
+
+    /// Reject entries until reconciled and confirmed.
+    pub fn check(&self, order: &OrderIntent) -> Result<()> {
+        if order.order_side == OrderSide::Buy && !self.entries_allowed() {
+            anyhow::bail!("Safe mode: entries held until reconciliation and operator confirmation");
+        }
+        Ok(())
+    }
+
+    pub fn status(&self) -> SafeModeStatus {
+        SafeModeStatus {
+            active: self.is_active(),
+            cause: self.cause,
+            reconciled: self.reconciled.load(Ordering::Relaxed),
+            confirmed: self.confirmed.load(Ordering::Relaxed),
+            entries_allowed: self.entries_allowed(),
+            size_mult: self.size_mult,
+            max_markets: self.max_markets(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_safe_mode_after_abnormal_end() {
+        let path = std::env::temp_dir().join(format!("sattebaaz-session-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = SessionStore::new(&path);
+        assert!(store.previous().unwrap().is_none());
+
+        // A crash leaves "running" behind
+        store.mark(SessionEnd::Running).unwrap();
+        let config = SafeModeConfig::default();
+        let previous = store.previous().unwrap().map(|s| s.state);
+        let safe = SafeMode::from_previous(&config, previous);
+        assert_eq!(safe.status().cause, Some(SessionEnd::Running));
+        assert_eq!((safe.size_mult(), safe.max_markets()), (0.5, Some(2)));
+
+        let mut order = OrderIntent {
+            token_id: "t".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price: dec!(0.50),
+            size: dec!(10),
+            order_type: OrderType::GTC,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+        assert!(safe.check(&order).is_err());
+        safe.confirm();
+        assert!(safe.check(&order).is_err(), "confirmation alone isn't enough");
+        order.order_side = OrderSide::Sell;
+        assert!(safe.check(&order).is_ok(), "exits always go through");
+        safe.mark_reconciled();
+        order.order_side = OrderSide::Buy;
+        assert!(safe.check(&order).is_ok());
+        assert_eq!(safe.max_markets(), Some(2), "sizing stays reduced for the session");
+
+        // Clean shutdowns and a disabled setting start normally
+        store.mark(SessionEnd::Clean).unwrap();
+        let clean = SafeMode::from_previous(&config, store.previous().unwrap().map(|s| s.state));
+        assert!(!clean.is_active() && clean.entries_allowed());
+        let off = SafeModeConfig { enabled: false, ..config };
+        assert!(!SafeMode::from_previous(&off, Some(SessionEnd::KillSwitch)).is_active());
+        let _ = std::fs::remove_file(&path);
+    }
+}
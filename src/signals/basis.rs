@@ -0,0 +1,207 @@
+use crate::config::BasisConfig;
+use crate::models::market::Asset;
+use crate::models::signal::{BiasDirection, BiasSignal};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+
+/// Where the perp-spot basis sits relative to its recent range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BasisSignal {
+    /// (perp - spot) / spot, latest sample
+    pub basis: f64,
+    /// Mean basis over the window
+    pub mean: f64,
+    /// Latest basis in standard deviations from the window mean
+    pub z: f64,
+}
+
+impl BasisSignal {
+    /// Perp premium jumping = leveraged buyers leaning in (Up); collapsing
+    /// toward or under spot = leveraged sellers (Down).
+    pub fn direction(&self) -> BiasDirection {
+        if self.z > 0.0 {
+            BiasDirection::Up
+        } else if self.z < 0.0 {
+            BiasDirection::Down
+        } else {
+            BiasDirection::Neutral
+        }
+    }
+
+    /// A move of at least `z_threshold` standard deviations.
+    pub fn is_sharp(&self, z_threshold: f64) -> bool {
+        self.z.abs() >= z_threshold
+    }
+}
+
+/// Rolling Binance perp-spot basis per asset.
+///
+/// Samples the basis at most once per `sample_ms` while both legs are
+/// fresher than `max_price_age_ms`, and keeps `window_secs` of samples. A
+/// sharp move away from the window mean tends to precede continuation in
+/// its direction: `adjust_bias` leans the straddle engine's bias confidence
+/// toward it, and `opposes` lets momentum skip entries against it.
+pub struct BasisTracker {
+    config: BasisConfig,
+    spot: DashMap<Asset, (f64, DateTime<Utc>)>,
+    perp: DashMap<Asset, (f64, DateTime<Utc>)>,
+    samples: DashMap<Asset, VecDeque<(DateTime<Utc>, f64)>>,
+}
+
+/// Too few samples for a meaningful spread
+const MIN_SAMPLES: usize = 30;
+
+impl BasisTracker {
+    pub fn new(config: BasisConfig) -> Self {
+        Self {
+            config,
+            spot: DashMap::new(),
+            perp: DashMap::new(),
+            samples: DashMap::new(),
+        }
+    }
+
+    pub fn observe_spot(&self, asset: Asset, price: f64, at: DateTime<Utc>) {
+        self.spot.insert(asset, (price, at));
+        self.sample(asset, at);
+    }
+
+    pub fn observe_perp(&self, asset: Asset, price: f64, at: DateTime<Utc>) {
+        self.perp.insert(asset, (price, at));
+        self.sample(asset, at);
+    }
+
+    fn sample(&self, asset: Asset, now: DateTime<Utc>) {
+        if !self.config.enabled {
+            return;
+        }
+        let max_age = chrono::Duration::milliseconds(self.config.max_price_age_ms as i64);
+        let (Some(spot), Some(perp)) = (self.spot.get(&asset).map(|s| *s), self.perp.get(&asset).map(|p| *p)) else {
+            return;
+        };
+        if spot.0 <= 0.0 || now - spot.1 > max_age || now - perp.1 > max_age {
+            return;
+        }
+
+        let mut samples = self.samples.entry(asset).or_default();
+        if samples
+            .back()
+            .is_some_and(|(t, _)| (now - *t).num_milliseconds() < self.config.sample_ms as i64)
+        {
+            return;
+        }
+        samples.push_back((now, (perp.0 - spot.0) / spot.0));
+        let cutoff = now - chrono::Duration::seconds(self.config.window_secs as i64);
+        while samples.front().is_some_and(|(t, _)| *t < cutoff) {
+            samples.pop_front();
+        }
+    }
+
+    /// Latest basis against its window, once there's enough history.
+    pub fn signal(&self, asset: Asset) -> Option<BasisSignal> {
+        if !self.config.enabled {
+            return None;
+        }
+        let samples = self.samples.get(&asset)?;
+        if samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let (_, basis) = *samples.back()?;
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(|(_, b)| b).sum::<f64>() / n;
+        let std = (samples.iter().map(|(_, b)| (b - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        let z = if std > 0.0 { (basis - mean) / std } else { 0.0 };
+        Some(BasisSignal { basis, mean, z })
+    }
+
+    /// `bias` with its confidence raised by `bias_boost` when a sharp basis
+    /// move agrees with it, and lowered when it disagrees.
+    pub fn adjust_bias(&self, asset: Asset, bias: &BiasSignal) -> BiasSignal {
+        let mut bias = bias.clone();
+        let Some(basis) = self.signal(asset).filter(|b| b.is_sharp(self.config.z_threshold)) else {
+            return bias;
+        };
+        if bias.direction == BiasDirection::Neutral {
+            return bias;
+        }
+        let boost = if basis.direction() == bias.direction {
+            self.config.bias_boost
+        } else {
+            -self.config.bias_boost
+        };
+        bias.confidence = (bias.confidence + boost).clamp(0.0, 1.0);
+        bias
+    }
+
+    /// Whether a sharp basis move points the other way from `direction`.
+    pub fn opposes(&self, asset: Asset, direction: BiasDirection) -> bool {
+        direction != BiasDirection::Neutral
+            && self
+                .signal(asset)
+                .is_some_and(|b| b.is_sharp(self.config.z_threshold) && b.direction() != direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 60s of a steady 5bp premium with a little wobble, then the premium jumps.
+    fn widening(tracker: &BasisTracker, jump_bp: f64) {
+        let start = Utc::now() - chrono::Duration::seconds(61);
+        for i in 0..=60 {
+            let at = start + chrono::Duration::seconds(i);
+            let wobble = if i % 2 == 0 { 0.2 } else { -0.2 };
+            let bp = if i == 60 { 5.0 + jump_bp } else { 5.0 + wobble };
+            tracker.observe_perp(Asset::BTC, 100_000.0 * (1.0 + bp / 10_000.0), at);
+            tracker.observe_spot(Asset::BTC, 100_000.0, at);
+        }
+    }
+
+    fn bias(direction: BiasDirection) -> BiasSignal {
+        BiasSignal {
+            direction,
+            confidence: 0.5,
+            momentum_score: 0.0,
+            trend_score: 0.0,
+            flow_score: 0.0,
+            funding_score: 0.0,
+            liquidation_score: 0.0,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_sharp_widening_leans_bias_and_momentum() {
+        let tracker = BasisTracker::new(BasisConfig::default());
+        assert!(tracker.signal(Asset::BTC).is_none());
+        widening(&tracker, 3.0);
+
+        let signal = tracker.signal(Asset::BTC).unwrap();
+        assert!((signal.basis - 0.0008).abs() < 1e-9);
+        assert!(signal.is_sharp(BasisConfig::default().z_threshold), "z = {}", signal.z);
+        assert_eq!(signal.direction(), BiasDirection::Up);
+
+        assert!(tracker.adjust_bias(Asset::BTC, &bias(BiasDirection::Up)).confidence > 0.5);
+        assert!(tracker.adjust_bias(Asset::BTC, &bias(BiasDirection::Down)).confidence < 0.5);
+        assert!(tracker.opposes(Asset::BTC, BiasDirection::Down));
+        assert!(!tracker.opposes(Asset::BTC, BiasDirection::Up));
+        assert!(!tracker.opposes(Asset::ETH, BiasDirection::Down));
+    }
+
+    #[test]
+    fn test_ordinary_wobble_and_stale_legs_are_ignored() {
+        let tracker = BasisTracker::new(BasisConfig::default());
+        widening(&tracker, 0.2);
+        assert!(!tracker.signal(Asset::BTC).unwrap().is_sharp(BasisConfig::default().z_threshold));
+        assert_eq!(tracker.adjust_bias(Asset::BTC, &bias(BiasDirection::Down)).confidence, 0.5);
+
+        // A perp print with no recent spot print doesn't sample
+        let tracker = BasisTracker::new(BasisConfig::default());
+        let now = Utc::now();
+        tracker.observe_spot(Asset::ETH, 3_000.0, now - chrono::Duration::seconds(30));
+        tracker.observe_perp(Asset::ETH, 3_001.0, now);
+        assert!(tracker.samples.get(&Asset::ETH).is_none());
+    }
+}
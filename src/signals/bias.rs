@@ -136,7 +136,7 @@ mod tests {
     #[test]
     fn test_neutral_in_flat_market() {
         let mut engine = IndicatorEngine::new(100);
-        for i in 0..30 {
+        for _ in 0..30 {
             engine.push(make_candle(100_000.0, 50.0, 50.0));
         }
 
@@ -0,0 +1,449 @@
+use crate::execution::rejection::RejectReason;
+use crate::models::market::{Asset, Bbo, BookDiff, Duration, Market};
+use crate::models::order::{OrderIntent, OrderResult, OrderSide, OrderStatus};
+use dashmap::DashMap;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::VecDeque;
+use tracing::info;
+
+/// How long a resting quote is watched for a competing reaction (ms).
+const PROBE_WINDOW_MS: i64 = 5_000;
+/// A probe this old never saw a book update to close it — its market has
+/// likely expired — and is dropped without a sample (ms).
+const PROBE_STALE_MS: i64 = 60_000;
+/// A reaction faster than this is treated as automated (ms).
+const FAST_REACTION_MS: i64 = 500;
+/// Samples needed before a market type gets a non-zero score.
+const MIN_SAMPLES: usize = 5;
+/// Rolling sample window per market type.
+const MAX_SAMPLES: usize = 100;
+/// Taker races older than this no longer count toward the fill rate, so a
+/// market type screened out for poor fills comes back once they age out (ms).
+const RACE_WINDOW_MS: i64 = 30 * 60_000;
+/// Presence score at which a market is considered contested.
+const CONTESTED_SCORE: f64 = 0.6;
+
+/// Competitor detection for each (asset, duration) market type.
+///
+/// Watches how fast the book reacts to our resting quotes and how often our
+/// taker orders come back unfilled, then estimates whether other bots are
+/// racing us to the same edge.
+pub struct CompetitionDetector {
+    stats: DashMap<(Asset, Duration), CompetitionStats>,
+    /// Resting quotes awaiting a book reaction, keyed by token_id.
+    probes: DashMap<String, Vec<QuoteProbe>>,
+}
+
+#[derive(Debug, Clone)]
+struct QuoteProbe {
+    key: (Asset, Duration),
+    side: OrderSide,
+    price: f64,
+    placed_ms: i64,
+}
+
+#[derive(Debug, Default)]
+struct CompetitionStats {
+    /// Taker races by time (ms): true = we got filled, false = edge was taken before us.
+    races: VecDeque<(i64, bool)>,
+    /// Quote reactions: Some(latency_ms) when a competitor improved on us.
+    reactions: VecDeque<Option<i64>>,
+}
+
+impl CompetitionStats {
+    fn push_race(&mut self, at_ms: i64, won: bool) {
+        while self.races.len() >= MAX_SAMPLES
+            || self.races.front().is_some_and(|(t, _)| *t < at_ms - RACE_WINDOW_MS)
+        {
+            self.races.pop_front();
+        }
+        self.races.push_back((at_ms, won));
+    }
+
+    fn push_reaction(&mut self, latency_ms: Option<i64>) {
+        if self.reactions.len() >= MAX_SAMPLES {
+            self.reactions.pop_front();
+        }
+        self.reactions.push_back(latency_ms);
+    }
+
+    /// Fraction of taker races since `from_ms` that we lost.
+    fn race_loss_rate_since(&self, from_ms: i64) -> Option<f64> {
+        let recent: Vec<bool> = self.races.iter().filter(|(t, _)| *t >= from_ms).map(|(_, won)| *won).collect();
+        if recent.len() < MIN_SAMPLES {
+            return None;
+        }
+        let lost = recent.iter().filter(|won| !**won).count();
+        Some(lost as f64 / recent.len() as f64)
+    }
+
+    /// Fraction of our quotes that were improved on within FAST_REACTION_MS.
+    fn fast_reaction_rate(&self) -> Option<f64> {
+        if self.reactions.len() < MIN_SAMPLES {
+            return None;
+        }
+        let fast = self
+            .reactions
+            .iter()
+            .filter(|r| matches!(r, Some(ms) if *ms <= FAST_REACTION_MS))
+            .count();
+        Some(fast as f64 / self.reactions.len() as f64)
+    }
+
+    /// Presence score as of `now_ms`, over the same race window as the fill rate.
+    fn score(&self, now_ms: i64) -> f64 {
+        match (self.race_loss_rate_since(now_ms - RACE_WINDOW_MS), self.fast_reaction_rate()) {
+            (Some(a), Some(b)) => a.max(b),
+            (Some(a), None) => a,
+            (None, Some(b)) => b,
+            (None, None) => 0.0,
+        }
+    }
+}
+
+impl CompetitionDetector {
+    pub fn new() -> Self {
+        Self {
+            stats: DashMap::new(),
+            probes: DashMap::new(),
+        }
+    }
+
+    /// Register a freshly submitted order. Resting (post-only) quotes are
+    /// watched for a competing reaction on subsequent book updates.
+    pub fn on_order_submitted(&self, market: &Market, intent: &OrderIntent, now_ms: i64) {
+        if !intent.post_only {
+            return;
+        }
+        self.probes.retain(|_, probes| {
+            probes.retain(|p| now_ms - p.placed_ms < PROBE_STALE_MS);
+            !probes.is_empty()
+        });
+        let price = intent.price.to_f64().unwrap_or(0.0);
+        self.probes
+            .entry(intent.token_id.clone())
+            .or_default()
+            .push(QuoteProbe {
+                key: (market.asset, market.duration),
+                side: intent.order_side,
+                price,
+                placed_ms: now_ms,
+            });
+    }
+
+    /// Record the outcome of a taker order. A FAK rejected for lack of a match,
+    /// or one that filled less than half its size, means someone else got to
+    /// the liquidity first. Rejections for other reasons are not races.
+    pub fn on_order_result(&self, market: &Market, intent: &OrderIntent, result: &OrderResult) {
+        if intent.post_only {
+            return;
+        }
+        let won = match result.status {
+            OrderStatus::Open | OrderStatus::Filled => true,
+            OrderStatus::PartiallyFilled => result.fill_ratio() >= 0.5,
+            OrderStatus::Rejected => {
+                let no_match = result
+                    .error_msg
+                    .as_deref()
+                    .is_some_and(|e| RejectReason::parse(e) == RejectReason::NoMatch);
+                if !no_match {
+                    return;
+                }
+                false
+            }
+            _ => return,
+        };
+        self.stats
+            .entry((market.asset, market.duration))
+            .or_default()
+            .push_race(result.timestamp.timestamp_millis(), won);
+    }
+
+    /// Check resting-quote probes for this token against a book update.
+    ///
+    /// A reaction is the best price moving past our quote in an update
+    /// applied after it was placed. Updates from before then are skipped, and
+    /// a quote that was already behind the best price says nothing about
+    /// competitors, so it's dropped without a sample.
+    pub fn on_book_update(&self, diff: &BookDiff) {
+        let Some(mut probes) = self.probes.get_mut(&diff.token_id) else {
+            return;
+        };
+
+        let at_ms = diff.at.timestamp_millis();
+        let price = |bbo: &Bbo, side| match side {
+            OrderSide::Buy => bbo.bid.and_then(|(p, _)| p.to_f64()),
+            OrderSide::Sell => bbo.ask.and_then(|(p, _)| p.to_f64()),
+        };
+        let beats = |best: Option<f64>, probe: &QuoteProbe| match probe.side {
+            OrderSide::Buy => best.is_some_and(|b| b > probe.price + 1e-9),
+            OrderSide::Sell => best.is_some_and(|a| a < probe.price - 1e-9),
+        };
+
+        probes.retain(|probe| {
+            let elapsed = at_ms - probe.placed_ms;
+            if elapsed <= 0 {
+                return true;
+            }
+            if beats(price(&diff.prev_bbo, probe.side), probe) {
+                return false;
+            }
+
+            if beats(price(&diff.bbo, probe.side), probe) {
+                self.stats
+                    .entry(probe.key)
+                    .or_default()
+                    .push_reaction(Some(elapsed));
+                false
+            } else if elapsed >= PROBE_WINDOW_MS {
+                self.stats.entry(probe.key).or_default().push_reaction(None);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Estimated competitor presence for a market type in [0, 1].
+    pub fn presence_score(&self, asset: Asset, duration: Duration, now_ms: i64) -> f64 {
+        self.stats
+            .get(&(asset, duration))
+            .map(|s| s.score(now_ms))
+            .unwrap_or(0.0)
+    }
+
+    /// Share of our recent taker orders on a market type that got filled,
+    /// once there are enough samples inside the window.
+    pub fn fill_success_rate(&self, asset: Asset, duration: Duration, now_ms: i64) -> Option<f64> {
+        self.stats
+            .get(&(asset, duration))
+            .and_then(|s| s.race_loss_rate_since(now_ms - RACE_WINDOW_MS))
+            .map(|loss| 1.0 - loss)
+    }
+
+    /// Whether we are consistently second to the edge in this market type.
+    pub fn is_contested(&self, asset: Asset, duration: Duration, now_ms: i64) -> bool {
+        self.presence_score(asset, duration, now_ms) >= CONTESTED_SCORE
+    }
+
+    /// Capital scaling for a market type: full size when uncontested,
+    /// down to 25% when competitors take nearly every edge.
+    pub fn capital_multiplier(&self, asset: Asset, duration: Duration, now_ms: i64) -> f64 {
+        (1.0 - 0.75 * self.presence_score(asset, duration, now_ms)).clamp(0.25, 1.0)
+    }
+
+    /// Log presence scores for all market types with enough samples.
+    pub fn log_summary(&self, now_ms: i64) {
+        for entry in self.stats.iter() {
+            let (asset, duration) = *entry.key();
+            let stats = entry.value();
+            if stats.races.len() < MIN_SAMPLES && stats.reactions.len() < MIN_SAMPLES {
+                continue;
+            }
+            info!(
+                "Competition {:?} {:?}: score={:.2} race_loss={:.0}% fast_react={:.0}% ({} races, {} quotes)",
+                asset,
+                duration,
+                stats.score(now_ms),
+                stats.race_loss_rate_since(now_ms - RACE_WINDOW_MS).unwrap_or(0.0) * 100.0,
+                stats.fast_reaction_rate().unwrap_or(0.0) * 100.0,
+                stats.races.len(),
+                stats.reactions.len(),
+            );
+        }
+    }
+}
+
+impl Default for CompetitionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderType;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn make_market() -> Market {
+        Market::new(
+            "btc-updown-5m-0".to_string(),
+            Asset::BTC,
+            Duration::FiveMin,
+            "yes".to_string(),
+            "no".to_string(),
+        )
+    }
+
+    fn make_intent(post_only: bool, price: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".to_string(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size: dec!(10),
+            order_type: if post_only { OrderType::GTC } else { OrderType::FAK },
+            post_only,
+            expiration: None,
+            strategy_tag: "test".to_string(),
+            group_id: None,
+        }
+    }
+
+    fn make_result(status: OrderStatus, error_msg: Option<&str>) -> OrderResult {
+        OrderResult {
+            order_id: "o1".to_string(),
+            token_id: "yes".to_string(),
+            status,
+            filled_size: Decimal::ZERO,
+            avg_fill_price: Decimal::ZERO,
+            remaining_size: dec!(10),
+            timestamp: chrono::Utc::now(),
+            error_msg: error_msg.map(|e| e.to_string()),
+        }
+    }
+
+    fn lost_race() -> OrderResult {
+        make_result(
+            OrderStatus::Rejected,
+            Some("no orders found to match with FAK order"),
+        )
+    }
+
+    #[test]
+    fn test_no_score_without_samples() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        det.on_order_result(&market, &make_intent(false, dec!(0.50)), &lost_race());
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        assert_eq!(det.presence_score(Asset::BTC, Duration::FiveMin, now_ms), 0.0);
+        assert_eq!(det.capital_multiplier(Asset::BTC, Duration::FiveMin, now_ms), 1.0);
+    }
+
+    #[test]
+    fn test_lost_races_mark_contested() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        let intent = make_intent(false, dec!(0.50));
+        for _ in 0..8 {
+            det.on_order_result(&market, &intent, &lost_race());
+        }
+        det.on_order_result(&market, &intent, &make_result(OrderStatus::Open, None));
+        det.on_order_result(&market, &intent, &make_result(OrderStatus::Open, None));
+        // Balance rejections say nothing about competitors
+        det.on_order_result(
+            &market,
+            &intent,
+            &make_result(OrderStatus::Rejected, Some("not enough balance / allowance")),
+        );
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        assert!((det.presence_score(Asset::BTC, Duration::FiveMin, now_ms) - 0.8).abs() < 1e-9);
+        assert!(det.is_contested(Asset::BTC, Duration::FiveMin, now_ms));
+        assert!(det.capital_multiplier(Asset::BTC, Duration::FiveMin, now_ms) < 0.5);
+        assert!(!det.is_contested(Asset::ETH, Duration::FifteenMin, now_ms));
+
+        // Lost races age out of the score on the fill rate's window
+        assert!(!det.is_contested(Asset::BTC, Duration::FiveMin, now_ms + RACE_WINDOW_MS + 1));
+    }
+
+    #[test]
+    fn test_fill_rate_forgets_old_races() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        let intent = make_intent(false, dec!(0.50));
+        for _ in 0..MIN_SAMPLES {
+            det.on_order_result(&market, &intent, &lost_race());
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        assert_eq!(det.fill_success_rate(Asset::BTC, Duration::FiveMin, now_ms), Some(0.0));
+
+        // Once the losses age out there is no verdict until fresh races come in
+        let later = now_ms + RACE_WINDOW_MS + 1;
+        assert_eq!(det.fill_success_rate(Asset::BTC, Duration::FiveMin, later), None);
+    }
+
+    /// A book update on "yes" moving the best bid from `prev` to `bid`.
+    fn bid_moved(prev: Decimal, bid: Decimal, at_ms: i64) -> BookDiff {
+        let bbo = |bid| Bbo { bid: Some((bid, dec!(10))), ask: Some((dec!(0.52), dec!(10))) };
+        BookDiff {
+            token_id: "yes".to_string(),
+            changes: Vec::new(),
+            snapshot: false,
+            prev_bbo: bbo(prev),
+            bbo: bbo(bid),
+            at: chrono::DateTime::from_timestamp_millis(at_ms).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_fast_quote_improvement_detected() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        let intent = make_intent(true, dec!(0.48));
+
+        for i in 0..MIN_SAMPLES as i64 {
+            let t0 = i * 10_000;
+            det.on_order_submitted(&market, &intent, t0);
+
+            // Someone outbids our 0.48 quote 200ms later
+            det.on_book_update(&bid_moved(dec!(0.48), dec!(0.49), t0 + 200));
+        }
+
+        let now_ms = MIN_SAMPLES as i64 * 10_000;
+        assert!(det.is_contested(Asset::BTC, Duration::FiveMin, now_ms));
+    }
+
+    #[test]
+    fn test_only_changes_after_the_quote_count() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+
+        for i in 0..MIN_SAMPLES as i64 {
+            let t0 = i * 10_000;
+            det.on_order_submitted(&market, &make_intent(true, dec!(0.48)), t0);
+            // An outbid applied before our quote went in isn't a reaction to it
+            det.on_book_update(&bid_moved(dec!(0.48), dec!(0.49), t0 - 50));
+            assert_eq!(det.probes.get("yes").unwrap().len(), 1);
+            // Nor is a book that was already ahead of us moving further
+            det.on_book_update(&bid_moved(dec!(0.49), dec!(0.50), t0 + 200));
+            assert!(det.probes.get("yes").unwrap().is_empty());
+        }
+
+        let now_ms = MIN_SAMPLES as i64 * 10_000;
+        assert_eq!(det.presence_score(Asset::BTC, Duration::FiveMin, now_ms), 0.0);
+    }
+
+    #[test]
+    fn test_unchallenged_quotes_expire() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        let intent = make_intent(true, dec!(0.48));
+
+        for i in 0..MIN_SAMPLES as i64 {
+            let t0 = i * 10_000;
+            det.on_order_submitted(&market, &intent, t0);
+            det.on_book_update(&bid_moved(dec!(0.48), dec!(0.48), t0 + PROBE_WINDOW_MS));
+        }
+
+        let now_ms = MIN_SAMPLES as i64 * 10_000;
+        assert_eq!(det.presence_score(Asset::BTC, Duration::FiveMin, now_ms), 0.0);
+        assert!(det.probes.get("yes").map(|p| p.is_empty()).unwrap_or(true));
+    }
+
+    #[test]
+    fn test_stale_probes_dropped_on_submit() {
+        let det = CompetitionDetector::new();
+        let market = make_market();
+        det.on_order_submitted(&market, &make_intent(true, dec!(0.48)), 0);
+
+        // Its market stopped sending books; the next quote clears it out
+        let other = OrderIntent { token_id: "no".to_string(), ..make_intent(true, dec!(0.50)) };
+        det.on_order_submitted(&market, &other, PROBE_STALE_MS);
+        assert!(det.probes.get("yes").is_none());
+        assert_eq!(det.probes.get("no").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::config::FairValueConfig;
+use crate::models::market::{Market, OrderBook, Side};
+use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
+
+/// Where a fair-value estimate came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FairValueSource {
+    /// `ProbabilityModel` on the Binance price
+    Model,
+    /// The YES book's own mid
+    Book,
+    /// `ProbabilityModel` on a second venue's price
+    CrossExchange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairValueComponent {
+    pub source: FairValueSource,
+    /// P(UP) according to this source
+    pub prob_up: f64,
+    /// Inverse-variance weight after staleness decay
+    pub weight: f64,
+}
+
+/// Blended P(UP) with its standard error, in probability units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FairValueEstimate {
+    pub prob_up: f64,
+    /// Combined standard error plus the spread of the sources around the
+    /// blend, so disagreement reads as uncertainty
+    pub uncertainty: f64,
+    pub components: Vec<FairValueComponent>,
+}
+
+impl FairValueEstimate {
+    pub fn prob(&self, side: Side) -> f64 {
+        match side {
+            Side::Yes => self.prob_up,
+            Side::No => 1.0 - self.prob_up,
+        }
+    }
+
+    /// `base_edge` widened by `k` standard errors of the fair value.
+    pub fn required_edge(&self, base_edge: f64, k: f64) -> f64 {
+        base_edge + k * self.uncertainty
+    }
+
+    pub fn component(&self, source: FairValueSource) -> Option<&FairValueComponent> {
+        self.components.iter().find(|c| c.source == source)
+    }
+}
+
+/// Inverse-variance blend of the model, the market's mid and a second
+/// venue's implied probability. The book's weight halves every
+/// `book_half_life_secs` without an update and shrinks with its spread; a
+/// cross-exchange price older than `alt_max_age_secs` is ignored.
+pub struct FairValueEnsemble {
+    config: FairValueConfig,
+    model: ProbabilityModel,
+}
+
+impl FairValueEnsemble {
+    pub fn new(config: FairValueConfig, seasonality: Arc<Seasonality>) -> Self {
+        Self { config, model: ProbabilityModel::new().with_seasonality(seasonality) }
+    }
+
+    /// Blend whatever is available; None when no source is.
+    pub fn estimate(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        binance_price: f64,
+        alt_price: Option<(f64, DateTime<Utc>)>,
+        now: DateTime<Utc>,
+    ) -> Option<FairValueEstimate> {
+        let c = &self.config;
+        let minutes = (market.close_time - now).num_milliseconds() as f64 / 60_000.0;
+        let vol = self.model.vol_per_minute(market.asset);
+        let implied = |spot: f64| {
+            (spot > 0.0 && market.reference_price > 0.0)
+                .then(|| self.model.fair_prob_up(spot, market.reference_price, minutes, vol, 0.0))
+        };
+        let inv_var = |sigma: f64| 1.0 / sigma.max(1e-6).powi(2);
+
+        let mut components = Vec::with_capacity(3);
+        if let Some(p) = implied(binance_price) {
+            components.push(FairValueComponent {
+                source: FairValueSource::Model,
+                prob_up: p,
+                weight: inv_var(c.model_sigma),
+            });
+        }
+        if let (Some(mid), Some(spread)) = (yes_book.midpoint(), yes_book.spread()) {
+            let age_secs = ((now - yes_book.timestamp).num_milliseconds() as f64 / 1000.0).max(0.0);
+            let sigma = c.book_sigma + spread.to_f64().unwrap_or(0.0) / 2.0;
+            let decay = if c.book_half_life_secs > 0.0 { 0.5f64.powf(age_secs / c.book_half_life_secs) } else { 1.0 };
+            components.push(FairValueComponent {
+                source: FairValueSource::Book,
+                prob_up: mid.to_f64().unwrap_or(0.5),
+                weight: inv_var(sigma) * decay,
+            });
+        }
+        if let Some((price, at)) = alt_price {
+            let age_secs = (now - at).num_milliseconds() as f64 / 1000.0;
+            if let Some(p) = implied(price).filter(|_| age_secs <= c.alt_max_age_secs) {
+                components.push(FairValueComponent {
+                    source: FairValueSource::CrossExchange,
+                    prob_up: p,
+                    weight: inv_var(c.alt_sigma),
+                });
+            }
+        }
+
+        let total: f64 = components.iter().map(|c| c.weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let prob_up = components.iter().map(|c| c.weight * c.prob_up).sum::<f64>() / total;
+        let dispersion = components.iter().map(|c| c.weight * (c.prob_up - prob_up).powi(2)).sum::<f64>() / total;
+        Some(FairValueEstimate { prob_up, uncertainty: (1.0 / total + dispersion).sqrt(), components })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use rust_decimal_macros::dec;
+
+    fn setup(now: DateTime<Utc>) -> (FairValueEnsemble, Market, OrderBook) {
+        let ensemble = FairValueEnsemble::new(FairValueConfig::default(), Arc::new(Seasonality::default()));
+        let mut market = Market::new("m".into(), Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+        market.reference_price = 100_000.0;
+        market.close_time = now + chrono::Duration::seconds(120);
+        let mut book = OrderBook::new("yes".into());
+        book.apply_delta([(dec!(0.49), dec!(100))], [(dec!(0.51), dec!(100))]);
+        book.timestamp = now;
+        (ensemble, market, book)
+    }
+
+    #[test]
+    fn test_blend_weights_and_uncertainty() {
+        let now = Utc::now();
+        let (ensemble, market, mut book) = setup(now);
+
+        // At the reference price every source says ~0.5
+        let agree = ensemble.estimate(&market, &book, 100_000.0, Some((100_000.0, now)), now).unwrap();
+        assert_eq!(agree.components.len(), 3);
+        assert!((agree.prob_up - 0.5).abs() < 1e-9);
+
+        // Binance well up, book still at 0.50: the blend sits in between and
+        // the disagreement shows up as uncertainty
+        let split = ensemble.estimate(&market, &book, 100_150.0, None, now).unwrap();
+        let model = split.component(FairValueSource::Model).unwrap().prob_up;
+        assert!(split.prob_up > 0.5 && split.prob_up < model);
+        assert!(split.uncertainty > agree.uncertainty);
+        assert!(split.required_edge(0.03, 1.0) > 0.03 + agree.uncertainty);
+
+        // A minute-old book barely counts
+        book.timestamp = now - chrono::Duration::seconds(60);
+        let stale = ensemble.estimate(&market, &book, 100_150.0, None, now).unwrap();
+        assert!(stale.prob_up > split.prob_up && (model - stale.prob_up) < 0.01);
+
+        // A stale cross-exchange print is ignored; nothing at all gives None
+        let old = now - chrono::Duration::seconds(30);
+        let est = ensemble.estimate(&market, &book, 100_150.0, Some((99_000.0, old)), now).unwrap();
+        assert!(est.component(FairValueSource::CrossExchange).is_none());
+        assert!(ensemble.estimate(&market, &OrderBook::new("yes".into()), 0.0, None, now).is_none());
+        assert!((est.prob(Side::No) - (1.0 - est.prob_up)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::config::HalfLifeConfig;
+use crate::models::market::Market;
+use crate::models::position::strategy_bucket;
+use crate::models::signal::VolRegime;
+use crate::telemetry::journal::JournalEntry;
+use crate::telemetry::markout::series_of;
+use chrono::{DateTime, Timelike, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Durations kept per (series, hour); older ones fall off.
+const MAX_SAMPLES: usize = 500;
+
+/// Lag timing for one market, from its half-life and the vol regime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldLimits {
+    /// Lag entries need at least this much of the window left to correct
+    pub min_remaining_secs: f64,
+    /// Lag positions held longer are sold
+    pub max_hold_secs: f64,
+}
+
+/// How long lag mispricings persist before the book corrects, per market
+/// series and UTC hour, from observer-mode "opportunity" journal lines.
+///
+/// The half-life is the median lifetime: by then half of the mispricings
+/// seen had closed. Short-lived ones mean a slower fill is likelier to find
+/// the edge gone, so lag entries demand more of it (`edge_mult`); entry
+/// cut-off and max hold are a number of half-lives (`limits`).
+/// Falls back from the hour to the whole series, and to the static
+/// behaviour, while samples are short of `min_samples`.
+pub struct OpportunityHalfLife {
+    config: HalfLifeConfig,
+    durations: RwLock<HashMap<(String, u32), VecDeque<f64>>>,
+}
+
+impl OpportunityHalfLife {
+    pub fn new(config: HalfLifeConfig) -> Self {
+        Self { config, durations: RwLock::new(HashMap::new()) }
+    }
+
+    /// Add every lag opportunity line in `entries`.
+    pub fn load(&self, entries: &[JournalEntry]) {
+        for entry in entries {
+            self.record(entry);
+        }
+    }
+
+    /// Add one closed opportunity; other journal lines are ignored.
+    pub fn record(&self, entry: &JournalEntry) {
+        let Some(stats) = entry.opportunity else { return };
+        // Lag opportunities only, mid-cycle and scale-in entries included
+        if strategy_bucket(&entry.strategy) != "lag" {
+            return;
+        }
+        let key = (series_of(&entry.market).to_string(), entry.timestamp.hour());
+        let mut durations = self.durations.write().unwrap();
+        let samples = durations.entry(key).or_default();
+        samples.push_back(stats.duration_secs);
+        if samples.len() > MAX_SAMPLES {
+            samples.pop_front();
+        }
+    }
+
+    /// Median opportunity lifetime for the market's series at `at`'s hour,
+    /// else across the series' hours.
+    pub fn half_life(&self, market: &Market, at: DateTime<Utc>) -> Option<f64> {
+        let series = series_of(&market.slug);
+        let durations = self.durations.read().unwrap();
+        if let Some(hour) = durations.get(&(series.to_string(), at.hour())) {
+            if hour.len() >= self.config.min_samples {
+                return median(hour.iter().copied());
+            }
+        }
+        let pooled: Vec<f64> =
+            durations.iter().filter(|((s, _), _)| s == series).flat_map(|(_, d)| d.iter().copied()).collect();
+        if pooled.len() >= self.config.min_samples {
+            median(pooled.into_iter())
+        } else {
+            None
+        }
+    }
+
+    /// Multiplier on the lag strategy's minimum edge: above 1 when
+    /// mispricings close faster than `reference_secs`, below when they linger.
+    pub fn edge_mult(&self, market: &Market, at: DateTime<Utc>) -> f64 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+        match self.half_life(market, at) {
+            Some(hl) => (self.config.reference_secs / hl.max(0.1))
+                .clamp(self.config.min_edge_mult, self.config.max_edge_mult),
+            None => 1.0,
+        }
+    }
+
+    /// Minimum window left for a lag entry and maximum hold for a lag
+    /// position; None without enough data. Quiet markets correct slower
+    /// than the half-life suggests and fast ones faster, so the regime's
+    /// multiplier stretches holds in low vol and shortens them in high vol
+    /// (dividing the remaining time needed the opposite way).
+    pub fn limits(&self, market: &Market, regime: VolRegime, at: DateTime<Utc>) -> Option<HoldLimits> {
+        if !self.config.enabled {
+            return None;
+        }
+        let hl = self.half_life(market, at)?;
+        let c = &self.config;
+        let mult = match regime {
+            VolRegime::Dead | VolRegime::Low => c.low_vol_mult,
+            VolRegime::Medium => 1.0,
+            VolRegime::High => c.high_vol_mult,
+            VolRegime::Extreme => c.extreme_vol_mult,
+        };
+        Some(HoldLimits {
+            min_remaining_secs: (hl * c.remaining_half_lives / mult).clamp(c.min_remaining_secs, c.max_remaining_secs),
+            max_hold_secs: (hl * c.hold_half_lives * mult).clamp(c.min_hold_secs, c.max_hold_secs),
+        })
+    }
+}
+
+fn median(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut sorted: Vec<f64> = values.collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use crate::telemetry::journal::JournalSource;
+    use crate::telemetry::observer::OpportunityStats;
+    use chrono::TimeZone;
+
+    fn line(slug: &str, strategy: &str, at: DateTime<Utc>, duration_secs: f64) -> JournalEntry {
+        JournalEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: at,
+            kind: "opportunity".into(),
+            source: JournalSource::Observer,
+            market: slug.into(),
+            token_id: "yes".into(),
+            side: None,
+            price: 0.5,
+            size: 10.0,
+            usdc: 5.0,
+            fee: 0.0,
+            strategy: strategy.into(),
+            order_id: None,
+            tx_hash: None,
+            exit_reason: None,
+            hold_secs: None,
+            markouts: None,
+            opportunity: Some(OpportunityStats { duration_secs, ..Default::default() }),
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_half_life_by_hour_then_series() {
+        let table = OpportunityHalfLife::new(HalfLifeConfig { min_samples: 3, ..HalfLifeConfig::default() });
+        let at = |h| Utc.with_ymd_and_hms(2026, 1, 5, h, 10, 0).unwrap();
+        let market = Market::new(
+            Market::generate_slug(Asset::BTC, Duration::FiveMin, 1_700_000_100),
+            Asset::BTC,
+            Duration::FiveMin,
+            "yes".into(),
+            "no".into(),
+        );
+        assert_eq!(table.edge_mult(&market, at(14)), 1.0);
+        assert_eq!(table.limits(&market, VolRegime::Medium, at(14)), None);
+
+        // Hour 14: mispricings last ~2s, mid-cycle and scale-in lag entries included;
+        // hour 3: ~20s; other strategies don't count
+        table.load(&[
+            line("btc-updown-5m-1", "lag_exploit", at(14), 1.0),
+            line("btc-updown-5m-2", "lag_exploit@mid", at(14), 2.0),
+            line("btc-updown-5m-3", "lag_exploit+add", at(14), 3.0),
+            line("btc-updown-5m-4", "lag_exploit", at(3), 20.0),
+            line("btc-updown-5m-5", "market_maker", at(3), 99.0),
+            line("btc-updown-5m-6", "market_maker", at(3), 99.0),
+        ]);
+        assert_eq!(table.half_life(&market, at(14)), Some(2.0));
+        assert_eq!(table.edge_mult(&market, at(14)), 2.0, "5s reference / 2s half-life, capped");
+        let limits = table.limits(&market, VolRegime::Medium, at(14)).unwrap();
+        assert_eq!(limits.max_hold_secs, 10.0, "3 half-lives, floored at 10s");
+        assert_eq!(limits.min_remaining_secs, 48.0, "24 half-lives");
+        let extreme = table.limits(&market, VolRegime::Extreme, at(14)).unwrap();
+        assert_eq!((extreme.min_remaining_secs, extreme.max_hold_secs), (96.0, 10.0));
+
+        // Too few in hour 3 alone: pooled series median of 1, 2, 3, 20
+        assert_eq!(table.half_life(&market, at(3)), Some(2.5));
+        table.load(&[line("btc-updown-5m-7", "lag_exploit", at(3), 30.0), line("btc-updown-5m-8", "lag_exploit", at(3), 25.0)]);
+        assert_eq!(table.half_life(&market, at(3)), Some(25.0));
+        assert_eq!(table.edge_mult(&market, at(3)), 0.75, "lingering mispricings relax the bar, floored");
+        let limits = table.limits(&market, VolRegime::Low, at(3)).unwrap();
+        assert_eq!(limits.max_hold_secs, 93.75, "3 half-lives, stretched 1.25x in low vol");
+        assert_eq!(limits.min_remaining_secs, 180.0, "capped");
+    }
+}
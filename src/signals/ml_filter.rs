@@ -0,0 +1,383 @@
+use crate::config::MlFilterConfig;
+use crate::models::market::{Market, OrderBook, Side};
+use crate::models::order::{IntentGroup, LegSet, OrderIntent, OrderSide};
+use crate::models::position::strategy_bucket;
+use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
+use crate::telemetry::recorder::TopOfBook;
+use anyhow::Result;
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Model inputs, in order: the snapshot columns of `export_features`
+/// (missing prices as NaN) followed by the candidate order — the columns of
+/// its intents file, which is labelled `won`.
+pub const INPUTS: [&str; 15] = [
+    "remaining_secs",
+    "spot_vs_ref",
+    "fair_prob_up",
+    "yes_bid",
+    "yes_ask",
+    "yes_bid_size",
+    "yes_ask_size",
+    "no_bid",
+    "no_ask",
+    "no_bid_size",
+    "no_ask_size",
+    "outcome_yes",
+    "is_buy",
+    "price",
+    "size",
+];
+
+enum Scorer {
+    #[cfg(feature = "ml")]
+    Onnx(Box<onnx::Plan>),
+    /// A closed-form score, for tests and replays
+    Fixed(fn(&[f32; INPUTS.len()]) -> f32),
+}
+
+/// Scores candidate entries with an ONNX model trained offline and drops
+/// the trades whose predicted win probability is under the strategy
+/// family's threshold. Exits are never scored. The model takes a `[1, 15]`
+/// float32 tensor (`INPUTS`) and its first float output's last value is
+/// read as P(win), so both a sigmoid
+/// `[1, 1]` and a softmax `[1, 2]` head work. Scoring failures let the
+/// intent through.
+pub struct MlFilter {
+    scorer: Scorer,
+    config: MlFilterConfig,
+    model: ProbabilityModel,
+    scored: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl MlFilter {
+    /// Load the configured model; None when no model is configured.
+    pub fn load(config: &MlFilterConfig, seasonality: Arc<Seasonality>) -> Result<Option<Self>> {
+        let Some(path) = &config.model_path else { return Ok(None) };
+        #[cfg(feature = "ml")]
+        {
+            let scorer = Scorer::Onnx(Box::new(onnx::load(path)?));
+            tracing::info!("ML entry filter loaded from {path} (min win prob {:.2})", config.min_win_prob);
+            Ok(Some(Self::with_scorer(scorer, config, seasonality)))
+        }
+        #[cfg(not(feature = "ml"))]
+        {
+            let _ = seasonality;
+            anyhow::bail!("ML_FILTER_MODEL={path} needs a build with `--features ml`")
+        }
+    }
+
+    fn with_scorer(scorer: Scorer, config: &MlFilterConfig, seasonality: Arc<Seasonality>) -> Self {
+        Self {
+            scorer,
+            config: config.clone(),
+            model: ProbabilityModel::new().with_seasonality(seasonality),
+            scored: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Minimum win probability for a strategy tag's family.
+    pub fn threshold(&self, strategy_tag: &str) -> f64 {
+        self.config
+            .strategy_min_win_prob
+            .get(strategy_bucket(strategy_tag))
+            .copied()
+            .unwrap_or(self.config.min_win_prob)
+    }
+
+    /// (entries scored, intents dropped) since startup.
+    pub fn counts(&self) -> (u64, u64) {
+        (self.scored.load(Ordering::Relaxed), self.rejected.load(Ordering::Relaxed))
+    }
+
+    /// Model input vector for one candidate intent.
+    pub fn inputs(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        intent: &OrderIntent,
+    ) -> [f32; INPUTS.len()] {
+        let remaining_secs = (market.close_time - chrono::Utc::now()).num_milliseconds() as f64 / 1000.0;
+        let (spot_vs_ref, fair_prob_up) = if market.reference_price > 0.0 && binance_price > 0.0 {
+            let vol = self.model.vol_per_minute(market.asset);
+            (
+                binance_price / market.reference_price - 1.0,
+                self.model.fair_prob_up(binance_price, market.reference_price, remaining_secs / 60.0, vol, 0.0),
+            )
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+        let (yes, no) = (TopOfBook::of(yes_book), TopOfBook::of(no_book));
+        let flag = |b: bool| if b { 1.0 } else { 0.0 };
+        [
+            remaining_secs,
+            spot_vs_ref,
+            fair_prob_up,
+            yes.bid.unwrap_or(f64::NAN),
+            yes.ask.unwrap_or(f64::NAN),
+            yes.bid_size,
+            yes.ask_size,
+            no.bid.unwrap_or(f64::NAN),
+            no.ask.unwrap_or(f64::NAN),
+            no.bid_size,
+            no.ask_size,
+            flag(intent.market_side == Side::Yes),
+            flag(intent.order_side == OrderSide::Buy),
+            intent.price.to_f64().unwrap_or(f64::NAN),
+            intent.size.to_f64().unwrap_or(f64::NAN),
+        ]
+        .map(|v| v as f32)
+    }
+
+    pub fn score(&self, inputs: &[f32; INPUTS.len()]) -> Result<f64> {
+        match self.scorer {
+            #[cfg(feature = "ml")]
+            Scorer::Onnx(ref plan) => onnx::run(plan, inputs),
+            Scorer::Fixed(f) => Ok(f(inputs) as f64),
+        }
+    }
+
+    /// Drop trades the model doesn't expect to win often enough.
+    ///
+    /// Intents are judged as the trades `sets` groups them into, whole: a
+    /// paired trade pays through whichever leg resolves in the money, so it
+    /// goes if its best-scoring entry leg clears the threshold, and is
+    /// dropped with all its legs otherwise. Sells reduce inventory and
+    /// always pass.
+    pub fn apply(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        sets: &[LegSet],
+        intents: &mut Vec<OrderIntent>,
+    ) {
+        let mut kept = Vec::with_capacity(intents.len());
+        for group in IntentGroup::collect(std::mem::take(intents), sets) {
+            let mut verdicts = group
+                .legs
+                .iter()
+                .filter(|leg| leg.order_side == OrderSide::Buy)
+                .filter_map(|leg| self.passes(market, yes_book, no_book, binance_price, leg))
+                .peekable();
+            // No entry legs, or none that could be scored: let it through
+            if verdicts.peek().is_none() || verdicts.any(|pass| pass) {
+                kept.extend(group.legs);
+                continue;
+            }
+            self.rejected.fetch_add(group.legs.len() as u64, Ordering::Relaxed);
+        }
+        *intents = kept;
+    }
+
+    /// Whether one entry clears its family's threshold; None if it couldn't
+    /// be scored.
+    fn passes(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        intent: &OrderIntent,
+    ) -> Option<bool> {
+        let inputs = self.inputs(market, yes_book, no_book, binance_price, intent);
+        let prob = match self.score(&inputs) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!("ML filter scoring failed for {}: {e}", intent.strategy_tag);
+                return None;
+            }
+        };
+        self.scored.fetch_add(1, Ordering::Relaxed);
+        let threshold = self.threshold(&intent.strategy_tag);
+        if prob < threshold {
+            debug!(
+                "ML filter: {} {:?} {:?} @ {} on {} has p(win) {prob:.3} < {threshold:.2}",
+                intent.strategy_tag, intent.order_side, intent.market_side, intent.price, market.slug
+            );
+        }
+        Some(prob >= threshold)
+    }
+}
+
+#[cfg(feature = "ml")]
+mod onnx {
+    use super::INPUTS;
+    use anyhow::{Context, Result};
+    use tract_onnx::prelude::*;
+
+    pub type Plan = TypedSimplePlan<TypedModel>;
+
+    pub fn load(path: &str) -> Result<Plan> {
+        compile(tract_onnx::onnx().model_for_path(path).with_context(|| format!("loading ONNX model {path}"))?)
+    }
+
+    pub fn compile(model: InferenceModel) -> Result<Plan> {
+        model
+            .with_input_fact(0, f32::fact([1, INPUTS.len()]).into())?
+            .into_optimized()?
+            .into_runnable()
+    }
+
+    pub fn run(plan: &Plan, inputs: &[f32; INPUTS.len()]) -> Result<f64> {
+        let input = tract_ndarray::Array2::from_shape_vec((1, INPUTS.len()), inputs.to_vec())?.into_tensor();
+        let outputs = plan.run(tvec!(input.into()))?;
+        let probs = outputs
+            .iter()
+            .find(|t| t.datum_type() == f32::datum_type())
+            .context("model has no float output")?;
+        let p = *probs.as_slice::<f32>()?.last().context("empty model output")?;
+        Ok(p as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    fn intent(tag: &str, price: rust_decimal::Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size: dec!(10),
+            order_type: OrderType::GTC,
+            post_only: false,
+            expiration: None,
+            strategy_tag: tag.into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_filters_by_family_threshold() {
+        let config = MlFilterConfig {
+            model_path: None,
+            min_win_prob: 0.5,
+            strategy_min_win_prob: [("lag".to_string(), 0.7)].into(),
+        };
+        // "Model": a cheap entry is a likely winner
+        let filter = MlFilter::with_scorer(
+            Scorer::Fixed(|x| 1.0 - x[INPUTS.len() - 2]),
+            &config,
+            Arc::new(Seasonality::default()),
+        );
+        let mut market = Market::new("m".into(), Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+        market.reference_price = 100_000.0;
+        let book = OrderBook::new("yes".into());
+
+        let inputs = filter.inputs(&market, &book, &book, 100_050.0, &intent("lag_exploit", dec!(0.40)));
+        assert!(inputs[1] > 0.0 && inputs[3].is_nan());
+        assert_eq!(filter.threshold("mm_bid"), 0.5);
+
+        let mut intents = vec![
+            intent("lag_exploit", dec!(0.25)), // 0.75 ≥ 0.7
+            intent("lag_exploit", dec!(0.40)), // 0.60 < 0.7
+            intent("mm_bid", dec!(0.40)),      // 0.60 ≥ 0.5
+            intent("momentum", dec!(0.60)),    // 0.40 < 0.5
+        ];
+        filter.apply(&market, &book, &book, 100_050.0, &[], &mut intents);
+        let kept: Vec<_> = intents.iter().map(|i| (i.strategy_tag.as_str(), i.price)).collect();
+        assert_eq!(kept, [("lag_exploit", dec!(0.25)), ("mm_bid", dec!(0.40))]);
+        assert_eq!(filter.counts(), (4, 2));
+    }
+
+    #[test]
+    fn test_judges_whole_trades_and_skips_exits() {
+        let filter = MlFilter::with_scorer(
+            Scorer::Fixed(|x| 1.0 - x[INPUTS.len() - 2]),
+            &MlFilterConfig { model_path: None, min_win_prob: 0.5, ..Default::default() },
+            Arc::new(Seasonality::default()),
+        );
+        let market = Market::new("m".into(), Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+        let book = OrderBook::new("yes".into());
+        let sets = [crate::strategies::pure_arb::LEGS];
+        let sell = OrderIntent { order_side: OrderSide::Sell, ..intent("lag_exploit", dec!(0.90)) };
+
+        // The pair rides on its cheap leg; the dear leg alone would have been dropped
+        let mut intents = vec![intent("arb_yes", dec!(0.45)), intent("arb_no", dec!(0.52)), sell];
+        filter.apply(&market, &book, &book, 0.0, &sets, &mut intents);
+        let kept: Vec<_> = intents.iter().map(|i| i.strategy_tag.as_str()).collect();
+        assert_eq!(kept, ["arb_yes", "arb_no", "lag_exploit"]);
+
+        // A pair with no leg worth having goes as a whole
+        let mut intents = vec![intent("arb_yes", dec!(0.55)), intent("arb_no", dec!(0.60))];
+        filter.apply(&market, &book, &book, 0.0, &sets, &mut intents);
+        assert!(intents.is_empty());
+        assert_eq!(filter.counts().1, 2);
+    }
+
+    /// A one-node ONNX graph, `p = sigmoid(sum(x * w))`, built in memory.
+    #[cfg(feature = "ml")]
+    #[test]
+    fn test_scores_onnx_model() {
+        use tract_onnx::pb;
+        use tract_onnx::prelude::Framework;
+
+        let float_input = |name: &str, dims: &[i64]| pb::ValueInfoProto {
+            name: name.into(),
+            r#type: Some(pb::TypeProto {
+                value: Some(pb::type_proto::Value::TensorType(pb::type_proto::Tensor {
+                    elem_type: pb::tensor_proto::DataType::Float as i32,
+                    shape: Some(pb::TensorShapeProto {
+                        dim: dims
+                            .iter()
+                            .map(|&d| pb::tensor_shape_proto::Dimension {
+                                value: Some(pb::tensor_shape_proto::dimension::Value::DimValue(d)),
+                                ..Default::default()
+                            })
+                            .collect(),
+                    }),
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let node = |op: &str, inputs: &[&str], output: &str| pb::NodeProto {
+            op_type: op.into(),
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: vec![output.into()],
+            ..Default::default()
+        };
+        // Weight only the price column: p = sigmoid(-4 × price)
+        let mut w = vec![0.0f32; INPUTS.len()];
+        w[INPUTS.len() - 2] = -4.0;
+        let proto = pb::ModelProto {
+            ir_version: 7,
+            opset_import: vec![pb::OperatorSetIdProto { domain: String::new(), version: 13 }],
+            graph: Some(pb::GraphProto {
+                node: vec![node("MatMul", &["x", "w"], "logit"), node("Sigmoid", &["logit"], "p")],
+                initializer: vec![pb::TensorProto {
+                    name: "w".into(),
+                    dims: vec![INPUTS.len() as i64, 1],
+                    data_type: pb::tensor_proto::DataType::Float as i32,
+                    float_data: w,
+                    ..Default::default()
+                }],
+                input: vec![float_input("x", &[1, INPUTS.len() as i64])],
+                output: vec![float_input("p", &[1, 1])],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let plan = onnx::compile(tract_onnx::onnx().model_for_proto_model(&proto).unwrap()).unwrap();
+        let filter = MlFilter::with_scorer(Scorer::Onnx(Box::new(plan)), &MlFilterConfig::default(), Arc::new(Seasonality::default()));
+
+        let mut inputs = [0.0f32; INPUTS.len()];
+        inputs[INPUTS.len() - 2] = 0.5;
+        let expected = 1.0 / (1.0 + 2.0f64.exp());
+        assert!((filter.score(&inputs).unwrap() - expected).abs() < 1e-6);
+    }
+}
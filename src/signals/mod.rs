@@ -5,3 +5,10 @@ pub mod arb_scanner;
 pub mod momentum;
 pub mod compression;
 pub mod realtime_vol;
+pub mod competition;
+pub mod seasonality;
+pub mod ml_filter;
+pub mod fair_value;
+pub mod projection;
+pub mod basis;
+pub mod half_life;
@@ -1,4 +1,8 @@
+use crate::models::market::Asset;
+use crate::signals::projection::ProjectedPrice;
+use crate::signals::seasonality::Seasonality;
 use statrs::distribution::{ContinuousCDF, Normal};
+use std::sync::Arc;
 
 /// Price-to-implied-probability model using Black-Scholes-like approach.
 ///
@@ -7,15 +11,28 @@ use statrs::distribution::{ContinuousCDF, Normal};
 ///   where d = pct_move / (σ_per_min × √(minutes_remaining))
 pub struct ProbabilityModel {
     normal: Normal,
+    seasonality: Arc<Seasonality>,
 }
 
 impl ProbabilityModel {
     pub fn new() -> Self {
         Self {
             normal: Normal::new(0.0, 1.0).expect("valid normal distribution"),
+            seasonality: Arc::new(Seasonality::default()),
         }
     }
 
+    /// Scale vol inputs by the hour-of-day curve.
+    pub fn with_seasonality(mut self, seasonality: Arc<Seasonality>) -> Self {
+        self.seasonality = seasonality;
+        self
+    }
+
+    /// Per-minute vol to feed `fair_prob_up` for `asset` right now.
+    pub fn vol_per_minute(&self, asset: Asset) -> f64 {
+        self.seasonality.vol_per_minute(asset, chrono::Utc::now())
+    }
+
     /// Calculate fair probability that price will be UP at expiry.
     ///
     /// - `current_price`: current underlying price (e.g. Binance BTC/USDT)
@@ -54,6 +71,25 @@ impl ProbabilityModel {
         self.normal.cdf(adjusted_z).clamp(0.01, 0.99)
     }
 
+    /// `fair_prob_up` on a latency-projected price: the projection's own
+    /// error adds to the variance left in the window.
+    pub fn fair_prob_up_projected(
+        &self,
+        projected: ProjectedPrice,
+        open_price: f64,
+        minutes_remaining: f64,
+        vol_per_min: f64,
+        momentum_adj: f64,
+    ) -> f64 {
+        if minutes_remaining <= 0.0 || projected.sigma_pct <= 0.0 {
+            return self.fair_prob_up(projected.price, open_price, minutes_remaining, vol_per_min, momentum_adj);
+        }
+        // Fold the projection error into an equivalent per-minute vol
+        let variance = vol_per_min.powi(2) * minutes_remaining + projected.sigma_pct.powi(2);
+        let effective_vol = (variance / minutes_remaining).sqrt();
+        self.fair_prob_up(projected.price, open_price, minutes_remaining, effective_vol, momentum_adj)
+    }
+
     /// Calculate fair probability that price will be DOWN at expiry.
     pub fn fair_prob_down(
         &self,
@@ -70,6 +106,7 @@ impl ProbabilityModel {
     ///
     /// Returns (yes_mispricing, no_mispricing).
     /// Positive = token is underpriced (buy opportunity).
+    #[allow(clippy::too_many_arguments)]
     pub fn mispricing(
         &self,
         current_price: f64,
@@ -179,6 +216,19 @@ mod tests {
         assert!(prob > 0.75, "Near expiry with positive drift should be high prob, got {prob}");
     }
 
+    #[test]
+    fn test_projection_error_widens_fair_value() {
+        let model = ProbabilityModel::new();
+        let spot = model.fair_prob_up(100_100.0, 100_000.0, 1.0, 0.000758, 0.0);
+        let exact = ProjectedPrice::spot(100_100.0);
+        assert_eq!(model.fair_prob_up_projected(exact, 100_000.0, 1.0, 0.000758, 0.0), spot);
+
+        // Same projected price, less sure of it → pulled toward 0.5
+        let unsure = ProjectedPrice { sigma_pct: 0.0005, ..exact };
+        let prob = model.fair_prob_up_projected(unsure, 100_000.0, 1.0, 0.000758, 0.0);
+        assert!(prob > 0.5 && prob < spot, "expected between 0.5 and {spot}, got {prob}");
+    }
+
     #[test]
     fn test_kelly_positive_edge() {
         let model = ProbabilityModel::new();
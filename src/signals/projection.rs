@@ -0,0 +1,165 @@
+use crate::config::ProjectionConfig;
+use crate::models::market::Asset;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+
+/// Underlying price carried forward to when our order can actually land.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectedPrice {
+    pub price: f64,
+    /// Standard error of the projection as a fraction of price; adds to the
+    /// model's remaining-window variance
+    pub sigma_pct: f64,
+    /// How far ahead of the last print we projected
+    pub horizon_secs: f64,
+}
+
+impl ProjectedPrice {
+    /// The last print as-is, with no projection error.
+    pub fn spot(price: f64) -> Self {
+        Self { price, sigma_pct: 0.0, horizon_secs: 0.0 }
+    }
+}
+
+/// Short-horizon drift extrapolation of the Binance price.
+///
+/// The last print is already stale by the time an order based on it reaches
+/// the book: it aged since it arrived, plus `latency_ms` of feed and
+/// submission delay. Fits a least-squares line through the prints of the last
+/// `lookback_ms` and extends it over that horizon (capped at
+/// `max_horizon_ms`). The slope is shrunk by t²/(1+t²) so noise around a flat
+/// price projects to ~nothing, and the slope's standard error over the
+/// horizon is reported as `sigma_pct`.
+pub struct PriceProjector {
+    config: ProjectionConfig,
+    ticks: DashMap<Asset, VecDeque<(DateTime<Utc>, f64)>>,
+}
+
+/// Fewer prints than this in the lookback can't support a slope
+const MIN_TICKS: usize = 3;
+
+impl PriceProjector {
+    pub fn new(config: ProjectionConfig) -> Self {
+        Self { config, ticks: DashMap::new() }
+    }
+
+    /// Record a Binance print received at `at`.
+    pub fn observe(&self, asset: Asset, price: f64, at: DateTime<Utc>) {
+        let mut ticks = self.ticks.entry(asset).or_default();
+        ticks.push_back((at, price));
+        let cutoff = at - chrono::Duration::milliseconds(self.config.lookback_ms as i64);
+        while ticks.front().is_some_and(|(t, _)| *t < cutoff) {
+            ticks.pop_front();
+        }
+    }
+
+    /// Project `price` (the latest print) to where it should be once an
+    /// order placed at `now` arrives. Falls back to the spot price when
+    /// disabled or without enough recent prints.
+    pub fn project(&self, asset: Asset, price: f64, now: DateTime<Utc>) -> ProjectedPrice {
+        if !self.config.enabled || price <= 0.0 {
+            return ProjectedPrice::spot(price);
+        }
+        let Some(ticks) = self.ticks.get(&asset) else {
+            return ProjectedPrice::spot(price);
+        };
+        let Some(&(last_at, _)) = ticks.back() else {
+            return ProjectedPrice::spot(price);
+        };
+        let cutoff = last_at - chrono::Duration::milliseconds(self.config.lookback_ms as i64);
+        let window: Vec<(f64, f64)> = ticks
+            .iter()
+            .filter(|(t, _)| *t >= cutoff)
+            .map(|(t, p)| ((*t - last_at).num_milliseconds() as f64 / 1000.0, *p))
+            .collect();
+        drop(ticks);
+        let Some((slope, slope_se)) = fit_slope(&window) else {
+            return ProjectedPrice::spot(price);
+        };
+
+        let age_ms = ((now - last_at).num_milliseconds() as f64).max(0.0);
+        let horizon_secs = (age_ms + self.config.latency_ms).min(self.config.max_horizon_ms) / 1000.0;
+        let t2 = if slope_se > 0.0 { (slope / slope_se).powi(2) } else { 0.0 };
+        let shrink = t2 / (1.0 + t2);
+
+        ProjectedPrice {
+            price: price + slope * shrink * horizon_secs,
+            sigma_pct: slope_se * horizon_secs / price,
+            horizon_secs,
+        }
+    }
+}
+
+/// Least-squares slope of (secs, price) points and its standard error.
+fn fit_slope(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < MIN_TICKS {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_t = points.iter().map(|(t, _)| t).sum::<f64>() / n;
+    let mean_p = points.iter().map(|(_, p)| p).sum::<f64>() / n;
+    let stt: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+    if stt <= 0.0 {
+        return None;
+    }
+    let slope = points.iter().map(|(t, p)| (t - mean_t) * (p - mean_p)).sum::<f64>() / stt;
+    let sse: f64 = points
+        .iter()
+        .map(|(t, p)| (p - mean_p - slope * (t - mean_t)).powi(2))
+        .sum();
+    let slope_se = (sse / (n - 2.0) / stt).sqrt();
+    Some((slope, slope_se))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(projector: &PriceProjector, start: DateTime<Utc>, prices: impl Fn(i64) -> f64) -> DateTime<Utc> {
+        let mut at = start;
+        for i in 0..10 {
+            at = start + chrono::Duration::milliseconds(i * 50);
+            projector.observe(Asset::BTC, prices(i), at);
+        }
+        at
+    }
+
+    #[test]
+    fn test_trend_projects_closer_to_future_price() {
+        let projector = PriceProjector::new(ProjectionConfig::default());
+        let start = Utc::now();
+        // +$2 per 50ms print with a little noise
+        let path = |i: i64| 100_000.0 + 2.0 * i as f64 + if i % 2 == 0 { 0.3 } else { -0.3 };
+        let last_at = feed(&projector, start, path);
+
+        let now = last_at + chrono::Duration::milliseconds(50);
+        let projected = projector.project(Asset::BTC, path(9), now);
+        let horizon_ticks = projected.horizon_secs / 0.05;
+        let future = 100_000.0 + 2.0 * (9.0 + horizon_ticks);
+        assert!((projected.horizon_secs - 0.2).abs() < 1e-9, "age 50ms + 150ms latency");
+        assert!(
+            (projected.price - future).abs() < (path(9) - future).abs() / 4.0,
+            "projection {} should be much nearer {future} than spot {}",
+            projected.price,
+            path(9)
+        );
+        assert!(projected.sigma_pct > 0.0);
+    }
+
+    #[test]
+    fn test_noise_and_sparse_history_stay_at_spot() {
+        let projector = PriceProjector::new(ProjectionConfig::default());
+        let start = Utc::now();
+        let noise = |i: i64| 100_000.0 + if i % 2 == 0 { 1.0 } else { -1.0 };
+        let last_at = feed(&projector, start, noise);
+        let projected = projector.project(Asset::BTC, noise(9), last_at);
+        assert!((projected.price - noise(9)).abs() < 0.2, "flat noise barely moves: {}", projected.price);
+
+        // Prints older than the lookback are forgotten
+        let later = last_at + chrono::Duration::seconds(5);
+        projector.observe(Asset::BTC, 100_000.0, later);
+        assert_eq!(projector.project(Asset::BTC, 100_000.0, later), ProjectedPrice::spot(100_000.0));
+        assert_eq!(projector.project(Asset::ETH, 3_000.0, later), ProjectedPrice::spot(3_000.0));
+    }
+}
@@ -156,6 +156,16 @@ impl RealtimeVolTracker {
     }
 }
 
+/// ATR(1m) and regime from a tick series `(price, timestamp_ms)`, the way
+/// the tracker computes them (over the last 300 ticks).
+pub fn atr_from_ticks(ticks: &[(f64, i64)]) -> (f64, VolRegime) {
+    let mut window = PriceWindow::new(300);
+    for &(price, timestamp_ms) in ticks {
+        window.push(price, timestamp_ms);
+    }
+    (window.atr_1m, window.regime)
+}
+
 impl Default for RealtimeVolTracker {
     fn default() -> Self {
         Self::new()
@@ -215,4 +225,18 @@ mod tests {
             regime
         );
     }
+
+    #[tokio::test]
+    async fn test_atr_from_ticks_matches_tracker() {
+        let tracker = RealtimeVolTracker::new();
+        let ticks: Vec<(f64, i64)> =
+            (0..400).map(|i| (100_000.0 + (i as f64 * 0.7).sin() * 40.0, i * 1000)).collect();
+        for &(price, ts) in &ticks {
+            tracker.on_price(Asset::BTC, price, ts).await;
+        }
+        let (atr, regime) = atr_from_ticks(&ticks);
+        assert!(atr > 0.0);
+        assert_eq!(atr, tracker.atr_1m(Asset::BTC).await);
+        assert_eq!(regime, tracker.regime(Asset::BTC).await);
+    }
 }
@@ -0,0 +1,295 @@
+use crate::models::market::Asset;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Hours with fewer 1-minute returns than this fall back to the asset's overall vol
+pub const MIN_HOUR_SAMPLES: usize = 30;
+
+/// Gaps longer than this between closes aren't treated as 1-minute returns
+const MAX_GAP_SECS: i64 = 90;
+
+/// Intraday vol curve for one asset: per-minute log-return vol by UTC hour.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolCurve {
+    pub asset: Asset,
+    /// σ of 1-minute log returns over the whole sample
+    pub overall_per_minute: f64,
+    /// σ of 1-minute log returns by UTC hour of day (sparse hours = overall)
+    pub hourly_per_minute: [f64; 24],
+    pub samples: [usize; 24],
+}
+
+impl VolCurve {
+    /// Estimate from 1-minute closes, oldest first. Returns are assumed
+    /// zero-mean, which is standard at this horizon. None with no returns.
+    pub fn from_closes(asset: Asset, closes: &[(DateTime<Utc>, f64)]) -> Option<Self> {
+        let mut sum_sq = [0.0; 24];
+        let mut samples = [0usize; 24];
+        for pair in closes.windows(2) {
+            let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+            let gap = (t1 - t0).num_seconds();
+            if gap <= 0 || gap > MAX_GAP_SECS || p0 <= 0.0 || p1 <= 0.0 {
+                continue;
+            }
+            let r = (p1 / p0).ln();
+            let hour = t1.hour() as usize;
+            sum_sq[hour] += r * r;
+            samples[hour] += 1;
+        }
+        let total: usize = samples.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let overall_per_minute = (sum_sq.iter().sum::<f64>() / total as f64).sqrt();
+        let mut hourly_per_minute = [overall_per_minute; 24];
+        for hour in 0..24 {
+            if samples[hour] >= MIN_HOUR_SAMPLES {
+                hourly_per_minute[hour] = (sum_sq[hour] / samples[hour] as f64).sqrt();
+            }
+        }
+        Some(Self { asset, overall_per_minute, hourly_per_minute, samples })
+    }
+
+    pub fn per_minute_at(&self, at: DateTime<Utc>) -> f64 {
+        self.hourly_per_minute[at.hour() as usize]
+    }
+
+    /// Hour's vol relative to the day's average (1.0 = typical).
+    pub fn seasonal_factor(&self, at: DateTime<Utc>) -> f64 {
+        if self.overall_per_minute <= 0.0 {
+            return 1.0;
+        }
+        self.per_minute_at(at) / self.overall_per_minute
+    }
+
+    pub fn annualized(&self) -> f64 {
+        self.overall_per_minute * 525_600.0_f64.sqrt()
+    }
+}
+
+/// Calibrated vol curves, written by `cargo run --bin calibrate_vol`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolCalibration {
+    pub generated_at: DateTime<Utc>,
+    /// Where the data came from, e.g. "binance klines 14d"
+    pub source: String,
+    pub curves: Vec<VolCurve>,
+}
+
+impl VolCalibration {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn curve(&self, asset: Asset) -> Option<&VolCurve> {
+        self.curves.iter().find(|c| c.asset == asset)
+    }
+
+    /// Calibrated per-minute vol at a time of day, else the asset's constant.
+    pub fn vol_per_minute(&self, asset: Asset, at: DateTime<Utc>) -> f64 {
+        self.curve(asset)
+            .map(|c| c.per_minute_at(at))
+            .unwrap_or_else(|| asset.vol_per_minute())
+    }
+}
+
+/// Bounds on the time-of-day size multiplier. It only ever shrinks size: a
+/// quiet hour must not push a market past its share of capital.
+const MIN_SIZE_FACTOR: f64 = 0.5;
+const MAX_SIZE_FACTOR: f64 = 1.0;
+
+/// Time-of-day adjustments from a vol calibration. Without one, every factor
+/// is 1.0 and the per-asset constants are used as-is.
+///
+/// The calibrated curve only supplies the *shape* of the day: the model's vol
+/// input is the asset constant scaled by the hour's factor, so the overall
+/// level stays where the strategies were tuned. Sizing leans the other way —
+/// smaller in the volatile hours, full size in quiet ones.
+#[derive(Debug, Clone, Default)]
+pub struct Seasonality {
+    calibration: Option<VolCalibration>,
+}
+
+impl Seasonality {
+    pub fn new(calibration: Option<VolCalibration>) -> Self {
+        Self { calibration }
+    }
+
+    /// Load the calibration at `path`; a missing or unreadable file means
+    /// no adjustment.
+    pub fn load(path: Option<&str>) -> Self {
+        let Some(path) = path else { return Self::default() };
+        if !Path::new(path).exists() {
+            info!("No vol calibration at {path} — run `cargo run --bin calibrate_vol` for time-of-day vol");
+            return Self::default();
+        }
+        match VolCalibration::load(path) {
+            Ok(cal) => {
+                info!("Vol calibration: {} curves from {} ({})", cal.curves.len(), cal.source, cal.generated_at.format("%Y-%m-%d"));
+                Self::new(Some(cal))
+            }
+            Err(e) => {
+                warn!("Ignoring vol calibration: {e:#}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn is_calibrated(&self) -> bool {
+        self.calibration.is_some()
+    }
+
+    /// The hour's vol relative to the asset's daily average.
+    pub fn vol_factor(&self, asset: Asset, at: DateTime<Utc>) -> f64 {
+        self.calibration
+            .as_ref()
+            .and_then(|c| c.curve(asset))
+            .map(|c| c.seasonal_factor(at))
+            .unwrap_or(1.0)
+    }
+
+    /// Per-minute vol input for the probability model.
+    pub fn vol_per_minute(&self, asset: Asset, at: DateTime<Utc>) -> f64 {
+        asset.vol_per_minute() * self.vol_factor(asset, at)
+    }
+
+    /// Baseline size multiplier: inverse of the vol factor, never above 1.
+    pub fn size_factor(&self, asset: Asset, at: DateTime<Utc>) -> f64 {
+        let vol = self.vol_factor(asset, at);
+        if vol <= 0.0 {
+            return 1.0;
+        }
+        (1.0 / vol).clamp(MIN_SIZE_FACTOR, MAX_SIZE_FACTOR)
+    }
+
+    pub fn log_summary(&self, at: DateTime<Utc>) {
+        let Some(cal) = &self.calibration else { return };
+        for curve in &cal.curves {
+            info!(
+                "Seasonality [{:?} {:02}h UTC]: vol x{:.2} size x{:.2}",
+                curve.asset,
+                at.hour(),
+                self.vol_factor(curve.asset, at),
+                self.size_factor(curve.asset, at),
+            );
+        }
+    }
+}
+
+/// Parse a data.binance.vision kline CSV: open time in ms in column 0, close
+/// in column 4. Returns the close stamped at the kline's open time; header
+/// and malformed rows are skipped.
+pub fn parse_kline_csv(text: &str) -> Vec<(DateTime<Utc>, f64)> {
+    text.lines()
+        .filter_map(|line| {
+            let cols: Vec<&str> = line.split(',').map(str::trim).collect();
+            let open_ms: i64 = cols.first()?.parse().ok()?;
+            let close: f64 = cols.get(4)?.parse().ok()?;
+            Some((DateTime::from_timestamp_millis(open_ms)?, close))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// 1-minute closes for a day, alternating ±`step(hour)` log returns.
+    fn day(step: impl Fn(u32) -> f64) -> Vec<(DateTime<Utc>, f64)> {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap();
+        let mut price = 100.0;
+        (0..24 * 60)
+            .map(|i| {
+                let t = start + chrono::Duration::minutes(i);
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                price *= (sign * step(t.hour())).exp();
+                (t, price)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hourly_curve() {
+        // US session (14:00-20:59) twice as volatile as the rest of the day
+        let closes = day(|h| if (14..21).contains(&h) { 0.002 } else { 0.001 });
+        let curve = VolCurve::from_closes(Asset::BTC, &closes).unwrap();
+        assert!((curve.hourly_per_minute[2] - 0.001).abs() < 1e-9);
+        assert!((curve.hourly_per_minute[15] - 0.002).abs() < 1e-9);
+        let at = |h| Utc.with_ymd_and_hms(2026, 2, 1, h, 30, 0).unwrap();
+        assert!(curve.seasonal_factor(at(15)) > 1.0);
+        assert!(curve.seasonal_factor(at(2)) < 1.0);
+        assert_eq!(curve.samples[3], 60);
+    }
+
+    #[test]
+    fn test_sparse_hours_and_gaps_fall_back() {
+        let mut closes = day(|_| 0.001);
+        // Drop most of hour 5 and open a gap: too few samples there
+        closes.retain(|(t, _)| t.hour() != 5 || t.minute() < 10);
+        let curve = VolCurve::from_closes(Asset::ETH, &closes).unwrap();
+        assert!(curve.samples[5] < MIN_HOUR_SAMPLES);
+        assert_eq!(curve.hourly_per_minute[5], curve.overall_per_minute);
+        assert!(VolCurve::from_closes(Asset::ETH, &closes[..1]).is_none());
+    }
+
+    #[test]
+    fn test_parse_and_roundtrip() {
+        let csv = "open_time,open,high,low,close,volume\n\
+                   1767571200000,100,101,99,100.5,10\n\
+                   1767571260000,100.5,101,100,100.2,8\n";
+        let closes = parse_kline_csv(csv);
+        assert_eq!(closes.len(), 2);
+        assert_eq!(closes[1].1, 100.2);
+
+        let cal = VolCalibration {
+            generated_at: Utc::now(),
+            source: "test".into(),
+            curves: vec![VolCurve::from_closes(Asset::SOL, &day(|_| 0.001)).unwrap()],
+        };
+        let path = std::env::temp_dir().join(format!("sattebaaz-vol-{}.json", uuid::Uuid::new_v4()));
+        cal.save(&path).unwrap();
+        let loaded = VolCalibration::load(&path).unwrap();
+        let (a, b) = (&loaded.curves[0], &cal.curves[0]);
+        assert_eq!((a.asset, a.samples), (b.asset, b.samples));
+        assert!((a.overall_per_minute - b.overall_per_minute).abs() < 1e-12);
+        let now = Utc::now();
+        assert_eq!(loaded.vol_per_minute(Asset::XRP, now), Asset::XRP.vol_per_minute());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_seasonality_factors() {
+        let closes = day(|h| if (14..21).contains(&h) { 0.002 } else { 0.001 });
+        let cal = VolCalibration {
+            generated_at: Utc::now(),
+            source: "test".into(),
+            curves: vec![VolCurve::from_closes(Asset::BTC, &closes).unwrap()],
+        };
+        let s = Seasonality::new(Some(cal));
+        let at = |h| Utc.with_ymd_and_hms(2026, 2, 1, h, 30, 0).unwrap();
+
+        // Busy hour: more vol into the model, smaller size
+        assert!(s.vol_per_minute(Asset::BTC, at(15)) > Asset::BTC.vol_per_minute());
+        assert!(s.size_factor(Asset::BTC, at(15)) < 1.0);
+        assert!(s.vol_per_minute(Asset::BTC, at(2)) < Asset::BTC.vol_per_minute());
+        assert_eq!(s.size_factor(Asset::BTC, at(2)), MAX_SIZE_FACTOR);
+
+        // Uncalibrated asset, or no calibration at all: untouched
+        assert_eq!(s.vol_factor(Asset::ETH, at(15)), 1.0);
+        let none = Seasonality::load(None);
+        assert_eq!(none.size_factor(Asset::BTC, at(15)), 1.0);
+        assert!(!none.is_calibrated());
+    }
+}
@@ -0,0 +1,362 @@
+use crate::models::market::{Asset, Side};
+use crate::models::order::OrderSide;
+use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
+use crate::telemetry::recorder::{Recorded, TopOfBook};
+use anyhow::{Context, Result};
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray, UInt32Array};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Lookbacks for the underlying-return features, in seconds.
+pub const RETURN_LOOKBACKS: [i64; 3] = [5, 30, 60];
+/// Horizons for the forward YES-mid labels, in seconds.
+pub const FORWARD_HORIZONS: [i64; 2] = [30, 60];
+/// Signals count toward a row if emitted this long before it.
+pub const SIGNAL_WINDOW_MS: i64 = 5_000;
+
+/// One evaluation-time snapshot of a market with everything known at that
+/// moment, labelled with what happened next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureRow {
+    pub ts_ms: i64,
+    pub market: String,
+    pub asset: Asset,
+    pub remaining_secs: f64,
+    pub reference_price: f64,
+    /// Last Binance price at or before `ts_ms`
+    pub spot: Option<f64>,
+    /// (spot - reference) / reference
+    pub spot_vs_ref: Option<f64>,
+    /// Underlying return over each of `RETURN_LOOKBACKS`
+    pub returns: [Option<f64>; 3],
+    pub fair_prob_up: Option<f64>,
+    pub yes: TopOfBook,
+    pub no: TopOfBook,
+    /// Signals on this market within `SIGNAL_WINDOW_MS` before the row
+    pub signals: u32,
+    /// ...of which buy YES or sell NO
+    pub signals_up: u32,
+    // Labels
+    pub yes_won: bool,
+    pub final_price: f64,
+    /// YES mid at each of `FORWARD_HORIZONS`, if the market was still recorded
+    pub yes_mid_fwd: [Option<f64>; 2],
+}
+
+/// A recorded strategy signal with the market as it stood when it was
+/// emitted — the candidate-order inputs `signals::ml_filter` scores — and
+/// whether it won.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntentRow {
+    /// The market's last book state at or before the signal, timed to it
+    pub state: FeatureRow,
+    pub strategy: String,
+    pub outcome_yes: bool,
+    pub is_buy: bool,
+    pub price: f64,
+    pub size: f64,
+    // Label
+    /// A buy of the winning side, or a sell of the losing one
+    pub won: bool,
+}
+
+/// Price series per asset, sorted by time.
+struct Ticks(HashMap<Asset, Vec<(i64, f64)>>);
+
+impl Ticks {
+    fn at(&self, asset: Asset, ts_ms: i64) -> Option<f64> {
+        let series = self.0.get(&asset)?;
+        let i = series.partition_point(|&(t, _)| t <= ts_ms);
+        (i > 0).then(|| series[i - 1].1)
+    }
+}
+
+/// Join a recording into feature rows, one per recorded book state of a
+/// market whose outcome is known. Rows are ordered by market, then time.
+pub fn align(records: &[Recorded], seasonality: &Seasonality) -> Vec<FeatureRow> {
+    let model = ProbabilityModel::new();
+    let mut ticks: HashMap<Asset, Vec<(i64, f64)>> = HashMap::new();
+    let mut books: HashMap<&str, Vec<&Recorded>> = HashMap::new();
+    let mut signals: HashMap<&str, Vec<(i64, bool)>> = HashMap::new();
+    let mut outcomes: HashMap<&str, (Side, f64)> = HashMap::new();
+    for record in records {
+        match record {
+            Recorded::Tick { ts_ms, asset, price } => ticks.entry(*asset).or_default().push((*ts_ms, *price)),
+            Recorded::Book { market, .. } => books.entry(market.as_str()).or_default().push(record),
+            Recorded::Signal { ts_ms, market, outcome, side, .. } => {
+                let up = (*outcome == Side::Yes) == (*side == OrderSide::Buy);
+                signals.entry(market.as_str()).or_default().push((*ts_ms, up));
+            }
+            Recorded::Outcome { market, winner, final_price, .. } => {
+                outcomes.insert(market.as_str(), (*winner, *final_price));
+            }
+        }
+    }
+    for series in ticks.values_mut() {
+        series.sort_by_key(|&(t, _)| t);
+    }
+    for series in signals.values_mut() {
+        series.sort_by_key(|&(t, _)| t);
+    }
+    let ticks = Ticks(ticks);
+
+    let mut markets: Vec<&str> = books.keys().copied().filter(|m| outcomes.contains_key(m)).collect();
+    markets.sort_unstable();
+    let mut rows = Vec::new();
+    for market in markets {
+        let (winner, final_price) = outcomes[market];
+        let mut states: Vec<(i64, &Recorded)> = books[market]
+            .iter()
+            .filter_map(|r| match r {
+                Recorded::Book { ts_ms, .. } => Some((*ts_ms, *r)),
+                _ => None,
+            })
+            .collect();
+        states.sort_by_key(|&(t, _)| t);
+        let mids: Vec<(i64, Option<f64>)> = states
+            .iter()
+            .map(|&(t, r)| match r {
+                Recorded::Book { yes, .. } => (t, yes.mid()),
+                _ => unreachable!(),
+            })
+            .collect();
+        let market_signals = signals.get(market).map(Vec::as_slice).unwrap_or_default();
+
+        for &(ts_ms, record) in &states {
+            let Recorded::Book { asset, reference_price, remaining_secs, yes, no, .. } = record else {
+                continue;
+            };
+            let spot = ticks.at(*asset, ts_ms);
+            let returns = RETURN_LOOKBACKS.map(|secs| {
+                let then = ticks.at(*asset, ts_ms - secs * 1000)?;
+                Some(spot? / then - 1.0)
+            });
+            let fair_prob_up = spot.filter(|_| *reference_price > 0.0).map(|spot| {
+                let at = chrono::DateTime::from_timestamp_millis(ts_ms).unwrap_or_default();
+                let vol = seasonality.vol_per_minute(*asset, at);
+                model.fair_prob_up(spot, *reference_price, remaining_secs / 60.0, vol, 0.0)
+            });
+            let recent = &market_signals[market_signals.partition_point(|&(t, _)| t < ts_ms - SIGNAL_WINDOW_MS)
+                ..market_signals.partition_point(|&(t, _)| t <= ts_ms)];
+            // First recorded state at or after the horizon
+            let yes_mid_fwd = FORWARD_HORIZONS.map(|secs| {
+                let i = mids.partition_point(|&(t, _)| t < ts_ms + secs * 1000);
+                mids.get(i).and_then(|&(_, mid)| mid)
+            });
+
+            rows.push(FeatureRow {
+                ts_ms,
+                market: market.to_string(),
+                asset: *asset,
+                remaining_secs: *remaining_secs,
+                reference_price: *reference_price,
+                spot,
+                spot_vs_ref: spot.filter(|_| *reference_price > 0.0).map(|s| s / reference_price - 1.0),
+                returns,
+                fair_prob_up,
+                yes: *yes,
+                no: *no,
+                signals: recent.len() as u32,
+                signals_up: recent.iter().filter(|&&(_, up)| up).count() as u32,
+                yes_won: winner == Side::Yes,
+                final_price,
+                yes_mid_fwd,
+            });
+        }
+    }
+    rows
+}
+
+/// One row per recorded signal on a resolved market that had a book state
+/// recorded at or before it. Rows are ordered by market, then time.
+pub fn align_intents(records: &[Recorded], seasonality: &Seasonality) -> Vec<IntentRow> {
+    let rows = align(records, seasonality);
+    let mut intents = Vec::new();
+    for record in records {
+        let Recorded::Signal { ts_ms, market, strategy, outcome, side, price, size } = record else {
+            continue;
+        };
+        // `rows` is sorted by market, then time
+        let end = rows.partition_point(|r| (r.market.as_str(), r.ts_ms) <= (market.as_str(), *ts_ms));
+        let Some(state) = end.checked_sub(1).map(|i| &rows[i]).filter(|r| r.market == *market) else {
+            continue;
+        };
+        let mut state = state.clone();
+        state.remaining_secs -= (ts_ms - state.ts_ms) as f64 / 1000.0;
+        state.ts_ms = *ts_ms;
+        let outcome_yes = *outcome == Side::Yes;
+        let is_buy = *side == OrderSide::Buy;
+        intents.push(IntentRow {
+            won: (outcome_yes == state.yes_won) == is_buy,
+            state,
+            strategy: strategy.clone(),
+            outcome_yes,
+            is_buy,
+            price: *price,
+            size: *size,
+        });
+    }
+    intents.sort_by(|a, b| (&a.state.market, a.state.ts_ms).cmp(&(&b.state.market, b.state.ts_ms)));
+    intents
+}
+
+fn f64s<T>(rows: &[T], f: impl Fn(&T) -> Option<f64>) -> ArrayRef {
+    Arc::new(rows.iter().map(f).collect::<Float64Array>())
+}
+
+/// Columnar form of `rows`, one column per feature/label.
+pub fn to_record_batch(rows: &[FeatureRow]) -> Result<RecordBatch> {
+    Ok(RecordBatch::try_from_iter(columns(rows))?)
+}
+
+/// Columnar form of `intents`: the snapshot columns of `to_record_batch`,
+/// then the candidate order in `signals::ml_filter::INPUTS` naming, then `won`.
+pub fn intents_to_record_batch(intents: &[IntentRow]) -> Result<RecordBatch> {
+    let states: Vec<FeatureRow> = intents.iter().map(|i| i.state.clone()).collect();
+    let mut columns = columns(&states);
+    columns.push(("strategy".into(), Arc::new(intents.iter().map(|i| Some(i.strategy.as_str())).collect::<StringArray>())));
+    columns.push(("outcome_yes".into(), Arc::new(intents.iter().map(|i| Some(i.outcome_yes)).collect::<BooleanArray>())));
+    columns.push(("is_buy".into(), Arc::new(intents.iter().map(|i| Some(i.is_buy)).collect::<BooleanArray>())));
+    columns.push(("price".into(), f64s(intents, |i| Some(i.price))));
+    columns.push(("size".into(), f64s(intents, |i| Some(i.size))));
+    columns.push(("won".into(), Arc::new(intents.iter().map(|i| Some(i.won)).collect::<BooleanArray>())));
+    Ok(RecordBatch::try_from_iter(columns)?)
+}
+
+fn columns(rows: &[FeatureRow]) -> Vec<(String, ArrayRef)> {
+    let mut columns: Vec<(String, ArrayRef)> = vec![
+        ("ts_ms".into(), Arc::new(rows.iter().map(|r| r.ts_ms).collect::<Int64Array>())),
+        ("market".into(), Arc::new(rows.iter().map(|r| Some(r.market.as_str())).collect::<StringArray>())),
+        ("asset".into(), Arc::new(rows.iter().map(|r| Some(r.asset.slug_prefix())).collect::<StringArray>())),
+        ("remaining_secs".into(), f64s(rows, |r| Some(r.remaining_secs))),
+        ("reference_price".into(), f64s(rows, |r| Some(r.reference_price))),
+        ("spot".into(), f64s(rows, |r| r.spot)),
+        ("spot_vs_ref".into(), f64s(rows, |r| r.spot_vs_ref)),
+    ];
+    for (i, secs) in RETURN_LOOKBACKS.iter().enumerate() {
+        columns.push((format!("ret_{secs}s"), f64s(rows, |r| r.returns[i])));
+    }
+    columns.push(("fair_prob_up".into(), f64s(rows, |r| r.fair_prob_up)));
+    for (name, book) in [("yes", (|r: &FeatureRow| r.yes) as fn(&FeatureRow) -> TopOfBook), ("no", |r| r.no)] {
+        columns.push((format!("{name}_bid"), f64s(rows, |r| book(r).bid)));
+        columns.push((format!("{name}_ask"), f64s(rows, |r| book(r).ask)));
+        columns.push((format!("{name}_bid_size"), f64s(rows, |r| Some(book(r).bid_size))));
+        columns.push((format!("{name}_ask_size"), f64s(rows, |r| Some(book(r).ask_size))));
+        columns.push((format!("{name}_mid"), f64s(rows, |r| book(r).mid())));
+        columns.push((format!("{name}_spread"), f64s(rows, |r| book(r).spread())));
+    }
+    columns.push(("signals".into(), Arc::new(rows.iter().map(|r| r.signals).collect::<UInt32Array>())));
+    columns.push(("signals_up".into(), Arc::new(rows.iter().map(|r| r.signals_up).collect::<UInt32Array>())));
+    columns.push(("yes_won".into(), Arc::new(rows.iter().map(|r| Some(r.yes_won)).collect::<BooleanArray>())));
+    columns.push(("final_price".into(), f64s(rows, |r| Some(r.final_price))));
+    for (i, secs) in FORWARD_HORIZONS.iter().enumerate() {
+        columns.push((format!("yes_mid_fwd_{secs}s"), f64s(rows, |r| r.yes_mid_fwd[i])));
+    }
+    columns
+}
+
+/// Write `rows` as a single-row-group Parquet file.
+pub fn write_parquet(rows: &[FeatureRow], path: impl AsRef<Path>) -> Result<()> {
+    write_batch(&to_record_batch(rows)?, path.as_ref())
+}
+
+/// Write `intents` as a single-row-group Parquet file.
+pub fn write_intents_parquet(intents: &[IntentRow], path: impl AsRef<Path>) -> Result<()> {
+    write_batch(&intents_to_record_batch(intents)?, path.as_ref())
+}
+
+fn write_batch(batch: &RecordBatch, path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    let props = parquet::file::properties::WriterProperties::builder()
+        .set_compression(parquet::basic::Compression::SNAPPY)
+        .build();
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), Some(props))?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(bid: f64, ask: f64) -> TopOfBook {
+        TopOfBook { bid: Some(bid), bid_size: 10.0, ask: Some(ask), ask_size: 10.0 }
+    }
+
+    fn book(ts_ms: i64, market: &str, yes_mid: f64) -> Recorded {
+        Recorded::Book {
+            ts_ms,
+            market: market.into(),
+            asset: Asset::BTC,
+            reference_price: 100_000.0,
+            remaining_secs: 300.0 - ts_ms as f64 / 1000.0,
+            yes: top(yes_mid - 0.01, yes_mid + 0.01),
+            no: top(0.99 - yes_mid, 1.01 - yes_mid),
+        }
+    }
+
+    #[test]
+    fn test_align_joins_ticks_signals_and_outcomes() {
+        let mut records: Vec<Recorded> =
+            (0..=120).map(|s| Recorded::Tick { ts_ms: s * 1000, asset: Asset::BTC, price: 100_000.0 + s as f64 }).collect();
+        records.extend([0, 30_000, 60_000, 90_000].map(|t| book(t, "m", 0.50 + t as f64 / 1e6)));
+        records.push(book(60_000, "unresolved", 0.5));
+        records.push(Recorded::Signal {
+            ts_ms: 58_000,
+            market: "m".into(),
+            strategy: "lag_exploit".into(),
+            outcome: Side::No,
+            side: OrderSide::Sell,
+            price: 0.45,
+            size: 5.0,
+        });
+        records.push(Recorded::Outcome {
+            ts_ms: 300_000,
+            market: "m".into(),
+            asset: Asset::BTC,
+            reference_price: 100_000.0,
+            final_price: 100_120.0,
+            winner: Side::Yes,
+        });
+
+        let rows = align(&records, &Seasonality::default());
+        assert_eq!(rows.len(), 4, "only the resolved market is labelled");
+        let row = &rows[2];
+        assert_eq!((row.ts_ms, row.spot), (60_000, Some(100_060.0)));
+        assert!((row.returns[1].unwrap() - (100_060.0 / 100_030.0 - 1.0)).abs() < 1e-12);
+        assert!(row.fair_prob_up.unwrap() > 0.5);
+        assert_eq!((row.signals, row.signals_up), (1, 1));
+        assert_eq!(rows[3].signals, 0);
+        assert!(row.yes_won);
+        assert!((row.yes_mid_fwd[0].unwrap() - 0.59).abs() < 1e-9);
+        assert_eq!(row.yes_mid_fwd[1], None, "nothing recorded 60s later");
+        // Before the first tick there is no spot
+        assert_eq!(rows[0].returns[0], None);
+
+        let path = std::env::temp_dir().join(format!("features-{}.parquet", uuid::Uuid::new_v4()));
+        write_parquet(&rows, &path).unwrap();
+        let reader = parquet::file::reader::SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        use parquet::file::reader::FileReader;
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 4);
+        std::fs::remove_file(path).ok();
+
+        // The signal, against the 30s state timed to it: selling NO on a YES win won
+        let intents = align_intents(&records, &Seasonality::default());
+        assert_eq!(intents.len(), 1);
+        let intent = &intents[0];
+        assert_eq!((intent.state.ts_ms, intent.state.remaining_secs), (58_000, 242.0));
+        assert_eq!(intent.state.yes, rows[1].yes);
+        assert_eq!((intent.outcome_yes, intent.is_buy, intent.price, intent.size), (false, false, 0.45, 5.0));
+        assert!(intent.won);
+
+        // Every model input is exported
+        let batch = intents_to_record_batch(&intents).unwrap();
+        for input in crate::signals::ml_filter::INPUTS {
+            assert!(batch.schema().field_with_name(input).is_ok(), "{input} not exported");
+        }
+        assert!(batch.schema().field_with_name("won").is_ok());
+    }
+}
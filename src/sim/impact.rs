@@ -0,0 +1,202 @@
+use crate::models::market::{from_ticks, Ladder, OrderBook, Ticks, TICKS_PER_DOLLAR};
+use crate::models::order::OrderSide;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Depletion below this many shares is forgotten.
+const EPS: f64 = 1e-6;
+
+/// Result of walking the book for one simulated taker order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimExecution {
+    pub shares: f64,
+    /// USDC paid (buy) or received (sell)
+    pub notional: f64,
+    /// Worst level reached
+    pub worst_price: f64,
+}
+
+impl SimExecution {
+    pub fn avg_price(&self) -> f64 {
+        self.notional / self.shares
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Depletion {
+    shares: f64,
+    at_ms: i64,
+}
+
+impl Depletion {
+    fn outstanding(&self, half_life_secs: f64, now_ms: i64) -> f64 {
+        if half_life_secs <= 0.0 {
+            return 0.0;
+        }
+        let age_secs = (now_ms - self.at_ms).max(0) as f64 / 1000.0;
+        self.shares * 0.5f64.powf(age_secs / half_life_secs)
+    }
+}
+
+/// Our own market impact on simulated fills.
+///
+/// Displayed books are snapshots the exchange keeps re-sending; without this
+/// a backtest or paper run can lift the same 20 shares at 0.52 every tick.
+/// Each level we take from is remembered as depleted, subtracted from later
+/// snapshots of the same book, and refills with a `half_life_secs` exponential
+/// decay as other participants re-quote. A half-life of 0 walks the book for
+/// each order but leaves no lasting impact.
+pub struct ImpactModel {
+    half_life_secs: f64,
+    /// (token, side of the book, price) → shares we took
+    depleted: HashMap<(String, OrderSide, Ticks), Depletion>,
+}
+
+impl ImpactModel {
+    pub fn new(half_life_secs: f64) -> Self {
+        Self { half_life_secs, depleted: HashMap::new() }
+    }
+
+    /// Shares still missing from a level at `now_ms`.
+    fn outstanding(&self, d: &Depletion, now_ms: i64) -> f64 {
+        d.outstanding(self.half_life_secs, now_ms)
+    }
+
+    fn prune(&mut self, now_ms: i64) {
+        let half_life = self.half_life_secs;
+        self.depleted.retain(|_, d| d.outstanding(half_life, now_ms) > EPS);
+    }
+
+    /// `book` as it stands after our recent fills: depleted levels shrunk,
+    /// emptied ones dropped.
+    pub fn apply(&self, book: &OrderBook, now_ms: i64) -> OrderBook {
+        let mut out = book.clone();
+        out.asks = self.deplete(&book.token_id, OrderSide::Buy, &book.asks, now_ms);
+        out.bids = self.deplete(&book.token_id, OrderSide::Sell, &book.bids, now_ms);
+        out
+    }
+
+    /// `taker` is the side of the order that consumes the ladder: buys take asks.
+    fn deplete(&self, token_id: &str, taker: OrderSide, ladder: &Ladder, now_ms: i64) -> Ladder {
+        let mut out = Ladder::default();
+        for &(tick, size) in ladder.ticks() {
+            let missing = self
+                .depleted
+                .get(&(token_id.to_string(), taker, tick))
+                .map_or(0.0, |d| self.outstanding(d, now_ms));
+            let left = size.to_f64().unwrap_or(0.0) - missing;
+            if left > EPS {
+                out.insert(from_ticks(tick), Decimal::from_f64_retain(left).unwrap_or(Decimal::ZERO).round_dp(6));
+            }
+        }
+        out
+    }
+
+    /// Shares a `side` taker order could get from `book` right now.
+    pub fn depth(&self, book: &OrderBook, side: OrderSide, now_ms: i64) -> f64 {
+        let live = self.apply(book, now_ms);
+        let ladder = if side == OrderSide::Buy { &live.asks } else { &live.bids };
+        ladder.iter().map(|(_, size)| size.to_f64().unwrap_or(0.0)).sum()
+    }
+
+    /// Fill up to `shares` against `book`, best level first, at prices no
+    /// worse than `limit` (None = any), and record the levels taken. None if
+    /// nothing is available within the limit.
+    pub fn execute(
+        &mut self,
+        book: &OrderBook,
+        side: OrderSide,
+        shares: f64,
+        limit: Option<f64>,
+        now_ms: i64,
+    ) -> Option<SimExecution> {
+        self.prune(now_ms);
+        let live = self.apply(book, now_ms);
+        let levels: Vec<(Ticks, Decimal)> = match side {
+            OrderSide::Buy => live.asks.ticks().to_vec(),
+            OrderSide::Sell => live.bids.ticks().iter().rev().copied().collect(),
+        };
+
+        let mut exec = SimExecution { shares: 0.0, notional: 0.0, worst_price: 0.0 };
+        for (tick, size) in levels {
+            let price = tick as f64 / TICKS_PER_DOLLAR as f64;
+            let within = limit.is_none_or(|l| match side {
+                OrderSide::Buy => price <= l + 1e-9,
+                OrderSide::Sell => price >= l - 1e-9,
+            });
+            if exec.shares >= shares - EPS || !within {
+                break;
+            }
+            let take = (shares - exec.shares).min(size.to_f64().unwrap_or(0.0));
+            exec.shares += take;
+            exec.notional += take * price;
+            exec.worst_price = price;
+
+            let key = (book.token_id.clone(), side, tick);
+            let already = self.depleted.get(&key).map_or(0.0, |d| self.outstanding(d, now_ms));
+            self.depleted.insert(key, Depletion { shares: already + take, at_ms: now_ms });
+        }
+        (exec.shares > EPS).then_some(exec)
+    }
+
+    /// Spend up to `usdc` buying from `book`.
+    pub fn buy_usdc(&mut self, book: &OrderBook, usdc: f64, now_ms: i64) -> Option<SimExecution> {
+        let live = self.apply(book, now_ms);
+        // Shares affordable walking the live asks, then fill exactly those
+        let mut shares = 0.0;
+        let mut left = usdc;
+        for &(tick, size) in live.asks.ticks() {
+            let price = tick as f64 / TICKS_PER_DOLLAR as f64;
+            let take = (left / price).min(size.to_f64().unwrap_or(0.0));
+            shares += take;
+            left -= take * price;
+            if left <= EPS {
+                break;
+            }
+        }
+        self.execute(book, OrderSide::Buy, shares, None, now_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_impact_depletes_and_decays() {
+        let mut book = OrderBook::new("yes".into());
+        book.asks.insert(dec!(0.52), dec!(20));
+        book.asks.insert(dec!(0.54), dec!(50));
+        book.bids.insert(dec!(0.50), dec!(30));
+        let mut impact = ImpactModel::new(10.0);
+
+        // First buy clears the top level and walks into the next
+        let first = impact.execute(&book, OrderSide::Buy, 30.0, None, 0).unwrap();
+        assert_eq!((first.shares, first.worst_price), (30.0, 0.54));
+        assert!((first.avg_price() - (20.0 * 0.52 + 10.0 * 0.54) / 30.0).abs() < 1e-9);
+
+        // The same snapshot arriving again doesn't hand back the 0.52s
+        let live = impact.apply(&book, 0);
+        assert_eq!(live.best_ask(), Some((dec!(0.54), dec!(40))));
+        assert!(impact.execute(&book, OrderSide::Buy, 10.0, Some(0.52), 0).is_none());
+        assert_eq!(live.best_bid(), Some((dec!(0.50), dec!(30))), "the other side is untouched");
+
+        // Half refilled after one half-life, all of it eventually
+        let later = impact.apply(&book, 10_000);
+        assert_eq!(later.best_ask(), Some((dec!(0.52), dec!(10))));
+        assert_eq!(impact.apply(&book, 600_000).asks, book.asks);
+
+        // Spending USDC buys what the live book can give for it
+        let spent = impact.buy_usdc(&book, 5.4, 0).unwrap();
+        assert!((spent.notional - 5.4).abs() < 1e-9 && spent.worst_price == 0.54);
+
+        assert!((impact.depth(&book, OrderSide::Buy, 0) - 30.0).abs() < 1e-9);
+
+        // No lasting impact at half-life 0
+        let mut walk_only = ImpactModel::new(0.0);
+        walk_only.execute(&book, OrderSide::Sell, 30.0, None, 0).unwrap();
+        assert_eq!(walk_only.apply(&book, 0).bids, book.bids);
+    }
+}
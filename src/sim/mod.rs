@@ -0,0 +1,7 @@
+pub mod rng;
+pub mod synthetic;
+pub mod scenarios;
+pub mod sensitivity;
+pub mod features;
+pub mod impact;
+pub mod regression;
@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Headline results of one backtest run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BacktestMetrics {
+    pub total_pnl: f64,
+    /// Mean over standard deviation of per-cycle P&L
+    pub sharpe: f64,
+    /// Deepest fall from a running peak, as a fraction of that peak
+    pub max_drawdown: f64,
+    /// Fills per strategy family (`strategy_bucket`)
+    pub fills: BTreeMap<String, usize>,
+}
+
+impl BacktestMetrics {
+    /// Metrics of a run that started with `starting_capital` and made
+    /// `cycle_pnls`, one per market cycle.
+    pub fn from_cycles(starting_capital: f64, cycle_pnls: &[f64], fills: BTreeMap<String, usize>) -> Self {
+        let n = cycle_pnls.len().max(1) as f64;
+        let mean = cycle_pnls.iter().sum::<f64>() / n;
+        let std_dev = (cycle_pnls.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+        let mut peak = starting_capital;
+        let mut running = starting_capital;
+        let mut max_drawdown = 0.0f64;
+        for &pnl in cycle_pnls {
+            running += pnl;
+            peak = peak.max(running);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - running) / peak);
+            }
+        }
+
+        Self {
+            total_pnl: cycle_pnls.iter().sum(),
+            sharpe: if std_dev > 0.0 { mean / std_dev } else { 0.0 },
+            max_drawdown,
+            fills,
+        }
+    }
+}
+
+/// How far a run may drift from its baseline before the gate fails.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    /// Share of the baseline's total P&L a run may lose; compounding runs
+    /// swing in dollars with their size
+    pub pnl_pct: f64,
+    /// Dollars of P&L always allowed, for baselines near zero
+    pub pnl_min: f64,
+    pub sharpe: f64,
+    /// Extra drawdown allowed, as a fraction of peak
+    pub max_drawdown: f64,
+    /// Relative drift allowed in each family's fill count, either way
+    pub fills_pct: f64,
+    /// Absolute drift always allowed, so families with few fills aren't brittle
+    pub fills_min: usize,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self { pnl_pct: 0.10, pnl_min: 0.25, sharpe: 0.05, max_drawdown: 0.05, fills_pct: 0.10, fills_min: 3 }
+    }
+}
+
+/// Stored baseline metrics per named backtest case.
+///
+/// P&L, Sharpe and drawdown only fail when they get worse — an improvement
+/// is reported so the baseline can be re-recorded. Fill counts fail on
+/// drift either way: a strategy suddenly trading twice as much is as
+/// unexpected as one that stopped.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BacktestBaseline {
+    #[serde(default)]
+    pub tolerance: Tolerance,
+    pub cases: BTreeMap<String, BacktestMetrics>,
+}
+
+/// What a run looked like against its baseline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comparison {
+    /// Degradations beyond tolerance: the gate fails on any
+    pub regressions: Vec<String>,
+    /// Gains beyond tolerance: worth re-recording the baseline
+    pub improvements: Vec<String>,
+}
+
+impl BacktestBaseline {
+    /// Baseline at `path`; empty if there's none yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n").with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Check `run` against the stored metrics for `case`. A case with no
+    /// baseline is itself a regression: record one first.
+    pub fn compare(&self, case: &str, run: &BacktestMetrics) -> Comparison {
+        let mut out = Comparison::default();
+        let Some(base) = self.cases.get(case) else {
+            out.regressions.push(format!("{case}: no baseline recorded"));
+            return out;
+        };
+        let tol = &self.tolerance;
+
+        // (name, baseline, run, tolerance, higher is better)
+        let pnl_tol = (base.total_pnl.abs() * tol.pnl_pct).max(tol.pnl_min);
+        let scalars = [
+            ("total_pnl", base.total_pnl, run.total_pnl, pnl_tol, true),
+            ("sharpe", base.sharpe, run.sharpe, tol.sharpe, true),
+            ("max_drawdown", base.max_drawdown, run.max_drawdown, tol.max_drawdown, false),
+        ];
+        for (name, b, r, tol, higher_is_better) in scalars {
+            let gain = if higher_is_better { r - b } else { b - r };
+            if gain < -tol {
+                out.regressions.push(format!("{case}: {name} {r:.4} vs baseline {b:.4} (tolerance {tol:.4})"));
+            } else if gain > tol {
+                out.improvements.push(format!("{case}: {name} {r:.4} vs baseline {b:.4}"));
+            }
+        }
+
+        let families: std::collections::BTreeSet<&String> = base.fills.keys().chain(run.fills.keys()).collect();
+        for family in families {
+            let b = base.fills.get(family).copied().unwrap_or(0);
+            let r = run.fills.get(family).copied().unwrap_or(0);
+            let band = ((b as f64 * tol.fills_pct).ceil() as usize).max(tol.fills_min);
+            if r.abs_diff(b) > band {
+                out.regressions.push(format!("{case}: {family} fills {r} vs baseline {b} (±{band})"));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fills(pairs: &[(&str, usize)]) -> BTreeMap<String, usize> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_metrics_and_tolerance_bands() {
+        // 10 → 12 → 9 → 10: a 25% drawdown from the 12 peak
+        let m = BacktestMetrics::from_cycles(10.0, &[2.0, -3.0, 1.0], fills(&[("arb", 4)]));
+        assert!((m.total_pnl - 0.0).abs() < 1e-12);
+        assert!((m.max_drawdown - 0.25).abs() < 1e-12);
+        assert_eq!(m.sharpe, 0.0, "zero mean");
+
+        let baseline = BacktestBaseline {
+            tolerance: Tolerance::default(),
+            cases: BTreeMap::from([(
+                "seed42".to_string(),
+                BacktestMetrics { total_pnl: 1.0, sharpe: 0.20, max_drawdown: 0.10, fills: fills(&[("arb", 50), ("lag", 2)]) },
+            )]),
+        };
+        let within = BacktestMetrics { total_pnl: 0.9, sharpe: 0.17, max_drawdown: 0.12, fills: fills(&[("arb", 54), ("lag", 4)]) };
+        assert_eq!(baseline.compare("seed42", &within), Comparison::default());
+
+        // Worse beyond tolerance fails; better is only reported; fills fail both ways
+        let drifted = BacktestMetrics {
+            total_pnl: 2.0,
+            sharpe: 0.10,
+            max_drawdown: 0.20,
+            fills: fills(&[("arb", 40), ("lag", 2), ("mm", 9)]),
+        };
+        let c = baseline.compare("seed42", &drifted);
+        assert_eq!(c.regressions.len(), 4, "{:?}", c.regressions);
+        assert!(c.regressions.iter().any(|r| r.contains("sharpe")));
+        assert!(c.regressions.iter().any(|r| r.contains("drawdown")));
+        assert!(c.regressions.iter().any(|r| r.contains("arb fills 40")));
+        assert!(c.regressions.iter().any(|r| r.contains("mm fills 9")));
+        assert_eq!(c.improvements.len(), 1);
+        assert_eq!(baseline.compare("seed7", &within).regressions.len(), 1, "unrecorded case");
+
+        // Round-trips through disk
+        let path = std::env::temp_dir().join(format!("baseline-{}.json", uuid::Uuid::new_v4()));
+        baseline.save(&path).unwrap();
+        assert_eq!(BacktestBaseline::load(&path).unwrap(), baseline);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,135 @@
+use crate::models::market::{Market, OrderBook};
+use crate::sim::rng::SimRng;
+use crate::sim::synthetic::SyntheticFeed;
+use std::ops::Range;
+
+/// Canned stress paths for backtests, layered on the seeded synthetic feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scenario {
+    /// Underlying drops 3% in three ticks, then walks on from the low
+    FlashCrash,
+    /// Steady 2% slide, then the same climb back
+    VReversal,
+    /// Small persistent drift all window; the book keeps lagging it
+    SlowGrind,
+    /// Spreads widen to 6x and depth drains to 5% over a third of the window,
+    /// and stay that way
+    LiquidityVacuum,
+    /// Binance and book updates both stop mid-window for a third of it
+    FeedOutage,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 5] = [
+        Self::FlashCrash,
+        Self::VReversal,
+        Self::SlowGrind,
+        Self::LiquidityVacuum,
+        Self::FeedOutage,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FlashCrash => "flash_crash",
+            Self::VReversal => "v_reversal",
+            Self::SlowGrind => "slow_grind",
+            Self::LiquidityVacuum => "liquidity_vacuum",
+            Self::FeedOutage => "feed_outage",
+        }
+    }
+
+    /// Ticks of a `ticks`-long window where the stress is applied.
+    pub fn stress_window(self, ticks: usize) -> Range<usize> {
+        let start = ticks / 3;
+        match self {
+            Self::FlashCrash => start..start + 3,
+            Self::VReversal | Self::LiquidityVacuum | Self::FeedOutage => start..start + ticks / 3,
+            Self::SlowGrind => 0..ticks,
+        }
+    }
+}
+
+/// One tick of a scenario.
+pub struct ScenarioTick {
+    pub tick: usize,
+    /// Latest Binance price; None while the feed is down
+    pub price: Option<f64>,
+    /// Fractional move since the previous tick, as `BinanceFeed::get_1s_move_pct` reports
+    pub move_1s: f64,
+    pub yes_book: OrderBook,
+    pub no_book: OrderBook,
+    pub stressed: bool,
+}
+
+/// Drives a `SyntheticFeed` through one scenario, one-second ticks.
+///
+/// During a feed outage the last books are replayed with their timestamps
+/// aged by the outage length, the way a dead WS leaves them in the cache.
+pub struct ScenarioFeed {
+    scenario: Scenario,
+    feed: SyntheticFeed,
+    ticks: usize,
+    tick: usize,
+    last_books: Option<(OrderBook, OrderBook)>,
+}
+
+impl ScenarioFeed {
+    pub fn new(scenario: Scenario, rng: SimRng, start_price: f64, ticks: usize) -> Self {
+        Self {
+            scenario,
+            feed: SyntheticFeed::new(rng, start_price, 0.0001, 3),
+            ticks,
+            tick: 0,
+            last_books: None,
+        }
+    }
+
+    pub fn scenario(&self) -> Scenario {
+        self.scenario
+    }
+
+    pub fn stress_window(&self) -> Range<usize> {
+        self.scenario.stress_window(self.ticks)
+    }
+
+    /// Advance one tick; None once the window is over.
+    pub fn step(&mut self, market: &Market, remaining_secs: f64) -> Option<ScenarioTick> {
+        if self.tick >= self.ticks {
+            return None;
+        }
+        let tick = self.tick;
+        self.tick += 1;
+        let window = self.stress_window();
+        let stressed = window.contains(&tick);
+        let into = tick.saturating_sub(window.start) as f64;
+        let len = window.len() as f64;
+
+        if self.scenario == Scenario::FeedOutage && stressed {
+            if let Some((yes, no)) = &self.last_books {
+                let age = chrono::Duration::seconds(into as i64 + 1);
+                let (mut yes_book, mut no_book) = (yes.clone(), no.clone());
+                yes_book.timestamp = yes.timestamp - age;
+                no_book.timestamp = no.timestamp - age;
+                return Some(ScenarioTick { tick, price: None, move_1s: 0.0, yes_book, no_book, stressed });
+            }
+        }
+
+        let drift = match self.scenario {
+            Scenario::FlashCrash if stressed => -0.01,
+            Scenario::VReversal if stressed && into < len / 2.0 => -0.001,
+            Scenario::VReversal if stressed => 0.001,
+            Scenario::SlowGrind => 0.0001,
+            _ => 0.0,
+        };
+        if self.scenario == Scenario::LiquidityVacuum {
+            let drained = if stressed { (into + 1.0) / len } else if tick >= window.end { 1.0 } else { 0.0 };
+            self.feed.set_liquidity(1.0 + 5.0 * drained, 1.0 - 0.95 * drained);
+        }
+
+        let prev = self.feed.price();
+        let price = self.feed.step_drift(drift);
+        let (yes_book, no_book) = self.feed.books(market, remaining_secs);
+        self.last_books = Some((yes_book.clone(), no_book.clone()));
+        Some(ScenarioTick { tick, price: Some(price), move_1s: (price - prev) / prev, yes_book, no_book, stressed })
+    }
+}
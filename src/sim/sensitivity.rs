@@ -0,0 +1,213 @@
+use crate::models::market::Side;
+use crate::models::order::OrderSide;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Assumed execution costs for replaying backtest fills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostModel {
+    /// Taker fee rate; per share the CLOB charges p×(1-p)×bps/10000
+    pub fee_bps: f64,
+    /// Adverse price move on taker fills, in probability units (0.01 = one tick)
+    pub slippage: f64,
+}
+
+impl CostModel {
+    /// Fee per share. Makers (post-only) pay none.
+    pub fn fee_per_share(&self, price: f64, post_only: bool) -> f64 {
+        if post_only {
+            return 0.0;
+        }
+        price * (1.0 - price) * self.fee_bps / 10_000.0
+    }
+
+    /// Fill price after slippage, kept inside the 1¢–99¢ tradable range.
+    /// Makers fill at their quote.
+    pub fn fill_price(&self, price: f64, side: OrderSide, post_only: bool) -> f64 {
+        if post_only {
+            return price;
+        }
+        match side {
+            OrderSide::Buy => (price + self.slippage).min(0.99),
+            OrderSide::Sell => (price - self.slippage).max(0.01),
+        }
+    }
+}
+
+/// A fill recorded once from a backtest run and re-priced under each cost
+/// model, so every grid point sees the same decisions.
+#[derive(Debug, Clone)]
+pub struct SimFill {
+    pub strategy: String,
+    /// Index into the outcomes the fills are settled against
+    pub window: usize,
+    pub market_side: Side,
+    pub order_side: OrderSide,
+    pub price: f64,
+    pub size: f64,
+    pub post_only: bool,
+}
+
+impl SimFill {
+    /// Net P&L once the window resolves to `winner`.
+    pub fn pnl(&self, winner: Side, cost: &CostModel) -> f64 {
+        let payout = if self.market_side == winner { 1.0 } else { 0.0 };
+        let fill = cost.fill_price(self.price, self.order_side, self.post_only);
+        let fee = cost.fee_per_share(fill, self.post_only);
+        let edge = match self.order_side {
+            OrderSide::Buy => payout - fill,
+            OrderSide::Sell => fill - payout,
+        };
+        self.size * (edge - fee)
+    }
+}
+
+/// Net P&L per strategy under one cost model. `outcomes[w]` is the winning
+/// side of window `w`.
+pub fn strategy_pnl(fills: &[SimFill], outcomes: &[Side], cost: &CostModel) -> BTreeMap<String, f64> {
+    let mut pnl = BTreeMap::new();
+    for f in fills {
+        *pnl.entry(f.strategy.clone()).or_insert(0.0) += f.pnl(outcomes[f.window], cost);
+    }
+    pnl
+}
+
+/// Strategy P&L over a fee × slippage grid.
+pub struct SensitivityReport {
+    pub fee_bps: Vec<f64>,
+    pub slippage: Vec<f64>,
+    /// `pnl[s][f]` for slippage `s`, fee `f`
+    pnl: Vec<Vec<BTreeMap<String, f64>>>,
+}
+
+impl SensitivityReport {
+    /// Replay `fills` at every grid point. Grids should be ascending.
+    pub fn sweep(fills: &[SimFill], outcomes: &[Side], fee_bps: &[f64], slippage: &[f64]) -> Self {
+        let pnl = slippage
+            .iter()
+            .map(|&slippage| {
+                fee_bps
+                    .iter()
+                    .map(|&fee_bps| strategy_pnl(fills, outcomes, &CostModel { fee_bps, slippage }))
+                    .collect()
+            })
+            .collect();
+        Self { fee_bps: fee_bps.to_vec(), slippage: slippage.to_vec(), pnl }
+    }
+
+    pub fn strategies(&self) -> Vec<&str> {
+        self.pnl
+            .first()
+            .and_then(|row| row.first())
+            .map(|cell| cell.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn pnl(&self, strategy: &str, slip_idx: usize, fee_idx: usize) -> f64 {
+        self.pnl[slip_idx][fee_idx].get(strategy).copied().unwrap_or(0.0)
+    }
+
+    /// Fee rate at which `strategy` stops making money at the given
+    /// slippage, interpolated between grid points. `Some(0.0)` when it loses
+    /// even without fees, `None` when it's still profitable at the top of
+    /// the grid.
+    pub fn breakeven_fee_bps(&self, strategy: &str, slip_idx: usize) -> Option<f64> {
+        let mut prev: Option<(f64, f64)> = None;
+        for (i, &fee) in self.fee_bps.iter().enumerate() {
+            let pnl = self.pnl(strategy, slip_idx, i);
+            if pnl <= 0.0 {
+                return Some(match prev {
+                    Some((prev_fee, prev_pnl)) => prev_fee + (fee - prev_fee) * prev_pnl / (prev_pnl - pnl),
+                    None => fee,
+                });
+            }
+            prev = Some((fee, pnl));
+        }
+        None
+    }
+}
+
+impl fmt::Display for SensitivityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for strategy in self.strategies() {
+            writeln!(f, "  {strategy}")?;
+            write!(f, "    {:>8}", "slip\\bps")?;
+            for fee in &self.fee_bps {
+                write!(f, " {fee:>9.0}")?;
+            }
+            writeln!(f, "  breakeven bps")?;
+            for (s, slip) in self.slippage.iter().enumerate() {
+                write!(f, "    {slip:>8.3}")?;
+                for i in 0..self.fee_bps.len() {
+                    write!(f, " {:>+9.2}", self.pnl(strategy, s, i))?;
+                }
+                match self.breakeven_fee_bps(strategy, s) {
+                    Some(bps) => writeln!(f, "  {bps:>13.0}")?,
+                    None => writeln!(f, "  {:>13}", format!(">{:.0}", self.fee_bps.last().unwrap_or(&0.0)))?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(strategy: &str, order_side: OrderSide, price: f64, post_only: bool) -> SimFill {
+        SimFill {
+            strategy: strategy.into(),
+            window: 0,
+            market_side: Side::Yes,
+            order_side,
+            price,
+            size: 10.0,
+            post_only,
+        }
+    }
+
+    #[test]
+    fn test_costs_apply_to_takers_only() {
+        let cost = CostModel { fee_bps: 200.0, slippage: 0.01 };
+        // Taker buy at 0.50 fills at 0.51, fee 0.51×0.49×2% per share
+        let taker = fill("lag", OrderSide::Buy, 0.50, false);
+        let expected = 10.0 * (1.0 - 0.51 - 0.51 * 0.49 * 0.02);
+        assert!((taker.pnl(Side::Yes, &cost) - expected).abs() < 1e-9);
+        // Maker sell at 0.60 that loses: keeps its quote, no fee
+        let maker = fill("mm", OrderSide::Sell, 0.60, true);
+        assert!((maker.pnl(Side::Yes, &cost) - -4.0).abs() < 1e-9);
+        assert_eq!(cost.fill_price(0.985, OrderSide::Buy, false), 0.99);
+    }
+
+    #[test]
+    fn test_sweep_degrades_and_finds_breakeven() {
+        let fills = vec![fill("lag", OrderSide::Buy, 0.50, false), fill("mm", OrderSide::Buy, 0.40, true)];
+        let fees = [0.0, 500.0, 1_000.0, 2_000.0];
+        let slips = [0.0, 0.02];
+        let report = SensitivityReport::sweep(&fills, &[Side::No], &fees, &slips);
+        assert_eq!(report.strategies(), vec!["lag", "mm"]);
+
+        // Losing outcome: the maker loses its stake whatever the costs
+        assert!((report.pnl("mm", 1, 3) - -4.0).abs() < 1e-9);
+        assert_eq!(report.breakeven_fee_bps("mm", 0), Some(0.0));
+
+        // One win, one loss, and a small extra win: +0.50 before costs
+        let fills = vec![
+            fill("lag", OrderSide::Buy, 0.50, false),
+            SimFill { window: 1, ..fill("lag", OrderSide::Buy, 0.50, false) },
+            SimFill { size: 1.0, ..fill("lag", OrderSide::Buy, 0.50, false) },
+        ];
+        let report = SensitivityReport::sweep(&fills, &[Side::Yes, Side::No], &fees, &slips);
+        for s in 0..slips.len() {
+            for i in 1..fees.len() {
+                assert!(report.pnl("lag", s, i) < report.pnl("lag", s, i - 1));
+            }
+        }
+        // 21 shares × 0.25 × bps/10000 = 0.50 → 952 bps
+        let bps = report.breakeven_fee_bps("lag", 0).unwrap();
+        assert!((bps - 952.4).abs() < 0.1, "{bps}");
+        assert!(report.breakeven_fee_bps("lag", 1).unwrap() < bps);
+        assert!(report.to_string().contains("breakeven bps"));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::models::market::{Market, OrderBook};
+use crate::signals::probability::ProbabilityModel;
+use crate::sim::rng::SimRng;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Seeded synthetic feed for full-engine simulations.
+///
+/// Drives a Binance price as a Gaussian random walk and quotes YES/NO books
+/// around fair value computed from the price `lag_ticks` ticks ago, so the
+/// book trails the underlying the way the live book does. Spreads and depth
+/// are randomised per snapshot. Same seed, same ticks, same books.
+///
+/// Time left is passed in rather than read from the market, so the feed stays
+/// independent of the wall clock.
+pub struct SyntheticFeed {
+    rng: SimRng,
+    prob_model: ProbabilityModel,
+    /// Per-tick return std-dev, as a fraction of price
+    vol_per_tick: f64,
+    lag_ticks: usize,
+    /// Scales quoted half-spreads
+    spread_mult: f64,
+    /// Scales resting size per level
+    depth_mult: f64,
+    /// Most recent first
+    history: Vec<f64>,
+}
+
+impl SyntheticFeed {
+    pub fn new(rng: SimRng, start_price: f64, vol_per_tick: f64, lag_ticks: usize) -> Self {
+        Self {
+            rng,
+            prob_model: ProbabilityModel::new(),
+            vol_per_tick,
+            lag_ticks,
+            spread_mult: 1.0,
+            depth_mult: 1.0,
+            history: vec![start_price],
+        }
+    }
+
+    pub fn price(&self) -> f64 {
+        self.history[0]
+    }
+
+    /// Widen spreads and thin depth for subsequent books (1.0 = normal).
+    pub fn set_liquidity(&mut self, spread_mult: f64, depth_mult: f64) {
+        self.spread_mult = spread_mult;
+        self.depth_mult = depth_mult;
+    }
+
+    /// Advance one tick; returns the new price.
+    pub fn step(&mut self) -> f64 {
+        self.step_drift(0.0)
+    }
+
+    /// Advance one tick with `drift` (fractional move) on top of the noise.
+    pub fn step_drift(&mut self, drift: f64) -> f64 {
+        let next = self.price() * (1.0 + drift + self.vol_per_tick * self.rng.normal());
+        self.history.insert(0, next);
+        self.history.truncate(self.lag_ticks + 1);
+        next
+    }
+
+    /// YES/NO books quoted around the lagged fair value for `market`.
+    pub fn books(&mut self, market: &Market, remaining_secs: f64) -> (OrderBook, OrderBook) {
+        let lagged = *self.history.last().unwrap_or(&market.reference_price);
+        let fair_up = self.prob_model.fair_prob_up(
+            lagged,
+            market.reference_price,
+            remaining_secs / 60.0,
+            market.asset.vol_per_minute(),
+            0.0,
+        );
+        (
+            self.book(&market.yes_token_id, fair_up),
+            self.book(&market.no_token_id, 1.0 - fair_up),
+        )
+    }
+
+    fn book(&mut self, token_id: &str, fair: f64) -> OrderBook {
+        let mut book = OrderBook::new(token_id.to_string());
+        let half_spread = (1 + (self.rng.next_f64() * 2.0) as u32) as f64 * 0.01 * self.spread_mult;
+        let best_bid = ((fair - half_spread) * 100.0).round() / 100.0;
+        let best_ask = ((fair + half_spread) * 100.0).round() / 100.0;
+        let dec = |x: f64| Decimal::from_str(&format!("{x:.2}")).unwrap_or_default();
+
+        for level in 0..5 {
+            let offset = level as f64 * 0.01;
+            let (bid, ask) = (best_bid - offset, best_ask + offset);
+            if bid >= 0.01 {
+                book.bids.insert(dec(bid), dec(self.rng.range(10.0, 100.0) * self.depth_mult));
+            }
+            if ask <= 0.99 {
+                book.asks.insert(dec(ask), dec(self.rng.range(10.0, 100.0) * self.depth_mult));
+            }
+        }
+        book
+    }
+}
@@ -0,0 +1,319 @@
+use crate::config::CapitalAllocation;
+use crate::feeds::market_discovery::MarketDiscovery;
+use crate::feeds::polymarket::RefreshTier;
+use crate::models::market::{Asset, Duration, OrderBook};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use std::time::Instant;
+use tracing::info;
+
+/// EMA smoothing for per-evaluation observations.
+const OBS_ALPHA: f64 = 0.02;
+/// EMA smoothing for per-resolution realized P&L.
+const PNL_ALPHA: f64 = 0.20;
+/// Evaluations needed before a market's score replaces its static prior.
+const MIN_OBSERVATIONS: u64 = 100;
+/// A market that found an edge this recently keeps full-rate book refreshes.
+const ACTIVE_EDGE_SECS: u64 = 60;
+/// Either token quoted wider than this (or one-sided) counts as a dead book.
+const DEAD_SPREAD: Decimal = Decimal::from_parts(20, 0, 0, false, 2);
+
+/// Per-market opportunity scanner and capital allocator.
+///
+/// Scores each (asset, duration) market by how often strategies find an edge,
+/// realized P&L, spread and top-of-book depth, then blends those scores with
+/// the static config split to produce capital fractions and evaluation order.
+pub struct MarketAllocator {
+    stats: DashMap<(Asset, Duration), MarketStats>,
+    config: CapitalAllocation,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MarketStats {
+    observations: u64,
+    /// Fraction of evaluations that produced at least one order
+    edge_freq: f64,
+    /// Average realized P&L per resolved window (USDC)
+    pnl: f64,
+    /// Average YES+NO spread (dollars)
+    spread: f64,
+    /// Average ask depth within 5¢ of best, both sides (shares)
+    depth: f64,
+    last_edge: Option<Instant>,
+}
+
+impl MarketStats {
+    fn observe(&mut self, had_edge: bool, spread: f64, depth: f64) {
+        let edge = if had_edge { 1.0 } else { 0.0 };
+        if self.observations == 0 {
+            self.edge_freq = edge;
+            self.spread = spread;
+            self.depth = depth;
+        } else {
+            self.edge_freq += OBS_ALPHA * (edge - self.edge_freq);
+            self.spread += OBS_ALPHA * (spread - self.spread);
+            self.depth += OBS_ALPHA * (depth - self.depth);
+        }
+        self.observations += 1;
+        if had_edge {
+            self.last_edge = Some(Instant::now());
+        }
+    }
+
+    /// Opportunity score ≥ 0. Edge frequency drives it; P&L, tight spreads
+    /// and depth scale it up or down.
+    fn score(&self) -> f64 {
+        let pnl_factor = 1.0 + 0.5 * (self.pnl / 0.50).tanh();
+        let spread_factor = 1.0 / (1.0 + self.spread / 0.04);
+        let depth_factor = self.depth / (self.depth + 50.0);
+        (self.edge_freq * pnl_factor * spread_factor * depth_factor).max(0.0)
+    }
+}
+
+impl MarketAllocator {
+    pub fn new(config: CapitalAllocation) -> Self {
+        Self {
+            stats: DashMap::new(),
+            config,
+        }
+    }
+
+    /// Record one evaluation of a market.
+    pub fn observe(
+        &self,
+        asset: Asset,
+        duration: Duration,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        had_edge: bool,
+    ) {
+        let to_f64 = |d: Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+        let spread = to_f64(
+            yes_book.spread().unwrap_or(Decimal::ONE) + no_book.spread().unwrap_or(Decimal::ONE),
+        );
+        let tolerance = Decimal::new(5, 2);
+        let depth = to_f64(yes_book.ask_depth_within(tolerance) + no_book.ask_depth_within(tolerance));
+
+        self.stats
+            .entry((asset, duration))
+            .or_default()
+            .observe(had_edge, spread, depth);
+    }
+
+    /// How often the feed should refresh this market's books: full rate
+    /// while we hold a position or a strategy found an edge in the last
+    /// minute, rarely while both books are wide or one-sided, and in between
+    /// otherwise.
+    pub fn refresh_tier(
+        &self,
+        asset: Asset,
+        duration: Duration,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        has_position: bool,
+    ) -> RefreshTier {
+        let recent_edge = self
+            .stats
+            .get(&(asset, duration))
+            .and_then(|s| s.last_edge)
+            .is_some_and(|t| t.elapsed().as_secs() < ACTIVE_EDGE_SECS);
+        if has_position || recent_edge {
+            return RefreshTier::Active;
+        }
+        let dead = |book: &OrderBook| book.spread().is_none_or(|s| s > DEAD_SPREAD);
+        if dead(yes_book) && dead(no_book) {
+            RefreshTier::Hibernated
+        } else {
+            RefreshTier::Watch
+        }
+    }
+
+    /// Record realized P&L for a resolved market window.
+    pub fn record_pnl(&self, asset: Asset, duration: Duration, pnl: f64) {
+        let mut stats = self.stats.entry((asset, duration)).or_default();
+        stats.pnl += PNL_ALPHA * (pnl - stats.pnl);
+    }
+
+    /// Capital fraction for a market type.
+    ///
+    /// Falls back to the static split until every market has enough
+    /// observations, so early noise can't starve a market.
+    pub fn fraction(&self, asset: Asset, duration: Duration) -> f64 {
+        self.fractions()
+            .into_iter()
+            .find(|(a, d, _)| *a == asset && *d == duration)
+            .map(|(_, _, f)| f)
+            .unwrap_or_else(|| self.config.for_market(asset, duration))
+    }
+
+    /// Capital fractions for all market types, summing to 1.0.
+    pub fn fractions(&self) -> Vec<(Asset, Duration, f64)> {
+        let markets = MarketDiscovery::all_market_types();
+        let priors: Vec<f64> = markets
+            .iter()
+            .map(|(a, d)| self.config.for_market(*a, *d))
+            .collect();
+
+        let warmed_up = self.config.dynamic
+            && markets.iter().all(|k| {
+                self.stats
+                    .get(k)
+                    .is_some_and(|s| s.observations >= MIN_OBSERVATIONS)
+            });
+        if !warmed_up {
+            return markets
+                .into_iter()
+                .zip(priors)
+                .map(|((a, d), p)| (a, d, p))
+                .collect();
+        }
+
+        let scores: Vec<f64> = markets
+            .iter()
+            .map(|k| self.stats.get(k).map(|s| s.score()).unwrap_or(0.0))
+            .collect();
+        let score_total: f64 = scores.iter().sum();
+        let prior_total: f64 = priors.iter().sum::<f64>().max(1e-9);
+        let w = if score_total > 0.0 { self.config.score_weight } else { 0.0 };
+
+        let blended: Vec<f64> = priors
+            .iter()
+            .zip(&scores)
+            .map(|(p, s)| {
+                let from_score = if score_total > 0.0 { s / score_total } else { 0.0 };
+                ((1.0 - w) * p / prior_total + w * from_score).max(self.config.min_market_pct)
+            })
+            .collect();
+        let blended_total: f64 = blended.iter().sum();
+
+        markets
+            .into_iter()
+            .zip(blended)
+            .map(|((a, d), f)| (a, d, f / blended_total))
+            .collect()
+    }
+
+    /// Market types ordered by capital fraction, highest first.
+    pub fn priority(&self) -> Vec<(Asset, Duration)> {
+        let mut fractions = self.fractions();
+        fractions.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+        fractions.into_iter().map(|(a, d, _)| (a, d)).collect()
+    }
+
+    /// Log current scores and fractions.
+    pub fn log_summary(&self) {
+        for (asset, duration, fraction) in self.fractions() {
+            let Some(stats) = self.stats.get(&(asset, duration)) else {
+                continue;
+            };
+            info!(
+                "Allocation {:?} {:?}: {:.0}% score={:.3} edge={:.1}% pnl=${:.2} spread={:.3} depth={:.0} (n={})",
+                asset,
+                duration,
+                fraction * 100.0,
+                stats.score(),
+                stats.edge_freq * 100.0,
+                stats.pnl,
+                stats.spread,
+                stats.depth,
+                stats.observations,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn make_book(bid: Decimal, ask: Decimal, size: Decimal) -> OrderBook {
+        let mut book = OrderBook::new("t".to_string());
+        book.bids.insert(bid, size);
+        book.asks.insert(ask, size);
+        book
+    }
+
+    fn warm_up(alloc: &MarketAllocator, edge_market: (Asset, Duration)) {
+        let book = make_book(dec!(0.48), dec!(0.50), dec!(100));
+        for (asset, duration) in MarketDiscovery::all_market_types() {
+            for i in 0..MIN_OBSERVATIONS {
+                // Edge market finds an edge every other tick; others 1 in 20
+                let had_edge = if (asset, duration) == edge_market {
+                    i % 2 == 0
+                } else {
+                    i % 20 == 0
+                };
+                alloc.observe(asset, duration, &book, &book, had_edge);
+            }
+        }
+    }
+
+    #[test]
+    fn test_static_split_before_warmup() {
+        let alloc = MarketAllocator::new(CapitalAllocation::default());
+        let book = make_book(dec!(0.48), dec!(0.50), dec!(100));
+        alloc.observe(Asset::ETH, Duration::FifteenMin, &book, &book, true);
+
+        assert!((alloc.fraction(Asset::BTC, Duration::FiveMin) - 0.40).abs() < 1e-9);
+        assert!((alloc.fraction(Asset::ETH, Duration::FifteenMin) - 0.20).abs() < 1e-9);
+        assert_eq!(alloc.priority()[0], (Asset::BTC, Duration::FiveMin));
+    }
+
+    #[test]
+    fn test_fractions_follow_opportunity() {
+        let alloc = MarketAllocator::new(CapitalAllocation::default());
+        warm_up(&alloc, (Asset::SOL, Duration::FifteenMin));
+
+        let fractions = alloc.fractions();
+        let total: f64 = fractions.iter().map(|(_, _, f)| f).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // SOL started at 10% but finds the most edges
+        assert!(alloc.fraction(Asset::SOL, Duration::FifteenMin) > 0.10);
+        assert_eq!(alloc.priority()[0], (Asset::SOL, Duration::FifteenMin));
+        for (_, _, f) in fractions {
+            assert!(f >= 0.04, "floor should keep every market funded, got {f}");
+        }
+    }
+
+    #[test]
+    fn test_losses_reduce_fraction() {
+        let alloc = MarketAllocator::new(CapitalAllocation::default());
+        warm_up(&alloc, (Asset::SOL, Duration::FifteenMin));
+        let before = alloc.fraction(Asset::SOL, Duration::FifteenMin);
+
+        for _ in 0..10 {
+            alloc.record_pnl(Asset::SOL, Duration::FifteenMin, -1.0);
+        }
+        assert!(alloc.fraction(Asset::SOL, Duration::FifteenMin) < before);
+    }
+
+    #[test]
+    fn test_refresh_tiers() {
+        let alloc = MarketAllocator::new(CapitalAllocation::default());
+        let live = make_book(dec!(0.48), dec!(0.50), dec!(100));
+        let dead = make_book(dec!(0.05), dec!(0.95), dec!(100));
+        let (a, d) = (Asset::XRP, Duration::FifteenMin);
+
+        assert_eq!(alloc.refresh_tier(a, d, &dead, &dead, false), RefreshTier::Hibernated);
+        assert_eq!(alloc.refresh_tier(a, d, &live, &dead, false), RefreshTier::Watch);
+        assert_eq!(alloc.refresh_tier(a, d, &dead, &dead, true), RefreshTier::Active);
+
+        alloc.observe(a, d, &live, &live, false);
+        assert_eq!(alloc.refresh_tier(a, d, &live, &live, false), RefreshTier::Watch);
+        alloc.observe(a, d, &live, &live, true);
+        assert_eq!(alloc.refresh_tier(a, d, &dead, &dead, false), RefreshTier::Active);
+    }
+
+    #[test]
+    fn test_dynamic_disabled_keeps_static_split() {
+        let config = CapitalAllocation {
+            dynamic: false,
+            ..CapitalAllocation::default()
+        };
+        let alloc = MarketAllocator::new(config);
+        warm_up(&alloc, (Asset::SOL, Duration::FifteenMin));
+        assert!((alloc.fraction(Asset::SOL, Duration::FifteenMin) - 0.10).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::models::position::strategy_bucket;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Reconciles the intents several strategies emitted for one market in a
+/// single evaluation, before anything reaches risk checks:
+///
+///   1. Opposing intents on the same token from different strategies (an MM
+///      ask against a lag buy) — the lower-priority strategy's side is dropped.
+///   2. Duplicates (same token, side, price, type) within one strategy family
+///      are merged into one order carrying the higher-priority tag. Duplicates
+///      from different families stay separate orders, so each fill is booked
+///      to — and sized against — the strategy that asked for it.
+///   3. Total notional is capped per evaluation, filled in priority order. A
+///      strategy that only partly fits is scaled down as a whole so paired
+///      legs (arb YES + NO) stay balanced.
+///
+/// Priority: arb > lag > momentum > late gamma / open sniper > straddle > MM.
+pub struct ConflictResolver {
+    /// 0 = no cap
+    max_notional: f64,
+}
+
+/// Scaled-down strategies below this many shares per order are dropped
+const MIN_SCALED_SIZE: f64 = 1.0;
+
+impl ConflictResolver {
+    pub fn new(max_notional: f64) -> Self {
+        Self { max_notional }
+    }
+
+    /// Lower rank = higher priority.
+    pub fn priority(strategy_tag: &str) -> u8 {
+        match strategy_bucket(strategy_tag) {
+            "arb" => 0,
+            "lag" => 1,
+            "momentum" => 2,
+            "late_gamma" | "open_sniper" => 3,
+            "straddle" => 4,
+            "mm" => 5,
+            _ => 6,
+        }
+    }
+
+    pub fn resolve(&self, intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        if intents.len() < 2 && self.max_notional <= 0.0 {
+            return intents;
+        }
+        let mut intents = intents;
+        // Stable: strategies keep their own intent order within a rank
+        intents.sort_by_key(|i| Self::priority(&i.strategy_tag));
+
+        let intents = self.drop_opposing(intents);
+        let intents = Self::merge_duplicates(intents);
+        self.cap_notional(intents)
+    }
+
+    /// Keep only the highest-priority strategy's side on each contested token.
+    fn drop_opposing(&self, intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        // token → (side, rank) of the best-ranked intent seen
+        let mut owner: HashMap<String, (OrderSide, u8)> = HashMap::new();
+        for intent in &intents {
+            owner
+                .entry(intent.token_id.clone())
+                .or_insert((intent.order_side, Self::priority(&intent.strategy_tag)));
+        }
+        intents
+            .into_iter()
+            .filter(|i| {
+                let (side, rank) = owner[&i.token_id];
+                // A strategy may quote both sides itself (MM bid + ask)
+                let keep = i.order_side == side || Self::priority(&i.strategy_tag) == rank;
+                if !keep {
+                    debug!(
+                        "Conflict: dropped {} {:?} on {} — opposes a higher-priority strategy",
+                        i.strategy_tag, i.order_side, i.token_id
+                    );
+                }
+                keep
+            })
+            .collect()
+    }
+
+    fn merge_duplicates(intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        let mut merged: Vec<OrderIntent> = Vec::with_capacity(intents.len());
+        for intent in intents {
+            let duplicate = merged.iter_mut().find(|m| {
+                strategy_bucket(&m.strategy_tag) == strategy_bucket(&intent.strategy_tag)
+                    && m.token_id == intent.token_id
+                    && m.order_side == intent.order_side
+                    && m.price == intent.price
+                    && m.order_type == intent.order_type
+                    && m.post_only == intent.post_only
+            });
+            match duplicate {
+                // `merged` is in priority order, so the survivor's tag wins
+                Some(m) => {
+                    debug!("Conflict: merged {} into {} on {}", intent.strategy_tag, m.strategy_tag, m.token_id);
+                    m.size += intent.size;
+                }
+                None => merged.push(intent),
+            }
+        }
+        merged
+    }
+
+    fn cap_notional(&self, intents: Vec<OrderIntent>) -> Vec<OrderIntent> {
+        if self.max_notional <= 0.0 {
+            return intents;
+        }
+        // Group by strategy family, preserving priority order
+        let mut groups: Vec<(u8, Vec<OrderIntent>)> = Vec::new();
+        for intent in intents {
+            let rank = Self::priority(&intent.strategy_tag);
+            match groups.last_mut() {
+                Some((r, group)) if *r == rank => group.push(intent),
+                _ => groups.push((rank, vec![intent])),
+            }
+        }
+
+        let mut remaining = self.max_notional;
+        let mut kept = Vec::new();
+        for (_, mut group) in groups {
+            let notional: f64 = group.iter().map(notional).sum();
+            if notional <= remaining {
+                remaining -= notional;
+                kept.extend(group);
+                continue;
+            }
+            let scale = remaining / notional;
+            let scaled_ok = group.iter().all(|i| to_f64(i.size) * scale >= MIN_SCALED_SIZE);
+            if scale > 0.0 && scaled_ok {
+                for intent in &mut group {
+                    intent.size = Decimal::from_f64_retain(to_f64(intent.size) * scale)
+                        .unwrap_or(Decimal::ZERO)
+                        .round_dp_with_strategy(2, RoundingStrategy::ToZero);
+                }
+                debug!("Conflict: scaled {} by {scale:.2} to fit notional cap", group[0].strategy_tag);
+                kept.extend(group);
+            } else {
+                debug!("Conflict: dropped {} — notional cap reached", group[0].strategy_tag);
+            }
+            // Later (lower-priority) strategies get nothing once the cap binds
+            break;
+        }
+        kept
+    }
+}
+
+fn to_f64(d: Decimal) -> f64 {
+    d.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+fn notional(intent: &OrderIntent) -> f64 {
+    to_f64(intent.price) * to_f64(intent.size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    fn intent(tag: &str, token: &str, side: OrderSide, price: Decimal, size: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: token.into(),
+            market_side: Side::Yes,
+            order_side: side,
+            price,
+            size,
+            order_type: OrderType::GTC,
+            post_only: false,
+            expiration: None,
+            strategy_tag: tag.into(),
+            group_id: None,
+        }
+    }
+
+    fn tags(intents: &[OrderIntent]) -> Vec<&str> {
+        intents.iter().map(|i| i.strategy_tag.as_str()).collect()
+    }
+
+    #[test]
+    fn test_priority_order() {
+        assert!(ConflictResolver::priority("arb_yes") < ConflictResolver::priority("lag_exploit"));
+        assert!(ConflictResolver::priority("lag_exploit@mid") < ConflictResolver::priority("mm_ask"));
+        assert!(ConflictResolver::priority("late_gamma") < ConflictResolver::priority("straddle_no"));
+        assert_eq!(ConflictResolver::priority("open_sniper"), ConflictResolver::priority("late_gamma"));
+    }
+
+    #[test]
+    fn test_opposing_lower_priority_side_dropped() {
+        let resolved = ConflictResolver::new(0.0).resolve(vec![
+            intent("mm_bid", "yes", OrderSide::Buy, dec!(0.48), dec!(10)),
+            intent("mm_ask", "yes", OrderSide::Sell, dec!(0.52), dec!(10)),
+            intent("lag_exploit", "yes", OrderSide::Buy, dec!(0.53), dec!(5)),
+            intent("mm_bid", "no", OrderSide::Buy, dec!(0.40), dec!(10)),
+        ]);
+        // Lag buys YES, so MM's YES ask goes; MM's own NO quote is untouched
+        assert_eq!(tags(&resolved), ["lag_exploit", "mm_bid", "mm_bid"]);
+        assert!(resolved.iter().all(|i| i.order_side == OrderSide::Buy));
+    }
+
+    #[test]
+    fn test_duplicates_merge_within_a_family() {
+        let resolved = ConflictResolver::new(0.0).resolve(vec![
+            intent("lag_exploit@mid", "yes", OrderSide::Buy, dec!(0.45), dec!(4)),
+            intent("lag_exploit", "yes", OrderSide::Buy, dec!(0.45), dec!(6)),
+        ]);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].size, dec!(10));
+
+        // Another family's identical order stays its own
+        let resolved = ConflictResolver::new(0.0).resolve(vec![
+            intent("straddle_yes", "yes", OrderSide::Buy, dec!(0.45), dec!(4)),
+            intent("arb_yes", "yes", OrderSide::Buy, dec!(0.45), dec!(6)),
+        ]);
+        assert_eq!(tags(&resolved), ["arb_yes", "straddle_yes"]);
+        assert_eq!((resolved[0].size, resolved[1].size), (dec!(6), dec!(4)));
+    }
+
+    #[test]
+    fn test_notional_cap_scales_pairs_and_drops_the_rest() {
+        let resolved = ConflictResolver::new(7.0).resolve(vec![
+            intent("mm_bid", "yes", OrderSide::Buy, dec!(0.40), dec!(10)),
+            intent("lag_exploit", "yes", OrderSide::Buy, dec!(0.50), dec!(4)),
+            intent("arb_yes", "yes", OrderSide::Buy, dec!(0.45), dec!(10)),
+            intent("arb_no", "no", OrderSide::Buy, dec!(0.50), dec!(10)),
+        ]);
+        // Arb ($9.50) doesn't fit $7 whole: both legs scaled by 7/9.5
+        assert_eq!(tags(&resolved), ["arb_yes", "arb_no"]);
+        assert_eq!(resolved[0].size, resolved[1].size);
+        // 7.368 rounds down: 7.37 a leg would cost $7.0015, over the cap
+        assert_eq!(resolved[0].size, dec!(7.36));
+    }
+}
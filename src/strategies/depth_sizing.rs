@@ -0,0 +1,121 @@
+use crate::config::DepthSizingConfig;
+use crate::models::market::{OrderBook, Side};
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::models::position::strategy_bucket;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Caps taker orders at a share of the liquidity they can reach.
+///
+/// A taker order is allowed at most `max_depth_pct` of the shares its side
+/// of the book can give before the fill's VWAP drifts `slippage_band` past
+/// the best price. Orders hitting the same book share that allowance, and a
+/// strategy family is scaled as a whole so paired legs (an arb's YES and NO)
+/// stay balanced. Resting post-only orders are left alone.
+pub struct DepthSizer {
+    config: DepthSizingConfig,
+}
+
+impl DepthSizer {
+    pub fn new(config: DepthSizingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Shares a `side` taker may take from `book`.
+    pub fn cap(&self, book: &OrderBook, side: OrderSide) -> Decimal {
+        let band = Decimal::from_f64_retain(self.config.slippage_band).unwrap_or(Decimal::ZERO);
+        let pct = Decimal::from_f64_retain(self.config.max_depth_pct).unwrap_or(Decimal::ZERO);
+        book.size_within_vwap_band(side, band) * pct
+    }
+
+    /// Shrink taker intents to their cap, dropping any that round to nothing.
+    pub fn apply(&self, yes_book: &OrderBook, no_book: &OrderBook, intents: &mut Vec<OrderIntent>) {
+        if !self.config.enabled {
+            return;
+        }
+
+        // Requested taker size per book and direction
+        let mut requested: HashMap<(Side, OrderSide), Decimal> = HashMap::new();
+        for i in intents.iter().filter(|i| !i.post_only) {
+            *requested.entry((i.market_side, i.order_side)).or_default() += i.size;
+        }
+        let ratio = |route: &(Side, OrderSide)| {
+            let book = if route.0 == Side::Yes { yes_book } else { no_book };
+            let wanted = requested[route];
+            let cap = self.cap(book, route.1);
+            if wanted > cap { cap / wanted } else { Decimal::ONE }
+        };
+
+        let mut family_ratio: HashMap<&'static str, Decimal> = HashMap::new();
+        for i in intents.iter().filter(|i| !i.post_only) {
+            let r = ratio(&(i.market_side, i.order_side));
+            let entry = family_ratio.entry(strategy_bucket(&i.strategy_tag)).or_insert(Decimal::ONE);
+            *entry = (*entry).min(r);
+        }
+
+        for intent in intents.iter_mut().filter(|i| !i.post_only) {
+            let r = family_ratio[strategy_bucket(&intent.strategy_tag)];
+            if r < Decimal::ONE {
+                let capped = (intent.size * r).round_dp_with_strategy(2, RoundingStrategy::ToZero);
+                debug!("Depth cap: {} {} → {}", intent.strategy_tag, intent.size, capped);
+                intent.size = capped;
+            }
+        }
+        intents.retain(|i| i.size > Decimal::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::OrderType;
+    use rust_decimal_macros::dec;
+
+    fn taker(side: Side, size: Decimal, tag: &str) -> OrderIntent {
+        OrderIntent {
+            token_id: format!("{side:?}"),
+            market_side: side,
+            order_side: OrderSide::Buy,
+            price: dec!(0.55),
+            size,
+            order_type: OrderType::FOK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: tag.into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_caps_takers_to_depth_within_band() {
+        let sizer = DepthSizer::new(DepthSizingConfig { enabled: true, max_depth_pct: 0.25, slippage_band: 0.02 });
+        let mut yes = OrderBook::new("yes".into());
+        yes.apply_delta([], [(dec!(0.50), dec!(40)), (dec!(0.52), dec!(40)), (dec!(0.70), dec!(500))]);
+        let mut no = OrderBook::new("no".into());
+        no.apply_delta([], [(dec!(0.45), dec!(400))]);
+        // 80 shares average 0.51 against a 0.52 limit, leaving room for 0.8/0.18 of the 0.70s
+        assert_eq!(sizer.cap(&yes, OrderSide::Buy).round_dp(2), dec!(21.11));
+
+        let mut resting = taker(Side::Yes, dec!(100), "mm_bid");
+        resting.post_only = true;
+        let mut intents = vec![
+            taker(Side::Yes, dec!(30), "lag_exploit"),
+            taker(Side::No, dec!(10), "lag_exploit"),
+            resting,
+        ];
+        sizer.apply(&yes, &no, &mut intents);
+        // The YES cap is shared by the whole family; resting orders are untouched
+        assert_eq!(intents.iter().map(|i| i.size).collect::<Vec<_>>(), [dec!(21.11), dec!(7.03), dec!(100)]);
+
+        // Arb legs shrink together
+        let mut arb = vec![taker(Side::Yes, dec!(50), "arb_yes"), taker(Side::No, dec!(50), "arb_no")];
+        sizer.apply(&yes, &no, &mut arb);
+        assert_eq!(arb[0].size, arb[1].size);
+
+        // A book with nothing in the band drops the order
+        let mut empty = vec![taker(Side::Yes, dec!(5), "momentum")];
+        sizer.apply(&OrderBook::new("e".into()), &no, &mut empty);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config::EdgeConfig;
+use crate::models::market::Side;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Extra edge an entry must clear on top of its strategy's base threshold,
+/// broken down by cause. All in probability units.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequiredEdge {
+    /// From fair-value uncertainty above the floor
+    pub uncertainty: f64,
+    /// From books that haven't updated recently
+    pub staleness: f64,
+    /// From the model's recent calibration error
+    pub calibration: f64,
+}
+
+impl RequiredEdge {
+    /// No adjustment: strategies use their base thresholds.
+    pub const NONE: RequiredEdge = RequiredEdge { uncertainty: 0.0, staleness: 0.0, calibration: 0.0 };
+
+    pub fn extra(&self) -> f64 {
+        self.uncertainty + self.staleness + self.calibration
+    }
+
+    /// Net edge required for an entry whose strategy asks for `base_edge`.
+    pub fn apply(&self, base_edge: f64) -> f64 {
+        base_edge + self.extra()
+    }
+}
+
+#[derive(Default)]
+struct Calibration {
+    /// Predictions sampled through each open window: market → (time, P(UP))
+    open: HashMap<String, Vec<(DateTime<Utc>, f64)>>,
+    /// Resolved samples: (P(UP), 1.0 if UP won)
+    scored: VecDeque<(f64, f64)>,
+}
+
+/// Entry edge requirements, in one place: a strategy's fixed threshold is
+/// widened when its fair value is uncertain, when the book it's reading is
+/// stale, and when the model has recently been miscalibrated.
+///
+/// Calibration error is the observed Brier score of sampled predictions
+/// minus the score a calibrated model would expect at those probabilities
+/// (mean p·(1-p)); its square root is used as an extra standard error.
+pub struct EdgePolicy {
+    config: EdgeConfig,
+    calibration: Mutex<Calibration>,
+}
+
+impl EdgePolicy {
+    pub fn new(config: EdgeConfig) -> Self {
+        Self { config, calibration: Mutex::new(Calibration::default()) }
+    }
+
+    /// Adjustment for one evaluation.
+    pub fn required(&self, fair_value_uncertainty: Option<f64>, book_age_secs: f64) -> RequiredEdge {
+        let c = &self.config;
+        if !c.enabled {
+            return RequiredEdge::NONE;
+        }
+        RequiredEdge {
+            uncertainty: c.uncertainty_mult * (fair_value_uncertainty.unwrap_or(0.0) - c.uncertainty_floor).max(0.0),
+            staleness: (c.book_age_per_sec * book_age_secs.max(0.0)).min(c.max_staleness_edge),
+            calibration: c.calibration_mult * self.calibration_gap().sqrt(),
+        }
+    }
+
+    /// Sample a live P(UP) for `market`, at most once per `calibration_sample_secs`.
+    pub fn observe_prediction(&self, market: &str, prob_up: f64, now: DateTime<Utc>) {
+        let mut cal = self.calibration.lock().unwrap();
+        let samples = cal.open.entry(market.to_string()).or_default();
+        let due = samples.last().is_none_or(|&(t, _)| {
+            (now - t).num_milliseconds() as f64 >= self.config.calibration_sample_secs * 1000.0
+        });
+        if due {
+            samples.push((now, prob_up));
+        }
+    }
+
+    /// Score the market's sampled predictions against how it resolved.
+    pub fn record_outcome(&self, market: &str, winner: Side) {
+        let mut cal = self.calibration.lock().unwrap();
+        let Some(samples) = cal.open.remove(market) else { return };
+        let y = if winner == Side::Yes { 1.0 } else { 0.0 };
+        for (_, p) in samples {
+            cal.scored.push_back((p, y));
+        }
+        while cal.scored.len() > self.config.calibration_window {
+            cal.scored.pop_front();
+        }
+    }
+
+    /// Observed minus expected Brier score over the window, floored at 0;
+    /// 0 until `calibration_min_samples` are in.
+    pub fn calibration_gap(&self) -> f64 {
+        let cal = self.calibration.lock().unwrap();
+        let n = cal.scored.len();
+        if n == 0 || n < self.config.calibration_min_samples {
+            return 0.0;
+        }
+        let observed = cal.scored.iter().map(|(p, y)| (p - y).powi(2)).sum::<f64>() / n as f64;
+        let expected = cal.scored.iter().map(|(p, _)| p * (1.0 - p)).sum::<f64>() / n as f64;
+        (observed - expected).max(0.0)
+    }
+
+    /// Resolved prediction samples in the calibration window.
+    pub fn calibration_samples(&self) -> usize {
+        self.calibration.lock().unwrap().scored.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_edge_components() {
+        let policy = EdgePolicy::new(EdgeConfig { calibration_min_samples: 4, ..EdgeConfig::default() });
+        let fresh = policy.required(Some(0.01), 0.0);
+        assert_eq!(fresh, RequiredEdge::NONE, "under the floor with a fresh book");
+        assert_eq!(fresh.apply(0.03), 0.03);
+
+        let c = EdgeConfig::default();
+        let unsure = policy.required(Some(c.uncertainty_floor + 0.04), 3.0);
+        assert!((unsure.uncertainty - c.uncertainty_mult * 0.04).abs() < 1e-12);
+        assert!((unsure.staleness - 3.0 * c.book_age_per_sec).abs() < 1e-12);
+        assert_eq!(policy.required(None, 1e6).staleness, c.max_staleness_edge);
+
+        // A confidently wrong model: 0.8 UP, twice, and DOWN won both times
+        let t0 = Utc::now();
+        for (i, market) in ["a", "b"].iter().enumerate() {
+            policy.observe_prediction(market, 0.8, t0);
+            policy.observe_prediction(market, 0.8, t0 + chrono::Duration::seconds(1)); // too soon, skipped
+            policy.observe_prediction(market, 0.8, t0 + chrono::Duration::seconds(60));
+            assert_eq!(policy.calibration_gap(), 0.0, "not enough samples yet ({i})");
+            policy.record_outcome(market, Side::No);
+        }
+        assert_eq!(policy.calibration_samples(), 4);
+        // Observed 0.64 vs expected 0.16
+        assert!((policy.calibration_gap() - 0.48).abs() < 1e-9);
+        assert!(policy.required(None, 0.0).calibration > 0.0);
+
+        let off = EdgePolicy::new(EdgeConfig { enabled: false, ..EdgeConfig::default() });
+        assert_eq!(off.required(Some(0.5), 100.0), RequiredEdge::NONE);
+    }
+}
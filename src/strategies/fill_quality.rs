@@ -0,0 +1,161 @@
+use crate::config::FillQualityConfig;
+use crate::models::order::OrderIntent;
+use crate::models::position::strategy_bucket;
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+use tracing::{info, warn};
+
+/// Feeds post-trade adverse selection back into sizing.
+///
+/// Keeps the last `window` marked fills per strategy family. While a family's
+/// size-weighted adverse selection exceeds `max_adverse_selection`, its orders
+/// are scaled by `max / observed` (floored at `min_size_mult`), so sizing
+/// recovers on its own as the picked-off fills roll out of the window.
+pub struct FillQualityController {
+    config: FillQualityConfig,
+    /// family → recent (adverse selection per share, shares)
+    recent: DashMap<&'static str, VecDeque<(f64, f64)>>,
+    /// family → multiplier last logged, to report changes only
+    logged: DashMap<&'static str, f64>,
+}
+
+impl FillQualityController {
+    pub fn new(config: FillQualityConfig) -> Self {
+        Self {
+            config,
+            recent: DashMap::new(),
+            logged: DashMap::new(),
+        }
+    }
+
+    /// Record a marked fill for the strategy that placed it.
+    pub fn observe(&self, strategy_tag: &str, adverse_selection: f64, size: f64) {
+        let family = strategy_bucket(strategy_tag);
+        {
+            let mut recent = self.recent.entry(family).or_default();
+            recent.push_back((adverse_selection, size));
+            while recent.len() > self.config.window {
+                recent.pop_front();
+            }
+        }
+
+        let mult = self.size_mult(strategy_tag);
+        let last = self.logged.get(family).map(|m| *m).unwrap_or(1.0);
+        if (mult - last).abs() >= 0.05 || (mult == 1.0 && last != 1.0) {
+            let adverse = self.adverse_selection(family).unwrap_or(0.0);
+            if mult < last {
+                warn!(
+                    "Fill quality: {family} adverse selection {:.2}c/share — size x{mult:.2}",
+                    adverse * 100.0
+                );
+            } else {
+                info!(
+                    "Fill quality: {family} adverse selection {:.2}c/share — size back to x{mult:.2}",
+                    adverse * 100.0
+                );
+            }
+            self.logged.insert(family, mult);
+        }
+    }
+
+    /// Size-weighted adverse selection over the window, once there are enough fills.
+    pub fn adverse_selection(&self, family: &str) -> Option<f64> {
+        let recent = self.recent.get(family)?;
+        let shares: f64 = recent.iter().map(|(_, s)| s).sum();
+        if recent.len() < self.config.min_fills || shares <= 0.0 {
+            return None;
+        }
+        Some(recent.iter().map(|(a, s)| a * s).sum::<f64>() / shares)
+    }
+
+    /// Current size multiplier for a strategy (1.0 = untouched).
+    pub fn size_mult(&self, strategy_tag: &str) -> f64 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+        match self.adverse_selection(strategy_bucket(strategy_tag)) {
+            Some(adverse) if adverse > self.config.max_adverse_selection => {
+                (self.config.max_adverse_selection / adverse).max(self.config.min_size_mult)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Scale intents by their strategy's multiplier, dropping any that round to nothing.
+    pub fn apply(&self, intents: &mut Vec<OrderIntent>) {
+        if !self.config.enabled {
+            return;
+        }
+        for intent in intents.iter_mut() {
+            let mult = self.size_mult(&intent.strategy_tag);
+            if mult < 1.0 {
+                let size = intent.size.to_string().parse::<f64>().unwrap_or(0.0) * mult;
+                intent.size = Decimal::from_f64_retain(size).unwrap_or(Decimal::ZERO).round_dp(2);
+            }
+        }
+        intents.retain(|i| i.size > Decimal::ZERO);
+    }
+
+    /// Current multipliers for families below 1.0.
+    pub fn adjustments(&self) -> Vec<(&'static str, f64)> {
+        let mut out: Vec<_> = self
+            .recent
+            .iter()
+            .map(|e| (*e.key(), self.size_mult(e.key())))
+            .filter(|(_, m)| *m < 1.0)
+            .collect();
+        out.sort_by(|a, b| a.0.cmp(b.0));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> FillQualityController {
+        FillQualityController::new(FillQualityConfig {
+            enabled: true,
+            max_adverse_selection: 0.02,
+            window: 10,
+            min_fills: 4,
+            min_size_mult: 0.25,
+        })
+    }
+
+    #[test]
+    fn test_shrinks_adversely_selected_strategy_only() {
+        let fq = controller();
+        for _ in 0..3 {
+            fq.observe("mm_bid", 0.04, 10.0);
+        }
+        assert_eq!(fq.size_mult("mm_ask"), 1.0, "Too few fills to react");
+
+        fq.observe("mm_ask", 0.04, 10.0);
+        fq.observe("lag_exploit", 0.04, 10.0);
+        // 4c against a 2c tolerance: half size, family-wide
+        assert!((fq.size_mult("mm_bid") - 0.5).abs() < 1e-9);
+        assert_eq!(fq.size_mult("lag_exploit"), 1.0);
+        assert_eq!(fq.adjustments().len(), 1);
+
+        // Floor
+        for _ in 0..4 {
+            fq.observe("mm_bid", 0.50, 10.0);
+        }
+        assert_eq!(fq.size_mult("mm_bid"), 0.25);
+    }
+
+    #[test]
+    fn test_recovers_as_window_rolls() {
+        let fq = controller();
+        for _ in 0..10 {
+            fq.observe("momentum", 0.05, 5.0);
+        }
+        assert!(fq.size_mult("momentum") < 1.0);
+        for _ in 0..10 {
+            fq.observe("momentum", -0.01, 5.0);
+        }
+        assert_eq!(fq.size_mult("momentum"), 1.0);
+    }
+}
@@ -1,9 +1,16 @@
 use crate::config::StrategyConfig;
 use crate::models::market::{LifecyclePhase, Market, OrderBook, Side};
 use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::models::position::{strategy_bucket, Position};
 use crate::models::signal::VolRegime;
+use crate::signals::half_life::{HoldLimits, OpportunityHalfLife};
 use crate::signals::probability::ProbabilityModel;
+use crate::signals::projection::ProjectedPrice;
+use crate::signals::seasonality::Seasonality;
+use crate::strategies::edge::RequiredEdge;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use std::sync::Arc;
 use tracing::info;
 
 /// Cross-exchange lag exploit engine.
@@ -11,9 +18,14 @@ use tracing::info;
 /// Compares Binance real-time price against Polymarket implied probability.
 /// When Polymarket lags Binance by >3 cents, buys the underpriced side.
 /// Our latency: ~500ms total. Average competitor: 5-60 seconds.
+///
+/// With an opportunity half-life table, the minimum edge scales with how
+/// quickly this market's mispricings have been correcting; the orchestrator
+/// passes the matching entry cut-off and max hold (`HoldLimits`).
 pub struct LagExploitEngine {
     config: StrategyConfig,
     prob_model: ProbabilityModel,
+    half_life: Option<Arc<OpportunityHalfLife>>,
 }
 
 impl LagExploitEngine {
@@ -21,23 +33,41 @@ impl LagExploitEngine {
         Self {
             config,
             prob_model: ProbabilityModel::new(),
+            half_life: None,
         }
     }
 
+    /// Adapt edge and max hold to observed opportunity lifetimes.
+    pub fn with_half_life(mut self, half_life: Arc<OpportunityHalfLife>) -> Self {
+        self.half_life = Some(half_life);
+        self
+    }
+
+    /// Scale fair value by the hour's vol so a lag in a busy hour needs a
+    /// bigger move to count as edge.
+    pub fn with_seasonality(mut self, seasonality: Arc<Seasonality>) -> Self {
+        self.prob_model = ProbabilityModel::new().with_seasonality(seasonality);
+        self
+    }
+
     /// Evaluate lag exploit opportunity.
     ///
-    /// - `binance_price`: current real-time price from Binance WebSocket
+    /// - `binance_price`: latest Binance price, projected over our latency
     /// - `open_price`: the market's reference price at open
     /// - `momentum_adj`: momentum adjustment from bias detector [-0.1, 0.1]
+    /// - `required_edge`: confidence adjustment on top of the regime's min edge
+    #[allow(clippy::too_many_arguments)]
     pub fn evaluate(
         &self,
         market: &Market,
         yes_book: &OrderBook,
         no_book: &OrderBook,
-        binance_price: f64,
+        binance_price: ProjectedPrice,
         vol_regime: VolRegime,
         available_capital: f64,
         momentum_adj: f64,
+        required_edge: RequiredEdge,
+        limits: Option<HoldLimits>,
     ) -> Vec<OrderIntent> {
         let phase = market.lifecycle_phase();
 
@@ -55,17 +85,21 @@ impl LagExploitEngine {
         if !self.should_trade(vol_regime, market) {
             return Vec::new();
         }
+        if limits.is_some_and(|l| market.time_remaining_secs() <= l.min_remaining_secs) {
+            return Vec::new();
+        }
 
         let min_edge = match vol_regime.lag_min_edge() {
-            Some(e) => e,
+            Some(e) => required_edge.apply(e),
             None => return Vec::new(), // DEAD vol = no lag trading
         };
+        let min_edge = min_edge * self.half_life.as_ref().map_or(1.0, |h| h.edge_mult(market, Utc::now()));
 
         let time_remaining_min = market.time_remaining_secs() / 60.0;
-        let vol_per_min = market.asset.vol_per_minute();
+        let vol_per_min = self.prob_model.vol_per_minute(market.asset);
 
         // Calculate fair probability from Binance price
-        let fair_prob_up = self.prob_model.fair_prob_up(
+        let fair_prob_up = self.prob_model.fair_prob_up_projected(
             binance_price,
             market.reference_price,
             time_remaining_min,
@@ -123,6 +157,7 @@ impl LagExploitEngine {
         orders
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn build_lag_order(
         &self,
         market: &Market,
@@ -183,9 +218,52 @@ impl LagExploitEngine {
             post_only: false,
             expiration: None,
             strategy_tag: "lag_exploit".into(),
+            group_id: None,
         })
     }
 
+    /// Sell lag positions held past `max_hold` seconds at the best bid: the
+    /// mispricing they bought should have corrected by now. A side with no
+    /// book (or no bid) is retried on a later call.
+    pub fn max_hold_exits(
+        &self,
+        market: &Market,
+        positions: &[Position],
+        yes_book: Option<&OrderBook>,
+        no_book: Option<&OrderBook>,
+        max_hold: f64,
+        now: DateTime<Utc>,
+    ) -> Vec<OrderIntent> {
+        positions
+            .iter()
+            .filter(|p| strategy_bucket(&p.strategy_tag) == "lag" && p.size > Decimal::ZERO)
+            .filter(|p| (now - p.opened_at).num_milliseconds() as f64 / 1000.0 > max_hold)
+            .filter_map(|p| {
+                let book = if p.side == Side::Yes { yes_book } else { no_book };
+                let (bid, _) = book?.best_bid()?;
+                info!(
+                    "LAG MAX HOLD: market={} sell {:?} {} @{bid} after {:.0}s (max {max_hold:.0}s)",
+                    market.slug,
+                    p.side,
+                    p.size,
+                    (now - p.opened_at).num_milliseconds() as f64 / 1000.0
+                );
+                Some(OrderIntent {
+                    token_id: p.token_id.clone(),
+                    market_side: p.side,
+                    order_side: OrderSide::Sell,
+                    price: bid,
+                    size: p.size,
+                    order_type: OrderType::FAK,
+                    post_only: false,
+                    expiration: None,
+                    strategy_tag: p.strategy_tag.clone(),
+                    group_id: None,
+                })
+            })
+            .collect()
+    }
+
     fn should_trade(&self, vol_regime: VolRegime, market: &Market) -> bool {
         let time_remaining = market.time_remaining_secs();
 
@@ -0,0 +1,180 @@
+use crate::config::StrategyConfig;
+use crate::models::market::{Market, OrderBook, Side};
+use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::models::signal::VolRegime;
+use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
+use crate::strategies::edge::RequiredEdge;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::info;
+
+/// Don't trade this close to resolution — fills may not settle in time.
+const MIN_REMAINING_SECS: f64 = 5.0;
+/// Only buy tokens in this price range; the extremes have no payoff left.
+const MIN_ASK: f64 = 0.05;
+const MAX_ASK: f64 = 0.90;
+/// Max orders per market window.
+const MAX_ENTRIES_PER_WINDOW: u32 = 3;
+
+/// Late-window gamma scalping engine.
+///
+/// In the last 60-90 seconds, a small Binance move swings token prices
+/// violently and the book often lags. When the model's probability strongly
+/// disagrees with the book, takes a tiny taker position on the cheap side.
+///
+/// Runs through the lockout phase (which blocks every other strategy) and
+/// has its own hard caps: a per-order cost, a per-window cost and a per-window
+/// order count. Caps count orders sent, not fills, so they err on the side
+/// of less exposure.
+pub struct LateGammaEngine {
+    config: StrategyConfig,
+    prob_model: ProbabilityModel,
+    /// market_id → exposure committed this window
+    windows: DashMap<String, WindowExposure>,
+}
+
+#[derive(Debug, Clone)]
+struct WindowExposure {
+    close_time: DateTime<Utc>,
+    spent: f64,
+    entries: u32,
+}
+
+impl LateGammaEngine {
+    pub fn new(config: StrategyConfig) -> Self {
+        Self {
+            config,
+            prob_model: ProbabilityModel::new(),
+            windows: DashMap::new(),
+        }
+    }
+
+    /// Price the final-seconds gamma bets off the hour's vol, which is what
+    /// decides how far spot can still travel before close.
+    pub fn with_seasonality(mut self, seasonality: Arc<Seasonality>) -> Self {
+        self.prob_model = ProbabilityModel::new().with_seasonality(seasonality);
+        self
+    }
+
+    /// Whether the market is inside the late-gamma window.
+    pub fn is_active(&self, market: &Market) -> bool {
+        let remaining = market.time_remaining_secs();
+        remaining > MIN_REMAINING_SECS && remaining <= self.config.late_gamma_window_secs
+    }
+
+    /// Evaluate a late-window entry; `required_edge` widens the configured min edge.
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        vol_regime: VolRegime,
+        available_capital: f64,
+        required_edge: RequiredEdge,
+    ) -> Vec<OrderIntent> {
+        self.prune_windows();
+
+        if !self.is_active(market) || market.reference_price == 0.0 {
+            return Vec::new();
+        }
+        // Extreme vol means our Binance price may already be stale
+        if matches!(vol_regime, VolRegime::Extreme) {
+            return Vec::new();
+        }
+
+        let (spent, entries) = self
+            .windows
+            .get(&market.id)
+            .map(|w| (w.spent, w.entries))
+            .unwrap_or((0.0, 0));
+        if entries >= MAX_ENTRIES_PER_WINDOW {
+            return Vec::new();
+        }
+        let budget = (self.config.late_gamma_market_usdc - spent)
+            .min(self.config.late_gamma_order_usdc)
+            .min(available_capital);
+        if budget < 0.10 {
+            return Vec::new();
+        }
+
+        let fair_up = self.prob_model.fair_prob_up(
+            binance_price,
+            market.reference_price,
+            market.time_remaining_secs() / 60.0,
+            self.prob_model.vol_per_minute(market.asset),
+            0.0,
+        );
+
+        let ask = |book: &OrderBook| {
+            book.best_ask()
+                .map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0))
+        };
+        let candidates = [
+            (Side::Yes, &market.yes_token_id, fair_up, ask(yes_book)),
+            (Side::No, &market.no_token_id, 1.0 - fair_up, ask(no_book)),
+        ];
+
+        // Take the side with the larger disagreement, if it clears the bar
+        let min_edge = required_edge.apply(self.config.late_gamma_min_edge);
+        let best = candidates
+            .into_iter()
+            .filter_map(|(side, token, fair, ask)| {
+                let ask = ask?;
+                let edge = fair - ask;
+                ((MIN_ASK..=MAX_ASK).contains(&ask) && edge >= min_edge)
+                    .then_some((side, token, fair, ask, edge))
+            })
+            .max_by(|a, b| a.4.partial_cmp(&b.4).unwrap_or(std::cmp::Ordering::Equal));
+
+        let Some((side, token_id, fair, ask, edge)) = best else {
+            return Vec::new();
+        };
+
+        let shares = (budget / ask * 100.0).floor() / 100.0;
+        if shares <= 0.0 {
+            return Vec::new();
+        }
+        let cost = shares * ask;
+
+        let mut window = self
+            .windows
+            .entry(market.id.clone())
+            .or_insert_with(|| WindowExposure {
+                close_time: market.close_time,
+                spent: 0.0,
+                entries: 0,
+            });
+        window.spent += cost;
+        window.entries += 1;
+
+        info!(
+            "LATE GAMMA: market={} buy {side:?}@{ask:.3} fair={fair:.3} edge={edge:.3} size={shares:.2} ({:.0}s left)",
+            market.slug,
+            market.time_remaining_secs(),
+        );
+
+        vec![OrderIntent {
+            token_id: token_id.clone(),
+            market_side: side,
+            order_side: OrderSide::Buy,
+            price: Decimal::from_f64_retain(ask).unwrap_or(Decimal::ZERO),
+            size: Decimal::from_f64_retain(shares).unwrap_or(Decimal::ZERO),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "late_gamma".into(),
+            group_id: None,
+        }]
+    }
+
+    /// Drop exposure records for windows that have closed.
+    fn prune_windows(&self) {
+        let now = Utc::now();
+        self.windows.retain(|_, w| w.close_time > now);
+    }
+}
@@ -1,14 +1,18 @@
 use crate::config::StrategyConfig;
 use crate::models::market::{LifecyclePhase, Market, OrderBook, Side};
 use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::models::position::quote_level_tag;
 use crate::models::signal::VolRegime;
 use crate::signals::probability::ProbabilityModel;
+use crate::signals::seasonality::Seasonality;
 use rust_decimal::Decimal;
+use std::sync::Arc;
 use tracing::debug;
 
 /// Micro market-making engine.
 ///
-/// Posts two-sided quotes (bid + ask) around fair value.
+/// Posts two-sided quotes (bid + ask) around fair value, optionally as a
+/// ladder of `mm_levels` per side, each further out and larger.
 /// Captures spread on thin books. Manages inventory via quote skew.
 /// Pulls quotes on adverse selection signals.
 pub struct MarketMakerEngine {
@@ -31,6 +35,12 @@ impl MarketMakerEngine {
         }
     }
 
+    /// Centre quotes on a fair value that uses the hour's vol.
+    pub fn with_seasonality(mut self, seasonality: Arc<Seasonality>) -> Self {
+        self.prob_model = ProbabilityModel::new().with_seasonality(seasonality);
+        self
+    }
+
     /// Evaluate and produce market-making quotes.
     ///
     /// - `binance_price`: real-time underlying price
@@ -38,6 +48,8 @@ impl MarketMakerEngine {
     /// - `binance_1s_move_pct`: absolute % move of Binance price in last 1 second
     /// - `order_flow_imbalance`: buy/sell ratio over last 5 seconds
     /// - `liquidation_active`: whether a liquidation cascade is detected
+    /// - `spread_mult`: the spread controller's multiplier for this series
+    #[allow(clippy::too_many_arguments)]
     pub fn evaluate(
         &self,
         market: &Market,
@@ -49,6 +61,7 @@ impl MarketMakerEngine {
         binance_1s_move_pct: f64,
         order_flow_imbalance: f64,
         liquidation_active: bool,
+        spread_mult: f64,
     ) -> Vec<OrderIntent> {
         // Should we market-make at all?
         if !self.should_mm(market, vol_regime, available_capital, yes_book) {
@@ -69,7 +82,7 @@ impl MarketMakerEngine {
         }
 
         let time_remaining_min = market.time_remaining_secs() / 60.0;
-        let vol_per_min = market.asset.vol_per_minute();
+        let vol_per_min = self.prob_model.vol_per_minute(market.asset);
 
         // Calculate fair value
         let fair_value = self.prob_model.fair_prob_up(
@@ -81,7 +94,7 @@ impl MarketMakerEngine {
         );
 
         // Calculate spread
-        let mut half_spread = vol_regime.mm_half_spread();
+        let mut half_spread = vol_regime.mm_half_spread() * spread_mult;
 
         // Widen on adverse selection signal
         if action == AdverseSelectionAction::WidenSpread {
@@ -120,47 +133,76 @@ impl MarketMakerEngine {
             return Vec::new();
         }
 
-        let bid_dec = Decimal::from_f64_retain(bid_price).unwrap_or(Decimal::ZERO);
-        let ask_dec = Decimal::from_f64_retain(ask_price).unwrap_or(Decimal::ZERO);
-        let size_dec = Decimal::from_f64_retain(quote_size).unwrap_or(Decimal::ZERO);
-
-        // Round to tick size
-        let tick = market.tick_size;
-        let bid_rounded = (bid_dec / tick).floor() * tick;
-        let ask_rounded = (ask_dec / tick).ceil() * tick;
-
         debug!(
-            "MM: market={} fair={fair_value:.3} bid={bid_price:.3} ask={ask_price:.3} spread={:.3} skew={skew:.4} size={quote_size:.1}",
+            "MM: market={} fair={fair_value:.3} bid={bid_price:.3} ask={ask_price:.3} spread={:.3} skew={skew:.4} size={quote_size:.1} levels={}",
             market.slug,
-            ask_price - bid_price
+            ask_price - bid_price,
+            self.config.mm_levels
         );
+        self.ladder(market, bid_price, ask_price, quote_size)
+    }
+
+    /// Quotes for a window that hasn't opened yet. At the open the price is
+    /// the reference, so fair value is 0.50 and there's no inventory to skew
+    /// for; the spread is the regime's, with no near-expiry widening.
+    pub fn opening_quotes(
+        &self,
+        market: &Market,
+        vol_regime: VolRegime,
+        available_capital: f64,
+        spread_mult: f64,
+    ) -> Vec<OrderIntent> {
+        if matches!(vol_regime, VolRegime::Extreme) || (matches!(vol_regime, VolRegime::High) && available_capital < 3.0) {
+            return Vec::new();
+        }
+        let half_spread = vol_regime.mm_half_spread() * spread_mult;
+        let quote_size = available_capital * self.config.mm_base_size_pct * vol_regime.mm_size_multiplier();
+        if quote_size < 0.10 {
+            return Vec::new();
+        }
+        self.ladder(market, 0.50 - half_spread, 0.50 + half_spread, quote_size)
+    }
 
-        vec![
-            // Bid (buy YES)
-            OrderIntent {
-                token_id: market.yes_token_id.clone(),
-                market_side: Side::Yes,
-                order_side: OrderSide::Buy,
-                price: bid_rounded,
-                size: size_dec,
-                order_type: OrderType::GTC,
-                post_only: true, // Ensure maker execution
-                expiration: None,
-                strategy_tag: "mm_bid".into(),
-            },
-            // Ask (sell YES)
-            OrderIntent {
-                token_id: market.yes_token_id.clone(),
-                market_side: Side::Yes,
-                order_side: OrderSide::Sell,
-                price: ask_rounded,
-                size: size_dec,
-                order_type: OrderType::GTC,
-                post_only: true,
-                expiration: None,
-                strategy_tag: "mm_ask".into(),
-            },
-        ]
+    /// `mm_levels` quotes per side from the inside bid and ask outwards.
+    fn ladder(&self, market: &Market, bid_price: f64, ask_price: f64, quote_size: f64) -> Vec<OrderIntent> {
+        let mut quotes = Vec::with_capacity(self.config.mm_levels * 2);
+        for level in 1..=self.config.mm_levels.max(1) {
+            let steps = (level - 1) as f64;
+            let offset = self.config.mm_level_spacing * steps;
+            let size = quote_size * self.config.mm_level_size_mult.powf(steps);
+            // Levels that would fall off the price range are skipped
+            let bid = bid_price - offset;
+            if bid >= 0.01 {
+                quotes.push(self.quote(market, OrderSide::Buy, bid, size, level));
+            }
+            let ask = ask_price + offset;
+            if ask <= 0.99 {
+                quotes.push(self.quote(market, OrderSide::Sell, ask, size, level));
+            }
+        }
+        quotes
+    }
+
+    /// A post-only YES quote at `level`, rounded away from fair value to the tick.
+    fn quote(&self, market: &Market, side: OrderSide, price: f64, size: f64, level: usize) -> OrderIntent {
+        let price_dec = Decimal::from_f64_retain(price).unwrap_or(Decimal::ZERO);
+        let tick = market.tick_size;
+        let (price, base) = match side {
+            OrderSide::Buy => ((price_dec / tick).floor() * tick, "mm_bid"),
+            OrderSide::Sell => ((price_dec / tick).ceil() * tick, "mm_ask"),
+        };
+        OrderIntent {
+            token_id: market.yes_token_id.clone(),
+            market_side: Side::Yes,
+            order_side: side,
+            price,
+            size: Decimal::from_f64_retain(size).unwrap_or(Decimal::ZERO),
+            order_type: OrderType::GTC,
+            post_only: true, // Ensure maker execution
+            expiration: None,
+            strategy_tag: quote_level_tag(base, level),
+            group_id: None,
+        }
     }
 
     fn should_mm(
@@ -4,3 +4,13 @@ pub mod lag_exploit;
 pub mod market_maker;
 pub mod momentum_capture;
 pub mod orchestrator;
+pub mod allocator;
+pub mod late_gamma;
+pub mod open_sniper;
+pub mod conflict;
+pub mod fill_quality;
+pub mod screen;
+pub mod edge;
+pub mod depth_sizing;
+pub mod spread_control;
+pub mod pre_position;
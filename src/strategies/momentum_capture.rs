@@ -100,6 +100,7 @@ impl MomentumCaptureEngine {
             post_only: false,
             expiration: None,
             strategy_tag: "momentum".into(),
+            group_id: None,
         }]
     }
 
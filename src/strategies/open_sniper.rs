@@ -0,0 +1,144 @@
+use crate::config::StrategyConfig;
+use crate::models::market::{Market, OrderBook, Side};
+use crate::models::order::{OrderIntent, OrderSide, OrderType, MIN_ORDER_SHARES};
+use crate::strategies::edge::RequiredEdge;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use tracing::{debug, info};
+
+/// Only buy tokens in this price range; opening quotes outside it are
+/// usually placeholders, not prices.
+const MIN_ASK: f64 = 0.05;
+const MAX_ASK: f64 = 0.95;
+
+/// Opening-seconds sniper.
+///
+/// Right after a window opens, makers post stale or lazy quotes before the
+/// book settles around fair value. In the first `open_sniper_window_secs`,
+/// takes the side whose ask sits furthest below the fair-value ensemble.
+///
+/// The edge only exists while our view is fresher than the book's, so it
+/// refuses books older than `open_sniper_max_book_age_ms` — a much tighter
+/// bar than other strategies apply. One entry per window, capped at
+/// `open_sniper_order_usdc`; the window is spent once the exchange accepts
+/// the order (`mark_fired`), so a rejected or unsent one can be retried.
+pub struct OpenSniperEngine {
+    config: StrategyConfig,
+    /// market_id → close time of windows whose entry was accepted
+    fired: DashMap<String, DateTime<Utc>>,
+}
+
+impl OpenSniperEngine {
+    pub fn new(config: StrategyConfig) -> Self {
+        Self {
+            config,
+            fired: DashMap::new(),
+        }
+    }
+
+    /// Whether the market is inside its opening window.
+    pub fn is_active(&self, market: &Market) -> bool {
+        market.time_remaining_secs() > 0.0 && market.time_elapsed_secs() < self.config.open_sniper_window_secs
+    }
+
+    /// Evaluate an opening entry against the ensemble's `fair_up`;
+    /// `book_age_secs` is the staler of the two books.
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        fair_up: Option<f64>,
+        book_age_secs: f64,
+        available_capital: f64,
+        required_edge: RequiredEdge,
+    ) -> Vec<OrderIntent> {
+        self.prune_fired();
+
+        if !self.is_active(market) || self.fired.contains_key(&market.id) {
+            return Vec::new();
+        }
+        let Some(fair_up) = fair_up else {
+            return Vec::new();
+        };
+        if book_age_secs * 1000.0 > self.config.open_sniper_max_book_age_ms {
+            debug!(
+                "OPEN SNIPER: {} book {:.0}ms old, limit {:.0}ms",
+                market.slug,
+                book_age_secs * 1000.0,
+                self.config.open_sniper_max_book_age_ms
+            );
+            return Vec::new();
+        }
+        let budget = self.config.open_sniper_order_usdc.min(available_capital);
+        if budget < 0.10 {
+            return Vec::new();
+        }
+
+        let ask = |book: &OrderBook| {
+            book.best_ask()
+                .map(|(p, _)| p.to_string().parse::<f64>().unwrap_or(1.0))
+        };
+        let candidates = [
+            (Side::Yes, &market.yes_token_id, fair_up, ask(yes_book)),
+            (Side::No, &market.no_token_id, 1.0 - fair_up, ask(no_book)),
+        ];
+
+        let min_edge = required_edge.apply(self.config.open_sniper_min_edge);
+        let best = candidates
+            .into_iter()
+            .filter_map(|(side, token, fair, ask)| {
+                let ask = ask?;
+                let edge = fair - ask;
+                ((MIN_ASK..=MAX_ASK).contains(&ask) && edge >= min_edge)
+                    .then_some((side, token, fair, ask, edge))
+            })
+            .max_by(|a, b| a.4.partial_cmp(&b.4).unwrap_or(std::cmp::Ordering::Equal));
+
+        let Some((side, token_id, fair, ask, edge)) = best else {
+            return Vec::new();
+        };
+
+        let shares = (budget / ask * 100.0).floor() / 100.0;
+        let min_shares = MIN_ORDER_SHARES.to_string().parse::<f64>().unwrap_or(5.0);
+        if shares < min_shares {
+            debug!(
+                "OPEN SNIPER: {} ${budget:.2} buys {shares:.2} shares at {ask:.3}, under the {min_shares} minimum",
+                market.slug
+            );
+            return Vec::new();
+        }
+
+        info!(
+            "OPEN SNIPER: market={} buy {side:?}@{ask:.3} fair={fair:.3} edge={edge:.3} size={shares:.2} ({:.1}s in)",
+            market.slug,
+            market.time_elapsed_secs(),
+        );
+
+        vec![OrderIntent {
+            token_id: token_id.clone(),
+            market_side: side,
+            order_side: OrderSide::Buy,
+            price: Decimal::from_f64_retain(ask).unwrap_or(Decimal::ZERO),
+            size: Decimal::from_f64_retain(shares).unwrap_or(Decimal::ZERO),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "open_sniper".into(),
+            group_id: None,
+        }]
+    }
+
+    /// The exchange accepted this window's entry: no more until the next one.
+    pub fn mark_fired(&self, market: &Market) {
+        self.fired.insert(market.id.clone(), market.close_time);
+    }
+
+    /// Forget windows that have closed.
+    fn prune_fired(&self) {
+        let now = Utc::now();
+        self.fired.retain(|_, close| *close > now);
+    }
+}
@@ -1,42 +1,337 @@
-use crate::config::StrategyConfig;
-use crate::models::market::{Asset, Duration, LifecyclePhase, Market, OrderBook};
-use crate::models::order::OrderIntent;
+use crate::config::{JoinKind, StrategyConfig};
+use crate::feeds::lifecycle::{MarketEvent, MarketEventKind};
+use crate::models::market::{Asset, LifecyclePhase, Market, OrderBook};
+use crate::models::order::{LegSet, OrderIntent};
+use crate::models::position::{Position, MID_CYCLE_TAG};
 use crate::models::signal::{ArbSignal, BiasSignal, MomentumSignal, VolRegime};
 use crate::signals::arb_scanner::ArbScanner;
+use crate::signals::basis::BasisTracker;
+use crate::signals::competition::CompetitionDetector;
+use crate::signals::fair_value::{FairValueEnsemble, FairValueEstimate};
+use crate::signals::half_life::{HoldLimits, OpportunityHalfLife};
+use crate::signals::ml_filter::MlFilter;
+use crate::signals::projection::PriceProjector;
+use crate::signals::seasonality::Seasonality;
+use crate::strategies::allocator::MarketAllocator;
+use crate::strategies::conflict::ConflictResolver;
+use crate::strategies::depth_sizing::DepthSizer;
+use crate::strategies::edge::{EdgePolicy, RequiredEdge};
+use crate::strategies::fill_quality::FillQualityController;
+use crate::strategies::screen::{MarketScreen, ScreenVerdict};
+use crate::strategies::spread_control::SpreadController;
 use crate::strategies::lag_exploit::LagExploitEngine;
+use crate::strategies::late_gamma::LateGammaEngine;
 use crate::strategies::market_maker::MarketMakerEngine;
 use crate::strategies::momentum_capture::MomentumCaptureEngine;
-use crate::strategies::pure_arb::PureArbEngine;
-use crate::strategies::straddle_bias::StraddleBiasEngine;
+use crate::strategies::open_sniper::OpenSniperEngine;
+use crate::strategies::pure_arb::{self, PureArbEngine};
+use crate::strategies::straddle_bias::{self, StraddleBiasEngine};
+use crate::telemetry::latency::LatencyTracker;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Strategies whose orders are legs of one trade (see `IntentGroup::collect`).
+pub const LEG_SETS: &[LegSet] = &[pure_arb::LEGS, straddle_bias::LEGS];
 
 /// Orchestrates all sub-strategies for a given market cycle.
 ///
 /// Decides which strategies run based on:
 ///   - Volatility regime
 ///   - Market lifecycle phase
-///   - Capital tier
+///   - Capital tier (per-market share from the allocator)
+///   - When we joined the market (fresh open vs mid-window)
 ///   - Available signals
+///   - Competitor presence
 pub struct StrategyOrchestrator {
     straddle: StraddleBiasEngine,
     arb: PureArbEngine,
     lag: LagExploitEngine,
     mm: MarketMakerEngine,
     momentum: MomentumCaptureEngine,
+    late_gamma: LateGammaEngine,
+    open_sniper: OpenSniperEngine,
+    competition: Arc<CompetitionDetector>,
+    allocator: Arc<MarketAllocator>,
+    /// Shrinks strategies whose fills keep getting adversely selected
+    fill_quality: Arc<FillQualityController>,
+    /// Widens MM spreads in series where fills get picked off
+    spread_control: Arc<SpreadController>,
+    /// Caps taker orders to the liquidity they can reach
+    depth_sizer: DepthSizer,
+    screen: MarketScreen,
+    /// Optional model veto on candidate entries
+    ml_filter: Option<Arc<MlFilter>>,
+    fair_value: FairValueEnsemble,
+    /// Latest second-venue price per asset, for the ensemble
+    cross_prices: DashMap<Asset, (f64, chrono::DateTime<chrono::Utc>)>,
+    /// Latest ensemble estimate per market slug
+    fair_values: DashMap<String, FairValueEstimate>,
+    /// Carries the Binance price forward over our latency for lag entries
+    projector: PriceProjector,
+    /// Rolling perp-spot basis; sharp moves lean straddle bias and gate momentum
+    basis: BasisTracker,
+    /// Observed mispricing lifetimes; set lag edge and max hold
+    half_life: Arc<OpportunityHalfLife>,
+    /// Lag entry cut-off and max hold per market slug, as of its last
+    /// evaluation, with the market's close time for pruning
+    hold_limits: DashMap<String, (HoldLimits, chrono::DateTime<chrono::Utc>)>,
+    /// Widens entry thresholds by uncertainty, book age and calibration error
+    edge: Arc<EdgePolicy>,
+    seasonality: Arc<Seasonality>,
     config: StrategyConfig,
+    /// Operator pause: no new intents while set (open orders untouched)
+    paused: AtomicBool,
+    /// Per-strategy evaluation durations ("strategy.<name>")
+    latency: Arc<LatencyTracker>,
+    /// Strategies sitting out after repeated budget overruns, until the given time
+    benched: DashMap<StrategyId, Instant>,
+    overruns: DashMap<StrategyId, u64>,
+    /// Overruns in a row per strategy; an evaluation within budget resets it
+    overrun_streaks: DashMap<StrategyId, u32>,
 }
 
 impl StrategyOrchestrator {
     pub fn new(config: StrategyConfig) -> Self {
+        Self::with_seasonality(config, Arc::new(Seasonality::default()))
+    }
+
+    /// Orchestrator whose fair values and baseline sizes follow the
+    /// hour-of-day vol curves in `seasonality`.
+    pub fn with_seasonality(config: StrategyConfig, seasonality: Arc<Seasonality>) -> Self {
+        let half_life = Arc::new(OpportunityHalfLife::new(config.half_life.clone()));
         Self {
             straddle: StraddleBiasEngine::new(config.clone()),
             arb: PureArbEngine::new(config.clone()),
-            lag: LagExploitEngine::new(config.clone()),
-            mm: MarketMakerEngine::new(config.clone()),
+            lag: LagExploitEngine::new(config.clone())
+                .with_seasonality(seasonality.clone())
+                .with_half_life(half_life.clone()),
+            mm: MarketMakerEngine::new(config.clone()).with_seasonality(seasonality.clone()),
             momentum: MomentumCaptureEngine::new(config.clone()),
+            late_gamma: LateGammaEngine::new(config.clone()).with_seasonality(seasonality.clone()),
+            open_sniper: OpenSniperEngine::new(config.clone()),
+            fair_value: FairValueEnsemble::new(config.fair_value.clone(), seasonality.clone()),
+            edge: Arc::new(EdgePolicy::new(config.edge.clone())),
+            seasonality,
+            competition: Arc::new(CompetitionDetector::new()),
+            allocator: Arc::new(MarketAllocator::new(config.capital_allocation.clone())),
+            fill_quality: Arc::new(FillQualityController::new(config.fill_quality.clone())),
+            spread_control: Arc::new(SpreadController::new(config.spread_control.clone())),
+            depth_sizer: DepthSizer::new(config.depth_sizing.clone()),
+            screen: MarketScreen::new(config.screen.clone()),
+            ml_filter: None,
+            cross_prices: DashMap::new(),
+            fair_values: DashMap::new(),
+            projector: PriceProjector::new(config.projection.clone()),
+            basis: BasisTracker::new(config.basis.clone()),
+            half_life,
+            hold_limits: DashMap::new(),
             config,
+            paused: AtomicBool::new(false),
+            latency: Arc::new(LatencyTracker::new(1000)),
+            benched: DashMap::new(),
+            overrun_streaks: DashMap::new(),
+            overruns: DashMap::new(),
         }
     }
 
+    /// Score candidate entries with `filter` before they leave `evaluate`.
+    pub fn with_ml_filter(mut self, filter: MlFilter) -> Self {
+        self.ml_filter = Some(Arc::new(filter));
+        self
+    }
+
+    pub fn ml_filter(&self) -> Option<Arc<MlFilter>> {
+        self.ml_filter.clone()
+    }
+
+    /// A second venue's spot for `asset`, blended into fair values.
+    pub fn observe_cross_exchange(&self, asset: Asset, price: f64, at: chrono::DateTime<chrono::Utc>) {
+        self.cross_prices.insert(asset, (price, at));
+    }
+
+    /// Every Binance print, so lag entries can project the price forward.
+    pub fn observe_price(&self, asset: Asset, price: f64, at: chrono::DateTime<chrono::Utc>) {
+        self.projector.observe(asset, price, at);
+        self.basis.observe_perp(asset, price, at);
+    }
+
+    /// Every Binance spot print, for the perp-spot basis.
+    pub fn observe_spot(&self, asset: Asset, price: f64, at: chrono::DateTime<chrono::Utc>) {
+        self.basis.observe_spot(asset, price, at);
+    }
+
+    /// Ensemble fair value from the market's latest evaluation.
+    pub fn fair_value(&self, slug: &str) -> Option<FairValueEstimate> {
+        self.fair_values.get(slug).map(|e| e.clone())
+    }
+
+    /// Feed resolved outcomes here so the calibration term stays current.
+    pub fn edge_policy(&self) -> Arc<EdgePolicy> {
+        self.edge.clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Flip the pause flag; returns the new state.
+    pub fn toggle_pause(&self) -> bool {
+        !self.paused.fetch_xor(true, Ordering::Relaxed)
+    }
+
+    /// Shared competitor detector, fed by the execution loop.
+    pub fn competition(&self) -> Arc<CompetitionDetector> {
+        self.competition.clone()
+    }
+
+    /// Shared per-market capital allocator.
+    pub fn allocator(&self) -> Arc<MarketAllocator> {
+        self.allocator.clone()
+    }
+
+    /// Hour-of-day vol and size factors.
+    pub fn seasonality(&self) -> Arc<Seasonality> {
+        self.seasonality.clone()
+    }
+
+    pub fn screen(&self) -> &MarketScreen {
+        &self.screen
+    }
+
+    /// Shared MM spread controller, fed market-making fills by the execution loop.
+    pub fn spread_control(&self) -> Arc<SpreadController> {
+        self.spread_control.clone()
+    }
+
+    pub fn fill_quality(&self) -> Arc<FillQualityController> {
+        self.fill_quality.clone()
+    }
+
+    /// Shared opportunity half-life table, fed observer journal lines.
+    pub fn half_life(&self) -> Arc<OpportunityHalfLife> {
+        self.half_life.clone()
+    }
+
+    /// Lag entry cut-off and max hold set by the market's last evaluation;
+    /// None until its half-life is known.
+    pub fn hold_limits(&self, slug: &str) -> Option<HoldLimits> {
+        self.hold_limits.get(slug).map(|l| l.0)
+    }
+
+    /// Sells for lag positions held past their market's half-life max hold.
+    /// Runs whether or not the market is being evaluated for entries; with
+    /// half-life adaptation off, or before the market's half-life is known,
+    /// there is no max hold and nothing is sold.
+    pub fn lag_exits(
+        &self,
+        market: &Market,
+        positions: &[Position],
+        yes_book: Option<&OrderBook>,
+        no_book: Option<&OrderBook>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<OrderIntent> {
+        if !self.config.half_life.enabled {
+            return Vec::new();
+        }
+        let Some(limits) = self.hold_limits(&market.slug) else {
+            return Vec::new();
+        };
+        self.lag.max_hold_exits(market, positions, yes_book, no_book, limits.max_hold_secs, now)
+    }
+
+    /// The exchange accepted `intent` on `market`. The opening sniper's one
+    /// entry per window is only spent here, not when it's proposed.
+    pub fn on_order_accepted(&self, market: &Market, intent: &OrderIntent) {
+        if intent.strategy_tag == "open_sniper" {
+            self.open_sniper.mark_fired(market);
+        }
+    }
+
+    /// React to a market milestone: a closed market's per-slug state
+    /// (fair value, lag hold limits) is dropped.
+    pub fn on_market_event(&self, event: &MarketEvent) {
+        if event.kind == MarketEventKind::Closed {
+            self.fair_values.remove(&event.slug);
+            self.hold_limits.remove(&event.slug);
+        }
+    }
+
+    /// Per-strategy evaluation timings.
+    pub fn strategy_latency(&self) -> Arc<LatencyTracker> {
+        self.latency.clone()
+    }
+
+    /// Strategies currently skipped after overrunning their budget.
+    pub fn benched_strategies(&self) -> Vec<&'static str> {
+        let now = Instant::now();
+        let mut names: Vec<&'static str> =
+            self.benched.iter().filter(|b| *b.value() > now).map(|b| b.key().name()).collect();
+        names.sort();
+        names
+    }
+
+    /// Budget overruns since startup for a strategy (by `name`).
+    pub fn overrun_count(&self, name: &str) -> u64 {
+        self.overruns.iter().filter(|o| o.key().name() == name).map(|o| *o.value()).sum()
+    }
+
+    /// Run one strategy under the evaluation budget. Strategies are synchronous,
+    /// so an overrun can't be cut short — instead its intents (priced off a
+    /// book that has since moved on) are dropped. A one-off stall (a GC-like
+    /// pause, a context switch) costs only that evaluation; a strategy that
+    /// overruns `eval_overrun_streak` times in a row is benched for
+    /// `eval_overrun_bench_secs` so it can't keep stalling the loop.
+    fn run_budgeted(&self, id: StrategyId, eval: impl FnOnce() -> Vec<OrderIntent>) -> Vec<OrderIntent> {
+        if let Some(until) = self.benched.get(&id).map(|u| *u) {
+            if Instant::now() < until {
+                return Vec::new();
+            }
+            self.benched.remove(&id);
+            info!("Strategy {} back from the bench", id.name());
+        }
+
+        let start = Instant::now();
+        let orders = eval();
+        let elapsed = start.elapsed();
+        self.latency.record(id.metric(), elapsed);
+
+        let budget = Duration::from_secs_f64(self.config.eval_budget_ms.max(0.0) / 1000.0);
+        if elapsed <= budget {
+            self.overrun_streaks.remove(&id);
+            return orders;
+        }
+
+        *self.overruns.entry(id).or_insert(0) += 1;
+        let streak = {
+            let mut streak = self.overrun_streaks.entry(id).or_insert(0);
+            *streak += 1;
+            *streak
+        };
+        let took = format!(
+            "Strategy {} took {:.1}ms (budget {:.1}ms, {streak} in a row) — dropped {} intents",
+            id.name(),
+            elapsed.as_secs_f64() * 1000.0,
+            self.config.eval_budget_ms,
+            orders.len(),
+        );
+        if streak < self.config.eval_overrun_streak {
+            debug!("{took}");
+            return Vec::new();
+        }
+        self.overrun_streaks.remove(&id);
+        let bench = Duration::from_secs(self.config.eval_overrun_bench_secs);
+        self.benched.insert(id, Instant::now() + bench);
+        warn!("{took}, benched {}s", bench.as_secs());
+        Vec::new()
+    }
+
     /// Run all eligible strategies for a market and collect order intents.
     #[allow(clippy::too_many_arguments)]
     pub fn evaluate(
@@ -58,11 +353,83 @@ impl StrategyOrchestrator {
         let mut all_orders: Vec<OrderIntent> = Vec::new();
         let phase = market.lifecycle_phase();
 
-        if matches!(phase, LifecyclePhase::Lockout | LifecyclePhase::Resolved) {
-            return all_orders;
+        if phase == LifecyclePhase::Resolved || self.is_paused() {
+            return self.observe_skipped(market, yes_book, no_book);
+        }
+
+        // Joined too late to trust the window — sit it out
+        let join = self.config.join_policy.classify(market);
+        if join == JoinKind::Skip {
+            return self.observe_skipped(market, yes_book, no_book);
         }
 
-        let capital_for_market = self.capital_for_market(market, available_capital);
+        // Untradeable books are skipped here, once, rather than by each strategy.
+        // A wide spread is what market making is paid for, so it still quotes one.
+        let screened_at = chrono::Utc::now();
+        let fill_success = self
+            .competition
+            .fill_success_rate(market.asset, market.duration, screened_at.timestamp_millis());
+        let quotes_only = match self.screen.check(&market.slug, yes_book, no_book, fill_success, screened_at) {
+            Ok(()) => false,
+            Err(ScreenVerdict::WideSpread { .. }) if phase != LifecyclePhase::Lockout => {
+                debug!("{}: wide spread, market making only", market.slug);
+                true
+            }
+            Err(verdict) => {
+                debug!("Skipping {}: {verdict}", market.slug);
+                return self.observe_skipped(market, yes_book, no_book);
+            }
+        };
+
+        let now = chrono::Utc::now();
+        let cross = self.cross_prices.get(&market.asset).map(|p| *p);
+        let (fair_up, uncertainty) = match self.fair_value.estimate(market, yes_book, binance_price, cross, now) {
+            Some(estimate) => {
+                self.edge.observe_prediction(&market.slug, estimate.prob_up, now);
+                let fair = (Some(estimate.prob_up), Some(estimate.uncertainty));
+                self.fair_values.insert(market.slug.clone(), estimate);
+                fair
+            }
+            None => {
+                self.fair_values.remove(&market.slug);
+                (None, None)
+            }
+        };
+        let book_age_secs = [yes_book, no_book]
+            .iter()
+            .map(|b| (now - b.timestamp).num_milliseconds() as f64 / 1000.0)
+            .fold(0.0, f64::max);
+        let required_edge = self.edge.required(uncertainty, book_age_secs);
+        if required_edge != RequiredEdge::NONE {
+            debug!("{}: entry edge +{:.3} ({:?})", market.slug, required_edge.extra(), required_edge);
+        }
+
+        // Markets where other bots consistently beat us to the edge get less capital,
+        // as do windows we joined part-way through
+        let capital_for_market = self.capital_for_market(market, available_capital)
+            * self
+                .competition
+                .capital_multiplier(market.asset, market.duration, screened_at.timestamp_millis())
+            * self.config.join_policy.size_mult(join);
+
+        // Lockout blocks every strategy except late-window gamma scalping,
+        // which is exempt and bounded by its own exposure caps
+        if phase == LifecyclePhase::Lockout {
+            if self.config.late_gamma_enabled {
+                all_orders.extend(self.run_budgeted(StrategyId::LateGamma, || {
+                    self.late_gamma.evaluate(
+                        market,
+                        yes_book,
+                        no_book,
+                        binance_price,
+                        vol_regime,
+                        capital_for_market,
+                        required_edge,
+                    )
+                }));
+            }
+            return self.finish(market, yes_book, no_book, binance_price, join, all_orders);
+        }
 
         // Pre-compute arb signal if not provided externally
         let computed_arb = if arb_signal.is_none() {
@@ -77,8 +444,43 @@ impl StrategyOrchestrator {
         };
         let effective_arb = arb_signal.or(computed_arb.as_ref());
 
+        // Opening quotes are the most perishable edge we have, so the sniper
+        // gets first call on capital while its window is open
+        if !quotes_only && self.config.open_sniper_enabled && self.open_sniper.is_active(market) {
+            all_orders.extend(self.run_budgeted(StrategyId::OpenSniper, || {
+                self.open_sniper.evaluate(
+                    market,
+                    yes_book,
+                    no_book,
+                    fair_up,
+                    book_age_secs,
+                    capital_for_market,
+                    required_edge,
+                )
+            }));
+        }
+
+        // Lag timing from this market's half-life under the current regime
+        let limits = self.half_life.limits(market, vol_regime, now);
+        if let Some(l) = limits {
+            self.hold_limits.insert(market.slug.clone(), (l, market.close_time));
+        } else {
+            self.hold_limits.remove(&market.slug);
+        }
+        // Windows that rolled over without a close event go too
+        self.hold_limits.retain(|_, (_, close)| *close > now);
+
         // Strategy priority order depends on vol regime and phase
-        let priority = self.strategy_priority(vol_regime, &phase);
+        let mut priority = self.strategy_priority(vol_regime, &phase);
+        if quotes_only {
+            priority.retain(|s| *s == StrategyId::MarketMaking);
+        }
+
+        // In contested markets, speed-sensitive taker strategies lose the race
+        // more often than not — run them last.
+        if self.competition.is_contested(market.asset, market.duration, screened_at.timestamp_millis()) {
+            priority.sort_by_key(|s| matches!(s, StrategyId::LagExploit | StrategyId::Momentum));
+        }
 
         for strategy in &priority {
             // Don't exceed capital allocation
@@ -90,27 +492,27 @@ impl StrategyOrchestrator {
             match strategy {
                 StrategyId::StraddleBias => {
                     if self.config.straddle_enabled {
-                        let orders = self.straddle.evaluate(
-                            market,
-                            yes_book,
-                            no_book,
-                            effective_arb,
-                            bias_signal,
-                            vol_regime,
-                            remaining_capital,
-                        );
+                        let bias = bias_signal.map(|b| self.basis.adjust_bias(market.asset, b));
+                        let orders = self.run_budgeted(*strategy, || {
+                            self.straddle.evaluate(
+                                market,
+                                yes_book,
+                                no_book,
+                                effective_arb,
+                                bias.as_ref(),
+                                vol_regime,
+                                remaining_capital,
+                                net_yes_inventory,
+                            )
+                        });
                         all_orders.extend(orders);
                     }
                 }
                 StrategyId::PureArb => {
                     if self.config.arb_enabled {
-                        let orders = self.arb.evaluate(
-                            market,
-                            yes_book,
-                            no_book,
-                            vol_regime,
-                            remaining_capital,
-                        );
+                        let orders = self.run_budgeted(*strategy, || {
+                            self.arb.evaluate(market, yes_book, no_book, vol_regime, remaining_capital)
+                        });
                         all_orders.extend(orders);
                     }
                 }
@@ -119,55 +521,148 @@ impl StrategyOrchestrator {
                         let momentum_adj = bias_signal
                             .map(|b| b.momentum_score * 0.05)
                             .unwrap_or(0.0);
-                        let orders = self.lag.evaluate(
-                            market,
-                            yes_book,
-                            no_book,
-                            binance_price,
-                            vol_regime,
-                            remaining_capital,
-                            momentum_adj,
-                        );
+                        let projected = self.projector.project(market.asset, binance_price, now);
+                        let orders = self.run_budgeted(*strategy, || {
+                            self.lag.evaluate(
+                                market,
+                                yes_book,
+                                no_book,
+                                projected,
+                                vol_regime,
+                                remaining_capital,
+                                momentum_adj,
+                                required_edge,
+                                limits,
+                            )
+                        });
                         all_orders.extend(orders);
                     }
                 }
                 StrategyId::MarketMaking => {
                     if self.config.market_making_enabled {
-                        let orders = self.mm.evaluate(
-                            market,
-                            yes_book,
-                            binance_price,
-                            vol_regime,
-                            remaining_capital,
-                            net_yes_inventory,
-                            binance_1s_move_pct,
-                            order_flow_imbalance,
-                            liquidation_active,
-                        );
+                        let orders = self.run_budgeted(*strategy, || {
+                            self.mm.evaluate(
+                                market,
+                                yes_book,
+                                binance_price,
+                                vol_regime,
+                                remaining_capital,
+                                net_yes_inventory,
+                                binance_1s_move_pct,
+                                order_flow_imbalance,
+                                liquidation_active,
+                                self.spread_control.spread_mult(market.asset, market.duration),
+                            )
+                        });
                         all_orders.extend(orders);
                     }
                 }
                 StrategyId::Momentum => {
                     if self.config.momentum_enabled {
                         if let Some(sig) = momentum_signal {
-                            let orders = self.momentum.evaluate(
-                                market,
-                                yes_book,
-                                no_book,
-                                sig,
-                                vol_regime,
-                                remaining_capital,
-                            );
+                            if self.basis.opposes(market.asset, sig.direction()) {
+                                debug!("MOMENTUM: {} skipped against a sharp basis move", market.slug);
+                                continue;
+                            }
+                            let orders = self.run_budgeted(*strategy, || {
+                                self.momentum.evaluate(
+                                    market,
+                                    yes_book,
+                                    no_book,
+                                    sig,
+                                    vol_regime,
+                                    remaining_capital,
+                                )
+                            });
                             all_orders.extend(orders);
                         }
                     }
                 }
+                // Run outside the priority order
+                StrategyId::LateGamma | StrategyId::OpenSniper => {}
             }
         }
 
+        if !quotes_only && self.config.late_gamma_enabled && self.late_gamma.is_active(market) {
+            let remaining_capital = capital_for_market - self.total_order_cost(&all_orders);
+            all_orders.extend(self.run_budgeted(StrategyId::LateGamma, || {
+                self.late_gamma.evaluate(
+                    market,
+                    yes_book,
+                    no_book,
+                    binance_price,
+                    vol_regime,
+                    remaining_capital,
+                    required_edge,
+                )
+            }));
+        }
+
+        self.finish(market, yes_book, no_book, binance_price, join, all_orders)
+    }
+
+    /// Filters, sizing and the per-market cap every evaluation's orders go
+    /// through, lockout's late-gamma orders included.
+    fn finish(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        binance_price: f64,
+        join: JoinKind,
+        mut all_orders: Vec<OrderIntent>,
+    ) -> Vec<OrderIntent> {
+        self.fill_quality.apply(&mut all_orders);
+        self.depth_sizer.apply(yes_book, no_book, &mut all_orders);
+        if let Some(filter) = &self.ml_filter {
+            filter.apply(market, yes_book, no_book, binance_price, LEG_SETS, &mut all_orders);
+        }
+
+        // Strategies run independently; reconcile what they emitted together
+        let mut all_orders = ConflictResolver::new(self.config.max_market_notional_per_eval).resolve(all_orders);
+        self.tag_join(join, &mut all_orders);
+
+        self.allocator.observe(
+            market.asset,
+            market.duration,
+            yes_book,
+            no_book,
+            !all_orders.is_empty(),
+        );
+
         all_orders
     }
 
+    /// Record an evaluation that ended before any strategy ran. The allocator
+    /// only leaves its static split once every market has warmed up, so a
+    /// market that is skipped for a long stretch must still be counted.
+    fn observe_skipped(&self, market: &Market, yes_book: &OrderBook, no_book: &OrderBook) -> Vec<OrderIntent> {
+        self.allocator.observe(market.asset, market.duration, yes_book, no_book, false);
+        Vec::new()
+    }
+
+    /// Opening orders for `next`, a window that hasn't opened yet, from the
+    /// enabled pre-position capable strategies.
+    pub fn opening_orders(&self, next: &Market, vol_regime: VolRegime, available_capital: f64) -> Vec<OrderIntent> {
+        if self.is_paused() {
+            return Vec::new();
+        }
+        let capital = self.capital_for_market(next, available_capital)
+            * self.competition.capital_multiplier(next.asset, next.duration, chrono::Utc::now().timestamp_millis());
+        let mut orders = Vec::new();
+        for strategy in self.strategy_priority(vol_regime, &LifecyclePhase::AlphaWindow) {
+            if !strategy.pre_position_capable() {
+                continue;
+            }
+            if strategy == StrategyId::MarketMaking && self.config.market_making_enabled {
+                let spread_mult = self.spread_control.spread_mult(next.asset, next.duration);
+                orders.extend(self.mm.opening_quotes(next, vol_regime, capital, spread_mult));
+            }
+        }
+        self.fill_quality.apply(&mut orders);
+        orders
+    }
+
     /// Determine strategy execution priority based on conditions.
     fn strategy_priority(&self, vol_regime: VolRegime, _phase: &LifecyclePhase) -> Vec<StrategyId> {
         match vol_regime {
@@ -204,17 +699,18 @@ impl StrategyOrchestrator {
 
     /// Calculate capital allocation for a specific market type.
     fn capital_for_market(&self, market: &Market, total_capital: f64) -> f64 {
-        let alloc = &self.config.capital_allocation;
-        let pct = match (market.asset, market.duration) {
-            (Asset::BTC, Duration::FiveMin) => alloc.btc_5m_pct,
-            (Asset::BTC, Duration::FifteenMin) => alloc.btc_15m_pct,
-            (Asset::ETH, Duration::FifteenMin) => alloc.eth_15m_pct,
-            (Asset::SOL, Duration::FifteenMin) => alloc.sol_15m_pct,
-            (Asset::XRP, Duration::FifteenMin) => alloc.xrp_15m_pct,
-            // 5-min markets for non-BTC assets (future expansion)
-            _ => 0.05,
-        };
-        total_capital * pct
+        total_capital
+            * self.allocator.fraction(market.asset, market.duration)
+            * self.seasonality.size_factor(market.asset, chrono::Utc::now())
+    }
+
+    /// Mark orders from a mid-cycle join so their results can be compared.
+    fn tag_join(&self, join: JoinKind, orders: &mut [OrderIntent]) {
+        if join == JoinKind::MidCycle {
+            for order in orders {
+                order.strategy_tag.push_str(MID_CYCLE_TAG);
+            }
+        }
     }
 
     /// Estimate total cost of pending orders (for capital budgeting).
@@ -230,11 +726,100 @@ impl StrategyOrchestrator {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum StrategyId {
     StraddleBias,
     PureArb,
     LagExploit,
     MarketMaking,
     Momentum,
+    LateGamma,
+    OpenSniper,
+}
+
+impl StrategyId {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::StraddleBias => "straddle",
+            Self::PureArb => "arb",
+            Self::LagExploit => "lag",
+            Self::MarketMaking => "mm",
+            Self::Momentum => "momentum",
+            Self::LateGamma => "late_gamma",
+            Self::OpenSniper => "open_sniper",
+        }
+    }
+
+    /// Whether the strategy can price a window's opening orders before it
+    /// opens: it needs neither the window's book nor its opening price.
+    fn pre_position_capable(&self) -> bool {
+        matches!(self, Self::MarketMaking)
+    }
+
+    /// LatencyTracker operation name
+    fn metric(&self) -> &'static str {
+        match self {
+            Self::StraddleBias => "strategy.straddle",
+            Self::PureArb => "strategy.arb",
+            Self::LagExploit => "strategy.lag",
+            Self::MarketMaking => "strategy.mm",
+            Self::Momentum => "strategy.momentum",
+            Self::LateGamma => "strategy.late_gamma",
+            Self::OpenSniper => "strategy.open_sniper",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Duration as MarketDuration, Side};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_lag_exits_need_hold_limits() {
+        let config = StrategyConfig { lag_exploit_enabled: false, ..StrategyConfig::default() };
+        let max_hold = 60.0;
+        let orch = StrategyOrchestrator::new(config.clone());
+        let market = Market::new("btc-5m".into(), Asset::BTC, MarketDuration::FiveMin, "yes".into(), "no".into());
+        let mut yes_book = OrderBook::new("yes".into());
+        yes_book.bids.insert(dec!(0.55), dec!(50));
+        let now = chrono::Utc::now();
+        let held = |age: f64| Position {
+            market_id: market.slug.clone(),
+            token_id: "yes".into(),
+            side: Side::Yes,
+            size: dec!(10),
+            avg_entry_price: dec!(0.50),
+            unrealized_pnl: Decimal::ZERO,
+            strategy_tag: "lag_exploit".into(),
+            opened_at: now - chrono::Duration::milliseconds((age * 1000.0) as i64),
+            adds: 0,
+            entry_fees: Decimal::ZERO,
+        };
+
+        // No half-life for this market yet: no max hold to cut at
+        assert!(orch.hold_limits(&market.slug).is_none());
+        assert!(orch.lag_exits(&market, &[held(max_hold + 5.0)], Some(&yes_book), None, now).is_empty());
+
+        // Once known, lag positions are cut at it even with lag entries off
+        let limits = HoldLimits { min_remaining_secs: 30.0, max_hold_secs: max_hold };
+        orch.hold_limits.insert(market.slug.clone(), (limits, market.close_time));
+        let young = orch.lag_exits(&market, &[held(max_hold - 5.0)], Some(&yes_book), None, now);
+        assert!(young.is_empty());
+        let exits = orch.lag_exits(&market, &[held(max_hold + 5.0)], Some(&yes_book), None, now);
+        assert_eq!(exits.len(), 1);
+        assert_eq!((exits[0].price, exits[0].size), (dec!(0.55), dec!(10)));
+
+        // No book on the position's side: nothing to price against yet
+        assert!(orch.lag_exits(&market, &[held(max_hold + 5.0)], None, Some(&yes_book), now).is_empty());
+
+        // Half-life adaptation off: no max hold at all
+        let mut off = config;
+        off.half_life.enabled = false;
+        let orch = StrategyOrchestrator::new(off);
+        orch.hold_limits.insert(market.slug.clone(), (limits, market.close_time));
+        assert!(orch.lag_exits(&market, &[held(max_hold + 5.0)], Some(&yes_book), None, now).is_empty());
+    }
 }
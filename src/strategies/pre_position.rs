@@ -0,0 +1,109 @@
+use crate::models::market::Market;
+use crate::models::order::OrderIntent;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+struct Queued {
+    closes_at: DateTime<Utc>,
+    orders: Vec<OrderIntent>,
+}
+
+/// Opening orders for windows that haven't opened yet.
+///
+/// In the last `PRE_POSITION_LEAD_SECS` of a window, pre-position capable
+/// strategies price the next window's opening orders (see
+/// `StrategyOrchestrator::opening_orders`) and they wait here until the
+/// lifecycle bus reports the window open, when they're submitted at once
+/// instead of on the first evaluation after it. Only post-only orders are
+/// queued: an opening order rests, it never takes.
+pub struct OpeningQueue {
+    /// Keyed by slug; an empty plan still marks the window as planned
+    queued: Mutex<HashMap<String, Queued>>,
+}
+
+impl OpeningQueue {
+    pub fn new() -> Self {
+        Self { queued: Mutex::new(HashMap::new()) }
+    }
+
+    /// Whether `slug`'s opening orders have been planned already.
+    pub fn is_planned(&self, slug: &str) -> bool {
+        self.queued.lock().unwrap().contains_key(slug)
+    }
+
+    /// Plan `market`'s opening orders; a window is planned once.
+    pub fn queue(&self, market: &Market, orders: Vec<OrderIntent>, now: DateTime<Utc>) {
+        let mut queued = self.queued.lock().unwrap();
+        queued.retain(|_, q| q.closes_at > now);
+        if queued.contains_key(&market.slug) {
+            return;
+        }
+        let (orders, dropped): (Vec<_>, Vec<_>) = orders.into_iter().partition(|o| o.post_only);
+        if !dropped.is_empty() {
+            debug!("Pre-position {}: {} taking orders not queued", market.slug, dropped.len());
+        }
+        if !orders.is_empty() {
+            info!("Pre-position {}: {} opening orders queued", market.slug, orders.len());
+        }
+        queued.insert(market.slug.clone(), Queued { closes_at: market.close_time, orders });
+    }
+
+    /// `slug`'s opening orders, removed from the queue.
+    pub fn take(&self, slug: &str) -> Vec<OrderIntent> {
+        self.queued.lock().unwrap().remove(slug).map(|q| q.orders).unwrap_or_default()
+    }
+}
+
+impl Default for OpeningQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration, Side};
+    use crate::models::order::{OrderSide, OrderType};
+    use rust_decimal_macros::dec;
+
+    fn order(post_only: bool) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price: dec!(0.48),
+            size: dec!(5),
+            order_type: if post_only { OrderType::GTC } else { OrderType::FAK },
+            post_only,
+            expiration: None,
+            strategy_tag: "mm_bid".into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_planned_once_and_taken_at_open() {
+        let queue = OpeningQueue::new();
+        let next = Market::new("btc-updown-5m-2".into(), Asset::BTC, Duration::FiveMin, "y".into(), "n".into());
+        let now = next.close_time - chrono::Duration::seconds(400);
+
+        assert!(!queue.is_planned(&next.slug));
+        queue.queue(&next, vec![order(true), order(false)], now);
+        assert!(queue.is_planned(&next.slug));
+        queue.queue(&next, vec![order(true), order(true)], now);
+
+        let taken = queue.take(&next.slug);
+        assert_eq!(taken.len(), 1, "planned once; taking orders dropped");
+        assert!(taken[0].post_only);
+        assert!(queue.take(&next.slug).is_empty());
+
+        // Plans for windows that closed unopened are dropped
+        queue.queue(&next, vec![order(true)], now);
+        let other = Market::new("eth-updown-15m-2".into(), Asset::ETH, Duration::FifteenMin, "y".into(), "n".into());
+        queue.queue(&other, Vec::new(), next.close_time);
+        assert!(!queue.is_planned(&next.slug) && queue.is_planned(&other.slug));
+    }
+}
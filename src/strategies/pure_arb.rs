@@ -1,11 +1,14 @@
 use crate::config::StrategyConfig;
 use crate::models::market::{LifecyclePhase, Market, OrderBook, Side};
-use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::models::order::{FailurePolicy, LegSet, OrderIntent, OrderSide, OrderType};
 use crate::models::signal::{ArbSignal, VolRegime};
 use crate::signals::arb_scanner::ArbScanner;
 use rust_decimal::Decimal;
 use tracing::{debug, info};
 
+/// The YES and NO buys are one trade: half an arb is a directional bet.
+pub const LEGS: LegSet = LegSet { tags: &["arb_yes", "arb_no"], policy: FailurePolicy::AllOrNothing };
+
 /// Pure YES+NO arbitrage engine.
 ///
 /// Detects when YES_ask + NO_ask < $1.00 and buys both sides
@@ -79,6 +82,8 @@ impl PureArbEngine {
             size * signal.edge
         );
 
+        // Both legs carry one id so a pair on another market stays its own trade
+        let group_id = Some(uuid::Uuid::new_v4().to_string());
         vec![
             OrderIntent {
                 token_id: market.yes_token_id.clone(),
@@ -90,6 +95,7 @@ impl PureArbEngine {
                 post_only: false,
                 expiration: None,
                 strategy_tag: "arb_yes".into(),
+                group_id: group_id.clone(),
             },
             OrderIntent {
                 token_id: market.no_token_id.clone(),
@@ -101,6 +107,7 @@ impl PureArbEngine {
                 post_only: false,
                 expiration: None,
                 strategy_tag: "arb_no".into(),
+                group_id,
             },
         ]
     }
@@ -0,0 +1,251 @@
+use crate::config::MarketScreenConfig;
+use crate::models::market::OrderBook;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use std::fmt;
+use tracing::info;
+
+/// While a market is screened out for poor fills, one evaluation is let
+/// through this often so fresh taker results can clear it (secs).
+const POOR_FILLS_PROBE_SECS: i64 = 120;
+
+/// Why a market was judged untradeable for this evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenVerdict {
+    /// Shares at the touch (best bid + best ask) on the thinner token
+    ThinBook { depth: f64 },
+    /// Takers only: market making is still run on a wide book
+    WideSpread { spread: f64 },
+    StaleBook { age_secs: f64 },
+    /// Share of our taker orders on this market type that got filled
+    PoorFills { success: f64 },
+}
+
+impl ScreenVerdict {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::ThinBook { .. } => "thin_book",
+            Self::WideSpread { .. } => "wide_spread",
+            Self::StaleBook { .. } => "stale_book",
+            Self::PoorFills { .. } => "poor_fills",
+        }
+    }
+}
+
+impl fmt::Display for ScreenVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ThinBook { depth } => write!(f, "thin book ({depth:.1} shares at the touch)"),
+            Self::WideSpread { spread } => write!(f, "wide spread ({spread:.2})"),
+            Self::StaleBook { age_secs } => write!(f, "stale book ({age_secs:.0}s old)"),
+            Self::PoorFills { success } => write!(f, "poor fill success ({:.0}%)", success * 100.0),
+        }
+    }
+}
+
+/// Cheap market-level "do not trade" screen run before any strategy, so an
+/// obviously untradeable book is skipped once instead of every strategy
+/// filtering it on its own.
+pub struct MarketScreen {
+    config: MarketScreenConfig,
+    skipped: DashMap<&'static str, u64>,
+    /// Last poor-fills probe by market slug
+    probes: DashMap<String, DateTime<Utc>>,
+}
+
+impl MarketScreen {
+    pub fn new(config: MarketScreenConfig) -> Self {
+        Self {
+            config,
+            skipped: DashMap::new(),
+            probes: DashMap::new(),
+        }
+    }
+
+    /// `fill_success` is the recent taker fill rate for the market type,
+    /// None until there are enough samples.
+    pub fn check(
+        &self,
+        slug: &str,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        fill_success: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ScreenVerdict> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let mut verdict = self.verdict(yes_book, no_book, fill_success, now);
+        if matches!(verdict, Some(ScreenVerdict::PoorFills { .. })) {
+            // A skipped market places no orders and so never earns new samples
+            if self.probe_due(slug, now) {
+                verdict = None;
+            }
+        } else {
+            self.probes.remove(slug);
+        }
+        if let Some(v) = &verdict {
+            *self.skipped.entry(v.reason()).or_insert(0) += 1;
+        }
+        verdict.map_or(Ok(()), Err)
+    }
+
+    /// The first poor-fills verdict starts the cool-off; each one after it
+    /// has run out lets a single evaluation through and starts another.
+    fn probe_due(&self, slug: &str, now: DateTime<Utc>) -> bool {
+        if !self.probes.contains_key(slug) {
+            // Markets that closed mid cool-off
+            self.probes.retain(|_, last| now - *last < chrono::Duration::hours(1));
+        }
+        let mut last = self.probes.entry(slug.to_string()).or_insert(now);
+        if now - *last >= chrono::Duration::seconds(POOR_FILLS_PROBE_SECS) {
+            *last = now;
+            return true;
+        }
+        false
+    }
+
+    fn verdict(
+        &self,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        fill_success: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Option<ScreenVerdict> {
+        let c = &self.config;
+        let books = [yes_book, no_book];
+
+        let age_secs = books
+            .iter()
+            .map(|b| (now - b.timestamp).num_milliseconds() as f64 / 1000.0)
+            .fold(0.0, f64::max);
+        if c.max_book_age_secs > 0.0 && age_secs > c.max_book_age_secs {
+            return Some(ScreenVerdict::StaleBook { age_secs });
+        }
+
+        let depth = books.iter().map(|b| touch_depth(b)).fold(f64::INFINITY, f64::min);
+        if depth < c.min_top_depth {
+            return Some(ScreenVerdict::ThinBook { depth });
+        }
+
+        // One-sided books have no spread to judge
+        let spread = books
+            .iter()
+            .filter_map(|b| b.spread())
+            .map(to_f64)
+            .fold(0.0, f64::max);
+        if c.max_spread > 0.0 && spread > c.max_spread {
+            return Some(ScreenVerdict::WideSpread { spread });
+        }
+
+        match fill_success {
+            Some(success) if success < c.min_fill_success => Some(ScreenVerdict::PoorFills { success }),
+            _ => None,
+        }
+    }
+
+    /// Skips so far, by reason.
+    pub fn skip_counts(&self) -> Vec<(&'static str, u64)> {
+        let mut counts: Vec<_> = self.skipped.iter().map(|e| (*e.key(), *e.value())).collect();
+        counts.sort();
+        counts
+    }
+
+    pub fn log_summary(&self) {
+        let counts = self.skip_counts();
+        if !counts.is_empty() {
+            let parts: Vec<String> = counts.iter().map(|(r, n)| format!("{r}={n}")).collect();
+            info!("Market screen skips: {}", parts.join(" "));
+        }
+    }
+}
+
+fn to_f64(d: rust_decimal::Decimal) -> f64 {
+    d.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+fn touch_depth(book: &OrderBook) -> f64 {
+    let bid = book.best_bid().map(|(_, s)| to_f64(s)).unwrap_or(0.0);
+    let ask = book.best_ask().map(|(_, s)| to_f64(s)).unwrap_or(0.0);
+    bid + ask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn book(bid: Option<(f64, f64)>, ask: Option<(f64, f64)>) -> OrderBook {
+        let d = |x: f64| Decimal::from_str(&format!("{x:.2}")).unwrap();
+        let mut book = OrderBook::new("t".into());
+        if let Some((p, s)) = bid {
+            book.bids.insert(d(p), d(s));
+        }
+        if let Some((p, s)) = ask {
+            book.asks.insert(d(p), d(s));
+        }
+        book
+    }
+
+    fn screen() -> MarketScreen {
+        MarketScreen::new(MarketScreenConfig {
+            enabled: true,
+            min_top_depth: 5.0,
+            max_spread: 0.10,
+            max_book_age_secs: 30.0,
+            min_fill_success: 0.20,
+        })
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let s = screen();
+        let good = book(Some((0.48, 20.0)), Some((0.50, 20.0)));
+        let now = Utc::now();
+        assert_eq!(s.check("m", &good, &good, Some(0.9), now), Ok(()));
+        assert_eq!(s.check("m", &good, &good, None, now), Ok(()), "No history is not a verdict");
+
+        let thin = book(Some((0.48, 1.0)), Some((0.50, 2.0)));
+        assert_eq!(s.check("m", &good, &thin, None, now).unwrap_err().reason(), "thin_book");
+
+        let wide = book(Some((0.30, 20.0)), Some((0.50, 20.0)));
+        assert_eq!(s.check("m", &wide, &good, None, now).unwrap_err().reason(), "wide_spread");
+
+        // One-sided but deep: tradeable
+        let one_sided = book(None, Some((0.02, 100.0)));
+        assert_eq!(s.check("m", &good, &one_sided, None, now), Ok(()));
+
+        let mut stale = good.clone();
+        stale.timestamp = now - chrono::Duration::seconds(60);
+        assert_eq!(s.check("m", &stale, &good, None, now).unwrap_err().reason(), "stale_book");
+
+        assert_eq!(s.check("m", &good, &good, Some(0.1), now).unwrap_err().reason(), "poor_fills");
+        assert_eq!(
+            s.skip_counts(),
+            [("poor_fills", 1), ("stale_book", 1), ("thin_book", 1), ("wide_spread", 1)]
+        );
+    }
+
+    #[test]
+    fn test_poor_fills_reprobed_after_cool_off() {
+        let s = screen();
+        let now = Utc::now();
+        let probe = chrono::Duration::seconds(POOR_FILLS_PROBE_SECS);
+        let check = |slug: &str, at: DateTime<Utc>| {
+            let mut good = book(Some((0.48, 20.0)), Some((0.50, 20.0)));
+            good.timestamp = at;
+            s.check(slug, &good, &good, Some(0.1), at)
+        };
+        assert!(check("m", now).is_err());
+        assert!(check("m", now + probe / 2).is_err());
+
+        // One evaluation gets through, then the cool-off starts again
+        assert_eq!(check("m", now + probe), Ok(()));
+        assert!(check("m", now + probe * 3 / 2).is_err());
+        assert_eq!(check("m", now + probe * 2), Ok(()));
+
+        // Other markets keep their own clock
+        assert!(check("other", now + probe * 2).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::config::SpreadControlConfig;
+use crate::models::market::{Asset, Duration};
+use crate::models::position::strategy_bucket;
+use crate::telemetry::markout::MarkedFill;
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use tracing::{info, warn};
+
+#[derive(Debug)]
+struct SeriesState {
+    /// Recent (markout per share averaged over both horizons, shares)
+    recent: VecDeque<(f64, f64)>,
+    mult: f64,
+}
+
+impl Default for SeriesState {
+    fn default() -> Self {
+        Self { recent: VecDeque::new(), mult: 1.0 }
+    }
+}
+
+/// Online MM spread controller.
+///
+/// Fed each market-making fill once its 5s and 30s mid markouts are in
+/// (see `telemetry::markout`). Once a series has `min_fills` marked fills,
+/// each new one nudges its spread multiplier:
+/// up by `step` while the size-weighted markout is worse than
+/// `-tolerance`, down by `step` while it's better than `tolerance`, within
+/// `[min_mult, max_mult]`. The market maker reads the multiplier every
+/// evaluation.
+pub struct SpreadController {
+    config: SpreadControlConfig,
+    series: DashMap<(Asset, Duration), SeriesState>,
+}
+
+impl SpreadController {
+    pub fn new(config: SpreadControlConfig) -> Self {
+        Self { config, series: DashMap::new() }
+    }
+
+    /// Fold in a marked fill from `asset`/`duration`'s market; fills of
+    /// other strategies, and ones with neither mid markout, are ignored.
+    pub fn observe(&self, asset: Asset, duration: Duration, fill: &MarkedFill) {
+        if !self.config.enabled || strategy_bucket(&fill.strategy) != "mm" {
+            return;
+        }
+        let markout = match (fill.markouts.short, fill.markouts.long) {
+            (Some(s), Some(l)) => (s + l) / 2.0,
+            (Some(m), None) | (None, Some(m)) => m,
+            (None, None) => return,
+        };
+        self.update((asset, duration), markout, fill.size);
+    }
+
+    fn update(&self, series: (Asset, Duration), markout: f64, size: f64) {
+        let mut state = self.series.entry(series).or_default();
+        state.recent.push_back((markout, size));
+        while state.recent.len() > self.config.window {
+            state.recent.pop_front();
+        }
+        let shares: f64 = state.recent.iter().map(|(_, s)| s).sum();
+        if state.recent.len() < self.config.min_fills || shares <= 0.0 {
+            return;
+        }
+        let avg = state.recent.iter().map(|(m, s)| m * s).sum::<f64>() / shares;
+        let before = state.mult;
+        if avg < -self.config.tolerance {
+            state.mult = (state.mult * (1.0 + self.config.step)).min(self.config.max_mult);
+        } else if avg > self.config.tolerance {
+            state.mult = (state.mult * (1.0 - self.config.step)).max(self.config.min_mult);
+        }
+        let (asset, duration) = series;
+        if state.mult > before {
+            warn!(
+                "Spread control: {}-{} markout {:+.2}c/share — spread x{:.2}",
+                asset.slug_prefix(), duration.slug_suffix(), avg * 100.0, state.mult
+            );
+        } else if state.mult < before {
+            info!(
+                "Spread control: {}-{} markout {:+.2}c/share — spread x{:.2}",
+                asset.slug_prefix(), duration.slug_suffix(), avg * 100.0, state.mult
+            );
+        }
+    }
+
+    /// Multiplier on the MM half-spread for a series (1.0 = untouched).
+    pub fn spread_mult(&self, asset: Asset, duration: Duration) -> f64 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+        self.series.get(&(asset, duration)).map_or(1.0, |s| s.mult)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::markout::Markouts;
+
+    #[test]
+    fn test_widens_on_negative_markouts_and_recovers() {
+        let ctl = SpreadController::new(SpreadControlConfig {
+            enabled: true,
+            window: 4,
+            min_fills: 2,
+            step: 0.5,
+            min_mult: 0.8,
+            max_mult: 2.0,
+            tolerance: 0.005,
+        });
+        let observe = |strategy: &str, short, long| {
+            let markouts = Markouts { short, long, close: None };
+            let fill = MarkedFill { market: "m".into(), strategy: strategy.into(), size: 10.0, markouts };
+            ctl.observe(Asset::BTC, Duration::FiveMin, &fill);
+        };
+        let mult = || ctl.spread_mult(Asset::BTC, Duration::FiveMin);
+
+        // Picked off: the mid moved 3c against us by 5s and 5c by 30s
+        observe("mm_bid", Some(-0.03), Some(-0.05));
+        observe("lag_exploit", Some(-0.03), Some(-0.05));
+        assert_eq!(mult(), 1.0, "too few MM fills to react");
+        observe("mm_bid_l2", Some(-0.03), Some(-0.05));
+        assert_eq!(mult(), 1.5);
+        assert_eq!(ctl.spread_mult(Asset::ETH, Duration::FiveMin), 1.0);
+
+        // More picked-off fills hit the cap; an unmarked fill changes nothing
+        observe("mm_ask", None, Some(-0.06));
+        observe("mm_ask", None, None);
+        assert_eq!(mult(), 2.0);
+
+        // Fills that pay off tighten once they dominate the window
+        for _ in 0..4 {
+            observe("mm_ask", Some(0.10), Some(0.10));
+        }
+        assert!(mult() < 2.0);
+    }
+}
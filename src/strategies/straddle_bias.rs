@@ -1,13 +1,18 @@
 use crate::config::StrategyConfig;
 use crate::models::market::{LifecyclePhase, Market, OrderBook, Side};
-use crate::models::order::{OrderIntent, OrderSide, OrderType};
+use crate::models::order::{FailurePolicy, LegSet, OrderIntent, OrderSide, OrderType, MIN_ORDER_SHARES};
 use crate::models::signal::{ArbSignal, BiasSignal, VolRegime};
 use rust_decimal::Decimal;
 use tracing::{debug, info};
 
+/// The straddle's two buys are one trade; the bias amplification order is
+/// a trade of its own.
+pub const LEGS: LegSet = LegSet { tags: &["straddle_yes", "straddle_no"], policy: FailurePolicy::AllOrNothing };
+
 /// The core strategy: Straddle-First Bias Engine.
 ///
-/// Phase 1: Buy BOTH YES and NO when combined price < $1.00 (guaranteed profit).
+/// Phase 1: Buy BOTH YES and NO when combined price < $1.00 (guaranteed profit),
+///          shrinking the leg that would add to inventory we already hold.
 /// Phase 2: If directional bias detected, amplify by buying more of the favored side
 ///          at the point of maximum counter-movement.
 pub struct StraddleBiasEngine {
@@ -22,6 +27,8 @@ impl StraddleBiasEngine {
     /// Evaluate whether to enter a straddle on this market.
     ///
     /// Returns a vec of OrderIntents (0, 2, or 3 orders).
+    /// `net_yes_inventory` is our YES minus NO shares in this market.
+    #[allow(clippy::too_many_arguments)]
     pub fn evaluate(
         &self,
         market: &Market,
@@ -31,6 +38,7 @@ impl StraddleBiasEngine {
         bias_signal: Option<&BiasSignal>,
         vol_regime: VolRegime,
         available_capital: f64,
+        net_yes_inventory: f64,
     ) -> Vec<OrderIntent> {
         let mut orders = Vec::new();
 
@@ -45,7 +53,7 @@ impl StraddleBiasEngine {
         if let Some(arb) = arb_signal {
             if arb.combined < self.config.straddle_max_combined {
                 let straddle_orders =
-                    self.build_straddle(market, yes_book, no_book, arb, vol_regime, available_capital);
+                    self.build_straddle(market, arb, available_capital, net_yes_inventory);
                 orders.extend(straddle_orders);
             }
         }
@@ -80,11 +88,9 @@ impl StraddleBiasEngine {
     fn build_straddle(
         &self,
         market: &Market,
-        _yes_book: &OrderBook,
-        _no_book: &OrderBook,
         arb: &ArbSignal,
-        _vol_regime: VolRegime,
         available_capital: f64,
+        net_yes_inventory: f64,
     ) -> Vec<OrderIntent> {
         let mut orders = Vec::new();
 
@@ -92,8 +98,9 @@ impl StraddleBiasEngine {
         let max_capital = available_capital * self.config.straddle_max_capital_pct;
         let max_affordable = max_capital / arb.combined;
         let size = arb.executable_size.min(max_affordable).max(0.0);
+        let min_shares = MIN_ORDER_SHARES.to_string().parse::<f64>().unwrap_or(5.0);
 
-        if size < 1.0 {
+        if size < min_shares {
             debug!(
                 "Straddle size too small: {size:.2} (capital={available_capital:.2}, combined={:.3})",
                 arb.combined
@@ -101,26 +108,38 @@ impl StraddleBiasEngine {
             return orders;
         }
 
-        let size_dec = Decimal::from_f64_retain(size).unwrap_or(Decimal::ZERO);
+        // The leg on the side we're already long shrinks by that exposure, so
+        // the pair leans the market back toward flat. Neither leg goes under the
+        // exchange minimum: a rejected leg unwinds its filled partner.
+        let floor = (size * self.config.straddle_min_leg_pct).max(min_shares);
+        let yes_size = (size - net_yes_inventory.max(0.0)).max(floor);
+        let no_size = (size + net_yes_inventory.min(0.0)).max(floor);
+
+        let yes_size_dec = Decimal::from_f64_retain(yes_size).unwrap_or(Decimal::ZERO).round_dp(2);
+        let no_size_dec = Decimal::from_f64_retain(no_size).unwrap_or(Decimal::ZERO).round_dp(2);
         let yes_price = Decimal::from_f64_retain(arb.yes_ask).unwrap_or(Decimal::ZERO);
         let no_price = Decimal::from_f64_retain(arb.no_ask).unwrap_or(Decimal::ZERO);
 
         info!(
-            "STRADDLE: market={} YES@{} + NO@{} = {:.3} | edge={:.3} | size={:.1}",
-            market.slug, arb.yes_ask, arb.no_ask, arb.combined, arb.edge, size
+            "STRADDLE: market={} YES@{} + NO@{} = {:.3} | edge={:.3} | size={yes_size:.1}/{no_size:.1} (inventory {net_yes_inventory:+.1})",
+            market.slug, arb.yes_ask, arb.no_ask, arb.combined, arb.edge
         );
 
+        // Both legs carry one id so a straddle on another market stays its own trade
+        let group_id = Some(uuid::Uuid::new_v4().to_string());
+
         // YES leg
         orders.push(OrderIntent {
             token_id: market.yes_token_id.clone(),
             market_side: Side::Yes,
             order_side: OrderSide::Buy,
             price: yes_price,
-            size: size_dec,
+            size: yes_size_dec,
             order_type: OrderType::FAK, // Fill what you can, cancel rest
             post_only: false,
             expiration: None,
             strategy_tag: "straddle_yes".into(),
+            group_id: group_id.clone(),
         });
 
         // NO leg
@@ -129,17 +148,19 @@ impl StraddleBiasEngine {
             market_side: Side::No,
             order_side: OrderSide::Buy,
             price: no_price,
-            size: size_dec,
+            size: no_size_dec,
             order_type: OrderType::FAK,
             post_only: false,
             expiration: None,
             strategy_tag: "straddle_no".into(),
+            group_id,
         });
 
         orders
     }
 
     /// Build the directional bias amplification order.
+    #[allow(clippy::too_many_arguments)]
     fn build_bias_amplification(
         &self,
         market: &Market,
@@ -206,6 +227,41 @@ impl StraddleBiasEngine {
             post_only: false,
             expiration: None,
             strategy_tag: "bias_amplify".into(),
+            group_id: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use rust_decimal_macros::dec;
+
+    fn arb(size: f64) -> ArbSignal {
+        ArbSignal {
+            yes_ask: 0.45,
+            no_ask: 0.50,
+            combined: 0.95,
+            edge: 0.05,
+            executable_size: size,
+            expected_profit: 0.05 * size,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_straddle_legs_meet_share_minimum() {
+        let engine = StraddleBiasEngine::new(StrategyConfig::default());
+        let market = Market::new("btc-5m".into(), Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+
+        // Long 15 YES shrinks the YES leg of a 16-share pair to its floor,
+        // which would be 4 shares before the minimum clamps it
+        let orders = engine.build_straddle(&market, &arb(16.0), 1000.0, 15.0);
+        assert_eq!(orders.len(), 2);
+        assert_eq!((orders[0].size, orders[1].size), (dec!(5), dec!(16)));
+
+        // A pair too small for the minimum isn't sent at all
+        assert!(engine.build_straddle(&market, &arb(4.0), 1000.0, 0.0).is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use crate::models::market::{Market, OrderBook};
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::strategies::orchestrator::StrategyOrchestrator;
+use crate::telemetry::journal::{JournalEntry, JournalSource};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// Which orchestrator an intent came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arm {
+    /// The configuration that trades
+    Live,
+    /// The candidate configuration, journaled only
+    Shadow,
+}
+
+impl Arm {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Arm::Live => "live",
+            Arm::Shadow => "shadow",
+        }
+    }
+}
+
+/// Same token, direction and strategy on the same market = same intent.
+type IntentKey = (String, String, OrderSide, String);
+
+/// One arm's hypothetical book.
+#[derive(Default)]
+struct Ledger {
+    /// (market, token) → (shares, cost)
+    holdings: HashMap<(String, String), (f64, f64)>,
+    /// Last assumed fill per intent, so a repeated intent isn't filled twice
+    last_fill: HashMap<IntentKey, DateTime<Utc>>,
+    intents: u64,
+    fills: u64,
+    volume: f64,
+    realized: f64,
+    settled: u64,
+}
+
+/// A/B test of two strategy configurations on the same live data.
+///
+/// The live orchestrator trades as usual; the shadow one (built from the
+/// `SHADOW_*` overrides) is evaluated on the same markets, books and prices
+/// but never submits. Both arms' intents are scored the same way — taker
+/// fills against the book at evaluation time, makers assumed unfilled, held
+/// to resolution — so the comparison reflects the configurations, not our
+/// real execution. Shadow fills and payouts are journaled with source
+/// `Shadow`; the live arm's real fills are already in the journal.
+pub struct AbHarness {
+    shadow: Arc<StrategyOrchestrator>,
+    /// Repeats of a filled intent within this window are the same order
+    dedup: chrono::Duration,
+    ledgers: Mutex<HashMap<Arm, Ledger>>,
+}
+
+impl AbHarness {
+    pub fn new(shadow: Arc<StrategyOrchestrator>, dedup_ms: u64) -> Self {
+        Self {
+            shadow,
+            dedup: chrono::Duration::milliseconds(dedup_ms as i64),
+            ledgers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The shadow orchestrator, to feed and evaluate alongside the live one.
+    pub fn shadow(&self) -> &Arc<StrategyOrchestrator> {
+        &self.shadow
+    }
+
+    /// Score one evaluation's intents for `arm`. Returns journal lines for
+    /// the shadow's hypothetical fills.
+    pub fn record(
+        &self,
+        arm: Arm,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        intents: &[OrderIntent],
+        now: DateTime<Utc>,
+    ) -> Vec<JournalEntry> {
+        let mut ledgers = self.ledgers.lock().unwrap();
+        let ledger = ledgers.entry(arm).or_default();
+        let mut lines = Vec::new();
+        for intent in intents {
+            ledger.intents += 1;
+            let key = (market.slug.clone(), intent.token_id.clone(), intent.order_side, intent.strategy_tag.clone());
+            if ledger.last_fill.get(&key).is_some_and(|t| now - *t < self.dedup) {
+                continue;
+            }
+            let book = if intent.token_id == no_book.token_id { no_book } else { yes_book };
+            let holding = (market.slug.clone(), intent.token_id.clone());
+            let held = ledger.holdings.get(&holding).map_or(0.0, |h| h.0);
+            let f = |d: rust_decimal::Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+            let size = match intent.order_side {
+                OrderSide::Buy => f(intent.size),
+                OrderSide::Sell => f(intent.size).min(held),
+            };
+            let Some((shares, price)) = taker_fill(book, intent.order_side, f(intent.price), size) else {
+                continue;
+            };
+
+            let position = ledger.holdings.entry(holding.clone()).or_insert((0.0, 0.0));
+            match intent.order_side {
+                OrderSide::Buy => {
+                    position.0 += shares;
+                    position.1 += shares * price;
+                }
+                OrderSide::Sell => {
+                    let cost = position.1 * shares / position.0;
+                    ledger.realized += shares * price - cost;
+                    position.0 -= shares;
+                    position.1 -= cost;
+                    if position.0 <= 1e-9 {
+                        ledger.holdings.remove(&holding);
+                    }
+                }
+            }
+            ledger.fills += 1;
+            ledger.volume += shares * price;
+            ledger.last_fill.insert(key, now);
+
+            if arm == Arm::Shadow {
+                lines.push(JournalEntry {
+                    id: format!("shadow:{}", uuid::Uuid::new_v4()),
+                    timestamp: now,
+                    kind: "trade".into(),
+                    source: JournalSource::Shadow,
+                    market: market.slug.clone(),
+                    token_id: intent.token_id.clone(),
+                    side: Some(intent.order_side),
+                    price,
+                    size: shares,
+                    usdc: shares * price,
+                    fee: 0.0,
+                    strategy: intent.strategy_tag.clone(),
+                    order_id: None,
+                    tx_hash: None,
+                    exit_reason: None,
+                    hold_secs: None,
+                    markouts: None,
+                    opportunity: None,
+                    group: None,
+                });
+            }
+        }
+        ledger.last_fill.retain(|_, t| now - *t < self.dedup);
+        lines
+    }
+
+    /// Pay out both arms' holdings in `market`. Returns journal lines for
+    /// the shadow's redemptions.
+    pub fn on_resolution(&self, market: &str, winning_token: &str, now: DateTime<Utc>) -> Vec<JournalEntry> {
+        let mut ledgers = self.ledgers.lock().unwrap();
+        let mut lines = Vec::new();
+        for (arm, ledger) in ledgers.iter_mut() {
+            let settled: Vec<_> = ledger.holdings.keys().filter(|(m, _)| m == market).cloned().collect();
+            for key in settled {
+                let Some((shares, cost)) = ledger.holdings.remove(&key) else { continue };
+                let payout = if key.1 == winning_token { shares } else { 0.0 };
+                ledger.realized += payout - cost;
+                ledger.settled += 1;
+                if *arm == Arm::Shadow {
+                    lines.push(JournalEntry {
+                        id: format!("shadow:{}", uuid::Uuid::new_v4()),
+                        timestamp: now,
+                        kind: "redeem".into(),
+                        source: JournalSource::Shadow,
+                        market: market.to_string(),
+                        token_id: key.1.clone(),
+                        side: None,
+                        price: if payout > 0.0 { 1.0 } else { 0.0 },
+                        size: shares,
+                        usdc: payout,
+                        fee: 0.0,
+                        strategy: "shadow".into(),
+                        order_id: None,
+                        tx_hash: None,
+                        exit_reason: None,
+                        hold_secs: None,
+                        markouts: None,
+                        opportunity: None,
+                        group: None,
+                    });
+                }
+            }
+        }
+        lines
+    }
+
+    /// Both arms so far, open holdings marked at `mid_of(token)` (cost if
+    /// there's no book).
+    pub fn report(&self, mid_of: impl Fn(&str) -> Option<f64>) -> AbReport {
+        let ledgers = self.ledgers.lock().unwrap();
+        let summarize = |arm: Arm| {
+            let Some(ledger) = ledgers.get(&arm) else {
+                return ArmSummary { arm, ..ArmSummary::default() };
+            };
+            let open_cost: f64 = ledger.holdings.values().map(|h| h.1).sum();
+            let open_value: f64 = ledger
+                .holdings
+                .iter()
+                .map(|((_, token), (shares, cost))| mid_of(token).map_or(*cost, |mid| shares * mid))
+                .sum();
+            ArmSummary {
+                arm,
+                intents: ledger.intents,
+                fills: ledger.fills,
+                volume: ledger.volume,
+                realized: ledger.realized,
+                unrealized: open_value - open_cost,
+                settled: ledger.settled,
+            }
+        };
+        AbReport { live: summarize(Arm::Live), shadow: summarize(Arm::Shadow) }
+    }
+}
+
+/// Taker fill of up to `size` shares at `limit` or better: (shares, VWAP).
+fn taker_fill(book: &OrderBook, side: OrderSide, limit: f64, size: f64) -> Option<(f64, f64)> {
+    let f = |d: rust_decimal::Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+    let levels: Vec<(f64, f64)> = match side {
+        OrderSide::Buy => book.asks.iter().map(|(p, s)| (f(p), f(s))).take_while(|(p, _)| *p <= limit).collect(),
+        OrderSide::Sell => book.bids.iter().rev().map(|(p, s)| (f(p), f(s))).take_while(|(p, _)| *p >= limit).collect(),
+    };
+    let (mut shares, mut notional) = (0.0, 0.0);
+    for (price, available) in levels {
+        let take = available.min(size - shares);
+        shares += take;
+        notional += take * price;
+        if shares >= size {
+            break;
+        }
+    }
+    (shares > 0.0).then(|| (shares, notional / shares))
+}
+
+/// One arm's hypothetical results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmSummary {
+    pub arm: Arm,
+    pub intents: u64,
+    pub fills: u64,
+    /// USDC traded
+    pub volume: f64,
+    /// From sells and resolved markets
+    pub realized: f64,
+    /// Open holdings marked to mid
+    pub unrealized: f64,
+    /// Holdings paid out at resolution
+    pub settled: u64,
+}
+
+impl Default for ArmSummary {
+    fn default() -> Self {
+        Self { arm: Arm::Live, intents: 0, fills: 0, volume: 0.0, realized: 0.0, unrealized: 0.0, settled: 0 }
+    }
+}
+
+impl ArmSummary {
+    pub fn pnl(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+impl std::fmt::Display for ArmSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<6} intents={:<6} fills={:<5} volume=${:>8.2} realized={:+.2} open={:+.2} pnl={:+.2}",
+            self.arm.label(),
+            self.intents,
+            self.fills,
+            self.volume,
+            self.realized,
+            self.unrealized,
+            self.pnl()
+        )
+    }
+}
+
+/// Hypothetical P&L of the two arms side by side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbReport {
+    pub live: ArmSummary,
+    pub shadow: ArmSummary,
+}
+
+impl AbReport {
+    /// Shadow minus live: what switching to the shadow config would have made.
+    pub fn uplift(&self) -> f64 {
+        self.shadow.pnl() - self.live.pnl()
+    }
+
+    pub fn log_summary(&self) {
+        info!("A/B [{}] {}", self.live.arm.label(), self.live);
+        info!("A/B [{}] {}", self.shadow.arm.label(), self.shadow);
+        info!("A/B shadow vs live: {:+.2} USDC", self.uplift());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StrategyConfig;
+    use crate::models::market::{Asset, Duration, Side};
+    use crate::models::order::OrderType;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn intent(token: &str, order_side: OrderSide, price: Decimal, size: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: token.into(),
+            market_side: if token == "yes" { Side::Yes } else { Side::No },
+            order_side,
+            price,
+            size,
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_arms_scored_alike_and_settled() {
+        let harness = AbHarness::new(Arc::new(StrategyOrchestrator::new(StrategyConfig::default())), 2000);
+        let slug = Market::generate_slug(Asset::BTC, Duration::FiveMin, 1_700_000_100);
+        let market = Market::new(slug.clone(), Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+        let yes = OrderBook::with_levels("yes", &[(dec!(0.50), dec!(20))], &[(dec!(0.52), dec!(10)), (dec!(0.54), dec!(10))]);
+        let no = OrderBook::with_levels("no", &[(dec!(0.46), dec!(20))], &[(dec!(0.48), dec!(20))]);
+        let now = Utc::now();
+
+        // Live buys Yes through two levels; the repeat 200ms later is the same order
+        let buy_yes = [intent("yes", OrderSide::Buy, dec!(0.54), dec!(20))];
+        assert!(harness.record(Arm::Live, &market, &yes, &no, &buy_yes, now).is_empty());
+        harness.record(Arm::Live, &market, &yes, &no, &buy_yes, now + chrono::Duration::milliseconds(200));
+
+        // Shadow buys No; a resting (non-crossing) bid and a sell of nothing don't fill
+        let lines = harness.record(
+            Arm::Shadow,
+            &market,
+            &yes,
+            &no,
+            &[
+                intent("no", OrderSide::Buy, dec!(0.48), dec!(10)),
+                intent("yes", OrderSide::Buy, dec!(0.45), dec!(10)),
+                intent("yes", OrderSide::Sell, dec!(0.50), dec!(10)),
+            ],
+            now,
+        );
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].source, lines[0].size, lines[0].price), (JournalSource::Shadow, 10.0, 0.48));
+
+        let report = harness.report(|token| (token == "yes").then_some(0.60));
+        assert_eq!((report.live.intents, report.live.fills), (2, 1));
+        assert!((report.live.volume - 10.6).abs() < 1e-9, "10 @ 0.52 + 10 @ 0.54");
+        assert!((report.live.unrealized - 1.4).abs() < 1e-9, "20 @ 0.60 mid vs 10.60 cost");
+        assert_eq!(report.shadow.unrealized, 0.0, "no book mid: held at cost");
+
+        // Yes wins: live collects 20, shadow's No expires worthless
+        let redeemed = harness.on_resolution(&slug, "yes", now);
+        assert_eq!(redeemed.len(), 1);
+        assert_eq!((redeemed[0].kind.as_str(), redeemed[0].usdc), ("redeem", 0.0));
+        let report = harness.report(|_| None);
+        assert!((report.live.pnl() - 9.4).abs() < 1e-9);
+        assert!((report.shadow.pnl() + 4.8).abs() < 1e-9);
+        assert!((report.uplift() + 14.2).abs() < 1e-9);
+    }
+}
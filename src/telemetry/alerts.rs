@@ -1,63 +1,306 @@
 use crate::config::TelemetryConfig;
-use anyhow::Result;
-use tracing::{error, info};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
 
-/// Sends alerts via Telegram or Discord webhooks.
+/// How urgent an alert is. Sinks drop alerts below their minimum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warning),
+            "critical" | "crit" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            Self::Info => "",
+            Self::Warning => "⚠️ ",
+            Self::Critical => "🚨 ",
+        }
+    }
+}
+
+/// A destination for alerts (chat webhook, bot API, ...).
+pub trait AlertSink: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Alerts below this severity are not sent to this sink.
+    fn min_severity(&self) -> AlertSeverity;
+
+    /// Build the delivery request. Called again for each retry.
+    fn request(
+        &self,
+        http: &reqwest::Client,
+        severity: AlertSeverity,
+        message: &str,
+    ) -> reqwest::RequestBuilder;
+}
+
+pub struct TelegramSink {
+    pub bot_token: String,
+    pub chat_id: String,
+    pub min_severity: AlertSeverity,
+}
+
+impl AlertSink for TelegramSink {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn min_severity(&self) -> AlertSeverity {
+        self.min_severity
+    }
+
+    fn request(&self, http: &reqwest::Client, severity: AlertSeverity, message: &str) -> reqwest::RequestBuilder {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        http.post(url).json(&serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": format!("🎰 SATTEBAAZ: {}{message}", severity.prefix()),
+            "parse_mode": "Markdown"
+        }))
+    }
+}
+
+pub struct DiscordSink {
+    pub webhook_url: String,
+    pub min_severity: AlertSeverity,
+}
+
+impl AlertSink for DiscordSink {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    fn min_severity(&self) -> AlertSeverity {
+        self.min_severity
+    }
+
+    fn request(&self, http: &reqwest::Client, severity: AlertSeverity, message: &str) -> reqwest::RequestBuilder {
+        http.post(&self.webhook_url).json(&serde_json::json!({
+            "content": format!("🎰 **SATTEBAAZ**: {}{message}", severity.prefix())
+        }))
+    }
+}
+
+/// Slack incoming webhook.
+pub struct SlackSink {
+    pub webhook_url: String,
+    pub min_severity: AlertSeverity,
+}
+
+impl AlertSink for SlackSink {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    fn min_severity(&self) -> AlertSeverity {
+        self.min_severity
+    }
+
+    fn request(&self, http: &reqwest::Client, severity: AlertSeverity, message: &str) -> reqwest::RequestBuilder {
+        http.post(&self.webhook_url).json(&serde_json::json!({
+            "text": format!("🎰 *SATTEBAAZ*: {}{message}", severity.prefix())
+        }))
+    }
+}
+
+/// Dedup and per-severity rate limiting, applied before any sink sees an alert.
+///
+/// An identical (severity, message) pair is sent at most once per dedup
+/// window; the next copy after the window carries a repeat count. Each
+/// severity also has a per-minute cap (0 = unlimited). Suppressed alerts
+/// are still logged locally.
+pub struct AlertThrottle {
+    dedup_window: Duration,
+    per_min: [u32; 3],
+    /// (severity, message) -> (last sent, copies suppressed since)
+    last_sent: HashMap<(AlertSeverity, String), (Instant, u32)>,
+    /// Send times within the last minute, per severity
+    recent: [VecDeque<Instant>; 3],
+    /// Alerts dropped by the rate limit since the last one that got through, per severity
+    rate_dropped: [u32; 3],
+}
+
+impl AlertThrottle {
+    pub fn new(config: &TelemetryConfig) -> Self {
+        Self {
+            dedup_window: Duration::from_secs(config.alert_dedup_window_secs),
+            per_min: [
+                config.alert_info_per_min,
+                config.alert_warning_per_min,
+                config.alert_critical_per_min,
+            ],
+            last_sent: HashMap::new(),
+            recent: Default::default(),
+            rate_dropped: [0; 3],
+        }
+    }
+
+    /// Decide whether to send. Returns the text to deliver (annotated with
+    /// suppression counts), or None if the alert is suppressed.
+    pub fn check(&mut self, severity: AlertSeverity, message: &str, now: Instant) -> Option<String> {
+        let idx = severity.index();
+        let key = (severity, message.to_string());
+
+        // Dedup
+        let mut repeats = 0;
+        if let Some((sent_at, suppressed)) = self.last_sent.get_mut(&key) {
+            if now.duration_since(*sent_at) < self.dedup_window {
+                *suppressed += 1;
+                return None;
+            }
+            repeats = *suppressed;
+        }
+
+        // Rate limit
+        let window = &mut self.recent[idx];
+        while window.front().is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60)) {
+            window.pop_front();
+        }
+        let limit = self.per_min[idx];
+        if limit > 0 && window.len() >= limit as usize {
+            self.rate_dropped[idx] += 1;
+            return None;
+        }
+        window.push_back(now);
+
+        if self.last_sent.len() > 1024 {
+            let dedup_window = self.dedup_window;
+            self.last_sent.retain(|_, (t, _)| now.duration_since(*t) < dedup_window);
+        }
+        self.last_sent.insert(key, (now, 0));
+
+        let mut text = message.to_string();
+        if repeats > 0 {
+            text.push_str(&format!(" (repeated {repeats}x)"));
+        }
+        let dropped = std::mem::take(&mut self.rate_dropped[idx]);
+        if dropped > 0 {
+            text.push_str(&format!(" (+{dropped} {severity:?} alerts rate-limited)"));
+        }
+        Some(text)
+    }
+}
+
+/// Dispatches alerts to every configured sink (Telegram, Discord, Slack).
+///
+/// Delivery runs in the background with retry, so a slow or failing webhook
+/// never holds up the caller.
 pub struct AlertManager {
     config: TelemetryConfig,
     http: reqwest::Client,
+    sinks: Vec<Arc<dyn AlertSink>>,
+    throttle: Mutex<AlertThrottle>,
 }
 
 impl AlertManager {
     pub fn new(config: TelemetryConfig) -> Self {
+        let sinks = Self::sinks_from_config(&config);
+        Self::with_sinks(config, sinks)
+    }
+
+    pub fn with_sinks(config: TelemetryConfig, sinks: Vec<Arc<dyn AlertSink>>) -> Self {
         Self {
+            throttle: Mutex::new(AlertThrottle::new(&config)),
             config,
             http: reqwest::Client::new(),
+            sinks,
         }
     }
 
-    /// Send an alert message.
-    pub async fn send(&self, message: &str) {
-        info!("ALERT: {message}");
-
-        if let Err(e) = self.send_telegram(message).await {
-            error!("Telegram alert failed: {e}");
+    fn sinks_from_config(config: &TelemetryConfig) -> Vec<Arc<dyn AlertSink>> {
+        let mut sinks: Vec<Arc<dyn AlertSink>> = Vec::new();
+        if let (Some(token), Some(chat_id)) = (&config.telegram_bot_token, &config.telegram_chat_id) {
+            sinks.push(Arc::new(TelegramSink {
+                bot_token: token.clone(),
+                chat_id: chat_id.clone(),
+                min_severity: config.telegram_min_severity,
+            }));
         }
-
-        if let Err(e) = self.send_discord(message).await {
-            error!("Discord alert failed: {e}");
+        if let Some(url) = &config.discord_webhook_url {
+            sinks.push(Arc::new(DiscordSink {
+                webhook_url: url.clone(),
+                min_severity: config.discord_min_severity,
+            }));
+        }
+        if let Some(url) = &config.slack_webhook_url {
+            sinks.push(Arc::new(SlackSink {
+                webhook_url: url.clone(),
+                min_severity: config.slack_min_severity,
+            }));
         }
+        sinks
     }
 
-    /// Send alert to Telegram.
-    async fn send_telegram(&self, message: &str) -> Result<()> {
-        let (Some(token), Some(chat_id)) = (&self.config.telegram_bot_token, &self.config.telegram_chat_id) else {
-            return Ok(()); // Not configured
-        };
-
-        let url = format!("https://api.telegram.org/bot{token}/sendMessage");
-        let body = serde_json::json!({
-            "chat_id": chat_id,
-            "text": format!("🎰 SATTEBAAZ: {message}"),
-            "parse_mode": "Markdown"
-        });
+    /// Names of the active sinks.
+    pub fn sink_names(&self) -> Vec<&'static str> {
+        self.sinks.iter().map(|s| s.name()).collect()
+    }
 
-        self.http.post(&url).json(&body).send().await?;
-        Ok(())
+    /// Send an info alert.
+    pub async fn send(&self, message: &str) {
+        self.send_at(AlertSeverity::Info, message).await;
     }
 
-    /// Send alert to Discord.
-    async fn send_discord(&self, message: &str) -> Result<()> {
-        let Some(webhook_url) = &self.config.discord_webhook_url else {
-            return Ok(());
+    /// Send an alert at the given severity. Returns once delivery is queued.
+    pub async fn send_at(&self, severity: AlertSeverity, message: &str) {
+        let Some(message) = self.throttle.lock().unwrap().check(severity, message, Instant::now()) else {
+            debug!("ALERT [{severity:?}] suppressed: {message}");
+            return;
         };
+        info!("ALERT [{severity:?}]: {message}");
 
-        let body = serde_json::json!({
-            "content": format!("🎰 **SATTEBAAZ**: {message}")
-        });
+        for sink in self.sinks.iter().filter(|s| severity >= s.min_severity()) {
+            let sink = sink.clone();
+            let http = self.http.clone();
+            let message = message.to_string();
+            let (retries, base_ms) = (self.config.alert_max_retries, self.config.alert_retry_base_ms);
+            tokio::spawn(async move {
+                if let Err(e) = deliver(&http, sink.as_ref(), severity, &message, retries, base_ms).await {
+                    error!("{} alert failed: {e}", sink.name());
+                }
+            });
+        }
+    }
 
-        self.http.post(webhook_url).json(&body).send().await?;
-        Ok(())
+    /// Deliver to all eligible sinks and wait for the outcome of each.
+    /// Bypasses dedup and rate limiting.
+    pub async fn dispatch(&self, severity: AlertSeverity, message: &str) -> Vec<(&'static str, Result<()>)> {
+        let deliveries = self
+            .sinks
+            .iter()
+            .filter(|s| severity >= s.min_severity())
+            .map(|sink| async move {
+                let result = deliver(
+                    &self.http,
+                    sink.as_ref(),
+                    severity,
+                    message,
+                    self.config.alert_max_retries,
+                    self.config.alert_retry_base_ms,
+                )
+                .await;
+                (sink.name(), result)
+            });
+        futures_util::future::join_all(deliveries).await
     }
 
     /// Alert on trade execution.
@@ -70,14 +313,200 @@ impl AlertManager {
     /// Alert on error.
     pub async fn on_error(&self, error: &str) {
         if self.config.alert_on_error {
-            self.send(&format!("⚠️ Error: {error}")).await;
+            self.send_at(AlertSeverity::Warning, &format!("Error: {error}")).await;
         }
     }
 
     /// Alert on drawdown.
     pub async fn on_drawdown(&self, pct: f64) {
         if self.config.alert_on_drawdown {
-            self.send(&format!("🔴 Drawdown: {pct:.1}%")).await;
+            self.send_at(AlertSeverity::Critical, &format!("🔴 Drawdown: {pct:.1}%")).await;
+        }
+    }
+}
+
+/// POST to one sink, retrying transport errors, 429s and 5xx with
+/// exponential backoff. Other 4xx (bad URL/token) fail immediately.
+async fn deliver(
+    http: &reqwest::Client,
+    sink: &dyn AlertSink,
+    severity: AlertSeverity,
+    message: &str,
+    max_retries: u32,
+    base_ms: u64,
+) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        let err = match sink.request(http, severity, message).send().await {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => {
+                let status = resp.status();
+                if !(status.is_server_error() || status.as_u16() == 429) {
+                    bail!("HTTP {status}");
+                }
+                anyhow::anyhow!("HTTP {status}")
+            }
+            Err(e) => e.into(),
+        };
+
+        if attempt >= max_retries {
+            return Err(err.context(format!("gave up after {} attempts", attempt + 1)));
         }
+        let backoff_ms = base_ms.saturating_mul(1 << attempt.min(10));
+        warn!("{} alert attempt {} failed ({err}), retrying in {backoff_ms}ms", sink.name(), attempt + 1);
+        tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// Webhook stub: answers each request with the next status in `script`
+    /// (200 once it runs out) and records the bodies.
+    #[derive(Clone, Default)]
+    struct Hook {
+        script: Arc<Mutex<Vec<u16>>>,
+        bodies: Arc<Mutex<Vec<serde_json::Value>>>,
+        hits: Arc<AtomicUsize>,
+    }
+
+    async fn handle(State(hook): State<Hook>, Json(body): Json<serde_json::Value>) -> StatusCode {
+        hook.hits.fetch_add(1, Ordering::SeqCst);
+        hook.bodies.lock().unwrap().push(body);
+        let mut script = hook.script.lock().unwrap();
+        let code = if script.is_empty() { 200 } else { script.remove(0) };
+        StatusCode::from_u16(code).unwrap()
+    }
+
+    async fn start_hook(script: &[u16]) -> (Hook, String) {
+        let hook = Hook {
+            script: Arc::new(Mutex::new(script.to_vec())),
+            ..Default::default()
+        };
+        let app = Router::new().route("/hook", post(handle)).with_state(hook.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (hook, url)
+    }
+
+    fn config() -> TelemetryConfig {
+        TelemetryConfig {
+            alert_max_retries: 2,
+            alert_retry_base_ms: 1,
+            ..Config::default().telemetry
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sinks_filter_by_severity() {
+        let (discord, discord_url) = start_hook(&[]).await;
+        let (slack, slack_url) = start_hook(&[]).await;
+        let alerts = AlertManager::with_sinks(
+            config(),
+            vec![
+                Arc::new(DiscordSink { webhook_url: discord_url, min_severity: AlertSeverity::Info }),
+                Arc::new(SlackSink { webhook_url: slack_url, min_severity: AlertSeverity::Critical }),
+            ],
+        );
+
+        let results = alerts.dispatch(AlertSeverity::Warning, "feed lagging").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "discord");
+        assert!(results[0].1.is_ok());
+
+        let results = alerts.dispatch(AlertSeverity::Critical, "KILL SWITCH").await;
+        assert!(results.iter().all(|(_, r)| r.is_ok()));
+        assert_eq!(discord.hits.load(Ordering::SeqCst), 2);
+        assert_eq!(slack.hits.load(Ordering::SeqCst), 1);
+
+        let slack_body = slack.bodies.lock().unwrap()[0].clone();
+        assert_eq!(slack_body["text"], "🎰 *SATTEBAAZ*: 🚨 KILL SWITCH");
+        let discord_body = discord.bodies.lock().unwrap()[0].clone();
+        assert_eq!(discord_body["content"], "🎰 **SATTEBAAZ**: ⚠️ feed lagging");
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_transient_failures() {
+        let (flaky, flaky_url) = start_hook(&[503, 429]).await;
+        let (down, down_url) = start_hook(&[500, 500, 500, 500]).await;
+        let (bad, bad_url) = start_hook(&[404]).await;
+        let sink = |url: String| -> Arc<dyn AlertSink> {
+            Arc::new(DiscordSink { webhook_url: url, min_severity: AlertSeverity::Info })
+        };
+        let alerts = AlertManager::with_sinks(config(), vec![sink(flaky_url), sink(down_url), sink(bad_url)]);
+
+        let results = alerts.dispatch(AlertSeverity::Info, "hello").await;
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+        assert!(results[2].1.is_err());
+
+        assert_eq!(flaky.hits.load(Ordering::SeqCst), 3);
+        assert_eq!(down.hits.load(Ordering::SeqCst), 3); // 1 + 2 retries
+        assert_eq!(bad.hits.load(Ordering::SeqCst), 1); // not retried
+    }
+
+    #[test]
+    fn test_throttle_dedupes_repeats() {
+        let mut throttle = AlertThrottle::new(&config());
+        let t0 = Instant::now();
+        let feed_down = "Binance WS disconnected";
+
+        assert_eq!(throttle.check(AlertSeverity::Warning, feed_down, t0).as_deref(), Some(feed_down));
+        for i in 1..=5 {
+            assert!(throttle.check(AlertSeverity::Warning, feed_down, t0 + Duration::from_secs(i)).is_none());
+        }
+        // Same text at another severity is a different alert
+        assert!(throttle.check(AlertSeverity::Critical, feed_down, t0).is_some());
+
+        // After the window the next copy goes out with the repeat count
+        let later = t0 + Duration::from_secs(301);
+        assert_eq!(
+            throttle.check(AlertSeverity::Warning, feed_down, later).as_deref(),
+            Some("Binance WS disconnected (repeated 5x)")
+        );
+        assert!(throttle.check(AlertSeverity::Warning, feed_down, later).is_none());
+    }
+
+    #[test]
+    fn test_throttle_rate_limits_per_severity() {
+        let mut throttle = AlertThrottle::new(&TelemetryConfig {
+            alert_info_per_min: 3,
+            alert_critical_per_min: 0,
+            ..config()
+        });
+        let t0 = Instant::now();
+
+        let sent = (0..500)
+            .filter(|i| throttle.check(AlertSeverity::Info, &format!("reconnect #{i}"), t0).is_some())
+            .count();
+        assert_eq!(sent, 3);
+
+        // A flood of info doesn't hold back a kill switch
+        assert_eq!(
+            throttle.check(AlertSeverity::Critical, "KILL SWITCH activated", t0).as_deref(),
+            Some("KILL SWITCH activated")
+        );
+
+        // Once the minute rolls over, the first alert through reports the drops
+        let next = throttle.check(AlertSeverity::Info, "reconnect #500", t0 + Duration::from_secs(60));
+        assert_eq!(next.as_deref(), Some("reconnect #500 (+497 Info alerts rate-limited)"));
+    }
+
+    #[test]
+    fn test_sinks_from_config() {
+        let mut cfg = config();
+        assert!(AlertManager::new(cfg.clone()).sink_names().is_empty());
+
+        cfg.telegram_bot_token = Some("t".into()); // no chat id: skipped
+        cfg.discord_webhook_url = Some("https://discord.test/hook".into());
+        cfg.slack_webhook_url = Some("https://hooks.slack.test/x".into());
+        assert_eq!(AlertManager::new(cfg).sink_names(), ["discord", "slack"]);
     }
 }
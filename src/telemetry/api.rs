@@ -0,0 +1,403 @@
+use crate::execution::fill_tracker::{FillTracker, RestingQuote};
+use crate::feeds::polymarket::PolymarketFeed;
+use crate::models::market::{Ladder, OrderBook};
+use crate::models::order::OrderSide;
+use crate::risk::approval::{ApprovalQueue, Decision, PendingOrder};
+use crate::risk::safe_mode::{SafeMode, SafeModeStatus};
+use crate::telemetry::state::{EngineState, StateSources};
+use anyhow::Result;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// JSON API for external dashboards, plus the safe-mode and order-approval controls.
+///
+///   GET /state               — the whole engine as one `EngineState` (404 until wired up)
+///   GET /depth               — every tracked token with its top of book and our order count
+///   GET /depth/{token_id}    — aggregated ladder (?levels=N, default 20) with our resting
+///                              orders marked on the levels they sit at
+///   GET /safe-mode           — safe-mode state after an abnormal shutdown
+///   POST /safe-mode/confirm  — operator sign-off to resume entries (409 when not in safe mode)
+///   GET /approvals           — entries held for operator approval
+///   POST /approvals/{id}/approve, POST /approvals/{id}/reject
+///                            — answer one (404 when it isn't waiting)
+#[derive(Clone)]
+pub struct DashboardApi {
+    poly: Arc<PolymarketFeed>,
+    tracker: Arc<FillTracker>,
+    safe_mode: Arc<SafeMode>,
+    approvals: Option<Arc<ApprovalQueue>>,
+    state: Option<StateSources>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Size from the best price down to and including this level
+    pub cumulative: Decimal,
+    /// Our resting size at this price (0 if none)
+    pub ours: Decimal,
+    pub our_orders: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthSnapshot {
+    pub token_id: String,
+    pub market: Option<String>,
+    pub outcome: Option<&'static str>,
+    pub timestamp: DateTime<Utc>,
+    pub mid: Option<Decimal>,
+    /// Best first
+    pub bids: Vec<DepthLevel>,
+    pub asks: Vec<DepthLevel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenSummary {
+    pub token_id: String,
+    pub market: String,
+    pub outcome: &'static str,
+    pub best_bid: Option<Decimal>,
+    pub best_ask: Option<Decimal>,
+    pub our_orders: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepthQuery {
+    levels: Option<usize>,
+}
+
+impl DepthSnapshot {
+    /// Ladder of the top `levels` prices per side. Our quotes are merged in
+    /// even when the book snapshot hasn't caught up with them yet.
+    pub fn build(book: &OrderBook, quotes: &[RestingQuote], levels: usize) -> Self {
+        Self {
+            token_id: book.token_id.clone(),
+            market: None,
+            outcome: None,
+            timestamp: book.timestamp,
+            mid: book.midpoint(),
+            bids: side_levels(&book.bids, quotes, OrderSide::Buy, levels),
+            asks: side_levels(&book.asks, quotes, OrderSide::Sell, levels),
+        }
+    }
+}
+
+fn side_levels(
+    book_side: &Ladder,
+    quotes: &[RestingQuote],
+    side: OrderSide,
+    levels: usize,
+) -> Vec<DepthLevel> {
+    let mut merged = book_side.clone();
+    for q in quotes.iter().filter(|q| q.side == side) {
+        // Book hasn't reflected our order yet: show at least our size
+        if merged.get(&q.price).unwrap_or(Decimal::ZERO) < q.remaining {
+            merged.insert(q.price, q.remaining);
+        }
+    }
+
+    let prices: Vec<(Decimal, Decimal)> = match side {
+        OrderSide::Buy => merged.iter().rev().take(levels).collect(),
+        OrderSide::Sell => merged.iter().take(levels).collect(),
+    };
+    let mut cumulative = Decimal::ZERO;
+    prices
+        .into_iter()
+        .map(|(price, size)| {
+            cumulative += size;
+            let mine: Vec<&RestingQuote> =
+                quotes.iter().filter(|q| q.side == side && q.price == price).collect();
+            DepthLevel {
+                price,
+                size,
+                cumulative,
+                ours: mine.iter().map(|q| q.remaining).sum(),
+                our_orders: mine.iter().map(|q| q.order_id.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+impl DashboardApi {
+    pub fn new(poly: Arc<PolymarketFeed>, tracker: Arc<FillTracker>) -> Self {
+        Self { poly, tracker, safe_mode: Arc::new(SafeMode::inactive()), approvals: None, state: None }
+    }
+
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    pub fn with_approvals(mut self, approvals: Arc<ApprovalQueue>) -> Self {
+        self.approvals = Some(approvals);
+        self
+    }
+
+    pub fn with_engine_state(mut self, state: StateSources) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/state", get(engine_state))
+            .route("/depth", get(list_tokens))
+            .route("/depth/:token_id", get(token_depth))
+            .route("/safe-mode", get(safe_mode_status))
+            .route("/safe-mode/confirm", post(confirm_safe_mode))
+            .route("/approvals", get(list_approvals))
+            .route("/approvals/:id/approve", post(approve))
+            .route("/approvals/:id/reject", post(reject))
+            .with_state(self)
+    }
+
+    /// Serve until shutdown.
+    pub async fn serve(self, addr: &str, mut shutdown_rx: broadcast::Receiver<()>) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!("Dashboard API listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, self.router())
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_rx.recv().await;
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// (market slug, outcome) for a token.
+    fn lookup(&self, token_id: &str) -> Option<(String, &'static str)> {
+        self.poly.markets.iter().find_map(|m| {
+            if m.yes_token_id == token_id {
+                Some((m.slug.clone(), "YES"))
+            } else if m.no_token_id == token_id {
+                Some((m.slug.clone(), "NO"))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+async fn engine_state(State(api): State<DashboardApi>) -> Result<Json<EngineState>, StatusCode> {
+    let sources = api.state.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(sources.get_state().await))
+}
+
+async fn list_tokens(State(api): State<DashboardApi>) -> Json<Vec<TokenSummary>> {
+    let mut tokens = Vec::new();
+    for market in api.poly.markets.iter() {
+        for (outcome, token_id) in [("YES", &market.yes_token_id), ("NO", &market.no_token_id)] {
+            tokens.push(TokenSummary {
+                token_id: token_id.clone(),
+                market: market.slug.clone(),
+                outcome,
+                best_bid: api.poly.best_bid(token_id).map(|(p, _)| p),
+                best_ask: api.poly.best_ask(token_id).map(|(p, _)| p),
+                our_orders: api.tracker.quotes_for(token_id).len(),
+            });
+        }
+    }
+    tokens.sort_by(|a, b| (&a.market, a.outcome).cmp(&(&b.market, b.outcome)));
+    Json(tokens)
+}
+
+async fn token_depth(
+    State(api): State<DashboardApi>,
+    Path(token_id): Path<String>,
+    Query(query): Query<DepthQuery>,
+) -> Result<Json<DepthSnapshot>, StatusCode> {
+    let Some(book) = api.poly.get_book(&token_id) else {
+        warn!("Depth requested for unknown token {token_id}");
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let quotes = api.tracker.quotes_for(&token_id);
+    let mut snapshot = DepthSnapshot::build(&book, &quotes, query.levels.unwrap_or(20).clamp(1, 200));
+    if let Some((market, outcome)) = api.lookup(&token_id) {
+        snapshot.market = Some(market);
+        snapshot.outcome = Some(outcome);
+    }
+    Ok(Json(snapshot))
+}
+
+async fn safe_mode_status(State(api): State<DashboardApi>) -> Json<SafeModeStatus> {
+    Json(api.safe_mode.status())
+}
+
+async fn confirm_safe_mode(State(api): State<DashboardApi>) -> Result<Json<SafeModeStatus>, StatusCode> {
+    if !api.safe_mode.is_active() {
+        return Err(StatusCode::CONFLICT);
+    }
+    warn!("Safe mode confirmed by operator");
+    api.safe_mode.confirm();
+    Ok(Json(api.safe_mode.status()))
+}
+
+async fn list_approvals(State(api): State<DashboardApi>) -> Json<Vec<PendingOrder>> {
+    Json(api.approvals.map(|q| q.pending()).unwrap_or_default())
+}
+
+async fn approve(State(api): State<DashboardApi>, Path(id): Path<String>) -> Result<Json<Decision>, StatusCode> {
+    decide(&api, &id, true)
+}
+
+async fn reject(State(api): State<DashboardApi>, Path(id): Path<String>) -> Result<Json<Decision>, StatusCode> {
+    decide(&api, &id, false)
+}
+
+fn decide(api: &DashboardApi, id: &str, approve: bool) -> Result<Json<Decision>, StatusCode> {
+    let queue = api.approvals.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    queue.decide(id, approve, Utc::now()).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::market::{Asset, Duration, Market};
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        let mut book = OrderBook::new("tok-yes".into());
+        book.bids.insert(dec!(0.48), dec!(100));
+        book.bids.insert(dec!(0.47), dec!(50));
+        book.bids.insert(dec!(0.45), dec!(10));
+        book.asks.insert(dec!(0.52), dec!(80));
+        book.asks.insert(dec!(0.55), dec!(20));
+        book
+    }
+
+    fn quote(id: &str, side: OrderSide, price: Decimal, remaining: Decimal) -> RestingQuote {
+        RestingQuote {
+            order_id: id.into(),
+            token_id: "tok-yes".into(),
+            side,
+            price,
+            remaining,
+            strategy_tag: "mm_bid".into(),
+        }
+    }
+
+    #[test]
+    fn test_depth_marks_our_orders() {
+        let quotes = [
+            quote("b1", OrderSide::Buy, dec!(0.47), dec!(20)),
+            // Not in the book snapshot yet
+            quote("a1", OrderSide::Sell, dec!(0.53), dec!(15)),
+        ];
+        let snap = DepthSnapshot::build(&book(), &quotes, 2);
+
+        assert_eq!(snap.mid, Some(dec!(0.50)));
+        let bids: Vec<_> = snap.bids.iter().map(|l| (l.price, l.cumulative, l.ours)).collect();
+        assert_eq!(bids, [(dec!(0.48), dec!(100), dec!(0)), (dec!(0.47), dec!(150), dec!(20))]);
+        assert_eq!(snap.bids[1].our_orders, ["b1"]);
+
+        let asks: Vec<_> = snap.asks.iter().map(|l| (l.price, l.size, l.ours)).collect();
+        assert_eq!(asks, [(dec!(0.52), dec!(80), dec!(0)), (dec!(0.53), dec!(15), dec!(15))]);
+    }
+
+    #[tokio::test]
+    async fn test_depth_endpoint() {
+        let poly = Arc::new(PolymarketFeed::new(Config::default().polymarket));
+        let market = Market::new("btc-updown-5m-1".into(), Asset::BTC, Duration::FiveMin, "tok-yes".into(), "tok-no".into());
+        poly.markets.insert(market.slug.clone(), market);
+        poly.books.insert("tok-yes".into(), Arc::new(book()));
+        let tracker = Arc::new(FillTracker::new());
+        tracker.quotes.insert("b1".into(), quote("b1", OrderSide::Buy, dec!(0.48), dec!(5)));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = DashboardApi::new(poly, tracker).router();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = reqwest::Client::new();
+        let depth: serde_json::Value = http
+            .get(format!("http://{addr}/depth/tok-yes?levels=1"))
+            .send().await.unwrap()
+            .json().await.unwrap();
+        assert_eq!(depth["market"], "btc-updown-5m-1");
+        assert_eq!(depth["outcome"], "YES");
+        assert_eq!(depth["bids"].as_array().unwrap().len(), 1);
+        assert_eq!(depth["bids"][0]["ours"], "5");
+        assert_eq!(depth["bids"][0]["our_orders"][0], "b1");
+
+        let tokens: serde_json::Value = http.get(format!("http://{addr}/depth")).send().await.unwrap().json().await.unwrap();
+        assert_eq!(tokens.as_array().unwrap().len(), 2);
+        assert_eq!(tokens[1]["our_orders"], 1);
+
+        let missing = http.get(format!("http://{addr}/depth/nope")).send().await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+        let state = http.get(format!("http://{addr}/state")).send().await.unwrap();
+        assert_eq!(state.status(), reqwest::StatusCode::NOT_FOUND, "no engine state wired up");
+
+        // Not in safe mode: nothing to confirm
+        let status: serde_json::Value = http.get(format!("http://{addr}/safe-mode")).send().await.unwrap().json().await.unwrap();
+        assert_eq!(status["active"], false);
+        let confirm = http.post(format!("http://{addr}/safe-mode/confirm")).send().await.unwrap();
+        assert_eq!(confirm.status(), reqwest::StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_confirm() {
+        use crate::config::SafeModeConfig;
+        use crate::risk::safe_mode::SessionEnd;
+
+        let poly = Arc::new(PolymarketFeed::new(Config::default().polymarket));
+        let safe_mode = Arc::new(SafeMode::from_previous(&SafeModeConfig::default(), Some(SessionEnd::KillSwitch)));
+        safe_mode.mark_reconciled();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = DashboardApi::new(poly, Arc::new(FillTracker::new())).with_safe_mode(safe_mode.clone()).router();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = reqwest::Client::new();
+        let before: serde_json::Value = http.get(format!("http://{addr}/safe-mode")).send().await.unwrap().json().await.unwrap();
+        assert_eq!((before["cause"].as_str(), before["entries_allowed"].as_bool()), (Some("kill_switch"), Some(false)));
+        let after: serde_json::Value = http.post(format!("http://{addr}/safe-mode/confirm")).send().await.unwrap().json().await.unwrap();
+        assert_eq!(after["entries_allowed"], true);
+        assert!(safe_mode.entries_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_approvals_endpoints() {
+        use crate::config::ApprovalConfig;
+        use crate::models::market::Side;
+        use crate::models::order::{OrderIntent, OrderType};
+
+        let poly = Arc::new(PolymarketFeed::new(Config::default().polymarket));
+        let queue = Arc::new(ApprovalQueue::new(&ApprovalConfig { min_notional_usdc: 10.0, timeout_secs: 60 }));
+        let order = OrderIntent {
+            token_id: "tok-yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price: dec!(0.50),
+            size: dec!(40),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+        let (_, held) = queue.hold("btc-updown-5m-1", vec![order], Utc::now());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = DashboardApi::new(poly, Arc::new(FillTracker::new())).with_approvals(queue.clone()).router();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = reqwest::Client::new();
+        let pending: serde_json::Value = http.get(format!("http://{addr}/approvals")).send().await.unwrap().json().await.unwrap();
+        assert_eq!(pending[0]["id"], held[0].id.as_str());
+        assert_eq!(pending[0]["notional"], "20.00");
+        let url = format!("http://{addr}/approvals/{}/reject", held[0].id);
+        let decision: serde_json::Value = http.post(&url).send().await.unwrap().json().await.unwrap();
+        assert_eq!(decision, "rejected");
+        assert_eq!(http.post(&url).send().await.unwrap().status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::risk::approval::{ApprovalQueue, Decision, PendingOrder};
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Seconds Telegram holds a getUpdates call open waiting for a tap.
+const POLL_TIMEOUT_SECS: u64 = 25;
+
+/// Callback payload for a button: "approve:<id>" or "reject:<id>".
+fn callback_data(approve: bool, id: &str) -> String {
+    format!("{}:{id}", if approve { "approve" } else { "reject" })
+}
+
+/// (approve?, order id) from a button's callback payload.
+pub fn parse_callback(data: &str) -> Option<(bool, &str)> {
+    match data.split_once(':')? {
+        ("approve", id) => Some((true, id)),
+        ("reject", id) => Some((false, id)),
+        _ => None,
+    }
+}
+
+/// One-tap approve/reject for held orders over the Telegram bot API.
+///
+/// Each held order is sent to the alert chat with inline Approve / Reject
+/// buttons; taps are read by long-polling getUpdates. Only taps from the
+/// configured chat count. The bot must not have a webhook set, and nothing
+/// else may poll it.
+pub struct TelegramApprover {
+    api: String,
+    chat_id: String,
+    http: reqwest::Client,
+    queue: Arc<ApprovalQueue>,
+}
+
+impl TelegramApprover {
+    pub fn new(bot_token: &str, chat_id: &str, queue: Arc<ApprovalQueue>) -> Self {
+        Self::with_api(format!("https://api.telegram.org/bot{bot_token}"), chat_id, queue)
+    }
+
+    /// Against another bot API base URL (tests).
+    pub fn with_api(api: String, chat_id: &str, queue: Arc<ApprovalQueue>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(POLL_TIMEOUT_SECS + 10))
+            .build()
+            .unwrap_or_default();
+        Self { api, chat_id: chat_id.to_string(), http, queue }
+    }
+
+    /// Ask the chat about one held order.
+    pub async fn notify(&self, p: &PendingOrder) -> Result<()> {
+        let secs = (p.expires_at - p.held_at).num_seconds();
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": format!("🎰 SATTEBAAZ: approve entry?\n{}\n(expires in {secs}s)", p.describe()),
+            "reply_markup": { "inline_keyboard": [[
+                { "text": "✅ Approve", "callback_data": callback_data(true, &p.id) },
+                { "text": "❌ Reject", "callback_data": callback_data(false, &p.id) },
+            ]] },
+        });
+        self.http
+            .post(format!("{}/sendMessage", self.api))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()
+            .context("sendMessage")?;
+        Ok(())
+    }
+
+    /// Poll for button taps until shutdown.
+    pub async fn run(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+        info!("Telegram approvals: polling for taps");
+        let mut offset = 0i64;
+        loop {
+            tokio::select! {
+                result = self.poll(offset) => match result {
+                    Ok(next) => offset = next,
+                    Err(e) => {
+                        warn!("Telegram approvals poll failed: {e:#}");
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                },
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    }
+
+    /// One getUpdates round; returns the next offset.
+    pub async fn poll(&self, offset: i64) -> Result<i64> {
+        let resp: serde_json::Value = self
+            .http
+            .post(format!("{}/getUpdates", self.api))
+            .json(&serde_json::json!({
+                "offset": offset,
+                "timeout": POLL_TIMEOUT_SECS,
+                "allowed_updates": ["callback_query"],
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let updates = resp.get("result").and_then(|r| r.as_array()).context("no result")?;
+
+        let mut next = offset;
+        for update in updates {
+            next = next.max(update["update_id"].as_i64().unwrap_or(0) + 1);
+            let query = &update["callback_query"];
+            let Some(query_id) = query["id"].as_str() else { continue };
+            let chat = &query["message"]["chat"]["id"];
+            let from_chat = chat.as_i64().map(|c| c.to_string()).or(chat.as_str().map(String::from));
+            let answer = if from_chat.as_deref() != Some(self.chat_id.as_str()) {
+                warn!("Telegram approvals: ignoring tap from chat {chat}");
+                "Not allowed"
+            } else {
+                match query["data"].as_str().and_then(parse_callback) {
+                    Some((approve, id)) => match self.queue.decide(id, approve, chrono::Utc::now()) {
+                        Some(Decision::Approved) => "Approved",
+                        Some(Decision::Rejected) => "Rejected",
+                        Some(Decision::Expired) => "Too late — expired",
+                        None => "Already answered or expired",
+                    },
+                    None => "Unknown button",
+                }
+            };
+            let ack = serde_json::json!({ "callback_query_id": query_id, "text": answer });
+            if let Err(e) = self.http.post(format!("{}/answerCallbackQuery", self.api)).json(&ack).send().await {
+                warn!("Telegram approvals: answerCallbackQuery failed: {e}");
+            }
+        }
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApprovalConfig;
+    use crate::models::market::Side;
+    use crate::models::order::{OrderIntent, OrderSide, OrderType};
+    use axum::{extract::State, routing::post, Json, Router};
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_parse_callback() {
+        assert_eq!(parse_callback(&callback_data(true, "ab12")), Some((true, "ab12")));
+        assert_eq!(parse_callback("reject:ab12"), Some((false, "ab12")));
+        assert_eq!(parse_callback("cancel:ab12"), None);
+        assert_eq!(parse_callback("nonsense"), None);
+    }
+
+    #[tokio::test]
+    async fn test_tap_approves_held_order() {
+        let queue = Arc::new(ApprovalQueue::new(&ApprovalConfig { min_notional_usdc: 10.0, timeout_secs: 60 }));
+        let order = OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price: dec!(0.50),
+            size: dec!(40),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        };
+        let (_, held) = queue.hold("m1", vec![order], chrono::Utc::now());
+        let id = held[0].id.clone();
+
+        // Bot API stub: one tap from a stranger, one from our chat
+        let acks: Arc<Mutex<Vec<serde_json::Value>>> = Arc::default();
+        let updates = serde_json::json!({ "ok": true, "result": [
+            { "update_id": 7, "callback_query": { "id": "q1", "data": format!("approve:{id}"), "message": { "chat": { "id": 999 } } } },
+            { "update_id": 8, "callback_query": { "id": "q2", "data": format!("approve:{id}"), "message": { "chat": { "id": 42 } } } },
+        ]});
+        let app = Router::new()
+            .route("/getUpdates", post(move || async move { Json(updates) }))
+            .route(
+                "/answerCallbackQuery",
+                post(|State(acks): State<Arc<Mutex<Vec<serde_json::Value>>>>, Json(body): Json<serde_json::Value>| async move {
+                    acks.lock().unwrap().push(body);
+                    Json(serde_json::json!({ "ok": true }))
+                }),
+            )
+            .with_state(acks.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let approver = TelegramApprover::with_api(api, "42", queue.clone());
+        assert_eq!(approver.poll(0).await.unwrap(), 9);
+        let acks = acks.lock().unwrap().clone();
+        assert_eq!(acks[0]["text"], "Not allowed");
+        assert_eq!(acks[1]["text"], "Approved");
+        assert_eq!(queue.take_approved("m1", chrono::Utc::now(), |o| Some(o.price)).len(), 1);
+    }
+}
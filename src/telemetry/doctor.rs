@@ -0,0 +1,265 @@
+use crate::config::Config;
+use crate::execution::clob_auth::ClobAuth;
+use crate::execution::clob_client::ClobClient;
+use crate::execution::order_builder::derive_proxy_wallet;
+use crate::execution::polygon_merger::PolygonMerger;
+use crate::feeds::matic_price::MaticPrice;
+use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::connect_async;
+use tracing::warn;
+
+/// EOA gas below this can't pay for a merge or sweep (~0.01 MATIC each).
+const MIN_GAS_MATIC: f64 = 0.05;
+/// L1 auth signatures carry a timestamp; the CLOB rejects clocks this far off.
+const MAX_CLOCK_SKEW_SECS: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// Couldn't run because an earlier check failed or it doesn't apply
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Pass, detail: detail.into() }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Fail, detail: detail.into() }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Skip, detail: detail.into() }
+    }
+
+    /// Pass with `ok`'s detail, or fail with the error chain.
+    fn from_result(name: &'static str, result: Result<String>) -> Self {
+        match result {
+            Ok(detail) => Self::pass(name, detail),
+            Err(e) => Self::fail(name, format!("{e:#}")),
+        }
+    }
+}
+
+/// Whether the configured funder is the address orders will be made from.
+/// Signature type 1 trades from the CREATE2 proxy of the EOA; type 2 from a
+/// Gnosis safe we can't derive, so the funder is taken as given.
+pub fn check_funder(signature_type: u8, funder: Option<&str>, eoa: Address) -> Check {
+    const NAME: &str = "proxy wallet";
+    let funder = match funder.map(|f| f.parse::<Address>()) {
+        Some(Ok(a)) => Some(a),
+        Some(Err(_)) => return Check::fail(NAME, "POLYMARKET_FUNDER_ADDRESS is not an address"),
+        None => None,
+    };
+    match (signature_type, funder) {
+        (0, None) => Check::pass(NAME, format!("EOA trades for itself ({eoa:?})")),
+        (0, Some(f)) => Check::fail(NAME, format!("signature type 0 (EOA) with funder {f:?} — unset the funder or use type 1/2")),
+        (1, funder) => {
+            let derived = derive_proxy_wallet(eoa);
+            match funder {
+                Some(f) if f != derived => {
+                    Check::fail(NAME, format!("funder {f:?} is not this key's proxy {derived:?}"))
+                }
+                Some(_) => Check::pass(NAME, format!("funder matches derived proxy {derived:?}")),
+                None => Check::pass(NAME, format!("derived proxy {derived:?} (no funder set)")),
+            }
+        }
+        (2, Some(f)) => Check::pass(NAME, format!("Gnosis safe {f:?} (not derivable, taken as configured)")),
+        (2, None) => Check::fail(NAME, "signature type 2 (Gnosis safe) needs POLYMARKET_FUNDER_ADDRESS"),
+        (other, _) => Check::fail(NAME, format!("unknown signature type {other}")),
+    }
+}
+
+/// Server clock minus ours, timing the request so half the round trip isn't
+/// counted as skew.
+async fn clock_skew(client: &ClobClient) -> Result<f64> {
+    let before = chrono::Utc::now();
+    let server = client.get_server_time().await? as f64;
+    let after = chrono::Utc::now();
+    let midpoint = (before.timestamp_millis() + after.timestamp_millis()) as f64 / 2000.0;
+    Ok(server - midpoint)
+}
+
+/// WS handshake; with `expect_message`, also wait for the first frame.
+async fn ws_check(url: &str, expect_message: bool) -> Result<String> {
+    let start = Instant::now();
+    let (mut ws, _) = tokio::time::timeout(Duration::from_secs(10), connect_async(url))
+        .await
+        .context("no handshake within 10s")??;
+    if expect_message {
+        tokio::time::timeout(Duration::from_secs(5), ws.next())
+            .await
+            .context("no message within 5s")?
+            .context("stream closed")??;
+    }
+    Ok(format!("{:.0}ms", start.elapsed().as_secs_f64() * 1000.0))
+}
+
+/// Every check, in order. Checks that need a parsed key are skipped
+/// without one.
+pub async fn checks(config: &Config) -> Vec<Check> {
+    let pm = &config.polymarket;
+    let mut out = Vec::new();
+
+    let signer = if pm.private_key.is_empty() {
+        out.push(Check::fail("private key", "POLYMARKET_PRIVATE_KEY is not set"));
+        None
+    } else {
+        match pm.private_key.strip_prefix("0x").unwrap_or(&pm.private_key).parse::<PrivateKeySigner>() {
+            Ok(signer) => {
+                out.push(Check::pass("private key", format!("EOA {:?}", signer.address())));
+                Some(signer)
+            }
+            Err(e) => {
+                out.push(Check::fail("private key", format!("doesn't parse: {e}")));
+                None
+            }
+        }
+    };
+
+    let http = reqwest::Client::builder().timeout(Duration::from_secs(10)).build().unwrap_or_default();
+    let client = ClobClient::new(pm.clone());
+    match &signer {
+        Some(signer) => {
+            out.push(check_funder(pm.signature_type, pm.funder_address.as_deref(), signer.address()));
+
+            let mut auth = ClobAuth::new(&pm.private_key, pm.chain_id);
+            out.push(Check::from_result(
+                "clob auth",
+                auth.derive_api_key(&pm.clob_host).await.map(|_| "L2 API key derived".to_string()),
+            ));
+
+            client.init_auth().await.ok();
+            match client.fetch_collateral().await {
+                Ok(c) => {
+                    out.push(if c.balance <= 0.0 {
+                        Check::fail("usdc balance", "0 USDC available to the exchange")
+                    } else {
+                        Check::pass("usdc balance", format!("{:.2} USDC", c.balance))
+                    });
+                    out.push(match c.allowance {
+                        Some(a) if a <= 0.0 => {
+                            Check::fail("usdc allowance", "the exchange can't spend our USDC — approve it on polymarket.com")
+                        }
+                        Some(a) => Check::pass("usdc allowance", format!("{a:.2} USDC")),
+                        None => Check::skip("usdc allowance", "server didn't report an allowance"),
+                    });
+                }
+                Err(e) => {
+                    out.push(Check::fail("usdc balance", e.to_string()));
+                    out.push(Check::skip("usdc allowance", "balance fetch failed"));
+                }
+            }
+
+            // Priced for sizing a top-up; the fallback stands in if the ticker is down
+            let price = MaticPrice::new(&pm.matic_price_url, pm.matic_usd);
+            if !pm.matic_price_url.is_empty() {
+                if let Err(e) = price.refresh().await {
+                    warn!("MATIC price unavailable, using the ${} fallback: {e:#}", price.get());
+                }
+            }
+            let gas = PolygonMerger::new(&pm.polygon_rpc_url, signer.clone());
+            out.push(match gas {
+                Ok(merger) => match merger.check_gas_balance().await {
+                    Ok(matic) if matic < MIN_GAS_MATIC => {
+                        let top_up = MIN_GAS_MATIC - matic;
+                        Check::fail(
+                            "matic for gas",
+                            format!(
+                                "{matic:.4} MATIC, want at least {MIN_GAS_MATIC} — top up {top_up:.4} MATIC (≈${:.2})",
+                                price.usd_for_matic(top_up)
+                            ),
+                        )
+                    }
+                    Ok(matic) => Check::pass("matic for gas", format!("{matic:.4} MATIC (≈${:.2})", price.usd_for_matic(matic))),
+                    Err(e) => Check::fail("matic for gas", format!("{e:#}")),
+                },
+                Err(e) => Check::fail("matic for gas", format!("{e:#}")),
+            });
+        }
+        None => {
+            for name in ["proxy wallet", "clob auth", "usdc balance", "usdc allowance", "matic for gas"] {
+                out.push(Check::skip(name, "needs a private key"));
+            }
+        }
+    }
+
+    out.push(match clock_skew(&client).await {
+        Ok(skew) if skew.abs() > MAX_CLOCK_SKEW_SECS => {
+            Check::fail("clock skew", format!("{skew:+.1}s vs CLOB — sync this host's clock (NTP)"))
+        }
+        Ok(skew) => Check::pass("clock skew", format!("{skew:+.1}s vs CLOB")),
+        Err(e) => Check::fail("clock skew", e.to_string()),
+    });
+
+    out.push(Check::from_result(
+        "binance ws",
+        ws_check(&format!("{}/ws/btcusdt@aggTrade", config.binance.ws_url), true).await,
+    ));
+    out.push(Check::from_result("polymarket ws", ws_check(&pm.ws_host, false).await));
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_chainId", "params": [] });
+    let rpc = async {
+        let resp: serde_json::Value =
+            http.post(&pm.polygon_rpc_url).json(&body).send().await?.error_for_status()?.json().await?;
+        let chain = resp.get("result").and_then(|r| r.as_str()).context("no result")?;
+        let chain = u64::from_str_radix(chain.trim_start_matches("0x"), 16)?;
+        anyhow::ensure!(chain == pm.chain_id, "RPC is on chain {chain}, config expects {}", pm.chain_id);
+        Ok(format!("chain {chain}"))
+    };
+    out.push(Check::from_result("polygon rpc", rpc.await));
+    out
+}
+
+/// `sattebaaz doctor`: check everything live trading needs — key, wallet,
+/// funds, auth, clock and connectivity — and print pass/fail per item.
+/// Fails if any check does.
+pub async fn run(config: &Config) -> Result<()> {
+    let results = checks(config).await;
+    for c in &results {
+        let tag = match c.status {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Skip => "SKIP",
+        };
+        println!("  {tag}  {:<15} {}", c.name, c.detail);
+    }
+    let failed = results.iter().filter(|c| c.status == Status::Fail).count();
+    println!();
+    anyhow::ensure!(failed == 0, "{failed} check(s) failed");
+    println!("  Ready to trade");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_funder() {
+        let eoa: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap();
+        let proxy = format!("{:?}", derive_proxy_wallet(eoa));
+        let other = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+        assert_eq!(check_funder(0, None, eoa).status, Status::Pass);
+        assert_eq!(check_funder(0, Some(other), eoa).status, Status::Fail);
+        assert_eq!(check_funder(1, Some(&proxy), eoa).status, Status::Pass);
+        assert_eq!(check_funder(1, None, eoa).status, Status::Pass);
+        assert_eq!(check_funder(1, Some(other), eoa).status, Status::Fail);
+        assert_eq!(check_funder(2, Some(other), eoa).status, Status::Pass);
+        assert_eq!(check_funder(2, None, eoa).status, Status::Fail);
+        assert_eq!(check_funder(1, Some("nope"), eoa).status, Status::Fail);
+    }
+}
@@ -0,0 +1,376 @@
+//! Structured log events.
+//!
+//! Each event is logged with an `event` field naming it plus a fixed set of
+//! fields, so tooling can consume `--log-format json` output without
+//! scraping messages. Field names are part of the schema: add fields freely,
+//! but don't rename or remove them.
+//!
+//! | event             | fields                                                                        |
+//! |-------------------|-------------------------------------------------------------------------------|
+//! | `order_submitted` | order_id, token_id, side, price, size, order_type, strategy, status, error    |
+//! | `fill`            | order_id, token_id, market, side, price, size, fee, strategy, source          |
+//! | `risk_action`     | action, reason, market                                                        |
+//! | `resolution`      | market, reference_price, final_price, winner, pnl                             |
+//! | `market_lifecycle`| market, kind, elapsed_secs                                                    |
+//! | `reconnect`       | feed, backoff_ms                                                              |
+//!
+//! Decimal values are logged as strings to keep full precision.
+
+use crate::feeds::lifecycle::MarketEventKind;
+use crate::models::market::Side;
+use crate::models::order::{OrderSide, OrderStatus, OrderType};
+use rust_decimal::Decimal;
+use tracing::{error, info, warn};
+
+/// An order result returned by the CLOB (accepted or rejected).
+pub struct OrderSubmitted<'a> {
+    pub order_id: &'a str,
+    pub token_id: &'a str,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub order_type: OrderType,
+    pub strategy: &'a str,
+    pub status: OrderStatus,
+    pub error: Option<&'a str>,
+}
+
+impl OrderSubmitted<'_> {
+    pub fn emit(&self) {
+        let error = self.error.unwrap_or("");
+        if self.status == OrderStatus::Rejected {
+            warn!(
+                event = "order_submitted",
+                order_id = self.order_id,
+                token_id = self.token_id,
+                side = ?self.side,
+                price = %self.price,
+                size = %self.size,
+                order_type = ?self.order_type,
+                strategy = self.strategy,
+                status = ?self.status,
+                error,
+                "Order rejected: {error}"
+            );
+        } else {
+            info!(
+                event = "order_submitted",
+                order_id = self.order_id,
+                token_id = self.token_id,
+                side = ?self.side,
+                price = %self.price,
+                size = %self.size,
+                order_type = ?self.order_type,
+                strategy = self.strategy,
+                status = ?self.status,
+                error,
+                "Order {:?}: {:?} {}@{}",
+                self.status,
+                self.side,
+                self.size,
+                self.price
+            );
+        }
+    }
+}
+
+/// Where a fill was learned about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillSource {
+    /// Immediate match reported in the order response
+    Submit,
+    /// CLOB user WebSocket
+    UserWs,
+}
+
+/// A (partial) fill of one of our orders.
+pub struct Fill<'a> {
+    pub order_id: &'a str,
+    pub token_id: &'a str,
+    pub market: &'a str,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub fee: Decimal,
+    pub strategy: &'a str,
+    pub source: FillSource,
+}
+
+impl Fill<'_> {
+    pub fn emit(&self) {
+        info!(
+            event = "fill",
+            order_id = self.order_id,
+            token_id = self.token_id,
+            market = self.market,
+            side = ?self.side,
+            price = %self.price,
+            size = %self.size,
+            fee = %self.fee,
+            strategy = self.strategy,
+            source = ?self.source,
+            "Fill: {:?} {}@{} order={}",
+            self.side,
+            self.size,
+            self.price,
+            &self.order_id[..8.min(self.order_id.len())]
+        );
+    }
+}
+
+/// Something the risk layer did to trading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskActionKind {
+    KillSwitch,
+    ManualKill,
+    KillReset,
+    Pause,
+    ReduceSize,
+    EntryBlocked,
+    EntryFlipped,
+    LoopCooloff,
+    MarketStop,
+    SafeMode,
+    SafeModeCleared,
+    CanaryGraduated,
+    CanaryHalted,
+}
+
+impl RiskActionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::KillSwitch => "kill_switch",
+            Self::ManualKill => "manual_kill",
+            Self::KillReset => "kill_reset",
+            Self::Pause => "pause",
+            Self::ReduceSize => "reduce_size",
+            Self::EntryBlocked => "entry_blocked",
+            Self::EntryFlipped => "entry_flipped",
+            Self::LoopCooloff => "loop_cooloff",
+            Self::MarketStop => "market_stop",
+            Self::SafeMode => "safe_mode",
+            Self::SafeModeCleared => "safe_mode_cleared",
+            Self::CanaryGraduated => "canary_graduated",
+            Self::CanaryHalted => "canary_halted",
+        }
+    }
+}
+
+pub struct RiskAction<'a> {
+    pub action: RiskActionKind,
+    pub reason: &'a str,
+    /// Empty for portfolio-wide actions
+    pub market: &'a str,
+}
+
+impl RiskAction<'_> {
+    pub fn emit(&self) {
+        let action = self.action.as_str();
+        match self.action {
+            RiskActionKind::KillSwitch | RiskActionKind::ManualKill => error!(
+                event = "risk_action",
+                action,
+                reason = self.reason,
+                market = self.market,
+                "RISK {action}: {}",
+                self.reason
+            ),
+            RiskActionKind::KillReset | RiskActionKind::SafeModeCleared | RiskActionKind::CanaryGraduated => info!(
+                event = "risk_action",
+                action,
+                reason = self.reason,
+                market = self.market,
+                "RISK {action}: {}",
+                self.reason
+            ),
+            _ => warn!(
+                event = "risk_action",
+                action,
+                reason = self.reason,
+                market = self.market,
+                "RISK {action}: {}",
+                self.reason
+            ),
+        }
+    }
+}
+
+/// A market window settled.
+pub struct Resolution<'a> {
+    pub market: &'a str,
+    pub reference_price: f64,
+    pub final_price: f64,
+    pub winner: Side,
+    pub pnl: Decimal,
+}
+
+impl Resolution<'_> {
+    pub fn emit(&self) {
+        info!(
+            event = "resolution",
+            market = self.market,
+            reference_price = self.reference_price,
+            final_price = self.final_price,
+            winner = ?self.winner,
+            pnl = %self.pnl,
+            "Market resolved: {} ref={:.2} final={:.2} winner={:?} pnl={}",
+            self.market,
+            self.reference_price,
+            self.final_price,
+            self.winner,
+            self.pnl
+        );
+    }
+}
+
+/// A tracked market opened, entered a phase, or closed.
+pub struct MarketLifecycle<'a> {
+    pub market: &'a str,
+    pub kind: MarketEventKind,
+    pub elapsed_secs: f64,
+}
+
+impl MarketLifecycle<'_> {
+    pub fn emit(&self) {
+        info!(
+            event = "market_lifecycle",
+            market = self.market,
+            kind = ?self.kind,
+            elapsed_secs = self.elapsed_secs,
+            "Market {:?}: {} ({:.1}s in)",
+            self.kind,
+            self.market,
+            self.elapsed_secs
+        );
+    }
+}
+
+/// A feed socket dropped and is about to reconnect.
+pub struct Reconnect<'a> {
+    pub feed: &'a str,
+    pub backoff_ms: u64,
+}
+
+impl Reconnect<'_> {
+    pub fn emit(&self) {
+        warn!(
+            event = "reconnect",
+            feed = self.feed,
+            backoff_ms = self.backoff_ms,
+            "{} WS reconnecting in {}ms...",
+            self.feed,
+            self.backoff_ms
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Run `f` under the JSON subscriber and return the parsed lines.
+    fn capture_json(f: impl FnOnce()) -> Vec<serde_json::Value> {
+        let buf = Buffer::default();
+        let writer = buf.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+
+        let out = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+        out.lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+    }
+
+    fn keys(v: &serde_json::Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = v.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        keys.retain(|k| !matches!(*k, "timestamp" | "level" | "target" | "message"));
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_event_schemas_are_stable() {
+        let lines = capture_json(|| {
+            OrderSubmitted {
+                order_id: "0xabc",
+                token_id: "123",
+                side: OrderSide::Buy,
+                price: dec!(0.52),
+                size: dec!(10),
+                order_type: OrderType::FAK,
+                strategy: "lag_exploit",
+                status: OrderStatus::Open,
+                error: None,
+            }
+            .emit();
+            Fill {
+                order_id: "0xabc",
+                token_id: "123",
+                market: "btc-updown-5m-1",
+                side: OrderSide::Buy,
+                price: dec!(0.52),
+                size: dec!(4),
+                fee: dec!(0.01),
+                strategy: "lag_exploit",
+                source: FillSource::UserWs,
+            }
+            .emit();
+            RiskAction { action: RiskActionKind::Pause, reason: "daily loss", market: "" }.emit();
+            Resolution {
+                market: "btc-updown-5m-1",
+                reference_price: 100_000.0,
+                final_price: 100_050.0,
+                winner: Side::Yes,
+                pnl: dec!(1.25),
+            }
+            .emit();
+            Reconnect { feed: "binance", backoff_ms: 2000 }.emit();
+        });
+
+        assert_eq!(lines.len(), 5);
+        let event = |i: usize| lines[i]["event"].as_str().unwrap();
+        assert_eq!(
+            (0..5).map(event).collect::<Vec<_>>(),
+            ["order_submitted", "fill", "risk_action", "resolution", "reconnect"]
+        );
+
+        assert_eq!(
+            keys(&lines[0]),
+            ["error", "event", "order_id", "order_type", "price", "side", "size", "status", "strategy", "token_id"]
+        );
+        assert_eq!(
+            keys(&lines[1]),
+            ["event", "fee", "market", "order_id", "price", "side", "size", "source", "strategy", "token_id"]
+        );
+        assert_eq!(keys(&lines[2]), ["action", "event", "market", "reason"]);
+        assert_eq!(keys(&lines[3]), ["event", "final_price", "market", "pnl", "reference_price", "winner"]);
+        assert_eq!(keys(&lines[4]), ["backoff_ms", "event", "feed"]);
+
+        // Values: decimals as exact strings, enums by name
+        assert_eq!(lines[0]["price"], "0.52");
+        assert_eq!(lines[0]["side"], "Buy");
+        assert_eq!(lines[2]["action"], "pause");
+        assert_eq!(lines[2]["level"], "WARN");
+        assert_eq!(lines[3]["pnl"], "1.25");
+        assert_eq!(lines[4]["backoff_ms"], 2000);
+    }
+}
@@ -0,0 +1,414 @@
+use crate::feeds::user_ws::{FillEvent, OrderUpdate};
+use crate::models::market::{Asset, BookDiff};
+use crate::models::order::OrderIntent;
+use crate::risk::position_manager::PositionManager;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, TcpListenerStream};
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+/// Wire types for `proto/sattebaaz/v1/telemetry.proto`, plus the generated
+/// service in `telemetry_server` / `telemetry_client`.
+pub mod pb {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Topic {
+        Unspecified = 0,
+        Prices = 1,
+        Books = 2,
+        Signals = 3,
+        Orders = 4,
+        Fills = 5,
+        Pnl = 6,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequest {
+        #[prost(enumeration = "Topic", repeated, tag = "1")]
+        pub topics: Vec<i32>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Event {
+        #[prost(int64, tag = "1")]
+        pub timestamp_ms: i64,
+        #[prost(oneof = "event::Payload", tags = "2, 3, 4, 5, 6, 7")]
+        pub payload: Option<event::Payload>,
+    }
+
+    pub mod event {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Payload {
+            #[prost(message, tag = "2")]
+            Price(super::PriceTick),
+            #[prost(message, tag = "3")]
+            Book(super::BookTop),
+            #[prost(message, tag = "4")]
+            Signal(super::Signal),
+            #[prost(message, tag = "5")]
+            Order(super::OrderUpdate),
+            #[prost(message, tag = "6")]
+            Fill(super::Fill),
+            #[prost(message, tag = "7")]
+            Pnl(super::PnlSnapshot),
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PriceTick {
+        #[prost(string, tag = "1")]
+        pub asset: String,
+        #[prost(double, tag = "2")]
+        pub price: f64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BookTop {
+        #[prost(string, tag = "1")]
+        pub token_id: String,
+        #[prost(string, tag = "2")]
+        pub best_bid: String,
+        #[prost(string, tag = "3")]
+        pub bid_size: String,
+        #[prost(string, tag = "4")]
+        pub best_ask: String,
+        #[prost(string, tag = "5")]
+        pub ask_size: String,
+        #[prost(string, tag = "6")]
+        pub spread: String,
+        #[prost(bool, tag = "7")]
+        pub snapshot: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Signal {
+        #[prost(string, tag = "1")]
+        pub market: String,
+        #[prost(string, tag = "2")]
+        pub strategy: String,
+        #[prost(string, tag = "3")]
+        pub token_id: String,
+        #[prost(string, tag = "4")]
+        pub outcome: String,
+        #[prost(string, tag = "5")]
+        pub side: String,
+        #[prost(string, tag = "6")]
+        pub price: String,
+        #[prost(string, tag = "7")]
+        pub size: String,
+        #[prost(string, tag = "8")]
+        pub order_type: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct OrderUpdate {
+        #[prost(string, tag = "1")]
+        pub order_id: String,
+        #[prost(string, tag = "2")]
+        pub token_id: String,
+        #[prost(string, tag = "3")]
+        pub status: String,
+        #[prost(string, tag = "4")]
+        pub original_size: String,
+        #[prost(string, tag = "5")]
+        pub size_matched: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Fill {
+        #[prost(string, tag = "1")]
+        pub order_id: String,
+        #[prost(string, tag = "2")]
+        pub token_id: String,
+        #[prost(string, tag = "3")]
+        pub side: String,
+        #[prost(string, tag = "4")]
+        pub price: String,
+        #[prost(string, tag = "5")]
+        pub size: String,
+        #[prost(string, tag = "6")]
+        pub fee: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PnlSnapshot {
+        #[prost(string, tag = "1")]
+        pub capital: String,
+        #[prost(string, tag = "2")]
+        pub daily_pnl: String,
+        #[prost(string, tag = "3")]
+        pub total_pnl: String,
+        #[prost(uint32, tag = "4")]
+        pub total_trades: u32,
+        #[prost(double, tag = "5")]
+        pub win_rate: f64,
+        #[prost(uint32, tag = "6")]
+        pub open_positions: u32,
+    }
+
+    impl event::Payload {
+        pub fn topic(&self) -> Topic {
+            match self {
+                Self::Price(_) => Topic::Prices,
+                Self::Book(_) => Topic::Books,
+                Self::Signal(_) => Topic::Signals,
+                Self::Order(_) => Topic::Orders,
+                Self::Fill(_) => Topic::Fills,
+                Self::Pnl(_) => Topic::Pnl,
+            }
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/sattebaaz.v1.Telemetry.rs"));
+}
+
+use pb::event::Payload;
+
+/// gRPC service streaming the bot's live state to external consumers — a
+/// research notebook, a separate UI. Feeds are forwarded into one broadcast;
+/// each subscriber filters it by topic.
+///
+///   sattebaaz.v1.Telemetry/StreamEvents — prices, book tops, signals,
+///                                         order updates, fills, P&L
+#[derive(Clone)]
+pub struct TelemetryHub {
+    tx: broadcast::Sender<pb::Event>,
+}
+
+impl Default for TelemetryHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TelemetryHub {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(4096);
+        Self { tx }
+    }
+
+    pub fn publish(&self, payload: Payload) {
+        // Nobody listening is the normal case
+        let _ = self.tx.send(pb::Event {
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            payload: Some(payload),
+        });
+    }
+
+    /// Strategy output for a market, before risk checks.
+    pub fn publish_signals(&self, market: &str, intents: &[OrderIntent]) {
+        if self.tx.receiver_count() == 0 {
+            return;
+        }
+        for intent in intents {
+            self.publish(Payload::Signal(pb::Signal {
+                market: market.to_string(),
+                strategy: intent.strategy_tag.clone(),
+                token_id: intent.token_id.clone(),
+                outcome: format!("{:?}", intent.market_side),
+                side: format!("{:?}", intent.order_side),
+                price: intent.price.to_string(),
+                size: intent.size.to_string(),
+                order_type: format!("{:?}", intent.order_type),
+            }));
+        }
+    }
+
+    /// Forward a feed channel until shutdown, converting what `convert` keeps.
+    pub fn forward<T: Clone + Send + 'static>(
+        &self,
+        mut rx: broadcast::Receiver<T>,
+        mut shutdown: broadcast::Receiver<()>,
+        convert: impl Fn(T) -> Option<Payload> + Send + 'static,
+    ) {
+        let hub = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    item = rx.recv() => match item {
+                        Ok(item) => {
+                            if let Some(payload) = convert(item) {
+                                hub.publish(payload);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+
+    /// Publish a P&L snapshot every `period` until shutdown.
+    pub fn publish_pnl_every(
+        &self,
+        pos_mgr: Arc<PositionManager>,
+        period: std::time::Duration,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let hub = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        let portfolio = pos_mgr.portfolio.read().await;
+                        hub.publish(Payload::Pnl(pb::PnlSnapshot {
+                            capital: portfolio.capital.to_string(),
+                            daily_pnl: portfolio.daily_pnl.to_string(),
+                            total_pnl: portfolio.total_pnl.to_string(),
+                            total_trades: portfolio.total_trades.min(u32::MAX as u64) as u32,
+                            win_rate: portfolio.win_rate(),
+                            open_positions: portfolio.positions.len() as u32,
+                        }));
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+
+    /// Serve until shutdown.
+    pub async fn serve(self, addr: &str, mut shutdown_rx: broadcast::Receiver<()>) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!("Telemetry gRPC listening on {}", listener.local_addr()?);
+        tonic::transport::Server::builder()
+            .add_service(pb::telemetry_server::TelemetryServer::new(self))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                let _ = shutdown_rx.recv().await;
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub fn price_tick((asset, price): (Asset, f64)) -> Option<Payload> {
+    Some(Payload::Price(pb::PriceTick { asset: format!("{asset:?}"), price }))
+}
+
+/// Book tops only when the best prices moved or the book was refreshed.
+pub fn book_top(diff: BookDiff) -> Option<Payload> {
+    if !diff.snapshot && !diff.bbo_changed() {
+        return None;
+    }
+    let level = |l: Option<(rust_decimal::Decimal, rust_decimal::Decimal)>| {
+        l.map(|(p, s)| (p.to_string(), s.to_string())).unwrap_or_default()
+    };
+    let (best_bid, bid_size) = level(diff.bbo.bid);
+    let (best_ask, ask_size) = level(diff.bbo.ask);
+    Some(Payload::Book(pb::BookTop {
+        spread: diff.spread().map(|s| s.to_string()).unwrap_or_default(),
+        token_id: diff.token_id,
+        best_bid,
+        bid_size,
+        best_ask,
+        ask_size,
+        snapshot: diff.snapshot,
+    }))
+}
+
+pub fn order_update(update: OrderUpdate) -> Option<Payload> {
+    Some(Payload::Order(pb::OrderUpdate {
+        order_id: update.order_id,
+        token_id: update.token_id,
+        status: format!("{:?}", update.status),
+        original_size: update.original_size.to_string(),
+        size_matched: update.size_matched.to_string(),
+    }))
+}
+
+pub fn fill(fill: FillEvent) -> Option<Payload> {
+    Some(Payload::Fill(pb::Fill {
+        order_id: fill.order_id,
+        token_id: fill.token_id,
+        side: format!("{:?}", fill.side),
+        price: fill.price.to_string(),
+        size: fill.size.to_string(),
+        fee: fill.fee.to_string(),
+    }))
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<pb::Event, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl pb::telemetry_server::Telemetry for TelemetryHub {
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<pb::SubscribeRequest>,
+    ) -> Result<Response<EventStream>, Status> {
+        let topics: HashSet<pb::Topic> = request
+            .into_inner()
+            .topics
+            .into_iter()
+            .filter_map(|t| pb::Topic::try_from(t).ok())
+            .filter(|t| *t != pb::Topic::Unspecified)
+            .collect();
+        info!("Telemetry subscriber connected (topics: {})", if topics.is_empty() { "all".into() } else { format!("{topics:?}") });
+
+        let stream = BroadcastStream::new(self.tx.subscribe()).filter_map(move |event| match event {
+            Ok(event) => {
+                let wanted = topics.is_empty()
+                    || event.payload.as_ref().is_some_and(|p| topics.contains(&p.topic()));
+                wanted.then_some(Ok(event))
+            }
+            Err(BroadcastStreamRecvError::Lagged(n)) => {
+                warn!("Telemetry subscriber lagged by {n} events");
+                None
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Bbo, OrderBook};
+    use pb::telemetry_client::TelemetryClient;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_stream_filters_by_topic() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hub = TelemetryHub::new();
+        let server = hub.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(pb::telemetry_server::TelemetryServer::new(server))
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let mut client = TelemetryClient::connect(format!("http://{addr}")).await.unwrap();
+        let request = pb::SubscribeRequest { topics: vec![pb::Topic::Books as i32] };
+        let mut stream = client.stream_events(request).await.unwrap().into_inner();
+
+        hub.publish(price_tick((Asset::BTC, 100_000.0)).unwrap());
+        let mut book = OrderBook::new("tok".into());
+        // Only a size change at the top: not forwarded
+        book.apply_delta([(dec!(0.48), dec!(10))], [(dec!(0.52), dec!(5))]);
+        let unchanged = BookDiff { prev_bbo: book.bbo(), ..BookDiff::snapshot(&book, Bbo::default()) };
+        assert!(book_top(BookDiff { snapshot: false, ..unchanged }).is_none());
+        hub.publish(book_top(BookDiff::snapshot(&book, Bbo::default())).unwrap());
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let Some(Payload::Book(top)) = event.payload else { panic!("expected a book top, got {event:?}") };
+        assert_eq!((top.token_id.as_str(), top.best_bid.as_str(), top.spread.as_str()), ("tok", "0.48", "0.04"));
+        assert!(top.snapshot);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::config::TelemetryConfig;
+use anyhow::{ensure, Result};
+use std::time::{Duration, Instant};
+
+/// Liveness signals: a periodic "still alive" alert and/or a ping to a
+/// dead-man's-switch URL (healthchecks.io, Uptime Kuma push, ...).
+///
+/// The ping service is what catches a silent death — it alerts when pings
+/// stop arriving, which the bot itself can't do once it's gone.
+pub struct Heartbeat {
+    healthcheck_url: Option<String>,
+    pub ping_interval: Duration,
+    /// None = heartbeat alerts disabled
+    pub alert_interval: Option<Duration>,
+    http: reqwest::Client,
+    started: Instant,
+}
+
+impl Heartbeat {
+    pub fn new(config: &TelemetryConfig) -> Self {
+        Self {
+            healthcheck_url: config.healthcheck_url.clone(),
+            ping_interval: Duration::from_secs(config.healthcheck_interval_secs.max(5)),
+            alert_interval: (config.heartbeat_alert_secs > 0)
+                .then(|| Duration::from_secs(config.heartbeat_alert_secs)),
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.healthcheck_url.is_some() || self.alert_interval.is_some()
+    }
+
+    pub fn has_healthcheck(&self) -> bool {
+        self.healthcheck_url.is_some()
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Ping the healthcheck URL. No-op when none is configured.
+    pub async fn ping(&self) -> Result<()> {
+        let Some(url) = &self.healthcheck_url else {
+            return Ok(());
+        };
+        let resp = self.http.get(url).send().await?;
+        ensure!(resp.status().is_success(), "healthcheck ping returned HTTP {}", resp.status());
+        Ok(())
+    }
+
+    /// Text of the periodic heartbeat alert.
+    pub fn summary(&self, capital: f64, open_positions: usize) -> String {
+        format!(
+            "💓 Alive | uptime {} | capital ${capital:.2} | {open_positions} open positions",
+            format_uptime(self.uptime())
+        )
+    }
+}
+
+/// "2d 03h 15m", "03h 15m" or "15m".
+pub fn format_uptime(d: Duration) -> String {
+    let mins = d.as_secs() / 60;
+    let (days, hours, mins) = (mins / 1440, (mins / 60) % 24, mins % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}h {mins:02}m")
+    } else if hours > 0 {
+        format!("{hours:02}h {mins:02}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use axum::{extract::State, routing::get, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(Duration::from_secs(59)), "0m");
+        assert_eq!(format_uptime(Duration::from_secs(15 * 60)), "15m");
+        assert_eq!(format_uptime(Duration::from_secs(3 * 3600 + 5 * 60)), "03h 05m");
+        assert_eq!(format_uptime(Duration::from_secs(2 * 86400 + 3600)), "2d 01h 00m");
+    }
+
+    #[tokio::test]
+    async fn test_ping_hits_healthcheck_url() {
+        let pings = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route("/ping/abc", get(|State(n): State<Arc<AtomicUsize>>| async move {
+                n.fetch_add(1, Ordering::SeqCst);
+            }))
+            .with_state(pings.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut config = Config::default().telemetry;
+        assert!(!Heartbeat::new(&config).is_enabled());
+        Heartbeat::new(&config).ping().await.unwrap(); // unconfigured: no-op
+
+        config.healthcheck_url = Some(format!("http://{addr}/ping/abc"));
+        let heartbeat = Heartbeat::new(&config);
+        assert!(heartbeat.is_enabled());
+        heartbeat.ping().await.unwrap();
+        heartbeat.ping().await.unwrap();
+        assert_eq!(pings.load(Ordering::SeqCst), 2);
+
+        config.healthcheck_url = Some(format!("http://{addr}/missing"));
+        assert!(Heartbeat::new(&config).ping().await.is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::models::position::strategy_bucket;
+use crate::telemetry::journal::JournalEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Why a position was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    /// Take-profit target hit
+    #[serde(rename = "tp")]
+    TakeProfit,
+    /// Stop loss hit
+    #[serde(rename = "sl")]
+    StopLoss,
+    /// Held for the maximum hold time
+    Time,
+    /// Dumped at any price near the close
+    Force,
+    /// Closed early in the last stretch of the window to lock in a gain
+    PreResolve,
+    /// Held until the market resolved
+    Resolution,
+    /// The strategy's own sell (a market-making ask, an arb merge)
+    Strategy,
+}
+
+impl ExitReason {
+    pub const ALL: [ExitReason; 7] = [
+        ExitReason::TakeProfit,
+        ExitReason::StopLoss,
+        ExitReason::Time,
+        ExitReason::Force,
+        ExitReason::PreResolve,
+        ExitReason::Resolution,
+        ExitReason::Strategy,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExitReason::TakeProfit => "tp",
+            ExitReason::StopLoss => "sl",
+            ExitReason::Time => "time",
+            ExitReason::Force => "force",
+            ExitReason::PreResolve => "pre_resolve",
+            ExitReason::Resolution => "resolution",
+            ExitReason::Strategy => "strategy",
+        }
+    }
+}
+
+impl std::fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One closed position's time in trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldSample {
+    /// Strategy family (see `strategy_bucket`)
+    pub strategy: String,
+    pub reason: ExitReason,
+    pub hold_secs: f64,
+}
+
+/// Hold-time distribution for one (strategy, exit reason) pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldSummary {
+    pub strategy: String,
+    pub reason: ExitReason,
+    pub count: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub max: f64,
+}
+
+impl std::fmt::Display for HoldSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<9} {:<11} n={:<4} mean={:>5.0}s p50={:>5.0}s p90={:>5.0}s max={:>5.0}s",
+            self.strategy, self.reason, self.count, self.mean, self.p50, self.p90, self.max
+        )
+    }
+}
+
+/// Time-in-trade statistics per strategy and exit reason, for tuning hold
+/// limits and pre-resolution exits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HoldTimeReport {
+    samples: Vec<HoldSample>,
+}
+
+impl HoldTimeReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, strategy_tag: &str, reason: ExitReason, hold_secs: f64) {
+        self.samples.push(HoldSample {
+            strategy: strategy_bucket(strategy_tag).to_string(),
+            reason,
+            hold_secs: hold_secs.max(0.0),
+        });
+    }
+
+    /// Rebuild from journal entries that carry an exit reason and hold time.
+    pub fn from_journal(entries: &[JournalEntry]) -> Self {
+        let mut report = Self::new();
+        for e in entries {
+            if let (Some(reason), Some(hold_secs)) = (e.exit_reason, e.hold_secs) {
+                report.record(&e.strategy, reason, hold_secs);
+            }
+        }
+        report
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Distributions sorted by strategy, then exit reason.
+    pub fn summary(&self) -> Vec<HoldSummary> {
+        let mut groups: BTreeMap<(&str, ExitReason), Vec<f64>> = BTreeMap::new();
+        for s in &self.samples {
+            groups.entry((s.strategy.as_str(), s.reason)).or_default().push(s.hold_secs);
+        }
+        groups
+            .into_iter()
+            .map(|((strategy, reason), mut secs)| {
+                secs.sort_by(|a, b| a.total_cmp(b));
+                let pct = |q: f64| secs[((secs.len() - 1) as f64 * q).round() as usize];
+                HoldSummary {
+                    strategy: strategy.to_string(),
+                    reason,
+                    count: secs.len(),
+                    mean: secs.iter().sum::<f64>() / secs.len() as f64,
+                    p50: pct(0.5),
+                    p90: pct(0.9),
+                    max: secs[secs.len() - 1],
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order::{Fill, OrderSide};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_hold_time_distributions() {
+        let mut report = HoldTimeReport::new();
+        for secs in [10.0, 20.0, 30.0, 40.0, 100.0] {
+            report.record("lag_exploit", ExitReason::TakeProfit, secs);
+        }
+        // Mid-cycle entries count with their family
+        report.record("lag_exploit@mid", ExitReason::Time, 120.0);
+        report.record("arb_yes", ExitReason::Resolution, 290.0);
+
+        let summary = report.summary();
+        assert_eq!(summary.len(), 3);
+        assert_eq!((summary[0].strategy.as_str(), summary[0].reason), ("arb", ExitReason::Resolution));
+        let tp = &summary[1];
+        assert_eq!((tp.count, tp.mean, tp.p50, tp.p90, tp.max), (5, 40.0, 30.0, 100.0, 100.0));
+        assert_eq!(summary[2].reason, ExitReason::Time);
+
+        // Survives the journal
+        let fill = Fill {
+            order_id: "o".into(),
+            token_id: "t".into(),
+            side: OrderSide::Sell,
+            price: dec!(0.6),
+            size: dec!(5),
+            timestamp: Utc::now(),
+            fee: dec!(0),
+        };
+        let entry = JournalEntry::from_fill(&fill, "t1", "m", "lag_exploit").with_exit(ExitReason::StopLoss, 42.0);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains("\"exit_reason\":\"sl\""));
+        let parsed: JournalEntry = serde_json::from_str(&line).unwrap();
+        let plain: JournalEntry = serde_json::from_str(&serde_json::to_string(&JournalEntry::from_fill(&fill, "t2", "m", "mm_bid")).unwrap()).unwrap();
+        let rebuilt = HoldTimeReport::from_journal(&[parsed, plain]).summary();
+        assert_eq!(rebuilt.len(), 1);
+        assert_eq!((rebuilt[0].reason, rebuilt[0].max), (ExitReason::StopLoss, 42.0));
+    }
+}
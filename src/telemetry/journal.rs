@@ -0,0 +1,443 @@
+use crate::execution::polygon_merger::GasSpend;
+use crate::models::order::{Fill, OrderSide};
+use crate::telemetry::hold_time::ExitReason;
+use crate::telemetry::markout::Markouts;
+use crate::telemetry::observer::OpportunityStats;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Where a journal entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalSource {
+    /// Recorded by the bot as the fill happened
+    Live,
+    /// Imported afterwards from the data API
+    Backfill,
+    /// A hypothetical opportunity seen in observer mode — never traded
+    Observer,
+    /// A hypothetical fill or payout of the A/B shadow configuration
+    Shadow,
+}
+
+/// One line of the trade journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique key; re-importing an entry with a known id is a no-op
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    /// "trade", "redeem", "merge", "split", "reward", "gas", ...
+    pub kind: String,
+    pub source: JournalSource,
+    pub market: String,
+    pub token_id: String,
+    pub side: Option<OrderSide>,
+    pub price: f64,
+    pub size: f64,
+    pub usdc: f64,
+    pub fee: f64,
+    pub strategy: String,
+    pub order_id: Option<String>,
+    pub tx_hash: Option<String>,
+    /// Set on the fill that closed a position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_reason: Option<ExitReason>,
+    /// Seconds the closed position was held
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hold_secs: Option<f64>,
+    /// Set on "markout" lines: how the fill looked afterwards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markouts: Option<Markouts>,
+    /// Set on "opportunity" lines from observer mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opportunity: Option<OpportunityStats>,
+    /// Set on the fills of a multi-leg trade: the `IntentGroup` id its legs share
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+impl JournalEntry {
+    /// Live entry for one of our fills. `fill_id` tells the order's fills
+    /// apart (the exchange trade id), so the same fill seen twice — a user
+    /// channel redelivery after a reconnect — keeps one line.
+    pub fn from_fill(fill: &Fill, fill_id: &str, market: &str, strategy: &str) -> Self {
+        let f = |d: rust_decimal::Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+        Self {
+            id: format!("{}:{fill_id}", fill.order_id),
+            timestamp: fill.timestamp,
+            kind: "trade".into(),
+            source: JournalSource::Live,
+            market: market.to_string(),
+            token_id: fill.token_id.clone(),
+            side: Some(fill.side),
+            price: f(fill.price),
+            size: f(fill.size),
+            usdc: f(fill.price * fill.size),
+            fee: f(fill.fee),
+            strategy: strategy.to_string(),
+            order_id: Some(fill.order_id.clone()),
+            tx_hash: None,
+            exit_reason: None,
+            hold_secs: None,
+            markouts: None,
+            opportunity: None,
+            group: None,
+        }
+    }
+
+    /// Live entry for the gas an on-chain transaction burned, charged to
+    /// `strategy`: `size` is MATIC and `fee` its USD value.
+    pub fn from_gas(gas: &GasSpend, market: &str, strategy: &str) -> Self {
+        Self {
+            id: format!("gas-{}", gas.tx),
+            timestamp: Utc::now(),
+            kind: "gas".into(),
+            source: JournalSource::Live,
+            market: market.to_string(),
+            token_id: String::new(),
+            side: None,
+            price: gas.matic_usd,
+            size: gas.matic(),
+            usdc: 0.0,
+            fee: gas.usd(),
+            strategy: strategy.to_string(),
+            order_id: None,
+            tx_hash: Some(gas.tx.clone()),
+            exit_reason: None,
+            hold_secs: None,
+            markouts: None,
+            opportunity: None,
+            group: None,
+        }
+    }
+
+    /// Mark this entry as a leg of the trade `group`.
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Mark this entry as closing a position held for `hold_secs`.
+    pub fn with_exit(mut self, reason: ExitReason, hold_secs: f64) -> Self {
+        self.exit_reason = Some(reason);
+        self.hold_secs = Some(hold_secs);
+        self
+    }
+}
+
+/// Append-only JSONL trade journal.
+pub struct TradeJournal {
+    path: PathBuf,
+    inner: Mutex<JournalFile>,
+}
+
+struct JournalFile {
+    file: File,
+    ids: HashSet<String>,
+}
+
+impl TradeJournal {
+    /// Open (or create) the journal, indexing existing entry ids.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let ids = Self::read(&path)?.into_iter().map(|e| e.id).collect();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("opening journal {}", path.display()))?;
+        Ok(Self { path, inner: Mutex::new(JournalFile { file, ids }) })
+    }
+
+    /// Append an entry. Returns false (and writes nothing) for a known id.
+    pub fn append(&self, entry: &JournalEntry) -> Result<bool> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.ids.contains(&entry.id) {
+            return Ok(false);
+        }
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        inner.file.write_all(line.as_bytes())?;
+        inner.ids.insert(entry.id.clone());
+        Ok(true)
+    }
+
+    /// Append, logging instead of failing — for the live fill path.
+    pub fn record(&self, entry: &JournalEntry) {
+        if let Err(e) = self.append(entry) {
+            warn!("Trade journal write failed: {e}");
+        }
+    }
+
+    /// All entries in file order. Unparseable lines are skipped.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        Self::read(&self.path)
+    }
+
+    /// Timestamp of the first live entry: anything before it predates journaling.
+    pub fn first_live(&self) -> Result<Option<DateTime<Utc>>> {
+        Ok(self
+            .entries()?
+            .iter()
+            .filter(|e| e.source == JournalSource::Live)
+            .map(|e| e.timestamp)
+            .min())
+    }
+
+    fn read(path: &Path) -> Result<Vec<JournalEntry>> {
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("reading journal {}", path.display())),
+        };
+        let mut entries = Vec::new();
+        for (n, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => warn!("{}:{}: skipping bad journal line: {e}", path.display(), n + 1),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct BackfillReport {
+    pub fetched: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+}
+
+/// Imports account history from Polymarket's data API (`GET /activity`):
+/// trades plus on-chain events like redemptions and merges.
+pub struct Backfill {
+    http: reqwest::Client,
+    host: String,
+    user: String,
+}
+
+/// Data API page size limit
+const ACTIVITY_PAGE: usize = 500;
+
+impl Backfill {
+    pub fn new(data_api_host: &str, user: &str) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(20))
+                .build()
+                .unwrap_or_default(),
+            host: data_api_host.trim_end_matches('/').to_string(),
+            user: user.to_string(),
+        }
+    }
+
+    /// All activity in `[start, end)` (unix seconds), oldest first.
+    pub async fn fetch(&self, start: i64, end: i64) -> Result<Vec<JournalEntry>> {
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        loop {
+            let resp = self
+                .http
+                .get(format!("{}/activity", self.host))
+                .query(&[
+                    ("user", self.user.clone()),
+                    ("start", start.to_string()),
+                    ("end", (end - 1).max(start).to_string()),
+                    ("limit", ACTIVITY_PAGE.to_string()),
+                    ("offset", offset.to_string()),
+                    ("sortBy", "TIMESTAMP".to_string()),
+                    ("sortDirection", "ASC".to_string()),
+                ])
+                .send()
+                .await?;
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                anyhow::bail!("Activity fetch failed: HTTP {status} — {body}");
+            }
+            let page: Vec<serde_json::Value> = resp.json().await?;
+            entries.extend(
+                page.iter()
+                    .filter_map(entry_from_activity)
+                    .filter(|e| (start..end).contains(&e.timestamp.timestamp())),
+            );
+            if page.len() < ACTIVITY_PAGE {
+                return Ok(entries);
+            }
+            offset += page.len();
+        }
+    }
+
+    /// Fetch `[start, end)` and append whatever the journal doesn't have yet.
+    pub async fn run(&self, journal: &TradeJournal, start: i64, end: i64) -> Result<BackfillReport> {
+        let entries = self.fetch(start, end).await?;
+        let mut report = BackfillReport { fetched: entries.len(), ..Default::default() };
+        for entry in &entries {
+            if journal.append(entry)? {
+                report.imported += 1;
+            } else {
+                report.duplicates += 1;
+            }
+        }
+        info!(
+            "Backfill: fetched {} activities, imported {}, {} already journaled",
+            report.fetched, report.imported, report.duplicates
+        );
+        Ok(report)
+    }
+}
+
+/// Map a data API activity record to a journal entry.
+pub fn entry_from_activity(v: &serde_json::Value) -> Option<JournalEntry> {
+    let s = |key: &str| v.get(key).and_then(|x| x.as_str()).unwrap_or_default().to_string();
+    let num = |key: &str| {
+        v.get(key)
+            .and_then(|x| x.as_f64().or_else(|| x.as_str().and_then(|s| s.parse().ok())))
+            .unwrap_or(0.0)
+    };
+    let timestamp = DateTime::from_timestamp(v.get("timestamp")?.as_i64()?, 0)?;
+    let kind = s("type").to_lowercase();
+    let side = match s("side").as_str() {
+        "BUY" => Some(OrderSide::Buy),
+        "SELL" => Some(OrderSide::Sell),
+        _ => None,
+    };
+    let tx_hash = Some(s("transactionHash")).filter(|t| !t.is_empty());
+    let (token_id, size, price) = (s("asset"), num("size"), num("price"));
+
+    Some(JournalEntry {
+        id: format!(
+            "{}:{}:{}:{}:{size}:{price}",
+            tx_hash.as_deref().unwrap_or(&timestamp.timestamp().to_string()),
+            kind,
+            token_id,
+            s("side"),
+        ),
+        timestamp,
+        kind,
+        source: JournalSource::Backfill,
+        market: s("slug"),
+        token_id,
+        side,
+        price,
+        size,
+        usdc: num("usdcSize"),
+        fee: 0.0,
+        strategy: String::new(),
+        order_id: None,
+        tx_hash,
+        exit_reason: None,
+        hold_secs: None,
+        markouts: None,
+        opportunity: None,
+        group: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Query, routing::get, Json, Router};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("sattebaaz-journal-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    fn activity(ts: i64, tx: &str, kind: &str) -> serde_json::Value {
+        json!({
+            "timestamp": ts, "type": kind, "transactionHash": tx, "asset": "tok-yes",
+            "side": if kind == "TRADE" { "BUY" } else { "" },
+            "size": 10.0, "usdcSize": 5.2, "price": 0.52, "slug": "btc-updown-5m-1",
+        })
+    }
+
+    #[test]
+    fn test_entry_from_activity() {
+        let entry = entry_from_activity(&activity(1_700_000_000, "0xaa", "TRADE")).unwrap();
+        assert_eq!(entry.kind, "trade");
+        assert_eq!(entry.side, Some(OrderSide::Buy));
+        assert_eq!((entry.price, entry.size, entry.usdc), (0.52, 10.0, 5.2));
+        assert_eq!(entry.tx_hash.as_deref(), Some("0xaa"));
+        assert_eq!(entry.source, JournalSource::Backfill);
+
+        let redeem = entry_from_activity(&activity(1_700_000_000, "0xaa", "REDEEM")).unwrap();
+        assert_eq!(redeem.side, None);
+        assert_ne!(redeem.id, entry.id);
+        assert!(entry_from_activity(&json!({ "type": "TRADE" })).is_none());
+    }
+
+    #[test]
+    fn test_redelivered_fill_journaled_once() {
+        let path = temp_path();
+        let journal = TradeJournal::open(&path).unwrap();
+        let fill = Fill {
+            order_id: "0xo".into(),
+            token_id: "tok-yes".into(),
+            side: OrderSide::Buy,
+            price: rust_decimal::Decimal::new(52, 2),
+            size: rust_decimal::Decimal::new(5, 0),
+            timestamp: Utc::now(),
+            fee: rust_decimal::Decimal::ZERO,
+        };
+        assert!(journal.append(&JournalEntry::from_fill(&fill, "trade-1", "m", "lag_exploit")).unwrap());
+        let later = Fill { timestamp: Utc::now() + chrono::Duration::seconds(5), ..fill.clone() };
+        assert!(!journal.append(&JournalEntry::from_fill(&later, "trade-1", "m", "lag_exploit")).unwrap());
+        // A second fill on the same order is its own line
+        assert!(journal.append(&JournalEntry::from_fill(&fill, "trade-2", "m", "lag_exploit")).unwrap());
+        assert_eq!(journal.entries().unwrap().len(), 2);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_pages_and_dedupes() {
+        // 600 trades: two pages at the 500 limit
+        let all: Vec<serde_json::Value> =
+            (0..600).map(|i| activity(1_000 + i, &format!("0x{i:x}"), "TRADE")).collect();
+        let app = Router::new().route(
+            "/activity",
+            get(move |Query(q): Query<HashMap<String, String>>| {
+                let all = all.clone();
+                async move {
+                    assert_eq!(q["user"], "0xme");
+                    let offset: usize = q["offset"].parse().unwrap();
+                    let limit: usize = q["limit"].parse().unwrap();
+                    Json(all.into_iter().skip(offset).take(limit).collect::<Vec<_>>())
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let path = temp_path();
+        let journal = TradeJournal::open(&path).unwrap();
+        let backfill = Backfill::new(&format!("http://{addr}"), "0xme");
+
+        let report = backfill.run(&journal, 0, i64::MAX).await.unwrap();
+        assert_eq!(report, BackfillReport { fetched: 600, imported: 600, duplicates: 0 });
+
+        // Range filter, and re-running imports nothing new
+        let report = backfill.run(&journal, 1_100, 1_200).await.unwrap();
+        assert_eq!(report, BackfillReport { fetched: 100, imported: 0, duplicates: 100 });
+
+        // Ids survive a reopen
+        let reopened = TradeJournal::open(&path).unwrap();
+        assert_eq!(reopened.entries().unwrap().len(), 600);
+        assert!(!reopened.append(&reopened.entries().unwrap()[0]).unwrap());
+        assert_eq!(reopened.first_live().unwrap(), None);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::EnvFilter;
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines (default)
+    #[default]
+    Text,
+    /// One JSON object per line, event fields at the top level
+    Json,
+}
+
+impl LogFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "text" | "pretty" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// `--log-format json` (or `--log-format=json`) on the command line,
+    /// falling back to the LOG_FORMAT env var.
+    pub fn from_args_or_env() -> Self {
+        Self::from_args(std::env::args().skip(1))
+            .or_else(|| std::env::var("LOG_FORMAT").ok().and_then(|v| Self::parse(&v)))
+            .unwrap_or_default()
+    }
+
+    fn from_args(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        while let Some(arg) = args.next() {
+            if let Some(value) = arg.strip_prefix("--log-format=") {
+                return Self::parse(value);
+            }
+            if arg == "--log-format" {
+                return args.next().and_then(|v| Self::parse(&v));
+            }
+        }
+        None
+    }
+}
+
+/// Install the global tracing subscriber. RUST_LOG overrides `default_level`.
+pub fn init_tracing(format: LogFormat, default_level: &str) {
+    init_tracing_with_writer(format, default_level, std::io::stdout, true);
+}
+
+/// Same as [`init_tracing`] but writing somewhere other than stdout
+/// (e.g. the TUI's log panel).
+pub fn init_tracing_with_writer<W>(format: LogFormat, default_level: &str, writer: W, ansi: bool)
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_level));
+
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_writer(writer)
+            .with_ansi(ansi)
+            .with_target(false)
+            .with_thread_ids(true)
+            .init(),
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_env_filter(filter)
+            .with_writer(writer)
+            .init(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn test_parse_log_format_flag() {
+        assert_eq!(LogFormat::from_args(args(&["--log-format", "json"])), Some(LogFormat::Json));
+        assert_eq!(LogFormat::from_args(args(&["--log-format=JSON"])), Some(LogFormat::Json));
+        assert_eq!(LogFormat::from_args(args(&["--log-format=text"])), Some(LogFormat::Text));
+        assert_eq!(LogFormat::from_args(args(&["--other"])), None);
+        assert_eq!(LogFormat::from_args(args(&["--log-format", "xml"])), None);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::models::order::{Fill, OrderSide};
+use crate::models::position::strategy_bucket;
+use crate::telemetry::journal::JournalEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Mid-price markout horizons.
+pub const SHORT_HORIZON: Duration = Duration::from_secs(5);
+pub const LONG_HORIZON: Duration = Duration::from_secs(30);
+
+/// Post-fill markouts per share, signed so positive = the price moved our way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Markouts {
+    /// Against the mid `SHORT_HORIZON` after the fill
+    #[serde(rename = "5s", default, skip_serializing_if = "Option::is_none")]
+    pub short: Option<f64>,
+    /// Against the mid `LONG_HORIZON` after the fill
+    #[serde(rename = "30s", default, skip_serializing_if = "Option::is_none")]
+    pub long: Option<f64>,
+    /// Against the resolution payout: 1 for the winning token, 0 for the other
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close: Option<f64>,
+}
+
+/// A fill whose mid horizons have both been sampled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkedFill {
+    pub market: String,
+    pub strategy: String,
+    pub size: f64,
+    pub markouts: Markouts,
+}
+
+#[derive(Debug)]
+struct PendingFill {
+    /// The "markout" journal line this fill will be written as
+    entry: JournalEntry,
+    /// +1 for buys, -1 for sells
+    sign: f64,
+    filled_at: Instant,
+    markouts: Markouts,
+    /// Both mid horizons passed (sampled or not)
+    horizons_done: bool,
+}
+
+impl PendingFill {
+    fn markout(&self, reference: f64) -> f64 {
+        self.sign * (reference - self.entry.price)
+    }
+
+    fn finish(mut self) -> JournalEntry {
+        self.entry.markouts = Some(self.markouts);
+        self.entry
+    }
+}
+
+/// Markouts for every fill: the mid 5s and 30s later, then the payout once
+/// the market resolves. Completed fills become "markout" journal lines
+/// (see `MarkoutReport::from_journal`); the mid horizons also feed the MM
+/// spread controller as they complete.
+#[derive(Default)]
+pub struct MarkoutTracker {
+    pending: Mutex<Vec<PendingFill>>,
+    /// Completed this session, for the shutdown report
+    done: Mutex<Vec<JournalEntry>>,
+}
+
+impl MarkoutTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start marking a fill on `market` (slug) placed by `strategy`;
+    /// `fill_id` as for `JournalEntry::from_fill`.
+    pub fn on_fill(&self, fill: &Fill, fill_id: &str, market: &str, strategy: &str) {
+        self.on_fill_at(fill, fill_id, market, strategy, Instant::now());
+    }
+
+    pub fn on_fill_at(&self, fill: &Fill, fill_id: &str, market: &str, strategy: &str, at: Instant) {
+        let mut entry = JournalEntry::from_fill(fill, fill_id, market, strategy);
+        entry.id = format!("markout:{}", entry.id);
+        entry.kind = "markout".into();
+        self.pending.lock().unwrap().push(PendingFill {
+            entry,
+            sign: if fill.side == OrderSide::Buy { 1.0 } else { -1.0 },
+            filled_at: at,
+            markouts: Markouts::default(),
+            horizons_done: false,
+        });
+    }
+
+    /// Sample the mid for fills past each horizon. Call periodically;
+    /// returns the fills whose last mid horizon passed in this call.
+    pub fn mark(&self, now: Instant, mid_of: impl Fn(&str) -> Option<f64>) -> Vec<MarkedFill> {
+        let mut marked = Vec::new();
+        for f in self.pending.lock().unwrap().iter_mut().filter(|f| !f.horizons_done) {
+            let age = now.saturating_duration_since(f.filled_at);
+            if f.markouts.short.is_none() && age >= SHORT_HORIZON {
+                f.markouts.short = mid_of(&f.entry.token_id).map(|mid| f.markout(mid));
+            }
+            if age >= LONG_HORIZON {
+                f.markouts.long = mid_of(&f.entry.token_id).map(|mid| f.markout(mid));
+                f.horizons_done = true;
+                marked.push(MarkedFill {
+                    market: f.entry.market.clone(),
+                    strategy: f.entry.strategy.clone(),
+                    size: f.entry.size,
+                    markouts: f.markouts,
+                });
+            }
+        }
+        marked
+    }
+
+    /// Mark `market`'s fills against its payout and return their journal lines.
+    pub fn on_resolution(&self, market: &str, winning_token: &str) -> Vec<JournalEntry> {
+        let mut pending = self.pending.lock().unwrap();
+        let (hit, rest): (Vec<_>, Vec<_>) = pending.drain(..).partition(|f| f.entry.market == market);
+        *pending = rest;
+        drop(pending);
+        let resolved: Vec<_> = hit
+            .into_iter()
+            .map(|mut f| {
+                let payout = if f.entry.token_id == winning_token { 1.0 } else { 0.0 };
+                f.markouts.close = Some(f.markout(payout));
+                f.finish()
+            })
+            .collect();
+        self.done.lock().unwrap().extend(resolved.iter().cloned());
+        resolved
+    }
+
+    /// Journal lines for fills still waiting on resolution, with what they
+    /// have so far (at shutdown).
+    pub fn drain(&self) -> Vec<JournalEntry> {
+        let drained: Vec<_> = self.pending.lock().unwrap().drain(..).map(PendingFill::finish).collect();
+        self.done.lock().unwrap().extend(drained.iter().cloned());
+        drained
+    }
+
+    /// Markouts of the fills completed this session.
+    pub fn report(&self) -> MarkoutReport {
+        MarkoutReport::from_journal(&self.done.lock().unwrap())
+    }
+}
+
+/// Size-weighted markouts for one strategy or market series, per share.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkoutSummary {
+    pub key: String,
+    pub fills: usize,
+    pub shares: f64,
+    pub short: Option<f64>,
+    pub long: Option<f64>,
+    pub close: Option<f64>,
+}
+
+impl std::fmt::Display for MarkoutSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = |m: Option<f64>| m.map_or("    -".to_string(), |m| format!("{:+5.2}", m * 100.0));
+        write!(
+            f,
+            "{:<16} n={:<4} shares={:>7.1} 5s={}c 30s={}c close={}c",
+            self.key, self.fills, self.shares, c(self.short), c(self.long), c(self.close)
+        )
+    }
+}
+
+/// Markouts aggregated per strategy family and per market series.
+#[derive(Debug, Clone, Default)]
+pub struct MarkoutReport {
+    /// (strategy family, series, shares, markouts)
+    fills: Vec<(&'static str, String, f64, Markouts)>,
+}
+
+impl MarkoutReport {
+    /// Rebuild from the journal's "markout" lines.
+    pub fn from_journal(entries: &[JournalEntry]) -> Self {
+        let fills = entries
+            .iter()
+            .filter_map(|e| {
+                let m = e.markouts?;
+                Some((strategy_bucket(&e.strategy), series_of(&e.market).to_string(), e.size, m))
+            })
+            .collect();
+        Self { fills }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fills.is_empty()
+    }
+
+    pub fn by_strategy(&self) -> Vec<MarkoutSummary> {
+        self.summarize(|(strategy, ..)| strategy.to_string())
+    }
+
+    /// Keyed by market series (the slug without its window timestamp).
+    pub fn by_market(&self) -> Vec<MarkoutSummary> {
+        self.summarize(|(_, series, ..)| series.clone())
+    }
+
+    fn summarize(&self, key: impl Fn(&(&'static str, String, f64, Markouts)) -> String) -> Vec<MarkoutSummary> {
+        let mut groups: BTreeMap<String, Vec<(f64, Markouts)>> = BTreeMap::new();
+        for fill in &self.fills {
+            groups.entry(key(fill)).or_default().push((fill.2, fill.3));
+        }
+        groups
+            .into_iter()
+            .map(|(key, fills)| {
+                let avg = |pick: fn(&Markouts) -> Option<f64>| {
+                    let (sum, shares) = fills
+                        .iter()
+                        .filter_map(|(size, m)| pick(m).map(|v| (v * size, *size)))
+                        .fold((0.0, 0.0), |(a, b), (v, s)| (a + v, b + s));
+                    (shares > 0.0).then(|| sum / shares)
+                };
+                MarkoutSummary {
+                    key,
+                    fills: fills.len(),
+                    shares: fills.iter().map(|(s, _)| s).sum(),
+                    short: avg(|m| m.short),
+                    long: avg(|m| m.long),
+                    close: avg(|m| m.close),
+                }
+            })
+            .collect()
+    }
+
+    pub fn log_summary(&self) {
+        for (title, groups) in [("strategy", self.by_strategy()), ("market", self.by_market())] {
+            for s in groups {
+                info!("Markouts [{title}] {s}");
+            }
+        }
+    }
+}
+
+/// "btc-updown-5m-1700000000" → "btc-updown-5m".
+pub fn series_of(slug: &str) -> &str {
+    match slug.rsplit_once('-') {
+        Some((series, ts)) if !ts.is_empty() && ts.bytes().all(|b| b.is_ascii_digit()) => series,
+        _ => slug,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn fill(id: &str, token: &str, side: OrderSide, price: Decimal) -> Fill {
+        Fill {
+            order_id: id.into(),
+            token_id: token.into(),
+            side,
+            price,
+            size: dec!(10),
+            timestamp: chrono::Utc::now(),
+            fee: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_markouts_at_each_horizon_and_close() {
+        let tracker = MarkoutTracker::new();
+        let t0 = Instant::now();
+        let slug = "btc-updown-5m-1700000000";
+        tracker.on_fill_at(&fill("a", "yes", OrderSide::Buy, dec!(0.50)), "t1", slug, "lag_exploit", t0);
+        tracker.on_fill_at(&fill("b", "yes", OrderSide::Sell, dec!(0.50)), "t1", slug, "mm_ask", t0);
+        tracker.on_fill_at(&fill("c", "no", OrderSide::Buy, dec!(0.40)), "t1", "eth-updown-15m-1700000000", "mm_bid", t0);
+
+        assert!(tracker.mark(t0 + SHORT_HORIZON, |_| Some(0.53)).is_empty());
+        let marked = tracker.mark(t0 + LONG_HORIZON, |t| (t == "yes").then_some(0.56));
+        assert_eq!(marked.len(), 3);
+        assert!((marked[0].markouts.short.unwrap() - 0.03).abs() < 1e-9);
+        assert!((marked[1].markouts.long.unwrap() + 0.06).abs() < 1e-9, "the sell was run over");
+        assert_eq!(marked[2].markouts.long, None, "no book, no mark");
+        assert!(tracker.mark(t0 + LONG_HORIZON * 2, |_| Some(0.9)).is_empty(), "marked once");
+
+        // YES wins: the buy made 0.50 a share, the sell gave up 0.50
+        let lines = tracker.on_resolution(slug, "yes");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].kind, "markout");
+        assert!((lines[0].markouts.unwrap().close.unwrap() - 0.50).abs() < 1e-9);
+        assert!((lines[1].markouts.unwrap().close.unwrap() + 0.50).abs() < 1e-9);
+        let drained = tracker.drain();
+        assert_eq!((drained.len(), drained[0].markouts.unwrap().close), (1, None));
+
+        // Survives the journal
+        let json = serde_json::to_string(&lines[0]).unwrap();
+        assert!(json.contains("\"markouts\":{\"5s\":"));
+        let parsed: JournalEntry = serde_json::from_str(&json).unwrap();
+        let report = MarkoutReport::from_journal(&[parsed, lines[1].clone(), drained[0].clone()]);
+        let by_strategy = report.by_strategy();
+        assert_eq!(by_strategy.iter().map(|s| s.key.as_str()).collect::<Vec<_>>(), ["lag", "mm"]);
+        assert_eq!(by_strategy[1].fills, 2);
+        assert!((by_strategy[1].close.unwrap() + 0.50).abs() < 1e-9, "only the resolved fill has a close");
+        let by_market = tracker.report().by_market();
+        assert_eq!(by_market.iter().map(|s| s.key.as_str()).collect::<Vec<_>>(), ["btc-updown-5m", "eth-updown-15m"]);
+    }
+}
@@ -1,3 +1,20 @@
 pub mod pnl;
 pub mod latency;
 pub mod alerts;
+pub mod logging;
+pub mod events;
+pub mod heartbeat;
+pub mod api;
+pub mod journal;
+pub mod tca;
+pub mod probe;
+pub mod doctor;
+pub mod grpc;
+pub mod recorder;
+pub mod hold_time;
+pub mod markout;
+pub mod observer;
+pub mod ab;
+pub mod approvals;
+pub mod state;
+pub mod supervisor;
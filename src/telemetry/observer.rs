@@ -0,0 +1,288 @@
+use crate::models::market::{Market, OrderBook, Side};
+use crate::models::order::{OrderIntent, OrderSide};
+use crate::telemetry::journal::{JournalEntry, JournalSource};
+use crate::telemetry::markout::series_of;
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tracing::info;
+
+/// How a hypothetical opportunity looked over its life.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpportunityStats {
+    /// Fair value minus price (buys) or price minus fair value (sells) when first seen
+    pub edge: f64,
+    /// Largest edge seen while it lasted
+    pub peak_edge: f64,
+    /// Most shares seen resting at or better than the intent price
+    pub depth: f64,
+    /// First to last evaluation that produced it
+    pub duration_secs: f64,
+    pub evaluations: u32,
+}
+
+/// Same token, direction and strategy on the same market = same opportunity.
+type Key = (String, String, OrderSide, String);
+
+struct OpenOpportunity {
+    /// The "opportunity" journal line it will be written as
+    entry: JournalEntry,
+    stats: OpportunityStats,
+    last_seen: DateTime<Utc>,
+}
+
+impl OpenOpportunity {
+    fn finish(mut self) -> JournalEntry {
+        self.entry.opportunity = Some(self.stats);
+        self.entry
+    }
+}
+
+/// Observer mode: tracks the intents strategies would have sent, without
+/// sending them. An intent seen again on later evaluations extends the same
+/// opportunity; one not seen for `gap_ms` is closed and becomes an
+/// "opportunity" journal line (see `OpportunityReport::from_journal`).
+pub struct OpportunityObserver {
+    gap: chrono::Duration,
+    open: Mutex<HashMap<Key, OpenOpportunity>>,
+    /// Closed this session, for the shutdown report
+    done: Mutex<Vec<JournalEntry>>,
+}
+
+impl OpportunityObserver {
+    pub fn new(gap_ms: u64) -> Self {
+        Self {
+            gap: chrono::Duration::milliseconds(gap_ms as i64),
+            open: Mutex::new(HashMap::new()),
+            done: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record one evaluation's intents for `market`; `fair_up` is the
+    /// ensemble's probability of Up. Returns the opportunities (on any
+    /// market) that closed by `now`.
+    pub fn observe(
+        &self,
+        market: &Market,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+        fair_up: Option<f64>,
+        intents: &[OrderIntent],
+        now: DateTime<Utc>,
+    ) -> Vec<JournalEntry> {
+        let mut open = self.open.lock().unwrap();
+        for intent in intents {
+            let f = |d: rust_decimal::Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+            let price = f(intent.price);
+            let book = if intent.token_id == no_book.token_id { no_book } else { yes_book };
+            let fair = fair_up.map(|p| if intent.market_side == Side::Yes { p } else { 1.0 - p });
+            let edge = fair.map_or(0.0, |fair| match intent.order_side {
+                OrderSide::Buy => fair - price,
+                OrderSide::Sell => price - fair,
+            });
+            let depth: f64 = match intent.order_side {
+                OrderSide::Buy => book.asks.iter().take_while(|(p, _)| *p <= intent.price).map(|(_, s)| f(s)).sum(),
+                OrderSide::Sell => book.bids.iter().rev().take_while(|(p, _)| *p >= intent.price).map(|(_, s)| f(s)).sum(),
+            };
+
+            let key = (market.slug.clone(), intent.token_id.clone(), intent.order_side, intent.strategy_tag.clone());
+            let opp = open.entry(key).or_insert_with(|| OpenOpportunity {
+                entry: JournalEntry {
+                    id: format!("opportunity:{}", uuid::Uuid::new_v4()),
+                    timestamp: now,
+                    kind: "opportunity".into(),
+                    source: JournalSource::Observer,
+                    market: market.slug.clone(),
+                    token_id: intent.token_id.clone(),
+                    side: Some(intent.order_side),
+                    price,
+                    size: f(intent.size),
+                    usdc: price * f(intent.size),
+                    fee: 0.0,
+                    strategy: intent.strategy_tag.clone(),
+                    order_id: None,
+                    tx_hash: None,
+                    exit_reason: None,
+                    hold_secs: None,
+                    markouts: None,
+                    opportunity: None,
+                    group: None,
+                },
+                stats: OpportunityStats { edge, peak_edge: edge, ..Default::default() },
+                last_seen: now,
+            });
+            opp.stats.peak_edge = opp.stats.peak_edge.max(edge);
+            opp.stats.depth = opp.stats.depth.max(depth);
+            opp.stats.duration_secs = (now - opp.entry.timestamp).num_milliseconds() as f64 / 1000.0;
+            opp.stats.evaluations += 1;
+            opp.last_seen = now;
+        }
+
+        let gone: Vec<Key> = open
+            .iter()
+            .filter(|(_, o)| now - o.last_seen > self.gap)
+            .map(|(k, _)| k.clone())
+            .collect();
+        let closed: Vec<_> = gone.iter().filter_map(|k| open.remove(k)).map(OpenOpportunity::finish).collect();
+        drop(open);
+        self.done.lock().unwrap().extend(closed.iter().cloned());
+        closed
+    }
+
+    /// Close everything still open (at shutdown).
+    pub fn drain(&self) -> Vec<JournalEntry> {
+        let drained: Vec<_> = self.open.lock().unwrap().drain().map(|(_, o)| o.finish()).collect();
+        self.done.lock().unwrap().extend(drained.iter().cloned());
+        drained
+    }
+
+    /// Opportunities closed this session.
+    pub fn report(&self) -> OpportunityReport {
+        OpportunityReport::from_journal(&self.done.lock().unwrap())
+    }
+}
+
+/// Edge on offer for one market series (or series and hour).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpportunitySummary {
+    pub key: String,
+    pub count: usize,
+    pub mean_edge: f64,
+    pub mean_peak_edge: f64,
+    pub mean_depth: f64,
+    pub mean_duration_secs: f64,
+    /// Sum over opportunities of peak edge × depth: the most it could have paid
+    pub edge_usdc: f64,
+}
+
+impl std::fmt::Display for OpportunitySummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<16} n={:<5} edge={:+5.2}c peak={:+5.2}c depth={:>7.1} lasted={:>5.1}s ~${:.2}",
+            self.key,
+            self.count,
+            self.mean_edge * 100.0,
+            self.mean_peak_edge * 100.0,
+            self.mean_depth,
+            self.mean_duration_secs,
+            self.edge_usdc
+        )
+    }
+}
+
+/// Observed opportunities aggregated per market series.
+#[derive(Debug, Clone, Default)]
+pub struct OpportunityReport {
+    /// (series, UTC hour first seen, stats)
+    opportunities: Vec<(String, u32, OpportunityStats)>,
+}
+
+impl OpportunityReport {
+    /// Rebuild from the journal's "opportunity" lines.
+    pub fn from_journal(entries: &[JournalEntry]) -> Self {
+        let opportunities = entries
+            .iter()
+            .filter_map(|e| Some((series_of(&e.market).to_string(), e.timestamp.hour(), e.opportunity?)))
+            .collect();
+        Self { opportunities }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.opportunities.is_empty()
+    }
+
+    /// Keyed by market series (the slug without its window timestamp).
+    pub fn by_market(&self) -> Vec<OpportunitySummary> {
+        self.summarize(|(series, ..)| series.clone())
+    }
+
+    /// Keyed like "btc-updown-5m@14" (UTC hour first seen).
+    pub fn by_market_hour(&self) -> Vec<OpportunitySummary> {
+        self.summarize(|(series, hour, _)| format!("{series}@{hour:02}"))
+    }
+
+    fn summarize(&self, key: impl Fn(&(String, u32, OpportunityStats)) -> String) -> Vec<OpportunitySummary> {
+        let mut groups: BTreeMap<String, Vec<OpportunityStats>> = BTreeMap::new();
+        for opp in &self.opportunities {
+            groups.entry(key(opp)).or_default().push(opp.2);
+        }
+        groups
+            .into_iter()
+            .map(|(key, stats)| {
+                let n = stats.len() as f64;
+                let mean = |pick: fn(&OpportunityStats) -> f64| stats.iter().map(pick).sum::<f64>() / n;
+                OpportunitySummary {
+                    key,
+                    count: stats.len(),
+                    mean_edge: mean(|s| s.edge),
+                    mean_peak_edge: mean(|s| s.peak_edge),
+                    mean_depth: mean(|s| s.depth),
+                    mean_duration_secs: mean(|s| s.duration_secs),
+                    edge_usdc: stats.iter().map(|s| s.peak_edge.max(0.0) * s.depth).sum(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn log_summary(&self) {
+        for s in self.by_market() {
+            info!("Opportunities [market] {s}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::{Asset, Duration};
+    use crate::models::order::OrderType;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn buy_yes(price: Decimal) -> OrderIntent {
+        OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: OrderSide::Buy,
+            price,
+            size: dec!(10),
+            order_type: OrderType::FAK,
+            post_only: false,
+            expiration: None,
+            strategy_tag: "lag_exploit".into(),
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_opportunity_lifecycle() {
+        let now = Utc::now();
+        let slug = Market::generate_slug(Asset::BTC, Duration::FiveMin, 1_700_000_100);
+        let market = Market::new(slug, Asset::BTC, Duration::FiveMin, "yes".into(), "no".into());
+        let yes = OrderBook::with_levels("yes", &[(dec!(0.50), dec!(20))], &[(dec!(0.52), dec!(15)), (dec!(0.53), dec!(30)), (dec!(0.60), dec!(99))]);
+        let no = OrderBook::with_levels("no", &[(dec!(0.46), dec!(20))], &[(dec!(0.48), dec!(20))]);
+        let observer = OpportunityObserver::new(1000);
+        let at = |ms: i64| now + chrono::Duration::milliseconds(ms);
+
+        assert!(observer.observe(&market, &yes, &no, Some(0.60), &[buy_yes(dec!(0.53))], at(0)).is_empty());
+        assert!(observer.observe(&market, &yes, &no, Some(0.62), &[buy_yes(dec!(0.53))], at(400)).is_empty());
+        // Not seen again: still open within the gap, closed after it
+        assert!(observer.observe(&market, &yes, &no, Some(0.55), &[], at(1200)).is_empty());
+        let closed = observer.observe(&market, &yes, &no, Some(0.55), &[], at(1500));
+        assert_eq!(closed.len(), 1);
+
+        let entry = &closed[0];
+        assert_eq!((entry.kind.as_str(), entry.source), ("opportunity", JournalSource::Observer));
+        let stats = entry.opportunity.unwrap();
+        assert!((stats.edge - 0.07).abs() < 1e-9);
+        assert!((stats.peak_edge - 0.09).abs() < 1e-9);
+        assert_eq!(stats.depth, 45.0, "asks at or under 0.53");
+        assert_eq!((stats.duration_secs, stats.evaluations), (0.4, 2));
+
+        let summary = &observer.report().by_market()[0];
+        assert_eq!((summary.key.as_str(), summary.count), ("btc-updown-5m", 1));
+        assert!(observer.drain().is_empty());
+    }
+}
@@ -1,13 +1,18 @@
+use crate::models::position::is_mid_cycle;
 use crate::risk::position_manager::PositionManager;
+use crate::telemetry::hold_time::ExitReason;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
 use tracing::info;
 
 /// Real-time P&L tracking per strategy and overall.
 pub struct PnlTracker {
     position_mgr: Arc<PositionManager>,
     strategy_pnl: dashmap::DashMap<String, Decimal>,
+    exit_pnl: Mutex<ExitPnlBreakdown>,
     trade_log: Arc<tokio::sync::RwLock<Vec<TradeRecord>>>,
 }
 
@@ -21,6 +26,111 @@ pub struct TradeRecord {
     pub size: f64,
     pub pnl: f64,
     pub cumulative_pnl: f64,
+    /// Set when the trade closed a position
+    pub exit_reason: Option<ExitReason>,
+}
+
+/// Realized results of the exits sharing one reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReasonPnl {
+    pub trades: u64,
+    pub wins: u64,
+    pub pnl: f64,
+}
+
+impl ReasonPnl {
+    pub fn avg_pnl(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.pnl / self.trades as f64
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64
+    }
+}
+
+/// Realized P&L by exit reason: what forced dumps cost against what
+/// take-profit fills earn.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExitPnlBreakdown {
+    by_reason: BTreeMap<ExitReason, ReasonPnl>,
+}
+
+impl ExitPnlBreakdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, reason: ExitReason, pnl: f64) {
+        let entry = self.by_reason.entry(reason).or_default();
+        entry.trades += 1;
+        entry.pnl += pnl;
+        if pnl > 0.0 {
+            entry.wins += 1;
+        }
+    }
+
+    pub fn get(&self, reason: ExitReason) -> ReasonPnl {
+        self.by_reason.get(&reason).copied().unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_reason.is_empty()
+    }
+
+    /// One line per reason with exits, in `ExitReason` order.
+    pub fn lines(&self) -> Vec<String> {
+        self.by_reason
+            .iter()
+            .map(|(reason, r)| {
+                format!(
+                    "{:<11} n={:<4} win={:>3.0}% pnl={:>+8.3} avg={:>+7.4}",
+                    reason.as_str(),
+                    r.trades,
+                    r.win_rate() * 100.0,
+                    r.pnl,
+                    r.avg_pnl()
+                )
+            })
+            .collect()
+    }
+}
+
+/// Realized P&L split by entry cohort: entries made at a fresh market open
+/// against those made after joining a window mid-cycle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CohortPnl {
+    pub fresh: f64,
+    pub mid_cycle: f64,
+    pub mid_cycle_entries: usize,
+}
+
+impl CohortPnl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attribute a closed trade's P&L to the cohort its strategy tag marks.
+    pub fn record(&mut self, strategy_tag: &str, pnl: f64) {
+        if is_mid_cycle(strategy_tag) {
+            self.mid_cycle += pnl;
+        } else {
+            self.fresh += pnl;
+        }
+    }
+
+    /// Summary line against the run's `entries` in total.
+    pub fn line(&self, entries: usize) -> String {
+        format!(
+            "Fresh P&L:  {:>+.4}  |  Mid-cycle P&L: {:>+.4} ({} of {} entries)",
+            self.fresh, self.mid_cycle, self.mid_cycle_entries, entries
+        )
+    }
 }
 
 impl PnlTracker {
@@ -28,6 +138,7 @@ impl PnlTracker {
         Self {
             position_mgr,
             strategy_pnl: dashmap::DashMap::new(),
+            exit_pnl: Mutex::new(ExitPnlBreakdown::new()),
             trade_log: Arc::new(tokio::sync::RwLock::new(Vec::new())),
         }
     }
@@ -41,10 +152,24 @@ impl PnlTracker {
             .entry(strategy.clone())
             .and_modify(|v| *v += pnl)
             .or_insert(pnl);
+        if let Some(reason) = record.exit_reason {
+            self.record_exit(reason, record.pnl);
+        }
 
         self.trade_log.write().await.push(record);
     }
 
+    /// Add realized P&L to the exit-reason breakdown only, for results that
+    /// aren't one strategy's trade (e.g. a whole market resolving).
+    pub fn record_exit(&self, reason: ExitReason, pnl: f64) {
+        self.exit_pnl.lock().unwrap().record(reason, pnl);
+    }
+
+    /// Realized P&L so far for one exit reason.
+    pub fn exit_reason_pnl(&self, reason: ExitReason) -> ReasonPnl {
+        self.exit_pnl.lock().unwrap().get(reason)
+    }
+
     /// Get P&L for a specific strategy.
     pub fn strategy_pnl(&self, strategy: &str) -> Decimal {
         self.strategy_pnl
@@ -57,10 +182,13 @@ impl PnlTracker {
     pub async fn log_summary(&self) {
         let portfolio = self.position_mgr.portfolio.read().await;
         info!(
-            "=== P&L SUMMARY === capital={} daily_pnl={} total_pnl={} trades={} win_rate={:.1}%",
+            "=== P&L SUMMARY === capital={} daily_pnl={} total_pnl={} (gross {} fees {} gas {}) trades={} win_rate={:.1}%",
             portfolio.capital,
             portfolio.daily_pnl,
             portfolio.total_pnl,
+            portfolio.gross_pnl(),
+            portfolio.fees.charged,
+            portfolio.gas.charged,
             portfolio.total_trades,
             portfolio.win_rate() * 100.0,
         );
@@ -68,6 +196,19 @@ impl PnlTracker {
         for entry in self.strategy_pnl.iter() {
             info!("  Strategy {}: P&L = {}", entry.key(), entry.value());
         }
+        let mut fees: Vec<_> = portfolio.fees.by_strategy.iter().collect();
+        fees.sort();
+        for (strategy, fee) in fees {
+            info!("  Fees {strategy}: {fee}");
+        }
+        let mut gas: Vec<_> = portfolio.gas.by_strategy.iter().collect();
+        gas.sort();
+        for (strategy, usd) in gas {
+            info!("  Gas {strategy}: {usd}");
+        }
+        for line in self.exit_pnl.lock().unwrap().lines() {
+            info!("  Exit {line}");
+        }
     }
 
     /// Record a fill event (from user WS or immediate fill).
@@ -99,3 +240,59 @@ impl PnlTracker {
         self.trade_log.read().await.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_cohort_pnl_split_by_tag() {
+        let mut cohorts = CohortPnl::new();
+        cohorts.record("lag_exploit", 0.30);
+        cohorts.record("lag_exploit@mid", -0.10);
+        cohorts.record("arb_yes@mid", 0.05);
+        cohorts.mid_cycle_entries = 2;
+        assert!((cohorts.fresh - 0.30).abs() < 1e-9);
+        assert!((cohorts.mid_cycle + 0.05).abs() < 1e-9);
+        assert_eq!(cohorts.line(3), "Fresh P&L:  +0.3000  |  Mid-cycle P&L: -0.0500 (2 of 3 entries)");
+    }
+
+    #[tokio::test]
+    async fn test_exit_reason_breakdown() {
+        let tracker = PnlTracker::new(Arc::new(PositionManager::new(dec!(100))));
+        let trade = |reason, pnl| TradeRecord {
+            timestamp: Utc::now(),
+            market_slug: "m".into(),
+            strategy: "lag_exploit".into(),
+            side: "SELL".into(),
+            entry_price: 0.5,
+            size: 10.0,
+            pnl,
+            cumulative_pnl: 0.0,
+            exit_reason: reason,
+        };
+        tracker.record_trade(trade(Some(ExitReason::TakeProfit), 0.5)).await;
+        tracker.record_trade(trade(Some(ExitReason::TakeProfit), 0.25)).await;
+        tracker.record_trade(trade(Some(ExitReason::Force), -4.0)).await;
+        tracker.record_trade(trade(None, 1.0)).await;
+        tracker.record_exit(ExitReason::Resolution, 2.0);
+
+        let tp = tracker.exit_reason_pnl(ExitReason::TakeProfit);
+        assert_eq!((tp.trades, tp.wins, tp.pnl, tp.avg_pnl()), (2, 2, 0.75, 0.375));
+        assert_eq!(tracker.exit_reason_pnl(ExitReason::Force).win_rate(), 0.0);
+        assert_eq!(tracker.exit_reason_pnl(ExitReason::StopLoss), ReasonPnl::default());
+        // Every trade counts toward its strategy; only exits toward a reason
+        assert_eq!(tracker.strategy_pnl("lag_exploit"), dec!(-2.25));
+        assert_eq!(tracker.exit_reason_pnl(ExitReason::Resolution).pnl, 2.0);
+
+        // Round-trips through a session snapshot
+        let mut breakdown = ExitPnlBreakdown::new();
+        breakdown.record(ExitReason::StopLoss, -1.0);
+        let json = serde_json::to_string(&breakdown).unwrap();
+        assert!(json.contains("\"sl\""));
+        let back: ExitPnlBreakdown = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.get(ExitReason::StopLoss).pnl, -1.0);
+        assert_eq!(back.lines().len(), 1);
+    }
+}
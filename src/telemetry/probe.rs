@@ -0,0 +1,264 @@
+use crate::cli::arg;
+use crate::config::Config;
+use crate::execution::clob_client::ClobClient;
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use std::future::Future;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::connect_async;
+
+/// Probe targets. Each maps to the env var that selects its endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointKind {
+    Clob,
+    /// Authenticated order post round trip on the CLOB host
+    OrderAck,
+    Gamma,
+    BinanceWs,
+    PolygonRpc,
+}
+
+impl EndpointKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Clob => "clob",
+            Self::OrderAck => "order ack",
+            Self::Gamma => "gamma",
+            Self::BinanceWs => "binance ws",
+            Self::PolygonRpc => "polygon rpc",
+        }
+    }
+
+    /// Env var persisted by `--save`; order ack rides the CLOB host.
+    pub fn env_key(self) -> Option<&'static str> {
+        match self {
+            Self::Clob => Some("CLOB_HOST"),
+            Self::OrderAck => None,
+            Self::Gamma => Some("GAMMA_API_HOST"),
+            Self::BinanceWs => Some("BINANCE_WS_URL"),
+            Self::PolygonRpc => Some("POLYGON_RPC_URL"),
+        }
+    }
+}
+
+/// Timings for one endpoint. The first sample includes connection setup
+/// (DNS, TCP, TLS) and is reported apart from the warm ones.
+#[derive(Debug, Clone)]
+pub struct ProbeResult {
+    pub kind: EndpointKind,
+    pub url: String,
+    pub cold: Option<Duration>,
+    pub warm: Vec<Duration>,
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut sorted = self.warm.clone();
+        sorted.sort();
+        let idx = ((sorted.len() as f64 * p) as usize).min(sorted.len().checked_sub(1)?);
+        Some(sorted[idx])
+    }
+
+    pub fn p50(&self) -> Option<Duration> {
+        self.percentile(0.50)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(0.95)
+    }
+}
+
+/// Time `samples` calls of `op`. Stops at the first error.
+async fn time_samples<F, Fut>(kind: EndpointKind, url: &str, samples: usize, op: F) -> ProbeResult
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<Duration>>,
+{
+    let mut result = ProbeResult { kind, url: url.to_string(), cold: None, warm: Vec::new(), error: None };
+    for i in 0..samples.max(2) {
+        match op().await {
+            Ok(rtt) if i == 0 => result.cold = Some(rtt),
+            Ok(rtt) => result.warm.push(rtt),
+            Err(e) => {
+                result.error = Some(format!("{e:#}"));
+                break;
+            }
+        }
+    }
+    result
+}
+
+async fn http_rtt(request: reqwest::RequestBuilder) -> Result<Duration> {
+    let start = Instant::now();
+    request.send().await?.error_for_status()?;
+    Ok(start.elapsed())
+}
+
+/// WS handshake plus the first stream message.
+async fn ws_rtt(url: &str) -> Result<Duration> {
+    let start = Instant::now();
+    let (mut ws, _) = connect_async(url).await?;
+    tokio::time::timeout(Duration::from_secs(5), ws.next())
+        .await
+        .context("no message within 5s")?
+        .context("stream closed")??;
+    Ok(start.elapsed())
+}
+
+/// The fastest reachable endpoint per kind, by warm median.
+pub fn best(results: &[ProbeResult]) -> Vec<&ProbeResult> {
+    let mut best: Vec<&ProbeResult> = Vec::new();
+    for r in results.iter().filter(|r| r.error.is_none()) {
+        let Some(p50) = r.p50() else { continue };
+        match best.iter_mut().find(|b| b.kind == r.kind) {
+            Some(b) if b.p50().is_some_and(|cur| p50 < cur) => *b = r,
+            Some(_) => {}
+            None => best.push(r),
+        }
+    }
+    best
+}
+
+/// Set `key=value` in an env file, replacing an existing assignment (even a
+/// commented-out one) or appending. Creates the file if needed.
+pub fn upsert_env(path: &Path, key: &str, value: &str) -> Result<()> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    let line = format!("{key}={value}");
+    let is_key = |l: &str| {
+        l.trim_start_matches('#').trim_start().strip_prefix(key).is_some_and(|rest| rest.starts_with('='))
+    };
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let existing = lines
+        .iter()
+        .position(|l| is_key(l) && !l.starts_with('#'))
+        .or_else(|| lines.iter().position(|l| is_key(l)));
+    match existing {
+        Some(i) => lines[i] = line,
+        None => lines.push(line),
+    }
+    std::fs::write(path, lines.join("\n") + "\n").with_context(|| format!("writing {}", path.display()))
+}
+
+/// Candidate endpoints: a comma-separated `--flag` list, else the configured one.
+fn candidates(flag: &str, configured: &str) -> Vec<String> {
+    arg(flag)
+        .map(|v| v.split(',').map(|s| s.trim().trim_end_matches('/').to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_else(|| vec![configured.to_string()])
+}
+
+/// `sattebaaz probe`: measure latency from this host to every endpoint the
+/// bot uses and print a report for choosing a hosting region.
+///
+/// Flags: `--samples N` (default 10); `--clob`, `--gamma`, `--binance-ws`,
+/// `--polygon-rpc` take comma-separated candidate URLs to compare; `--save`
+/// writes the fastest of each to `.env`.
+pub async fn run(config: &Config) -> Result<()> {
+    let samples: usize = arg("--samples").map(|s| s.parse()).transpose().context("--samples")?.unwrap_or(10);
+    let http = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut results = Vec::new();
+
+    for url in candidates("--clob", &config.polymarket.clob_host) {
+        let time = format!("{url}/time");
+        results.push(time_samples(EndpointKind::Clob, &url, samples, || http_rtt(http.get(&time))).await);
+
+        if config.is_dry_run() {
+            println!("  (dry run: skipping order-ack probe against {url})");
+            continue;
+        }
+        let client = ClobClient::new(crate::config::PolymarketConfig { clob_host: url.clone(), ..config.polymarket.clone() });
+        client.init_auth().await?;
+        results.push(time_samples(EndpointKind::OrderAck, &url, samples, || async { Ok(client.probe_order_ack().await?) }).await);
+    }
+    for url in candidates("--gamma", &config.polymarket.gamma_api_host) {
+        let markets = format!("{url}/markets?limit=1");
+        results.push(time_samples(EndpointKind::Gamma, &url, samples, || http_rtt(http.get(&markets))).await);
+    }
+    for url in candidates("--binance-ws", &config.binance.ws_url) {
+        // Each sample is a fresh connection — WS latency that matters is the reconnect
+        let stream = format!("{url}/ws/btcusdt@aggTrade");
+        results.push(time_samples(EndpointKind::BinanceWs, &url, samples.min(3), || ws_rtt(&stream)).await);
+    }
+    for url in candidates("--polygon-rpc", &config.polymarket.polygon_rpc_url) {
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_blockNumber", "params": [] });
+        results.push(
+            time_samples(EndpointKind::PolygonRpc, &url, samples, || http_rtt(http.post(&url).json(&body))).await,
+        );
+    }
+
+    let ms = |d: Option<Duration>| d.map(|d| format!("{:.1}", d.as_secs_f64() * 1000.0)).unwrap_or_else(|| "-".into());
+    println!("  {:<12} {:>9} {:>9} {:>9}  endpoint", "target", "cold ms", "p50 ms", "p95 ms");
+    for r in &results {
+        println!("  {:<12} {:>9} {:>9} {:>9}  {}", r.kind.label(), ms(r.cold), ms(r.p50()), ms(r.p95()), r.url);
+        if let Some(e) = &r.error {
+            println!("  {:<12} error: {e}", "");
+        }
+    }
+
+    let chosen = best(&results);
+    println!();
+    for r in &chosen {
+        if let Some(key) = r.kind.env_key() {
+            println!("  {key}={}", r.url);
+        }
+    }
+    if std::env::args().any(|a| a == "--save") {
+        let path = Path::new(".env");
+        for r in &chosen {
+            if let Some(key) = r.kind.env_key() {
+                upsert_env(path, key, &r.url)?;
+            }
+        }
+        println!("  Saved to {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(kind: EndpointKind, url: &str, warm_ms: &[u64]) -> ProbeResult {
+        ProbeResult {
+            kind,
+            url: url.into(),
+            cold: Some(Duration::from_millis(100)),
+            warm: warm_ms.iter().map(|&m| Duration::from_millis(m)).collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_best_picks_fastest_reachable_per_kind() {
+        let mut down = result(EndpointKind::Clob, "https://down", &[1]);
+        down.error = Some("timeout".into());
+        let results = vec![
+            result(EndpointKind::Clob, "https://far", &[80, 90, 85]),
+            result(EndpointKind::Clob, "https://near", &[5, 7, 6]),
+            down,
+            result(EndpointKind::Gamma, "https://gamma", &[20]),
+        ];
+        let chosen = best(&results);
+        assert_eq!(chosen.len(), 2);
+        assert_eq!(chosen[0].url, "https://near");
+        assert_eq!(chosen[0].p50(), Some(Duration::from_millis(6)));
+        assert_eq!(chosen[1].kind, EndpointKind::Gamma);
+    }
+
+    #[test]
+    fn test_upsert_env() {
+        let path = std::env::temp_dir().join(format!("sattebaaz-env-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "A=1\n# CLOB_HOST=https://old\nCLOB_HOSTS=x\n").unwrap();
+        upsert_env(&path, "CLOB_HOST", "https://new").unwrap();
+        upsert_env(&path, "GAMMA_API_HOST", "https://g").unwrap();
+        upsert_env(&path, "CLOB_HOST", "https://newer").unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text, "A=1\nCLOB_HOST=https://newer\nCLOB_HOSTS=x\nGAMMA_API_HOST=https://g\n");
+        std::fs::remove_file(path).unwrap();
+    }
+}
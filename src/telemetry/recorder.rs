@@ -0,0 +1,194 @@
+use crate::models::market::{Asset, Market, OrderBook, Side};
+use crate::models::order::{OrderIntent, OrderSide};
+use anyhow::{Context, Result};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Best level on each side of one book.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TopOfBook {
+    pub bid: Option<f64>,
+    pub bid_size: f64,
+    pub ask: Option<f64>,
+    pub ask_size: f64,
+}
+
+impl TopOfBook {
+    pub fn of(book: &OrderBook) -> Self {
+        let f = |d: rust_decimal::Decimal| d.to_f64().unwrap_or(0.0);
+        let bid = book.best_bid();
+        let ask = book.best_ask();
+        Self {
+            bid: bid.map(|(p, _)| f(p)),
+            bid_size: bid.map(|(_, s)| f(s)).unwrap_or(0.0),
+            ask: ask.map(|(p, _)| f(p)),
+            ask_size: ask.map(|(_, s)| f(s)).unwrap_or(0.0),
+        }
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.bid? + self.ask?) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.ask? - self.bid?)
+    }
+}
+
+/// One line of a market-data recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Recorded {
+    /// Binance price, sampled
+    Tick { ts_ms: i64, asset: Asset, price: f64 },
+    /// Both books of a market as the strategies saw them at evaluation
+    Book {
+        ts_ms: i64,
+        market: String,
+        asset: Asset,
+        reference_price: f64,
+        remaining_secs: f64,
+        yes: TopOfBook,
+        no: TopOfBook,
+    },
+    /// Strategy output, before risk checks
+    Signal {
+        ts_ms: i64,
+        market: String,
+        strategy: String,
+        outcome: Side,
+        side: OrderSide,
+        price: f64,
+        size: f64,
+    },
+    /// Window close, whether or not we traded it
+    Outcome {
+        ts_ms: i64,
+        market: String,
+        asset: Asset,
+        reference_price: f64,
+        final_price: f64,
+        winner: Side,
+    },
+}
+
+/// Append-only JSONL recording of prices, books, signals and outcomes, the
+/// raw material for `export_features`.
+pub struct MarketRecorder {
+    file: Mutex<BufWriter<File>>,
+}
+
+impl MarketRecorder {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("opening recording {}", path.display()))?;
+        Ok(Self { file: Mutex::new(BufWriter::new(file)) })
+    }
+
+    /// Buffer one record, logging instead of failing.
+    pub fn record(&self, record: &Recorded) {
+        let result = serde_json::to_string(record)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| Ok(writeln!(self.file.lock().unwrap(), "{line}")?));
+        if let Err(e) = result {
+            warn!("Market recording write failed: {e}");
+        }
+    }
+
+    pub fn flush(&self) {
+        if let Err(e) = self.file.lock().unwrap().flush() {
+            warn!("Market recording flush failed: {e}");
+        }
+    }
+
+    pub fn record_books(&self, market: &Market, yes_book: &OrderBook, no_book: &OrderBook) {
+        let now = chrono::Utc::now();
+        self.record(&Recorded::Book {
+            ts_ms: now.timestamp_millis(),
+            market: market.slug.clone(),
+            asset: market.asset,
+            reference_price: market.reference_price,
+            remaining_secs: (market.close_time - now).num_milliseconds() as f64 / 1000.0,
+            yes: TopOfBook::of(yes_book),
+            no: TopOfBook::of(no_book),
+        });
+    }
+
+    pub fn record_signals(&self, market: &str, intents: &[OrderIntent]) {
+        let ts_ms = chrono::Utc::now().timestamp_millis();
+        for intent in intents {
+            self.record(&Recorded::Signal {
+                ts_ms,
+                market: market.to_string(),
+                strategy: intent.strategy_tag.clone(),
+                outcome: intent.market_side,
+                side: intent.order_side,
+                price: intent.price.to_f64().unwrap_or(0.0),
+                size: intent.size.to_f64().unwrap_or(0.0),
+            });
+        }
+    }
+
+    /// Record Binance prints at most once per `sample_ms` per asset, and
+    /// flush the file every second, until shutdown.
+    pub fn spawn_ticks(
+        self: &Arc<Self>,
+        mut prices: broadcast::Receiver<(Asset, f64)>,
+        sample_ms: i64,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let recorder = self.clone();
+        tokio::spawn(async move {
+            let mut last: HashMap<Asset, i64> = HashMap::new();
+            let mut flush = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                tokio::select! {
+                    tick = prices.recv() => match tick {
+                        Ok((asset, price)) => {
+                            let ts_ms = chrono::Utc::now().timestamp_millis();
+                            if last.get(&asset).is_some_and(|&t| ts_ms - t < sample_ms) {
+                                continue;
+                            }
+                            last.insert(asset, ts_ms);
+                            recorder.record(&Recorded::Tick { ts_ms, asset, price });
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = flush.tick() => recorder.flush(),
+                    _ = shutdown.recv() => break,
+                }
+            }
+            recorder.flush();
+        });
+    }
+
+    /// All records in file order. Unparseable lines are skipped.
+    pub fn read(path: impl AsRef<Path>) -> Result<Vec<Recorded>> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("reading recording {}", path.display()))?;
+        let mut records = Vec::new();
+        for (n, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => records.push(record),
+                Err(e) => warn!("{}:{}: skipping bad recording line: {e}", path.display(), n + 1),
+            }
+        }
+        Ok(records)
+    }
+}
@@ -0,0 +1,316 @@
+use crate::execution::fill_tracker::{FillTracker, RestingQuote};
+use crate::execution::session;
+use crate::feeds::binance::BinanceFeed;
+use crate::feeds::matic_price::MaticPrice;
+use crate::feeds::polymarket::PolymarketFeed;
+use crate::models::market::{Asset, Duration};
+use crate::models::position::{FeeLedger, Position, SettlingKind, StraddlePosition};
+use crate::risk::approval::{ApprovalQueue, PendingOrder};
+use crate::risk::canary::CanaryRecord;
+use crate::risk::position_manager::PositionManager;
+use crate::risk::risk_manager::RiskManager;
+use crate::risk::safe_mode::SafeModeStatus;
+use crate::strategies::orchestrator::StrategyOrchestrator;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// Everything `get_state` reads from: the process's shared components.
+#[derive(Clone)]
+pub struct StateSources {
+    pub poly: Arc<PolymarketFeed>,
+    pub binance: Arc<BinanceFeed>,
+    pub pos_mgr: Arc<PositionManager>,
+    pub tracker: Arc<FillTracker>,
+    pub risk: Arc<RiskManager>,
+    pub orchestrator: Arc<StrategyOrchestrator>,
+    pub approvals: Option<Arc<ApprovalQueue>>,
+    /// Prices gas; None in dry runs, which send no transactions
+    pub matic: Option<Arc<MaticPrice>>,
+}
+
+/// Capital and realized results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalState {
+    pub capital: Decimal,
+    pub starting_capital: Decimal,
+    /// Cost of everything held
+    pub exposure: Decimal,
+    pub daily_pnl: Decimal,
+    /// Net of fees and gas
+    pub total_pnl: Decimal,
+    /// `total_pnl` before fees and gas
+    #[serde(default)]
+    pub gross_pnl: Decimal,
+    /// Every fee paid, open positions' entries included, per strategy and market
+    #[serde(default)]
+    pub fees: FeeLedger,
+    /// Gas for on-chain transactions in USD, per strategy and market
+    #[serde(default)]
+    pub gas: FeeLedger,
+    pub swept_total: Decimal,
+    /// Resolution payouts carried until redeemed on-chain
+    #[serde(default)]
+    pub pending_redemption: Decimal,
+    /// Sell proceeds waiting for their trades to confirm on-chain
+    #[serde(default)]
+    pub pending_settlement: Decimal,
+    pub total_trades: u64,
+    pub winning_trades: u64,
+    pub consecutive_losses: u32,
+}
+
+impl CapitalState {
+    pub fn win_rate(&self) -> f64 {
+        if self.total_trades == 0 {
+            return 0.0;
+        }
+        self.winning_trades as f64 / self.total_trades as f64
+    }
+}
+
+/// What the risk layer is currently doing to trading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFlags {
+    pub killed: bool,
+    /// Strategies paused from the dashboard
+    pub paused: bool,
+    pub size_reduction_active: bool,
+    pub size_mult: f64,
+    pub safe_mode: SafeModeStatus,
+    /// Age of the risk watchdog's last check while it's too old to trade on
+    #[serde(default)]
+    pub watchdog_stale_ms: Option<i64>,
+    /// Canary families and their probation so far
+    pub canary: Vec<(String, CanaryRecord)>,
+    /// Entries waiting for operator approval
+    pub pending_approvals: Vec<PendingOrder>,
+}
+
+/// Top of one outcome's book.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookTop {
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+    pub age_ms: i64,
+}
+
+/// One discovered market window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketState {
+    pub slug: String,
+    pub asset: Asset,
+    pub duration: Duration,
+    pub reference_price: f64,
+    pub remaining_secs: f64,
+    pub yes: Option<BookTop>,
+    pub no: Option<BookTop>,
+}
+
+/// Latest Binance print for one asset.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceFeedState {
+    pub asset: Asset,
+    pub price: f64,
+    pub age_ms: i64,
+}
+
+/// Latest MATIC/USD ticker read, which prices gas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GasTokenPriceState {
+    pub price: f64,
+    pub age_ms: i64,
+}
+
+/// How fresh each feed is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedHealth {
+    /// Assets that have had a print
+    pub binance: Vec<PriceFeedState>,
+    pub polymarket_markets: usize,
+    pub polymarket_books: usize,
+    /// Age of the stalest book among active markets
+    pub oldest_book_age_ms: Option<i64>,
+    /// None until the ticker has been read
+    #[serde(default)]
+    pub matic: Option<GasTokenPriceState>,
+}
+
+/// The whole engine at one instant: one struct for the dashboard, the
+/// control API, state snapshots on disk and tests, instead of each asking
+/// every component separately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineState {
+    pub time: DateTime<Utc>,
+    pub capital: CapitalState,
+    pub positions: Vec<Position>,
+    pub straddles: Vec<StraddlePosition>,
+    pub resting_orders: Vec<RestingQuote>,
+    pub risk: RiskFlags,
+    /// Sorted by slug
+    pub markets: Vec<MarketState>,
+    pub feeds: FeedHealth,
+}
+
+impl StateSources {
+    /// Snapshot of every component, taken now.
+    pub async fn get_state(&self) -> EngineState {
+        let now = Utc::now();
+        let age = |t: DateTime<Utc>| (now - t).num_milliseconds().max(0);
+
+        let (capital, positions, straddles) = {
+            let p = self.pos_mgr.portfolio.read().await;
+            let capital = CapitalState {
+                capital: p.capital,
+                starting_capital: p.starting_capital,
+                exposure: p.total_exposure(),
+                daily_pnl: p.daily_pnl,
+                total_pnl: p.total_pnl,
+                gross_pnl: p.gross_pnl(),
+                fees: p.fees.clone(),
+                gas: p.gas.clone(),
+                swept_total: p.swept_total,
+                pending_redemption: p.settling_value(Some(SettlingKind::Redemption)),
+                pending_settlement: p.settling_value(Some(SettlingKind::Settlement)),
+                total_trades: p.total_trades,
+                winning_trades: p.winning_trades,
+                consecutive_losses: p.consecutive_losses,
+            };
+            (capital, p.positions.clone(), p.straddles.clone())
+        };
+
+        let mut resting_orders: Vec<RestingQuote> = self.tracker.quotes.iter().map(|q| q.value().clone()).collect();
+        resting_orders.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+
+        let risk = RiskFlags {
+            killed: self.risk.killed.load(Ordering::Relaxed),
+            paused: self.orchestrator.is_paused(),
+            size_reduction_active: self.risk.size_reduction_active.load(Ordering::Relaxed),
+            size_mult: self.risk.current_size_multiplier().await,
+            safe_mode: self.risk.safe_mode.status(),
+            watchdog_stale_ms: self.risk.watchdog.stale_ms(Utc::now()),
+            canary: self.risk.canary.as_ref().map(|c| c.records()).unwrap_or_default(),
+            pending_approvals: self.approvals.as_ref().map(|q| q.pending()).unwrap_or_default(),
+        };
+
+        let top = |token: &str| {
+            self.poly.get_book(token).map(|b| BookTop {
+                bid: b.best_bid().map(|(p, _)| p),
+                ask: b.best_ask().map(|(p, _)| p),
+                age_ms: age(b.timestamp),
+            })
+        };
+        let mut markets: Vec<MarketState> = self
+            .poly
+            .markets
+            .iter()
+            .map(|m| MarketState {
+                slug: m.slug.clone(),
+                asset: m.asset,
+                duration: m.duration,
+                reference_price: m.reference_price,
+                remaining_secs: m.time_remaining_secs(),
+                yes: top(&m.yes_token_id),
+                no: top(&m.no_token_id),
+            })
+            .collect();
+        markets.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        let feeds = FeedHealth {
+            binance: Asset::ALL
+                .iter()
+                .filter_map(|&asset| {
+                    let p = self.binance.prices.get(asset)?;
+                    Some(PriceFeedState { asset, price: p.price, age_ms: age(p.timestamp) })
+                })
+                .collect(),
+            polymarket_markets: self.poly.market_count(),
+            polymarket_books: self.poly.books.len(),
+            oldest_book_age_ms: markets
+                .iter()
+                .filter(|m| m.remaining_secs > 0.0)
+                .flat_map(|m| [m.yes, m.no])
+                .flatten()
+                .map(|t| t.age_ms)
+                .max(),
+            matic: self
+                .matic
+                .as_ref()
+                .and_then(|m| m.state())
+                .map(|p| GasTokenPriceState { price: p.price, age_ms: age(p.timestamp) }),
+        };
+
+        EngineState { time: now, capital, positions, straddles, resting_orders, risk, markets, feeds }
+    }
+
+    /// Write the current state to `path` (atomically, as a session snapshot).
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        session::save(path, &self.get_state().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::market::{Market, OrderBook, Side};
+    use crate::models::order::{Fill, OrderSide};
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_get_state_round_trips() {
+        let config = Config::default();
+        let poly = Arc::new(PolymarketFeed::new(config.polymarket.clone()));
+        let market = Market::new("btc-updown-5m-1".into(), Asset::BTC, Duration::FiveMin, "tok-yes".into(), "tok-no".into());
+        poly.markets.insert(market.slug.clone(), market);
+        let mut book = OrderBook::new("tok-yes".into());
+        book.bids.insert(dec!(0.48), dec!(10));
+        book.asks.insert(dec!(0.52), dec!(10));
+        poly.books.insert("tok-yes".into(), Arc::new(book));
+        let binance = Arc::new(BinanceFeed::new(config.binance.clone()));
+        binance.prices.record(Asset::BTC, 65_000.0, Utc::now());
+
+        let pos_mgr = Arc::new(PositionManager::new(dec!(100)));
+        let fill = Fill {
+            order_id: "o1".into(),
+            token_id: "tok-yes".into(),
+            side: OrderSide::Buy,
+            price: dec!(0.50),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            fee: Decimal::ZERO,
+        };
+        pos_mgr.record_fill(&fill, "btc-updown-5m-1", Side::Yes, "lag_exploit").await;
+        let sources = StateSources {
+            poly,
+            binance,
+            risk: Arc::new(RiskManager::new(config.risk.clone(), pos_mgr.clone())),
+            pos_mgr,
+            tracker: Arc::new(FillTracker::new()),
+            orchestrator: Arc::new(StrategyOrchestrator::new(config.strategy.clone())),
+            approvals: None,
+            matic: Some(Arc::new(MaticPrice::new("", 0.25))),
+        };
+        sources.matic.as_ref().unwrap().set(0.3, Utc::now());
+
+        let state = sources.get_state().await;
+        assert_eq!((state.capital.capital, state.capital.exposure), (dec!(95), dec!(5)));
+        assert_eq!(state.positions.len(), 1);
+        assert!(!state.risk.killed && !state.risk.paused);
+        assert_eq!(state.risk.watchdog_stale_ms, None, "a fresh watchdog has time for its first check");
+        assert_eq!(state.markets[0].yes.map(|t| (t.bid, t.ask)), Some((Some(dec!(0.48)), Some(dec!(0.52)))));
+        assert_eq!(state.markets[0].no, None);
+        assert_eq!(state.feeds.binance.len(), 1);
+        assert_eq!(state.feeds.matic.map(|m| m.price), Some(0.3));
+        assert_eq!((state.feeds.polymarket_markets, state.feeds.polymarket_books), (1, 1));
+
+        let json = serde_json::to_string(&state).unwrap();
+        let back: EngineState = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.capital, state.capital);
+        assert_eq!(back.markets, state.markets);
+    }
+}
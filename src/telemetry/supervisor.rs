@@ -0,0 +1,186 @@
+use crate::telemetry::alerts::{AlertManager, AlertSeverity};
+use dashmap::DashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::{JoinError, JoinHandle};
+use tracing::{error, info};
+
+/// First restart delay; doubles on each crash in a row.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// A task that ran this long before dying starts its backoff over.
+const HEALTHY_RUN: Duration = Duration::from_secs(300);
+
+/// How one supervised task is doing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskStatus {
+    pub running: bool,
+    pub restarts: u32,
+    /// Why it last stopped: a panic message or "exited"
+    pub last_exit: Option<String>,
+}
+
+/// Keeps the background loops alive.
+///
+/// Each loop is spawned from a factory so it can be built again: when the
+/// task panics — or returns before shutdown, which a loop only does when
+/// its input channel is gone — the supervisor logs it, raises a critical
+/// alert and spawns a fresh one after a backoff. A crashed risk watchdog
+/// comes back within seconds instead of leaving the bot trading unguarded.
+pub struct Supervisor {
+    alerts: Arc<AlertManager>,
+    shutdown: broadcast::Sender<()>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    tasks: DashMap<&'static str, TaskStatus>,
+}
+
+impl Supervisor {
+    pub fn new(alerts: Arc<AlertManager>, shutdown: broadcast::Sender<()>) -> Self {
+        Self {
+            alerts,
+            shutdown,
+            initial_backoff: INITIAL_BACKOFF,
+            max_backoff: MAX_BACKOFF,
+            tasks: DashMap::new(),
+        }
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Run `make()` as the task `name`, restarting it whenever it stops
+    /// before shutdown. The returned handle is the supervisor's own loop.
+    pub fn spawn<F, Fut>(self: &Arc<Self>, name: &'static str, make: F) -> JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        let mut shutdown_rx = self.shutdown.subscribe();
+        self.tasks.insert(name, TaskStatus { running: true, ..Default::default() });
+
+        tokio::spawn(async move {
+            let mut backoff = supervisor.initial_backoff;
+            loop {
+                let started = tokio::time::Instant::now();
+                let result = tokio::spawn(make()).await;
+                // Loops return on shutdown too; that isn't a crash
+                if !matches!(shutdown_rx.try_recv(), Err(broadcast::error::TryRecvError::Empty)) {
+                    supervisor.set(name, |s| s.running = false);
+                    break;
+                }
+                if started.elapsed() >= HEALTHY_RUN {
+                    backoff = supervisor.initial_backoff;
+                }
+
+                let reason = exit_reason(result);
+                let restarts = supervisor.set(name, |s| {
+                    s.running = false;
+                    s.restarts += 1;
+                    s.last_exit = Some(reason.clone());
+                });
+                error!(task = name, restarts, "Task {name} stopped ({reason}) — restarting in {backoff:?}");
+                supervisor
+                    .alerts
+                    .send_at(AlertSeverity::Critical, &format!("Task {name} stopped ({reason}); restarting in {backoff:?}"))
+                    .await;
+
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown_rx.recv() => break,
+                }
+                backoff = (backoff * 2).min(supervisor.max_backoff);
+                supervisor.set(name, |s| s.running = true);
+                info!(task = name, "Task {name} restarted");
+            }
+        })
+    }
+
+    /// Every supervised task by name.
+    pub fn status(&self) -> Vec<(&'static str, TaskStatus)> {
+        let mut tasks: Vec<_> = self.tasks.iter().map(|t| (*t.key(), t.value().clone())).collect();
+        tasks.sort_by_key(|(name, _)| *name);
+        tasks
+    }
+
+    /// Tasks waiting out a backoff.
+    pub fn down(&self) -> Vec<&'static str> {
+        self.status().into_iter().filter(|(_, s)| !s.running).map(|(name, _)| name).collect()
+    }
+
+    /// Update `name`'s status; returns its restart count.
+    fn set(&self, name: &'static str, f: impl FnOnce(&mut TaskStatus)) -> u32 {
+        let mut status = self.tasks.entry(name).or_default();
+        f(&mut status);
+        status.restarts
+    }
+}
+
+fn exit_reason(result: Result<(), JoinError>) -> String {
+    match result {
+        Ok(()) => "exited".to_string(),
+        Err(e) if e.is_panic() => {
+            let panic = e.into_panic();
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown payload".to_string());
+            format!("panicked: {message}")
+        }
+        Err(e) => format!("cancelled: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_restarts_crashed_task_until_shutdown() {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let alerts = Arc::new(AlertManager::new(crate::config::Config::default().telemetry));
+        let supervisor = Arc::new(
+            Supervisor::new(alerts, shutdown_tx.clone()).with_backoff(Duration::from_millis(5), Duration::from_millis(20)),
+        );
+
+        // Panics twice, then runs until shutdown like a well-behaved loop
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        let tx = shutdown_tx.clone();
+        let handle = supervisor.spawn("watchdog", move || {
+            let run = counter.fetch_add(1, Ordering::SeqCst);
+            let mut shutdown_rx = tx.subscribe();
+            async move {
+                if run < 2 {
+                    panic!("boom {run}");
+                }
+                let _ = shutdown_rx.recv().await;
+            }
+        });
+
+        while runs.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let (name, status) = supervisor.status().remove(0);
+        assert_eq!(name, "watchdog");
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.last_exit.as_deref(), Some("panicked: boom 1"));
+        assert!(status.running);
+        assert!(supervisor.down().is_empty());
+
+        // A shutdown exit is not restarted
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(supervisor.down(), ["watchdog"]);
+        assert_eq!(supervisor.status()[0].1.restarts, 2);
+    }
+}
@@ -0,0 +1,400 @@
+use crate::models::market::OrderBook;
+use crate::models::order::{Fill, OrderIntent, OrderResult, OrderSide, OrderType};
+use crate::models::position::{quote_level, strategy_bucket};
+use dashmap::DashMap;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// How long after a fill the mid is sampled for adverse selection
+pub const MARKOUT_HORIZON: Duration = Duration::from_secs(10);
+
+/// Decisions for orders that never fill are dropped after this long
+const DECISION_TTL: Duration = Duration::from_secs(3600);
+
+/// How an order was worked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExecStyle {
+    /// FOK/FAK: crosses the spread
+    Taker,
+    /// Post-only limit: never crosses
+    Maker,
+    /// Plain GTC/GTD: rests, but may cross on arrival
+    Limit,
+}
+
+impl ExecStyle {
+    pub fn of(intent: &OrderIntent) -> Self {
+        match intent.order_type {
+            OrderType::FOK | OrderType::FAK => Self::Taker,
+            _ if intent.post_only => Self::Maker,
+            _ => Self::Limit,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Taker => "taker",
+            Self::Maker => "maker",
+            Self::Limit => "limit",
+        }
+    }
+}
+
+/// Book state when an order was decided on.
+#[derive(Debug, Clone)]
+struct Decision {
+    strategy: String,
+    style: ExecStyle,
+    side: OrderSide,
+    mid: f64,
+    best_bid: f64,
+    best_ask: f64,
+    at: Instant,
+}
+
+/// One fill measured against its decision.
+#[derive(Debug, Clone)]
+pub struct TcaFill {
+    pub token_id: String,
+    pub strategy: String,
+    pub style: ExecStyle,
+    pub size: f64,
+    /// Paid beyond the touch (ask for buys, bid for sells); negative = improvement
+    pub slippage: f64,
+    /// Better than the decision mid; negative = paid spread
+    pub spread_capture: f64,
+    /// Decision mid minus the mid `MARKOUT_HORIZON` later, signed so positive
+    /// = the market moved against us. None until marked.
+    pub adverse_selection: Option<f64>,
+    sign: f64,
+    decision_mid: f64,
+    filled_at: Instant,
+}
+
+/// Size-weighted TCA figures for one group of fills, in price units per share.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TcaSummary {
+    pub fills: usize,
+    pub shares: f64,
+    pub slippage: f64,
+    pub spread_capture: f64,
+    /// Over marked fills only
+    pub adverse_selection: f64,
+    pub marked: usize,
+}
+
+/// Fill statistics for one market-making ladder level, for tuning spacing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteLevelSummary {
+    /// Quotes submitted at this level
+    pub quotes: u64,
+    /// Fills per quote submitted
+    pub fill_rate: f64,
+    pub tca: TcaSummary,
+}
+
+/// Post-trade transaction cost analysis.
+///
+/// Snapshots the book for every order at submit, then compares each fill
+/// against it: slippage vs the touch, spread captured vs the mid, and adverse
+/// selection from where the mid went after the fill. Spread capture minus
+/// adverse selection is the fill's markout per share.
+pub struct TcaTracker {
+    decisions: DashMap<String, Decision>,
+    fills: Mutex<Vec<TcaFill>>,
+    /// Market-making ladder level → quotes submitted
+    level_quotes: DashMap<usize, u64>,
+}
+
+impl TcaTracker {
+    pub fn new() -> Self {
+        Self {
+            decisions: DashMap::new(),
+            fills: Mutex::new(Vec::new()),
+            level_quotes: DashMap::new(),
+        }
+    }
+
+    /// Record the decision-time book for a submitted order.
+    pub fn on_submit(&self, result: &OrderResult, intent: &OrderIntent, book: &OrderBook) {
+        let (Some((bid, _)), Some((ask, _))) = (book.best_bid(), book.best_ask()) else {
+            return;
+        };
+        if result.order_id.is_empty() {
+            return;
+        }
+        if let Some(level) = quote_level(&intent.strategy_tag) {
+            *self.level_quotes.entry(level).or_default() += 1;
+        }
+        let (best_bid, best_ask) = (to_f64(bid), to_f64(ask));
+        self.decisions.insert(
+            result.order_id.clone(),
+            Decision {
+                strategy: intent.strategy_tag.clone(),
+                style: ExecStyle::of(intent),
+                side: intent.order_side,
+                mid: (best_bid + best_ask) / 2.0,
+                best_bid,
+                best_ask,
+                at: Instant::now(),
+            },
+        );
+    }
+
+    /// Measure a fill. Fills of orders submitted elsewhere are ignored.
+    pub fn on_fill(&self, fill: &Fill) {
+        let Some(d) = self.decisions.get(&fill.order_id).map(|d| d.clone()) else {
+            return;
+        };
+        let price = to_f64(fill.price);
+        let sign = if d.side == OrderSide::Buy { 1.0 } else { -1.0 };
+        let touch = if d.side == OrderSide::Buy { d.best_ask } else { d.best_bid };
+        self.fills.lock().unwrap().push(TcaFill {
+            token_id: fill.token_id.clone(),
+            strategy: d.strategy,
+            style: d.style,
+            size: to_f64(fill.size),
+            slippage: sign * (price - touch),
+            spread_capture: sign * (d.mid - price),
+            adverse_selection: None,
+            sign,
+            decision_mid: d.mid,
+            filled_at: Instant::now(),
+        });
+    }
+
+    /// Sample the mid for fills older than `MARKOUT_HORIZON`, and forget
+    /// decisions too old to still fill. Call periodically; returns the fills
+    /// marked by this call.
+    pub fn mark(&self, mid_of: impl Fn(&str) -> Option<f64>) -> Vec<TcaFill> {
+        let mut marked = Vec::new();
+        for fill in self.fills.lock().unwrap().iter_mut() {
+            if fill.adverse_selection.is_none() && fill.filled_at.elapsed() >= MARKOUT_HORIZON {
+                if let Some(mid) = mid_of(&fill.token_id) {
+                    fill.adverse_selection = Some(fill.sign * (fill.decision_mid - mid));
+                    marked.push(fill.clone());
+                }
+            }
+        }
+        self.decisions.retain(|_, d| d.at.elapsed() < DECISION_TTL);
+        marked
+    }
+
+    pub fn fills(&self) -> Vec<TcaFill> {
+        self.fills.lock().unwrap().clone()
+    }
+
+    /// Summaries keyed by strategy family.
+    pub fn by_strategy(&self) -> BTreeMap<&'static str, TcaSummary> {
+        self.summarize(|f| Some(strategy_bucket(&f.strategy)))
+    }
+
+    /// Summaries keyed by execution style.
+    pub fn by_style(&self) -> BTreeMap<&'static str, TcaSummary> {
+        self.summarize(|f| Some(f.style.name()))
+    }
+
+    /// Market-making fills by ladder level (1 = nearest fair value), with how
+    /// often each level's quotes fill.
+    pub fn by_quote_level(&self) -> BTreeMap<usize, QuoteLevelSummary> {
+        let mut tca = self.summarize(|f| quote_level(&f.strategy));
+        self.level_quotes
+            .iter()
+            .map(|e| {
+                let (level, quotes) = (*e.key(), *e.value());
+                let tca = tca.remove(&level).unwrap_or_default();
+                let fill_rate = if quotes > 0 { tca.fills as f64 / quotes as f64 } else { 0.0 };
+                (level, QuoteLevelSummary { quotes, fill_rate, tca })
+            })
+            .collect()
+    }
+
+    fn summarize<K: Ord>(&self, key: impl Fn(&TcaFill) -> Option<K>) -> BTreeMap<K, TcaSummary> {
+        let mut groups: BTreeMap<K, (TcaSummary, f64)> = BTreeMap::new();
+        for f in self.fills.lock().unwrap().iter() {
+            let Some(k) = key(f) else { continue };
+            let (s, marked_shares) = groups.entry(k).or_default();
+            s.fills += 1;
+            s.shares += f.size;
+            s.slippage += f.slippage * f.size;
+            s.spread_capture += f.spread_capture * f.size;
+            if let Some(adverse) = f.adverse_selection {
+                s.marked += 1;
+                s.adverse_selection += adverse * f.size;
+                *marked_shares += f.size;
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(k, (mut s, marked_shares))| {
+                if s.shares > 0.0 {
+                    s.slippage /= s.shares;
+                    s.spread_capture /= s.shares;
+                }
+                if marked_shares > 0.0 {
+                    s.adverse_selection /= marked_shares;
+                }
+                (k, s)
+            })
+            .collect()
+    }
+
+    /// Log the TCA report (cents per share).
+    pub fn log_summary(&self) {
+        for (title, groups) in [("strategy", self.by_strategy()), ("style", self.by_style())] {
+            for (key, s) in groups {
+                info!(
+                    "TCA [{title}={key}]: fills={} shares={:.1} slippage={:+.2}c capture={:+.2}c adverse={:+.2}c ({} marked)",
+                    s.fills,
+                    s.shares,
+                    s.slippage * 100.0,
+                    s.spread_capture * 100.0,
+                    s.adverse_selection * 100.0,
+                    s.marked,
+                );
+            }
+        }
+        for (level, l) in self.by_quote_level() {
+            info!(
+                "TCA [mm level={level}]: quotes={} fill_rate={:.1}% shares={:.1} capture={:+.2}c adverse={:+.2}c",
+                l.quotes,
+                l.fill_rate * 100.0,
+                l.tca.shares,
+                l.tca.spread_capture * 100.0,
+                l.tca.adverse_selection * 100.0,
+            );
+        }
+    }
+}
+
+impl Default for TcaTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_f64(d: Decimal) -> f64 {
+    d.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::market::Side;
+    use crate::models::order::OrderStatus;
+    use rust_decimal_macros::dec;
+
+    fn book(bid: Decimal, ask: Decimal) -> OrderBook {
+        let mut book = OrderBook::new("yes".into());
+        book.bids.insert(bid, dec!(100));
+        book.asks.insert(ask, dec!(100));
+        book
+    }
+
+    fn submit(tca: &TcaTracker, id: &str, tag: &str, side: OrderSide, order_type: OrderType, post_only: bool) {
+        let intent = OrderIntent {
+            token_id: "yes".into(),
+            market_side: Side::Yes,
+            order_side: side,
+            price: dec!(0.50),
+            size: dec!(10),
+            order_type,
+            post_only,
+            expiration: None,
+            strategy_tag: tag.into(),
+            group_id: None,
+        };
+        let result = OrderResult {
+            order_id: id.into(),
+            token_id: "yes".into(),
+            status: OrderStatus::Open,
+            filled_size: Decimal::ZERO,
+            avg_fill_price: Decimal::ZERO,
+            remaining_size: dec!(10),
+            timestamp: chrono::Utc::now(),
+            error_msg: None,
+        };
+        tca.on_submit(&result, &intent, &book(dec!(0.48), dec!(0.52)));
+    }
+
+    fn fill(id: &str, side: OrderSide, price: Decimal, size: Decimal) -> Fill {
+        Fill {
+            order_id: id.into(),
+            token_id: "yes".into(),
+            side,
+            price,
+            size,
+            timestamp: chrono::Utc::now(),
+            fee: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_costs_vs_decision_book() {
+        let tca = TcaTracker::new();
+        submit(&tca, "lag", "lag_exploit", OrderSide::Buy, OrderType::FOK, false);
+        submit(&tca, "mm", "mm_ask", OrderSide::Sell, OrderType::GTC, true);
+
+        // Taker buy through the 0.52 ask; maker sell at 0.51, a cent above mid
+        tca.on_fill(&fill("lag", OrderSide::Buy, dec!(0.53), dec!(10)));
+        tca.on_fill(&fill("mm", OrderSide::Sell, dec!(0.51), dec!(10)));
+        tca.on_fill(&fill("unknown", OrderSide::Buy, dec!(0.50), dec!(10)));
+
+        let by_strategy = tca.by_strategy();
+        let lag = &by_strategy["lag"];
+        assert!((lag.slippage - 0.01).abs() < 1e-9);
+        assert!((lag.spread_capture + 0.03).abs() < 1e-9);
+        let mm = &by_strategy["mm"];
+        assert!((mm.slippage + 0.03).abs() < 1e-9, "sold above the bid");
+        assert!((mm.spread_capture - 0.01).abs() < 1e-9);
+
+        let by_style = tca.by_style();
+        assert_eq!(by_style.keys().copied().collect::<Vec<_>>(), ["maker", "taker"]);
+        assert_eq!(by_style["taker"].fills, 1);
+    }
+
+    #[test]
+    fn test_fill_stats_per_quote_level() {
+        let tca = TcaTracker::new();
+        submit(&tca, "l1a", "mm_bid", OrderSide::Buy, OrderType::GTC, true);
+        submit(&tca, "l1b", "mm_ask@mid", OrderSide::Sell, OrderType::GTC, true);
+        submit(&tca, "l3", "mm_bid_l3", OrderSide::Buy, OrderType::GTC, true);
+        submit(&tca, "lag", "lag_exploit", OrderSide::Buy, OrderType::FOK, false);
+        tca.on_fill(&fill("l1a", OrderSide::Buy, dec!(0.49), dec!(10)));
+        tca.on_fill(&fill("l3", OrderSide::Buy, dec!(0.47), dec!(20)));
+        tca.on_fill(&fill("lag", OrderSide::Buy, dec!(0.52), dec!(5)));
+
+        let levels = tca.by_quote_level();
+        assert_eq!(levels.keys().copied().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!((levels[&1].quotes, levels[&1].fill_rate), (2, 0.5));
+        assert_eq!(levels[&3].tca.fills, 1);
+        // The deeper level captured 3c against the 0.50 mid
+        assert!((levels[&3].tca.spread_capture - 0.03).abs() < 1e-9);
+
+        assert_eq!(quote_level("mm_ask_l2"), Some(2));
+        assert_eq!(quote_level("mm_bid_lx"), None);
+        assert_eq!(quote_level("momentum"), None);
+    }
+
+    #[test]
+    fn test_adverse_selection_marked_after_horizon() {
+        let tca = TcaTracker::new();
+        submit(&tca, "mm", "mm_bid", OrderSide::Buy, OrderType::GTC, true);
+        tca.on_fill(&fill("mm", OrderSide::Buy, dec!(0.49), dec!(10)));
+
+        // Too soon to mark
+        tca.mark(|_| Some(0.45));
+        assert_eq!(tca.by_strategy()["mm"].marked, 0);
+
+        tca.fills.lock().unwrap()[0].filled_at -= MARKOUT_HORIZON;
+        assert_eq!(tca.mark(|_| Some(0.45)).len(), 1);
+        assert!(tca.mark(|_| Some(0.45)).is_empty(), "Marked once");
+        let mm = &tca.by_strategy()["mm"];
+        assert_eq!(mm.marked, 1);
+        // Bought, then the mid fell from 0.50 to 0.45
+        assert!((mm.adverse_selection - 0.05).abs() < 1e-9);
+    }
+}
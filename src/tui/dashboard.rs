@@ -0,0 +1,483 @@
+use crate::models::market::{OrderBook, Side};
+use chrono::{DateTime, Utc};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table};
+use ratatui::Frame;
+use std::io::Write;
+
+/// Whether new entries are allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TradingState {
+    #[default]
+    Active,
+    /// Strategies paused from the dashboard; open orders untouched
+    Paused,
+    /// Kill switch tripped
+    Killed,
+}
+
+impl TradingState {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Active => "ACTIVE",
+            Self::Paused => "PAUSED",
+            Self::Killed => "KILLED",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Self::Active => Color::Green,
+            Self::Paused => Color::Yellow,
+            Self::Killed => Color::Red,
+        }
+    }
+}
+
+/// Best bid/ask of one outcome token (bid 0.0 / ask 1.0 when that side is empty).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BookTop {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl BookTop {
+    pub fn from_book(book: &OrderBook) -> Self {
+        let f = |p: rust_decimal::Decimal| p.to_string().parse::<f64>().unwrap_or(0.0);
+        Self {
+            bid: book.best_bid().map(|(p, _)| f(p)).unwrap_or(0.0),
+            ask: book.best_ask().map(|(p, _)| f(p)).unwrap_or(1.0),
+        }
+    }
+
+    /// Spread as a percentage of the ask.
+    pub fn spread_pct(&self) -> f64 {
+        if self.ask > 0.0 {
+            (self.ask - self.bid) / self.ask * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Top levels of one side of the book, best first.
+#[derive(Debug, Clone, Default)]
+pub struct Ladder {
+    pub label: String,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl Ladder {
+    pub fn from_book(label: &str, book: &OrderBook, depth: usize) -> Self {
+        let f = |d: rust_decimal::Decimal| d.to_string().parse::<f64>().unwrap_or(0.0);
+        Self {
+            label: label.to_string(),
+            bids: book.bids.iter().rev().take(depth).map(|(p, s)| (f(p), f(s))).collect(),
+            asks: book.asks.iter().take(depth).map(|(p, s)| (f(p), f(s))).collect(),
+        }
+    }
+}
+
+/// One market's prices and signal.
+#[derive(Debug, Clone, Default)]
+pub struct MarketRow {
+    pub slug: String,
+    pub remaining_secs: f64,
+    pub reference: f64,
+    pub spot: f64,
+    pub fair_up: f64,
+    pub yes: BookTop,
+    pub no: BookTop,
+    /// Free-form signal/threshold line
+    pub signal: String,
+    /// YES/NO depth, shown for the selected market
+    pub ladders: Vec<Ladder>,
+}
+
+impl MarketRow {
+    /// Fair value minus ask, YES and NO.
+    pub fn edges(&self) -> (f64, f64) {
+        (self.fair_up - self.yes.ask, (1.0 - self.fair_up) - self.no.ask)
+    }
+
+    pub fn move_from_ref_pct(&self) -> f64 {
+        if self.reference > 0.0 {
+            (self.spot - self.reference) / self.reference * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PositionRow {
+    pub id: String,
+    pub market: String,
+    pub side: Side,
+    pub entry: f64,
+    pub size: f64,
+    pub held_secs: u64,
+    pub strategy: String,
+}
+
+/// Snapshot of everything the dashboard shows.
+#[derive(Debug, Clone, Default)]
+pub struct Dashboard {
+    pub title: String,
+    pub time: DateTime<Utc>,
+    pub capital: f64,
+    pub starting_capital: f64,
+    /// Net of fees and gas
+    pub realized_pnl: f64,
+    /// Fees charged against `realized_pnl`
+    pub fees: f64,
+    /// Gas for on-chain transactions charged against `realized_pnl`, in USD
+    pub gas: f64,
+    pub exposure: f64,
+    /// Owned but not spendable yet: awaiting redemption or settlement
+    pub settling: f64,
+    pub state: TradingState,
+    /// Loss-streak size multiplier (1.0 = full size)
+    pub size_mult: f64,
+    /// Extra stat lines (entries, exits, win rate, ...)
+    pub stats: Vec<String>,
+    pub markets: Vec<MarketRow>,
+    pub positions: Vec<PositionRow>,
+    /// Recent trades, oldest first
+    pub recent: Vec<String>,
+    /// Captured log tail, oldest first (TUI only)
+    pub logs: Vec<String>,
+}
+
+impl Dashboard {
+    fn pnl_pct(&self) -> f64 {
+        if self.starting_capital > 0.0 {
+            self.realized_pnl / self.starting_capital * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    fn header(&self) -> String {
+        format!(
+            "{} | {} | Capital: ${:.2}{} | Realized P&L: {:>+.3} ({:>+.1}%) | Exposure: ${:.2} | {} open",
+            self.time.format("%H:%M:%S"),
+            self.title,
+            self.capital,
+            self.settling_note(),
+            self.realized_pnl,
+            self.pnl_pct(),
+            self.exposure,
+            self.positions.len(),
+        )
+    }
+
+    fn settling_note(&self) -> String {
+        if self.settling > 0.0 {
+            format!(" (+${:.2} settling)", self.settling)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Plain-text dashboard block for line-oriented output.
+    pub fn print_text(&self) {
+        let rule = "-".repeat(76);
+        let mut out = String::new();
+        out.push_str(&format!("\n  {rule}\n  {}", self.header()));
+        if self.state != TradingState::Active {
+            out.push_str(&format!(" | {}", self.state.label()));
+        }
+        out.push('\n');
+        for line in &self.stats {
+            out.push_str(&format!("  {line}\n"));
+        }
+        for m in &self.markets {
+            out.push_str(&format!("  Market: {} | {:.0}s left\n", m.slug, m.remaining_secs));
+            out.push_str(&format!(
+                "  Fair: UP={:.3} DN={:.3} | spot {:>+.3}% from ref | YES {:.2}/{:.2} ({:.0}%sp) | NO {:.2}/{:.2} ({:.0}%sp)\n",
+                m.fair_up,
+                1.0 - m.fair_up,
+                m.move_from_ref_pct(),
+                m.yes.bid,
+                m.yes.ask,
+                m.yes.spread_pct(),
+                m.no.bid,
+                m.no.ask,
+                m.no.spread_pct(),
+            ));
+            if !m.signal.is_empty() {
+                out.push_str(&format!("  {}\n", m.signal));
+            }
+        }
+        if !self.positions.is_empty() {
+            out.push_str("  Open positions:\n");
+            for p in &self.positions {
+                out.push_str(&format!(
+                    "    #{} {:?} @ {:.3} x{:.2} | {}s held | {}\n",
+                    p.id, p.side, p.entry, p.size, p.held_secs, p.strategy
+                ));
+            }
+        }
+        if !self.recent.is_empty() {
+            out.push_str("  Recent:\n");
+            for t in self.recent.iter().rev().take(5).collect::<Vec<_>>().iter().rev() {
+                out.push_str(&format!("    {t}\n"));
+            }
+        }
+        out.push_str(&format!("  {rule}\n"));
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    /// Draw the full-screen view. `selected` picks the market whose depth is shown.
+    pub fn render(&self, frame: &mut Frame, selected: usize) {
+        let [header, markets, middle, bottom, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(self.markets.len().max(1) as u16 + 3),
+            Constraint::Min(8),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.render_header(frame, header);
+        self.render_markets(frame, markets, selected);
+
+        let [depth, pnl] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(middle);
+        self.render_depth(frame, depth, selected);
+        self.render_pnl(frame, pnl);
+
+        let [positions, activity] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(bottom);
+        self.render_positions(frame, positions);
+        self.render_activity(frame, activity);
+
+        frame.render_widget(
+            Paragraph::new(" [p] pause/resume strategies  [c] cancel all orders  [↑/↓] select market  [q] quit")
+                .style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let state = Span::styled(
+            format!(" {} ", self.state.label()),
+            Style::default().fg(Color::Black).bg(self.state.color()).add_modifier(Modifier::BOLD),
+        );
+        let line = Line::from(vec![state, Span::raw(" "), Span::raw(self.header())]);
+        frame.render_widget(Paragraph::new(line).block(Block::bordered().title(" SATTEBAAZ ")), area);
+    }
+
+    fn render_markets(&self, frame: &mut Frame, area: Rect, selected: usize) {
+        let header = Row::new(["Market", "Left", "Spot Δref", "Fair UP", "YES b/a", "NO b/a", "Edge Y/N", "Signal"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.markets.iter().enumerate().map(|(i, m)| {
+            let (yes_edge, no_edge) = m.edges();
+            let style = if i == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Row::new([
+                m.slug.clone(),
+                format!("{:.0}s", m.remaining_secs),
+                format!("{:>+.3}%", m.move_from_ref_pct()),
+                format!("{:.3}", m.fair_up),
+                format!("{:.2}/{:.2}", m.yes.bid, m.yes.ask),
+                format!("{:.2}/{:.2}", m.no.bid, m.no.ask),
+                format!("{:>+.2}/{:>+.2}", yes_edge, no_edge),
+                m.signal.clone(),
+            ])
+            .style(style)
+        });
+        let widths = [
+            Constraint::Min(24),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ];
+        frame.render_widget(
+            Table::new(rows, widths).header(header).block(Block::bordered().title(" Markets ")),
+            area,
+        );
+    }
+
+    fn render_depth(&self, frame: &mut Frame, area: Rect, selected: usize) {
+        let block = Block::bordered().title(" Book depth ");
+        let Some(market) = self.markets.get(selected).filter(|m| !m.ladders.is_empty()) else {
+            frame.render_widget(Paragraph::new("no book").block(block), area);
+            return;
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let cols = Layout::horizontal(vec![Constraint::Ratio(1, market.ladders.len() as u32); market.ladders.len()])
+            .split(inner);
+        for (ladder, col) in market.ladders.iter().zip(cols.iter()) {
+            let max_size = ladder
+                .bids
+                .iter()
+                .chain(&ladder.asks)
+                .map(|(_, s)| *s)
+                .fold(0.0, f64::max)
+                .max(1.0);
+            let bar = |size: f64| "█".repeat(((size / max_size) * 12.0).ceil() as usize);
+            let mut lines = vec![Line::styled(ladder.label.clone(), Style::default().add_modifier(Modifier::BOLD))];
+            for (p, s) in ladder.asks.iter().rev() {
+                lines.push(Line::styled(format!("{p:.2} {s:>9.1} {}", bar(*s)), Style::default().fg(Color::Red)));
+            }
+            lines.push(Line::raw("----"));
+            for (p, s) in &ladder.bids {
+                lines.push(Line::styled(format!("{p:.2} {s:>9.1} {}", bar(*s)), Style::default().fg(Color::Green)));
+            }
+            frame.render_widget(Paragraph::new(lines), *col);
+        }
+    }
+
+    fn render_pnl(&self, frame: &mut Frame, area: Rect) {
+        let pnl_color = if self.realized_pnl >= 0.0 { Color::Green } else { Color::Red };
+        let mut lines = vec![
+            Line::from(format!("Capital:   ${:.2} (start ${:.2})", self.capital, self.starting_capital)),
+            Line::from(format!("Settling:  ${:.2}", self.settling)),
+            Line::styled(
+                format!("Realized:  {:>+.3} ({:>+.1}%)", self.realized_pnl, self.pnl_pct()),
+                Style::default().fg(pnl_color),
+            ),
+            Line::from(format!(
+                "Gross:     {:>+.3} (fees ${:.3}, gas ${:.3})",
+                self.realized_pnl + self.fees + self.gas,
+                self.fees,
+                self.gas
+            )),
+            Line::from(format!("Exposure:  ${:.2}", self.exposure)),
+            Line::styled(format!("Trading:   {}", self.state.label()), Style::default().fg(self.state.color())),
+            Line::from(format!("Size mult: {:.2}x", self.size_mult)),
+        ];
+        lines.extend(self.stats.iter().map(|s| Line::from(s.clone())));
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" P&L / Risk ")), area);
+    }
+
+    fn render_positions(&self, frame: &mut Frame, area: Rect) {
+        let header = Row::new(["#", "Market", "Side", "Entry", "Size", "Held", "Strategy"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.positions.iter().map(|p| {
+            Row::new([
+                p.id.clone(),
+                p.market.clone(),
+                format!("{:?}", p.side),
+                format!("{:.3}", p.entry),
+                format!("{:.2}", p.size),
+                format!("{}s", p.held_secs),
+                p.strategy.clone(),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Min(16),
+            Constraint::Length(4),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Min(10),
+        ];
+        frame.render_widget(
+            Table::new(rows, widths).header(header).block(Block::bordered().title(" Positions ")),
+            area,
+        );
+    }
+
+    fn render_activity(&self, frame: &mut Frame, area: Rect) {
+        let (title, lines) = if self.logs.is_empty() {
+            (" Recent ", &self.recent)
+        } else {
+            (" Log ", &self.logs)
+        };
+        let visible = area.height.saturating_sub(2) as usize;
+        let tail: Vec<Line> = lines
+            .iter()
+            .skip(lines.len().saturating_sub(visible))
+            .map(|l| Line::from(l.clone()))
+            .collect();
+        frame.render_widget(Paragraph::new(tail).block(Block::bordered().title(title)), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use rust_decimal_macros::dec;
+
+    fn sample() -> Dashboard {
+        let mut book = OrderBook::new("yes".into());
+        book.bids.insert(dec!(0.48), dec!(120));
+        book.bids.insert(dec!(0.47), dec!(40));
+        book.asks.insert(dec!(0.52), dec!(80));
+        Dashboard {
+            title: "BTC 5m".into(),
+            capital: 104.2,
+            starting_capital: 100.0,
+            realized_pnl: 4.2,
+            fees: 0.35,
+            gas: 0.05,
+            state: TradingState::Paused,
+            size_mult: 1.0,
+            markets: vec![MarketRow {
+                slug: "btc-updown-5m-1700000000".into(),
+                remaining_secs: 142.0,
+                reference: 100_000.0,
+                spot: 100_050.0,
+                fair_up: 0.58,
+                yes: BookTop::from_book(&book),
+                no: BookTop { bid: 0.47, ask: 0.53 },
+                signal: String::new(),
+                ladders: vec![Ladder::from_book("YES", &book, 5)],
+            }],
+            positions: vec![PositionRow {
+                id: "1".into(),
+                market: "btc-updown-5m-1700000000".into(),
+                side: Side::Yes,
+                entry: 0.51,
+                size: 10.0,
+                held_secs: 30,
+                strategy: "lag_exploit".into(),
+            }],
+            logs: vec!["Fill: Buy 10@0.51".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_book_helpers() {
+        let dash = sample();
+        let m = &dash.markets[0];
+        assert_eq!(m.yes, BookTop { bid: 0.48, ask: 0.52 });
+        assert_eq!(m.ladders[0].bids, [(0.48, 120.0), (0.47, 40.0)]);
+        let (yes_edge, no_edge) = m.edges();
+        assert!((yes_edge - 0.06).abs() < 1e-9);
+        assert!((no_edge - (0.42 - 0.53)).abs() < 1e-9);
+        assert!((m.move_from_ref_pct() - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_render_shows_state_market_and_positions() {
+        let mut terminal = Terminal::new(TestBackend::new(140, 40)).unwrap();
+        terminal.draw(|f| sample().render(f, 0)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        for expected in ["PAUSED", "btc-updown-5m-1700000000", "lag_exploit", "0.48", "Fill: Buy 10@0.51", "[c] cancel all", "+4.600 (fees $0.350, gas $0.050)"] {
+            assert!(text.contains(expected), "missing {expected:?}");
+        }
+    }
+}
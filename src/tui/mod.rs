@@ -0,0 +1,8 @@
+//! Terminal dashboard shared by the bot's `tui` mode and the paper/live traders.
+//!
+//! Callers fill a [`dashboard::Dashboard`] snapshot each refresh; it renders
+//! either as plain text blocks (the traders' default) or as a full-screen
+//! ratatui view with keyboard controls.
+
+pub mod dashboard;
+pub mod terminal;
@@ -0,0 +1,189 @@
+use crate::tui::dashboard::Dashboard;
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::collections::VecDeque;
+use std::io::{Stdout, Write};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Keyboard actions the host loop has to carry out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    TogglePause,
+    CancelAll,
+    Quit,
+}
+
+/// Full-screen terminal session. Restores the terminal on drop (and on panic).
+pub struct Tui {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Market whose depth is shown
+    pub selected: usize,
+    market_count: usize,
+}
+
+impl Tui {
+    pub fn enter() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen)?;
+
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            default_hook(info);
+        }));
+
+        let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+        terminal.hide_cursor()?;
+        terminal.clear()?;
+        Ok(Self { terminal, selected: 0, market_count: 0 })
+    }
+
+    pub fn draw(&mut self, dashboard: &Dashboard) -> Result<()> {
+        self.market_count = dashboard.markets.len();
+        self.selected = self.selected.min(self.market_count.saturating_sub(1));
+        let selected = self.selected;
+        self.terminal.draw(|f| dashboard.render(f, selected))?;
+        Ok(())
+    }
+
+    /// Drain pending key presses without blocking. Market selection is
+    /// handled here; everything else is returned to the caller.
+    pub fn poll_commands(&mut self) -> Result<Vec<Command>> {
+        let mut commands = Vec::new();
+        while event::poll(std::time::Duration::ZERO)? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(self.market_count.saturating_sub(1))
+                }
+                _ => commands.extend(command_for_key(key)),
+            }
+        }
+        Ok(commands)
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = self.terminal.show_cursor();
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen);
+}
+
+/// Key bindings. Raw mode swallows SIGINT, so Ctrl+C is mapped to quit.
+pub fn command_for_key(key: KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Command::Quit),
+        KeyCode::Char('p') | KeyCode::Char(' ') => Some(Command::TogglePause),
+        KeyCode::Char('c') => Some(Command::CancelAll),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+/// Ring buffer of log lines, used as the tracing writer in TUI mode so
+/// log output lands in the dashboard instead of tearing the screen.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<LogLines>>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct LogLines {
+    lines: VecDeque<String>,
+    /// Text after the last newline, completed by the next write
+    partial: String,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogLines::default())),
+            capacity,
+        }
+    }
+
+    /// Most recent `n` lines, oldest first.
+    pub fn tail(&self, n: usize) -> Vec<String> {
+        let inner = self.inner.lock().unwrap();
+        let lines = &inner.lines;
+        lines.iter().skip(lines.len().saturating_sub(n)).cloned().collect()
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.partial.push_str(&String::from_utf8_lossy(buf));
+        while let Some(end) = inner.partial.find('\n') {
+            let line: String = inner.partial.drain(..=end).collect();
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            if inner.lines.len() >= self.capacity {
+                inner.lines.pop_front();
+            }
+            inner.lines.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_bindings() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(command_for_key(key(KeyCode::Char('p'))), Some(Command::TogglePause));
+        assert_eq!(command_for_key(key(KeyCode::Char('c'))), Some(Command::CancelAll));
+        assert_eq!(command_for_key(key(KeyCode::Char('q'))), Some(Command::Quit));
+        assert_eq!(
+            command_for_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Command::Quit)
+        );
+        assert_eq!(command_for_key(key(KeyCode::Char('x'))), None);
+    }
+
+    #[test]
+    fn test_log_buffer_keeps_tail() {
+        let mut buf = LogBuffer::new(3);
+        for i in 0..5 {
+            writeln!(buf, "line {i}").unwrap();
+        }
+        buf.write_all(b"a\nb\n").unwrap();
+        assert_eq!(buf.tail(10), ["line 4", "a", "b"]);
+        assert_eq!(buf.tail(1), ["b"]);
+    }
+}
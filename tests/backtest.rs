@@ -5,18 +5,26 @@
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 // Re-export from the crate
-use sattebaaz::config::{RiskConfig, StrategyConfig};
+use sattebaaz::config::{RiskConfig, SimConfig, StrategyConfig};
 use sattebaaz::models::candle::{Candle, IndicatorEngine};
 use sattebaaz::models::market::{Asset, Duration, LifecyclePhase, Market, OrderBook, Side};
-use sattebaaz::models::order::OrderIntent;
+use sattebaaz::models::order::{OrderIntent, OrderSide, MIN_ORDER_SHARES};
+use sattebaaz::models::position::strategy_bucket;
 use sattebaaz::models::signal::VolRegime;
 use sattebaaz::risk::position_manager::PositionManager;
-use sattebaaz::risk::risk_manager::RiskManager;
+use sattebaaz::risk::risk_manager::{RiskAction, RiskManager};
 use sattebaaz::signals::bias::BiasDetector;
 use sattebaaz::signals::momentum::MomentumDetector;
+use sattebaaz::sim::impact::ImpactModel;
+use sattebaaz::sim::rng::SimRng;
+use sattebaaz::sim::scenarios::{Scenario, ScenarioFeed};
+use sattebaaz::sim::regression::{BacktestBaseline, BacktestMetrics};
+use sattebaaz::sim::sensitivity::{SensitivityReport, SimFill};
+use sattebaaz::sim::synthetic::SyntheticFeed;
 use sattebaaz::strategies::orchestrator::StrategyOrchestrator;
 
 // ---------------------------------------------------------------------------
@@ -132,6 +140,360 @@ fn test_arb_fires_on_cheap_combined() {
     assert!(has_yes && has_no, "Arb should produce both YES and NO orders");
 }
 
+/// Test: Straddle legs lean against existing inventory.
+#[test]
+fn test_straddle_legs_shrink_against_inventory() {
+    let mut config = default_strategy_config();
+    config.arb_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let legs = |inventory: f64| {
+        let orders = orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            inventory, 0.0, 0.0, false,
+        );
+        let size = |tag: &str| orders.iter().find(|o| o.strategy_tag == tag).map(|o| o.size).unwrap();
+        (size("straddle_yes"), size("straddle_no"))
+    };
+
+    let (yes, no) = legs(0.0);
+    assert_eq!(yes, no, "flat: symmetric legs");
+
+    // Long 5 YES already: the YES leg gives up those 5 shares
+    let (long_yes, long_no) = legs(5.0);
+    assert_eq!((long_yes, long_no), (yes - dec!(5), no));
+
+    // Long plenty of NO: the NO leg bottoms out at the floor, which never
+    // goes under the exchange's share minimum
+    let (short_yes, short_no) = legs(-1000.0);
+    assert_eq!(short_yes, yes);
+    assert_eq!(short_no, (no * dec!(0.25)).round_dp(2).max(MIN_ORDER_SHARES));
+}
+
+/// Test: the opening sniper takes one small entry on mispriced opening quotes,
+/// and only on fresh books inside its window. The window is spent once the
+/// entry is accepted.
+#[test]
+fn test_open_sniper_fires_once_in_opening_window() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.arb_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    config.open_sniper_enabled = true;
+    // The model and the opening book disagree by design; test the sniper's own bar
+    config.edge.enabled = false;
+
+    let mut market = make_market(Asset::BTC, Duration::FiveMin);
+    market.open_time = chrono::Utc::now() - chrono::Duration::seconds(1);
+    market.close_time = market.open_time + chrono::Duration::seconds(300);
+    assert_eq!(market.lifecycle_phase(), LifecyclePhase::AlphaWindow);
+
+    // BTC already +0.3% but the opening quotes still sit near 50/50
+    let yes_book = make_book("yes", 0.45, 0.47, 50.0);
+    let no_book = make_book("no", 0.51, 0.53, 50.0);
+    let evaluate = |orch: &StrategyOrchestrator, market: &Market, yes_book: &OrderBook| {
+        orch.evaluate(
+            market, yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_300.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    let orch = StrategyOrchestrator::new(config.clone());
+    let orders = evaluate(&orch, &market, &yes_book);
+    assert_eq!(orders.len(), 1, "sniper should take the cheap side: {orders:?}");
+    assert_eq!(orders[0].strategy_tag, "open_sniper");
+    assert_eq!(orders[0].market_side, Side::Yes);
+    assert!(orders[0].price * orders[0].size <= dec!(5.0));
+    assert!(orders[0].size >= sattebaaz::models::order::MIN_ORDER_SHARES);
+
+    // Not accepted yet: proposed again. Accepted: one entry per window
+    assert_eq!(evaluate(&orch, &market, &yes_book).len(), 1);
+    orch.on_order_accepted(&market, &orders[0]);
+    assert!(evaluate(&orch, &market, &yes_book).is_empty());
+
+    // A budget that can't buy the 5-share minimum sends nothing
+    let mut small = config.clone();
+    small.open_sniper_order_usdc = 1.0;
+    let orch = StrategyOrchestrator::new(small);
+    assert!(evaluate(&orch, &market, &yes_book).is_empty());
+
+    // A book older than the latency limit is not trusted
+    let orch = StrategyOrchestrator::new(config.clone());
+    let mut stale = yes_book.clone();
+    stale.timestamp = chrono::Utc::now() - chrono::Duration::seconds(1);
+    assert!(evaluate(&orch, &market, &stale).is_empty());
+
+    // Outside the opening window it stays silent
+    let orch = StrategyOrchestrator::new(config);
+    let later = make_market(Asset::BTC, Duration::FiveMin);
+    assert!(evaluate(&orch, &later, &yes_book).is_empty());
+}
+
+/// Test: pausing the orchestrator (TUI `p`) suppresses all intents until resumed.
+#[test]
+fn test_paused_orchestrator_produces_no_orders() {
+    let mut config = default_strategy_config();
+    config.arb_enabled = true;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let evaluate = || {
+        orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    assert!(orch.toggle_pause());
+    assert!(evaluate().is_empty(), "Paused orchestrator must not emit orders");
+    assert!(!orch.toggle_pause());
+    assert!(!evaluate().is_empty(), "Resumed orchestrator trades the arb again");
+}
+
+/// Test: a strategy that blows its evaluation budget has its intents dropped,
+/// is benched for the cooldown once it keeps doing so, and its timing is
+/// recorded.
+#[test]
+fn test_strategy_over_budget_is_benched() {
+    let mut config = default_strategy_config();
+    config.arb_enabled = true;
+    config.straddle_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    config.eval_budget_ms = 0.0; // any evaluation overruns
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let evaluate = || {
+        orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    // One slow evaluation costs only its own intents
+    assert!(evaluate().is_empty(), "Over-budget arb intents must be dropped");
+    assert!(orch.benched_strategies().is_empty(), "A single overrun doesn't bench");
+    assert_eq!(orch.overrun_count("arb"), 1);
+    assert!(orch.strategy_latency().percentiles("strategy.arb").is_some());
+
+    // The third in a row does
+    assert!(evaluate().is_empty());
+    assert!(evaluate().is_empty());
+    assert_eq!(orch.benched_strategies(), ["arb"]);
+    assert_eq!(orch.overrun_count("arb"), 3);
+
+    // Benched: skipped without running, so no new overrun
+    assert!(evaluate().is_empty());
+    assert_eq!(orch.overrun_count("arb"), 3);
+}
+
+/// Test: the per-market notional cap trims an arb pair without unbalancing it.
+#[test]
+fn test_market_notional_cap_keeps_arb_balanced() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    config.max_market_notional_per_eval = 3.0;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+
+    let orders = orch.evaluate(
+        &market, &yes_book, &no_book,
+        VolRegime::Medium, 100.0, 100_000.0,
+        None, None, None,
+        0.0, 0.0, 0.0, false,
+    );
+    assert_eq!(orders.len(), 2, "Arb should emit both legs");
+    assert_eq!(orders[0].size, orders[1].size, "Legs must stay balanced");
+    let notional: f64 = orders
+        .iter()
+        .map(|o| (o.price * o.size).to_string().parse::<f64>().unwrap())
+        .sum();
+    assert!(notional <= 3.0 + 1e-9, "Notional {notional} over cap");
+}
+
+/// Test: adverse selection fed back from TCA shrinks the offending strategy.
+#[test]
+fn test_fill_quality_feedback_shrinks_size() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let evaluate = || {
+        orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    let before = evaluate();
+    assert!(!before.is_empty());
+    for _ in 0..10 {
+        orch.fill_quality().observe("arb_yes", 0.10, 10.0);
+    }
+    let after = evaluate();
+    assert_eq!(after.len(), before.len());
+    for (b, a) in before.iter().zip(&after) {
+        assert!(a.size < b.size, "{} not shrunk: {} -> {}", a.strategy_tag, b.size, a.size);
+    }
+    assert_eq!(orch.fill_quality().adjustments(), [("arb", 0.25)]);
+}
+
+/// Test: the market screen short-circuits evaluation on an untradeable book.
+#[test]
+fn test_market_screen_skips_untradeable_books() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let evaluate = |no_book: &OrderBook| {
+        orch.evaluate(
+            &market, &yes_book, no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+    assert!(!evaluate(&no_book).is_empty());
+
+    let mut stale = no_book.clone();
+    stale.timestamp -= chrono::Duration::seconds(120);
+    assert!(evaluate(&stale).is_empty());
+    assert!(evaluate(&make_book("no", 0.45, 0.47, 1.0)).is_empty());
+    assert_eq!(orch.screen().skip_counts(), [("stale_book", 1), ("thin_book", 1)]);
+}
+
+/// Test: join policy skips late joins and tags/downsizes mid-cycle joins.
+#[test]
+fn test_mid_window_join_policy() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+
+    let orch = StrategyOrchestrator::new(config);
+    let yes_book = make_book("yes", 0.43, 0.45, 50.0);
+    let no_book = make_book("no", 0.45, 0.47, 50.0);
+    let evaluate = |market: &Market| {
+        orch.evaluate(
+            market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_000.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+    let cost = |orders: &[OrderIntent]| -> Decimal { orders.iter().map(|o| o.price * o.size).sum() };
+
+    // Discovered at open → fresh, untagged
+    let mut market = make_market(Asset::BTC, Duration::FiveMin);
+    market.joined_at = Some(market.open_time + chrono::Duration::seconds(5));
+    let fresh = evaluate(&market);
+    assert!(!fresh.is_empty());
+    assert!(fresh.iter().all(|o| !o.strategy_tag.ends_with("@mid")));
+
+    // Discovered 60s in (80% left) → mid-cycle: tagged, smaller
+    market.joined_at = Some(market.open_time + chrono::Duration::seconds(60));
+    let mid = evaluate(&market);
+    assert!(!mid.is_empty());
+    assert!(mid.iter().all(|o| o.strategy_tag.ends_with("@mid")));
+    assert!(cost(&mid) < cost(&fresh), "mid-cycle joins should trade smaller");
+
+    // Discovered 200s in (33% left) → skipped entirely
+    market.joined_at = Some(market.open_time + chrono::Duration::seconds(200));
+    assert!(evaluate(&market).is_empty());
+}
+
+/// Test: late-window gamma scalping trades through lockout within its caps.
+#[test]
+fn test_late_gamma_exempt_from_lockout() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.arb_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    config.late_gamma_enabled = true;
+
+    let orch = StrategyOrchestrator::new(config.clone());
+    let mut market = make_market(Asset::BTC, Duration::FiveMin);
+    let now = chrono::Utc::now();
+    market.open_time = now - chrono::Duration::seconds(280);
+    market.close_time = now + chrono::Duration::seconds(20);
+    assert_eq!(market.lifecycle_phase(), LifecyclePhase::Lockout);
+
+    // BTC +0.1% with 20s left → model says YES is near-certain, book still at 0.60
+    let yes_book = make_book("yes", 0.58, 0.60, 50.0);
+    let no_book = make_book("no", 0.38, 0.40, 50.0);
+    let evaluate = || {
+        orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_100.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    let mut total_cost = Decimal::ZERO;
+    let mut entries = 0;
+    for _ in 0..10 {
+        for o in evaluate() {
+            assert_eq!(o.strategy_tag, "late_gamma");
+            assert_eq!(o.market_side, Side::Yes);
+            assert!(o.price * o.size <= dec!(1.0));
+            total_cost += o.price * o.size;
+            entries += 1;
+        }
+    }
+    assert!(entries > 0, "late gamma should trade through lockout");
+    assert!(total_cost <= dec!(2.0), "per-window cap exceeded: {total_cost}");
+
+    // Disabled by default: lockout stays silent
+    let orch = StrategyOrchestrator::new(default_strategy_config());
+    let orders = orch.evaluate(
+        &market, &yes_book, &no_book,
+        VolRegime::Medium, 100.0, 100_100.0,
+        None, None, None,
+        0.0, 0.0, 0.0, false,
+    );
+    assert!(orders.is_empty());
+}
+
 /// Test: No arb when combined price is near $1.00.
 #[test]
 fn test_no_arb_when_fairly_priced() {
@@ -160,6 +522,41 @@ fn test_no_arb_when_fairly_priced() {
     assert!(orders.is_empty(), "No arb orders when combined >= $1.00");
 }
 
+/// Test: The market maker posts a ladder of quotes, deeper levels larger.
+#[test]
+fn test_market_maker_quote_ladder() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.arb_enabled = false;
+    config.lag_exploit_enabled = false;
+    config.momentum_enabled = false;
+    config.mm_levels = 3;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.45, 0.55, 50.0);
+    let no_book = make_book("no", 0.45, 0.55, 50.0);
+
+    let orders = orch.evaluate(
+        &market, &yes_book, &no_book,
+        VolRegime::Low, 100.0, 100_000.0,
+        None, None, None,
+        0.0, 0.0, 0.0, false,
+    );
+
+    let bids: Vec<_> = orders.iter().filter(|o| o.order_side == OrderSide::Buy).collect();
+    let asks: Vec<_> = orders.iter().filter(|o| o.order_side == OrderSide::Sell).collect();
+    assert_eq!(bids.iter().map(|o| o.strategy_tag.as_str()).collect::<Vec<_>>(), ["mm_bid", "mm_bid_l2", "mm_bid_l3"]);
+    assert_eq!(asks.len(), 3);
+    for pair in bids.windows(2) {
+        assert_eq!(pair[0].price - pair[1].price, dec!(0.01), "levels a cent apart");
+        assert!(pair[1].size > pair[0].size, "deeper levels are larger");
+    }
+    for pair in asks.windows(2) {
+        assert_eq!(pair[1].price - pair[0].price, dec!(0.01));
+    }
+    assert!(bids[0].price < asks[0].price);
+}
+
 /// Test: Strategy produces orders during PrimeZone with medium volatility.
 #[test]
 fn test_prime_zone_medium_vol_produces_orders() {
@@ -217,6 +614,312 @@ fn test_lag_exploit_on_price_divergence() {
     println!("Lag exploit orders: {}", orders.len());
 }
 
+/// Test: lag entries price off the Binance print projected over our latency,
+/// so a book that has caught up with the last print but not the trend is
+/// still an entry.
+#[test]
+fn test_lag_exploit_uses_projected_price() {
+    let mut config = default_strategy_config();
+    config.straddle_enabled = false;
+    config.arb_enabled = false;
+    config.market_making_enabled = false;
+    config.momentum_enabled = false;
+    config.edge.enabled = false;
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let yes_book = make_book("yes", 0.56, 0.58, 50.0);
+    let no_book = make_book("no", 0.42, 0.44, 50.0);
+    let evaluate = |orch: &StrategyOrchestrator| {
+        orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, 100_030.0,
+            None, None, None,
+            0.0, 0.0, 0.0, false,
+        )
+    };
+
+    // Only the last print: the YES ask already reflects it
+    let orch = StrategyOrchestrator::new(config.clone());
+    assert!(evaluate(&orch).is_empty());
+
+    // Same last print at the end of a steady climb: BTC will be higher by
+    // the time our order lands
+    let orch = StrategyOrchestrator::new(config);
+    let last = chrono::Utc::now();
+    for i in 0..10 {
+        let at = last - chrono::Duration::milliseconds(50 * (9 - i));
+        orch.observe_price(Asset::BTC, 100_030.0 - 15.0 * (9 - i) as f64, at);
+    }
+    let orders = evaluate(&orch);
+    assert!(
+        orders.iter().any(|o| o.strategy_tag == "lag_exploit" && o.market_side == Side::Yes),
+        "projected trend should make YES an entry: {orders:?}"
+    );
+}
+
+/// Run the orchestrator over one seeded synthetic window; returns the Binance
+/// path and every order produced. Sizes are left out: they scale with time
+/// remaining, which the orchestrator reads from the wall clock.
+fn run_seeded_session(seed: u64) -> (Vec<f64>, Vec<(String, Decimal, String)>) {
+    let mut config = default_strategy_config();
+    config.lag_exploit_enabled = true;
+    config.lag_min_edge = 0.02;
+    let orch = StrategyOrchestrator::new(config);
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+
+    let mut feed = SyntheticFeed::new(SimRng::new(seed).fork("feed"), 100_000.0, 0.0005, 3);
+    let mut path = Vec::new();
+    let mut orders = Vec::new();
+    for tick in 0..120 {
+        let price = feed.step();
+        let (yes_book, no_book) = feed.books(&market, 240.0 - tick as f64);
+        path.push(price);
+        for o in orch.evaluate(
+            &market, &yes_book, &no_book,
+            VolRegime::Medium, 100.0, price,
+            None, None, None,
+            0.0, 0.003, 0.0, false,
+        ) {
+            orders.push((o.token_id, o.price, o.strategy_tag));
+        }
+    }
+    (path, orders)
+}
+
+/// Test: the same seed replays the same feed and the same decisions.
+#[test]
+fn test_seeded_simulation_is_reproducible() {
+    let (path_a, orders_a) = run_seeded_session(42);
+    let (path_b, orders_b) = run_seeded_session(42);
+    assert_eq!(path_a, path_b);
+    assert_eq!(orders_a, orders_b);
+    assert!(!orders_a.is_empty(), "synthetic window should trigger some strategy");
+
+    let (path_c, _) = run_seeded_session(43);
+    assert_ne!(path_a, path_c);
+}
+
+/// Record the fills the orchestrator gets over `windows` seeded synthetic
+/// markets, and which side each window resolved to. Takers walk the book up
+/// to their limit, and the levels they take stay depleted until they refill;
+/// post-only quotes fill when the next tick's book trades through them.
+fn record_seeded_fills(seed: u64, windows: usize) -> (Vec<SimFill>, Vec<Side>) {
+    let mut config = default_strategy_config();
+    config.lag_exploit_enabled = true;
+    config.lag_min_edge = 0.02;
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+    let root = SimRng::new(seed);
+
+    let mut fills = Vec::new();
+    let mut outcomes = Vec::new();
+    for window in 0..windows {
+        let orch = StrategyOrchestrator::new(config.clone());
+        let mut feed = SyntheticFeed::new(root.fork(&format!("window-{window}")), 100_000.0, 0.0005, 3);
+        let mut impact = ImpactModel::new(SimConfig::default().impact_half_life_secs);
+        let mut resting: Vec<OrderIntent> = Vec::new();
+        let mut price = feed.price();
+        for tick in 0..120 {
+            price = feed.step();
+            let now_ms = tick * 1000;
+            let (yes_raw, no_raw) = feed.books(&market, 240.0 - tick as f64);
+            let (yes_book, no_book) = (impact.apply(&yes_raw, now_ms), impact.apply(&no_raw, now_ms));
+            for o in resting.drain(..) {
+                let book = if o.market_side == Side::Yes { &yes_book } else { &no_book };
+                let crossed = match o.order_side {
+                    OrderSide::Buy => book.best_ask().is_some_and(|(ask, _)| ask <= o.price),
+                    OrderSide::Sell => book.best_bid().is_some_and(|(bid, _)| bid >= o.price),
+                };
+                if crossed {
+                    fills.push(sim_fill(&o, window));
+                }
+            }
+            for o in orch.evaluate(
+                &market, &yes_book, &no_book,
+                VolRegime::Medium, 100.0, price,
+                None, None, None,
+                0.0, 0.003, 0.0, false,
+            ) {
+                if o.post_only {
+                    resting.push(o);
+                    continue;
+                }
+                let raw = if o.market_side == Side::Yes { &yes_raw } else { &no_raw };
+                let fill = sim_fill(&o, window);
+                if let Some(exec) = impact.execute(raw, o.order_side, fill.size, Some(fill.price), now_ms) {
+                    fills.push(SimFill { price: exec.avg_price(), size: exec.shares, ..fill });
+                }
+            }
+        }
+        outcomes.push(if price >= market.reference_price { Side::Yes } else { Side::No });
+    }
+    (fills, outcomes)
+}
+
+fn sim_fill(o: &OrderIntent, window: usize) -> SimFill {
+    SimFill {
+        strategy: o.strategy_tag.clone(),
+        window,
+        market_side: o.market_side,
+        order_side: o.order_side,
+        price: o.price.to_string().parse().unwrap(),
+        size: o.size.to_string().parse().unwrap(),
+        post_only: o.post_only,
+    }
+}
+
+/// Sensitivity mode: replay one set of seeded decisions across a fee ×
+/// slippage grid and report each strategy's P&L and breakeven fee rate.
+/// `cargo test --test backtest cost_sensitivity -- --nocapture` prints it.
+#[test]
+fn test_cost_sensitivity_sweep() {
+    let (fills, outcomes) = record_seeded_fills(7, 20);
+    assert!(!fills.is_empty(), "synthetic windows should produce fills");
+
+    let fees = [0.0, 50.0, 100.0, 200.0, 500.0, 1_000.0];
+    let slips = [0.0, 0.005, 0.01, 0.02];
+    let report = SensitivityReport::sweep(&fills, &outcomes, &fees, &slips);
+
+    println!("\n  COST SENSITIVITY — {} fills over {} windows", fills.len(), outcomes.len());
+    print!("{report}");
+
+    for strategy in report.strategies() {
+        for s in 0..slips.len() {
+            for i in 0..fees.len() {
+                let pnl = report.pnl(strategy, s, i);
+                if i > 0 {
+                    assert!(pnl <= report.pnl(strategy, s, i - 1) + 1e-9, "{strategy}: P&L rose with fees");
+                }
+                if s > 0 {
+                    assert!(pnl <= report.pnl(strategy, s - 1, i) + 1e-9, "{strategy}: P&L rose with slippage");
+                }
+            }
+            // More slippage leaves less room for fees
+            if s > 0 {
+                let (tight, wide) = (report.breakeven_fee_bps(strategy, s - 1), report.breakeven_fee_bps(strategy, s));
+                if let (Some(tight), Some(wide)) = (tight, wide) {
+                    assert!(wide <= tight + 1e-9, "{strategy}: breakeven rose with slippage");
+                }
+            }
+        }
+    }
+}
+
+/// What the pipeline did over one stress scenario.
+struct ScenarioRun {
+    /// (tick, order) for every order that passed risk and filled
+    fills: Vec<(usize, OrderIntent)>,
+    /// Highest exposure / capital seen after any tick
+    peak_exposure_ratio: f64,
+    daily_pnl: Decimal,
+    starting_capital: Decimal,
+    action: RiskAction,
+}
+
+/// Run a scenario through orchestrator → risk → fills → resolution. Orders
+/// that pass the pre-flight check fill at their limit; the Binance price
+/// holds its last value while the feed is down.
+async fn run_scenario(scenario: Scenario, seed: u64) -> ScenarioRun {
+    let risk_config = default_risk_config();
+    let starting_capital = dec!(100);
+    let pos_mgr = std::sync::Arc::new(PositionManager::new(starting_capital));
+    let risk_mgr = RiskManager::new(risk_config, pos_mgr.clone());
+    let orch = StrategyOrchestrator::new(default_strategy_config());
+    let market = make_market(Asset::BTC, Duration::FiveMin);
+
+    let mut feed = ScenarioFeed::new(scenario, SimRng::new(seed).fork(scenario.label()), 100_000.0, 120);
+    let mut price = market.reference_price;
+    let mut fills = Vec::new();
+    let mut peak_exposure_ratio: f64 = 0.0;
+    let mut remaining_secs = 240.0;
+    while let Some(t) = feed.step(&market, remaining_secs) {
+        remaining_secs -= 1.0;
+        price = t.price.unwrap_or(price);
+        let available = pos_mgr.available_capital().await;
+        let inventory = pos_mgr.net_yes_inventory(&market.slug).await;
+        let orders = orch.evaluate(
+            &market, &t.yes_book, &t.no_book,
+            VolRegime::Medium, available, price,
+            None, None, None,
+            inventory, t.move_1s, 0.0, false,
+        );
+        for order in orders {
+            if risk_mgr.check_order(&order).await.is_err() {
+                continue;
+            }
+            let fill = sattebaaz::models::order::Fill {
+                order_id: format!("{}_{}_{}", scenario.label(), t.tick, fills.len()),
+                token_id: order.token_id.clone(),
+                side: order.order_side,
+                price: order.price,
+                size: order.size,
+                timestamp: chrono::Utc::now(),
+                fee: Decimal::ZERO,
+            };
+            pos_mgr.record_fill(&fill, &market.slug, order.market_side, &order.strategy_tag).await;
+            fills.push((t.tick, order));
+        }
+        // Measured the way the pre-flight check caps it: against the larger of
+        // starting and current capital
+        let portfolio = pos_mgr.portfolio.read().await;
+        let ratio = portfolio.total_exposure() / portfolio.starting_capital.max(portfolio.capital);
+        peak_exposure_ratio = peak_exposure_ratio.max(ratio.to_string().parse().unwrap());
+    }
+
+    let winner = if price >= market.reference_price { Side::Yes } else { Side::No };
+    pos_mgr.record_resolution(&market.slug, winner).await;
+    let action = risk_mgr.periodic_check().await;
+    let daily_pnl = pos_mgr.portfolio.read().await.daily_pnl;
+    ScenarioRun { fills, peak_exposure_ratio, daily_pnl, starting_capital, action }
+}
+
+/// Test: risk controls hold under each canned stress scenario.
+#[tokio::test]
+async fn test_stress_scenarios() {
+    let risk = default_risk_config();
+    let screen = default_strategy_config().screen;
+    for scenario in Scenario::ALL {
+        let run = run_scenario(scenario, 11).await;
+        let window = scenario.stress_window(120);
+        let in_window = |tick: usize| window.contains(&tick);
+        println!(
+            "  {:<17} fills={:>3} peak exposure={:.2} daily pnl={:+.2} -> {:?}",
+            scenario.label(), run.fills.len(), run.peak_exposure_ratio, run.daily_pnl, run.action
+        );
+
+        // Exposure never breaches the cap, whatever the path
+        assert!(
+            run.peak_exposure_ratio <= risk.max_exposure_pct + 1e-9,
+            "{}: exposure {:.2} over cap", scenario.label(), run.peak_exposure_ratio
+        );
+        // A window that blows the daily loss limit pauses trading
+        let limit = run.starting_capital * Decimal::from_f64_retain(risk.max_daily_loss_pct).unwrap();
+        if run.daily_pnl < -limit {
+            assert!(matches!(run.action, RiskAction::Pause(_)), "{}: no pause after {}", scenario.label(), run.daily_pnl);
+        }
+
+        match scenario {
+            // Market maker pulls its quotes while the underlying is moving fast
+            Scenario::FlashCrash | Scenario::VReversal => {
+                let quoted = run.fills.iter().filter(|(tick, o)| in_window(*tick) && o.strategy_tag.starts_with("mm_"));
+                assert_eq!(quoted.count(), 0, "{}: market maker quoted into the move", scenario.label());
+            }
+            // Once spreads blow out past the screen, only the market maker trades
+            Scenario::LiquidityVacuum => {
+                let taken = run.fills.iter().filter(|(tick, o)| *tick >= window.end && !o.strategy_tag.starts_with("mm_"));
+                assert_eq!(taken.count(), 0, "{}: took a drained book", scenario.label());
+            }
+            // Nothing trades on books older than the screen allows
+            Scenario::FeedOutage => {
+                let stale_from = window.start + screen.max_book_age_secs as usize;
+                let stale = run.fills.iter().filter(|(tick, _)| in_window(*tick) && *tick >= stale_from);
+                assert_eq!(stale.count(), 0, "{}: traded on stale books", scenario.label());
+            }
+            Scenario::SlowGrind => {
+                assert!(!run.fills.is_empty(), "{}: persistent lag should be traded", scenario.label());
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Risk manager integration tests
 // ---------------------------------------------------------------------------
@@ -237,6 +940,7 @@ async fn test_kill_switch_blocks_orders() {
         post_only: false,
         expiration: None,
         strategy_tag: "test".to_string(),
+        group_id: None,
     };
 
     // Should be OK initially
@@ -272,6 +976,7 @@ async fn test_exposure_limit() {
         post_only: false,
         expiration: None,
         strategy_tag: "test".to_string(),
+        group_id: None,
     };
 
     // Should be rejected — $10 order > $5 max exposure
@@ -288,6 +993,7 @@ async fn test_exposure_limit() {
         post_only: false,
         expiration: None,
         strategy_tag: "test".to_string(),
+        group_id: None,
     };
 
     assert!(risk.check_order(&small_order).await.is_ok());
@@ -309,11 +1015,37 @@ async fn test_balance_check() {
         post_only: false,
         expiration: None,
         strategy_tag: "test".to_string(),
+        group_id: None,
     };
 
     assert!(risk.check_order(&order).await.is_err());
 }
 
+/// Test: A silent risk watchdog blocks entries but not exits.
+#[tokio::test]
+async fn test_stale_watchdog_lets_sells_through() {
+    let pos_mgr = std::sync::Arc::new(PositionManager::new(dec!(100)));
+    let risk = RiskManager::new(default_risk_config(), pos_mgr);
+    risk.watchdog.beat(sattebaaz::feeds::clock::now() - chrono::Duration::seconds(5));
+
+    let mut order = OrderIntent {
+        token_id: "test_token".to_string(),
+        market_side: Side::Yes,
+        order_side: sattebaaz::models::order::OrderSide::Buy,
+        price: dec!(0.50),
+        size: dec!(10),
+        order_type: sattebaaz::models::order::OrderType::FAK,
+        post_only: false,
+        expiration: None,
+        strategy_tag: "test".to_string(),
+        group_id: None,
+    };
+    assert!(risk.check_market_order("m", &order).await.is_err());
+
+    order.order_side = sattebaaz::models::order::OrderSide::Sell;
+    assert!(risk.check_market_order("m", &order).await.is_ok());
+}
+
 // ---------------------------------------------------------------------------
 // Backtesting simulation
 // ---------------------------------------------------------------------------
@@ -332,6 +1064,7 @@ struct SimTick {
 struct CycleResult {
     orders_generated: usize,
     total_notional: f64,
+    #[allow(dead_code)]
     strategies_used: Vec<String>,
 }
 
@@ -500,15 +1233,85 @@ fn simulate_price_path(rng: &mut Rng, start: f64, steps: usize, vol_per_step: f6
     prices
 }
 
-/// Full P&L backtest: simulates 100 market cycles with $5 starting capital.
+/// One parameterization of the full P&L backtest, gated against the
+/// metrics stored for it in `BASELINE_PATH`.
+struct BacktestCase {
+    name: &'static str,
+    seed: u64,
+    starting_capital: f64,
+    /// Binance volatility per 10s tick
+    vol_per_step: f64,
+    /// Chance a resting MM order fills on a given tick
+    mm_fill_prob: f64,
+}
+
+/// Baseline metrics per case. Re-record after an intended change with
+/// `BACKTEST_RECORD_BASELINE=1 cargo test --test backtest gate_`.
+const BASELINE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/baselines/backtest.json");
+
+/// Serializes baseline reads and writes across the parallel gate tests.
+static BASELINE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Run `case` and fail on any metric that degraded beyond the baseline's
+/// tolerance — or record it as the new baseline when asked to.
+async fn assert_no_regression(case: &BacktestCase) {
+    let metrics = run_full_pnl_backtest(case).await;
+    let _guard = BASELINE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut baseline = BacktestBaseline::load(BASELINE_PATH).unwrap();
+    if std::env::var("BACKTEST_RECORD_BASELINE").is_ok_and(|v| v == "1") {
+        baseline.cases.insert(case.name.to_string(), metrics);
+        baseline.save(BASELINE_PATH).unwrap();
+        println!("Recorded baseline for {}", case.name);
+        return;
+    }
+    let comparison = baseline.compare(case.name, &metrics);
+    for line in &comparison.improvements {
+        println!("  Improved: {line} — consider re-recording the baseline");
+    }
+    assert!(
+        comparison.regressions.is_empty(),
+        "Backtest regressed:\n  {}\nIf intended, re-record with BACKTEST_RECORD_BASELINE=1",
+        comparison.regressions.join("\n  ")
+    );
+}
+
+/// Full P&L backtest: the reference case, 100 cycles at $5 starting capital.
+#[tokio::test]
+async fn test_full_pnl_backtest() {
+    let metrics = run_full_pnl_backtest(&REFERENCE_CASE).await;
+    assert!(REFERENCE_CASE.starting_capital + metrics.total_pnl > 0.0, "Should not go bankrupt");
+    assert!(metrics.fills.values().sum::<usize>() > 0, "Should have placed orders");
+}
+
+const REFERENCE_CASE: BacktestCase =
+    BacktestCase { name: "reference_5usd", seed: 42, starting_capital: 5.0, vol_per_step: 0.0015, mm_fill_prob: 0.25 };
+
+/// Regression gates: one test per case, each against its stored baseline.
+macro_rules! backtest_gates {
+    ($($test:ident => $case:expr;)*) => {
+        $(
+            #[tokio::test]
+            async fn $test() {
+                assert_no_regression(&$case).await;
+            }
+        )*
+    };
+}
+
+backtest_gates! {
+    gate_reference_5usd => REFERENCE_CASE;
+    gate_volatile_5usd => BacktestCase { name: "volatile_5usd", seed: 7, starting_capital: 5.0, vol_per_step: 0.003, mm_fill_prob: 0.25 };
+    gate_calm_50usd => BacktestCase { name: "calm_50usd", seed: 1234, starting_capital: 50.0, vol_per_step: 0.0005, mm_fill_prob: 0.40 };
+}
+
+/// Simulates 100 market cycles of `case` and returns its metrics.
 ///
 /// Microstructure model:
 ///   - Polymarket book implied-prob LAGS Binance by 1-3 ticks.
 ///   - When lagged, the stale side's ask is cheap → arb or lag exploit.
 ///   - Market-maker quotes around fair value with spread.
-#[tokio::test]
-async fn test_full_pnl_backtest() {
-    let starting_capital = 5.0;
+async fn run_full_pnl_backtest(case: &BacktestCase) -> BacktestMetrics {
+    let starting_capital = case.starting_capital;
     let num_cycles = 100;
     let ticks_per_cycle = 25;
 
@@ -526,19 +1329,20 @@ async fn test_full_pnl_backtest() {
     let mut total_lag_orders = 0usize;
     let mut total_mm_orders = 0usize;
     let mut total_momentum_orders = 0usize;
+    let mut fills_by_family: BTreeMap<String, usize> = BTreeMap::new();
     let mut total_mm_skipped = 0usize;
     let mut wins = 0u32;
     let mut losses = 0u32;
     let mut cycle_pnls: Vec<f64> = Vec::new();
 
     let btc_start = 97_500.0;
-    let mut rng = Rng::new(42);
+    let mut rng = Rng::new(case.seed);
 
     // MM resting orders fill with this probability per tick (realistic: ~25%)
-    let mm_fill_prob = 0.25;
+    let mm_fill_prob = case.mm_fill_prob;
 
     println!("\n============================================================");
-    println!("  SATTEBAAZ BACKTEST  {} cycles x 5min  (REALISTIC MODEL)", num_cycles);
+    println!("  SATTEBAAZ BACKTEST  {} cycles x 5min  (REALISTIC MODEL)  [{}]", num_cycles, case.name);
     println!("  Starting capital: ${:.2}", starting_capital);
     println!("  BTC starting price: ${:.0}", btc_start);
     println!("  Fees: ZERO (5-min crypto markets are fee-free)");
@@ -546,7 +1350,7 @@ async fn test_full_pnl_backtest() {
     println!("============================================================\n");
 
     for cycle in 0..num_cycles {
-        let prices = simulate_price_path(&mut rng, btc_start, ticks_per_cycle, 0.0015);
+        let prices = simulate_price_path(&mut rng, btc_start, ticks_per_cycle, case.vol_per_step);
         let reference_price = prices[0];
 
         // Unique slug per cycle so positions don't collide
@@ -720,6 +1524,7 @@ async fn test_full_pnl_backtest() {
 
                 cycle_orders += 1;
                 total_fills += 1;
+                *fills_by_family.entry(strategy_bucket(&order.strategy_tag).to_string()).or_default() += 1;
 
                 match order.strategy_tag.as_str() {
                     s if s.contains("arb") => total_arb_orders += 1,
@@ -769,23 +1574,8 @@ async fn test_full_pnl_backtest() {
     let max_pnl = cycle_pnls.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
     let min_pnl = cycle_pnls.iter().cloned().fold(f64::INFINITY, f64::min);
 
-    // Max drawdown
-    let mut peak = starting_capital;
-    let mut max_drawdown = 0.0f64;
-    let mut running = starting_capital;
-    for &pnl in &cycle_pnls {
-        running += pnl;
-        if running > peak { peak = running; }
-        let dd = (peak - running) / peak;
-        if dd > max_drawdown { max_drawdown = dd; }
-    }
-
-    // Sharpe ratio (per-cycle)
-    let mean = avg_pnl;
-    let variance = cycle_pnls.iter().map(|p| (p - mean).powi(2)).sum::<f64>()
-        / cycle_pnls.len().max(1) as f64;
-    let std_dev = variance.sqrt();
-    let sharpe = if std_dev > 0.0 { mean / std_dev } else { 0.0 };
+    let metrics = BacktestMetrics::from_cycles(starting_capital, &cycle_pnls, fills_by_family);
+    let (max_drawdown, sharpe) = (metrics.max_drawdown, metrics.sharpe);
 
     println!("\n============================================================");
     println!("  BACKTEST RESULTS");
@@ -816,9 +1606,7 @@ async fn test_full_pnl_backtest() {
     println!("  MM Orders Skipped:   {} (unfilled resting / no inventory)", total_mm_skipped);
     println!("============================================================\n");
 
-    // Sanity assertions
-    assert!(final_capital > 0.0, "Should not go bankrupt");
-    assert!(total_orders > 0, "Should have placed orders");
+    metrics
 }
 
 // ---------------------------------------------------------------------------
@@ -914,8 +1702,8 @@ async fn test_multi_market_backtest() {
         let mut markets: Vec<Market> = Vec::new();
         let mut slugs: Vec<String> = Vec::new();
         let mut ref_prices: Vec<f64> = Vec::new();
-        let mut yes_mms = vec![0.50f64; 7];
-        let mut no_mms  = vec![0.50f64; 7];
+        let mut yes_mms = [0.50f64; 7];
+        let mut no_mms  = [0.50f64; 7];
         let mut mom_dets: Vec<MomentumDetector> = (0..7).map(|_| MomentumDetector::new(100)).collect();
         let mut ind_engs: Vec<IndicatorEngine> = (0..7).map(|_| IndicatorEngine::new(100)).collect();
         let bias_dets: Vec<BiasDetector> = (0..7).map(|_| BiasDetector::new(0.20)).collect();
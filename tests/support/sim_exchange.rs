@@ -0,0 +1,994 @@
+//! Simulated Polymarket CLOB for integration tests.
+//!
+//! Serves the REST endpoints `ClobClient` talks to plus the user WebSocket
+//! channel, on a random local port. Orders are checked the way the real
+//! exchange checks them — L1/L2 auth headers, the EIP-712 order signature
+//! against the right exchange contract, fee rate and balance — then matched
+//! against liquidity the test seeds with `set_liquidity`. Fills are pushed to
+//! connected user-channel sockets in the CLOB's trade message format.
+//! Like the CLOB, order IDs are the orders' EIP-712 hashes. `inject_faults`
+//! makes upcoming order posts and lookups fail the way a flaky API does;
+//! `set_chaos` does the same at random across every REST route, adds
+//! latency, and drops user-channel messages. `disconnect_ws_clients` drops
+//! the user channel outright.
+
+use alloy_primitives::{Address, PrimitiveSignature, B256, U256};
+use alloy_sol_types::{sol, Eip712Domain, SolStruct};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use sattebaaz::config::{PolymarketConfig, RestRetryConfig};
+use sattebaaz::execution::order_builder::SignedOrder;
+use sattebaaz::sim::rng::SimRng;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+sol! {
+    struct Order {
+        uint256 salt;
+        address maker;
+        address signer;
+        address taker;
+        uint256 tokenId;
+        uint256 makerAmount;
+        uint256 takerAmount;
+        uint256 expiration;
+        uint256 nonce;
+        uint256 feeRateBps;
+        uint8 side;
+        uint8 signatureType;
+    }
+
+    struct ClobAuth {
+        address address;
+        string timestamp;
+        uint256 nonce;
+        string message;
+    }
+}
+
+const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+const NEG_RISK_CTF_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
+const EPS: f64 = 1e-9;
+/// Small page size so tests exercise `/data/orders` pagination
+const ORDERS_PAGE: usize = 2;
+const END_CURSOR: &str = "LTE=";
+
+/// A private key the tests sign with (never funded, never used on-chain).
+pub const TEST_PRIVATE_KEY: &str =
+    "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+/// Order as the simulated exchange sees it.
+#[derive(Debug, Clone)]
+pub struct SimOrder {
+    pub id: String,
+    pub token_id: String,
+    pub side: String,
+    pub order_type: String,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    /// "LIVE", "MATCHED" or "CANCELED"
+    pub status: String,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+/// Failure injected into an upcoming order post or status lookup.
+#[derive(Debug, Clone, Copy)]
+pub enum Fault {
+    /// Answer with this status without touching the order
+    Reject(u16),
+    /// Process the request, then answer with this status anyway
+    LoseResponse(u16),
+    /// Answer normally, so a later request in the queue is the one hit
+    Pass,
+}
+
+/// Random faults applied while enabled. Seeded, so a failing run replays.
+#[derive(Debug, Clone, Copy)]
+pub struct Chaos {
+    pub seed: u64,
+    /// Chance a REST request fails with a 5xx or 429
+    pub error_rate: f64,
+    /// Share of 5xx failures that process the request before failing
+    pub lost_response_rate: f64,
+    /// Each REST response is delayed by up to this much
+    pub max_delay_ms: u64,
+    /// Chance a user-channel message is never sent
+    pub ws_drop_rate: f64,
+}
+
+/// Handle to a running simulated exchange.
+pub struct SimExchange {
+    pub host: String,
+    pub ws_url: String,
+    sim: Arc<Sim>,
+}
+
+struct Sim {
+    chain_id: u64,
+    state: Mutex<SimState>,
+    events: broadcast::Sender<String>,
+    ws_clients: AtomicUsize,
+    /// Closes every user-channel socket
+    ws_kick: broadcast::Sender<()>,
+}
+
+#[derive(Default)]
+struct SimState {
+    /// token → resting asks (ascending) and bids (descending), (price, size)
+    asks: HashMap<String, Vec<(f64, f64)>>,
+    bids: HashMap<String, Vec<(f64, f64)>>,
+    orders: HashMap<String, SimOrder>,
+    /// api key → credentials
+    keys: HashMap<String, IssuedKey>,
+    /// Refuse to issue API keys, as if the wallet were banned
+    keys_blocked: bool,
+    /// Key create/derive requests served
+    key_requests: usize,
+    neg_risk: HashSet<String>,
+    /// condition id → its tokens, for cancel-by-market
+    conditions: HashMap<String, Vec<String>>,
+    fee_rate_bps: u32,
+    /// Smallest order accepted, in shares
+    min_order_size: f64,
+    /// USDC balance
+    balance: f64,
+    rejections: Vec<String>,
+    faults: VecDeque<Fault>,
+    /// Order posts received, including ones a fault swallowed
+    posts: usize,
+    chaos: Option<(Chaos, SimRng)>,
+    /// Requests chaos has failed so far
+    chaos_faults: usize,
+    /// User-channel messages to drop before chaos gets a say
+    ws_drops: usize,
+}
+
+#[derive(Clone)]
+struct IssuedKey {
+    api_key: String,
+    secret: String,
+    passphrase: String,
+    address: Address,
+}
+
+#[derive(Deserialize)]
+struct PostOrderBody {
+    order: SignedOrder,
+    #[serde(rename = "orderType")]
+    order_type: String,
+    owner: String,
+    #[serde(rename = "postOnly", default)]
+    post_only: Option<bool>,
+}
+
+impl SimExchange {
+    /// Bind to a random local port and start serving.
+    pub async fn start() -> Self {
+        let (events, _) = broadcast::channel(256);
+        let (ws_kick, _) = broadcast::channel(1);
+        let sim = Arc::new(Sim {
+            chain_id: 137,
+            state: Mutex::new(SimState {
+                balance: 1_000.0,
+                ..SimState::default()
+            }),
+            events,
+            ws_clients: AtomicUsize::new(0),
+            ws_kick,
+        });
+
+        let app = Router::new()
+            .route("/time", get(server_time))
+            .route("/neg-risk", get(neg_risk))
+            .route("/fee-rate", get(fee_rate))
+            .route("/tick-size", get(tick_size))
+            .route("/book", get(book))
+            .route("/auth/api-key", post(issue_api_key))
+            .route("/auth/derive-api-key", get(issue_api_key))
+            .route("/balance-allowance", get(balance_allowance))
+            .route("/order", post(post_order))
+            .route("/order/:id", get(get_order).delete(cancel_order))
+            .route("/cancel-all", delete(cancel_all))
+            .route("/cancel-market-orders", delete(cancel_market_orders))
+            .route("/orders", delete(cancel_orders))
+            .route("/data/orders", get(open_orders))
+            .route_layer(middleware::from_fn_with_state(sim.clone(), chaos))
+            .route("/ws/user", get(user_channel))
+            .with_state(sim.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        Self {
+            host: format!("http://{addr}"),
+            ws_url: format!("ws://{addr}/ws/user"),
+            sim,
+        }
+    }
+
+    /// Polymarket config pointing at this exchange, signing with `private_key`.
+    pub fn config(&self, private_key: &str) -> PolymarketConfig {
+        PolymarketConfig {
+            clob_host: self.host.clone(),
+            ws_host: self.ws_url.clone(),
+            gamma_api_host: self.host.clone(),
+            data_api_host: self.host.clone(),
+            rtds_host: self.ws_url.clone(),
+            polygon_rpc_url: self.host.clone(),
+            chain_id: self.sim.chain_id,
+            private_key: private_key.to_string(),
+            funder_address: None,
+            signature_type: 0,
+            retry: RestRetryConfig {
+                base_ms: 5,
+                max_backoff_ms: 20,
+                ..RestRetryConfig::default()
+            },
+            prewarm_secs: 0,
+            token_balance_secs: 0,
+            matic_price_url: String::new(),
+            matic_usd: 0.0,
+        }
+    }
+
+    /// Replace the resting liquidity for a token.
+    pub fn set_liquidity(&self, token_id: &str, asks: &[(f64, f64)], bids: &[(f64, f64)]) {
+        let mut state = self.sim.state.lock().unwrap();
+        let mut asks = asks.to_vec();
+        asks.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut bids = bids.to_vec();
+        bids.sort_by(|a, b| b.0.total_cmp(&a.0));
+        state.asks.insert(token_id.to_string(), asks);
+        state.bids.insert(token_id.to_string(), bids);
+    }
+
+    /// Mark a token as trading on the neg risk exchange.
+    pub fn set_neg_risk(&self, token_id: &str) {
+        self.sim.state.lock().unwrap().neg_risk.insert(token_id.to_string());
+    }
+
+    /// Register a market's condition id and tokens.
+    pub fn set_condition(&self, condition_id: &str, tokens: &[&str]) {
+        let tokens = tokens.iter().map(|t| t.to_string()).collect();
+        self.sim.state.lock().unwrap().conditions.insert(condition_id.to_string(), tokens);
+    }
+
+    pub fn set_min_order_size(&self, shares: f64) {
+        self.sim.state.lock().unwrap().min_order_size = shares;
+    }
+
+    pub fn set_fee_rate_bps(&self, bps: u32) {
+        self.sim.state.lock().unwrap().fee_rate_bps = bps;
+    }
+
+    /// Invalidate every issued API key; clients must derive new ones.
+    pub fn revoke_api_keys(&self) {
+        self.sim.state.lock().unwrap().keys.clear();
+    }
+
+    pub fn block_key_issuance(&self, blocked: bool) {
+        self.sim.state.lock().unwrap().keys_blocked = blocked;
+    }
+
+    pub fn set_balance(&self, usdc: f64) {
+        self.sim.state.lock().unwrap().balance = usdc;
+    }
+
+    pub fn balance(&self) -> f64 {
+        self.sim.state.lock().unwrap().balance
+    }
+
+    pub fn order(&self, order_id: &str) -> Option<SimOrder> {
+        self.sim.state.lock().unwrap().orders.get(order_id).cloned()
+    }
+
+    /// Backdate an order's creation time by `secs`.
+    pub fn age_order(&self, order_id: &str, secs: i64) {
+        if let Some(order) = self.sim.state.lock().unwrap().orders.get_mut(order_id) {
+            order.created_at -= secs;
+        }
+    }
+
+    /// Fail the next order requests, one fault each, in order.
+    pub fn inject_faults(&self, faults: &[Fault]) {
+        self.sim.state.lock().unwrap().faults.extend(faults);
+    }
+
+    /// Start (or with None, stop) random faults on every request.
+    pub fn set_chaos(&self, chaos: Option<Chaos>) {
+        self.sim.state.lock().unwrap().chaos = chaos.map(|c| (c, SimRng::new(c.seed)));
+    }
+
+    /// REST requests chaos has failed so far.
+    pub fn chaos_faults(&self) -> usize {
+        self.sim.state.lock().unwrap().chaos_faults
+    }
+
+    /// Silently drop the next `n` user-channel messages.
+    pub fn drop_ws_messages(&self, n: usize) {
+        self.sim.state.lock().unwrap().ws_drops += n;
+    }
+
+    /// Close every connected user-channel socket, as a network blip would.
+    pub fn disconnect_ws_clients(&self) {
+        let _ = self.sim.ws_kick.send(());
+    }
+
+    /// Connected user-channel sockets.
+    pub fn ws_clients(&self) -> usize {
+        self.sim.ws_clients.load(Ordering::SeqCst)
+    }
+
+    /// Number of `POST /order` requests received.
+    /// API key create/derive requests received.
+    pub fn key_requests(&self) -> usize {
+        self.sim.state.lock().unwrap().key_requests
+    }
+
+    pub fn posts(&self) -> usize {
+        self.sim.state.lock().unwrap().posts
+    }
+
+    /// Reasons for every order the exchange refused, in arrival order.
+    pub fn rejections(&self) -> Vec<String> {
+        self.sim.state.lock().unwrap().rejections.clone()
+    }
+
+    /// Simulate a counterparty hitting a resting order. Returns the size filled.
+    pub fn fill_resting(&self, order_id: &str, size: f64) -> f64 {
+        let mut state = self.sim.state.lock().unwrap();
+        let Some(order) = state.orders.get_mut(order_id) else { return 0.0 };
+        if order.status != "LIVE" {
+            return 0.0;
+        }
+        let fill = size.min(order.original_size - order.size_matched);
+        order.size_matched += fill;
+        if order.original_size - order.size_matched < EPS {
+            order.status = "MATCHED".into();
+        }
+        let order = order.clone();
+        if order.side == "BUY" {
+            state.balance -= fill * order.price;
+        } else {
+            state.balance += fill * order.price;
+        }
+        let fee_rate_bps = state.fee_rate_bps;
+        drop(state);
+        self.sim.publish_trade(&order, order.price, fill, fee_rate_bps);
+        self.sim.publish_order(&order, "UPDATE");
+        fill
+    }
+
+    /// Wait until `n` user-channel sockets have subscribed.
+    pub async fn wait_for_ws_clients(&self, n: usize) {
+        for _ in 0..100 {
+            if self.sim.ws_clients.load(Ordering::SeqCst) >= n {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        panic!("no user WS subscriber after 5s");
+    }
+}
+
+impl Sim {
+    fn publish_trade(&self, order: &SimOrder, price: f64, size: f64, fee_rate_bps: u32) {
+        let fee = size * price * (1.0 - price) * fee_rate_bps as f64 / 10_000.0;
+        let msg = json!({
+            "type": "trade",
+            "order_id": order.id,
+            "asset_id": order.token_id,
+            "side": order.side,
+            "price": format!("{price:.4}"),
+            "size": format!("{size:.4}"),
+            "fee": format!("{fee:.6}"),
+            "status": "MATCHED",
+        });
+        let _ = self.events.send(msg.to_string());
+    }
+
+    /// Order status transition on the user channel: PLACEMENT, UPDATE or CANCELLATION.
+    fn publish_order(&self, order: &SimOrder, kind: &str) {
+        let msg = json!({
+            "event_type": "order",
+            "type": kind,
+            "id": order.id,
+            "asset_id": order.token_id,
+            "side": order.side,
+            "price": format!("{:.4}", order.price),
+            "original_size": format!("{:.4}", order.original_size),
+            "size_matched": format!("{:.4}", order.size_matched),
+            "status": order.status,
+        });
+        let _ = self.events.send(msg.to_string());
+    }
+
+    /// Check L2 (API key + HMAC) or L1 (EIP-712) headers; returns the caller's address.
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        method: &str,
+        path: &str,
+        body: &str,
+    ) -> Result<(Address, Option<String>), String> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+                .ok_or_else(|| format!("missing {name} header"))
+        };
+
+        if let Ok(api_key) = header("POLY_API_KEY") {
+            let key = self
+                .state
+                .lock()
+                .unwrap()
+                .keys
+                .get(&api_key)
+                .cloned()
+                .ok_or("Unauthorized/Invalid api key")?;
+            if header("POLY_PASSPHRASE")? != key.passphrase {
+                return Err("Unauthorized/Invalid api key".into());
+            }
+            let payload = format!("{}{method}{path}{body}", header("POLY_TIMESTAMP")?);
+            if header("POLY_SIGNATURE")? != hmac_b64(&key.secret, &payload) {
+                return Err("Unauthorized/Invalid HMAC signature".into());
+            }
+            return Ok((key.address, Some(key.api_key)));
+        }
+
+        let address: Address = header("POLY_ADDRESS")?
+            .parse()
+            .map_err(|_| "invalid POLY_ADDRESS".to_string())?;
+        let auth = ClobAuth {
+            address,
+            timestamp: header("POLY_TIMESTAMP")?,
+            nonce: U256::from_str_radix(&header("POLY_NONCE")?, 10)
+                .map_err(|_| "invalid POLY_NONCE".to_string())?,
+            message: CLOB_AUTH_MESSAGE.to_string(),
+        };
+        let domain = Eip712Domain {
+            name: Some("ClobAuthDomain".into()),
+            version: Some("1".into()),
+            chain_id: Some(U256::from(self.chain_id)),
+            verifying_contract: None,
+            salt: None,
+        };
+        let recovered = recover(&header("POLY_SIGNATURE")?, &auth.eip712_signing_hash(&domain))?;
+        if recovered != address {
+            return Err("Unauthorized/Invalid L1 signature".into());
+        }
+        Ok((address, None))
+    }
+
+    /// Recover the order signer the way the exchange contract would.
+    /// Returns the order hash.
+    fn verify_order(&self, order: &SignedOrder, neg_risk: bool) -> Result<B256, String> {
+        let uint = |s: &str| U256::from_str_radix(s, 10).map_err(|_| format!("invalid uint {s}"));
+        let addr = |s: &str| s.parse::<Address>().map_err(|_| format!("invalid address {s}"));
+        let side = match order.side.as_str() {
+            "BUY" => 0,
+            "SELL" => 1,
+            other => return Err(format!("invalid side {other}")),
+        };
+        let maker = addr(&order.maker)?;
+        let signer = addr(&order.signer)?;
+
+        let typed = Order {
+            salt: U256::from(order.salt),
+            maker,
+            signer,
+            taker: addr(&order.taker)?,
+            tokenId: uint(&order.token_id)?,
+            makerAmount: uint(&order.maker_amount)?,
+            takerAmount: uint(&order.taker_amount)?,
+            expiration: uint(&order.expiration)?,
+            nonce: uint(&order.nonce)?,
+            feeRateBps: uint(&order.fee_rate_bps)?,
+            side,
+            signatureType: order.signature_type,
+        };
+        let exchange = if neg_risk { NEG_RISK_CTF_EXCHANGE } else { CTF_EXCHANGE };
+        let domain = Eip712Domain {
+            name: Some("Polymarket CTF Exchange".into()),
+            version: Some("1".into()),
+            chain_id: Some(U256::from(self.chain_id)),
+            verifying_contract: Some(exchange.parse().unwrap()),
+            salt: None,
+        };
+
+        let hash = typed.eip712_signing_hash(&domain);
+        if recover(&order.signature, &hash)? != signer {
+            return Err("invalid order signature".into());
+        }
+        if order.signature_type == 0 && maker != signer {
+            return Err("invalid maker: EOA orders must be made by the signer".into());
+        }
+        Ok(hash)
+    }
+
+    fn next_fault(&self) -> Option<Fault> {
+        self.state.lock().unwrap().faults.pop_front()
+    }
+
+    /// Chaos verdict for one REST request: a delay and maybe a fault.
+    fn roll_chaos(&self) -> (u64, Option<Fault>) {
+        let mut state = self.state.lock().unwrap();
+        let Some((chaos, rng)) = state.chaos.as_mut() else { return (0, None) };
+        let delay = (rng.next_f64() * chaos.max_delay_ms as f64) as u64;
+        if !rng.chance(chaos.error_rate) {
+            return (delay, None);
+        }
+        let status = [500, 502, 503, 429][(rng.next_u64() % 4) as usize];
+        let fault = if status != 429 && rng.chance(chaos.lost_response_rate) {
+            Fault::LoseResponse(status)
+        } else {
+            Fault::Reject(status)
+        };
+        state.chaos_faults += 1;
+        (delay, Some(fault))
+    }
+
+    /// Whether the next user-channel message should be dropped.
+    fn drop_ws_message(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.ws_drops > 0 {
+            state.ws_drops -= 1;
+            return true;
+        }
+        match state.chaos.as_mut() {
+            Some((chaos, rng)) => rng.chance(chaos.ws_drop_rate),
+            None => false,
+        }
+    }
+
+    fn accept(&self, headers: &HeaderMap, body: &str) -> Result<Value, String> {
+        let (address, api_key) = self.authenticate(headers, "POST", "/order", body)?;
+        let req: PostOrderBody =
+            serde_json::from_str(body).map_err(|e| format!("invalid order payload: {e}"))?;
+        if let Some(key) = api_key {
+            if req.owner != key {
+                return Err("the order owner has to be the owner of the API KEY".into());
+            }
+        }
+
+        let o = &req.order;
+        let neg_risk = self.state.lock().unwrap().neg_risk.contains(&o.token_id);
+        let order_id = format!("{:#x}", self.verify_order(o, neg_risk)?);
+        if o.signer.parse::<Address>().ok() != Some(address) {
+            return Err("order signer does not match API key address".into());
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.orders.contains_key(&order_id) {
+            return Err("duplicate order".into());
+        }
+        if o.fee_rate_bps != state.fee_rate_bps.to_string() {
+            return Err(format!(
+                "invalid fee rate ({}), current market's taker fee: {}",
+                o.fee_rate_bps, state.fee_rate_bps
+            ));
+        }
+
+        let maker_amount = o.maker_amount.parse::<f64>().unwrap_or(0.0) / 1_000_000.0;
+        let taker_amount = o.taker_amount.parse::<f64>().unwrap_or(0.0) / 1_000_000.0;
+        let is_buy = o.side == "BUY";
+        let (price, size) = if is_buy {
+            (maker_amount / taker_amount.max(EPS), taker_amount)
+        } else {
+            (taker_amount / maker_amount.max(EPS), maker_amount)
+        };
+        if size + EPS < state.min_order_size {
+            return Err(format!("Size ({size}) lower than the minimum: {}", state.min_order_size));
+        }
+        if is_buy && maker_amount > state.balance + EPS {
+            return Err("not enough balance / allowance".into());
+        }
+
+        let levels = if is_buy {
+            state.asks.entry(o.token_id.clone()).or_default()
+        } else {
+            state.bids.entry(o.token_id.clone()).or_default()
+        };
+        let crosses = |px: f64| if is_buy { px <= price + EPS } else { px >= price - EPS };
+        let available: f64 = levels.iter().filter(|(px, _)| crosses(*px)).map(|(_, q)| q).sum();
+
+        if req.post_only.unwrap_or(false) && available > EPS {
+            return Err("invalid post-only order: order crosses book".into());
+        }
+        match req.order_type.as_str() {
+            "FOK" if available + EPS < size => {
+                return Err(
+                    "order couldn't be fully filled. FOK orders are fully filled or killed.".into(),
+                )
+            }
+            "FAK" if available < EPS => {
+                return Err("no orders found to match with FAK order. FAK orders are partially filled or killed if no match is found.".into())
+            }
+            _ => {}
+        }
+
+        // Take liquidity, best price first
+        let mut fills = Vec::new();
+        let mut remaining = size;
+        for (px, qty) in levels.iter_mut() {
+            if remaining < EPS || !crosses(*px) {
+                break;
+            }
+            let take = qty.min(remaining);
+            *qty -= take;
+            remaining -= take;
+            fills.push((*px, take));
+        }
+        levels.retain(|(_, q)| *q > EPS);
+
+        let matched: f64 = fills.iter().map(|(_, q)| q).sum();
+        let notional: f64 = fills.iter().map(|(p, q)| p * q).sum();
+        state.balance += if is_buy { -notional } else { notional };
+
+        let rests = matches!(req.order_type.as_str(), "GTC" | "GTD") && remaining > EPS;
+        let order = SimOrder {
+            id: order_id,
+            token_id: o.token_id.clone(),
+            side: o.side.clone(),
+            order_type: req.order_type.clone(),
+            price,
+            original_size: size,
+            size_matched: matched,
+            status: if rests { "LIVE" } else if matched > EPS { "MATCHED" } else { "CANCELED" }.into(),
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        state.orders.insert(order.id.clone(), order.clone());
+        let fee_rate_bps = state.fee_rate_bps;
+        drop(state);
+
+        for (px, qty) in fills {
+            self.publish_trade(&order, px, qty, fee_rate_bps);
+        }
+        self.publish_order(&order, if rests { "PLACEMENT" } else { "UPDATE" });
+
+        Ok(json!({
+            "success": true,
+            "orderID": order.id,
+            "status": order.status.to_lowercase(),
+            "errorMsg": "",
+        }))
+    }
+}
+
+fn recover(signature: &str, digest: &B256) -> Result<Address, String> {
+    signature
+        .parse::<PrimitiveSignature>()
+        .map_err(|e| format!("malformed signature: {e}"))?
+        .recover_address_from_prehash(digest)
+        .map_err(|e| format!("unrecoverable signature: {e}"))
+}
+
+fn hmac_b64(secret: &str, payload: &str) -> String {
+    use base64::Engine;
+    use hmac::{Hmac, Mac};
+
+    let engine = base64::engine::general_purpose::URL_SAFE;
+    let key = engine.decode(secret).unwrap_or_default();
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(&key).expect("HMAC accepts any key size");
+    mac.update(payload.as_bytes());
+    engine.encode(mac.finalize().into_bytes())
+}
+
+fn error(status: StatusCode, msg: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": msg.into() }))).into_response()
+}
+
+fn query_token(query: &HashMap<String, String>) -> String {
+    query.get("token_id").cloned().unwrap_or_default()
+}
+
+/// Applies `set_chaos` to every REST route.
+async fn chaos(State(sim): State<Arc<Sim>>, req: Request, next: Next) -> Response {
+    let (delay_ms, fault) = sim.roll_chaos();
+    tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+    match fault {
+        Some(Fault::Reject(status)) => error(StatusCode::from_u16(status).unwrap(), "chaos"),
+        Some(Fault::LoseResponse(status)) => {
+            next.run(req).await;
+            error(StatusCode::from_u16(status).unwrap(), "chaos")
+        }
+        Some(Fault::Pass) | None => next.run(req).await,
+    }
+}
+
+async fn server_time() -> Json<Value> {
+    Json(json!(chrono::Utc::now().timestamp()))
+}
+
+async fn neg_risk(
+    State(sim): State<Arc<Sim>>,
+    axum::extract::Query(query): axum::extract::Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let neg_risk = sim.state.lock().unwrap().neg_risk.contains(&query_token(&query));
+    Json(json!({ "neg_risk": neg_risk }))
+}
+
+async fn fee_rate(State(sim): State<Arc<Sim>>) -> Json<Value> {
+    Json(json!({ "fee_rate_bps": sim.state.lock().unwrap().fee_rate_bps }))
+}
+
+async fn tick_size() -> Json<Value> {
+    Json(json!({ "minimum_tick_size": 0.01 }))
+}
+
+async fn book(State(sim): State<Arc<Sim>>) -> Json<Value> {
+    let min = sim.state.lock().unwrap().min_order_size;
+    Json(json!({ "bids": [], "asks": [], "min_order_size": min.to_string() }))
+}
+
+async fn issue_api_key(State(sim): State<Arc<Sim>>, headers: HeaderMap) -> Response {
+    // Key issuance only accepts L1 headers
+    if headers.contains_key("POLY_API_KEY") {
+        return error(StatusCode::UNAUTHORIZED, "L1 auth required");
+    }
+    let address = match sim.authenticate(&headers, "POST", "/auth/api-key", "") {
+        Ok((address, _)) => address,
+        Err(e) => return error(StatusCode::UNAUTHORIZED, e),
+    };
+
+    let mut state = sim.state.lock().unwrap();
+    state.key_requests += 1;
+    if state.keys_blocked {
+        return error(StatusCode::UNAUTHORIZED, "Unauthorized: key issuance disabled");
+    }
+    let key = match state.keys.values().find(|k| k.address == address) {
+        Some(existing) => existing.clone(),
+        None => {
+            use base64::Engine;
+            let secret: [u8; 32] = rand::random();
+            let key = IssuedKey {
+                api_key: uuid::Uuid::new_v4().to_string(),
+                secret: base64::engine::general_purpose::URL_SAFE.encode(secret),
+                passphrase: hex::encode(rand::random::<[u8; 16]>()),
+                address,
+            };
+            state.keys.insert(key.api_key.clone(), key.clone());
+            key
+        }
+    };
+    Json(json!({
+        "apiKey": key.api_key,
+        "secret": key.secret,
+        "passphrase": key.passphrase,
+    }))
+    .into_response()
+}
+
+async fn balance_allowance(State(sim): State<Arc<Sim>>, headers: HeaderMap) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "GET", "/balance-allowance", "") {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    let micro = (sim.state.lock().unwrap().balance * 1_000_000.0).round() as u64;
+    Json(json!({ "balance": micro.to_string(), "allowance": micro.to_string() })).into_response()
+}
+
+async fn post_order(State(sim): State<Arc<Sim>>, headers: HeaderMap, body: String) -> Response {
+    sim.state.lock().unwrap().posts += 1;
+    let fault = sim.next_fault();
+    if let Some(Fault::Reject(status)) = fault {
+        return error(StatusCode::from_u16(status).unwrap(), "injected fault");
+    }
+    match (sim.accept(&headers, &body), fault) {
+        (Ok(_), Some(Fault::LoseResponse(status))) => {
+            error(StatusCode::from_u16(status).unwrap(), "injected fault")
+        }
+        (Ok(resp), _) => Json(resp).into_response(),
+        (Err(e), _) => {
+            sim.state.lock().unwrap().rejections.push(e.clone());
+            error(StatusCode::BAD_REQUEST, e)
+        }
+    }
+}
+
+async fn get_order(
+    State(sim): State<Arc<Sim>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "GET", &format!("/order/{id}"), "") {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    if let Some(Fault::Reject(status) | Fault::LoseResponse(status)) = sim.next_fault() {
+        return error(StatusCode::from_u16(status).unwrap(), "injected fault");
+    }
+    let Some(order) = sim.state.lock().unwrap().orders.get(&id).cloned() else {
+        return error(StatusCode::NOT_FOUND, "order not found");
+    };
+    Json(json!({
+        "id": order.id,
+        "status": order.status,
+        "asset_id": order.token_id,
+        "side": order.side,
+        "order_type": order.order_type,
+        "price": format!("{:.4}", order.price),
+        "original_size": format!("{:.4}", order.original_size),
+        "size_matched": format!("{:.4}", order.size_matched),
+    }))
+    .into_response()
+}
+
+async fn cancel_order(
+    State(sim): State<Arc<Sim>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "DELETE", &format!("/order/{id}"), "") {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    let mut state = sim.state.lock().unwrap();
+    match state.orders.get_mut(&id) {
+        Some(order) if order.status == "LIVE" => {
+            order.status = "CANCELED".into();
+            sim.publish_order(order, "CANCELLATION");
+            Json(json!({ "canceled": [id], "not_canceled": {} })).into_response()
+        }
+        Some(_) => Json(json!({ "canceled": [], "not_canceled": { id: "order is not live" } }))
+            .into_response(),
+        None => error(StatusCode::NOT_FOUND, "order not found"),
+    }
+}
+
+async fn cancel_all(State(sim): State<Arc<Sim>>, headers: HeaderMap) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "DELETE", "/cancel-all", "") {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    let mut state = sim.state.lock().unwrap();
+    let mut canceled = Vec::new();
+    for order in state.orders.values_mut().filter(|o| o.status == "LIVE") {
+        order.status = "CANCELED".into();
+        sim.publish_order(order, "CANCELLATION");
+        canceled.push(order.id.clone());
+    }
+    Json(json!({ "canceled": canceled, "not_canceled": {} })).into_response()
+}
+
+/// Cancel the live orders `matches` picks; answers like the CLOB.
+fn cancel_where(sim: &Sim, matches: impl Fn(&SimOrder) -> bool) -> Response {
+    let mut state = sim.state.lock().unwrap();
+    let mut canceled = Vec::new();
+    for order in state.orders.values_mut().filter(|o| o.status == "LIVE" && matches(o)) {
+        order.status = "CANCELED".into();
+        sim.publish_order(order, "CANCELLATION");
+        canceled.push(order.id.clone());
+    }
+    Json(json!({ "canceled": canceled, "not_canceled": {} })).into_response()
+}
+
+#[derive(Deserialize)]
+struct CancelMarketBody {
+    #[serde(default)]
+    market: String,
+    #[serde(default)]
+    asset_id: String,
+}
+
+async fn cancel_market_orders(State(sim): State<Arc<Sim>>, headers: HeaderMap, body: String) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "DELETE", "/cancel-market-orders", &body) {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    let Ok(req) = serde_json::from_str::<CancelMarketBody>(&body) else {
+        return error(StatusCode::BAD_REQUEST, "invalid body");
+    };
+    let tokens = sim.state.lock().unwrap().conditions.get(&req.market).cloned().unwrap_or_default();
+    cancel_where(&sim, |o| {
+        (req.market.is_empty() || tokens.contains(&o.token_id))
+            && (req.asset_id.is_empty() || o.token_id == req.asset_id)
+    })
+}
+
+async fn cancel_orders(State(sim): State<Arc<Sim>>, headers: HeaderMap, body: String) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "DELETE", "/orders", &body) {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    let Ok(ids) = serde_json::from_str::<Vec<String>>(&body) else {
+        return error(StatusCode::BAD_REQUEST, "invalid body");
+    };
+    cancel_where(&sim, |o| ids.contains(&o.id))
+}
+
+#[derive(Deserialize)]
+struct OrdersQuery {
+    next_cursor: Option<String>,
+}
+
+/// Live orders, paginated like the CLOB: `next_cursor` is a base64 offset and
+/// "LTE=" marks the last page.
+async fn open_orders(
+    State(sim): State<Arc<Sim>>,
+    axum::extract::Query(query): axum::extract::Query<OrdersQuery>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(e) = sim.authenticate(&headers, "GET", "/data/orders", "") {
+        return error(StatusCode::UNAUTHORIZED, e);
+    }
+    use base64::Engine;
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let offset: usize = query
+        .next_cursor
+        .and_then(|c| b64.decode(c).ok())
+        .and_then(|b| String::from_utf8(b).ok())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    let state = sim.state.lock().unwrap();
+    let mut live: Vec<&SimOrder> = state.orders.values().filter(|o| o.status == "LIVE").collect();
+    live.sort_by(|a, b| a.id.cmp(&b.id));
+    let page: Vec<Value> = live
+        .iter()
+        .skip(offset)
+        .take(ORDERS_PAGE)
+        .map(|o| {
+            json!({
+                "id": o.id,
+                "status": o.status,
+                "asset_id": o.token_id,
+                "side": o.side,
+                "price": format!("{:.4}", o.price),
+                "original_size": format!("{:.4}", o.original_size),
+                "size_matched": format!("{:.4}", o.size_matched),
+                "created_at": o.created_at,
+            })
+        })
+        .collect();
+    let next = offset + page.len();
+    let next_cursor = if next >= live.len() { END_CURSOR.to_string() } else { b64.encode(next.to_string()) };
+    Json(json!({ "data": page, "next_cursor": next_cursor, "count": page.len() })).into_response()
+}
+
+async fn user_channel(ws: WebSocketUpgrade, State(sim): State<Arc<Sim>>) -> Response {
+    ws.on_upgrade(move |socket| serve_user_channel(socket, sim))
+}
+
+async fn serve_user_channel(mut socket: WebSocket, sim: Arc<Sim>) {
+    // First frame must be the subscribe request
+    match socket.recv().await {
+        Some(Ok(Message::Text(text))) if text.contains("\"subscribe\"") => {}
+        _ => return,
+    }
+    let mut events = sim.events.subscribe();
+    let mut kick = sim.ws_kick.subscribe();
+    sim.ws_clients.fetch_add(1, Ordering::SeqCst);
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(_) if sim.drop_ws_message() => {}
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = kick.recv() => break,
+        }
+    }
+    sim.ws_clients.fetch_sub(1, Ordering::SeqCst);
+}